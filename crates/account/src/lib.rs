@@ -7,7 +7,7 @@ mod storage_key;
 mod types;
 
 use borsh::{BorshDeserialize, BorshSerialize};
-pub use namada_core::types::account::AccountPublicKeysMap;
+pub use namada_core::types::account::{AccountPublicKeysMap, AccountWeights};
 use namada_core::types::address::Address;
 use namada_core::types::key::common;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,9 @@ pub struct Account {
     pub public_keys_map: AccountPublicKeysMap,
     /// The account signature threshold
     pub threshold: u8,
+    /// The weights of the public keys and the weight threshold, if the
+    /// account is weighted
+    pub weights: Option<AccountWeights>,
     /// The address corresponding to the account owner
     pub address: Address,
 }
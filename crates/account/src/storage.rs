@@ -1,8 +1,12 @@
 //! Cryptographic signature keys storage API
 
+use std::collections::BTreeMap;
+
 use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
-use namada_core::types::storage;
+use namada_core::types::storage::{self, Epoch};
+use namada_core::types::token::Amount;
 use namada_storage::{Result, StorageRead, StorageWrite};
 
 use super::*;
@@ -121,3 +125,413 @@ where
     }
     Ok(())
 }
+
+/// Get the weights of the public keys associated with an account and its
+/// weight threshold, if the account is weighted
+pub fn weights<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<AccountWeights>>
+where
+    S: StorageRead,
+{
+    let weight_threshold_key = weight_threshold_key(owner);
+    let threshold: Option<u16> = storage.read(&weight_threshold_key)?;
+    threshold
+        .map(|threshold| {
+            let weights = weights_handle(owner)
+                .iter(storage)?
+                .collect::<Result<BTreeMap<u8, u8>>>()?;
+            Ok(AccountWeights { weights, threshold })
+        })
+        .transpose()
+}
+
+/// Set the weights of the public keys associated with an account and its
+/// weight threshold. The default weights remove the weighting of the account,
+/// falling back to its signature threshold. Other weights must be valid for
+/// the current public keys of the account.
+pub fn set_weights<S>(
+    storage: &mut S,
+    owner: &Address,
+    weights: &AccountWeights,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if weights.is_removal() {
+        return clear_weights(storage, owner);
+    }
+    let num_public_keys = pks_handle(owner).len(storage)?;
+    weights
+        .validate(num_public_keys as usize)
+        .map_err(namada_storage::Error::new)?;
+    clear_weights(storage, owner)?;
+    for (index, weight) in &weights.weights {
+        weights_handle(owner).insert(storage, *index, *weight)?;
+    }
+    let weight_threshold_key = weight_threshold_key(owner);
+    storage.write(&weight_threshold_key, weights.threshold)
+}
+
+/// Clear the weights of the public keys associated with an account and its
+/// weight threshold
+pub fn clear_weights<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let indices = weights_handle(owner)
+        .iter(storage)?
+        .map(|entry| entry.map(|(index, _weight)| index))
+        .collect::<Result<Vec<u8>>>()?;
+    for index in indices {
+        weights_handle(owner).remove(storage, &index)?;
+    }
+    let weight_threshold_key = weight_threshold_key(owner);
+    storage.delete(&weight_threshold_key)
+}
+
+/// Get the spending policy of an account, if any
+pub fn spending_policy<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<SpendingPolicy>>
+where
+    S: StorageRead,
+{
+    storage.read(&spending_policy_key(owner))
+}
+
+/// Get the pending change of the spending policy of an account, if any
+pub fn pending_spending_policy<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<PendingSpendingPolicy>>
+where
+    S: StorageRead,
+{
+    storage.read(&pending_spending_policy_key(owner))
+}
+
+/// Set or remove the spending policy of an account. A change that loosens the
+/// current policy is only queued, and is applied by the same update once the
+/// delay of the current policy has passed. Any other change is applied
+/// immediately and discards a pending change.
+pub fn update_spending_policy<S>(
+    storage: &mut S,
+    owner: &Address,
+    policy: Option<SpendingPolicy>,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let key = spending_policy_key(owner);
+    let pending_key = pending_spending_policy_key(owner);
+    let current = spending_policy(storage, owner)?;
+    if let Some(current) = current {
+        if current.is_loosened_by(policy.as_ref()) {
+            let epoch = storage.get_block_epoch()?;
+            match pending_spending_policy(storage, owner)? {
+                Some(pending) if pending.policy == policy => {
+                    if pending.unlock_epoch > epoch {
+                        return Err(namada_storage::Error::new_const(
+                            "The spending policy change cannot be applied yet",
+                        ));
+                    }
+                }
+                _ => {
+                    let pending = PendingSpendingPolicy {
+                        policy,
+                        unlock_epoch: epoch + current.delay,
+                    };
+                    return storage.write(&pending_key, pending);
+                }
+            }
+        }
+    }
+    storage.delete(&pending_key)?;
+    match policy {
+        Some(policy) => storage.write(&key, policy),
+        None => storage.delete(&key),
+    }
+}
+
+/// Get the pending VP update of an account, if any
+pub fn pending_vp_update<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<PendingVpUpdate>>
+where
+    S: StorageRead,
+{
+    storage.read(&pending_vp_update_key(owner))
+}
+
+/// Check if the VP of an account can be replaced with the given code now.
+/// While the account has a spending policy, the replacement is only queued
+/// and can be applied by the same update once the delay of the policy has
+/// passed. Returns `true` when the VP can be replaced, discarding any pending
+/// update.
+pub fn queue_vp_update<S>(
+    storage: &mut S,
+    owner: &Address,
+    vp_code_hash: Hash,
+) -> Result<bool>
+where
+    S: StorageWrite + StorageRead,
+{
+    let pending_key = pending_vp_update_key(owner);
+    if let Some(policy) = spending_policy(storage, owner)? {
+        let epoch = storage.get_block_epoch()?;
+        match pending_vp_update(storage, owner)? {
+            Some(pending) if pending.vp_code_hash == vp_code_hash => {
+                if pending.unlock_epoch > epoch {
+                    return Err(namada_storage::Error::new_const(
+                        "The VP update cannot be applied yet",
+                    ));
+                }
+            }
+            _ => {
+                let pending = PendingVpUpdate {
+                    vp_code_hash,
+                    unlock_epoch: epoch + policy.delay,
+                };
+                storage.write(&pending_key, pending)?;
+                return Ok(false);
+            }
+        }
+    }
+    storage.delete(&pending_key)?;
+    Ok(true)
+}
+
+/// Get the amounts debited from an account per token, in the epoch in which
+/// each of them was last debited
+pub fn epoch_spending<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<BTreeMap<Address, EpochSpending>>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&epoch_spending_key(owner))?
+        .unwrap_or_default())
+}
+
+/// Record a debit of a token from an account, if its spending policy limits
+/// the debits of the token in an epoch. Returns an `Err` if the limit would be
+/// exceeded.
+pub fn record_spending<S>(
+    storage: &mut S,
+    owner: &Address,
+    token: &Address,
+    amount: Amount,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let limit = match spending_policy(storage, owner)?
+        .and_then(|policy| policy.epoch_limits.get(token).copied())
+    {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let epoch = storage.get_block_epoch()?;
+    let mut spending = epoch_spending(storage, owner)?;
+    let spent = spending
+        .get(token)
+        .filter(|spending| spending.epoch == epoch)
+        .map(|spending| spending.amount)
+        .unwrap_or_default();
+    let amount = spent
+        .checked_add(amount)
+        .filter(|amount| *amount <= limit)
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "The spending limit of the account would be exceeded",
+            )
+        })?;
+    spending.insert(token.clone(), EpochSpending { epoch, amount });
+    storage.write(&epoch_spending_key(owner), spending)
+}
+
+/// Get the queued withdrawals of an account by their ids
+pub fn queued_withdrawals<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<BTreeMap<u64, QueuedWithdrawal>>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&queued_withdrawals_key(owner))?
+        .unwrap_or_default())
+}
+
+/// Queue a withdrawal from an account with a spending policy. Returns the id
+/// of the queued withdrawal.
+pub fn queue_withdrawal<S>(
+    storage: &mut S,
+    owner: &Address,
+    token: &Address,
+    target: &Address,
+    amount: Amount,
+) -> Result<u64>
+where
+    S: StorageWrite + StorageRead,
+{
+    let policy = spending_policy(storage, owner)?.ok_or_else(|| {
+        namada_storage::Error::new_const("The account has no spending policy")
+    })?;
+    let unlock_epoch = storage.get_block_epoch()? + policy.delay;
+    let mut withdrawals = queued_withdrawals(storage, owner)?;
+    let id = withdrawals
+        .keys()
+        .next_back()
+        .map(|id| id + 1)
+        .unwrap_or_default();
+    withdrawals.insert(
+        id,
+        QueuedWithdrawal {
+            token: token.clone(),
+            target: target.clone(),
+            amount,
+            unlock_epoch,
+        },
+    );
+    storage.write(&queued_withdrawals_key(owner), withdrawals)?;
+    Ok(id)
+}
+
+/// Remove a queued withdrawal from an account. If `matured` is set, returns
+/// an `Err` if the withdrawal cannot be executed yet.
+pub fn remove_queued_withdrawal<S>(
+    storage: &mut S,
+    owner: &Address,
+    id: u64,
+    matured: bool,
+) -> Result<QueuedWithdrawal>
+where
+    S: StorageWrite + StorageRead,
+{
+    let mut withdrawals = queued_withdrawals(storage, owner)?;
+    let withdrawal = withdrawals.remove(&id).ok_or_else(|| {
+        namada_storage::Error::new_const("No queued withdrawal with this id")
+    })?;
+    if matured && withdrawal.unlock_epoch > storage.get_block_epoch()? {
+        return Err(namada_storage::Error::new_const(
+            "The queued withdrawal cannot be executed yet",
+        ));
+    }
+    let key = queued_withdrawals_key(owner);
+    if withdrawals.is_empty() {
+        storage.delete(&key)?;
+    } else {
+        storage.write(&key, withdrawals)?;
+    }
+    Ok(withdrawal)
+}
+
+/// Get the guardian set of an account, if any
+pub fn guardians<S>(storage: &S, owner: &Address) -> Result<Option<GuardianSet>>
+where
+    S: StorageRead,
+{
+    storage.read(&guardians_key(owner))
+}
+
+/// Set or remove the guardian set of an account
+pub fn update_guardians<S>(
+    storage: &mut S,
+    owner: &Address,
+    guardians: Option<GuardianSet>,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let key = guardians_key(owner);
+    match guardians {
+        Some(guardians) => storage.write(&key, guardians),
+        None => storage.delete(&key),
+    }
+}
+
+/// Get the pending recovery of an account, if any
+pub fn pending_recovery<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<PendingRecovery>>
+where
+    S: StorageRead,
+{
+    storage.read(&pending_recovery_key(owner))
+}
+
+/// Initiate a recovery of an account with a guardian set, replacing its keys
+/// once the recovery delay has passed. Returns the epoch from which the
+/// recovery can be finalized.
+pub fn initiate_recovery<S>(
+    storage: &mut S,
+    owner: &Address,
+    public_keys: Vec<common::PublicKey>,
+    threshold: u8,
+) -> Result<Epoch>
+where
+    S: StorageWrite + StorageRead,
+{
+    let guardians = guardians(storage, owner)?.ok_or_else(|| {
+        namada_storage::Error::new_const("The account has no guardians")
+    })?;
+    if pending_recovery(storage, owner)?.is_some() {
+        return Err(namada_storage::Error::new_const(
+            "A recovery of the account is already pending",
+        ));
+    }
+    let unlock_epoch = storage.get_block_epoch()? + guardians.delay;
+    let recovery = PendingRecovery {
+        public_keys,
+        threshold,
+        unlock_epoch,
+    };
+    storage.write(&pending_recovery_key(owner), recovery)?;
+    Ok(unlock_epoch)
+}
+
+/// Cancel the pending recovery of an account
+pub fn cancel_recovery<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if pending_recovery(storage, owner)?.is_none() {
+        return Err(namada_storage::Error::new_const(
+            "No recovery of the account is pending",
+        ));
+    }
+    storage.delete(&pending_recovery_key(owner))
+}
+
+/// Finalize the pending recovery of an account after its delay has passed,
+/// replacing the account's keys and threshold and removing its weights
+pub fn finalize_recovery<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let recovery = pending_recovery(storage, owner)?.ok_or_else(|| {
+        namada_storage::Error::new_const(
+            "No recovery of the account is pending",
+        )
+    })?;
+    if recovery.unlock_epoch > storage.get_block_epoch()? {
+        return Err(namada_storage::Error::new_const(
+            "The recovery of the account cannot be finalized yet",
+        ));
+    }
+    clear_public_keys(storage, owner)?;
+    for (index, public_key) in recovery.public_keys.iter().enumerate() {
+        set_public_key_at(storage, owner, public_key, index as u8)?;
+    }
+    storage.write(&threshold_key(owner), recovery.threshold)?;
+    clear_weights(storage, owner)?;
+    storage.delete(&pending_recovery_key(owner))
+}
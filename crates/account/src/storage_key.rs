@@ -10,7 +10,16 @@ use namada_storage::collections::{lazy_map, LazyCollection};
 struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
+    weights: &'static str,
+    weight_threshold: &'static str,
     protocol_public_keys: &'static str,
+    spending_policy: &'static str,
+    pending_spending_policy: &'static str,
+    pending_vp_update: &'static str,
+    epoch_spending: &'static str,
+    queued_withdrawals: &'static str,
+    guardians: &'static str,
+    pending_recovery: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -68,6 +77,63 @@ pub fn threshold_key(owner: &Address) -> storage::Key {
     }
 }
 
+/// Obtain a storage key prefix for the weights of user's public keys.
+pub fn weights_key_prefix(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.weights.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the weights of user's public keys, by their index
+pub fn weights_handle(owner: &Address) -> LazyMap<u8, u8> {
+    LazyMap::open(weights_key_prefix(owner))
+}
+
+/// Check if the given storage key is a public key weight. If it is, returns
+/// the owner.
+pub fn is_weights_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::StringSeg(index),
+        ] if prefix.as_str() == Keys::VALUES.weights
+            && data.as_str() == lazy_map::DATA_SUBKEY
+            && index.parse::<u8>().is_ok() =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Check if the given storage key is a weight threshold key. If it is,
+/// returns the owner.
+pub fn is_weight_threshold_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix)]
+            if prefix.as_str() == Keys::VALUES.weight_threshold =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for a user weight threshold
+pub fn weight_threshold_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.weight_threshold.to_string()),
+        ],
+    }
+}
+
 /// Obtain a storage key for user's protocol public key.
 pub fn protocol_pk_key(owner: &Address) -> storage::Key {
     storage::Key {
@@ -89,3 +155,167 @@ pub fn is_protocol_pk_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Obtain the storage key for the spending policy of an account
+pub fn spending_policy_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.spending_policy.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a spending policy key. If it is, returns
+/// the owner.
+pub fn is_spending_policy_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.spending_policy =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the pending spending policy change of an account
+pub fn pending_spending_policy_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(
+                Keys::VALUES.pending_spending_policy.to_string(),
+            ),
+        ],
+    }
+}
+
+/// Check if the given storage key is a pending spending policy key. If it is,
+/// returns the owner.
+pub fn is_pending_spending_policy_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.pending_spending_policy =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the pending VP update of an account
+pub fn pending_vp_update_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.pending_vp_update.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a pending VP update key. If it is,
+/// returns the owner.
+pub fn is_pending_vp_update_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.pending_vp_update =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the amounts debited from an account in the
+/// current epoch
+pub fn epoch_spending_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.epoch_spending.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is an epoch spending key. If it is, returns
+/// the owner.
+pub fn is_epoch_spending_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.epoch_spending =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the queued withdrawals of an account
+pub fn queued_withdrawals_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.queued_withdrawals.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a queued withdrawals key. If it is,
+/// returns the owner.
+pub fn is_queued_withdrawals_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.queued_withdrawals =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the guardian set of an account
+pub fn guardians_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.guardians.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a guardian set key. If it is, returns the
+/// owner.
+pub fn is_guardians_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.guardians =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the pending recovery of an account
+pub fn pending_recovery_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.pending_recovery.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a pending recovery key. If it is, returns
+/// the owner.
+pub fn is_pending_recovery_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.pending_recovery =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
@@ -1,7 +1,12 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::types::account::AccountWeights;
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
+use namada_core::types::storage::Epoch;
+use namada_core::types::token::{Amount, DenominatedAmount};
 use serde::{Deserialize, Serialize};
 
 /// A tx data type to initialize a new established account
@@ -24,6 +29,9 @@ pub struct InitAccount {
     pub vp_code_hash: Hash,
     /// The account signature threshold
     pub threshold: u8,
+    /// The weights of the public keys and the weight threshold, if the
+    /// account is weighted
+    pub weights: Option<AccountWeights>,
 }
 
 /// A tx data type to update an account's validity predicate
@@ -48,6 +56,275 @@ pub struct UpdateAccount {
     pub public_keys: Vec<common::PublicKey>,
     /// The account signature threshold
     pub threshold: Option<u8>,
+    /// The weights of the public keys and the weight threshold. Empty weights
+    /// remove the weighting of the account.
+    pub weights: Option<AccountWeights>,
+}
+
+/// A policy restricting the debits from an account, to limit the damage a
+/// compromised set of signing keys can do
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct SpendingPolicy {
+    /// Per-token maximum amounts that can be debited in a single epoch
+    pub epoch_limits: BTreeMap<Address, Amount>,
+    /// Per-token amounts above which a debit has to be queued first
+    pub delay_thresholds: BTreeMap<Address, Amount>,
+    /// The number of epochs a queued withdrawal has to wait before it can be
+    /// executed
+    pub delay: u64,
+    /// A key that can cancel queued withdrawals. When set, it also has to
+    /// authorize changes of the policy and of the account's VP.
+    pub recovery_key: Option<common::PublicKey>,
+}
+
+impl SpendingPolicy {
+    /// Check if replacing this policy with the given one, or removing it when
+    /// `None`, loosens any of its restrictions. Loosening changes have to wait
+    /// for the delay of this policy.
+    pub fn is_loosened_by(&self, new: Option<&SpendingPolicy>) -> bool {
+        let new = match new {
+            Some(new) => new,
+            None => return true,
+        };
+        let is_loosened =
+            |limits: &BTreeMap<Address, Amount>,
+             new_limits: &BTreeMap<Address, Amount>| {
+                limits.iter().any(|(token, limit)| {
+                    new_limits.get(token).map_or(true, |new| new > limit)
+                })
+            };
+        new.delay < self.delay
+            || is_loosened(&self.epoch_limits, &new.epoch_limits)
+            || is_loosened(&self.delay_thresholds, &new.delay_thresholds)
+            || (self.recovery_key.is_some()
+                && self.recovery_key != new.recovery_key)
+    }
+}
+
+/// A change of a spending policy that loosens it, waiting to be applied
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct PendingSpendingPolicy {
+    /// The new policy. `None` removes the policy.
+    pub policy: Option<SpendingPolicy>,
+    /// The epoch from which the change can be applied
+    pub unlock_epoch: Epoch,
+}
+
+/// A replacement of the VP of an account with a spending policy, waiting to
+/// be applied. Replacing the VP can lift any restriction of the policy, so it
+/// is delayed like a loosening change of the policy.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct PendingVpUpdate {
+    /// The new VP code hash
+    pub vp_code_hash: Hash,
+    /// The epoch from which the update can be applied
+    pub unlock_epoch: Epoch,
+}
+
+/// The amount of a token debited from an account in an epoch
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct EpochSpending {
+    /// The epoch of the debits
+    pub epoch: Epoch,
+    /// The total amount debited in the epoch
+    pub amount: Amount,
+}
+
+/// A withdrawal above the delay threshold of an account, waiting to be
+/// executed
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct QueuedWithdrawal {
+    /// Token's address
+    pub token: Address,
+    /// Target address will receive the tokens
+    pub target: Address,
+    /// The amount of tokens
+    pub amount: Amount,
+    /// The epoch from which the withdrawal can be executed
+    pub unlock_epoch: Epoch,
+}
+
+/// The guardians that can together recover an account whose keys are lost
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct GuardianSet {
+    /// The addresses of the guardians
+    pub guardians: BTreeSet<Address>,
+    /// The minimum number of guardians needed to initiate a recovery
+    pub threshold: u8,
+    /// The number of epochs a recovery has to wait before it can be
+    /// finalized, during which the account's keys can cancel it
+    pub delay: u64,
+}
+
+/// A recovery of an account initiated by its guardians, waiting to be
+/// finalized
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct PendingRecovery {
+    /// The public keys replacing the account's keys
+    pub public_keys: Vec<common::PublicKey>,
+    /// The account signature threshold replacing the account's threshold
+    pub threshold: u8,
+    /// The epoch from which the recovery can be finalized
+    pub unlock_epoch: Epoch,
+}
+
+/// A tx data type to set or remove the guardian set of an account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateGuardians {
+    /// An address of the account
+    pub addr: Address,
+    /// The new guardian set. `None` removes the guardians.
+    pub guardians: Option<GuardianSet>,
+}
+
+/// A tx data type for the guardians to initiate a recovery of an account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct InitiateRecovery {
+    /// An address of the account
+    pub addr: Address,
+    /// The public keys replacing the account's keys
+    pub public_keys: Vec<common::PublicKey>,
+    /// The account signature threshold replacing the account's threshold
+    pub threshold: u8,
+}
+
+/// A tx data type to set or remove the spending policy of an account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateSpendingPolicy {
+    /// An address of the account
+    pub addr: Address,
+    /// The new policy. `None` removes the policy.
+    pub policy: Option<SpendingPolicy>,
+}
+
+/// A tx data type to queue a withdrawal above the delay threshold
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct QueueWithdrawal {
+    /// Source address will spend the tokens
+    pub source: Address,
+    /// Target address will receive the tokens
+    pub target: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens
+    pub amount: DenominatedAmount,
+}
+
+/// A tx data type to execute or cancel a queued withdrawal
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct QueuedWithdrawalAction {
+    /// Source address of the withdrawal
+    pub source: Address,
+    /// The id of the queued withdrawal
+    pub id: u64,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -74,6 +351,7 @@ pub mod tests {
                 public_keys,
                 vp_code_hash,
                 threshold,
+                weights: None,
             }
         }
     }
@@ -86,6 +364,10 @@ pub mod tests {
             addr in arb_non_internal_address(),
             vp_code_hash in option::of(arb_hash()),
             threshold in option::of(0..=public_keys.len() as u8),
+            weights in option::of(
+                collection::btree_map(0..10_u8, 1..=u8::MAX, 0..10)
+            ),
+            weight_threshold in 0..=u16::from(u8::MAX),
             public_keys in Just(public_keys),
         ) -> UpdateAccount {
             UpdateAccount {
@@ -93,6 +375,10 @@ pub mod tests {
                 vp_code_hash,
                 public_keys,
                 threshold,
+                weights: weights.map(|weights| AccountWeights {
+                    weights,
+                    threshold: weight_threshold,
+                }),
             }
         }
     }
@@ -0,0 +1,170 @@
+//! An external MASP prover. It proves and signs the shielded transfers built
+//! by a client configured with the `NAMADA_MASP_PROVER` env var, so that the
+//! proving parameters and work, and optionally the spending keys, can be kept
+//! out of the client process.
+//!
+//! Without arguments, the prover answers the requests of the process that
+//! spawned it over its stdio. With `--listen <address>`, it instead serves
+//! the connections to the given loopback TCP address one at a time. Other
+//! addresses are rejected, as these connections are neither authenticated nor
+//! encrypted. With `--listen-tls <address> --cert <file> --key <file>
+//! --client-ca <file>`, it serves mutually authenticated TLS connections on
+//! any address, only accepting the clients with a certificate issued by the
+//! given authorities.
+//!
+//! With `--spending-keys <file>`, the prover signs the spends of the spending
+//! keys of the given file (one per line) when the client doesn't send them,
+//! so that the client only needs their viewing keys.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use color_eyre::eyre::{eyre, Result};
+use namada::types::masp::ExtendedSpendingKey;
+use namada_apps::logging;
+use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::masp::ShieldedUtils;
+use namada_sdk::masp_prover::{self, LocalMaspProver, TlsCredentials};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::Subscriber;
+
+const USAGE: &str = "Usage: namada-masp-prover [--listen <loopback address> \
+                     | --listen-tls <address> --cert <file> --key <file> \
+                     --client-ca <file>] [--spending-keys <file>]";
+
+/// The stdio of this process, used as a stream to the client that spawned it
+struct Stdio;
+
+impl Read for Stdio {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::stdin().read(buf)
+    }
+}
+
+impl Write for Stdio {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::stdout().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+/// The command line arguments of the prover
+#[derive(Default)]
+struct Args {
+    listen: Option<SocketAddr>,
+    listen_tls: Option<SocketAddr>,
+    cert: Option<PathBuf>,
+    key: Option<PathBuf>,
+    client_ca: Option<PathBuf>,
+    spending_keys: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or_else(|| eyre!(USAGE))?;
+            match arg.as_str() {
+                "--listen" => parsed.listen = Some(value.parse()?),
+                "--listen-tls" => parsed.listen_tls = Some(value.parse()?),
+                "--cert" => parsed.cert = Some(value.into()),
+                "--key" => parsed.key = Some(value.into()),
+                "--client-ca" => parsed.client_ca = Some(value.into()),
+                "--spending-keys" => parsed.spending_keys = Some(value.into()),
+                _ => return Err(eyre!(USAGE)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Read the spending keys of the given file, one per line
+fn read_spending_keys(
+    path: &PathBuf,
+) -> Result<Vec<masp_primitives::zip32::ExtendedSpendingKey>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            ExtendedSpendingKey::from_str(line)
+                .map(Into::into)
+                .map_err(|err| eyre!("Invalid spending key: {err}"))
+        })
+        .collect()
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    // Nothing else may be written to stdout, as it carries the responses, so
+    // the logs go to stderr
+    let subscriber = Subscriber::builder()
+        .with_writer(io::stderr)
+        .with_env_filter(logging::filter_from_env_or(LevelFilter::INFO))
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+    logging::init_log_tracer()?;
+
+    let args = Args::parse()?;
+    let mut prover =
+        LocalMaspProver::new(FsShieldedUtils::default().local_tx_prover());
+    if let Some(path) = &args.spending_keys {
+        prover = prover.with_spending_keys(read_spending_keys(path)?);
+    }
+
+    match (args.listen, args.listen_tls) {
+        (None, None) => masp_prover::serve(&prover, Stdio)?,
+        (Some(addr), None) => {
+            if !addr.ip().is_loopback() {
+                return Err(eyre!(
+                    "The MASP prover can only listen on a loopback address \
+                     without TLS, got {addr}"
+                ));
+            }
+            let listener = TcpListener::bind(addr)?;
+            tracing::info!("MASP prover listening on {addr}");
+            for stream in listener.incoming() {
+                if let Err(err) = masp_prover::serve(&prover, stream?) {
+                    tracing::error!("MASP prover connection failed: {err}");
+                }
+            }
+        }
+        (None, Some(addr)) => {
+            let (Some(cert), Some(key), Some(client_ca)) =
+                (args.cert, args.key, args.client_ca)
+            else {
+                return Err(eyre!(
+                    "The MASP prover requires --cert, --key and --client-ca \
+                     to listen with TLS"
+                ));
+            };
+            let config = TlsCredentials {
+                ca_certs: client_ca,
+                cert_chain: cert,
+                private_key: key,
+            }
+            .server_config()?;
+            let listener = TcpListener::bind(addr)?;
+            tracing::info!("MASP prover listening with TLS on {addr}");
+            for stream in listener.incoming() {
+                let served = stream
+                    .and_then(|stream| {
+                        masp_prover::accept_tls(config.clone(), stream)
+                    })
+                    .and_then(|stream| masp_prover::serve(&prover, stream));
+                if let Err(err) = served {
+                    tracing::error!("MASP prover connection failed: {err}");
+                }
+            }
+        }
+        (Some(_), Some(_)) => return Err(eyre!(USAGE)),
+    }
+    Ok(())
+}
@@ -38,6 +38,16 @@ pub fn main() -> Result<()> {
                 ledger::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::ExportState(cmds::LedgerExportState(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::export_state(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to export the Namada node's state")?;
+            }
+            cmds::Ledger::ImportState(cmds::LedgerImportState(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::import_state(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to import the Namada node's state")?;
+            }
         },
         cmds::NamadaNode::Config(sub) => match sub {
             cmds::Config::Gen(cmds::ConfigGen) => {
@@ -79,17 +79,23 @@ use namada_sdk::masp::{
     self, ShieldedContext, ShieldedTransfer, ShieldedUtils,
 };
 pub use namada_sdk::tx::{
-    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
+    TX_APPROVE_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+    TX_BRIDGE_POOL_WASM, TX_BURN_TOKENS_WASM, TX_CANCEL_RECOVERY_WASM,
+    TX_CANCEL_WITHDRAWAL_WASM,
     TX_CHANGE_COMMISSION_WASM as TX_CHANGE_VALIDATOR_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM,
     TX_CHANGE_METADATA_WASM as TX_CHANGE_VALIDATOR_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL as TX_INIT_PROPOSAL_WASM,
-    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
-    TX_REVEAL_PK as TX_REVEAL_PK_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL as TX_VOTE_PROPOSAL_WASM,
-    TX_WITHDRAW_WASM, VP_USER_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+    TX_EXECUTE_WITHDRAWAL_WASM, TX_FINALIZE_RECOVERY_WASM, TX_IBC_WASM,
+    TX_INITIATE_RECOVERY_WASM, TX_INIT_ACCOUNT_WASM,
+    TX_INIT_PROPOSAL as TX_INIT_PROPOSAL_WASM, TX_MINT_TOKENS_WASM,
+    TX_QUEUE_WITHDRAWAL_WASM, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
+    TX_REGISTER_TOKEN_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK as TX_REVEAL_PK_WASM, TX_TRANSFER_FROM_WASM, TX_TRANSFER_WASM,
+    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_UPDATE_GUARDIANS_WASM, TX_UPDATE_SPENDING_POLICY_WASM,
+    TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_TOKEN_METADATA_WASM,
+    TX_VOTE_PROPOSAL as TX_VOTE_PROPOSAL_WASM, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use namada_sdk::wallet::Wallet;
 use namada_sdk::{Namada, NamadaImpl};
@@ -219,6 +219,20 @@ pub mod cmds {
                 .subcommand(TxUpdateAccount::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
+                .subcommand(TxRegisterToken::def().display_order(1))
+                .subcommand(TxMintTokens::def().display_order(1))
+                .subcommand(TxBurnTokens::def().display_order(1))
+                .subcommand(TxUpdateTokenMetadata::def().display_order(1))
+                .subcommand(TxApprove::def().display_order(1))
+                .subcommand(TxTransferFrom::def().display_order(1))
+                .subcommand(TxUpdateSpendingPolicy::def().display_order(1))
+                .subcommand(TxQueueWithdrawal::def().display_order(1))
+                .subcommand(TxExecuteWithdrawal::def().display_order(1))
+                .subcommand(TxCancelWithdrawal::def().display_order(1))
+                .subcommand(TxUpdateGuardians::def().display_order(1))
+                .subcommand(TxInitiateRecovery::def().display_order(1))
+                .subcommand(TxCancelRecovery::def().display_order(1))
+                .subcommand(TxFinalizeRecovery::def().display_order(1))
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
@@ -245,9 +259,11 @@ pub mod cmds {
                 .subcommand(QueryEpoch::def().display_order(5))
                 .subcommand(QueryStatus::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
+                .subcommand(DiscoverAccounts::def().display_order(5))
                 .subcommand(QueryTransfers::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
+                .subcommand(QueryMaspStats::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
                 .subcommand(QueryBalance::def().display_order(5))
                 .subcommand(QueryBonds::def().display_order(5))
@@ -281,6 +297,31 @@ pub mod cmds {
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
+            let tx_register_token =
+                Self::parse_with_ctx(matches, TxRegisterToken);
+            let tx_mint_tokens = Self::parse_with_ctx(matches, TxMintTokens);
+            let tx_burn_tokens = Self::parse_with_ctx(matches, TxBurnTokens);
+            let tx_update_token_metadata =
+                Self::parse_with_ctx(matches, TxUpdateTokenMetadata);
+            let tx_approve = Self::parse_with_ctx(matches, TxApprove);
+            let tx_transfer_from =
+                Self::parse_with_ctx(matches, TxTransferFrom);
+            let tx_update_spending_policy =
+                Self::parse_with_ctx(matches, TxUpdateSpendingPolicy);
+            let tx_queue_withdrawal =
+                Self::parse_with_ctx(matches, TxQueueWithdrawal);
+            let tx_execute_withdrawal =
+                Self::parse_with_ctx(matches, TxExecuteWithdrawal);
+            let tx_cancel_withdrawal =
+                Self::parse_with_ctx(matches, TxCancelWithdrawal);
+            let tx_update_guardians =
+                Self::parse_with_ctx(matches, TxUpdateGuardians);
+            let tx_initiate_recovery =
+                Self::parse_with_ctx(matches, TxInitiateRecovery);
+            let tx_cancel_recovery =
+                Self::parse_with_ctx(matches, TxCancelRecovery);
+            let tx_finalize_recovery =
+                Self::parse_with_ctx(matches, TxFinalizeRecovery);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
             let tx_init_validator =
@@ -314,11 +355,15 @@ pub mod cmds {
             let query_epoch = Self::parse_with_ctx(matches, QueryEpoch);
             let query_status = Self::parse_with_ctx(matches, QueryStatus);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
+            let discover_accounts =
+                Self::parse_with_ctx(matches, DiscoverAccounts);
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
             let query_masp_reward_tokens =
                 Self::parse_with_ctx(matches, QueryMaspRewardTokens);
+            let query_masp_stats =
+                Self::parse_with_ctx(matches, QueryMaspStats);
             let query_block = Self::parse_with_ctx(matches, QueryBlock);
             let query_balance = Self::parse_with_ctx(matches, QueryBalance);
             let query_bonds = Self::parse_with_ctx(matches, QueryBonds);
@@ -357,6 +402,20 @@ pub mod cmds {
                 .or(tx_update_account)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
+                .or(tx_register_token)
+                .or(tx_mint_tokens)
+                .or(tx_burn_tokens)
+                .or(tx_update_token_metadata)
+                .or(tx_approve)
+                .or(tx_transfer_from)
+                .or(tx_update_spending_policy)
+                .or(tx_queue_withdrawal)
+                .or(tx_execute_withdrawal)
+                .or(tx_cancel_withdrawal)
+                .or(tx_update_guardians)
+                .or(tx_initiate_recovery)
+                .or(tx_cancel_recovery)
+                .or(tx_finalize_recovery)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
                 .or(tx_become_validator)
@@ -380,6 +439,7 @@ pub mod cmds {
                 .or(query_transfers)
                 .or(query_conversions)
                 .or(query_masp_reward_tokens)
+                .or(query_masp_stats)
                 .or(query_block)
                 .or(query_balance)
                 .or(query_bonds)
@@ -399,6 +459,7 @@ pub mod cmds {
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
+                .or(discover_accounts)
                 .or(sign_tx)
                 .or(gen_ibc_shielded)
                 .or(utils)
@@ -455,6 +516,20 @@ pub mod cmds {
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
         TxRevealPk(TxRevealPk),
+        TxRegisterToken(TxRegisterToken),
+        TxMintTokens(TxMintTokens),
+        TxBurnTokens(TxBurnTokens),
+        TxUpdateTokenMetadata(TxUpdateTokenMetadata),
+        TxApprove(TxApprove),
+        TxTransferFrom(TxTransferFrom),
+        TxUpdateSpendingPolicy(TxUpdateSpendingPolicy),
+        TxQueueWithdrawal(TxQueueWithdrawal),
+        TxExecuteWithdrawal(TxExecuteWithdrawal),
+        TxCancelWithdrawal(TxCancelWithdrawal),
+        TxUpdateGuardians(TxUpdateGuardians),
+        TxInitiateRecovery(TxInitiateRecovery),
+        TxCancelRecovery(TxCancelRecovery),
+        TxFinalizeRecovery(TxFinalizeRecovery),
         Bond(Bond),
         Unbond(Unbond),
         Withdraw(Withdraw),
@@ -466,9 +541,11 @@ pub mod cmds {
         QueryEpoch(QueryEpoch),
         QueryStatus(QueryStatus),
         QueryAccount(QueryAccount),
+        DiscoverAccounts(DiscoverAccounts),
         QueryTransfers(QueryTransfers),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
+        QueryMaspStats(QueryMaspStats),
         QueryBlock(QueryBlock),
         QueryBalance(QueryBalance),
         QueryBonds(QueryBonds),
@@ -497,6 +574,8 @@ pub mod cmds {
         KeyGen(WalletGen),
         /// Key derivation
         KeyDerive(WalletDerive),
+        /// Extended public key derivation
+        KeyXpub(WalletXpub),
         /// Payment address generation
         PayAddrGen(WalletGenPaymentAddress),
         /// Key / address list
@@ -507,6 +586,10 @@ pub mod cmds {
         KeyExport(WalletExportKey),
         /// Key import
         KeyImport(WalletImportKey),
+        /// Wallet backup
+        Backup(WalletBackup),
+        /// Wallet restore
+        Restore(WalletRestore),
         /// Key / address add
         KeyAddrAdd(WalletAddKeyAddress),
         /// Key / address remove
@@ -517,11 +600,14 @@ pub mod cmds {
         fn add_sub(app: App) -> App {
             app.subcommand(WalletGen::def())
                 .subcommand(WalletDerive::def())
+                .subcommand(WalletXpub::def())
                 .subcommand(WalletGenPaymentAddress::def())
                 .subcommand(WalletListKeysAddresses::def())
                 .subcommand(WalletFindKeysAddresses::def())
                 .subcommand(WalletExportKey::def())
                 .subcommand(WalletImportKey::def())
+                .subcommand(WalletBackup::def())
+                .subcommand(WalletRestore::def())
                 .subcommand(WalletAddKeyAddress::def())
                 .subcommand(WalletRemoveKeyAddress::def())
         }
@@ -529,20 +615,26 @@ pub mod cmds {
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let gen = SubCmd::parse(matches).map(Self::KeyGen);
             let derive = SubCmd::parse(matches).map(Self::KeyDerive);
+            let xpub = SubCmd::parse(matches).map(Self::KeyXpub);
             let pay_addr_gen = SubCmd::parse(matches).map(Self::PayAddrGen);
             let key_addr_list = SubCmd::parse(matches).map(Self::KeyAddrList);
             let key_addr_find = SubCmd::parse(matches).map(Self::KeyAddrFind);
             let export = SubCmd::parse(matches).map(Self::KeyExport);
             let import = SubCmd::parse(matches).map(Self::KeyImport);
+            let backup = SubCmd::parse(matches).map(Self::Backup);
+            let restore = SubCmd::parse(matches).map(Self::Restore);
             let key_addr_add = SubCmd::parse(matches).map(Self::KeyAddrAdd);
             let key_addr_remove =
                 SubCmd::parse(matches).map(Self::KeyAddrRemove);
             gen.or(derive)
+                .or(xpub)
                 .or(pay_addr_gen)
                 .or(key_addr_list)
                 .or(key_addr_find)
                 .or(export)
                 .or(import)
+                .or(backup)
+                .or(restore)
                 .or(key_addr_add)
                 .or(key_addr_remove)
         }
@@ -632,6 +724,36 @@ pub mod cmds {
         }
     }
 
+    /// Derive a secp256k1 extended public key from the mnemonic code
+    #[derive(Clone, Debug)]
+    pub struct WalletXpub(pub args::KeyXpub);
+
+    impl SubCmd for WalletXpub {
+        const CMD: &'static str = "xpub";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::KeyXpub::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Derive a secp256k1 extended public key from the mnemonic \
+                     code.",
+                )
+                .long_about(
+                    "Derives the BIP32 extended public key at the given HD \
+                     derivation path from the mnemonic code. The extended \
+                     public key can be used to discover and track the \
+                     addresses of its children in watch-only mode, without \
+                     access to the mnemonic code.",
+                )
+                .add_args::<args::KeyXpub>()
+        }
+    }
+
     /// List known keys and addresses
     #[derive(Clone, Debug)]
     pub struct WalletListKeysAddresses(pub args::KeyAddressList);
@@ -731,6 +853,61 @@ pub mod cmds {
         }
     }
 
+    /// Backup the whole wallet to an encrypted file
+    #[derive(Clone, Debug)]
+    pub struct WalletBackup(pub args::WalletBackup);
+
+    impl SubCmd for WalletBackup {
+        const CMD: &'static str = "backup";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletBackup::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Writes a password encrypted backup of the whole wallet \
+                     to a file.",
+                )
+                .long_about(
+                    "Writes a password encrypted backup of the whole wallet \
+                     to a file, including its aliases, HD derivation paths, \
+                     transparent and shielded keys, payment addresses and \
+                     validator data. Keys that are encrypted in the wallet \
+                     stay encrypted with their own password in the backup.",
+                )
+                .add_args::<args::WalletBackup>()
+        }
+    }
+
+    /// Restore the wallet from an encrypted backup file
+    #[derive(Clone, Debug)]
+    pub struct WalletRestore(pub args::WalletRestore);
+
+    impl SubCmd for WalletRestore {
+        const CMD: &'static str = "restore";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletRestore::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Restores the wallet from an encrypted backup file.")
+                .long_about(
+                    "Restores the wallet from an encrypted backup file. The \
+                     entries of the backup are added to the wallet, replacing \
+                     the entries with the same aliases.",
+                )
+                .add_args::<args::WalletRestore>()
+        }
+    }
+
     /// Add public / payment address to the wallet
     #[derive(Clone, Debug)]
     pub struct WalletAddKeyAddress(pub args::KeyAddressAdd);
@@ -803,6 +980,8 @@ pub mod cmds {
         Reset(LedgerReset),
         DumpDb(LedgerDumpDb),
         RollBack(LedgerRollBack),
+        ExportState(LedgerExportState),
+        ImportState(LedgerImportState),
     }
 
     impl SubCmd for Ledger {
@@ -815,10 +994,16 @@ pub mod cmds {
                 let dump_db = SubCmd::parse(matches).map(Self::DumpDb);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
+                let export_state =
+                    SubCmd::parse(matches).map(Self::ExportState);
+                let import_state =
+                    SubCmd::parse(matches).map(Self::ImportState);
                 run.or(reset)
                     .or(dump_db)
                     .or(rollback)
                     .or(run_until)
+                    .or(export_state)
+                    .or(import_state)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
@@ -837,6 +1022,8 @@ pub mod cmds {
                 .subcommand(LedgerReset::def())
                 .subcommand(LedgerDumpDb::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerExportState::def())
+                .subcommand(LedgerImportState::def())
         }
     }
 
@@ -938,6 +1125,53 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerExportState(pub args::LedgerExportState);
+
+    impl SubCmd for LedgerExportState {
+        const CMD: &'static str = "export-state";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerExportState::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Export the state of Namada ledger node's last committed \
+                     block into a compressed archive. The node must be \
+                     stopped.",
+                )
+                .add_args::<args::LedgerExportState>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerImportState(pub args::LedgerImportState);
+
+    impl SubCmd for LedgerImportState {
+        const CMD: &'static str = "import-state";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerImportState::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Import the state exported with `export-state` into \
+                     Namada ledger node's empty DB and verify its merkle \
+                     root. The Tendermint node's data is not part of the \
+                     archive.",
+                )
+                .add_args::<args::LedgerImportState>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1175,141 +1409,146 @@ pub mod cmds {
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxIbcTransfer(pub args::TxIbcTransfer<args::CliTypes>);
+    pub struct TxRegisterToken(pub args::TxRegisterToken<args::CliTypes>);
 
-    impl SubCmd for TxIbcTransfer {
-        const CMD: &'static str = "ibc-transfer";
+    impl SubCmd for TxRegisterToken {
+        const CMD: &'static str = "register-token";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxIbcTransfer(args::TxIbcTransfer::parse(matches))
+                TxRegisterToken(args::TxRegisterToken::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Send a signed IBC transfer transaction.")
-                .add_args::<args::TxIbcTransfer<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to register an established \
+                     account as a user-issued token.",
+                )
+                .add_args::<args::TxRegisterToken<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxUpdateAccount(pub args::TxUpdateAccount<args::CliTypes>);
+    pub struct TxMintTokens(pub args::TxMintTokens<args::CliTypes>);
 
-    impl SubCmd for TxUpdateAccount {
-        const CMD: &'static str = "update-account";
+    impl SubCmd for TxMintTokens {
+        const CMD: &'static str = "mint-tokens";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxUpdateAccount(args::TxUpdateAccount::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxMintTokens(args::TxMintTokens::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Send a signed transaction to update account's validity \
-                     predicate.",
-                )
-                .add_args::<args::TxUpdateAccount<args::CliTypes>>()
+                .about("Send a signed transaction to mint a user-issued token.")
+                .add_args::<args::TxMintTokens<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
+    pub struct TxBurnTokens(pub args::TxBurnTokens<args::CliTypes>);
 
-    impl SubCmd for TxInitAccount {
-        const CMD: &'static str = "init-account";
+    impl SubCmd for TxBurnTokens {
+        const CMD: &'static str = "burn-tokens";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxInitAccount(args::TxInitAccount::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxBurnTokens(args::TxBurnTokens::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Send a signed transaction to create a new established \
-                     account.",
-                )
-                .add_args::<args::TxInitAccount<args::CliTypes>>()
+                .about("Send a signed transaction to burn a user-issued token.")
+                .add_args::<args::TxBurnTokens<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxBecomeValidator(pub args::TxBecomeValidator<args::CliTypes>);
+    pub struct TxUpdateTokenMetadata(
+        pub args::TxUpdateTokenMetadata<args::CliTypes>,
+    );
 
-    impl SubCmd for TxBecomeValidator {
-        const CMD: &'static str = "become-validator";
+    impl SubCmd for TxUpdateTokenMetadata {
+        const CMD: &'static str = "update-token-metadata";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxBecomeValidator(args::TxBecomeValidator::parse(matches))
+                TxUpdateTokenMetadata(args::TxUpdateTokenMetadata::parse(
+                    matches,
+                ))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Send a signed transaction to become a validator.")
-                .add_args::<args::TxBecomeValidator<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to update the on-chain \
+                     metadata of a token.",
+                )
+                .add_args::<args::TxUpdateTokenMetadata<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxInitValidator(pub args::TxInitValidator<args::CliTypes>);
+    pub struct TxApprove(pub args::TxApprove<args::CliTypes>);
 
-    impl SubCmd for TxInitValidator {
-        const CMD: &'static str = "init-validator";
+    impl SubCmd for TxApprove {
+        const CMD: &'static str = "approve";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxInitValidator(args::TxInitValidator::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxApprove(args::TxApprove::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to create an established \
-                     account and then become a validator.",
+                    "Send a signed transaction to allow a spender to transfer \
+                     tokens from the owner's account.",
                 )
-                .add_args::<args::TxInitValidator<args::CliTypes>>()
+                .add_args::<args::TxApprove<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxUnjailValidator(pub args::TxUnjailValidator<args::CliTypes>);
+    pub struct TxTransferFrom(pub args::TxTransferFrom<args::CliTypes>);
 
-    impl SubCmd for TxUnjailValidator {
-        const CMD: &'static str = "unjail-validator";
+    impl SubCmd for TxTransferFrom {
+        const CMD: &'static str = "transfer-from";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxUnjailValidator(args::TxUnjailValidator::parse(matches))
+                TxTransferFrom(args::TxTransferFrom::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to unjail a jailed validator.",
+                    "Send a signed transaction to transfer tokens from an \
+                     owner's account within the spender's allowance.",
                 )
-                .add_args::<args::TxUnjailValidator<args::CliTypes>>()
+                .add_args::<args::TxTransferFrom<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxDeactivateValidator(
-        pub args::TxDeactivateValidator<args::CliTypes>,
+    pub struct TxUpdateSpendingPolicy(
+        pub args::TxUpdateSpendingPolicy<args::CliTypes>,
     );
 
-    impl SubCmd for TxDeactivateValidator {
-        const CMD: &'static str = "deactivate-validator";
+    impl SubCmd for TxUpdateSpendingPolicy {
+        const CMD: &'static str = "update-spending-policy";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxDeactivateValidator(args::TxDeactivateValidator::parse(
+                TxUpdateSpendingPolicy(args::TxUpdateSpendingPolicy::parse(
                     matches,
                 ))
             })
@@ -1317,2647 +1556,3764 @@ pub mod cmds {
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Send a signed transaction to deactivate a validator.")
-                .add_args::<args::TxDeactivateValidator<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to set or remove the spending \
+                     policy of an account. A change loosening the current \
+                     policy is queued and has to be sent again once the \
+                     delay of the current policy has passed.",
+                )
+                .add_args::<args::TxUpdateSpendingPolicy<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxReactivateValidator(
-        pub args::TxReactivateValidator<args::CliTypes>,
-    );
+    pub struct TxQueueWithdrawal(pub args::TxQueueWithdrawal<args::CliTypes>);
 
-    impl SubCmd for TxReactivateValidator {
-        const CMD: &'static str = "reactivate-validator";
+    impl SubCmd for TxQueueWithdrawal {
+        const CMD: &'static str = "queue-withdrawal";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxReactivateValidator(args::TxReactivateValidator::parse(
-                    matches,
-                ))
+                TxQueueWithdrawal(args::TxQueueWithdrawal::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to reactivate an inactive \
-                     validator.",
+                    "Send a signed transaction to queue a withdrawal above \
+                     the delay threshold of an account's spending policy.",
                 )
-                .add_args::<args::TxReactivateValidator<args::CliTypes>>()
+                .add_args::<args::TxQueueWithdrawal<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct Bond(pub args::Bond<args::CliTypes>);
+    pub struct TxExecuteWithdrawal(
+        pub args::TxExecuteWithdrawal<args::CliTypes>,
+    );
 
-    impl SubCmd for Bond {
-        const CMD: &'static str = "bond";
+    impl SubCmd for TxExecuteWithdrawal {
+        const CMD: &'static str = "execute-withdrawal";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Bond(args::Bond::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxExecuteWithdrawal(args::TxExecuteWithdrawal::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Bond tokens in PoS system.")
-                .add_args::<args::Bond<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to execute a queued \
+                     withdrawal after its delay has passed.",
+                )
+                .add_args::<args::TxExecuteWithdrawal<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct Unbond(pub args::Unbond<args::CliTypes>);
+    pub struct TxCancelWithdrawal(pub args::TxCancelWithdrawal<args::CliTypes>);
 
-    impl SubCmd for Unbond {
-        const CMD: &'static str = "unbond";
+    impl SubCmd for TxCancelWithdrawal {
+        const CMD: &'static str = "cancel-withdrawal";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Unbond(args::Unbond::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxCancelWithdrawal(args::TxCancelWithdrawal::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Unbond tokens from a PoS bond.")
-                .add_args::<args::Unbond<args::CliTypes>>()
+                .about(
+                    "Send a transaction signed by the recovery key of an \
+                     account's spending policy to cancel a queued withdrawal.",
+                )
+                .add_args::<args::TxCancelWithdrawal<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct Withdraw(pub args::Withdraw<args::CliTypes>);
+    pub struct TxUpdateGuardians(pub args::TxUpdateGuardians<args::CliTypes>);
 
-    impl SubCmd for Withdraw {
-        const CMD: &'static str = "withdraw";
+    impl SubCmd for TxUpdateGuardians {
+        const CMD: &'static str = "update-guardians";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Withdraw(args::Withdraw::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateGuardians(args::TxUpdateGuardians::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Withdraw tokens from previously unbonded PoS bond.")
-                .add_args::<args::Withdraw<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to set the guardians of an \
+                     account that can initiate its recovery, or to remove \
+                     them.",
+                )
+                .add_args::<args::TxUpdateGuardians<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ClaimRewards(pub args::ClaimRewards<args::CliTypes>);
+    pub struct TxInitiateRecovery(pub args::TxInitiateRecovery<args::CliTypes>);
 
-    impl SubCmd for ClaimRewards {
-        const CMD: &'static str = "claim-rewards";
+    impl SubCmd for TxInitiateRecovery {
+        const CMD: &'static str = "initiate-recovery";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| ClaimRewards(args::ClaimRewards::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxInitiateRecovery(args::TxInitiateRecovery::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Claim available rewards tokens from bonds that \
-                     contributed in consensus.",
+                    "Send a transaction signed by the guardians of an \
+                     account to initiate the replacement of its keys.",
                 )
-                .add_args::<args::ClaimRewards<args::CliTypes>>()
+                .add_args::<args::TxInitiateRecovery<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct Redelegate(pub args::Redelegate<args::CliTypes>);
+    pub struct TxCancelRecovery(pub args::TxCancelRecovery<args::CliTypes>);
 
-    impl SubCmd for Redelegate {
-        const CMD: &'static str = "redelegate";
+    impl SubCmd for TxCancelRecovery {
+        const CMD: &'static str = "cancel-recovery";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Redelegate(args::Redelegate::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxCancelRecovery(args::TxCancelRecovery::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Redelegate bonded tokens from one validator to another.",
+                    "Send a transaction signed by the keys of an account to \
+                     cancel a pending recovery of the account.",
                 )
-                .add_args::<args::Redelegate<args::CliTypes>>()
+                .add_args::<args::TxCancelRecovery<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryEpoch(pub args::Query<args::CliTypes>);
+    pub struct TxFinalizeRecovery(pub args::TxFinalizeRecovery<args::CliTypes>);
 
-    impl SubCmd for QueryEpoch {
-        const CMD: &'static str = "epoch";
+    impl SubCmd for TxFinalizeRecovery {
+        const CMD: &'static str = "finalize-recovery";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryEpoch(args::Query::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxFinalizeRecovery(args::TxFinalizeRecovery::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the epoch of the last committed block.")
-                .add_args::<args::Query<args::CliTypes>>()
+                .about(
+                    "Send a transaction to finalize a recovery of an account \
+                     after its delay has passed.",
+                )
+                .add_args::<args::TxFinalizeRecovery<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryStatus(pub args::Query<args::CliTypes>);
+    pub struct TxIbcTransfer(pub args::TxIbcTransfer<args::CliTypes>);
 
-    impl SubCmd for QueryStatus {
-        const CMD: &'static str = "status";
+    impl SubCmd for TxIbcTransfer {
+        const CMD: &'static str = "ibc-transfer";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryStatus(args::Query::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxIbcTransfer(args::TxIbcTransfer::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the node's status.")
-                .add_args::<args::Query<args::CliTypes>>()
+                .about("Send a signed IBC transfer transaction.")
+                .add_args::<args::TxIbcTransfer<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryAccount(pub args::QueryAccount<args::CliTypes>);
+    pub struct TxUpdateAccount(pub args::TxUpdateAccount<args::CliTypes>);
 
-    impl SubCmd for QueryAccount {
-        const CMD: &'static str = "query-account";
+    impl SubCmd for TxUpdateAccount {
+        const CMD: &'static str = "update-account";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryAccount(args::QueryAccount::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateAccount(args::TxUpdateAccount::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Query the substorage space of a specific enstablished \
-                     address.",
+                    "Send a signed transaction to update account's validity \
+                     predicate.",
                 )
-                .add_args::<args::QueryAccount<args::CliTypes>>()
+                .add_args::<args::TxUpdateAccount<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryConversions(pub args::QueryConversions<args::CliTypes>);
+    pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
 
-    impl SubCmd for QueryConversions {
-        const CMD: &'static str = "conversions";
+    impl SubCmd for TxInitAccount {
+        const CMD: &'static str = "init-account";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryConversions(args::QueryConversions::parse(matches))
+                TxInitAccount(args::TxInitAccount::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query currently applicable conversions.")
-                .add_args::<args::QueryConversions<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to create a new established \
+                     account.",
+                )
+                .add_args::<args::TxInitAccount<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryMaspRewardTokens(pub args::Query<args::CliTypes>);
+    pub struct TxBecomeValidator(pub args::TxBecomeValidator<args::CliTypes>);
 
-    impl SubCmd for QueryMaspRewardTokens {
-        const CMD: &'static str = "masp-reward-tokens";
+    impl SubCmd for TxBecomeValidator {
+        const CMD: &'static str = "become-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryMaspRewardTokens(args::Query::parse(matches))
+                TxBecomeValidator(args::TxBecomeValidator::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Query the tokens which can earn MASP rewards while \
-                     shielded.",
-                )
-                .add_args::<args::Query<args::CliTypes>>()
+                .about("Send a signed transaction to become a validator.")
+                .add_args::<args::TxBecomeValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBlock(pub args::Query<args::CliTypes>);
+    pub struct TxInitValidator(pub args::TxInitValidator<args::CliTypes>);
 
-    impl SubCmd for QueryBlock {
-        const CMD: &'static str = "block";
+    impl SubCmd for TxInitValidator {
+        const CMD: &'static str = "init-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryBlock(args::Query::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxInitValidator(args::TxInitValidator::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the last committed block.")
-                .add_args::<args::Query<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to create an established \
+                     account and then become a validator.",
+                )
+                .add_args::<args::TxInitValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBalance(pub args::QueryBalance<args::CliTypes>);
+    pub struct TxUnjailValidator(pub args::TxUnjailValidator<args::CliTypes>);
 
-    impl SubCmd for QueryBalance {
-        const CMD: &'static str = "balance";
+    impl SubCmd for TxUnjailValidator {
+        const CMD: &'static str = "unjail-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryBalance(args::QueryBalance::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUnjailValidator(args::TxUnjailValidator::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query balance(s) of tokens.")
-                .add_args::<args::QueryBalance<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to unjail a jailed validator.",
+                )
+                .add_args::<args::TxUnjailValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBonds(pub args::QueryBonds<args::CliTypes>);
+    pub struct TxDeactivateValidator(
+        pub args::TxDeactivateValidator<args::CliTypes>,
+    );
 
-    impl SubCmd for QueryBonds {
-        const CMD: &'static str = "bonds";
+    impl SubCmd for TxDeactivateValidator {
+        const CMD: &'static str = "deactivate-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryBonds(args::QueryBonds::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxDeactivateValidator(args::TxDeactivateValidator::parse(
+                    matches,
+                ))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query PoS bond(s).")
-                .add_args::<args::QueryBonds<args::CliTypes>>()
+                .about("Send a signed transaction to deactivate a validator.")
+                .add_args::<args::TxDeactivateValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBondedStake(pub args::QueryBondedStake<args::CliTypes>);
+    pub struct TxReactivateValidator(
+        pub args::TxReactivateValidator<args::CliTypes>,
+    );
 
-    impl SubCmd for QueryBondedStake {
-        const CMD: &'static str = "bonded-stake";
+    impl SubCmd for TxReactivateValidator {
+        const CMD: &'static str = "reactivate-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryBondedStake(args::QueryBondedStake::parse(matches))
+                TxReactivateValidator(args::TxReactivateValidator::parse(
+                    matches,
+                ))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query PoS bonded stake.")
-                .add_args::<args::QueryBondedStake<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to reactivate an inactive \
+                     validator.",
+                )
+                .add_args::<args::TxReactivateValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct SignTx(pub args::SignTx<args::CliTypes>);
+    pub struct Bond(pub args::Bond<args::CliTypes>);
 
-    impl SubCmd for SignTx {
-        const CMD: &'static str = "sign-tx";
+    impl SubCmd for Bond {
+        const CMD: &'static str = "bond";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| SignTx(args::SignTx::parse(matches)))
+                .map(|matches| Bond(args::Bond::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query PoS bonded stake.")
-                .add_args::<args::SignTx<args::CliTypes>>()
+                .about("Bond tokens in PoS system.")
+                .add_args::<args::Bond<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryValidatorState(
-        pub args::QueryValidatorState<args::CliTypes>,
-    );
+    pub struct Unbond(pub args::Unbond<args::CliTypes>);
 
-    impl SubCmd for QueryValidatorState {
-        const CMD: &'static str = "validator-state";
+    impl SubCmd for Unbond {
+        const CMD: &'static str = "unbond";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryValidatorState(args::QueryValidatorState::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Unbond(args::Unbond::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the state of a PoS validator.")
-                .add_args::<args::QueryValidatorState<args::CliTypes>>()
+                .about("Unbond tokens from a PoS bond.")
+                .add_args::<args::Unbond<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryTransfers(pub args::QueryTransfers<args::CliTypes>);
+    pub struct Withdraw(pub args::Withdraw<args::CliTypes>);
 
-    impl SubCmd for QueryTransfers {
-        const CMD: &'static str = "show-transfers";
+    impl SubCmd for Withdraw {
+        const CMD: &'static str = "withdraw";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryTransfers(args::QueryTransfers::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Withdraw(args::Withdraw::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the accepted transfers to date.")
-                .add_args::<args::QueryTransfers<args::CliTypes>>()
+                .about("Withdraw tokens from previously unbonded PoS bond.")
+                .add_args::<args::Withdraw<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryCommissionRate(
-        pub args::QueryCommissionRate<args::CliTypes>,
-    );
+    pub struct ClaimRewards(pub args::ClaimRewards<args::CliTypes>);
 
-    impl SubCmd for QueryCommissionRate {
-        const CMD: &'static str = "commission-rate";
+    impl SubCmd for ClaimRewards {
+        const CMD: &'static str = "claim-rewards";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryCommissionRate(args::QueryCommissionRate::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| ClaimRewards(args::ClaimRewards::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query a validator's commission rate.")
-                .add_args::<args::QueryCommissionRate<args::CliTypes>>()
+                .about(
+                    "Claim available rewards tokens from bonds that \
+                     contributed in consensus.",
+                )
+                .add_args::<args::ClaimRewards<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryMetaData(pub args::QueryMetaData<args::CliTypes>);
+    pub struct Redelegate(pub args::Redelegate<args::CliTypes>);
 
-    impl SubCmd for QueryMetaData {
-        const CMD: &'static str = "validator-metadata";
+    impl SubCmd for Redelegate {
+        const CMD: &'static str = "redelegate";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryMetaData(args::QueryMetaData::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Redelegate(args::Redelegate::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query a validator's metadata.")
-                .add_args::<args::QueryMetaData<args::CliTypes>>()
+                .about(
+                    "Redelegate bonded tokens from one validator to another.",
+                )
+                .add_args::<args::Redelegate<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QuerySlashes(pub args::QuerySlashes<args::CliTypes>);
+    pub struct QueryEpoch(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for QuerySlashes {
-        const CMD: &'static str = "slashes";
+    impl SubCmd for QueryEpoch {
+        const CMD: &'static str = "epoch";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
+        fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| QuerySlashes(args::QuerySlashes::parse(matches)))
+                .map(|matches| QueryEpoch(args::Query::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query PoS applied slashes.")
-                .add_args::<args::QuerySlashes<args::CliTypes>>()
+                .about("Query the epoch of the last committed block.")
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryRewards(pub args::QueryRewards<args::CliTypes>);
+    pub struct QueryStatus(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for QueryRewards {
-        const CMD: &'static str = "rewards";
+    impl SubCmd for QueryStatus {
+        const CMD: &'static str = "status";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| QueryRewards(args::QueryRewards::parse(matches)))
+                .map(|matches| QueryStatus(args::Query::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Query the latest rewards available to claim for a given \
-                     delegation (or self-bond).",
-                )
-                .add_args::<args::QueryRewards<args::CliTypes>>()
+                .about("Query the node's status.")
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryDelegations(pub args::QueryDelegations<args::CliTypes>);
+    pub struct QueryAccount(pub args::QueryAccount<args::CliTypes>);
 
-    impl SubCmd for QueryDelegations {
-        const CMD: &'static str = "delegations";
+    impl SubCmd for QueryAccount {
+        const CMD: &'static str = "query-account";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryDelegations(args::QueryDelegations::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryAccount(args::QueryAccount::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Find PoS delegations from the given owner address.")
-                .add_args::<args::QueryDelegations<args::CliTypes>>()
+                .about(
+                    "Query the substorage space of a specific enstablished \
+                     address.",
+                )
+                .add_args::<args::QueryAccount<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryFindValidator(pub args::QueryFindValidator<args::CliTypes>);
+    pub struct DiscoverAccounts(pub args::DiscoverAccounts<args::CliTypes>);
 
-    impl SubCmd for QueryFindValidator {
-        const CMD: &'static str = "find-validator";
+    impl SubCmd for DiscoverAccounts {
+        const CMD: &'static str = "discover-accounts";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
+        fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryFindValidator(args::QueryFindValidator::parse(matches))
+                DiscoverAccounts(args::DiscoverAccounts::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Find a PoS validator and its consensus key by its native \
-                     address or Tendermint address.",
+                    "Discover the used addresses of an HD wallet and import \
+                     them into the wallet.",
                 )
-                .add_args::<args::QueryFindValidator<args::CliTypes>>()
+                .long_about(
+                    "Derives successive address indices from the mnemonic \
+                     code, or from a secp256k1 extended public key in \
+                     watch-only mode, and queries the chain for revealed \
+                     public keys, balances and bonds. The keys of the used \
+                     addresses are stored with the given alias suffixed by \
+                     their address index. The scan stops after a number of \
+                     consecutive unused addresses given by the gap limit.",
+                )
+                .add_args::<args::DiscoverAccounts<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryRawBytes(pub args::QueryRawBytes<args::CliTypes>);
+    pub struct QueryConversions(pub args::QueryConversions<args::CliTypes>);
 
-    impl SubCmd for QueryRawBytes {
-        const CMD: &'static str = "query-bytes";
+    impl SubCmd for QueryConversions {
+        const CMD: &'static str = "conversions";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryRawBytes(args::QueryRawBytes::parse(matches))
+                QueryConversions(args::QueryConversions::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the raw bytes of a given storage key")
-                .add_args::<args::QueryRawBytes<args::CliTypes>>()
+                .about("Query currently applicable conversions.")
+                .add_args::<args::QueryConversions<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxInitProposal(pub args::InitProposal<args::CliTypes>);
+    pub struct QueryMaspRewardTokens(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for TxInitProposal {
-        const CMD: &'static str = "init-proposal";
+    impl SubCmd for QueryMaspRewardTokens {
+        const CMD: &'static str = "masp-reward-tokens";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
+        fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxInitProposal(args::InitProposal::parse(matches))
+                QueryMaspRewardTokens(args::Query::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Create a new proposal.")
-                .add_args::<args::InitProposal<args::CliTypes>>()
+                .about(
+                    "Query the tokens which can earn MASP rewards while \
+                     shielded.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxUpdateStewardCommission(
-        pub args::UpdateStewardCommission<args::CliTypes>,
-    );
+    pub struct QueryMaspStats(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for TxUpdateStewardCommission {
-        const CMD: &'static str = "update-steward-rewards";
+    impl SubCmd for QueryMaspStats {
+        const CMD: &'static str = "masp-stats";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxUpdateStewardCommission(args::UpdateStewardCommission::parse(
-                    matches,
-                ))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryMaspStats(args::Query::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Update how steward commissions are split.")
-                .add_args::<args::UpdateStewardCommission<args::CliTypes>>()
+                .about(
+                    "Query the shielded pool statistics: the shielded \
+                     balance of each token, the number of note commitments \
+                     and nullifiers and the current conversion epoch.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxResignSteward(pub args::ResignSteward<args::CliTypes>);
+    pub struct QueryBlock(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for TxResignSteward {
-        const CMD: &'static str = "resign-steward";
+    impl SubCmd for QueryBlock {
+        const CMD: &'static str = "block";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxResignSteward(args::ResignSteward::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryBlock(args::Query::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Craft a transaction to resign as a steward.")
-                .add_args::<args::ResignSteward<args::CliTypes>>()
+                .about("Query the last committed block.")
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxCommissionRateChange(
-        pub args::CommissionRateChange<args::CliTypes>,
-    );
+    pub struct QueryBalance(pub args::QueryBalance<args::CliTypes>);
 
-    impl SubCmd for TxCommissionRateChange {
-        const CMD: &'static str = "change-commission-rate";
+    impl SubCmd for QueryBalance {
+        const CMD: &'static str = "balance";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxCommissionRateChange(args::CommissionRateChange::parse(
-                    matches,
-                ))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryBalance(args::QueryBalance::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Change commission rate.")
-                .add_args::<args::CommissionRateChange<args::CliTypes>>()
+                .about("Query balance(s) of tokens.")
+                .add_args::<args::QueryBalance<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxMetadataChange(pub args::MetaDataChange<args::CliTypes>);
+    pub struct QueryBonds(pub args::QueryBonds<args::CliTypes>);
 
-    impl SubCmd for TxMetadataChange {
-        const CMD: &'static str = "change-metadata";
+    impl SubCmd for QueryBonds {
+        const CMD: &'static str = "bonds";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxMetadataChange(args::MetaDataChange::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryBonds(args::QueryBonds::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Change validator's metadata, including the commission \
-                     rate if desired.",
-                )
-                .add_args::<args::MetaDataChange<args::CliTypes>>()
+                .about("Query PoS bond(s).")
+                .add_args::<args::QueryBonds<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxChangeConsensusKey(
-        pub args::ConsensusKeyChange<args::CliTypes>,
-    );
+    pub struct QueryBondedStake(pub args::QueryBondedStake<args::CliTypes>);
 
-    impl SubCmd for TxChangeConsensusKey {
-        const CMD: &'static str = "change-consensus-key";
+    impl SubCmd for QueryBondedStake {
+        const CMD: &'static str = "bonded-stake";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
+        fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxChangeConsensusKey(args::ConsensusKeyChange::parse(matches))
+                QueryBondedStake(args::QueryBondedStake::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Change consensus key.")
-                .add_args::<args::ConsensusKeyChange<args::CliTypes>>()
+                .about("Query PoS bonded stake.")
+                .add_args::<args::QueryBondedStake<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxVoteProposal(pub args::VoteProposal<args::CliTypes>);
+    pub struct SignTx(pub args::SignTx<args::CliTypes>);
 
-    impl SubCmd for TxVoteProposal {
-        const CMD: &'static str = "vote-proposal";
+    impl SubCmd for SignTx {
+        const CMD: &'static str = "sign-tx";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxVoteProposal(args::VoteProposal::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| SignTx(args::SignTx::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Vote a proposal.")
-                .add_args::<args::VoteProposal<args::CliTypes>>()
+                .about("Query PoS bonded stake.")
+                .add_args::<args::SignTx<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxRevealPk(pub args::RevealPk<args::CliTypes>);
+    pub struct QueryValidatorState(
+        pub args::QueryValidatorState<args::CliTypes>,
+    );
 
-    impl SubCmd for TxRevealPk {
-        const CMD: &'static str = "reveal-pk";
+    impl SubCmd for QueryValidatorState {
+        const CMD: &'static str = "validator-state";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| TxRevealPk(args::RevealPk::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryValidatorState(args::QueryValidatorState::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Submit a tx to reveal the public key an implicit \
-                     account. Typically, you don't have to do this manually \
-                     and the client will detect when a tx to reveal PK is \
-                     needed and submit it automatically. This will write the \
-                     PK into the account's storage so that it can be used for \
-                     signature verification on transactions authorized by \
-                     this account.",
-                )
-                .add_args::<args::RevealPk<args::CliTypes>>()
+                .about("Query the state of a PoS validator.")
+                .add_args::<args::QueryValidatorState<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct GenIbcShieldedTransafer(
-        pub args::GenIbcShieldedTransafer<args::CliTypes>,
-    );
+    pub struct QueryTransfers(pub args::QueryTransfers<args::CliTypes>);
 
-    impl SubCmd for GenIbcShieldedTransafer {
-        const CMD: &'static str = "ibc-gen-shielded";
+    impl SubCmd for QueryTransfers {
+        const CMD: &'static str = "show-transfers";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                GenIbcShieldedTransafer(args::GenIbcShieldedTransafer::parse(
-                    matches,
-                ))
+                QueryTransfers(args::QueryTransfers::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Generate shielded transfer for IBC.")
-                .add_args::<args::GenIbcShieldedTransafer<args::CliTypes>>()
+                .about("Query the accepted transfers to date.")
+                .add_args::<args::QueryTransfers<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct EpochSleep(pub args::Query<args::CliTypes>);
+    pub struct QueryCommissionRate(
+        pub args::QueryCommissionRate<args::CliTypes>,
+    );
 
-    impl SubCmd for EpochSleep {
-        const CMD: &'static str = "epoch-sleep";
+    impl SubCmd for QueryCommissionRate {
+        const CMD: &'static str = "commission-rate";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::Query::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryCommissionRate(args::QueryCommissionRate::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Query for the current epoch, then sleep until the next \
-                     epoch.",
-                )
-                .add_args::<args::Query<args::CliTypes>>()
+                .about("Query a validator's commission rate.")
+                .add_args::<args::QueryCommissionRate<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub enum Utils {
-        JoinNetwork(JoinNetwork),
-        FetchWasms(FetchWasms),
-        ValidateWasm(ValidateWasm),
-        InitNetwork(InitNetwork),
-        DeriveGenesisAddresses(DeriveGenesisAddresses),
-        GenesisBond(GenesisBond),
-        InitGenesisEstablishedAccount(InitGenesisEstablishedAccount),
-        InitGenesisValidator(InitGenesisValidator),
-        PkToTmAddress(PkToTmAddress),
-        DefaultBaseDir(DefaultBaseDir),
-        EpochSleep(EpochSleep),
-        ValidateGenesisTemplates(ValidateGenesisTemplates),
-        TestGenesis(TestGenesis),
-        SignGenesisTxs(SignGenesisTxs),
-    }
+    pub struct QueryMetaData(pub args::QueryMetaData<args::CliTypes>);
 
-    impl SubCmd for Utils {
-        const CMD: &'static str = "utils";
+    impl SubCmd for QueryMetaData {
+        const CMD: &'static str = "validator-metadata";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).and_then(|matches| {
-                let join_network =
-                    SubCmd::parse(matches).map(Self::JoinNetwork);
-                let fetch_wasms = SubCmd::parse(matches).map(Self::FetchWasms);
-                let validate_wasm =
-                    SubCmd::parse(matches).map(Self::ValidateWasm);
-                let init_network =
-                    SubCmd::parse(matches).map(Self::InitNetwork);
-                let derive_addresses =
-                    SubCmd::parse(matches).map(Self::DeriveGenesisAddresses);
-                let genesis_bond =
-                    SubCmd::parse(matches).map(Self::GenesisBond);
-                let init_established = SubCmd::parse(matches)
-                    .map(Self::InitGenesisEstablishedAccount);
-                let init_genesis =
-                    SubCmd::parse(matches).map(Self::InitGenesisValidator);
-                let pk_to_tm_address =
-                    SubCmd::parse(matches).map(Self::PkToTmAddress);
-                let default_base_dir =
-                    SubCmd::parse(matches).map(Self::DefaultBaseDir);
-                let epoch_sleep = SubCmd::parse(matches).map(Self::EpochSleep);
-                let validate_genesis_templates =
-                    SubCmd::parse(matches).map(Self::ValidateGenesisTemplates);
-                let genesis_tx =
-                    SubCmd::parse(matches).map(Self::SignGenesisTxs);
-                let test_genesis =
-                    SubCmd::parse(matches).map(Self::TestGenesis);
-                join_network
-                    .or(fetch_wasms)
-                    .or(validate_wasm)
-                    .or(init_network)
-                    .or(derive_addresses)
-                    .or(genesis_bond)
-                    .or(init_established)
-                    .or(init_genesis)
-                    .or(pk_to_tm_address)
-                    .or(default_base_dir)
-                    .or(epoch_sleep)
-                    .or(validate_genesis_templates)
-                    .or(test_genesis)
-                    .or(genesis_tx)
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryMetaData(args::QueryMetaData::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Utilities.")
-                .subcommand(JoinNetwork::def())
-                .subcommand(FetchWasms::def())
-                .subcommand(ValidateWasm::def())
-                .subcommand(InitNetwork::def())
-                .subcommand(DeriveGenesisAddresses::def())
-                .subcommand(GenesisBond::def())
-                .subcommand(InitGenesisEstablishedAccount::def())
-                .subcommand(InitGenesisValidator::def())
-                .subcommand(PkToTmAddress::def())
-                .subcommand(DefaultBaseDir::def())
-                .subcommand(EpochSleep::def())
-                .subcommand(ValidateGenesisTemplates::def())
-                .subcommand(TestGenesis::def())
-                .subcommand(SignGenesisTxs::def())
-                .subcommand_required(true)
-                .arg_required_else_help(true)
+                .about("Query a validator's metadata.")
+                .add_args::<args::QueryMetaData<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct JoinNetwork(pub args::JoinNetwork);
+    pub struct QuerySlashes(pub args::QuerySlashes<args::CliTypes>);
 
-    impl SubCmd for JoinNetwork {
-        const CMD: &'static str = "join-network";
+    impl SubCmd for QuerySlashes {
+        const CMD: &'static str = "slashes";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::JoinNetwork::parse(matches)))
+                .map(|matches| QuerySlashes(args::QuerySlashes::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Configure Namada to join an existing network.")
-                .add_args::<args::JoinNetwork>()
+                .about("Query PoS applied slashes.")
+                .add_args::<args::QuerySlashes<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct FetchWasms(pub args::FetchWasms);
+    pub struct QueryRewards(pub args::QueryRewards<args::CliTypes>);
 
-    impl SubCmd for FetchWasms {
-        const CMD: &'static str = "fetch-wasms";
+    impl SubCmd for QueryRewards {
+        const CMD: &'static str = "rewards";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::FetchWasms::parse(matches)))
+                .map(|matches| QueryRewards(args::QueryRewards::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Ensure pre-built wasms are present")
-                .add_args::<args::FetchWasms>()
+                .about(
+                    "Query the latest rewards available to claim for a given \
+                     delegation (or self-bond).",
+                )
+                .add_args::<args::QueryRewards<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ValidateWasm(pub args::ValidateWasm);
+    pub struct QueryDelegations(pub args::QueryDelegations<args::CliTypes>);
 
-    impl SubCmd for ValidateWasm {
-        const CMD: &'static str = "validate-wasm";
+    impl SubCmd for QueryDelegations {
+        const CMD: &'static str = "delegations";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::ValidateWasm::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryDelegations(args::QueryDelegations::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Check that the provided wasm code is valid by the Namada \
-                     standards.",
-                )
-                .add_args::<args::ValidateWasm>()
+                .about("Find PoS delegations from the given owner address.")
+                .add_args::<args::QueryDelegations<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct InitNetwork(pub args::InitNetwork);
+    pub struct QueryFindValidator(pub args::QueryFindValidator<args::CliTypes>);
 
-    impl SubCmd for InitNetwork {
-        const CMD: &'static str = "init-network";
+    impl SubCmd for QueryFindValidator {
+        const CMD: &'static str = "find-validator";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::InitNetwork::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryFindValidator(args::QueryFindValidator::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Initialize a new test network.")
-                .add_args::<args::InitNetwork>()
+                .about(
+                    "Find a PoS validator and its consensus key by its native \
+                     address or Tendermint address.",
+                )
+                .add_args::<args::QueryFindValidator<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct DeriveGenesisAddresses(pub args::DeriveGenesisAddresses);
+    pub struct QueryRawBytes(pub args::QueryRawBytes<args::CliTypes>);
 
-    impl SubCmd for DeriveGenesisAddresses {
-        const CMD: &'static str = "derive-genesis-addresses";
+    impl SubCmd for QueryRawBytes {
+        const CMD: &'static str = "query-bytes";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                Self(args::DeriveGenesisAddresses::parse(matches))
+                QueryRawBytes(args::QueryRawBytes::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Derive account addresses from a genesis txs toml file.")
-                .add_args::<args::DeriveGenesisAddresses>()
+                .about("Query the raw bytes of a given storage key")
+                .add_args::<args::QueryRawBytes<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct InitGenesisEstablishedAccount(
-        pub args::InitGenesisEstablishedAccount,
-    );
+    pub struct TxInitProposal(pub args::InitProposal<args::CliTypes>);
 
-    impl SubCmd for InitGenesisEstablishedAccount {
-        const CMD: &'static str = "init-genesis-established-account";
+    impl SubCmd for TxInitProposal {
+        const CMD: &'static str = "init-proposal";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                Self(args::InitGenesisEstablishedAccount::parse(matches))
+                TxInitProposal(args::InitProposal::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Initialize an established account available at genesis.",
-                )
-                .add_args::<args::InitGenesisEstablishedAccount>()
+                .about("Create a new proposal.")
+                .add_args::<args::InitProposal<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct GenesisBond(pub args::GenesisBond);
+    pub struct TxUpdateStewardCommission(
+        pub args::UpdateStewardCommission<args::CliTypes>,
+    );
 
-    impl SubCmd for GenesisBond {
-        const CMD: &'static str = "genesis-bond";
+    impl SubCmd for TxUpdateStewardCommission {
+        const CMD: &'static str = "update-steward-rewards";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::GenesisBond::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateStewardCommission(args::UpdateStewardCommission::parse(
+                    matches,
+                ))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Bond to a validator at pre-genesis.")
-                .add_args::<args::GenesisBond>()
+                .about("Update how steward commissions are split.")
+                .add_args::<args::UpdateStewardCommission<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct InitGenesisValidator(pub args::InitGenesisValidator);
+    pub struct TxResignSteward(pub args::ResignSteward<args::CliTypes>);
 
-    impl SubCmd for InitGenesisValidator {
-        const CMD: &'static str = "init-genesis-validator";
+    impl SubCmd for TxResignSteward {
+        const CMD: &'static str = "resign-steward";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::InitGenesisValidator::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxResignSteward(args::ResignSteward::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Initialize genesis validator's address, consensus key \
-                     and validator account key and use it in the ledger's \
-                     node. Appends validator creation and self-bond txs to a \
-                     .toml file containing an established account tx.",
-                )
-                .add_args::<args::InitGenesisValidator>()
+                .about("Craft a transaction to resign as a steward.")
+                .add_args::<args::ResignSteward<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ValidateGenesisTemplates(pub args::ValidateGenesisTemplates);
+    pub struct TxCommissionRateChange(
+        pub args::CommissionRateChange<args::CliTypes>,
+    );
 
-    impl SubCmd for ValidateGenesisTemplates {
-        const CMD: &'static str = "validate-genesis-templates";
+    impl SubCmd for TxCommissionRateChange {
+        const CMD: &'static str = "change-commission-rate";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                Self(args::ValidateGenesisTemplates::parse(matches))
+                TxCommissionRateChange(args::CommissionRateChange::parse(
+                    matches,
+                ))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Validate genesis templates.")
-                .add_args::<args::ValidateGenesisTemplates>()
+                .about("Change commission rate.")
+                .add_args::<args::CommissionRateChange<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TestGenesis(pub args::TestGenesis);
+    pub struct TxMetadataChange(pub args::MetaDataChange<args::CliTypes>);
 
-    impl SubCmd for TestGenesis {
-        const CMD: &'static str = "test-genesis";
+    impl SubCmd for TxMetadataChange {
+        const CMD: &'static str = "change-metadata";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::TestGenesis::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxMetadataChange(args::MetaDataChange::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Dry run genesis files and get a report on problems that \
-                     may be found.",
+                    "Change validator's metadata, including the commission \
+                     rate if desired.",
                 )
-                .add_args::<args::TestGenesis>()
+                .add_args::<args::MetaDataChange<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct SignGenesisTxs(pub args::SignGenesisTxs);
+    pub struct TxChangeConsensusKey(
+        pub args::ConsensusKeyChange<args::CliTypes>,
+    );
 
-    impl SubCmd for SignGenesisTxs {
-        const CMD: &'static str = "sign-genesis-txs";
+    impl SubCmd for TxChangeConsensusKey {
+        const CMD: &'static str = "change-consensus-key";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::SignGenesisTxs::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxChangeConsensusKey(args::ConsensusKeyChange::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Sign genesis transaction(s).")
-                .add_args::<args::SignGenesisTxs>()
+                .about("Change consensus key.")
+                .add_args::<args::ConsensusKeyChange<args::CliTypes>>()
         }
     }
 
-    /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
-    #[derive(Clone, Debug)]
-    pub enum EthBridgePool {
-        /// The [`super::Context`] provides access to the wallet and the
-        /// config. It will generate a new wallet and config, if they
-        /// don't exist.
-        WithContext(EthBridgePoolWithCtx),
-        /// Utils don't have [`super::Context`], only the global arguments.
-        WithoutContext(EthBridgePoolWithoutCtx),
-    }
-
-    /// Ethereum Bridge pool commands requiring [`super::Context`].
-    #[derive(Clone, Debug)]
-    pub enum EthBridgePoolWithCtx {
-        /// Get a recommendation on a batch of transfers
-        /// to relay.
-        RecommendBatch(RecommendBatch),
-    }
-
-    /// Ethereum Bridge pool commands not requiring [`super::Context`].
     #[derive(Clone, Debug)]
-    pub enum EthBridgePoolWithoutCtx {
-        /// Construct a proof that a set of transfers is in the pool.
-        /// This can be used to relay transfers across the
-        /// bridge to Ethereum.
-        ConstructProof(ConstructProof),
-        /// Construct and relay a Bridge pool proof to
-        /// Ethereum directly.
-        RelayProof(RelayProof),
-        /// Query the contents of the pool.
-        QueryPool(QueryEthBridgePool),
-        /// Query to provable contents of the pool.
-        QuerySigned(QuerySignedBridgePool),
-        /// Check the confirmation status of `TransferToEthereum`
-        /// events.
-        QueryRelays(QueryRelayProgress),
-    }
-
-    impl Cmd for EthBridgePool {
-        fn add_sub(app: App) -> App {
-            app.subcommand(RecommendBatch::def().display_order(1))
-                .subcommand(ConstructProof::def().display_order(1))
-                .subcommand(RelayProof::def().display_order(1))
-                .subcommand(QueryEthBridgePool::def().display_order(1))
-                .subcommand(QuerySignedBridgePool::def().display_order(1))
-                .subcommand(QueryRelayProgress::def().display_order(1))
-        }
-
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            use EthBridgePoolWithCtx::*;
-            use EthBridgePoolWithoutCtx::*;
-
-            let recommend = Self::parse_with_ctx(matches, RecommendBatch);
-            let construct_proof =
-                Self::parse_without_ctx(matches, ConstructProof);
-            let relay_proof = Self::parse_without_ctx(matches, RelayProof);
-            let query_pool = Self::parse_without_ctx(matches, QueryPool);
-            let query_signed = Self::parse_without_ctx(matches, QuerySigned);
-            let query_relays = Self::parse_without_ctx(matches, QueryRelays);
-
-            construct_proof
-                .or(recommend)
-                .or(relay_proof)
-                .or(query_pool)
-                .or(query_signed)
-                .or(query_relays)
-        }
-    }
-
-    impl EthBridgePool {
-        /// A helper method to parse sub cmds with context
-        fn parse_with_ctx<T: SubCmd>(
-            matches: &ArgMatches,
-            sub_to_self: impl Fn(T) -> EthBridgePoolWithCtx,
-        ) -> Option<Self> {
-            T::parse(matches).map(|sub| Self::WithContext(sub_to_self(sub)))
-        }
-
-        /// A helper method to parse sub cmds without context
-        fn parse_without_ctx<T: SubCmd>(
-            matches: &ArgMatches,
-            sub_to_self: impl Fn(T) -> EthBridgePoolWithoutCtx,
-        ) -> Option<Self> {
-            T::parse(matches).map(|sub| Self::WithoutContext(sub_to_self(sub)))
-        }
-    }
+    pub struct TxVoteProposal(pub args::VoteProposal<args::CliTypes>);
 
-    impl SubCmd for EthBridgePool {
-        const CMD: &'static str = "ethereum-bridge-pool";
+    impl SubCmd for TxVoteProposal {
+        const CMD: &'static str = "vote-proposal";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).and_then(Cmd::parse)
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxVoteProposal(args::VoteProposal::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Functionality for interacting with the Ethereum bridge \
-                     pool. This pool holds transfers waiting to be relayed to \
-                     Ethereum.",
-                )
-                .subcommand_required(true)
-                .subcommand(ConstructProof::def().display_order(1))
-                .subcommand(RecommendBatch::def().display_order(1))
-                .subcommand(RelayProof::def().display_order(1))
-                .subcommand(QueryEthBridgePool::def().display_order(1))
-                .subcommand(QuerySignedBridgePool::def().display_order(1))
-                .subcommand(QueryRelayProgress::def().display_order(1))
+                .about("Vote a proposal.")
+                .add_args::<args::VoteProposal<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct AddToEthBridgePool(pub args::EthereumBridgePool<args::CliTypes>);
+    pub struct TxRevealPk(pub args::RevealPk<args::CliTypes>);
 
-    impl SubCmd for AddToEthBridgePool {
-        const CMD: &'static str = "add-erc20-transfer";
+    impl SubCmd for TxRevealPk {
+        const CMD: &'static str = "reveal-pk";
 
-        fn parse(matches: &ArgMatches) -> Option<Self> {
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::EthereumBridgePool::parse(matches)))
+                .map(|matches| TxRevealPk(args::RevealPk::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Add a new transfer to the Ethereum Bridge pool.")
-                .arg_required_else_help(true)
-                .add_args::<args::EthereumBridgePool<args::CliTypes>>()
+                .about(
+                    "Submit a tx to reveal the public key an implicit \
+                     account. Typically, you don't have to do this manually \
+                     and the client will detect when a tx to reveal PK is \
+                     needed and submit it automatically. This will write the \
+                     PK into the account's storage so that it can be used for \
+                     signature verification on transactions authorized by \
+                     this account.",
+                )
+                .add_args::<args::RevealPk<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
+    pub struct GenIbcShieldedTransafer(
+        pub args::GenIbcShieldedTransafer<args::CliTypes>,
+    );
 
-    impl SubCmd for ConstructProof {
-        const CMD: &'static str = "construct-proof";
+    impl SubCmd for GenIbcShieldedTransafer {
+        const CMD: &'static str = "ibc-gen-shielded";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::BridgePoolProof::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                GenIbcShieldedTransafer(args::GenIbcShieldedTransafer::parse(
+                    matches,
+                ))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Construct a merkle proof that the given transfers are in \
-                     the pool.",
-                )
-                .arg_required_else_help(true)
-                .add_args::<args::BridgePoolProof<args::CliTypes>>()
+                .about("Generate shielded transfer for IBC.")
+                .add_args::<args::GenIbcShieldedTransafer<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct RelayProof(pub args::RelayBridgePoolProof<args::CliTypes>);
+    pub struct EpochSleep(pub args::Query<args::CliTypes>);
 
-    impl SubCmd for RelayProof {
-        const CMD: &'static str = "relay-proof";
+    impl SubCmd for EpochSleep {
+        const CMD: &'static str = "epoch-sleep";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::RelayBridgePoolProof::parse(matches)))
+                .map(|matches| Self(args::Query::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Construct a merkle proof that the given transfers are in \
-                     the pool and relay it to Ethereum.",
+                    "Query for the current epoch, then sleep until the next \
+                     epoch.",
                 )
-                .arg_required_else_help(true)
-                .add_args::<args::RelayBridgePoolProof<args::CliTypes>>()
+                .add_args::<args::Query<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct RecommendBatch(pub args::RecommendBatch<args::CliTypes>);
+    pub enum Utils {
+        JoinNetwork(JoinNetwork),
+        FetchWasms(FetchWasms),
+        ValidateWasm(ValidateWasm),
+        InitNetwork(InitNetwork),
+        DeriveGenesisAddresses(DeriveGenesisAddresses),
+        GenesisBond(GenesisBond),
+        InitGenesisEstablishedAccount(InitGenesisEstablishedAccount),
+        InitGenesisValidator(InitGenesisValidator),
+        PkToTmAddress(PkToTmAddress),
+        DefaultBaseDir(DefaultBaseDir),
+        EpochSleep(EpochSleep),
+        ValidateGenesisTemplates(ValidateGenesisTemplates),
+        TestGenesis(TestGenesis),
+        SignGenesisTxs(SignGenesisTxs),
+    }
 
-    impl SubCmd for RecommendBatch {
-        const CMD: &'static str = "recommend-batch";
+    impl SubCmd for Utils {
+        const CMD: &'static str = "utils";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::RecommendBatch::parse(matches)))
-        }
-
-        fn def() -> App {
-            App::new(Self::CMD)
-                .about(
-                    "Get a recommended batch of transfers from the bridge \
-                     pool to relay to Ethereum.",
-                )
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let join_network =
+                    SubCmd::parse(matches).map(Self::JoinNetwork);
+                let fetch_wasms = SubCmd::parse(matches).map(Self::FetchWasms);
+                let validate_wasm =
+                    SubCmd::parse(matches).map(Self::ValidateWasm);
+                let init_network =
+                    SubCmd::parse(matches).map(Self::InitNetwork);
+                let derive_addresses =
+                    SubCmd::parse(matches).map(Self::DeriveGenesisAddresses);
+                let genesis_bond =
+                    SubCmd::parse(matches).map(Self::GenesisBond);
+                let init_established = SubCmd::parse(matches)
+                    .map(Self::InitGenesisEstablishedAccount);
+                let init_genesis =
+                    SubCmd::parse(matches).map(Self::InitGenesisValidator);
+                let pk_to_tm_address =
+                    SubCmd::parse(matches).map(Self::PkToTmAddress);
+                let default_base_dir =
+                    SubCmd::parse(matches).map(Self::DefaultBaseDir);
+                let epoch_sleep = SubCmd::parse(matches).map(Self::EpochSleep);
+                let validate_genesis_templates =
+                    SubCmd::parse(matches).map(Self::ValidateGenesisTemplates);
+                let genesis_tx =
+                    SubCmd::parse(matches).map(Self::SignGenesisTxs);
+                let test_genesis =
+                    SubCmd::parse(matches).map(Self::TestGenesis);
+                join_network
+                    .or(fetch_wasms)
+                    .or(validate_wasm)
+                    .or(init_network)
+                    .or(derive_addresses)
+                    .or(genesis_bond)
+                    .or(init_established)
+                    .or(init_genesis)
+                    .or(pk_to_tm_address)
+                    .or(default_base_dir)
+                    .or(epoch_sleep)
+                    .or(validate_genesis_templates)
+                    .or(test_genesis)
+                    .or(genesis_tx)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Utilities.")
+                .subcommand(JoinNetwork::def())
+                .subcommand(FetchWasms::def())
+                .subcommand(ValidateWasm::def())
+                .subcommand(InitNetwork::def())
+                .subcommand(DeriveGenesisAddresses::def())
+                .subcommand(GenesisBond::def())
+                .subcommand(InitGenesisEstablishedAccount::def())
+                .subcommand(InitGenesisValidator::def())
+                .subcommand(PkToTmAddress::def())
+                .subcommand(DefaultBaseDir::def())
+                .subcommand(EpochSleep::def())
+                .subcommand(ValidateGenesisTemplates::def())
+                .subcommand(TestGenesis::def())
+                .subcommand(SignGenesisTxs::def())
+                .subcommand_required(true)
                 .arg_required_else_help(true)
-                .add_args::<args::RecommendBatch<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryEthBridgePool(pub args::QueryWithoutCtx<args::CliTypes>);
+    pub struct JoinNetwork(pub args::JoinNetwork);
 
-    impl SubCmd for QueryEthBridgePool {
-        const CMD: &'static str = "query";
+    impl SubCmd for JoinNetwork {
+        const CMD: &'static str = "join-network";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+                .map(|matches| Self(args::JoinNetwork::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Get the contents of the Ethereum Bridge pool.")
-                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+                .about("Configure Namada to join an existing network.")
+                .add_args::<args::JoinNetwork>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QuerySignedBridgePool(pub args::QueryWithoutCtx<args::CliTypes>);
+    pub struct FetchWasms(pub args::FetchWasms);
 
-    impl SubCmd for QuerySignedBridgePool {
-        const CMD: &'static str = "query-signed";
+    impl SubCmd for FetchWasms {
+        const CMD: &'static str = "fetch-wasms";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+                .map(|matches| Self(args::FetchWasms::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Get the contents of the Ethereum Bridge pool with a \
-                     signed Merkle root.",
-                )
-                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+                .about("Ensure pre-built wasms are present")
+                .add_args::<args::FetchWasms>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryRelayProgress(pub args::QueryWithoutCtx<args::CliTypes>);
+    pub struct ValidateWasm(pub args::ValidateWasm);
 
-    impl SubCmd for QueryRelayProgress {
-        const CMD: &'static str = "query-relayed";
+    impl SubCmd for ValidateWasm {
+        const CMD: &'static str = "validate-wasm";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+                .map(|matches| Self(args::ValidateWasm::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Get the confirmation status of transfers to Ethereum.")
-                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+                .about(
+                    "Check that the provided wasm code is valid by the Namada \
+                     standards.",
+                )
+                .add_args::<args::ValidateWasm>()
         }
     }
 
-    /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
     #[derive(Clone, Debug)]
-    pub enum ValidatorSet {
-        /// Query the Bridge validator set in Namada, at the given epoch,
-        /// or the latest one, if none is provided.
-        BridgeValidatorSet(BridgeValidatorSet),
-        /// Query the Governance validator set in Namada, at the given epoch,
-        /// or the latest one, if none is provided.
-        GovernanceValidatorSet(GovernanceValidatorSet),
-        /// Query an Ethereum ABI encoding of a proof of the consensus
-        /// validator set in Namada, at the given epoch, or the next
-        /// one, if none is provided.
-        ValidatorSetProof(ValidatorSetProof),
-        /// Relay a validator set update to Namada's Ethereum bridge
-        /// smart contracts.
-        ValidatorSetUpdateRelay(ValidatorSetUpdateRelay),
-    }
+    pub struct InitNetwork(pub args::InitNetwork);
 
-    impl SubCmd for ValidatorSet {
-        const CMD: &'static str = "validator-set";
+    impl SubCmd for InitNetwork {
+        const CMD: &'static str = "init-network";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).and_then(|matches| {
-                let bridge_validator_set = BridgeValidatorSet::parse(matches)
-                    .map(Self::BridgeValidatorSet);
-                let governance_validator_set =
-                    GovernanceValidatorSet::parse(matches)
-                        .map(Self::GovernanceValidatorSet);
-                let validator_set_proof = ValidatorSetProof::parse(matches)
-                    .map(Self::ValidatorSetProof);
-                let relay = ValidatorSetUpdateRelay::parse(matches)
-                    .map(Self::ValidatorSetUpdateRelay);
-                bridge_validator_set
-                    .or(governance_validator_set)
-                    .or(validator_set_proof)
-                    .or(relay)
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::InitNetwork::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Validator set queries, that return data in a format to \
-                     be consumed by the Namada Ethereum bridge smart \
-                     contracts.",
-                )
-                .subcommand_required(true)
-                .subcommand(BridgeValidatorSet::def().display_order(1))
-                .subcommand(GovernanceValidatorSet::def().display_order(1))
-                .subcommand(ValidatorSetProof::def().display_order(1))
-                .subcommand(ValidatorSetUpdateRelay::def().display_order(1))
+                .about("Initialize a new test network.")
+                .add_args::<args::InitNetwork>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct BridgeValidatorSet(pub args::BridgeValidatorSet<args::CliTypes>);
+    pub struct DeriveGenesisAddresses(pub args::DeriveGenesisAddresses);
 
-    impl SubCmd for BridgeValidatorSet {
-        const CMD: &'static str = "bridge";
+    impl SubCmd for DeriveGenesisAddresses {
+        const CMD: &'static str = "derive-genesis-addresses";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::BridgeValidatorSet::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::DeriveGenesisAddresses::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Query the Bridge validator set in Namada, at the given \
-                     epoch, or the latest one, if none is provided.",
-                )
-                .add_args::<args::BridgeValidatorSet<args::CliTypes>>()
+                .about("Derive account addresses from a genesis txs toml file.")
+                .add_args::<args::DeriveGenesisAddresses>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct GovernanceValidatorSet(
-        pub args::GovernanceValidatorSet<args::CliTypes>,
+    pub struct InitGenesisEstablishedAccount(
+        pub args::InitGenesisEstablishedAccount,
     );
 
-    impl SubCmd for GovernanceValidatorSet {
-        const CMD: &'static str = "governance";
+    impl SubCmd for InitGenesisEstablishedAccount {
+        const CMD: &'static str = "init-genesis-established-account";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                Self(args::GovernanceValidatorSet::parse(matches))
+                Self(args::InitGenesisEstablishedAccount::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Query the Governance validator set in Namada, at the \
-                     given epoch, or the latest one, if none is provided.",
+                    "Initialize an established account available at genesis.",
                 )
-                .add_args::<args::GovernanceValidatorSet<args::CliTypes>>()
+                .add_args::<args::InitGenesisEstablishedAccount>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ValidatorSetProof(pub args::ValidatorSetProof<args::CliTypes>);
+    pub struct GenesisBond(pub args::GenesisBond);
 
-    impl SubCmd for ValidatorSetProof {
-        const CMD: &'static str = "proof";
+    impl SubCmd for GenesisBond {
+        const CMD: &'static str = "genesis-bond";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::ValidatorSetProof::parse(matches)))
+                .map(|matches| Self(args::GenesisBond::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Query an Ethereum ABI encoding of a proof of the \
-                     consensus validator set in Namada, at the requested \
-                     epoch, or the next one, if no epoch is provided.",
-                )
-                .add_args::<args::ValidatorSetProof<args::CliTypes>>()
+                .about("Bond to a validator at pre-genesis.")
+                .add_args::<args::GenesisBond>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct ValidatorSetUpdateRelay(
-        pub args::ValidatorSetUpdateRelay<args::CliTypes>,
-    );
+    pub struct InitGenesisValidator(pub args::InitGenesisValidator);
 
-    impl SubCmd for ValidatorSetUpdateRelay {
-        const CMD: &'static str = "relay";
+    impl SubCmd for InitGenesisValidator {
+        const CMD: &'static str = "init-genesis-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                Self(args::ValidatorSetUpdateRelay::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::InitGenesisValidator::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Relay a validator set update to Namada's Ethereum bridge \
-                     smart contracts.",
+                    "Initialize genesis validator's address, consensus key \
+                     and validator account key and use it in the ledger's \
+                     node. Appends validator creation and self-bond txs to a \
+                     .toml file containing an established account tx.",
                 )
-                .add_args::<args::ValidatorSetUpdateRelay<args::CliTypes>>()
+                .add_args::<args::InitGenesisValidator>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct PkToTmAddress(pub args::PkToTmAddress);
+    pub struct ValidateGenesisTemplates(pub args::ValidateGenesisTemplates);
 
-    impl SubCmd for PkToTmAddress {
-        const CMD: &'static str = "pk-to-tm";
+    impl SubCmd for ValidateGenesisTemplates {
+        const CMD: &'static str = "validate-genesis-templates";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::PkToTmAddress::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::ValidateGenesisTemplates::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about(
-                    "Convert a validator's consensus public key to a \
-                     Tendermint address.",
-                )
-                .add_args::<args::PkToTmAddress>()
+                .about("Validate genesis templates.")
+                .add_args::<args::ValidateGenesisTemplates>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct DefaultBaseDir(pub args::DefaultBaseDir);
+    pub struct TestGenesis(pub args::TestGenesis);
 
-    impl SubCmd for DefaultBaseDir {
-        const CMD: &'static str = "default-base-dir";
+    impl SubCmd for TestGenesis {
+        const CMD: &'static str = "test-genesis";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::DefaultBaseDir::parse(matches)))
+                .map(|matches| Self(args::TestGenesis::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Print the default base directory that would be used if \
-                     --base-dir or NAMADA_BASE_DIR were not used to set the \
-                     base directory.",
+                    "Dry run genesis files and get a report on problems that \
+                     may be found.",
                 )
-                .add_args::<args::DefaultBaseDir>()
+                .add_args::<args::TestGenesis>()
         }
     }
-}
 
-pub mod args {
-    use std::collections::HashMap;
-    use std::convert::TryFrom;
-    use std::env;
-    use std::net::SocketAddr;
-    use std::path::PathBuf;
-    use std::str::FromStr;
+    #[derive(Clone, Debug)]
+    pub struct SignGenesisTxs(pub args::SignGenesisTxs);
 
-    use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
-    use namada::tx::data::GasLimit;
-    use namada::types::address::{Address, EstablishedAddress};
-    use namada::types::chain::{ChainId, ChainIdPrefix};
-    use namada::types::dec::Dec;
-    use namada::types::ethereum_events::EthAddress;
-    use namada::types::keccak::KeccakHash;
-    use namada::types::key::*;
-    use namada::types::masp::PaymentAddress;
-    use namada::types::storage::{self, BlockHeight, Epoch};
-    use namada::types::time::DateTimeUtc;
-    use namada::types::token;
-    use namada::types::token::NATIVE_MAX_DECIMAL_PLACES;
-    pub use namada_sdk::args::*;
-    pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-        TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-        VP_USER_WASM,
-    };
+    impl SubCmd for SignGenesisTxs {
+        const CMD: &'static str = "sign-genesis-txs";
 
-    use super::context::*;
-    use super::utils::*;
-    use super::{ArgGroup, ArgMatches};
-    use crate::client::utils::PRE_GENESIS_DIR;
-    use crate::config::genesis::GenesisAddress;
-    use crate::config::{self, Action, ActionAtHeight};
-    use crate::facade::tendermint::Timeout;
-    use crate::facade::tendermint_config::net::Address as TendermintAddress;
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::SignGenesisTxs::parse(matches)))
+        }
 
-    pub const ADDRESS: Arg<WalletAddress> = arg("address");
-    pub const ALIAS_OPT: ArgOpt<String> = ALIAS.opt();
-    pub const ALIAS: Arg<String> = arg("alias");
-    pub const ALIAS_FORCE: ArgFlag = flag("alias-force");
-    pub const ALIAS_MANY: ArgMulti<String, GlobPlus> = arg_multi("aliases");
-    pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
-    pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
-    pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
-    pub const AVATAR_OPT: ArgOpt<String> = arg_opt("avatar");
-    pub const BALANCE_OWNER: ArgOpt<WalletBalanceOwner> = arg_opt("owner");
-    pub const BASE_DIR: ArgDefault<PathBuf> = arg_default(
-        "base-dir",
-        DefaultFn(|| match env::var("NAMADA_BASE_DIR") {
-            Ok(dir) => PathBuf::from(dir),
-            Err(_) => config::get_default_namada_folder(),
-        }),
-    );
-    pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
-    pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
-    pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
-        arg_default(
-            "pool-gas-amount",
-            DefaultFn(|| {
-                token::DenominatedAmount::new(
-                    token::Amount::zero(),
-                    NATIVE_MAX_DECIMAL_PLACES.into(),
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Sign genesis transaction(s).")
+                .add_args::<args::SignGenesisTxs>()
+        }
+    }
+
+    /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
+    #[derive(Clone, Debug)]
+    pub enum EthBridgePool {
+        /// The [`super::Context`] provides access to the wallet and the
+        /// config. It will generate a new wallet and config, if they
+        /// don't exist.
+        WithContext(EthBridgePoolWithCtx),
+        /// Utils don't have [`super::Context`], only the global arguments.
+        WithoutContext(EthBridgePoolWithoutCtx),
+    }
+
+    /// Ethereum Bridge pool commands requiring [`super::Context`].
+    #[derive(Clone, Debug)]
+    pub enum EthBridgePoolWithCtx {
+        /// Get a recommendation on a batch of transfers
+        /// to relay.
+        RecommendBatch(RecommendBatch),
+    }
+
+    /// Ethereum Bridge pool commands not requiring [`super::Context`].
+    #[derive(Clone, Debug)]
+    pub enum EthBridgePoolWithoutCtx {
+        /// Construct a proof that a set of transfers is in the pool.
+        /// This can be used to relay transfers across the
+        /// bridge to Ethereum.
+        ConstructProof(ConstructProof),
+        /// Construct and relay a Bridge pool proof to
+        /// Ethereum directly.
+        RelayProof(RelayProof),
+        /// Query the contents of the pool.
+        QueryPool(QueryEthBridgePool),
+        /// Query to provable contents of the pool.
+        QuerySigned(QuerySignedBridgePool),
+        /// Check the confirmation status of `TransferToEthereum`
+        /// events.
+        QueryRelays(QueryRelayProgress),
+    }
+
+    impl Cmd for EthBridgePool {
+        fn add_sub(app: App) -> App {
+            app.subcommand(RecommendBatch::def().display_order(1))
+                .subcommand(ConstructProof::def().display_order(1))
+                .subcommand(RelayProof::def().display_order(1))
+                .subcommand(QueryEthBridgePool::def().display_order(1))
+                .subcommand(QuerySignedBridgePool::def().display_order(1))
+                .subcommand(QueryRelayProgress::def().display_order(1))
+        }
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            use EthBridgePoolWithCtx::*;
+            use EthBridgePoolWithoutCtx::*;
+
+            let recommend = Self::parse_with_ctx(matches, RecommendBatch);
+            let construct_proof =
+                Self::parse_without_ctx(matches, ConstructProof);
+            let relay_proof = Self::parse_without_ctx(matches, RelayProof);
+            let query_pool = Self::parse_without_ctx(matches, QueryPool);
+            let query_signed = Self::parse_without_ctx(matches, QuerySigned);
+            let query_relays = Self::parse_without_ctx(matches, QueryRelays);
+
+            construct_proof
+                .or(recommend)
+                .or(relay_proof)
+                .or(query_pool)
+                .or(query_signed)
+                .or(query_relays)
+        }
+    }
+
+    impl EthBridgePool {
+        /// A helper method to parse sub cmds with context
+        fn parse_with_ctx<T: SubCmd>(
+            matches: &ArgMatches,
+            sub_to_self: impl Fn(T) -> EthBridgePoolWithCtx,
+        ) -> Option<Self> {
+            T::parse(matches).map(|sub| Self::WithContext(sub_to_self(sub)))
+        }
+
+        /// A helper method to parse sub cmds without context
+        fn parse_without_ctx<T: SubCmd>(
+            matches: &ArgMatches,
+            sub_to_self: impl Fn(T) -> EthBridgePoolWithoutCtx,
+        ) -> Option<Self> {
+            T::parse(matches).map(|sub| Self::WithoutContext(sub_to_self(sub)))
+        }
+    }
+
+    impl SubCmd for EthBridgePool {
+        const CMD: &'static str = "ethereum-bridge-pool";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(Cmd::parse)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Functionality for interacting with the Ethereum bridge \
+                     pool. This pool holds transfers waiting to be relayed to \
+                     Ethereum.",
                 )
-            }),
-        );
-    pub const BRIDGE_POOL_GAS_PAYER: ArgOpt<WalletAddress> =
-        arg_opt("pool-gas-payer");
-    pub const BRIDGE_POOL_GAS_TOKEN: ArgDefaultFromCtx<
-        WalletAddrOrNativeToken,
-    > = arg_default_from_ctx(
-        "pool-gas-token",
-        DefaultFn(|| "".parse().unwrap()),
-    );
-    pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
-    pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
-    pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
-    pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
-    pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
-    pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
-    pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
-    pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
-    pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
-    pub const COMMISSION_RATE_OPT: ArgOpt<Dec> = COMMISSION_RATE.opt();
-    pub const CONSENSUS_TIMEOUT_COMMIT: ArgDefault<Timeout> = arg_default(
-        "consensus-timeout-commit",
-        DefaultFn(|| Timeout::from_str("1s").unwrap()),
-    );
-    pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
-    pub const DAEMON_MODE: ArgFlag = flag("daemon");
-    pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
-    pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
-        arg_opt("success-sleep");
-    pub const DATA_PATH_OPT: ArgOpt<PathBuf> = arg_opt("data-path");
-    pub const DATA_PATH: Arg<PathBuf> = arg("data-path");
-    pub const DECRYPT: ArgFlag = flag("decrypt");
-    pub const DESCRIPTION_OPT: ArgOpt<String> = arg_opt("description");
-    pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
-    pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
-        arg("destination-validator");
-    pub const DISCORD_OPT: ArgOpt<String> = arg_opt("discord-handle");
-    pub const DO_IT: ArgFlag = flag("do-it");
-    pub const DONT_ARCHIVE: ArgFlag = flag("dont-archive");
-    pub const DONT_PREFETCH_WASM: ArgFlag = flag("dont-prefetch-wasm");
-    pub const DRY_RUN_TX: ArgFlag = flag("dry-run");
-    pub const DRY_RUN_WRAPPER_TX: ArgFlag = flag("dry-run-wrapper");
-    pub const DUMP_TX: ArgFlag = flag("dump-tx");
-    pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
-    pub const ERC20: Arg<EthAddress> = arg("erc20");
-    pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
-    pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
-    pub const ETH_GAS_PRICE: ArgOpt<u64> = arg_opt("eth-gas-price");
-    pub const ETH_ADDRESS: Arg<EthAddress> = arg("ethereum-address");
-    pub const ETH_ADDRESS_OPT: ArgOpt<EthAddress> = ETH_ADDRESS.opt();
-    pub const ETH_RPC_ENDPOINT: ArgDefault<String> = arg_default(
-        "eth-rpc-endpoint",
-        DefaultFn(|| "http://localhost:8545".into()),
-    );
-    pub const ETH_SYNC: ArgFlag = flag("sync");
-    pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
-    pub const EMAIL: Arg<String> = arg("email");
-    pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
-    pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
-        arg_opt("gas-spending-key");
-    pub const FEE_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
-        arg_opt("gas-price");
-    pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
-    pub const FILE_PATH: Arg<String> = arg("file");
-    pub const FORCE: ArgFlag = flag("force");
-    pub const GAS_LIMIT: ArgDefault<GasLimit> =
-        arg_default("gas-limit", DefaultFn(|| GasLimit::from(25_000)));
-    pub const FEE_TOKEN: ArgDefaultFromCtx<WalletAddrOrNativeToken> =
-        arg_default_from_ctx("gas-token", DefaultFn(|| "".parse().unwrap()));
-    pub const FEE_PAYER: Arg<WalletAddress> = arg("fee-payer");
-    pub const FEE_AMOUNT: ArgDefault<token::DenominatedAmount> = arg_default(
-        "fee-amount",
-        DefaultFn(|| {
-            token::DenominatedAmount::new(
-                token::Amount::default(),
-                NATIVE_MAX_DECIMAL_PLACES.into(),
-            )
-        }),
+                .subcommand_required(true)
+                .subcommand(ConstructProof::def().display_order(1))
+                .subcommand(RecommendBatch::def().display_order(1))
+                .subcommand(RelayProof::def().display_order(1))
+                .subcommand(QueryEthBridgePool::def().display_order(1))
+                .subcommand(QuerySignedBridgePool::def().display_order(1))
+                .subcommand(QueryRelayProgress::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct AddToEthBridgePool(pub args::EthereumBridgePool<args::CliTypes>);
+
+    impl SubCmd for AddToEthBridgePool {
+        const CMD: &'static str = "add-erc20-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::EthereumBridgePool::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Add a new transfer to the Ethereum Bridge pool.")
+                .arg_required_else_help(true)
+                .add_args::<args::EthereumBridgePool<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
+
+    impl SubCmd for ConstructProof {
+        const CMD: &'static str = "construct-proof";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::BridgePoolProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Construct a merkle proof that the given transfers are in \
+                     the pool.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::BridgePoolProof<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct RelayProof(pub args::RelayBridgePoolProof<args::CliTypes>);
+
+    impl SubCmd for RelayProof {
+        const CMD: &'static str = "relay-proof";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::RelayBridgePoolProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Construct a merkle proof that the given transfers are in \
+                     the pool and relay it to Ethereum.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::RelayBridgePoolProof<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct RecommendBatch(pub args::RecommendBatch<args::CliTypes>);
+
+    impl SubCmd for RecommendBatch {
+        const CMD: &'static str = "recommend-batch";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::RecommendBatch::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Get a recommended batch of transfers from the bridge \
+                     pool to relay to Ethereum.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::RecommendBatch<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryEthBridgePool(pub args::QueryWithoutCtx<args::CliTypes>);
+
+    impl SubCmd for QueryEthBridgePool {
+        const CMD: &'static str = "query";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Get the contents of the Ethereum Bridge pool.")
+                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QuerySignedBridgePool(pub args::QueryWithoutCtx<args::CliTypes>);
+
+    impl SubCmd for QuerySignedBridgePool {
+        const CMD: &'static str = "query-signed";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Get the contents of the Ethereum Bridge pool with a \
+                     signed Merkle root.",
+                )
+                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryRelayProgress(pub args::QueryWithoutCtx<args::CliTypes>);
+
+    impl SubCmd for QueryRelayProgress {
+        const CMD: &'static str = "query-relayed";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Get the confirmation status of transfers to Ethereum.")
+                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+        }
+    }
+
+    /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
+    #[derive(Clone, Debug)]
+    pub enum ValidatorSet {
+        /// Query the Bridge validator set in Namada, at the given epoch,
+        /// or the latest one, if none is provided.
+        BridgeValidatorSet(BridgeValidatorSet),
+        /// Query the Governance validator set in Namada, at the given epoch,
+        /// or the latest one, if none is provided.
+        GovernanceValidatorSet(GovernanceValidatorSet),
+        /// Query an Ethereum ABI encoding of a proof of the consensus
+        /// validator set in Namada, at the given epoch, or the next
+        /// one, if none is provided.
+        ValidatorSetProof(ValidatorSetProof),
+        /// Relay a validator set update to Namada's Ethereum bridge
+        /// smart contracts.
+        ValidatorSetUpdateRelay(ValidatorSetUpdateRelay),
+    }
+
+    impl SubCmd for ValidatorSet {
+        const CMD: &'static str = "validator-set";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let bridge_validator_set = BridgeValidatorSet::parse(matches)
+                    .map(Self::BridgeValidatorSet);
+                let governance_validator_set =
+                    GovernanceValidatorSet::parse(matches)
+                        .map(Self::GovernanceValidatorSet);
+                let validator_set_proof = ValidatorSetProof::parse(matches)
+                    .map(Self::ValidatorSetProof);
+                let relay = ValidatorSetUpdateRelay::parse(matches)
+                    .map(Self::ValidatorSetUpdateRelay);
+                bridge_validator_set
+                    .or(governance_validator_set)
+                    .or(validator_set_proof)
+                    .or(relay)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Validator set queries, that return data in a format to \
+                     be consumed by the Namada Ethereum bridge smart \
+                     contracts.",
+                )
+                .subcommand_required(true)
+                .subcommand(BridgeValidatorSet::def().display_order(1))
+                .subcommand(GovernanceValidatorSet::def().display_order(1))
+                .subcommand(ValidatorSetProof::def().display_order(1))
+                .subcommand(ValidatorSetUpdateRelay::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct BridgeValidatorSet(pub args::BridgeValidatorSet<args::CliTypes>);
+
+    impl SubCmd for BridgeValidatorSet {
+        const CMD: &'static str = "bridge";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::BridgeValidatorSet::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the Bridge validator set in Namada, at the given \
+                     epoch, or the latest one, if none is provided.",
+                )
+                .add_args::<args::BridgeValidatorSet<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GovernanceValidatorSet(
+        pub args::GovernanceValidatorSet<args::CliTypes>,
     );
-    pub const GENESIS_BOND_SOURCE: ArgOpt<GenesisAddress> = arg_opt("source");
-    pub const GENESIS_PATH: Arg<PathBuf> = arg("genesis-path");
-    pub const GENESIS_TIME: Arg<DateTimeUtc> = arg("genesis-time");
-    pub const GENESIS_VALIDATOR: ArgOpt<String> =
-        arg("genesis-validator").opt();
-    pub const GENESIS_VALIDATOR_ADDRESS: Arg<EstablishedAddress> =
-        arg("validator");
-    pub const HALT_ACTION: ArgFlag = flag("halt");
-    pub const HASH_LIST: Arg<String> = arg("hash-list");
-    pub const HD_DERIVATION_PATH: ArgDefault<String> =
-        arg_default("hd-path", DefaultFn(|| "default".to_string()));
-    pub const HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH: ArgFlag =
-        flag("allow-non-compliant");
-    pub const HD_PROMPT_BIP39_PASSPHRASE: ArgFlag = flag("bip39-passphrase");
-    pub const HISTORIC: ArgFlag = flag("historic");
-    pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
-    pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
-    pub const LEDGER_ADDRESS_ABOUT: &str =
-        "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
-         scheme is not supplied, it is assumed to be TCP.";
-    pub const CONFIG_RPC_LEDGER_ADDRESS: ArgDefaultFromCtx<ConfigRpcAddress> =
-        arg_default_from_ctx("node", DefaultFn(|| "".to_string()));
-    pub const LEDGER_ADDRESS: ArgDefault<TendermintAddress> = arg("node")
-        .default(DefaultFn(|| {
-            let raw = "127.0.0.1:26657";
-            TendermintAddress::from_str(raw).unwrap()
-        }));
-    pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
-    pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
-    pub const LOCALHOST: ArgFlag = flag("localhost");
-    pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
-        arg("max-commission-rate-change");
-    pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
-    pub const MEMO_OPT: ArgOpt<String> = arg_opt("memo");
-    pub const MODE: ArgOpt<String> = arg_opt("mode");
-    pub const NET_ADDRESS: Arg<SocketAddr> = arg("net-address");
-    pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
-    pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
-    pub const NUT: ArgFlag = flag("nut");
-    pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
-    pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
-    pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
-        arg_opt("output-folder-path");
-    pub const OWNER: Arg<WalletAddress> = arg("owner");
-    pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
-    pub const PATH: Arg<PathBuf> = arg("path");
-    pub const PIN: ArgFlag = flag("pin");
-    pub const PORT_ID: ArgDefault<PortId> = arg_default(
-        "port-id",
-        DefaultFn(|| PortId::from_str("transfer").unwrap()),
+
+    impl SubCmd for GovernanceValidatorSet {
+        const CMD: &'static str = "governance";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::GovernanceValidatorSet::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the Governance validator set in Namada, at the \
+                     given epoch, or the latest one, if none is provided.",
+                )
+                .add_args::<args::GovernanceValidatorSet<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ValidatorSetProof(pub args::ValidatorSetProof<args::CliTypes>);
+
+    impl SubCmd for ValidatorSetProof {
+        const CMD: &'static str = "proof";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ValidatorSetProof::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query an Ethereum ABI encoding of a proof of the \
+                     consensus validator set in Namada, at the requested \
+                     epoch, or the next one, if no epoch is provided.",
+                )
+                .add_args::<args::ValidatorSetProof<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ValidatorSetUpdateRelay(
+        pub args::ValidatorSetUpdateRelay<args::CliTypes>,
     );
-    pub const PRE_GENESIS: ArgFlag = flag("pre-genesis");
-    pub const PROPOSAL_ETH: ArgFlag = flag("eth");
-    pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
-    pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
-    pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
-    pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
-    pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
-    pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
-    pub const PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
-        arg_multi("public-keys");
-    pub const PROPOSAL_ID: Arg<u64> = arg("proposal-id");
-    pub const PROPOSAL_ID_OPT: ArgOpt<u64> = arg_opt("proposal-id");
-    pub const PROPOSAL_VOTE_PGF_OPT: ArgOpt<String> = arg_opt("pgf");
-    pub const PROPOSAL_VOTE_ETH_OPT: ArgOpt<String> = arg_opt("eth");
-    pub const PROPOSAL_VOTE: Arg<String> = arg("vote");
-    pub const RAW_ADDRESS: Arg<Address> = arg("address");
-    pub const RAW_ADDRESS_ESTABLISHED: Arg<EstablishedAddress> = arg("address");
-    pub const RAW_ADDRESS_OPT: ArgOpt<Address> = RAW_ADDRESS.opt();
-    pub const RAW_KEY_GEN: ArgFlag = flag("raw");
-    pub const RAW_PAYMENT_ADDRESS: Arg<PaymentAddress> = arg("payment-address");
-    pub const RAW_PAYMENT_ADDRESS_OPT: ArgOpt<PaymentAddress> =
-        RAW_PAYMENT_ADDRESS.opt();
-    pub const RAW_PUBLIC_KEY: Arg<common::PublicKey> = arg("public-key");
-    pub const RAW_PUBLIC_KEY_OPT: ArgOpt<common::PublicKey> =
-        RAW_PUBLIC_KEY.opt();
-    pub const RAW_PUBLIC_KEY_HASH: Arg<String> = arg("public-key-hash");
-    pub const RAW_PUBLIC_KEY_HASH_OPT: ArgOpt<String> =
-        RAW_PUBLIC_KEY_HASH.opt();
-    pub const RECEIVER: Arg<String> = arg("receiver");
-    pub const RELAYER: Arg<Address> = arg("relayer");
-    pub const SAFE_MODE: ArgFlag = flag("safe-mode");
-    pub const SCHEME: ArgDefault<SchemeType> =
-        arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
-    pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
-        arg("self-bond-amount");
-    pub const SENDER: Arg<String> = arg("sender");
-    pub const SHIELDED: ArgFlag = flag("shielded");
-    pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
-    pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
-        arg_multi("signing-keys");
-    pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
-    pub const SOURCE: Arg<WalletAddress> = arg("source");
-    pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
-    pub const STEWARD: Arg<WalletAddress> = arg("steward");
-    pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
-    pub const STORAGE_KEY: Arg<storage::Key> = arg("storage-key");
-    pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
-    pub const TEMPLATES_PATH: Arg<PathBuf> = arg("templates-path");
-    pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
-    pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
-    pub const TM_ADDRESS: ArgOpt<String> = arg_opt("tm-address");
-    pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
-    pub const TOKEN: Arg<WalletAddress> = arg("token");
-    pub const TOKEN_STR: Arg<String> = arg("token");
-    pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
-    pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
-    pub const TRANSPARENT: ArgFlag = flag("transparent");
-    pub const TX_HASH: Arg<String> = arg("tx-hash");
-    pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
-    pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
-    pub const UNSAFE_SHOW_SECRET: ArgFlag = flag("unsafe-show-secret");
-    pub const USE_DEVICE: ArgFlag = flag("use-device");
-    pub const VALIDATOR: Arg<WalletAddress> = arg("validator");
-    pub const VALIDATOR_OPT: ArgOpt<WalletAddress> = VALIDATOR.opt();
-    pub const VALIDATOR_ACCOUNT_KEY: ArgOpt<WalletPublicKey> =
-        arg_opt("account-key");
-    pub const VALIDATOR_ACCOUNT_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
-        arg_multi("account-keys");
-    pub const VALIDATOR_CONSENSUS_KEY: ArgOpt<WalletPublicKey> =
-        arg_opt("consensus-key");
-    pub const VALIDATOR_CODE_PATH: ArgOpt<PathBuf> =
-        arg_opt("validator-code-path");
-    pub const VALIDATOR_ETH_COLD_KEY: ArgOpt<WalletPublicKey> =
-        arg_opt("eth-cold-key");
-    pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
-        arg_opt("eth-hot-key");
-    pub const VALUE: Arg<String> = arg("value");
-    pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
-    pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
-    pub const VP: ArgOpt<String> = arg_opt("vp");
-    pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
-    pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
-    pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
-    pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
-    pub const TX_PATH: Arg<PathBuf> = arg("tx-path");
-    pub const TX_PATH_OPT: ArgOpt<PathBuf> = TX_PATH.opt();
 
-    /// Global command arguments
-    #[derive(Clone, Debug)]
-    pub struct Global {
-        pub is_pre_genesis: bool,
-        pub chain_id: Option<ChainId>,
-        pub base_dir: PathBuf,
-        pub wasm_dir: Option<PathBuf>,
+    impl SubCmd for ValidatorSetUpdateRelay {
+        const CMD: &'static str = "relay";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::ValidatorSetUpdateRelay::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Relay a validator set update to Namada's Ethereum bridge \
+                     smart contracts.",
+                )
+                .add_args::<args::ValidatorSetUpdateRelay<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PkToTmAddress(pub args::PkToTmAddress);
+
+    impl SubCmd for PkToTmAddress {
+        const CMD: &'static str = "pk-to-tm";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::PkToTmAddress::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Convert a validator's consensus public key to a \
+                     Tendermint address.",
+                )
+                .add_args::<args::PkToTmAddress>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DefaultBaseDir(pub args::DefaultBaseDir);
+
+    impl SubCmd for DefaultBaseDir {
+        const CMD: &'static str = "default-base-dir";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::DefaultBaseDir::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Print the default base directory that would be used if \
+                     --base-dir or NAMADA_BASE_DIR were not used to set the \
+                     base directory.",
+                )
+                .add_args::<args::DefaultBaseDir>()
+        }
+    }
+}
+
+pub mod args {
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+    use std::env;
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
+    use namada::tx::data::GasLimit;
+    use namada::types::address::{Address, EstablishedAddress};
+    use namada::types::chain::{ChainId, ChainIdPrefix};
+    use namada::types::dec::Dec;
+    use namada::types::ethereum_events::EthAddress;
+    use namada::types::keccak::KeccakHash;
+    use namada::types::key::*;
+    use namada::types::masp::PaymentAddress;
+    use namada::types::storage::{self, BlockHeight, Epoch};
+    use namada::types::time::DateTimeUtc;
+    use namada::types::token;
+    use namada::types::token::NATIVE_MAX_DECIMAL_PLACES;
+    pub use namada_sdk::args::*;
+    pub use namada_sdk::tx::{
+        TX_APPROVE_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+        TX_BRIDGE_POOL_WASM, TX_BURN_TOKENS_WASM, TX_CANCEL_RECOVERY_WASM,
+        TX_CANCEL_WITHDRAWAL_WASM, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+        TX_EXECUTE_WITHDRAWAL_WASM, TX_FINALIZE_RECOVERY_WASM, TX_IBC_WASM,
+        TX_INITIATE_RECOVERY_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+        TX_MINT_TOKENS_WASM, TX_QUEUE_WITHDRAWAL_WASM,
+        TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
+        TX_REGISTER_TOKEN_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK,
+        TX_TRANSFER_FROM_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+        TX_UPDATE_GUARDIANS_WASM, TX_UPDATE_SPENDING_POLICY_WASM,
+        TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_TOKEN_METADATA_WASM,
+        TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
+    };
+    use namada_sdk::wallet::discovery::DEFAULT_GAP_LIMIT;
+    use namada_sdk::wallet::ExtendedPublicKey;
+
+    use super::context::*;
+    use super::utils::*;
+    use super::{ArgGroup, ArgMatches};
+    use crate::client::utils::PRE_GENESIS_DIR;
+    use crate::config::genesis::GenesisAddress;
+    use crate::config::{self, Action, ActionAtHeight};
+    use crate::facade::tendermint::Timeout;
+    use crate::facade::tendermint_config::net::Address as TendermintAddress;
+
+    pub const ADDRESS: Arg<WalletAddress> = arg("address");
+    pub const ALIAS_OPT: ArgOpt<String> = ALIAS.opt();
+    pub const ALIAS: Arg<String> = arg("alias");
+    pub const ALIAS_FORCE: ArgFlag = flag("alias-force");
+    pub const ALIAS_MANY: ArgMulti<String, GlobPlus> = arg_multi("aliases");
+    pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
+    pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
+    pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
+    pub const ARCHIVE_PATH: Arg<PathBuf> = arg("archive-path");
+    pub const AVATAR_OPT: ArgOpt<String> = arg_opt("avatar");
+    pub const BALANCE_OWNER: ArgOpt<WalletBalanceOwner> = arg_opt("owner");
+    pub const BASE_DIR: ArgDefault<PathBuf> = arg_default(
+        "base-dir",
+        DefaultFn(|| match env::var("NAMADA_BASE_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => config::get_default_namada_folder(),
+        }),
+    );
+    pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
+    pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
+    pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
+        arg_default(
+            "pool-gas-amount",
+            DefaultFn(|| {
+                token::DenominatedAmount::new(
+                    token::Amount::zero(),
+                    NATIVE_MAX_DECIMAL_PLACES.into(),
+                )
+            }),
+        );
+    pub const BRIDGE_POOL_GAS_PAYER: ArgOpt<WalletAddress> =
+        arg_opt("pool-gas-payer");
+    pub const BRIDGE_POOL_GAS_TOKEN: ArgDefaultFromCtx<
+        WalletAddrOrNativeToken,
+    > = arg_default_from_ctx(
+        "pool-gas-token",
+        DefaultFn(|| "".parse().unwrap()),
+    );
+    pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
+    pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
+    pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
+    pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
+    pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
+    pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
+    pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
+    pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
+    pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
+    pub const COMMISSION_RATE_OPT: ArgOpt<Dec> = COMMISSION_RATE.opt();
+    pub const CONSENSUS_TIMEOUT_COMMIT: ArgDefault<Timeout> = arg_default(
+        "consensus-timeout-commit",
+        DefaultFn(|| Timeout::from_str("1s").unwrap()),
+    );
+    pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
+    pub const DAEMON_MODE: ArgFlag = flag("daemon");
+    pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
+    pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
+        arg_opt("success-sleep");
+    pub const DATA_PATH_OPT: ArgOpt<PathBuf> = arg_opt("data-path");
+    pub const DATA_PATH: Arg<PathBuf> = arg("data-path");
+    pub const DECRYPT: ArgFlag = flag("decrypt");
+    pub const DENOMINATION: Arg<u8> = arg("denomination");
+    pub const DESCRIPTION_OPT: ArgOpt<String> = arg_opt("description");
+    pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
+    pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
+        arg("destination-validator");
+    pub const DISCORD_OPT: ArgOpt<String> = arg_opt("discord-handle");
+    pub const DO_IT: ArgFlag = flag("do-it");
+    pub const DONT_ARCHIVE: ArgFlag = flag("dont-archive");
+    pub const DONT_PREFETCH_WASM: ArgFlag = flag("dont-prefetch-wasm");
+    pub const DRY_RUN_TX: ArgFlag = flag("dry-run");
+    pub const DRY_RUN_WRAPPER_TX: ArgFlag = flag("dry-run-wrapper");
+    pub const DUMP_TX: ArgFlag = flag("dump-tx");
+    pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
+    pub const ERC20: Arg<EthAddress> = arg("erc20");
+    pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
+    pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
+    pub const ETH_GAS_PRICE: ArgOpt<u64> = arg_opt("eth-gas-price");
+    pub const ETH_ADDRESS: Arg<EthAddress> = arg("ethereum-address");
+    pub const ETH_ADDRESS_OPT: ArgOpt<EthAddress> = ETH_ADDRESS.opt();
+    pub const ETH_RPC_ENDPOINT: ArgDefault<String> = arg_default(
+        "eth-rpc-endpoint",
+        DefaultFn(|| "http://localhost:8545".into()),
+    );
+    pub const ETH_SYNC: ArgFlag = flag("sync");
+    pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
+    pub const EXPIRY_EPOCH: ArgOpt<Epoch> = arg_opt("expiry-epoch");
+    pub const EMAIL: Arg<String> = arg("email");
+    pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
+    pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
+        arg_opt("gas-spending-key");
+    pub const FEE_MASP_SPENDING_KEY: ArgOpt<WalletTransferSource> =
+        arg_opt("gas-shielded-spending-key");
+    pub const FEE_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("gas-price");
+    pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
+    pub const FILE_PATH: Arg<String> = arg("file");
+    pub const FORCE: ArgFlag = flag("force");
+    pub const GUARDIANS: ArgMulti<WalletAddress, GlobStar> =
+        arg_multi("guardians");
+    pub const GAS_LIMIT: ArgDefault<GasLimit> =
+        arg_default("gas-limit", DefaultFn(|| GasLimit::from(25_000)));
+    pub const GAP_LIMIT: ArgDefault<u32> =
+        arg_default("gap-limit", DefaultFn(|| DEFAULT_GAP_LIMIT));
+    pub const FEE_TOKEN: ArgDefaultFromCtx<WalletAddrOrNativeToken> =
+        arg_default_from_ctx("gas-token", DefaultFn(|| "".parse().unwrap()));
+    pub const FEE_PAYER: Arg<WalletAddress> = arg("fee-payer");
+    pub const FEE_AMOUNT: ArgDefault<token::DenominatedAmount> = arg_default(
+        "fee-amount",
+        DefaultFn(|| {
+            token::DenominatedAmount::new(
+                token::Amount::default(),
+                NATIVE_MAX_DECIMAL_PLACES.into(),
+            )
+        }),
+    );
+    pub const GENESIS_BOND_SOURCE: ArgOpt<GenesisAddress> = arg_opt("source");
+    pub const GENESIS_PATH: Arg<PathBuf> = arg("genesis-path");
+    pub const GENESIS_TIME: Arg<DateTimeUtc> = arg("genesis-time");
+    pub const GENESIS_VALIDATOR: ArgOpt<String> =
+        arg("genesis-validator").opt();
+    pub const GENESIS_VALIDATOR_ADDRESS: Arg<EstablishedAddress> =
+        arg("validator");
+    pub const HALT_ACTION: ArgFlag = flag("halt");
+    pub const HASH_LIST: Arg<String> = arg("hash-list");
+    pub const HD_DERIVATION_PATH: ArgDefault<String> =
+        arg_default("hd-path", DefaultFn(|| "default".to_string()));
+    pub const HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH: ArgFlag =
+        flag("allow-non-compliant");
+    pub const HD_PROMPT_BIP39_PASSPHRASE: ArgFlag = flag("bip39-passphrase");
+    pub const HISTORIC: ArgFlag = flag("historic");
+    pub const IBC_TRACE_OPT: ArgOpt<String> = arg_opt("ibc-trace");
+    pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
+    pub const ICON_URL_OPT: ArgOpt<String> = arg_opt("icon-url");
+    pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
+    pub const LEDGER_ADDRESS_ABOUT: &str =
+        "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
+         scheme is not supplied, it is assumed to be TCP.";
+    pub const CONFIG_RPC_LEDGER_ADDRESS: ArgDefaultFromCtx<ConfigRpcAddress> =
+        arg_default_from_ctx("node", DefaultFn(|| "".to_string()));
+    pub const LEDGER_ADDRESS: ArgDefault<TendermintAddress> = arg("node")
+        .default(DefaultFn(|| {
+            let raw = "127.0.0.1:26657";
+            TendermintAddress::from_str(raw).unwrap()
+        }));
+    pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
+    pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
+    pub const LOCALHOST: ArgFlag = flag("localhost");
+    pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
+        arg("max-commission-rate-change");
+    pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MAX_SUPPLY_OPT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("max-supply");
+    pub const MEMO_OPT: ArgOpt<String> = arg_opt("memo");
+    pub const MINTER: Arg<WalletAddress> = arg("minter");
+    pub const MODE: ArgOpt<String> = arg_opt("mode");
+    pub const NAME: Arg<String> = arg("name");
+    pub const NET_ADDRESS: Arg<SocketAddr> = arg("net-address");
+    pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
+    pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
+    pub const NUT: ArgFlag = flag("nut");
+    pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
+    pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
+    pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
+        arg_opt("output-folder-path");
+    pub const OWNER: Arg<WalletAddress> = arg("owner");
+    pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
+    pub const PATH: Arg<PathBuf> = arg("path");
+    pub const PIN: ArgFlag = flag("pin");
+    pub const PORT_ID: ArgDefault<PortId> = arg_default(
+        "port-id",
+        DefaultFn(|| PortId::from_str("transfer").unwrap()),
+    );
+    pub const PRE_GENESIS: ArgFlag = flag("pre-genesis");
+    pub const PROPOSAL_ETH: ArgFlag = flag("eth");
+    pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
+    pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
+    pub const PROPOSAL_SHIELDED_REWARDS: ArgFlag = flag("shielded-rewards");
+    pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
+    pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
+    pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
+    pub const PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
+        arg_multi("public-keys");
+    pub const PROPOSAL_ID: Arg<u64> = arg("proposal-id");
+    pub const PROPOSAL_ID_OPT: ArgOpt<u64> = arg_opt("proposal-id");
+    pub const PROPOSAL_VOTE_PGF_OPT: ArgOpt<String> = arg_opt("pgf");
+    pub const PROPOSAL_VOTE_ETH_OPT: ArgOpt<String> = arg_opt("eth");
+    pub const PROPOSAL_VOTE: Arg<String> = arg("vote");
+    pub const RAW_ADDRESS: Arg<Address> = arg("address");
+    pub const RAW_ADDRESS_ESTABLISHED: Arg<EstablishedAddress> = arg("address");
+    pub const RAW_ADDRESS_OPT: ArgOpt<Address> = RAW_ADDRESS.opt();
+    pub const RAW_KEY_GEN: ArgFlag = flag("raw");
+    pub const RAW_PAYMENT_ADDRESS: Arg<PaymentAddress> = arg("payment-address");
+    pub const RAW_PAYMENT_ADDRESS_OPT: ArgOpt<PaymentAddress> =
+        RAW_PAYMENT_ADDRESS.opt();
+    pub const RAW_PUBLIC_KEY: Arg<common::PublicKey> = arg("public-key");
+    pub const RAW_PUBLIC_KEY_OPT: ArgOpt<common::PublicKey> =
+        RAW_PUBLIC_KEY.opt();
+    pub const RAW_PUBLIC_KEY_HASH: Arg<String> = arg("public-key-hash");
+    pub const RAW_PUBLIC_KEY_HASH_OPT: ArgOpt<String> =
+        RAW_PUBLIC_KEY_HASH.opt();
+    pub const RECEIVER: Arg<String> = arg("receiver");
+    pub const RECOVERY_DELAY: ArgDefault<u64> =
+        arg_default("recovery-delay", DefaultFn(|| 0));
+    pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const SAFE_MODE: ArgFlag = flag("safe-mode");
+    pub const SCHEME: ArgDefault<SchemeType> =
+        arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
+    pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
+        arg("self-bond-amount");
+    pub const SENDER: Arg<String> = arg("sender");
+    pub const SHIELDED: ArgFlag = flag("shielded");
+    pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
+    pub const SIGNER_SOCKET: ArgOpt<PathBuf> = arg_opt("signer-socket");
+    pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
+        arg_multi("signing-keys");
+    pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
+    pub const SOURCE: Arg<WalletAddress> = arg("source");
+    pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
+    pub const SPENDER: Arg<WalletAddress> = arg("spender");
+    pub const STEWARD: Arg<WalletAddress> = arg("steward");
+    pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
+    pub const STORAGE_KEY: Arg<storage::Key> = arg("storage-key");
+    pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
+    pub const SYMBOL: Arg<String> = arg("symbol");
+    pub const TARGET: Arg<WalletAddress> = arg("target");
+    pub const TEMPLATES_PATH: Arg<PathBuf> = arg("templates-path");
+    pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
+    pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
+    pub const TM_ADDRESS: ArgOpt<String> = arg_opt("tm-address");
+    pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
+    pub const TOKEN: Arg<WalletAddress> = arg("token");
+    pub const TOKEN_STR: Arg<String> = arg("token");
+    pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
+    pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
+    pub const TRANSPARENT: ArgFlag = flag("transparent");
+    pub const TX_HASH: Arg<String> = arg("tx-hash");
+    pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
+    pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
+    pub const UNSAFE_SHOW_SECRET: ArgFlag = flag("unsafe-show-secret");
+    pub const USE_DEVICE: ArgFlag = flag("use-device");
+    pub const VALIDATOR: Arg<WalletAddress> = arg("validator");
+    pub const VALIDATOR_OPT: ArgOpt<WalletAddress> = VALIDATOR.opt();
+    pub const VALIDATOR_ACCOUNT_KEY: ArgOpt<WalletPublicKey> =
+        arg_opt("account-key");
+    pub const VALIDATOR_ACCOUNT_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
+        arg_multi("account-keys");
+    pub const VALIDATOR_CONSENSUS_KEY: ArgOpt<WalletPublicKey> =
+        arg_opt("consensus-key");
+    pub const VALIDATOR_CODE_PATH: ArgOpt<PathBuf> =
+        arg_opt("validator-code-path");
+    pub const VALIDATOR_ETH_COLD_KEY: ArgOpt<WalletPublicKey> =
+        arg_opt("eth-cold-key");
+    pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
+        arg_opt("eth-hot-key");
+    pub const VALUE: Arg<String> = arg("value");
+    pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
+    pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
+    pub const VP: ArgOpt<String> = arg_opt("vp");
+    pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
+    pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
+    pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
+    pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
+    pub const XPUB: ArgOpt<ExtendedPublicKey> = arg_opt("xpub");
+    pub const WEIGHTS: ArgMulti<u8, GlobStar> = arg_multi("weights");
+    pub const WEIGHT_THRESHOLD: ArgOpt<u16> = arg_opt("weight-threshold");
+    pub const WITHDRAWAL_ID: Arg<u64> = arg("withdrawal-id");
+    pub const TX_PATH: Arg<PathBuf> = arg("tx-path");
+    pub const TX_PATH_OPT: ArgOpt<PathBuf> = TX_PATH.opt();
+
+    /// Global command arguments
+    #[derive(Clone, Debug)]
+    pub struct Global {
+        pub is_pre_genesis: bool,
+        pub chain_id: Option<ChainId>,
+        pub base_dir: PathBuf,
+        pub wasm_dir: Option<PathBuf>,
+    }
+
+    impl Global {
+        /// Parse global arguments
+        pub fn parse(matches: &ArgMatches) -> Self {
+            let is_pre_genesis = PRE_GENESIS.parse(matches);
+            let chain_id = CHAIN_ID_OPT.parse(matches);
+            let base_dir = BASE_DIR.parse(matches);
+            let wasm_dir = WASM_DIR.parse(matches);
+            Global {
+                is_pre_genesis,
+                chain_id,
+                base_dir,
+                wasm_dir,
+            }
+        }
+
+        /// Add global args definition. Should be added to every top-level
+        /// command.
+        pub fn def(app: App) -> App {
+            app.arg(CHAIN_ID_OPT.def().help("The chain ID."))
+                .arg(BASE_DIR.def().help(
+                    "The base directory is where the nodes, client and wallet \
+                     configuration and state is stored. This value can also \
+                     be set via `NAMADA_BASE_DIR` environment variable, but \
+                     the argument takes precedence, if specified. Defaults to \
+                     `$XDG_DATA_HOME/namada` (`$HOME/.local/share/namada` \
+                     where `XDG_DATA_HOME` is unset) on \
+                     Unix,`$HOME/Library/Application Support/Namada` on \
+                     Mac,and `%AppData%\\Namada` on Windows.",
+                ))
+                .arg(WASM_DIR.def().help(
+                    "Directory with built WASM validity predicates, \
+                     transactions. This value can also be set via \
+                     `NAMADA_WASM_DIR` environment variable, but the argument \
+                     takes precedence, if specified.",
+                ))
+                .arg(
+                    PRE_GENESIS
+                        .def()
+                        .help("Dispatch pre-genesis specific logic."),
+                )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerRun {
+        pub start_time: Option<DateTimeUtc>,
+    }
+
+    impl Args for LedgerRun {
+        fn parse(matches: &ArgMatches) -> Self {
+            let start_time = NAMADA_START_TIME.parse(matches);
+            Self { start_time }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(NAMADA_START_TIME.def().help(
+                "The start time of the ledger. Accepts a relaxed form of \
+                 RFC3339. A space or a 'T' are accepted as the separator \
+                 between the date and time components. Additional spaces are \
+                 allowed between each component.\nAll of these examples are \
+                 equivalent:\n2023-01-20T12:12:12Z\n2023-01-20 \
+                 12:12:12Z\n2023-  01-20T12:  12:12Z",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerRunUntil {
+        pub time: Option<DateTimeUtc>,
+        pub action_at_height: ActionAtHeight,
+    }
+
+    impl Args for LedgerRunUntil {
+        fn parse(matches: &ArgMatches) -> Self {
+            Self {
+                time: NAMADA_START_TIME.parse(matches),
+                action_at_height: ActionAtHeight {
+                    height: BLOCK_HEIGHT.parse(matches),
+                    action: if HALT_ACTION.parse(matches) {
+                        Action::Halt
+                    } else {
+                        Action::Suspend
+                    },
+                },
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                NAMADA_START_TIME
+                    .def()
+                    .help("The start time of the ledger."),
+            )
+            .arg(BLOCK_HEIGHT.def().help("The block height to run until."))
+            .arg(HALT_ACTION.def().help("Halt at the given block height"))
+            .arg(
+                SUSPEND_ACTION
+                    .def()
+                    .help("Suspend consensus at the given block height"),
+            )
+            .group(
+                ArgGroup::new("find_flags")
+                    .args([HALT_ACTION.name, SUSPEND_ACTION.name])
+                    .required(true),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerDumpDb {
+        // TODO: allow to specify height
+        pub block_height: Option<BlockHeight>,
+        pub out_file_path: PathBuf,
+        pub historic: bool,
+    }
+
+    impl Args for LedgerDumpDb {
+        fn parse(matches: &ArgMatches) -> Self {
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            let out_file_path = OUT_FILE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from("db_dump".to_string()));
+            let historic = HISTORIC.parse(matches);
+
+            Self {
+                block_height,
+                out_file_path,
+                historic,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height to dump. Defaults to latest committed
+                block.",
+            ))
+            .arg(OUT_FILE_PATH_OPT.def().help(
+                "Path for the output file (omitting file extension). Defaults \
+                 to \"db_dump.{block_height}.toml\" in the current working \
+                 directory.",
+            ))
+            .arg(
+                HISTORIC
+                    .def()
+                    .help("If provided, dump also the diff of the last height"),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerExportState {
+        pub archive_path: PathBuf,
+        pub block_height: Option<BlockHeight>,
+    }
+
+    impl Args for LedgerExportState {
+        fn parse(matches: &ArgMatches) -> Self {
+            let archive_path = ARCHIVE_PATH.parse(matches);
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            Self {
+                archive_path,
+                block_height,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ARCHIVE_PATH
+                    .def()
+                    .help("Path of the archive file to write."),
+            )
+            .arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height to export. It must be the height of the \
+                 last committed block, at which the node can be halted with \
+                 `run-until`. Defaults to the last committed block.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerImportState {
+        pub archive_path: PathBuf,
+    }
+
+    impl Args for LedgerImportState {
+        fn parse(matches: &ArgMatches) -> Self {
+            let archive_path = ARCHIVE_PATH.parse(matches);
+            Self { archive_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ARCHIVE_PATH.def().help("Path of the archive to import."))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct UpdateLocalConfig {
+        pub config_path: PathBuf,
+    }
+
+    impl Args for UpdateLocalConfig {
+        fn parse(matches: &ArgMatches) -> Self {
+            let config_path = DATA_PATH.parse(matches);
+            Self { config_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(DATA_PATH.def().help(
+                "The path to the toml file containing the updated local \
+                 configuration.",
+            ))
+        }
+    }
+
+    /// Convert CLI args to SDK args, with contextual data.
+    pub trait CliToSdk<SDK>: Args {
+        /// Convert CLI args to SDK args, with contextual data.
+        fn to_sdk(self, ctx: &mut Context) -> SDK;
+    }
+
+    /// Convert CLI args to SDK args, without contextual data.
+    pub trait CliToSdkCtxless<SDK>: Args {
+        /// Convert CLI args to SDK args, without contextual data.
+        fn to_sdk_ctxless(self) -> SDK;
+    }
+
+    impl<CLI, SDK> CliToSdk<SDK> for CLI
+    where
+        CLI: Args + CliToSdkCtxless<SDK>,
+    {
+        #[inline]
+        fn to_sdk(self, _: &mut Context) -> SDK {
+            self.to_sdk_ctxless()
+        }
+    }
+
+    impl CliToSdk<QueryResult<SdkTypes>> for QueryResult<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryResult<SdkTypes> {
+            QueryResult::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                tx_hash: self.tx_hash,
+            }
+        }
+    }
+
+    impl Args for QueryResult<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let tx_hash = TX_HASH.parse(matches);
+            Self { query, tx_hash }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                TX_HASH
+                    .def()
+                    .help("The hash of the transaction being looked up."),
+            )
+        }
+    }
+
+    impl CliToSdk<EthereumBridgePool<SdkTypes>> for EthereumBridgePool<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> EthereumBridgePool<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            EthereumBridgePool::<SdkTypes> {
+                nut: self.nut,
+                tx,
+                asset: self.asset,
+                recipient: self.recipient,
+                sender: chain_ctx.get(&self.sender),
+                amount: self.amount,
+                fee_amount: self.fee_amount,
+                fee_payer: self
+                    .fee_payer
+                    .map(|fee_payer| chain_ctx.get(&fee_payer)),
+                fee_token: chain_ctx.get(&self.fee_token).into(),
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for EthereumBridgePool<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let asset = ERC20.parse(matches);
+            let recipient = BRIDGE_POOL_TARGET.parse(matches);
+            let sender = SOURCE.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let fee_amount =
+                InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
+            let fee_payer = BRIDGE_POOL_GAS_PAYER.parse(matches);
+            let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_WASM);
+            let nut = NUT.parse(matches);
+            Self {
+                tx,
+                asset,
+                recipient,
+                sender,
+                amount,
+                fee_amount,
+                fee_payer,
+                fee_token,
+                code_path,
+                nut,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    ERC20
+                        .def()
+                        .help("The Ethereum address of the ERC20 token."),
+                )
+                .arg(
+                    BRIDGE_POOL_TARGET
+                        .def()
+                        .help("The Ethereum address receiving the tokens."),
+                )
+                .arg(
+                    SOURCE.def().help("The Namada address sending the tokens."),
+                )
+                .arg(
+                    AMOUNT.def().help(
+                        "The amount of tokens being sent across the bridge.",
+                    ),
+                )
+                .arg(BRIDGE_POOL_GAS_AMOUNT.def().help(
+                    "The amount of gas you wish to pay to have this transfer \
+                     relayed to Ethereum.",
+                ))
+                .arg(BRIDGE_POOL_GAS_PAYER.def().help(
+                    "The Namada address of the account paying the gas. By \
+                     default, it is the same as the source.",
+                ))
+                .arg(BRIDGE_POOL_GAS_TOKEN.def().help(
+                    "The token for paying the Bridge pool gas fees. Defaults \
+                     to NAM.",
+                ))
+                .arg(NUT.def().help(
+                    "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
+                     are usually obtained from invalid transfers to Namada.",
+                ))
+        }
+    }
+
+    impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> RecommendBatch<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            RecommendBatch::<SdkTypes> {
+                query,
+                max_gas: self.max_gas,
+                gas: self.gas,
+                conversion_table: {
+                    let file = std::io::BufReader::new(
+                        std::fs::File::open(self.conversion_table).expect(
+                            "Failed to open the provided file to the \
+                             conversion table",
+                        ),
+                    );
+                    let table: HashMap<String, f64> =
+                        serde_json::from_reader(file)
+                            .expect("Failed to parse conversion table");
+                    table
+                        .into_iter()
+                        .map(|(token, conversion_rate)| {
+                            let token_from_ctx =
+                                FromContext::<Address>::new(token);
+                            let address = chain_ctx.get(&token_from_ctx);
+                            let alias = token_from_ctx.raw;
+                            (
+                                address,
+                                BpConversionTableEntry {
+                                    alias,
+                                    conversion_rate,
+                                },
+                            )
+                        })
+                        .collect()
+                },
+            }
+        }
+    }
+
+    impl Args for RecommendBatch<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let max_gas = MAX_ETH_GAS.parse(matches);
+            let gas = ETH_GAS.parse(matches);
+            let conversion_table = CONVERSION_TABLE.parse(matches);
+            Self {
+                query,
+                max_gas,
+                gas,
+                conversion_table,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(MAX_ETH_GAS.def().help(
+                    "The maximum amount Ethereum gas that can be spent during \
+                     the relay call.",
+                ))
+                .arg(ETH_GAS.def().help(
+                    "Under ideal conditions, relaying transfers will yield a \
+                     net profit. If that is not possible, setting this \
+                     optional value will result in a batch transfer that \
+                     costs as close to the given value as possible without \
+                     exceeding it.",
+                ))
+                .arg(CONVERSION_TABLE.def().help(
+                    "Path to a JSON object containing a mapping between token \
+                     aliases (or addresses) and their conversion rates in gwei",
+                ))
+        }
+    }
+
+    impl CliToSdkCtxless<BridgePoolProof<SdkTypes>> for BridgePoolProof<CliTypes> {
+        fn to_sdk_ctxless(self) -> BridgePoolProof<SdkTypes> {
+            BridgePoolProof::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                transfers: self.transfers,
+                relayer: self.relayer,
+            }
+        }
+    }
+
+    impl Args for BridgePoolProof<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let hashes = HASH_LIST.parse(matches);
+            let relayer = RELAYER.parse(matches);
+            Self {
+                ledger_address,
+                transfers: hashes
+                    .split_whitespace()
+                    .map(|hash| {
+                        KeccakHash::try_from(hash).unwrap_or_else(|_| {
+                            tracing::info!(
+                                "Could not parse '{}' as a Keccak hash.",
+                                hash
+                            );
+                            safe_exit(1)
+                        })
+                    })
+                    .collect(),
+                relayer,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(HASH_LIST.def().help(
+                    "Whitespace separated Keccak hash list of transfers in \
+                     the Bridge pool.",
+                ))
+                .arg(
+                    RELAYER
+                        .def()
+                        .help("The rewards address for relaying this proof."),
+                )
+        }
+    }
+
+    impl CliToSdkCtxless<RelayBridgePoolProof<SdkTypes>>
+        for RelayBridgePoolProof<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> RelayBridgePoolProof<SdkTypes> {
+            RelayBridgePoolProof::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                transfers: self.transfers,
+                relayer: self.relayer,
+                confirmations: self.confirmations,
+                eth_rpc_endpoint: (),
+                gas: self.gas,
+                gas_price: self.gas_price,
+                eth_addr: self.eth_addr,
+                sync: self.sync,
+                safe_mode: self.safe_mode,
+            }
+        }
+    }
+
+    impl Args for RelayBridgePoolProof<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let safe_mode = SAFE_MODE.parse(matches);
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let hashes = HASH_LIST.parse(matches);
+            let relayer = RELAYER.parse(matches);
+            let gas = ETH_GAS.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let sync = ETH_SYNC.parse(matches);
+            Self {
+                ledger_address,
+                sync,
+                transfers: hashes
+                    .split(' ')
+                    .map(|hash| {
+                        KeccakHash::try_from(hash).unwrap_or_else(|_| {
+                            tracing::info!(
+                                "Could not parse '{}' as a Keccak hash.",
+                                hash
+                            );
+                            safe_exit(1)
+                        })
+                    })
+                    .collect(),
+                relayer,
+                gas,
+                gas_price,
+                eth_rpc_endpoint,
+                eth_addr,
+                confirmations,
+                safe_mode,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(SAFE_MODE.def().help(
+                    "Safe mode overrides keyboard interrupt signals, to \
+                     ensure Ethereum transfers aren't canceled midway through.",
+                ))
+                .arg(HASH_LIST.def().help(
+                    "Whitespace separated Keccak hash list of transfers in \
+                     the Bridge pool.",
+                ))
+                .arg(
+                    RELAYER
+                        .def()
+                        .help("The rewards address for relaying this proof."),
+                )
+                .arg(ETH_ADDRESS_OPT.def().help(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     If unset, the default wallet is used.",
+                ))
+                .arg(ETH_GAS.def().help(
+                    "The Ethereum gas that can be spent during the relay call.",
+                ))
+                .arg(
+                    ETH_GAS_PRICE.def().help(
+                        "The price of Ethereum gas, during the relay call.",
+                    ),
+                )
+                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    ETH_CONFIRMATIONS
+                        .def()
+                        .help("The number of block confirmations on Ethereum."),
+                )
+                .arg(ETH_SYNC.def().help(
+                    "Synchronize with the network, or exit immediately, if \
+                     the Ethereum node has fallen behind.",
+                ))
+        }
+    }
+
+    impl CliToSdkCtxless<BridgeValidatorSet<SdkTypes>>
+        for BridgeValidatorSet<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> BridgeValidatorSet<SdkTypes> {
+            BridgeValidatorSet::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                epoch: self.epoch,
+            }
+        }
     }
 
-    impl Global {
-        /// Parse global arguments
-        pub fn parse(matches: &ArgMatches) -> Self {
-            let is_pre_genesis = PRE_GENESIS.parse(matches);
-            let chain_id = CHAIN_ID_OPT.parse(matches);
-            let base_dir = BASE_DIR.parse(matches);
-            let wasm_dir = WASM_DIR.parse(matches);
-            Global {
-                is_pre_genesis,
-                chain_id,
-                base_dir,
-                wasm_dir,
+    impl Args for BridgeValidatorSet<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self {
+                ledger_address,
+                epoch,
             }
         }
 
-        /// Add global args definition. Should be added to every top-level
-        /// command.
-        pub fn def(app: App) -> App {
-            app.arg(CHAIN_ID_OPT.def().help("The chain ID."))
-                .arg(BASE_DIR.def().help(
-                    "The base directory is where the nodes, client and wallet \
-                     configuration and state is stored. This value can also \
-                     be set via `NAMADA_BASE_DIR` environment variable, but \
-                     the argument takes precedence, if specified. Defaults to \
-                     `$XDG_DATA_HOME/namada` (`$HOME/.local/share/namada` \
-                     where `XDG_DATA_HOME` is unset) on \
-                     Unix,`$HOME/Library/Application Support/Namada` on \
-                     Mac,and `%AppData%\\Namada` on Windows.",
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(EPOCH.def().help(
+                    "The epoch of the Bridge set of validators to query.",
                 ))
-                .arg(WASM_DIR.def().help(
-                    "Directory with built WASM validity predicates, \
-                     transactions. This value can also be set via \
-                     `NAMADA_WASM_DIR` environment variable, but the argument \
-                     takes precedence, if specified.",
+        }
+    }
+
+    impl CliToSdkCtxless<GovernanceValidatorSet<SdkTypes>>
+        for GovernanceValidatorSet<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> GovernanceValidatorSet<SdkTypes> {
+            GovernanceValidatorSet::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                epoch: self.epoch,
+            }
+        }
+    }
+
+    impl Args for GovernanceValidatorSet<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self {
+                ledger_address,
+                epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(EPOCH.def().help(
+                    "The epoch of the Governance set of validators to query.",
                 ))
+        }
+    }
+
+    impl CliToSdkCtxless<ValidatorSetProof<SdkTypes>>
+        for ValidatorSetProof<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> ValidatorSetProof<SdkTypes> {
+            ValidatorSetProof::<SdkTypes> {
+                ledger_address: self.ledger_address,
+                epoch: self.epoch,
+            }
+        }
+    }
+
+    impl Args for ValidatorSetProof<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self {
+                ledger_address,
+                epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
                 .arg(
-                    PRE_GENESIS
-                        .def()
-                        .help("Dispatch pre-genesis specific logic."),
+                    EPOCH.def().help(
+                        "The epoch of the set of validators to be proven.",
+                    ),
                 )
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct LedgerRun {
-        pub start_time: Option<DateTimeUtc>,
+    impl CliToSdkCtxless<ValidatorSetUpdateRelay<SdkTypes>>
+        for ValidatorSetUpdateRelay<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> ValidatorSetUpdateRelay<SdkTypes> {
+            ValidatorSetUpdateRelay::<SdkTypes> {
+                daemon: self.daemon,
+                ledger_address: self.ledger_address,
+                confirmations: self.confirmations,
+                eth_rpc_endpoint: (),
+                epoch: self.epoch,
+                gas: self.gas,
+                gas_price: self.gas_price,
+                eth_addr: self.eth_addr,
+                sync: self.sync,
+                retry_dur: self.retry_dur,
+                success_dur: self.success_dur,
+                safe_mode: self.safe_mode,
+            }
+        }
     }
 
-    impl Args for LedgerRun {
+    impl Args for ValidatorSetUpdateRelay<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let start_time = NAMADA_START_TIME.parse(matches);
-            Self { start_time }
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let safe_mode = SAFE_MODE.parse(matches);
+            let daemon = DAEMON_MODE.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            let gas = ETH_GAS.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let sync = ETH_SYNC.parse(matches);
+            let retry_dur =
+                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
+            let success_dur =
+                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            Self {
+                ledger_address,
+                sync,
+                daemon,
+                epoch,
+                gas,
+                gas_price,
+                confirmations,
+                eth_rpc_endpoint,
+                eth_addr,
+                retry_dur,
+                success_dur,
+                safe_mode,
+            }
         }
 
         fn def(app: App) -> App {
-            app.arg(NAMADA_START_TIME.def().help(
-                "The start time of the ledger. Accepts a relaxed form of \
-                 RFC3339. A space or a 'T' are accepted as the separator \
-                 between the date and time components. Additional spaces are \
-                 allowed between each component.\nAll of these examples are \
-                 equivalent:\n2023-01-20T12:12:12Z\n2023-01-20 \
-                 12:12:12Z\n2023-  01-20T12:  12:12Z",
-            ))
+            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(SAFE_MODE.def().help(
+                    "Safe mode overrides keyboard interrupt signals, to \
+                     ensure Ethereum transfers aren't canceled midway through.",
+                ))
+                .arg(DAEMON_MODE.def().help(
+                    "Run in daemon mode, which will continuously perform \
+                     validator set updates.",
+                ))
+                .arg(DAEMON_MODE_RETRY_DUR.def().help(
+                    "The amount of time to sleep between failed daemon mode \
+                     relays.",
+                ))
+                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(
+                    "The amount of time to sleep between successful daemon \
+                     mode relays.",
+                ))
+                .arg(ETH_ADDRESS_OPT.def().help(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     If unset, the default wallet is used.",
+                ))
+                .arg(
+                    EPOCH
+                        .def()
+                        .help("The epoch of the set of validators to relay."),
+                )
+                .arg(ETH_GAS.def().help(
+                    "The Ethereum gas that can be spent during the relay call.",
+                ))
+                .arg(
+                    ETH_GAS_PRICE.def().help(
+                        "The price of Ethereum gas, during the relay call.",
+                    ),
+                )
+                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    ETH_CONFIRMATIONS
+                        .def()
+                        .help("The number of block confirmations on Ethereum."),
+                )
+                .arg(ETH_SYNC.def().help(
+                    "Synchronize with the network, or exit immediately, if \
+                     the Ethereum node has fallen behind.",
+                ))
+        }
+    }
+
+    impl CliToSdk<TxCustom<SdkTypes>> for TxCustom<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxCustom<SdkTypes> {
+            TxCustom::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                code_path: self.code_path,
+                data_path: self.data_path.map(|data_path| {
+                    std::fs::read(data_path)
+                        .expect("Expected a file at given path")
+                }),
+                serialized_tx: self.serialized_tx.map(|path| {
+                    std::fs::read(path).expect("Expected a file at given path")
+                }),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct LedgerRunUntil {
-        pub time: Option<DateTimeUtc>,
-        pub action_at_height: ActionAtHeight,
-    }
-
-    impl Args for LedgerRunUntil {
+    impl Args for TxCustom<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let code_path = CODE_PATH_OPT.parse(matches);
+            let data_path = DATA_PATH_OPT.parse(matches);
+            let serialized_tx = TX_PATH_OPT.parse(matches);
+            let owner = OWNER.parse(matches);
             Self {
-                time: NAMADA_START_TIME.parse(matches),
-                action_at_height: ActionAtHeight {
-                    height: BLOCK_HEIGHT.parse(matches),
-                    action: if HALT_ACTION.parse(matches) {
-                        Action::Halt
-                    } else {
-                        Action::Suspend
-                    },
-                },
+                tx,
+                code_path,
+                data_path,
+                serialized_tx,
+                owner,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(
-                NAMADA_START_TIME
-                    .def()
-                    .help("The start time of the ledger."),
-            )
-            .arg(BLOCK_HEIGHT.def().help("The block height to run until."))
-            .arg(HALT_ACTION.def().help("Halt at the given block height"))
-            .arg(
-                SUSPEND_ACTION
-                    .def()
-                    .help("Suspend consensus at the given block height"),
-            )
-            .group(
-                ArgGroup::new("find_flags")
-                    .args([HALT_ACTION.name, SUSPEND_ACTION.name])
-                    .required(true),
-            )
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    CODE_PATH_OPT
+                        .def()
+                        .help("The path to the transaction's WASM code.")
+                        .conflicts_with(TX_PATH_OPT.name),
+                )
+                .arg(
+                    DATA_PATH_OPT
+                        .def()
+                        .help(
+                            "The data file at this path containing arbitrary \
+                             bytes will be passed to the transaction code \
+                             when it's executed.",
+                        )
+                        .requires(CODE_PATH_OPT.name)
+                        .conflicts_with(TX_PATH_OPT.name),
+                )
+                .arg(
+                    TX_PATH_OPT
+                        .def()
+                        .help("The path to a serialized transaction.")
+                        .conflicts_with_all([
+                            CODE_PATH_OPT.name,
+                            DATA_PATH_OPT.name,
+                        ]),
+                )
+                .arg(OWNER.def().help(
+                    "The address corresponding to the signatures or signing \
+                     keys.",
+                ))
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct LedgerDumpDb {
-        // TODO: allow to specify height
-        pub block_height: Option<BlockHeight>,
-        pub out_file_path: PathBuf,
-        pub historic: bool,
+    impl CliToSdk<TxTransfer<SdkTypes>> for TxTransfer<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxTransfer<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxTransfer::<SdkTypes> {
+                tx,
+                source: chain_ctx.get_cached(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
     }
 
-    impl Args for LedgerDumpDb {
+    impl Args for TxTransfer<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
-            let out_file_path = OUT_FILE_PATH_OPT
-                .parse(matches)
-                .unwrap_or_else(|| PathBuf::from("db_dump".to_string()));
-            let historic = HISTORIC.parse(matches);
-
+            let tx = Tx::parse(matches);
+            let source = TRANSFER_SOURCE.parse(matches);
+            let target = TRANSFER_TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
             Self {
-                block_height,
-                out_file_path,
-                historic,
+                tx,
+                source,
+                target,
+                token,
+                amount,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(BLOCK_HEIGHT_OPT.def().help(
-                "The block height to dump. Defaults to latest committed
-                block.",
-            ))
-            .arg(OUT_FILE_PATH_OPT.def().help(
-                "Path for the output file (omitting file extension). Defaults \
-                 to \"db_dump.{block_height}.toml\" in the current working \
-                 directory.",
-            ))
-            .arg(
-                HISTORIC
-                    .def()
-                    .help("If provided, dump also the diff of the last height"),
-            )
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TRANSFER_SOURCE.def().help(
+                    "The source account address. The source's key may be used \
+                     to produce the signature. A shielded source is given by \
+                     its spending key, or by its viewing key when its \
+                     spends are signed by an external MASP prover holding \
+                     the spending key.",
+                ))
+                .arg(TRANSFER_TARGET.def().help(
+                    "The target account address. The target's key may be used \
+                     to produce the signature.",
+                ))
+                .arg(TOKEN.def().help("The transfer token."))
+                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct UpdateLocalConfig {
-        pub config_path: PathBuf,
+    impl CliToSdk<TxRegisterToken<SdkTypes>> for TxRegisterToken<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxRegisterToken<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxRegisterToken::<SdkTypes> {
+                tx,
+                token: chain_ctx.get(&self.token),
+                denom: self.denom,
+                minter: chain_ctx.get(&self.minter),
+                max_supply: self.max_supply,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
     }
 
-    impl Args for UpdateLocalConfig {
+    impl Args for TxRegisterToken<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let config_path = DATA_PATH.parse(matches);
-            Self { config_path }
+            let tx = Tx::parse(matches);
+            let token = TOKEN.parse(matches);
+            let denom = DENOMINATION.parse(matches).into();
+            let minter = MINTER.parse(matches);
+            let max_supply = MAX_SUPPLY_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_REGISTER_TOKEN_WASM);
+            Self {
+                tx,
+                token,
+                denom,
+                minter,
+                max_supply,
+                tx_code_path,
+            }
         }
 
         fn def(app: App) -> App {
-            app.arg(DATA_PATH.def().help(
-                "The path to the toml file containing the updated local \
-                 configuration.",
-            ))
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TOKEN.def().help(
+                    "The established account issuing the token. Its key is \
+                     used to produce the signature.",
+                ))
+                .arg(
+                    DENOMINATION
+                        .def()
+                        .help("The number of decimal places of the token."),
+                )
+                .arg(MINTER.def().help(
+                    "The address whose signature authorizes the mints and \
+                     burns of the token.",
+                ))
+                .arg(MAX_SUPPLY_OPT.def().help(
+                    "The maximum supply of the token in decimal. The supply \
+                     is not capped if omitted.",
+                ))
         }
     }
 
-    /// Convert CLI args to SDK args, with contextual data.
-    pub trait CliToSdk<SDK>: Args {
-        /// Convert CLI args to SDK args, with contextual data.
-        fn to_sdk(self, ctx: &mut Context) -> SDK;
+    impl CliToSdk<TxMintTokens<SdkTypes>> for TxMintTokens<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxMintTokens<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxMintTokens::<SdkTypes> {
+                tx,
+                minter: chain_ctx.get(&self.minter),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
     }
 
-    /// Convert CLI args to SDK args, without contextual data.
-    pub trait CliToSdkCtxless<SDK>: Args {
-        /// Convert CLI args to SDK args, without contextual data.
-        fn to_sdk_ctxless(self) -> SDK;
-    }
+    impl Args for TxMintTokens<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let minter = MINTER.parse(matches);
+            let target = TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_MINT_TOKENS_WASM);
+            Self {
+                tx,
+                minter,
+                target,
+                token,
+                amount,
+                tx_code_path,
+            }
+        }
 
-    impl<CLI, SDK> CliToSdk<SDK> for CLI
-    where
-        CLI: Args + CliToSdkCtxless<SDK>,
-    {
-        #[inline]
-        fn to_sdk(self, _: &mut Context) -> SDK {
-            self.to_sdk_ctxless()
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(MINTER.def().help(
+                    "The minter of the token. Its key is used to produce the \
+                     signature.",
+                ))
+                .arg(
+                    TARGET
+                        .def()
+                        .help("The address receiving the minted tokens."),
+                )
+                .arg(TOKEN.def().help("The minted token."))
+                .arg(AMOUNT.def().help("The amount to mint in decimal."))
         }
     }
 
-    impl CliToSdk<QueryResult<SdkTypes>> for QueryResult<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryResult<SdkTypes> {
-            QueryResult::<SdkTypes> {
-                query: self.query.to_sdk(ctx),
-                tx_hash: self.tx_hash,
+    impl CliToSdk<TxBurnTokens<SdkTypes>> for TxBurnTokens<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxBurnTokens<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxBurnTokens::<SdkTypes> {
+                tx,
+                owner: chain_ctx.get(&self.owner),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for QueryResult<CliTypes> {
+    impl Args for TxBurnTokens<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let query = Query::parse(matches);
-            let tx_hash = TX_HASH.parse(matches);
-            Self { query, tx_hash }
+            let tx = Tx::parse(matches);
+            let owner = OWNER.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_BURN_TOKENS_WASM);
+            Self {
+                tx,
+                owner,
+                token,
+                amount,
+                tx_code_path,
+            }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                TX_HASH
-                    .def()
-                    .help("The hash of the transaction being looked up."),
-            )
+            app.add_args::<Tx<CliTypes>>()
+                .arg(OWNER.def().help(
+                    "The owner of the burned tokens. Its key is used to \
+                     produce the signature.",
+                ))
+                .arg(TOKEN.def().help("The burned token."))
+                .arg(AMOUNT.def().help("The amount to burn in decimal."))
         }
     }
-
-    impl CliToSdk<EthereumBridgePool<SdkTypes>> for EthereumBridgePool<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> EthereumBridgePool<SdkTypes> {
+
+    impl CliToSdk<TxUpdateTokenMetadata<SdkTypes>>
+        for TxUpdateTokenMetadata<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> TxUpdateTokenMetadata<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
             let chain_ctx = ctx.borrow_chain_or_exit();
-            EthereumBridgePool::<SdkTypes> {
-                nut: self.nut,
+            TxUpdateTokenMetadata::<SdkTypes> {
                 tx,
-                asset: self.asset,
-                recipient: self.recipient,
-                sender: chain_ctx.get(&self.sender),
-                amount: self.amount,
-                fee_amount: self.fee_amount,
-                fee_payer: self
-                    .fee_payer
-                    .map(|fee_payer| chain_ctx.get(&fee_payer)),
-                fee_token: chain_ctx.get(&self.fee_token).into(),
-                code_path: self.code_path,
+                token: chain_ctx.get(&self.token),
+                symbol: self.symbol,
+                name: self.name,
+                icon_url: self.icon_url,
+                description: self.description,
+                ibc_trace: self.ibc_trace,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for EthereumBridgePool<CliTypes> {
+    impl Args for TxUpdateTokenMetadata<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
-            let asset = ERC20.parse(matches);
-            let recipient = BRIDGE_POOL_TARGET.parse(matches);
-            let sender = SOURCE.parse(matches);
-            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
-            let fee_amount =
-                InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
-            let fee_payer = BRIDGE_POOL_GAS_PAYER.parse(matches);
-            let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
-            let code_path = PathBuf::from(TX_BRIDGE_POOL_WASM);
-            let nut = NUT.parse(matches);
+            let token = TOKEN.parse(matches);
+            let symbol = SYMBOL.parse(matches);
+            let name = NAME.parse(matches);
+            let icon_url = ICON_URL_OPT.parse(matches);
+            let description = DESCRIPTION_OPT.parse(matches);
+            let ibc_trace = IBC_TRACE_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_TOKEN_METADATA_WASM);
             Self {
                 tx,
-                asset,
-                recipient,
-                sender,
-                amount,
-                fee_amount,
-                fee_payer,
-                fee_token,
-                code_path,
-                nut,
+                token,
+                symbol,
+                name,
+                icon_url,
+                description,
+                ibc_trace,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Tx<CliTypes>>()
+                .arg(TOKEN.def().help(
+                    "The token whose metadata is updated. The key of its \
+                     minter is used to produce the signature.",
+                ))
+                .arg(SYMBOL.def().help("The ticker symbol of the token."))
+                .arg(NAME.def().help("The full name of the token."))
+                .arg(ICON_URL_OPT.def().help("The URL of the token's icon."))
+                .arg(DESCRIPTION_OPT.def().help("A description of the token."))
                 .arg(
-                    ERC20
-                        .def()
-                        .help("The Ethereum address of the ERC20 token."),
-                )
-                .arg(
-                    BRIDGE_POOL_TARGET
+                    IBC_TRACE_OPT
                         .def()
-                        .help("The Ethereum address receiving the tokens."),
-                )
-                .arg(
-                    SOURCE.def().help("The Namada address sending the tokens."),
-                )
-                .arg(
-                    AMOUNT.def().help(
-                        "The amount of tokens being sent across the bridge.",
-                    ),
+                        .help("The IBC denomination trace of an IBC token."),
                 )
-                .arg(BRIDGE_POOL_GAS_AMOUNT.def().help(
-                    "The amount of gas you wish to pay to have this transfer \
-                     relayed to Ethereum.",
-                ))
-                .arg(BRIDGE_POOL_GAS_PAYER.def().help(
-                    "The Namada address of the account paying the gas. By \
-                     default, it is the same as the source.",
-                ))
-                .arg(BRIDGE_POOL_GAS_TOKEN.def().help(
-                    "The token for paying the Bridge pool gas fees. Defaults \
-                     to NAM.",
-                ))
-                .arg(NUT.def().help(
-                    "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
-                     are usually obtained from invalid transfers to Namada.",
-                ))
         }
     }
 
-    impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> RecommendBatch<SdkTypes> {
-            let query = self.query.to_sdk(ctx);
+    impl CliToSdk<TxApprove<SdkTypes>> for TxApprove<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxApprove<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
             let chain_ctx = ctx.borrow_chain_or_exit();
-            RecommendBatch::<SdkTypes> {
-                query,
-                max_gas: self.max_gas,
-                gas: self.gas,
-                conversion_table: {
-                    let file = std::io::BufReader::new(
-                        std::fs::File::open(self.conversion_table).expect(
-                            "Failed to open the provided file to the \
-                             conversion table",
-                        ),
-                    );
-                    let table: HashMap<String, f64> =
-                        serde_json::from_reader(file)
-                            .expect("Failed to parse conversion table");
-                    table
-                        .into_iter()
-                        .map(|(token, conversion_rate)| {
-                            let token_from_ctx =
-                                FromContext::<Address>::new(token);
-                            let address = chain_ctx.get(&token_from_ctx);
-                            let alias = token_from_ctx.raw;
-                            (
-                                address,
-                                BpConversionTableEntry {
-                                    alias,
-                                    conversion_rate,
-                                },
-                            )
-                        })
-                        .collect()
-                },
+            TxApprove::<SdkTypes> {
+                tx,
+                owner: chain_ctx.get(&self.owner),
+                spender: chain_ctx.get(&self.spender),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                expiry: self.expiry,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for RecommendBatch<CliTypes> {
+    impl Args for TxApprove<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let query = Query::parse(matches);
-            let max_gas = MAX_ETH_GAS.parse(matches);
-            let gas = ETH_GAS.parse(matches);
-            let conversion_table = CONVERSION_TABLE.parse(matches);
+            let tx = Tx::parse(matches);
+            let owner = OWNER.parse(matches);
+            let spender = SPENDER.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let expiry = EXPIRY_EPOCH.parse(matches);
+            let tx_code_path = PathBuf::from(TX_APPROVE_WASM);
             Self {
-                query,
-                max_gas,
-                gas,
-                conversion_table,
+                tx,
+                owner,
+                spender,
+                token,
+                amount,
+                expiry,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>()
-                .arg(MAX_ETH_GAS.def().help(
-                    "The maximum amount Ethereum gas that can be spent during \
-                     the relay call.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(OWNER.def().help(
+                    "The owner of the tokens. Its key is used to produce the \
+                     signature.",
                 ))
-                .arg(ETH_GAS.def().help(
-                    "Under ideal conditions, relaying transfers will yield a \
-                     net profit. If that is not possible, setting this \
-                     optional value will result in a batch transfer that \
-                     costs as close to the given value as possible without \
-                     exceeding it.",
+                .arg(
+                    SPENDER
+                        .def()
+                        .help("The address allowed to spend the tokens."),
+                )
+                .arg(TOKEN.def().help("The token to allow spending."))
+                .arg(AMOUNT.def().help(
+                    "The amount the spender may transfer in decimal. A zero \
+                     amount revokes the allowance.",
                 ))
-                .arg(CONVERSION_TABLE.def().help(
-                    "Path to a JSON object containing a mapping between token \
-                     aliases (or addresses) and their conversion rates in gwei",
+                .arg(EXPIRY_EPOCH.def().help(
+                    "The epoch from which the allowance can no longer be \
+                     spent.",
                 ))
         }
     }
 
-    impl CliToSdkCtxless<BridgePoolProof<SdkTypes>> for BridgePoolProof<CliTypes> {
-        fn to_sdk_ctxless(self) -> BridgePoolProof<SdkTypes> {
-            BridgePoolProof::<SdkTypes> {
-                ledger_address: self.ledger_address,
-                transfers: self.transfers,
-                relayer: self.relayer,
+    impl CliToSdk<TxTransferFrom<SdkTypes>> for TxTransferFrom<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxTransferFrom<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxTransferFrom::<SdkTypes> {
+                tx,
+                spender: chain_ctx.get(&self.spender),
+                source: chain_ctx.get(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for BridgePoolProof<CliTypes> {
+    impl Args for TxTransferFrom<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let hashes = HASH_LIST.parse(matches);
-            let relayer = RELAYER.parse(matches);
+            let tx = Tx::parse(matches);
+            let spender = SPENDER.parse(matches);
+            let source = SOURCE.parse(matches);
+            let target = TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_TRANSFER_FROM_WASM);
             Self {
-                ledger_address,
-                transfers: hashes
-                    .split_whitespace()
-                    .map(|hash| {
-                        KeccakHash::try_from(hash).unwrap_or_else(|_| {
-                            tracing::info!(
-                                "Could not parse '{}' as a Keccak hash.",
-                                hash
-                            );
-                            safe_exit(1)
-                        })
-                    })
-                    .collect(),
-                relayer,
+                tx,
+                spender,
+                source,
+                target,
+                token,
+                amount,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
-                .arg(HASH_LIST.def().help(
-                    "Whitespace separated Keccak hash list of transfers in \
-                     the Bridge pool.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SPENDER.def().help(
+                    "The address spending its allowance. Its key is used to \
+                     produce the signature.",
                 ))
-                .arg(
-                    RELAYER
-                        .def()
-                        .help("The rewards address for relaying this proof."),
-                )
-        }
-    }
-
-    impl CliToSdkCtxless<RelayBridgePoolProof<SdkTypes>>
-        for RelayBridgePoolProof<CliTypes>
-    {
-        fn to_sdk_ctxless(self) -> RelayBridgePoolProof<SdkTypes> {
-            RelayBridgePoolProof::<SdkTypes> {
-                ledger_address: self.ledger_address,
-                transfers: self.transfers,
-                relayer: self.relayer,
-                confirmations: self.confirmations,
-                eth_rpc_endpoint: (),
-                gas: self.gas,
-                gas_price: self.gas_price,
-                eth_addr: self.eth_addr,
-                sync: self.sync,
-                safe_mode: self.safe_mode,
-            }
+                .arg(SOURCE.def().help("The owner of the transferred tokens."))
+                .arg(TARGET.def().help("The address receiving the tokens."))
+                .arg(TOKEN.def().help("The transferred token."))
+                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
         }
     }
 
-    impl Args for RelayBridgePoolProof<CliTypes> {
-        fn parse(matches: &ArgMatches) -> Self {
-            let safe_mode = SAFE_MODE.parse(matches);
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let hashes = HASH_LIST.parse(matches);
-            let relayer = RELAYER.parse(matches);
-            let gas = ETH_GAS.parse(matches);
-            let gas_price = ETH_GAS_PRICE.parse(matches);
-            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
-            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
-            let confirmations = ETH_CONFIRMATIONS.parse(matches);
-            let sync = ETH_SYNC.parse(matches);
-            Self {
-                ledger_address,
-                sync,
-                transfers: hashes
-                    .split(' ')
-                    .map(|hash| {
-                        KeccakHash::try_from(hash).unwrap_or_else(|_| {
-                            tracing::info!(
-                                "Could not parse '{}' as a Keccak hash.",
-                                hash
-                            );
-                            safe_exit(1)
-                        })
-                    })
-                    .collect(),
-                relayer,
-                gas,
-                gas_price,
-                eth_rpc_endpoint,
-                eth_addr,
-                confirmations,
-                safe_mode,
+    impl CliToSdk<TxUpdateSpendingPolicy<SdkTypes>>
+        for TxUpdateSpendingPolicy<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> TxUpdateSpendingPolicy<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxUpdateSpendingPolicy::<SdkTypes> {
+                tx,
+                addr: chain_ctx.get(&self.addr),
+                policy: self.policy.map(|path| {
+                    std::fs::read(path).expect(
+                        "Expected a file at given spending policy data path",
+                    )
+                }),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxUpdateSpendingPolicy<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let policy = DATA_PATH_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_SPENDING_POLICY_WASM);
+            Self {
+                tx,
+                addr,
+                policy,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
-                .arg(SAFE_MODE.def().help(
-                    "Safe mode overrides keyboard interrupt signals, to \
-                     ensure Ethereum transfers aren't canceled midway through.",
-                ))
-                .arg(HASH_LIST.def().help(
-                    "Whitespace separated Keccak hash list of transfers in \
-                     the Bridge pool.",
-                ))
-                .arg(
-                    RELAYER
-                        .def()
-                        .help("The rewards address for relaying this proof."),
-                )
-                .arg(ETH_ADDRESS_OPT.def().help(
-                    "The address of the Ethereum wallet to pay the gas fees. \
-                     If unset, the default wallet is used.",
-                ))
-                .arg(ETH_GAS.def().help(
-                    "The Ethereum gas that can be spent during the relay call.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ADDRESS.def().help(
+                    "The address of the account whose spending policy to \
+                     update.",
                 ))
-                .arg(
-                    ETH_GAS_PRICE.def().help(
-                        "The price of Ethereum gas, during the relay call.",
-                    ),
-                )
-                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
-                .arg(
-                    ETH_CONFIRMATIONS
-                        .def()
-                        .help("The number of block confirmations on Ethereum."),
-                )
-                .arg(ETH_SYNC.def().help(
-                    "Synchronize with the network, or exit immediately, if \
-                     the Ethereum node has fallen behind.",
+                .arg(DATA_PATH_OPT.def().help(
+                    "The path to a JSON file with the new spending policy. \
+                     When not given, the spending policy is removed.",
                 ))
         }
     }
 
-    impl CliToSdkCtxless<BridgeValidatorSet<SdkTypes>>
-        for BridgeValidatorSet<CliTypes>
-    {
-        fn to_sdk_ctxless(self) -> BridgeValidatorSet<SdkTypes> {
-            BridgeValidatorSet::<SdkTypes> {
-                ledger_address: self.ledger_address,
-                epoch: self.epoch,
+    impl CliToSdk<TxQueueWithdrawal<SdkTypes>> for TxQueueWithdrawal<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxQueueWithdrawal<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxQueueWithdrawal::<SdkTypes> {
+                tx,
+                source: chain_ctx.get(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for BridgeValidatorSet<CliTypes> {
+    impl Args for TxQueueWithdrawal<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let epoch = EPOCH.parse(matches);
+            let tx = Tx::parse(matches);
+            let source = SOURCE.parse(matches);
+            let target = TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_QUEUE_WITHDRAWAL_WASM);
             Self {
-                ledger_address,
-                epoch,
+                tx,
+                source,
+                target,
+                token,
+                amount,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
-                .arg(EPOCH.def().help(
-                    "The epoch of the Bridge set of validators to query.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SOURCE.def().help(
+                    "The source account address. The source's key is used to \
+                     produce the signature.",
                 ))
+                .arg(TARGET.def().help("The address receiving the tokens."))
+                .arg(TOKEN.def().help("The withdrawn token."))
+                .arg(AMOUNT.def().help("The amount to withdraw in decimal."))
         }
     }
 
-    impl CliToSdkCtxless<GovernanceValidatorSet<SdkTypes>>
-        for GovernanceValidatorSet<CliTypes>
+    impl CliToSdk<TxExecuteWithdrawal<SdkTypes>>
+        for TxExecuteWithdrawal<CliTypes>
     {
-        fn to_sdk_ctxless(self) -> GovernanceValidatorSet<SdkTypes> {
-            GovernanceValidatorSet::<SdkTypes> {
-                ledger_address: self.ledger_address,
-                epoch: self.epoch,
+        fn to_sdk(self, ctx: &mut Context) -> TxExecuteWithdrawal<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxExecuteWithdrawal::<SdkTypes> {
+                tx,
+                source: chain_ctx.get(&self.source),
+                id: self.id,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for GovernanceValidatorSet<CliTypes> {
+    impl Args for TxExecuteWithdrawal<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let epoch = EPOCH.parse(matches);
+            let tx = Tx::parse(matches);
+            let source = SOURCE.parse(matches);
+            let id = WITHDRAWAL_ID.parse(matches);
+            let tx_code_path = PathBuf::from(TX_EXECUTE_WITHDRAWAL_WASM);
             Self {
-                ledger_address,
-                epoch,
+                tx,
+                source,
+                id,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
-                .arg(EPOCH.def().help(
-                    "The epoch of the Governance set of validators to query.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SOURCE.def().help(
+                    "The source account address of the queued withdrawal.",
                 ))
+                .arg(
+                    WITHDRAWAL_ID
+                        .def()
+                        .help("The id of the queued withdrawal to execute."),
+                )
         }
     }
 
-    impl CliToSdkCtxless<ValidatorSetProof<SdkTypes>>
-        for ValidatorSetProof<CliTypes>
+    impl CliToSdk<TxCancelWithdrawal<SdkTypes>>
+        for TxCancelWithdrawal<CliTypes>
     {
-        fn to_sdk_ctxless(self) -> ValidatorSetProof<SdkTypes> {
-            ValidatorSetProof::<SdkTypes> {
-                ledger_address: self.ledger_address,
-                epoch: self.epoch,
+        fn to_sdk(self, ctx: &mut Context) -> TxCancelWithdrawal<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxCancelWithdrawal::<SdkTypes> {
+                tx,
+                source: chain_ctx.get(&self.source),
+                id: self.id,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for ValidatorSetProof<CliTypes> {
+    impl Args for TxCancelWithdrawal<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let epoch = EPOCH.parse(matches);
+            let tx = Tx::parse(matches);
+            let source = SOURCE.parse(matches);
+            let id = WITHDRAWAL_ID.parse(matches);
+            let tx_code_path = PathBuf::from(TX_CANCEL_WITHDRAWAL_WASM);
             Self {
-                ledger_address,
-                epoch,
+                tx,
+                source,
+                id,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SOURCE.def().help(
+                    "The source account address of the queued withdrawal.",
+                ))
                 .arg(
-                    EPOCH.def().help(
-                        "The epoch of the set of validators to be proven.",
-                    ),
+                    WITHDRAWAL_ID
+                        .def()
+                        .help("The id of the queued withdrawal to cancel."),
                 )
         }
     }
 
-    impl CliToSdkCtxless<ValidatorSetUpdateRelay<SdkTypes>>
-        for ValidatorSetUpdateRelay<CliTypes>
-    {
-        fn to_sdk_ctxless(self) -> ValidatorSetUpdateRelay<SdkTypes> {
-            ValidatorSetUpdateRelay::<SdkTypes> {
-                daemon: self.daemon,
-                ledger_address: self.ledger_address,
-                confirmations: self.confirmations,
-                eth_rpc_endpoint: (),
-                epoch: self.epoch,
-                gas: self.gas,
-                gas_price: self.gas_price,
-                eth_addr: self.eth_addr,
-                sync: self.sync,
-                retry_dur: self.retry_dur,
-                success_dur: self.success_dur,
-                safe_mode: self.safe_mode,
+    impl CliToSdk<TxUpdateGuardians<SdkTypes>> for TxUpdateGuardians<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxUpdateGuardians<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxUpdateGuardians::<SdkTypes> {
+                tx,
+                addr: chain_ctx.get(&self.addr),
+                guardians: self
+                    .guardians
+                    .iter()
+                    .map(|guardian| chain_ctx.get(guardian))
+                    .collect(),
+                threshold: self.threshold,
+                delay: self.delay,
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for ValidatorSetUpdateRelay<CliTypes> {
+    impl Args for TxUpdateGuardians<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
-            let ledger_address = LEDGER_ADDRESS.parse(matches);
-            let safe_mode = SAFE_MODE.parse(matches);
-            let daemon = DAEMON_MODE.parse(matches);
-            let epoch = EPOCH.parse(matches);
-            let gas = ETH_GAS.parse(matches);
-            let gas_price = ETH_GAS_PRICE.parse(matches);
-            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
-            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
-            let confirmations = ETH_CONFIRMATIONS.parse(matches);
-            let sync = ETH_SYNC.parse(matches);
-            let retry_dur =
-                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
-            let success_dur =
-                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let guardians = GUARDIANS.parse(matches);
+            let threshold = THRESHOLD.parse(matches);
+            let delay = RECOVERY_DELAY.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_GUARDIANS_WASM);
             Self {
-                ledger_address,
-                sync,
-                daemon,
-                epoch,
-                gas,
-                gas_price,
-                confirmations,
-                eth_rpc_endpoint,
-                eth_addr,
-                retry_dur,
-                success_dur,
-                safe_mode,
+                tx,
+                addr,
+                guardians,
+                threshold,
+                delay,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(LEDGER_ADDRESS.def().help(LEDGER_ADDRESS_ABOUT))
-                .arg(SAFE_MODE.def().help(
-                    "Safe mode overrides keyboard interrupt signals, to \
-                     ensure Ethereum transfers aren't canceled midway through.",
-                ))
-                .arg(DAEMON_MODE.def().help(
-                    "Run in daemon mode, which will continuously perform \
-                     validator set updates.",
-                ))
-                .arg(DAEMON_MODE_RETRY_DUR.def().help(
-                    "The amount of time to sleep between failed daemon mode \
-                     relays.",
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    ADDRESS
+                        .def()
+                        .help("The address of the account to be guarded."),
+                )
+                .arg(GUARDIANS.def().help(
+                    "The addresses of the guardians. The guardians are \
+                     removed if none are given.",
                 ))
-                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(
-                    "The amount of time to sleep between successful daemon \
-                     mode relays.",
+                .arg(THRESHOLD.def().help(
+                    "The minimum number of guardians needed to initiate a \
+                     recovery. Required if more than one guardian is given.",
                 ))
-                .arg(ETH_ADDRESS_OPT.def().help(
-                    "The address of the Ethereum wallet to pay the gas fees. \
-                     If unset, the default wallet is used.",
+                .arg(RECOVERY_DELAY.def().help(
+                    "The number of epochs a recovery has to wait before it \
+                     can be finalized, during which it can be cancelled with \
+                     the account's keys.",
                 ))
+        }
+    }
+
+    impl CliToSdk<TxInitiateRecovery<SdkTypes>>
+        for TxInitiateRecovery<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> TxInitiateRecovery<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxInitiateRecovery::<SdkTypes> {
+                tx,
+                addr: chain_ctx.get(&self.addr),
+                public_keys: self
+                    .public_keys
+                    .iter()
+                    .map(|pk| chain_ctx.get(pk))
+                    .collect(),
+                threshold: self.threshold,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxInitiateRecovery<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let public_keys = PUBLIC_KEYS.parse(matches);
+            let threshold = THRESHOLD.parse(matches);
+            let tx_code_path = PathBuf::from(TX_INITIATE_RECOVERY_WASM);
+            Self {
+                tx,
+                addr,
+                public_keys,
+                threshold,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
                 .arg(
-                    EPOCH
+                    ADDRESS
                         .def()
-                        .help("The epoch of the set of validators to relay."),
+                        .help("The address of the account to be recovered."),
                 )
-                .arg(ETH_GAS.def().help(
-                    "The Ethereum gas that can be spent during the relay call.",
+                .arg(PUBLIC_KEYS.def().help(
+                    "A list of public keys replacing the keys of the account \
+                     once the recovery is finalized.",
                 ))
-                .arg(
-                    ETH_GAS_PRICE.def().help(
-                        "The price of Ethereum gas, during the relay call.",
-                    ),
-                )
-                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
-                .arg(
-                    ETH_CONFIRMATIONS
-                        .def()
-                        .help("The number of block confirmations on Ethereum."),
-                )
-                .arg(ETH_SYNC.def().help(
-                    "Synchronize with the network, or exit immediately, if \
-                     the Ethereum node has fallen behind.",
+                .arg(THRESHOLD.def().help(
+                    "The minimum number of signature to be provided for \
+                     authorization after the recovery. Must be less then the \
+                     maximum number of public keys provided.",
                 ))
         }
     }
 
-    impl CliToSdk<TxCustom<SdkTypes>> for TxCustom<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> TxCustom<SdkTypes> {
-            TxCustom::<SdkTypes> {
-                tx: self.tx.to_sdk(ctx),
-                code_path: self.code_path,
-                data_path: self.data_path.map(|data_path| {
-                    std::fs::read(data_path)
-                        .expect("Expected a file at given path")
-                }),
-                serialized_tx: self.serialized_tx.map(|path| {
-                    std::fs::read(path).expect("Expected a file at given path")
-                }),
-                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+    impl CliToSdk<TxCancelRecovery<SdkTypes>> for TxCancelRecovery<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxCancelRecovery<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxCancelRecovery::<SdkTypes> {
+                tx,
+                addr: chain_ctx.get(&self.addr),
+                tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for TxCustom<CliTypes> {
+    impl Args for TxCancelRecovery<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
-            let code_path = CODE_PATH_OPT.parse(matches);
-            let data_path = DATA_PATH_OPT.parse(matches);
-            let serialized_tx = TX_PATH_OPT.parse(matches);
-            let owner = OWNER.parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let tx_code_path = PathBuf::from(TX_CANCEL_RECOVERY_WASM);
             Self {
                 tx,
-                code_path,
-                data_path,
-                serialized_tx,
-                owner,
+                addr,
+                tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Tx<CliTypes>>()
-                .arg(
-                    CODE_PATH_OPT
-                        .def()
-                        .help("The path to the transaction's WASM code.")
-                        .conflicts_with(TX_PATH_OPT.name),
-                )
-                .arg(
-                    DATA_PATH_OPT
-                        .def()
-                        .help(
-                            "The data file at this path containing arbitrary \
-                             bytes will be passed to the transaction code \
-                             when it's executed.",
-                        )
-                        .requires(CODE_PATH_OPT.name)
-                        .conflicts_with(TX_PATH_OPT.name),
-                )
-                .arg(
-                    TX_PATH_OPT
-                        .def()
-                        .help("The path to a serialized transaction.")
-                        .conflicts_with_all([
-                            CODE_PATH_OPT.name,
-                            DATA_PATH_OPT.name,
-                        ]),
-                )
-                .arg(OWNER.def().help(
-                    "The address corresponding to the signatures or signing \
-                     keys.",
-                ))
+            app.add_args::<Tx<CliTypes>>().arg(
+                ADDRESS
+                    .def()
+                    .help("The address of the account under recovery."),
+            )
         }
     }
 
-    impl CliToSdk<TxTransfer<SdkTypes>> for TxTransfer<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> TxTransfer<SdkTypes> {
+    impl CliToSdk<TxFinalizeRecovery<SdkTypes>>
+        for TxFinalizeRecovery<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> TxFinalizeRecovery<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
-            let chain_ctx = ctx.borrow_mut_chain_or_exit();
-            TxTransfer::<SdkTypes> {
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            TxFinalizeRecovery::<SdkTypes> {
                 tx,
-                source: chain_ctx.get_cached(&self.source),
-                target: chain_ctx.get(&self.target),
-                token: chain_ctx.get(&self.token),
-                amount: self.amount,
+                addr: chain_ctx.get(&self.addr),
                 tx_code_path: self.tx_code_path.to_path_buf(),
             }
         }
     }
 
-    impl Args for TxTransfer<CliTypes> {
+    impl Args for TxFinalizeRecovery<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
-            let source = TRANSFER_SOURCE.parse(matches);
-            let target = TRANSFER_TARGET.parse(matches);
-            let token = TOKEN.parse(matches);
-            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
-            let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
+            let addr = ADDRESS.parse(matches);
+            let tx_code_path = PathBuf::from(TX_FINALIZE_RECOVERY_WASM);
             Self {
                 tx,
-                source,
-                target,
-                token,
-                amount,
+                addr,
                 tx_code_path,
             }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Tx<CliTypes>>()
-                .arg(TRANSFER_SOURCE.def().help(
-                    "The source account address. The source's key may be used \
-                     to produce the signature.",
-                ))
-                .arg(TRANSFER_TARGET.def().help(
-                    "The target account address. The target's key may be used \
-                     to produce the signature.",
-                ))
-                .arg(TOKEN.def().help("The transfer token."))
-                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
+            app.add_args::<Tx<CliTypes>>().arg(
+                ADDRESS
+                    .def()
+                    .help("The address of the account under recovery."),
+            )
         }
     }
 
@@ -4053,6 +5409,8 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                weights: self.weights,
+                weight_threshold: self.weight_threshold,
             }
         }
     }
@@ -4066,11 +5424,15 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_INIT_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let weights = WEIGHTS.parse(matches);
+            let weight_threshold = WEIGHT_THRESHOLD.parse(matches);
             Self {
                 tx,
                 vp_code_path,
                 public_keys,
                 threshold,
+                weights,
+                weight_threshold,
                 tx_code_path,
             }
         }
@@ -4091,6 +5453,21 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided.",
                 ))
+                .arg(
+                    WEIGHTS
+                        .def()
+                        .help(
+                            "The weights of the account's public keys, in \
+                             the order of the keys. Every key must be \
+                             weighted.",
+                        )
+                        .requires(WEIGHT_THRESHOLD.name),
+                )
+                .arg(WEIGHT_THRESHOLD.def().help(
+                    "The minimum total weight of the signatures to be \
+                     provided for authorization, replacing the signature \
+                     threshold.",
+                ))
         }
     }
 
@@ -4378,6 +5755,8 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                weights: self.weights,
+                weight_threshold: self.weight_threshold,
             }
         }
     }
@@ -4390,6 +5769,8 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_UPDATE_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let weights = WEIGHTS.parse(matches);
+            let weight_threshold = WEIGHT_THRESHOLD.parse(matches);
             Self {
                 tx,
                 vp_code_path,
@@ -4397,6 +5778,8 @@ pub mod args {
                 tx_code_path,
                 public_keys,
                 threshold,
+                weights,
+                weight_threshold,
             }
         }
 
@@ -4420,6 +5803,22 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided.",
                 ))
+                .arg(
+                    WEIGHTS
+                        .def()
+                        .help(
+                            "The weights of the account's public keys, in \
+                             the order of the keys. Every key must be \
+                             weighted.",
+                        )
+                        .requires(WEIGHT_THRESHOLD.name),
+                )
+                .arg(WEIGHT_THRESHOLD.def().help(
+                    "The minimum total weight of the signatures to be \
+                     provided for authorization, replacing the signature \
+                     threshold. Without weights, it removes the weighting of \
+                     the account.",
+                ))
         }
     }
 
@@ -4662,6 +6061,7 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_shielded_rewards: self.is_shielded_rewards,
                 tx_code_path: self.tx_code_path,
             }
         }
@@ -4674,6 +6074,7 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_shielded_rewards = PROPOSAL_SHIELDED_REWARDS.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4683,6 +6084,7 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_shielded_rewards,
             }
         }
 
@@ -4702,6 +6104,7 @@ pub mod args {
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
                             PROPOSAL_ETH.name,
+                            PROPOSAL_SHIELDED_REWARDS.name,
                         ]),
                 )
                 .arg(
@@ -4711,6 +6114,7 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_SHIELDED_REWARDS.name,
                         ]),
                 )
                 .arg(
@@ -4723,6 +6127,7 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_SHIELDED_REWARDS.name,
                         ]),
                 )
                 .arg(
@@ -4735,6 +6140,21 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_SHIELDED_REWARDS.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_SHIELDED_REWARDS
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type \
+                             shielded-rewards. Used to update the shielded \
+                             rewards parameters of tokens.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
                         ]),
                 )
         }
@@ -5147,6 +6567,107 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<DiscoverAccounts<SdkTypes>> for DiscoverAccounts<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> DiscoverAccounts<SdkTypes> {
+            DiscoverAccounts::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                scheme: self.scheme,
+                alias: self.alias,
+                alias_force: self.alias_force,
+                unsafe_dont_encrypt: self.unsafe_dont_encrypt,
+                derivation_path: self.derivation_path,
+                allow_non_compliant: self.allow_non_compliant,
+                prompt_bip39_passphrase: self.prompt_bip39_passphrase,
+                xpub: self.xpub,
+                gap_limit: self.gap_limit,
+            }
+        }
+    }
+
+    impl Args for DiscoverAccounts<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let scheme = SCHEME.parse(matches);
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let derivation_path = HD_DERIVATION_PATH.parse(matches);
+            let allow_non_compliant =
+                HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH.parse(matches);
+            let prompt_bip39_passphrase =
+                HD_PROMPT_BIP39_PASSPHRASE.parse(matches);
+            let xpub = XPUB.parse(matches);
+            let gap_limit = GAP_LIMIT.parse(matches);
+            Self {
+                query,
+                scheme,
+                alias,
+                alias_force,
+                unsafe_dont_encrypt,
+                derivation_path,
+                allow_non_compliant,
+                prompt_bip39_passphrase,
+                xpub,
+                gap_limit,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SCHEME.def().help(
+                    "The type of the derived keys. Argument must be one of \
+                     ed25519, secp256k1 or bls. If none provided, the default \
+                     key scheme is ed25519.",
+                ))
+                .arg(ALIAS.def().help(
+                    "The alias prefix of the imported keys and addresses, \
+                     suffixed by their address index.",
+                ))
+                .arg(
+                    ALIAS_FORCE
+                        .def()
+                        .help("Force overwrite the aliases if they exist."),
+                )
+                .arg(UNSAFE_DONT_ENCRYPT.def().help(
+                    "UNSAFE: Do not encrypt the keypairs. Do not use this for \
+                     keys used in a live network.",
+                ))
+                .arg(HD_DERIVATION_PATH.def().help(
+                    "HD key derivation path of the first address. Its last \
+                     index is replaced by successive address indices. Use \
+                     keyword `default` to refer to a scheme default path:\n- \
+                     m/44'/60'/0'/0/0 for the transparent secp256k1 \
+                     scheme\n- m/44'/877'/0'/0'/0' for the transparent \
+                     ed25519 scheme",
+                ))
+                .arg(
+                    HD_ALLOW_NON_COMPLIANT_DERIVATION_PATH
+                        .def()
+                        .help("Allow non-compliant HD derivation path."),
+                )
+                .arg(HD_PROMPT_BIP39_PASSPHRASE.def().help(
+                    "Use an additional passphrase for HD-key derivation.",
+                ))
+                .arg(
+                    XPUB.def()
+                        .help(
+                            "Derive the public keys of the addresses from \
+                             this secp256k1 extended public key, in \
+                             watch-only mode, instead of the mnemonic code.",
+                        )
+                        .conflicts_with_all([
+                            HD_DERIVATION_PATH.name,
+                            HD_PROMPT_BIP39_PASSPHRASE.name,
+                            UNSAFE_DONT_ENCRYPT.name,
+                        ]),
+                )
+                .arg(GAP_LIMIT.def().help(
+                    "The number of consecutive unused addresses after which \
+                     the discovery stops. Defaults to 20.",
+                ))
+        }
+    }
+
     impl CliToSdk<QueryBalance<SdkTypes>> for QueryBalance<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryBalance<SdkTypes> {
             let query = self.query.to_sdk(ctx);
@@ -5939,6 +7460,9 @@ pub mod args {
                 fee_unshield: self
                     .fee_unshield
                     .map(|ref fee_unshield| ctx.get_cached(fee_unshield)),
+                masp_fee: self
+                    .masp_fee
+                    .map(|ref masp_fee| ctx.get_cached(masp_fee)),
                 gas_limit: self.gas_limit,
                 signing_keys: self
                     .signing_keys
@@ -5960,6 +7484,7 @@ pub mod args {
                 wrapper_fee_payer: self.wrapper_fee_payer.map(|x| ctx.get(&x)),
                 memo: self.memo,
                 use_device: self.use_device,
+                signer_socket: self.signer_socket,
             }
         }
     }
@@ -6011,6 +7536,20 @@ pub mod args {
                 "The spending key to be used for fee unshielding. If none is \
                  provided, fee will be paid from the unshielded balance only.",
             ))
+            .arg(
+                FEE_MASP_SPENDING_KEY
+                    .def()
+                    .help(
+                        "The spending key to pay the fee directly from the \
+                         shielded pool. The wrapper transaction is then \
+                         signed with an ephemeral keypair that holds no funds \
+                         and is not stored in the wallet.",
+                    )
+                    .conflicts_with_all([
+                        FEE_UNSHIELD_SPENDING_KEY.name,
+                        FEE_PAYER_OPT.name,
+                    ]),
+            )
             .arg(GAS_LIMIT.def().help(
                 "The multiplier of the gas limit resolution defining the \
                  maximum amount of gas needed to run transaction.",
@@ -6073,6 +7612,16 @@ pub mod args {
                 "Use an attached hardware wallet device to sign the \
                  transaction.",
             ))
+            .arg(
+                SIGNER_SOCKET
+                    .def()
+                    .help(
+                        "The path to the socket of a remote signing daemon \
+                         to sign the transaction with the keys missing from \
+                         the wallet.",
+                    )
+                    .conflicts_with(USE_DEVICE.name),
+            )
             .arg(
                 MEMO_OPT
                     .def()
@@ -6092,6 +7641,7 @@ pub mod args {
                 FEE_AMOUNT_OPT.parse(matches).map(InputAmount::Unvalidated);
             let fee_token = FEE_TOKEN.parse(matches);
             let fee_unshield = FEE_UNSHIELD_SPENDING_KEY.parse(matches);
+            let masp_fee = FEE_MASP_SPENDING_KEY.parse(matches);
             let _wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
             let gas_limit = GAS_LIMIT.parse(matches);
             let wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
@@ -6106,6 +7656,7 @@ pub mod args {
             let wrapper_fee_payer = FEE_PAYER_OPT.parse(matches);
             let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
             let use_device = USE_DEVICE.parse(matches);
+            let signer_socket = SIGNER_SOCKET.parse(matches);
             Self {
                 dry_run,
                 dry_run_wrapper,
@@ -6118,6 +7669,7 @@ pub mod args {
                 fee_amount,
                 fee_token,
                 fee_unshield,
+                masp_fee,
                 gas_limit,
                 expiration,
                 disposable_signing_key,
@@ -6130,6 +7682,7 @@ pub mod args {
                 output_folder,
                 memo,
                 use_device,
+                signer_socket,
             }
         }
     }
@@ -6279,8 +7832,8 @@ pub mod args {
         fn def(app: App) -> App {
             app.arg(SCHEME.def().conflicts_with(SHIELDED.name).help(
                 "For the transparent pool, the type of key that should be \
-                 derived. Argument must be either ed25519 or secp256k1. If \
-                 none provided, the default key scheme is ed25519.\nNot \
+                 derived. Argument must be one of ed25519, secp256k1 or bls. \
+                 If none provided, the default key scheme is ed25519.\nNot \
                  applicable for the shielded pool.",
             ))
             .arg(
@@ -6362,8 +7915,10 @@ pub mod args {
         fn def(app: App) -> App {
             app.arg(SCHEME.def().conflicts_with(SHIELDED.name).help(
                 "For the transparent pool, the type of key that should be \
-                 generated. Argument must be either ed25519 or secp256k1. If \
-                 none provided, the default key scheme is ed25519.\nNot \
+                 generated. Argument must be one of ed25519, secp256k1 or \
+                 bls. If none provided, the default key scheme is ed25519. \
+                 BLS keys can have their signatures aggregated in \
+                 multisignature accounts.\nNot \
                  applicable for the shielded pool.",
             ))
             .arg(
@@ -6419,6 +7974,31 @@ pub mod args {
         }
     }
 
+    impl Args for KeyXpub {
+        fn parse(matches: &ArgMatches) -> Self {
+            let derivation_path = HD_DERIVATION_PATH.parse(matches);
+            let prompt_bip39_passphrase =
+                HD_PROMPT_BIP39_PASSPHRASE.parse(matches);
+            Self {
+                derivation_path,
+                prompt_bip39_passphrase,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(HD_DERIVATION_PATH.def().help(
+                "HD key derivation path of the extended public key. Use \
+                 keyword `default` to refer to the parent of the default \
+                 secp256k1 path, m/44'/60'/0'/0.",
+            ))
+            .arg(
+                HD_PROMPT_BIP39_PASSPHRASE.def().help(
+                    "Use an additional passphrase for HD-key derivation.",
+                ),
+            )
+        }
+    }
+
     impl Args for KeyAddressList {
         fn parse(matches: &ArgMatches) -> Self {
             let transparent_only = TRANSPARENT.parse(matches);
@@ -6605,6 +8185,37 @@ pub mod args {
         }
     }
 
+    impl Args for WalletBackup {
+        fn parse(matches: &ArgMatches) -> Self {
+            let file_path = FILE_PATH.parse(matches);
+            Self { file_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                FILE_PATH
+                    .def()
+                    .help("Path of the backup file to be created."),
+            )
+        }
+    }
+
+    impl Args for WalletRestore {
+        fn parse(matches: &ArgMatches) -> Self {
+            let file_path = FILE_PATH.parse(matches);
+            let force = FORCE.parse(matches);
+            Self { file_path, force }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(FILE_PATH.def().help("Path of the backup file."))
+                .arg(FORCE.def().help(
+                    "Replace the existing aliases that the backup would \
+                     override.",
+                ))
+        }
+    }
+
     impl Args for KeyImport {
         fn parse(matches: &ArgMatches) -> Self {
             let file_path = FILE_PATH.parse(matches);
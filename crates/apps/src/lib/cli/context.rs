@@ -570,13 +570,18 @@ impl ArgFromMutContext for TransferSource {
         raw: impl AsRef<str>,
     ) -> Result<Self, String> {
         let raw = raw.as_ref();
-        // Either the string is a transparent address or a spending key
+        // Either the string is a transparent address, a spending key or a
+        // viewing key
         Address::arg_from_ctx(ctx, raw)
             .map(Self::Address)
             .or_else(|_| {
                 ExtendedSpendingKey::arg_from_mut_ctx(ctx, raw)
                     .map(Self::ExtendedSpendingKey)
             })
+            .or_else(|_| {
+                ExtendedViewingKey::arg_from_mut_ctx(ctx, raw)
+                    .map(Self::ExtendedViewingKey)
+            })
     }
 }
 
@@ -18,7 +18,8 @@ use namada::types::key::*;
 use namada::types::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
 use namada_sdk::masp::find_valid_diversifier;
 use namada_sdk::wallet::{
-    DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
+    DecryptionError, DerivationPath, DerivationPathError, ExtendedPublicKey,
+    FindKeyError, Wallet, WalletBackup, WalletIo,
 };
 use namada_sdk::{display_line, edisplay_line};
 use rand_core::OsRng;
@@ -45,6 +46,9 @@ impl CliApi {
             cmds::NamadaWallet::KeyDerive(cmds::WalletDerive(args)) => {
                 key_derive(ctx, io, args).await
             }
+            cmds::NamadaWallet::KeyXpub(cmds::WalletXpub(args)) => {
+                key_xpub(io, args)
+            }
             cmds::NamadaWallet::KeyAddrList(cmds::WalletListKeysAddresses(
                 args,
             )) => key_address_list(ctx, io, args),
@@ -57,6 +61,12 @@ impl CliApi {
             cmds::NamadaWallet::KeyImport(cmds::WalletImportKey(args)) => {
                 key_import(ctx, io, args)
             }
+            cmds::NamadaWallet::Backup(cmds::WalletBackup(args)) => {
+                wallet_backup(ctx, io, args)
+            }
+            cmds::NamadaWallet::Restore(cmds::WalletRestore(args)) => {
+                wallet_restore(ctx, io, args)
+            }
             cmds::NamadaWallet::KeyAddrAdd(cmds::WalletAddKeyAddress(args)) => {
                 key_address_add(ctx, io, args)
             }
@@ -414,6 +424,41 @@ pub fn decode_transparent_derivation_path(
     Ok(parsed_derivation_path)
 }
 
+/// Derive a secp256k1 extended public key from the mnemonic code and print
+/// it.
+fn key_xpub(
+    io: &impl Io,
+    args::KeyXpub {
+        derivation_path,
+        prompt_bip39_passphrase,
+    }: args::KeyXpub,
+) {
+    let derivation_path = if derivation_path.eq_ignore_ascii_case("DEFAULT") {
+        DerivationPath::default_for_transparent_scheme(SchemeType::Secp256k1)
+            .parent()
+            .expect("The default derivation path is not empty")
+    } else {
+        DerivationPath::from_path_string(&derivation_path).unwrap_or_else(
+            |err| {
+                edisplay_line!(io, "{}", err);
+                cli::safe_exit(1)
+            },
+        )
+    };
+    println!("Using HD derivation path {}", derivation_path);
+    let seed = Wallet::<CliWalletUtils>::read_hd_seed(prompt_bip39_passphrase)
+        .unwrap_or_else(|| {
+            edisplay_line!(io, "Failed to read the mnemonic code.");
+            cli::safe_exit(1)
+        });
+    let xpub = ExtendedPublicKey::from_seed(seed.as_bytes(), &derivation_path)
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            cli::safe_exit(1)
+        });
+    display_line!(io, "Extended public key: {}", xpub);
+}
+
 /// Decode the derivation path from the given string unless it is "default",
 /// in which case use the default derivation path for the shielded setting.
 pub fn decode_shielded_derivation_path(
@@ -1289,6 +1334,63 @@ fn key_import(
     }
 }
 
+/// Write an encrypted backup of the whole wallet to a file.
+fn wallet_backup(
+    ctx: Context,
+    io: &impl Io,
+    args::WalletBackup { file_path }: args::WalletBackup,
+) {
+    let wallet = load_wallet(ctx);
+    let password = CliWalletUtils::read_password(true);
+    let backup = wallet.backup(password).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    // Never overwrite an existing file, it may be an older backup
+    let mut file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(&file_path)
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "Failed to create {}: {}", file_path, err);
+            cli::safe_exit(1)
+        });
+    file.write_all(&backup.encode()).unwrap_or_else(|err| {
+        edisplay_line!(io, "Failed to write {}: {}", file_path, err);
+        cli::safe_exit(1)
+    });
+    display_line!(io, "Backed up the wallet to file {}", file_path);
+}
+
+/// Restore the wallet from an encrypted backup file.
+fn wallet_restore(
+    ctx: Context,
+    io: &impl Io,
+    args::WalletRestore { file_path, force }: args::WalletRestore,
+) {
+    let file_data = std::fs::read(&file_path).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        display_line!(io, "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+    let backup = WalletBackup::decode(&file_data).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        display_line!(io, "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+    let mut wallet = load_wallet(ctx);
+    let password = CliWalletUtils::read_password(false);
+    wallet
+        .restore(&backup, password, force)
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            display_line!(io, "No changes are persisted. Exiting.");
+            cli::safe_exit(1)
+        });
+    wallet.save().unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(io, "Restored the wallet from file {}", file_path);
+}
+
 /// List all known transparent addresses.
 fn transparent_addresses_list(
     wallet: &Wallet<CliWalletUtils>,
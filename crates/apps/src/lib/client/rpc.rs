@@ -52,7 +52,9 @@ use namada::{state as storage, token};
 use namada_sdk::error::{
     is_pinned_error, Error, PinnedBalanceError, QueryError,
 };
-use namada_sdk::masp::{Conversions, MaspChange, MaspTokenRewardData};
+use namada_sdk::masp::{
+    Conversions, MaspChange, MaspStats, MaspTokenRewardData, MaspTokenStats,
+};
 use namada_sdk::proof_of_stake::types::ValidatorMetaData;
 use namada_sdk::queries::Client;
 use namada_sdk::rpc::{
@@ -60,13 +62,15 @@ use namada_sdk::rpc::{
 };
 use namada_sdk::tendermint_rpc::endpoint::status;
 use namada_sdk::tx::{display_inner_resp, display_wrapper_resp_and_get_result};
-use namada_sdk::wallet::AddressVpType;
+use namada_sdk::wallet::discovery::discover_keys;
+use namada_sdk::wallet::{AddressVpType, KeySource, Wallet};
 use namada_sdk::{display, display_line, edisplay_line, error, prompt, Namada};
 use tokio::time::Instant;
 
 use crate::cli::{self, args};
 use crate::facade::tendermint::merkle::proof::ProofOps;
 use crate::facade::tendermint_rpc::error::Error as TError;
+use crate::wallet::{read_and_confirm_encryption_password, CliWalletUtils};
 
 /// Query the status of a given transaction.
 ///
@@ -702,7 +706,14 @@ async fn lookup_token_alias(
             Err(_) => token.to_string(),
         }
     } else {
-        context.wallet().await.lookup_alias(token)
+        if let Some(alias) = context.wallet().await.find_alias(token) {
+            return alias.to_string();
+        }
+        // Fall back to the on-chain symbol of the token
+        match rpc::query_token_metadata(context.client(), token).await {
+            Ok(Some(metadata)) => metadata.symbol,
+            _ => token.to_string(),
+        }
     }
 }
 
@@ -1328,15 +1339,115 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     if let Some(account) = account {
         display_line!(context.io(), "Address: {}", account.address);
         display_line!(context.io(), "Threshold: {}", account.threshold);
+        if let Some(weights) = &account.weights {
+            display_line!(
+                context.io(),
+                "Weight threshold: {}",
+                weights.threshold
+            );
+        }
         display_line!(context.io(), "Public keys:");
-        for (public_key, _) in account.public_keys_map.pk_to_idx {
-            display_line!(context.io(), "- {}", public_key);
+        for (public_key, index) in account.public_keys_map.pk_to_idx {
+            match &account.weights {
+                Some(weights) => display_line!(
+                    context.io(),
+                    "- {} (weight {})",
+                    public_key,
+                    weights.weight_of(index)
+                ),
+                None => display_line!(context.io(), "- {}", public_key),
+            }
         }
     } else {
         display_line!(context.io(), "No account exists for {}", args.owner);
     }
 }
 
+/// Discover the used addresses of an HD wallet and import their keys into
+/// the wallet
+pub async fn discover_accounts(
+    context: &impl Namada,
+    args: args::DiscoverAccounts,
+) {
+    let seed;
+    let source = if let Some(xpub) = &args.xpub {
+        KeySource::Xpub(xpub)
+    } else {
+        let derivation_path = cli::wallet::decode_transparent_derivation_path(
+            args.scheme,
+            args.derivation_path,
+        )
+        .unwrap_or_else(|err| {
+            edisplay_line!(context.io(), "{}", err);
+            cli::safe_exit(1)
+        });
+        if !args.allow_non_compliant
+            && !derivation_path.is_namada_transparent_compliant(args.scheme)
+        {
+            edisplay_line!(
+                context.io(),
+                "Path {} is not compliant.",
+                derivation_path
+            );
+            cli::safe_exit(1)
+        }
+        seed = Wallet::<CliWalletUtils>::read_hd_seed(
+            args.prompt_bip39_passphrase,
+        )
+        .unwrap_or_else(|| {
+            edisplay_line!(context.io(), "Failed to read the mnemonic code.");
+            cli::safe_exit(1)
+        });
+        KeySource::Seed {
+            scheme: args.scheme,
+            seed: seed.as_bytes(),
+            derivation_path,
+        }
+    };
+    let keys = discover_keys(context.client(), &source, args.gap_limit)
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(context.io(), "{}", err);
+            cli::safe_exit(1)
+        });
+    if keys.is_empty() {
+        display_line!(context.io(), "No used addresses were found.");
+        return;
+    }
+    let password = if args.xpub.is_none() {
+        read_and_confirm_encryption_password(args.unsafe_dont_encrypt)
+    } else {
+        None
+    };
+    let alias_prefix = args.alias.to_lowercase();
+    let mut wallet = context.wallet_mut().await;
+    for key in keys {
+        let alias = format!("{}-{}", alias_prefix, key.index);
+        let address = key.address.clone();
+        match wallet.insert_discovered_key(
+            alias,
+            args.alias_force,
+            key,
+            password.clone(),
+        ) {
+            Some(alias) => display_line!(
+                context.io(),
+                "Imported the used address {} with alias \"{}\"",
+                address,
+                alias
+            ),
+            None => edisplay_line!(
+                context.io(),
+                "Skipped the used address {}",
+                address
+            ),
+        }
+    }
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(context.io(), "{}", err));
+}
+
 pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
@@ -2557,6 +2668,27 @@ pub async fn query_masp_reward_tokens(context: &impl Namada) {
     }
 }
 
+/// Query the shielded pool statistics.
+pub async fn query_masp_stats(context: &impl Namada) {
+    let MaspStats {
+        tokens,
+        note_commitments,
+        nullifiers,
+        conversion_epoch,
+    } = namada_sdk::rpc::query_masp_stats(context.client())
+        .await
+        .expect("The shielded pool statistics should be available");
+    display_line!(context.io(), "Conversion epoch: {}", conversion_epoch);
+    display_line!(context.io(), "Note commitments: {}", note_commitments);
+    display_line!(context.io(), "Revealed nullifiers: {}", nullifiers);
+    display_line!(context.io(), "Shielded balances:");
+    for MaspTokenStats { address, balance } in tokens {
+        let token_alias = lookup_token_alias(context, &address, &MASP).await;
+        let balance = context.format_amount(&address, balance).await;
+        display_line!(context.io(), "  {}: {}", token_alias, balance);
+    }
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
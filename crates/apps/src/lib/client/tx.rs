@@ -12,6 +12,7 @@ use namada::governance::cli::offline::{
 };
 use namada::governance::cli::onchain::{
     DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    ShieldedRewardsProposal,
 };
 use namada::governance::ProposalVote;
 use namada::ibc::apps::transfer::types::Memo;
@@ -23,6 +24,7 @@ use namada::types::dec::Dec;
 use namada::types::io::Io;
 use namada::types::key::{self, *};
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
+use namada_sdk::signer::remote::RemoteSigner;
 use namada_sdk::wallet::alias::validator_consensus_key;
 use namada_sdk::wallet::{Wallet, WalletIo};
 use namada_sdk::{display_line, edisplay_line, error, signing, tx, Namada};
@@ -187,6 +189,16 @@ pub async fn sign<N: Namada>(
                 with_hw_data,
             )
             .await?;
+    } else if let Some(socket_path) = &args.signer_socket {
+        // Sign the keys missing from the wallet with a remote signer
+        context
+            .sign_with_signer(
+                tx,
+                args,
+                signing_data,
+                &RemoteSigner::new(socket_path),
+            )
+            .await?;
     } else {
         // Otherwise sign without a backup procedure
         context
@@ -357,7 +369,7 @@ pub async fn submit_change_consensus_key(
     let new_key = consensus_key
         .map(|key| match key {
             common::PublicKey::Ed25519(_) => key,
-            common::PublicKey::Secp256k1(_) => {
+            common::PublicKey::Secp256k1(_) | common::PublicKey::Bls(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Consensus key can only be ed25519"
@@ -578,7 +590,7 @@ pub async fn submit_become_validator(
     let consensus_key = consensus_key
         .map(|key| match key {
             common::PublicKey::Ed25519(_) => key,
-            common::PublicKey::Secp256k1(_) => {
+            common::PublicKey::Secp256k1(_) | common::PublicKey::Bls(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Consensus key can only be ed25519"
@@ -607,7 +619,7 @@ pub async fn submit_become_validator(
     let eth_cold_pk = eth_cold_key
         .map(|key| match key {
             common::PublicKey::Secp256k1(_) => key,
-            common::PublicKey::Ed25519(_) => {
+            common::PublicKey::Ed25519(_) | common::PublicKey::Bls(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Eth cold key can only be secp256k1"
@@ -636,7 +648,7 @@ pub async fn submit_become_validator(
     let eth_hot_pk = eth_hot_key
         .map(|key| match key {
             common::PublicKey::Secp256k1(_) => key,
-            common::PublicKey::Ed25519(_) => {
+            common::PublicKey::Ed25519(_) | common::PublicKey::Bls(_) => {
                 edisplay_line!(
                     namada.io(),
                     "Eth hot key can only be secp256k1"
@@ -873,6 +885,8 @@ pub async fn submit_init_validator(
             tx_code_path: tx_init_account_code_path,
             public_keys: account_keys,
             threshold,
+            weights: vec![],
+            weight_threshold: None,
         },
     )
     .await?;
@@ -1099,6 +1113,36 @@ where
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
+    } else if args.is_shielded_rewards {
+        let proposal = ShieldedRewardsProposal::try_from(
+            args.proposal_data.as_ref(),
+        )
+        .map_err(|e| {
+            error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                e.to_string(),
+            )
+        })?;
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_shielded_rewards_proposal(namada, &args, proposal).await?
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1563,6 +1607,286 @@ where
     Ok(())
 }
 
+pub async fn submit_register_token<N: Namada>(
+    namada: &N,
+    args: args::TxRegisterToken,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_mint_tokens<N: Namada>(
+    namada: &N,
+    args: args::TxMintTokens,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_burn_tokens<N: Namada>(
+    namada: &N,
+    args: args::TxBurnTokens,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_token_metadata<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateTokenMetadata,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_approve<N: Namada>(
+    namada: &N,
+    args: args::TxApprove,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_transfer_from<N: Namada>(
+    namada: &N,
+    args: args::TxTransferFrom,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_spending_policy<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateSpendingPolicy,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_queue_withdrawal<N: Namada>(
+    namada: &N,
+    args: args::TxQueueWithdrawal,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_execute_withdrawal<N: Namada>(
+    namada: &N,
+    args: args::TxExecuteWithdrawal,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_cancel_withdrawal<N: Namada>(
+    namada: &N,
+    args: args::TxCancelWithdrawal,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_guardians<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateGuardians,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_initiate_recovery<N: Namada>(
+    namada: &N,
+    args: args::TxInitiateRecovery,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_cancel_recovery<N: Namada>(
+    namada: &N,
+    args: args::TxCancelRecovery,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_finalize_recovery<N: Namada>(
+    namada: &N,
+    args: args::TxFinalizeRecovery,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 /// Save accounts initialized from a tx into the wallet, if any.
 pub async fn save_initialized_accounts(
     namada: &impl Namada,
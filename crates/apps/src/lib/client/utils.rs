@@ -20,7 +20,6 @@ use namada::vm::validate_untrusted_wasm;
 use namada_sdk::wallet::{alias, Wallet};
 use prost::bytes::Bytes;
 use serde_json::json;
-use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
 
 use crate::cli::args;
@@ -34,7 +33,6 @@ use crate::config::global::GlobalConfig;
 use crate::config::{
     self, genesis, get_default_namada_folder, Config, TendermintMode,
 };
-use crate::facade::tendermint::node::Id as TendermintNodeId;
 use crate::node::ledger::tendermint_node;
 use crate::wallet::{pre_genesis, CliWalletUtils};
 use crate::wasm_loader;
@@ -292,7 +290,10 @@ pub async fn join_network(
         let tm_home_dir = chain_dir.join(config::COMETBFT_DIR);
         // Write consensus key to tendermint home
         tendermint_node::write_validator_key(&tm_home_dir, &consensus_key)
-            .unwrap();
+            .unwrap_or_else(|err| {
+                eprintln!("Invalid consensus key: {err}.");
+                safe_exit(1)
+            });
 
         // Write tendermint node key
         write_tendermint_node_key(&tm_home_dir, tendermint_node_key);
@@ -368,27 +369,8 @@ pub fn validate_wasm(args::ValidateWasm { code_path }: args::ValidateWasm) {
     }
 }
 
-/// Length of a Tendermint Node ID in bytes
-const TENDERMINT_NODE_ID_LENGTH: usize = 20;
-
 /// Derive Tendermint node ID from public key
-pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
-    let mut bytes = [0u8; TENDERMINT_NODE_ID_LENGTH];
-
-    match pk {
-        common::PublicKey::Ed25519(_) => {
-            let _pk: ed25519::PublicKey = pk.try_to_pk().unwrap();
-            let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
-            bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
-        }
-        common::PublicKey::Secp256k1(_) => {
-            let _pk: secp256k1::PublicKey = pk.try_to_pk().unwrap();
-            let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
-            bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
-        }
-    }
-    TendermintNodeId::new(bytes)
-}
+pub use crate::node::ledger::tendermint_node::id_from_pk;
 
 /// Initialize a new test network from the given configuration.
 ///
@@ -979,6 +961,10 @@ pub fn write_tendermint_node_key(
         common::SecretKey::Secp256k1(sk) => {
             (sk.serialize_to_vec(), "Secp256k1")
         }
+        common::SecretKey::Bls(_) => {
+            eprintln!("BLS keys cannot be used as CometBFT node keys.");
+            safe_exit(1)
+        }
     };
 
     let tm_node_keypair_json = json!({
@@ -28,6 +28,7 @@ use namada::types::time::DateTimeUtc;
 use namada::types::token;
 use namada::types::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use namada_sdk::args::Tx as TxArgs;
+use namada_sdk::signer::SignWith;
 use namada_sdk::signing::{sign_tx, SigningTxData};
 use namada_sdk::tx::{TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM};
 use namada_sdk::wallet::alias::Alias;
@@ -83,6 +84,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         wrapper_fee_payer: None,
         fee_token: nam(),
         fee_unshield: None,
+        masp_fee: None,
         gas_limit: Default::default(),
         expiration: None,
         disposable_signing_key: false,
@@ -93,6 +95,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         password: None,
         memo: None,
         use_device,
+        signer_socket: None,
     }
 }
 
@@ -757,8 +760,10 @@ impl<T> Signed<T> {
                 &get_tx_args(use_device),
                 &mut tx,
                 signing_data,
-                utils::with_hardware_wallet,
-                (wallet_lock, &app),
+                &SignWith::new(
+                    utils::with_hardware_wallet,
+                    (wallet_lock, &app),
+                ),
             )
             .await
             .expect("Failed to sign pre-genesis transaction.");
@@ -783,8 +788,7 @@ impl<T> Signed<T> {
                 &get_tx_args(use_device),
                 &mut tx,
                 signing_data,
-                software_wallet_sign,
-                (),
+                &SignWith::new(software_wallet_sign, ()),
             )
             .await
             .expect("Failed to sign pre-genesis transaction.");
@@ -852,7 +856,7 @@ impl<T> Signed<T> {
                 &None,
                 threshold,
                 None,
-                || Ok(()),
+                |_| Ok(()),
             )
             .map_err(|err| err.to_string())?;
         Ok(())
@@ -1424,6 +1428,25 @@ pub fn validate_validator_account(
         is_valid = false;
     }
 
+    // Check that the keys used by CometBFT aren't BLS keys, which it doesn't
+    // support
+    if matches!(tx.consensus_key.pk.raw, common::PublicKey::Bls(_)) {
+        eprintln!(
+            "The `consensus_key` of a `validator_account` tx with address \
+             \"{}\" cannot be a BLS key.",
+            established_address
+        );
+        is_valid = false;
+    }
+    if matches!(tx.tendermint_node_key.pk.raw, common::PublicKey::Bls(_)) {
+        eprintln!(
+            "The `tendermint_node_key` of a `validator_account` tx with \
+             address \"{}\" cannot be a BLS key.",
+            established_address
+        );
+        is_valid = false;
+    }
+
     // Check keys authorizations
     let unsigned = UnsignedValidatorAccountTx::from(tx);
     if !validate_signature(
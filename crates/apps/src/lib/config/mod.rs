@@ -65,6 +65,78 @@ impl TendermintMode {
     }
 }
 
+/// Which historical state is kept in the DB. The diffs and block data older
+/// than the retained state are pruned.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PruningMode {
+    /// Keep the state at every height.
+    #[default]
+    Archive,
+    /// Keep the state of the given number of most recent blocks.
+    KeepBlocks(u64),
+    /// Keep the state of the current epoch and of the given number of
+    /// previous epochs.
+    KeepEpochs(u64),
+}
+
+/// The backend of the persistent storage. A node can only use the backends
+/// that it's built with, which are enabled by the features of the same name.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DbBackend {
+    /// RocksDB, enabled by the `rocksdb` feature
+    RocksDB,
+    /// redb, a pure-Rust embedded DB, enabled by the `redb` feature
+    Redb,
+}
+
+impl Default for DbBackend {
+    /// RocksDB, unless the node is built without it
+    fn default() -> Self {
+        if cfg!(feature = "rocksdb") {
+            Self::RocksDB
+        } else {
+            Self::Redb
+        }
+    }
+}
+
+/// The feed of the changes of the state committed in every block, which is
+/// published to a sink for the consumers outside of the node.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StateDiffFeed {
+    /// Where the changes are published
+    pub sink: StateDiffSink,
+    /// How the changes of a block are encoded
+    #[serde(default)]
+    pub encoding: StateDiffEncoding,
+    /// Only publish the changes of the keys with one of these storage key
+    /// prefixes. When empty, the changes of all the keys are published.
+    #[serde(default)]
+    pub key_prefixes: Vec<String>,
+}
+
+/// The sink of the state changes feed
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StateDiffSink {
+    /// Append the changes to the file at the given path
+    File(PathBuf),
+    /// Write the changes to the Unix socket listening at the given path
+    UnixSocket(PathBuf),
+}
+
+/// The encoding of the changes of a block in the state changes feed
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub enum StateDiffEncoding {
+    /// A line of JSON per block
+    #[default]
+    Json,
+    /// Borsh, prefixed with the length of the encoded block changes as a
+    /// little-endian `u64`
+    Borsh,
+}
+
 /// An action to be performed at a
 /// certain block height.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,7 +171,11 @@ pub struct Ledger {
 pub struct Shell {
     pub base_dir: PathBuf,
     // pub ledger_address: SocketAddr,
-    /// RocksDB block cache maximum size in bytes.
+    /// The backend of a new DB. An existing DB must be opened with the
+    /// backend that created it.
+    #[serde(default)]
+    pub db_backend: DbBackend,
+    /// DB block cache maximum size in bytes.
     /// When not set, defaults to 1/3 of the available memory.
     pub block_cache_bytes: Option<u64>,
     /// VP WASM compilation cache maximum size in bytes.
@@ -111,6 +187,22 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, a state sync snapshot of the storage is taken at every
+    /// block height that is a multiple of this interval.
+    #[serde(default)]
+    pub snapshot_interval: Option<u64>,
+    /// How many of the most recent state sync snapshots to keep. When not
+    /// set, defaults to 2.
+    #[serde(default)]
+    pub snapshots_to_keep: Option<u64>,
+    /// Which historical state to keep in the DB. The reads past the
+    /// retained heights are limited accordingly.
+    #[serde(default)]
+    pub pruning: PruningMode,
+    /// When set, the changes of the state committed in every block are
+    /// published to the configured sink.
+    #[serde(default)]
+    pub state_diff_feed: Option<StateDiffFeed>,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -138,11 +230,16 @@ impl Ledger {
             chain_id,
             shell: Shell {
                 base_dir: base_dir.as_ref().to_owned(),
+                db_backend: DbBackend::default(),
                 block_cache_bytes: None,
                 vp_wasm_compilation_cache_bytes: None,
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                snapshot_interval: None,
+                snapshots_to_keep: None,
+                pruning: PruningMode::Archive,
+                state_diff_feed: None,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
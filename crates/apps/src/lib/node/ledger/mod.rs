@@ -7,6 +7,8 @@ pub mod storage;
 pub mod tendermint_node;
 
 use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -133,7 +135,10 @@ impl Shell {
             }
             Request::Commit => {
                 tracing::debug!("Request Commit");
-                Ok(Response::Commit(self.commit()))
+                let response = self.commit();
+                self.publish_state_diff();
+                self.take_snapshot_if_due();
+                Ok(Response::Commit(response))
             }
             Request::Flush => Ok(Response::Flush),
             Request::Echo(msg) => Ok(Response::Echo(response::Echo {
@@ -148,16 +153,18 @@ impl Shell {
                 Ok(Response::CheckTx(self.mempool_validate(&tx.tx, r#type)))
             }
             Request::ListSnapshots => {
-                Ok(Response::ListSnapshots(Default::default()))
+                Ok(Response::ListSnapshots(self.list_snapshots()))
             }
-            Request::OfferSnapshot(_) => {
-                Ok(Response::OfferSnapshot(Default::default()))
+            Request::OfferSnapshot(req) => {
+                Ok(Response::OfferSnapshot(self.offer_snapshot(req)))
             }
-            Request::LoadSnapshotChunk(_) => {
-                Ok(Response::LoadSnapshotChunk(Default::default()))
+            Request::LoadSnapshotChunk(req) => {
+                Ok(Response::LoadSnapshotChunk(self.load_snapshot_chunk(req)))
             }
-            Request::ApplySnapshotChunk(_) => {
-                Ok(Response::ApplySnapshotChunk(Default::default()))
+            Request::ApplySnapshotChunk(req) => {
+                Ok(Response::ApplySnapshotChunk(
+                    self.apply_snapshot_chunk(req),
+                ))
             }
         }
     }
@@ -224,6 +231,60 @@ pub fn dump_db(
     db.dump_block(out_file_path, historic, block_height);
 }
 
+/// Export the state of the last committed block into an archive
+pub fn export_state(
+    config: config::Ledger,
+    args::LedgerExportState {
+        archive_path,
+        block_height,
+    }: args::LedgerExportState,
+) -> Result<(), storage::archive::Error> {
+    use namada::state::DB;
+
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+
+    let db = storage::PersistentDB::open(db_path, None);
+    let file = BufWriter::new(File::create(&archive_path)?);
+    let header =
+        storage::archive::export_state(&db, chain_id, block_height, file)?;
+    println!(
+        "Exported the state at height {} with merkle root {} to {}",
+        header.height,
+        header.merkle_root,
+        archive_path.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Import the state from an archive into an empty DB
+pub fn import_state(
+    config: config::Ledger,
+    args::LedgerImportState { archive_path }: args::LedgerImportState,
+) -> Result<(), storage::archive::Error> {
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+
+    // The native token isn't needed to verify the imported state
+    let mut storage = storage::PersistentStorage::open(
+        db_path,
+        chain_id.clone(),
+        namada::types::address::nam(),
+        None,
+        None,
+        shell::is_merklized_storage_key,
+    );
+    storage.masp_merkle_tree_key_filter =
+        namada::token::storage_key::is_masp_merklized_key;
+    let file = BufReader::new(File::open(&archive_path)?);
+    let header = storage::archive::import_state(&mut storage, &chain_id, file)?;
+    println!(
+        "Imported the state at height {} with merkle root {}",
+        header.height, header.merkle_root
+    );
+    Ok(())
+}
+
 /// Roll Namada state back to the previous height
 pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
@@ -383,7 +444,7 @@ async fn run_aux_setup(
             .get_appropriate_unit(true)
     );
 
-    // Find the RocksDB block cache size
+    // Find the DB block cache size
     let db_block_cache_size_bytes = match config.shell.block_cache_bytes {
         Some(block_cache_bytes) => {
             tracing::info!("Block cache set from the configuration.");
@@ -398,7 +459,7 @@ async fn run_aux_setup(
         }
     };
     tracing::info!(
-        "RocksDB block cache size: {}",
+        "DB block cache size: {}",
         Byte::from_bytes(db_block_cache_size_bytes as u128)
             .get_appropriate_unit(true)
     );
@@ -464,8 +525,11 @@ fn start_abci_broadcaster_shell(
     };
 
     // Setup DB cache, it must outlive the DB instance that's in the shell
-    let db_cache =
-        rocksdb::Cache::new_lru_cache(db_block_cache_size_bytes as usize);
+    let db_cache = storage::DbCache::new(
+        config.shell.db_backend,
+        db_block_cache_size_bytes as usize,
+    )
+    .expect("The configured storage backend should be supported");
 
     // Construct our ABCI application.
     let tendermint_mode = config.shell.tendermint_mode.clone();
@@ -7,7 +7,9 @@ use namada::governance::pgf::inflation as pgf_inflation;
 use namada::ledger::events::EventType;
 use namada::ledger::gas::{GasMetering, TxGasMeter};
 use namada::ledger::pos::namada_proof_of_stake;
+use namada::ledger::protocol::parallel::{SpeculativeBlock, SpeculativeTx};
 use namada::ledger::protocol::{self, WrapperArgs};
+use namada::ledger::storage_deposit;
 use namada::proof_of_stake::storage::{
     find_validator_by_raw_hash, read_last_block_proposer_address,
     write_last_block_proposer_address,
@@ -29,6 +31,11 @@ use super::*;
 use crate::facade::tendermint::abci::types::{Misbehavior, VoteInfo};
 use crate::node::ledger::shell::stats::InternalStats;
 
+/// The maximum number of existing MASP keys read to be merklized in a block.
+/// The merklization of a long history of nullifiers is spread over many
+/// blocks.
+pub const MAX_MERKLIZED_MASP_KEYS_PER_BLOCK: usize = 1000;
+
 impl<D, H> Shell<D, H>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -63,6 +70,8 @@ where
         // Begin the new block and check if a new epoch has begun
         let (height, new_epoch) =
             self.update_state(req.header, req.hash, req.byzantine_validators);
+        self.activate_masp_merklization(height)?;
+        self.backfill_masp_counters()?;
 
         let (current_epoch, _gas) = self.wl_storage.storage.get_current_epoch();
         let update_for_tendermint = matches!(
@@ -195,6 +204,15 @@ where
                 )
         };
 
+        // Speculatively execute the decrypted transactions in parallel. Their
+        // results are applied in the block order in the loop below
+        let mut speculative_block = SpeculativeBlock::execute(
+            self.speculative_txs(&req.txs),
+            &self.wl_storage,
+            &self.vp_wasm_cache,
+            &self.tx_wasm_cache,
+        );
+
         // Tracks the accepted transactions
         self.wl_storage.storage.block.results = BlockResults::default();
         let mut changed_keys = BTreeSet::new();
@@ -410,20 +428,35 @@ where
 
             let tx_result = protocol::check_tx_allowed(&tx, &self.wl_storage)
                 .and_then(|()| {
-                    protocol::dispatch_tx(
-                        tx,
-                        processed_tx.tx.as_ref(),
-                        TxIndex(
-                            tx_index
-                                .try_into()
-                                .expect("transaction index out of bounds"),
-                        ),
-                        &mut tx_gas_meter,
-                        &mut self.wl_storage,
-                        &mut self.vp_wasm_cache,
-                        &mut self.tx_wasm_cache,
-                        wrapper_args.as_mut(),
-                    )
+                    let tx_index = TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    );
+                    if matches!(
+                        tx_header.tx_type,
+                        TxType::Decrypted(DecryptedTx::Decrypted)
+                    ) {
+                        speculative_block.apply(
+                            tx,
+                            tx_index,
+                            &mut tx_gas_meter,
+                            &mut self.wl_storage,
+                            &mut self.vp_wasm_cache,
+                            &mut self.tx_wasm_cache,
+                        )
+                    } else {
+                        protocol::dispatch_tx(
+                            tx,
+                            processed_tx.tx.as_ref(),
+                            tx_index,
+                            &mut tx_gas_meter,
+                            &mut self.wl_storage,
+                            &mut self.vp_wasm_cache,
+                            &mut self.tx_wasm_cache,
+                            wrapper_args.as_mut(),
+                        )
+                    }
                 })
                 .map_err(Error::TxApply);
             match tx_result {
@@ -465,6 +498,7 @@ where
                                 .extend(result.changed_keys.iter().cloned());
                             stats.increment_successful_txs();
                             if let Some(wrapper) = embedding_wrapper {
+                                self.settle_tx_storage(&wrapper);
                                 self.commit_inner_tx_hash(wrapper);
                             }
                         }
@@ -663,6 +697,89 @@ where
         (height, new_epoch)
     }
 
+    /// Start merklizing the MASP note commitment tree and nullifiers from the
+    /// block at the activation height set by governance. The existing keys
+    /// are written again, so that they are added to the merkle tree and that
+    /// their diffs are kept to rebuild the trees of the next heights. At most
+    /// [`MAX_MERKLIZED_MASP_KEYS_PER_BLOCK`] keys are read in a block, from a
+    /// cursor kept in storage. Once all the keys are merklized, the height is
+    /// written in storage, for the proofs of the older heights to be
+    /// rejected.
+    fn activate_masp_merklization(
+        &mut self,
+        height: BlockHeight,
+    ) -> Result<()> {
+        let merklization_height_key =
+            token::storage_key::masp_merklization_height_key();
+        if self
+            .wl_storage
+            .storage
+            .merkle_tree_layout
+            .masp_merklization_height
+            .is_none()
+        {
+            match namada::parameters::storage::read_masp_merklization_height(
+                &self.wl_storage,
+            )? {
+                Some(activation_height) if activation_height <= height => {}
+                _ => return Ok(()),
+            }
+            // The keys written from this block are merklized
+            self.wl_storage
+                .storage
+                .merkle_tree_layout
+                .masp_merklization_height = Some(height);
+            tracing::info!("Merklizing the MASP keys from the height {height}");
+        } else if self.wl_storage.has_key(&merklization_height_key)? {
+            return Ok(());
+        }
+
+        let cursor_key = token::storage_key::masp_merklization_cursor_key();
+        let prefix = Key::from(address::MASP.to_db_key());
+        let start: Key = self
+            .wl_storage
+            .read(&cursor_key)?
+            .unwrap_or_else(|| prefix.clone());
+        let mut entries = vec![];
+        let mut next = None;
+        for (key, value, _gas) in
+            self.wl_storage.storage.iter_prefix_from(&prefix, &start).0
+        {
+            let key = Key::parse(key).into_storage_result()?;
+            if entries.len() == MAX_MERKLIZED_MASP_KEYS_PER_BLOCK {
+                next = Some(key);
+                break;
+            }
+            entries.push((key, value));
+        }
+        for (key, value) in entries {
+            if token::storage_key::is_masp_merklized_key(&key) {
+                self.wl_storage.write_bytes(&key, value)?;
+            }
+        }
+        match next {
+            Some(next) => self.wl_storage.write(&cursor_key, next)?,
+            None => {
+                tracing::info!(
+                    "All the MASP keys are merklized from the height {height}"
+                );
+                self.wl_storage.delete(&cursor_key)?;
+                self.wl_storage.write(&merklization_height_key, height)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the MASP nullifiers and note commitments counters in the first
+    /// block after the upgrade that introduced them. This is a no-op once the
+    /// counters are in storage.
+    fn backfill_masp_counters(&mut self) -> Result<()> {
+        if token::utils::backfill_counters(&mut self.wl_storage)? {
+            tracing::info!("Backfilled the MASP counters");
+        }
+        Ok(())
+    }
+
     /// If a new epoch begins, we update the response to include
     /// changes to the validator sets and consensus parameters
     fn update_epoch(&mut self, response: &mut shim::response::FinalizeBlock) {
@@ -768,6 +885,66 @@ where
         Ok(())
     }
 
+    /// Collect the decrypted transactions of the block to execute them
+    /// speculatively, together with the gas available to them. This mirrors
+    /// the checks done in [`Shell::finalize_block`] before applying a
+    /// decrypted transaction and the order in which their wrappers are popped
+    /// from the queue.
+    fn speculative_txs(
+        &self,
+        txs: &[shim::request::ProcessedTx],
+    ) -> Vec<SpeculativeTx> {
+        let mut tx_queue = self.wl_storage.storage.tx_queue.iter();
+        txs.iter()
+            .enumerate()
+            .filter_map(|(tx_index, processed_tx)| {
+                let tx = Tx::try_from(processed_tx.tx.as_ref()).ok()?;
+                let code = ResultCode::from_u32(processed_tx.result.code)?;
+                if code == ResultCode::InvalidSig || tx.validate_tx().is_err() {
+                    return None;
+                }
+                let TxType::Decrypted(inner) = tx.header().tx_type else {
+                    return None;
+                };
+                // Rejected and undecryptable txs still pop their wrapper
+                let tx_in_queue = tx_queue.next()?;
+                if code != ResultCode::Ok
+                    || !matches!(inner, DecryptedTx::Decrypted)
+                {
+                    return None;
+                }
+                Some(SpeculativeTx {
+                    tx,
+                    tx_index: TxIndex(tx_index.try_into().ok()?),
+                    gas_limit: tx_in_queue.gas,
+                })
+            })
+            .collect()
+    }
+
+    // Update the storage deposit of the fee payer of the wrapper with the
+    // storage occupied and freed by its accepted inner tx. The fees paid from
+    // the shielded pool have no account to track a deposit for.
+    fn settle_tx_storage(&mut self, wrapper_tx: &Tx) {
+        let wrapper = wrapper_tx
+            .header()
+            .wrapper()
+            .expect("Missing wrapper header");
+        let fee_payer = protocol::fee_payer(
+            wrapper_tx,
+            &wrapper,
+            protocol::get_fee_unshielding_transaction(wrapper_tx, &wrapper)
+                .as_ref(),
+        );
+        if fee_payer != address::MASP {
+            storage_deposit::settle_tx_storage(
+                &mut self.wl_storage,
+                &fee_payer,
+            )
+            .expect("Error while settling the storage deposit");
+        }
+    }
+
     // Write the inner tx hash to storage and remove the corresponding wrapper
     // hash since it's redundant (we check the inner tx hash too when validating
     // the wrapper). Requires the wrapper transaction as argument to recover
@@ -2851,6 +3028,83 @@ mod test_finalize_block {
         }
     }
 
+    /// Test that the existing MASP nullifiers are added to the merkle tree
+    /// only from the MASP merklization height set by governance, over as many
+    /// blocks as needed to stay under the per block limit
+    #[test]
+    fn test_masp_merklization_activation() {
+        let (mut shell, _, _, _) = setup();
+        // A chain started before the MASP keys were merklized
+        shell
+            .wl_storage
+            .storage
+            .merkle_tree_layout
+            .masp_merklization_height = None;
+        let nullifier_keys: Vec<_> = (0..=MAX_MERKLIZED_MASP_KEYS_PER_BLOCK)
+            .map(|i| {
+                let mut nullifier = [0; 32];
+                nullifier[..8].copy_from_slice(&(i as u64).to_be_bytes());
+                token::storage_key::masp_nullifier_key(
+                    &masp_primitives::sapling::Nullifier(nullifier),
+                )
+            })
+            .collect();
+        for nullifier_key in &nullifier_keys {
+            shell
+                .wl_storage
+                .storage
+                .write(nullifier_key, ().serialize_to_vec())
+                .unwrap();
+        }
+        let activation_height =
+            shell.wl_storage.storage.get_last_block_height() + 2;
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &namada::parameters::storage::get_masp_merklization_height_key(
+                ),
+                activation_height.serialize_to_vec(),
+            )
+            .unwrap();
+        let merklized_count = |shell: &TestShell| {
+            nullifier_keys
+                .iter()
+                .filter(|key| {
+                    shell.wl_storage.storage.block.tree.has_key(key).unwrap()
+                })
+                .count()
+        };
+        let merklization_height = |shell: &TestShell| -> Option<BlockHeight> {
+            shell
+                .wl_storage
+                .read(&token::storage_key::masp_merklization_height_key())
+                .unwrap()
+        };
+
+        // The nullifiers aren't merklized before the activation height
+        shell.finalize_and_commit(None);
+        assert_eq!(merklized_count(&shell), 0);
+
+        // Only a part of the nullifiers is merklized in the activation block
+        shell.finalize_and_commit(None);
+        assert_eq!(
+            shell.wl_storage.storage.merkle_tree_layout,
+            namada::state::MerkleTreeLayout {
+                masp_merklization_height: Some(activation_height),
+                ..Default::default()
+            }
+        );
+        let count = merklized_count(&shell);
+        assert!(count > 0 && count <= MAX_MERKLIZED_MASP_KEYS_PER_BLOCK);
+        assert_eq!(merklization_height(&shell), None);
+
+        // The rest of them is merklized in the next block
+        shell.finalize_and_commit(None);
+        assert_eq!(merklized_count(&shell), nullifier_keys.len());
+        assert_eq!(merklization_height(&shell), Some(activation_height + 1));
+    }
+
     /// Test that replay protection keys are not added to the merkle tree
     #[test]
     fn test_replay_keys_not_merklized() {
@@ -3026,6 +3280,90 @@ mod test_finalize_block {
         }
     }
 
+    /// Test that the decrypted txs of a block executed speculatively in
+    /// parallel give the same results as a sequential execution, with a tx
+    /// reading a key written by a previous tx of the block being re-executed
+    #[test]
+    fn test_parallel_decrypted_txs() {
+        let (mut shell, _, _, _) = setup();
+        let keypair = gen_keypair();
+        let mut batch = namada::state::testing::TestStorage::batch();
+
+        let key = Key::parse("parallel").unwrap();
+        let write_data = borsh::to_vec(&TxWriteData {
+            key: key.clone(),
+            value: "value".as_bytes().to_owned(),
+        })
+        .unwrap();
+        // The second tx fails if it doesn't see the write of the first one,
+        // the third one is independent from the others
+        let txs = [
+            (TestWasms::TxWriteStorageKey, write_data),
+            (TestWasms::TxReadStorageKey, key.serialize_to_vec()),
+            (TestWasms::TxNoOp, vec![]),
+        ];
+
+        let mut processed_txs: Vec<ProcessedTx> = vec![];
+        for (wasm, data) in txs {
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            Amount::zero(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper.set_code(Code::new(wasm.read_bytes(), None));
+            wrapper.set_data(Data::new(data));
+            wrapper.add_section(Section::Signature(Signature::new(
+                wrapper.sechashes(),
+                [(0, keypair.clone())].into_iter().collect(),
+                None,
+            )));
+            let mut inner = wrapper.clone();
+            inner.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+
+            let hash_subkey =
+                replay_protection::last_key(&wrapper.header_hash());
+            shell
+                .wl_storage
+                .storage
+                .write_replay_protection_entry(&mut batch, &hash_subkey)
+                .expect("Test failed");
+            processed_txs.push(ProcessedTx {
+                tx: inner.to_bytes().into(),
+                result: TxResult {
+                    code: ResultCode::Ok.into(),
+                    info: "".into(),
+                },
+            });
+            shell.enqueue_tx(wrapper, GAS_LIMIT_MULTIPLIER.into());
+        }
+
+        let event = &shell
+            .finalize_block(FinalizeBlock {
+                txs: processed_txs,
+                ..Default::default()
+            })
+            .expect("Test failed");
+
+        for event in event.iter().take(3) {
+            assert_eq!(event.event_type.to_string(), String::from("applied"));
+            let code = event.attributes.get("code").unwrap().as_str();
+            assert_eq!(code, String::from(ResultCode::Ok).as_str());
+        }
+        assert_eq!(
+            shell.wl_storage.read_bytes(&key).unwrap(),
+            Some("value".as_bytes().to_owned())
+        );
+    }
+
     /// Test that if a decrypted transaction fails because of out-of-gas,
     /// undecryptable, invalid signature or wrong section commitment, its hash
     /// is not committed to storage. Also checks that a tx failing for other
@@ -5,7 +5,8 @@ use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
 use namada::governance::storage::keys as gov_storage;
 use namada::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, PGFTarget, ProposalType, ShieldedRewardsUpdate,
+    StoragePgfFunding,
 };
 use namada::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
@@ -157,6 +158,23 @@ where
                         ProposalEvent::pgf_payments_proposal_event(id, result)
                             .into()
                     }
+                    ProposalType::ShieldedRewards(updates) => {
+                        let result = execute_shielded_rewards_proposal(
+                            &mut shell.wl_storage,
+                            updates,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (shielded rewards) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::shielded_rewards_proposal_event(
+                            id, result,
+                        )
+                        .into()
+                    }
                 };
                 response.events.push(proposal_event);
                 proposals_result.passed.push(id);
@@ -362,6 +380,61 @@ where
     Ok(true)
 }
 
+fn execute_shielded_rewards_proposal<D, H>(
+    storage: &mut WlStorage<D, H>,
+    updates: BTreeSet<ShieldedRewardsUpdate>,
+) -> Result<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    // Check all the updates before writing anything, so that the proposal is
+    // applied either entirely or not at all
+    for update in &updates {
+        if !update.params.is_valid() {
+            tracing::warn!(
+                "Invalid shielded rewards parameters for token {}.",
+                update.token
+            );
+            return Ok(false);
+        }
+        if token::read_denom(storage, &update.token)?.is_none() {
+            tracing::warn!(
+                "Token {} has no denomination in storage, it can't earn \
+                 shielded rewards.",
+                update.token
+            );
+            return Ok(false);
+        }
+    }
+
+    for ShieldedRewardsUpdate {
+        alias,
+        token,
+        params,
+    } in updates
+    {
+        token::update_params(&params, storage, &token)?;
+
+        // Register the token for the next update of the MASP conversions
+        let tokens = &mut storage.storage.conversion_state.tokens;
+        if !tokens.values().any(|address| address == &token) {
+            let alias = if tokens.contains_key(&alias) {
+                token.to_string()
+            } else {
+                alias
+            };
+            tokens.insert(alias, token.clone());
+        }
+        tracing::info!(
+            "Updated the shielded rewards parameters of token {}.",
+            token
+        );
+    }
+
+    Ok(true)
+}
+
 fn execute_pgf_funding_proposal<D, H>(
     storage: &mut WlStorage<D, H>,
     token: &Address,
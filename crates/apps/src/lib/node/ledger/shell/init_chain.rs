@@ -161,10 +161,27 @@ where
             ),
         )?;
 
+        // The masp keys of a new chain are merklized from the genesis
+        if let Some(height) = self
+            .wl_storage
+            .storage
+            .merkle_tree_layout
+            .masp_merklization_height
+        {
+            self.wl_storage.write(
+                &token::storage_key::masp_merklization_height_key(),
+                height,
+            )?;
+        }
+
         // Set the initial validator set
         response.validators = self
             .get_abci_validator_updates(true, |pk, power| {
-                let pub_key: crate::facade::tendermint::PublicKey = pk.into();
+                let pub_key: crate::facade::tendermint::PublicKey =
+                    pk.try_into().expect(
+                        "Genesis consensus keys are validated not to be BLS \
+                         keys",
+                    );
                 let power =
                     crate::facade::tendermint::vote::Power::try_from(power)
                         .unwrap();
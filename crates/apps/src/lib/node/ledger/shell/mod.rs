@@ -13,7 +13,10 @@ pub use init_chain::InitChainValidation;
 use namada_sdk::tx::data::GasLimit;
 pub mod prepare_proposal;
 pub mod process_proposal;
+mod pruning;
 pub(super) mod queries;
+mod snapshots;
+mod state_diff_feed;
 mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
@@ -98,6 +101,7 @@ fn key_to_tendermint(
             secp256k1::PublicKey::try_from_pk(pk)
                 .map(|pk| public_key::Sum::Secp256k1(pk.serialize_to_vec()))
         }
+        common::PublicKey::Bls(_) => Err(ParsePublicKeyError::MismatchedScheme),
     }
 }
 
@@ -357,14 +361,22 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Pruning of the historical state
+    pruning: pruning::Pruning,
     /// Proposal execution tracking
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// State sync snapshots
+    snapshots: snapshots::Snapshots,
+    /// The feed of the state changes, if enabled
+    state_diff_feed: Option<state_diff_feed::StateDiffFeed>,
 }
 
 /// Merkle tree storage key filter. Return `false` for keys that shouldn't be
-/// merklized.
+/// merklized. The MASP keys accepted by
+/// [`token::storage_key::is_masp_merklized_key`] are also merklized from the
+/// height at which the MASP merklization is activated.
 pub fn is_merklized_storage_key(key: &namada_sdk::types::storage::Key) -> bool {
     !token::storage_key::is_masp_key(key)
         && !namada::ibc::storage::is_ibc_counter_key(key)
@@ -413,8 +425,6 @@ where
         let db_path = config.shell.db_dir(&chain_id);
         let base_dir = config.shell.base_dir;
         let mode = config.shell.tendermint_mode;
-        let storage_read_past_height_limit =
-            config.shell.storage_read_past_height_limit;
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -440,16 +450,38 @@ where
             config.shell.storage_read_past_height_limit,
             is_merklized_storage_key,
         );
+        storage.masp_merkle_tree_key_filter =
+            token::storage_key::is_masp_merklized_key;
         storage
             .load_last_state()
             .map_err(|e| {
                 tracing::error!("Cannot load the last state from the DB {}", e);
             })
             .expect("PersistentStorage cannot be initialized");
+        let pruning = pruning::Pruning::new(
+            config.shell.pruning,
+            config.shell.storage_read_past_height_limit,
+        );
+        storage.storage_read_past_height_limit =
+            pruning.read_past_height_limit(&storage);
+        storage.history_pruning = pruning.is_enabled();
+        let storage_read_past_height_limit =
+            storage.storage_read_past_height_limit;
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("tx_wasm_cache");
+        let snapshots = snapshots::Snapshots::new(
+            base_dir.join(chain_id.as_str()).join("snapshots"),
+            config.shell.snapshot_interval,
+            config.shell.snapshots_to_keep,
+        );
+        let state_diff_feed = config.shell.state_diff_feed.map(|feed| {
+            state_diff_feed::StateDiffFeed::new(
+                feed,
+                &base_dir.join(chain_id.as_str()),
+            )
+        });
         // load in keys and address from wallet if mode is set to `Validator`
         let mode = match mode {
             TendermintMode::Validator => {
@@ -534,9 +566,12 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            pruning,
             proposal_data: HashSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            snapshots,
+            state_diff_feed,
         };
         shell.update_eth_oracle(&Default::default());
         shell
@@ -773,6 +808,7 @@ where
             retain_height: tendermint::block::Height::from(0_u32),
             ..Default::default()
         };
+        self.update_read_past_height_limit();
         // commit block's data from write log and store the in DB
         self.wl_storage.commit_block().unwrap_or_else(|e| {
             tracing::error!(
@@ -1241,6 +1277,7 @@ where
 
                 // Validate wrapper fees
                 if let Err(e) = mempool_fee_check(
+                    &tx,
                     &wrapper,
                     get_fee_unshielding_transaction(&tx, &wrapper),
                     &mut TempWlStorage::new(&self.wl_storage.storage),
@@ -1383,6 +1420,7 @@ where
 
 // Perform the fee check in mempool
 fn mempool_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     temp_wl_storage: &mut TempWlStorage<D, H>,
@@ -1404,7 +1442,9 @@ where
         wrapper.fee.token
     ))))?;
 
+    let fee_payer = protocol::fee_payer(tx, wrapper, masp_transaction.as_ref());
     wrapper_fee_check(
+        tx,
         wrapper,
         masp_transaction,
         minimum_gas_price,
@@ -1412,12 +1452,14 @@ where
         vp_wasm_cache,
         tx_wasm_cache,
     )?;
-    protocol::check_fees(temp_wl_storage, wrapper).map_err(Error::TxApply)
+    protocol::check_fees(temp_wl_storage, wrapper, &fee_payer)
+        .map_err(Error::TxApply)
 }
 
 /// Check the validity of the fee payment, including the minimum amounts
-/// required and the optional unshield
+/// required and the optional unshield or payment from the shielded pool
 pub fn wrapper_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     minimum_gas_price: token::Amount,
@@ -1455,7 +1497,18 @@ where
         }
     }
 
-    if let Some(transaction) = masp_transaction {
+    if masp_transaction.as_ref().is_some_and(|transaction| {
+        protocol::pays_fee_from_shielded_pool(tx, wrapper, transaction)
+    }) {
+        protocol::apply_masp_fee_payment(
+            tx,
+            wrapper,
+            masp_transaction,
+            temp_wl_storage,
+            vp_wasm_cache,
+        )
+        .map_err(Error::TxApply)?;
+    } else if let Some(transaction) = masp_transaction {
         fee_unshielding_validation(
             wrapper,
             transaction,
@@ -1663,6 +1716,9 @@ mod test_utils {
                         .unwrap();
                 common::Signature::Secp256k1((&bytes).try_into().unwrap())
             }
+            common::Signature::Bls(bls::Signature(sig)) => {
+                common::Signature::Bls(bls::Signature(-sig))
+            }
         }
     }
 
@@ -2058,7 +2114,7 @@ mod test_utils {
     #[test]
     fn test_tx_queue_persistence() {
         let base_dir = tempdir().unwrap().as_ref().canonicalize().unwrap();
-        // we have to use RocksDB for this test
+        // we have to use the persistent DB for this test
         let (sender, _) = tokio::sync::mpsc::unbounded_channel();
         let (_, eth_receiver) =
             tokio::sync::mpsc::channel(ORACLE_CHANNEL_BUFFER_SIZE);
@@ -356,6 +356,7 @@ where
 
         // Check fees and extract the gas limit of this transaction
         match prepare_proposal_fee_check(
+            &tx,
             &wrapper,
             protocol::get_fee_unshielding_transaction(&tx, &wrapper),
             block_proposer,
@@ -373,6 +374,7 @@ where
 }
 
 fn prepare_proposal_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     proposer: &Address,
@@ -411,7 +413,9 @@ where
         }
     };
 
+    let fee_payer = protocol::fee_payer(tx, wrapper, masp_transaction.as_ref());
     super::wrapper_fee_check(
+        tx,
         wrapper,
         masp_transaction,
         minimum_gas_price,
@@ -420,7 +424,7 @@ where
         tx_wasm_cache,
     )?;
 
-    protocol::transfer_fee(temp_wl_storage, proposer, wrapper)
+    protocol::transfer_fee(temp_wl_storage, proposer, wrapper, &fee_payer)
         .map_err(Error::TxApply)
 }
 
@@ -590,6 +590,7 @@ where
 
                 // Check that the fee payer has sufficient balance.
                 match process_proposal_fee_check(
+                    &tx,
                     &wrapper,
                     get_fee_unshielding_transaction(&tx, &wrapper),
                     block_proposer,
@@ -628,6 +629,7 @@ where
 }
 
 fn process_proposal_fee_check<D, H, CA>(
+    tx: &Tx,
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     proposer: &Address,
@@ -650,7 +652,9 @@ where
         wrapper.fee.token
     ))))?;
 
+    let fee_payer = protocol::fee_payer(tx, wrapper, masp_transaction.as_ref());
     wrapper_fee_check(
+        tx,
         wrapper,
         masp_transaction,
         minimum_gas_price,
@@ -659,7 +663,7 @@ where
         tx_wasm_cache,
     )?;
 
-    protocol::transfer_fee(temp_wl_storage, proposer, wrapper)
+    protocol::transfer_fee(temp_wl_storage, proposer, wrapper, &fee_payer)
         .map_err(Error::TxApply)
 }
 
@@ -0,0 +1,130 @@
+//! Pruning of the historical state that is beyond the retention of the
+//! configured [`PruningMode`].
+
+use namada::types::storage::Epoch;
+
+use super::*;
+use crate::config::PruningMode;
+
+/// The pruning of the historical state
+#[derive(Debug)]
+pub struct Pruning {
+    mode: PruningMode,
+    /// Taken from config `storage_read_past_height_limit`
+    read_past_height_limit: Option<u64>,
+}
+
+impl Pruning {
+    /// Prune the history according to the given mode
+    pub fn new(mode: PruningMode, read_past_height_limit: Option<u64>) -> Self {
+        Self {
+            mode,
+            read_past_height_limit,
+        }
+    }
+
+    /// Whether the history is pruned
+    pub fn is_enabled(&self) -> bool {
+        self.mode != PruningMode::Archive
+    }
+
+    /// The number of heights before the current block whose state can be
+    /// read. It's the configured limit, further limited by the retention of
+    /// the pruning mode.
+    pub fn read_past_height_limit<D, H>(
+        &self,
+        storage: &State<D, H>,
+    ) -> Option<u64>
+    where
+        D: DB + for<'iter> DBIter<'iter>,
+        H: StorageHasher,
+    {
+        let height = storage.block.height;
+        let retained_heights = match self.mode {
+            PruningMode::Archive => None,
+            PruningMode::KeepBlocks(blocks) => Some(blocks),
+            PruningMode::KeepEpochs(epochs) => {
+                let oldest_epoch =
+                    Epoch(storage.block.epoch.0.saturating_sub(epochs));
+                // Nothing can be pruned before the start of the oldest
+                // retained epoch is known
+                storage
+                    .block
+                    .pred_epochs
+                    .get_start_height_of_epoch(oldest_epoch)
+                    .map(|oldest_height| {
+                        height.0.saturating_sub(oldest_height.0)
+                    })
+            }
+        };
+        match (self.read_past_height_limit, retained_heights) {
+            (Some(limit), Some(retained)) => Some(limit.min(retained)),
+            (limit, retained) => limit.or(retained),
+        }
+    }
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Update the limit of the reads past the block that is about to be
+    /// committed. The history that can't be read anymore is pruned in the
+    /// same batch as the commit of the block.
+    pub fn update_read_past_height_limit(&mut self) {
+        if !self.pruning.is_enabled() {
+            return;
+        }
+        let limit =
+            self.pruning.read_past_height_limit(&self.wl_storage.storage);
+        self.storage_read_past_height_limit = limit;
+        self.wl_storage.storage.storage_read_past_height_limit = limit;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::types::storage::Epochs;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::TestShell;
+
+    /// Test that the reads past the current block are limited to the
+    /// retained epochs, unless the start of the oldest retained epoch isn't
+    /// known, and that the limit is updated on commit.
+    #[test]
+    fn test_keep_epochs_read_past_height_limit() {
+        let (mut shell, _, _, _) = TestShell::new();
+        let pruning = Pruning::new(PruningMode::KeepEpochs(1), None);
+        let storage = &mut shell.wl_storage.storage;
+        storage.block.pred_epochs = Epochs {
+            first_block_heights: vec![
+                BlockHeight(1),
+                BlockHeight(4),
+                BlockHeight(8),
+            ],
+        };
+        storage.block.height = BlockHeight(10);
+        storage.block.epoch = Epoch(2);
+        // The whole previous epoch is retained
+        assert_eq!(pruning.read_past_height_limit(storage), Some(6));
+
+        // The configured limit applies if it's lower
+        let limited = Pruning::new(PruningMode::KeepEpochs(1), Some(3));
+        assert_eq!(limited.read_past_height_limit(storage), Some(3));
+
+        // Nothing is pruned if the start of the oldest epoch isn't known
+        storage.block.epoch = Epoch(5);
+        assert_eq!(pruning.read_past_height_limit(storage), None);
+
+        storage.block.epoch = Epoch(2);
+        shell.pruning = pruning;
+        shell.commit();
+        assert_eq!(shell.storage_read_past_height_limit, Some(6));
+        assert_eq!(
+            shell.wl_storage.storage.storage_read_past_height_limit,
+            Some(6)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Implementation of the CometBFT state sync ABCI calls. Snapshots of the
+//! storage are taken at regular heights and served to the peers, and a fresh
+//! node can restore its storage from the snapshot offered by a peer, which is
+//! verified against the app hash of the light client verified block.
+
+use std::thread::JoinHandle;
+
+use namada::state::DbResult;
+
+use super::*;
+use crate::facade::tendermint::abci::types::Snapshot;
+use crate::facade::tendermint::v0_37::abci::response::ApplySnapshotChunkResult;
+use crate::node::ledger::storage::snapshot::{
+    self, SnapshotMetadata, SnapshotStore, SNAPSHOT_FORMAT,
+};
+
+/// The default number of most recent snapshots to keep
+const DEFAULT_SNAPSHOTS_TO_KEEP: u64 = 2;
+
+/// The state sync snapshots of the node
+#[derive(Debug)]
+pub struct Snapshots {
+    store: SnapshotStore,
+    /// Taken from config `snapshot_interval`
+    interval: Option<u64>,
+    /// Taken from config `snapshots_to_keep`
+    keep: usize,
+    /// The thread writing the latest snapshot, if any
+    writer: Option<JoinHandle<()>>,
+    /// The snapshot being restored, if any
+    restoring: Option<Restoration>,
+}
+
+/// A snapshot being restored from the chunks sent by peers
+#[derive(Debug)]
+struct Restoration {
+    height: BlockHeight,
+    app_hash: AppHash,
+    metadata: SnapshotMetadata,
+    applied_chunks: BTreeSet<u32>,
+}
+
+impl Snapshots {
+    /// Store snapshots in the given directory
+    pub fn new(
+        dir: PathBuf,
+        interval: Option<u64>,
+        snapshots_to_keep: Option<u64>,
+    ) -> Self {
+        let keep = snapshots_to_keep.unwrap_or(DEFAULT_SNAPSHOTS_TO_KEEP);
+        Self {
+            store: SnapshotStore::new(dir),
+            interval,
+            keep: keep.max(1) as usize,
+            writer: None,
+            restoring: None,
+        }
+    }
+
+    /// Check if a snapshot should be taken at the given height
+    fn is_due(&self, height: BlockHeight) -> bool {
+        match self.interval {
+            Some(interval) if interval > 0 => height.0 % interval == 0,
+            _ => false,
+        }
+    }
+}
+
+impl<H> Shell<storage::PersistentDB, H>
+where
+    H: StorageHasher + Sync + 'static,
+{
+    /// Take a state sync snapshot of the last committed block, if one is due
+    /// at its height. The snapshot is written in the background.
+    pub fn take_snapshot_if_due(&mut self) {
+        let height = self.wl_storage.storage.get_last_block_height();
+        if !self.snapshots.is_due(height) {
+            return;
+        }
+        if let Some(writer) = self.snapshots.writer.as_ref() {
+            if !writer.is_finished() {
+                tracing::warn!(
+                    "Skipping the state sync snapshot at height {height}, the \
+                     previous snapshot is still being written"
+                );
+                return;
+            }
+        }
+        match self.snapshots.store.take_snapshot(
+            &self.wl_storage.storage.db,
+            height,
+            self.snapshots.keep,
+        ) {
+            Ok(writer) => self.snapshots.writer = Some(writer),
+            Err(e) => tracing::error!(
+                "Failed to take a state sync snapshot at height {height}: {e}"
+            ),
+        }
+    }
+
+    /// List the snapshots available to the peers
+    pub fn list_snapshots(&self) -> response::ListSnapshots {
+        let snapshots = self.snapshots.store.list().unwrap_or_else(|e| {
+            tracing::error!("Failed to list the state sync snapshots: {e}");
+            vec![]
+        });
+        response::ListSnapshots {
+            snapshots: snapshots
+                .into_iter()
+                .map(|snapshot| Snapshot {
+                    height: tendermint::block::Height::try_from(
+                        snapshot.height.0,
+                    )
+                    .expect("Snapshot height should be a valid block height"),
+                    format: SNAPSHOT_FORMAT,
+                    chunks: snapshot.metadata.chunks(),
+                    hash: snapshot.metadata.hash().0.to_vec().into(),
+                    metadata: snapshot.metadata.serialize_to_vec().into(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Decide whether to restore the storage from the snapshot offered by a
+    /// peer. The storage is cleared of the chunks applied from any previously
+    /// offered snapshot before restoring a new one.
+    pub fn offer_snapshot(
+        &mut self,
+        req: request::OfferSnapshot,
+    ) -> response::OfferSnapshot {
+        if self.wl_storage.storage.last_block.is_some() {
+            tracing::error!(
+                "State sync is only possible on a node without any state"
+            );
+            return response::OfferSnapshot::Abort;
+        }
+        if req.snapshot.format != SNAPSHOT_FORMAT {
+            return response::OfferSnapshot::RejectFormat;
+        }
+        let metadata =
+            match SnapshotMetadata::try_from_slice(&req.snapshot.metadata) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    tracing::info!(
+                        "Rejecting a state sync snapshot with invalid \
+                         metadata: {e}"
+                    );
+                    return response::OfferSnapshot::Reject;
+                }
+            };
+        if metadata.hash().0.as_slice() != req.snapshot.hash.as_ref()
+            || metadata.chunks() != req.snapshot.chunks
+        {
+            tracing::info!(
+                "Rejecting a state sync snapshot that doesn't match its \
+                 metadata"
+            );
+            return response::OfferSnapshot::Reject;
+        }
+        let height = BlockHeight(req.snapshot.height.value());
+        if let Err(e) = self.clear_restored_state() {
+            tracing::error!(
+                "Failed to clear the storage before restoring a state sync \
+                 snapshot: {e}"
+            );
+            return response::OfferSnapshot::Abort;
+        }
+        tracing::info!(
+            "Restoring the state sync snapshot at height {height} with {} \
+             chunks",
+            metadata.chunks()
+        );
+        self.snapshots.restoring = Some(Restoration {
+            height,
+            app_hash: req.app_hash,
+            metadata,
+            applied_chunks: BTreeSet::new(),
+        });
+        response::OfferSnapshot::Accept
+    }
+
+    /// Load a chunk of a snapshot requested by a peer
+    pub fn load_snapshot_chunk(
+        &self,
+        req: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        if req.format != SNAPSHOT_FORMAT {
+            return Default::default();
+        }
+        let height = BlockHeight(req.height.value());
+        match self.snapshots.store.load_chunk(height, req.chunk) {
+            Ok(chunk) => response::LoadSnapshotChunk {
+                chunk: chunk.into(),
+            },
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load chunk {} of the state sync snapshot at \
+                     height {height}: {e}",
+                    req.chunk
+                );
+                Default::default()
+            }
+        }
+    }
+
+    /// Verify and apply a chunk of the snapshot being restored. Once all the
+    /// chunks are applied, the restored state is loaded and verified against
+    /// the app hash.
+    pub fn apply_snapshot_chunk(
+        &mut self,
+        req: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        let restoration = match self.snapshots.restoring.as_mut() {
+            Some(restoration) => restoration,
+            None => {
+                return response::ApplySnapshotChunk {
+                    result: ApplySnapshotChunkResult::Abort,
+                    ..Default::default()
+                };
+            }
+        };
+        if let Err(e) = snapshot::apply_chunk(
+            &mut self.wl_storage.storage.db,
+            &restoration.metadata,
+            req.index,
+            &req.chunk,
+        ) {
+            tracing::info!(
+                "Failed to apply chunk {} of the state sync snapshot from \
+                 {}: {e}",
+                req.index,
+                req.sender
+            );
+            return response::ApplySnapshotChunk {
+                result: ApplySnapshotChunkResult::Retry,
+                refetch_chunks: vec![req.index],
+                reject_senders: vec![req.sender],
+            };
+        }
+        restoration.applied_chunks.insert(req.index);
+        if restoration.applied_chunks.len()
+            < restoration.metadata.chunk_hashes.len()
+        {
+            return response::ApplySnapshotChunk {
+                result: ApplySnapshotChunkResult::Accept,
+                ..Default::default()
+            };
+        }
+
+        let restoration = self
+            .snapshots
+            .restoring
+            .take()
+            .expect("The snapshot being restored must be set");
+        let result = if self.verify_restored_state(&restoration) {
+            tracing::info!(
+                "Restored the state sync snapshot at height {}",
+                restoration.height
+            );
+            ApplySnapshotChunkResult::Accept
+        } else {
+            // Start over from an empty storage
+            if let Err(e) = self.clear_restored_state() {
+                tracing::error!(
+                    "Failed to clear the storage after an invalid state sync \
+                     snapshot: {e}"
+                );
+                ApplySnapshotChunkResult::Abort
+            } else {
+                ApplySnapshotChunkResult::RejectSnapshot
+            }
+        };
+        response::ApplySnapshotChunk {
+            result,
+            ..Default::default()
+        }
+    }
+
+    /// Drop the state restored from a snapshot, if any, to start over from an
+    /// empty storage
+    fn clear_restored_state(&mut self) -> DbResult<()> {
+        self.snapshots.restoring = None;
+        let storage = &mut self.wl_storage.storage;
+        storage.last_block = None;
+        storage.block.height = BlockHeight::sentinel();
+        storage.db.clear()
+    }
+
+    /// Load the state restored from a snapshot and check that it matches the
+    /// height and app hash of the snapshot
+    fn verify_restored_state(&mut self, restoration: &Restoration) -> bool {
+        match snapshot::verify_restored_state(
+            &mut self.wl_storage.storage,
+            restoration.height,
+            restoration.app_hash.as_bytes(),
+        ) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!(
+                    "The state restored from the snapshot at height {} with \
+                     app hash {} is invalid: {e}",
+                    restoration.height,
+                    restoration.app_hash
+                );
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! The feed of the changes of the state committed in every block. After a
+//! block is committed, its changes are read from the diffs in the DB and
+//! queued to a background thread that publishes them to the configured sink,
+//! so that the consumers outside of the node can follow the state without
+//! querying it. A block that couldn't be published can still be fetched with
+//! the `state_diff` query.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+#[cfg(unix)]
+use std::time::Duration;
+
+use namada::types::storage::StateDiff;
+
+use super::*;
+use crate::config::{StateDiffEncoding, StateDiffSink};
+
+/// The maximum time to wait on a consumer of a Unix socket sink, so that a
+/// stalled consumer doesn't hold up the publisher thread
+#[cfg(unix)]
+const SOCKET_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The maximum number of blocks queued to the publisher thread. The changes
+/// of the blocks committed while the queue is full are dropped.
+const QUEUE_CAPACITY: usize = 32;
+
+/// The feed of the state changes
+#[derive(Debug)]
+pub struct StateDiffFeed {
+    encoding: StateDiffEncoding,
+    /// Taken from config `key_prefixes`
+    key_prefixes: Vec<Key>,
+    /// The queue of the encoded changes of the blocks to be published. It's
+    /// only taken on drop, to stop the publisher thread.
+    queue: Option<SyncSender<(BlockHeight, Vec<u8>)>>,
+    /// The thread writing the queued changes to the sink
+    publisher: Option<JoinHandle<()>>,
+}
+
+/// The writer of the changes to the sink, owned by the publisher thread
+#[derive(Debug)]
+struct Publisher {
+    sink: StateDiffSink,
+    /// The open sink, if any. It's reopened after a failed write.
+    writer: Option<Writer>,
+}
+
+/// An open sink of the feed
+#[derive(Debug)]
+enum Writer {
+    File(File),
+    #[cfg(unix)]
+    Socket(UnixStream),
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Writer::File(file) => file.write(buf),
+            #[cfg(unix)]
+            Writer::Socket(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Writer::File(file) => file.flush(),
+            #[cfg(unix)]
+            Writer::Socket(stream) => stream.flush(),
+        }
+    }
+}
+
+impl StateDiffFeed {
+    /// Publish the state changes as configured, starting the publisher
+    /// thread. The relative paths of the sink are resolved against the given
+    /// chain directory.
+    pub fn new(config: config::StateDiffFeed, chain_dir: &Path) -> Self {
+        let sink = match config.sink {
+            StateDiffSink::File(path) => {
+                StateDiffSink::File(chain_dir.join(path))
+            }
+            StateDiffSink::UnixSocket(path) => {
+                StateDiffSink::UnixSocket(chain_dir.join(path))
+            }
+        };
+        let key_prefixes = config
+            .key_prefixes
+            .iter()
+            .map(|prefix| {
+                Key::parse(prefix).unwrap_or_else(|e| {
+                    panic!(
+                        "Invalid key prefix \"{prefix}\" of the state diff \
+                         feed: {e}"
+                    )
+                })
+            })
+            .collect();
+        let (queue, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let publisher = Publisher { sink, writer: None };
+        let publisher = thread::Builder::new()
+            .name("state-diff-feed".into())
+            .spawn(move || publisher.run(receiver))
+            .expect("Must be able to start a thread for the state diff feed");
+        Self {
+            encoding: config.encoding,
+            key_prefixes,
+            queue: Some(queue),
+            publisher: Some(publisher),
+        }
+    }
+
+    /// Get the changes of the block at the given height of the keys with one
+    /// of the configured prefixes
+    fn state_diff<D, H>(
+        &self,
+        storage: &State<D, H>,
+        height: BlockHeight,
+    ) -> StateDiff
+    where
+        D: DB + for<'iter> DBIter<'iter>,
+        H: StorageHasher,
+    {
+        if self.key_prefixes.is_empty() {
+            return storage.get_state_diff(height, None);
+        }
+        let mut changes: Vec<_> = self
+            .key_prefixes
+            .iter()
+            .flat_map(|prefix| {
+                storage.get_state_diff(height, Some(prefix)).changes
+            })
+            .collect();
+        // The prefixes may overlap
+        changes.sort_by_cached_key(|change| change.key.to_string());
+        changes.dedup_by(|a, b| a.key == b.key);
+        StateDiff { height, changes }
+    }
+
+    /// Encode the changes of a block for the sink
+    fn encode(&self, diff: &StateDiff) -> Vec<u8> {
+        match self.encoding {
+            StateDiffEncoding::Json => {
+                let mut bytes = serde_json::to_vec(diff)
+                    .expect("Encoding a state diff to JSON shouldn't fail");
+                bytes.push(b'\n');
+                bytes
+            }
+            StateDiffEncoding::Borsh => {
+                let bytes = diff.serialize_to_vec();
+                let mut encoded = (bytes.len() as u64).to_le_bytes().to_vec();
+                encoded.extend(bytes);
+                encoded
+            }
+        }
+    }
+
+    /// Queue the encoded changes of a block to the publisher thread, without
+    /// waiting on the sink
+    fn queue(&self, height: BlockHeight, bytes: Vec<u8>) {
+        let queue = self.queue.as_ref().expect("The queue should be open");
+        match queue.try_send((height, bytes)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => tracing::error!(
+                "Dropped the state diff of the block at height {height}: the \
+                 queue of the state diff feed is full"
+            ),
+            Err(TrySendError::Disconnected(_)) => tracing::error!(
+                "Dropped the state diff of the block at height {height}: the \
+                 publisher of the state diff feed has stopped"
+            ),
+        }
+    }
+}
+
+impl Drop for StateDiffFeed {
+    /// Close the queue and wait for the publisher thread to write the
+    /// remaining changes
+    fn drop(&mut self) {
+        drop(self.queue.take());
+        if let Some(publisher) = self.publisher.take() {
+            if publisher.join().is_err() {
+                tracing::error!("The state diff feed publisher panicked");
+            }
+        }
+    }
+}
+
+impl Publisher {
+    /// Write the queued changes to the sink until the queue is closed
+    fn run(mut self, receiver: Receiver<(BlockHeight, Vec<u8>)>) {
+        for (height, bytes) in receiver {
+            if let Err(e) = self.write(&bytes) {
+                tracing::error!(
+                    "Failed to publish the state diff of the block at height \
+                     {height} to {:?}: {e}",
+                    self.sink
+                );
+            }
+        }
+    }
+
+    /// Write the encoded changes of a block to the sink, opening it first if
+    /// needed. The sink is closed if the write fails.
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.writer.is_none() {
+            self.writer = Some(self.open()?);
+        }
+        let writer = self.writer.as_mut().expect("The sink should be open");
+        let result = writer.write_all(bytes).and_then(|()| writer.flush());
+        if result.is_err() {
+            self.writer = None;
+        }
+        result
+    }
+
+    /// Open the sink
+    fn open(&self) -> io::Result<Writer> {
+        match &self.sink {
+            StateDiffSink::File(path) => OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map(Writer::File),
+            StateDiffSink::UnixSocket(path) => connect(path),
+        }
+    }
+}
+
+/// Connect to the Unix socket listening at the given path
+#[cfg(unix)]
+fn connect(path: &Path) -> io::Result<Writer> {
+    let stream = UnixStream::connect(path)?;
+    stream.set_write_timeout(Some(SOCKET_WRITE_TIMEOUT))?;
+    Ok(Writer::Socket(stream))
+}
+
+/// Unix sockets are only available on Unix platforms
+#[cfg(not(unix))]
+fn connect(_path: &Path) -> io::Result<Writer> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Unix sockets are not supported on this platform",
+    ))
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Queue the changes of the state committed in the last block to the
+    /// state diff feed, if it's enabled
+    pub fn publish_state_diff(&mut self) {
+        let Some(feed) = self.state_diff_feed.as_ref() else {
+            return;
+        };
+        let height = self.wl_storage.storage.get_last_block_height();
+        let diff = feed.state_diff(&self.wl_storage.storage, height);
+        let bytes = feed.encode(&diff);
+        feed.queue(height, bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::state::StorageWrite;
+    use namada::types::storage::StateChange;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::TestShell;
+
+    /// Test that the changes of the committed blocks are appended to a file
+    /// sink, filtered by the key prefixes.
+    #[test]
+    fn test_publish_state_diff() {
+        let (mut shell, _, _, _) = TestShell::new();
+        let dir = tempdir().unwrap();
+        let config = config::StateDiffFeed {
+            sink: StateDiffSink::File("state_diffs.jsonl".into()),
+            encoding: StateDiffEncoding::Json,
+            key_prefixes: vec!["feed".to_owned()],
+        };
+        shell.state_diff_feed = Some(StateDiffFeed::new(config, dir.path()));
+
+        let key = Key::parse("feed/key").unwrap();
+        let other_key = Key::parse("other/key").unwrap();
+        shell.wl_storage.write(&key, 1_u64).unwrap();
+        shell.wl_storage.write(&other_key, 1_u64).unwrap();
+        shell.commit();
+        shell.publish_state_diff();
+        shell.wl_storage.storage.block.height =
+            shell.wl_storage.storage.block.height.next_height();
+        shell.wl_storage.delete(&key).unwrap();
+        shell.commit();
+        shell.publish_state_diff();
+        // Wait for the publisher thread to write the queued changes
+        shell.state_diff_feed = None;
+
+        let published =
+            std::fs::read_to_string(dir.path().join("state_diffs.jsonl"))
+                .unwrap();
+        let diffs: Vec<StateDiff> = published
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            diffs,
+            vec![
+                StateDiff {
+                    height: BlockHeight(1),
+                    changes: vec![StateChange {
+                        key: key.clone(),
+                        old: None,
+                        new: Some(1_u64.serialize_to_vec()),
+                    }],
+                },
+                StateDiff {
+                    height: BlockHeight(2),
+                    changes: vec![StateChange {
+                        key,
+                        old: Some(1_u64.serialize_to_vec()),
+                        new: None,
+                    }],
+                },
+            ]
+        );
+    }
+}
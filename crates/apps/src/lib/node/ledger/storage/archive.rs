@@ -0,0 +1,278 @@
+//! Portable archives of the persistent storage.
+//!
+//! An archive holds the same DB entries as a state sync snapshot, i.e. the
+//! subspace, the merkle tree stores, the block state and the replay
+//! protection entries needed to resume the chain from the last committed
+//! block. It's a gzip compressed stream of a borsh encoded header, followed by
+//! the entries and terminated by the SHA-256 checksum of the header and the
+//! entries.
+//!
+//! Only the state of the last committed block can be exported, as the DB
+//! doesn't keep the block state of older heights. To export the state at a
+//! given height, the node has to be halted at that height first, e.g. with the
+//! `ledger run-until` command.
+
+use std::io::{self, Read, Write};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use namada::state::{
+    self, DbError, MerkleTree, Sha256Hasher, State, StorageHasher, DB,
+};
+use namada::types::chain::ChainId;
+use namada::types::hash::Hash;
+use namada::types::storage::BlockHeight;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::backend::PersistentDB;
+use super::snapshot::Entry;
+
+/// The version of the archive format
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// The size of the entries written to the DB at once on import
+const IMPORT_BATCH_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("DB error: {0}")]
+    Db(#[from] DbError),
+    #[error("State error: {0}")]
+    State(#[from] state::Error),
+    #[error("The DB doesn't contain any committed block")]
+    NoState,
+    #[error(
+        "The state at height {requested} cannot be exported, the last \
+         committed block is at height {last}"
+    )]
+    UnavailableHeight {
+        requested: BlockHeight,
+        last: BlockHeight,
+    },
+    #[error("The DB already contains a committed block")]
+    NonEmptyDb,
+    #[error("Unsupported archive version {0}")]
+    UnsupportedVersion(u32),
+    #[error("The archive is for chain {found}, expected {expected}")]
+    ChainIdMismatch { expected: ChainId, found: ChainId },
+    #[error("The archive's checksum doesn't match its content")]
+    InvalidChecksum,
+    #[error(
+        "The imported state at height {height} with merkle root {root} \
+         doesn't match the archive's header {header:?}"
+    )]
+    StateMismatch {
+        height: BlockHeight,
+        root: Hash,
+        header: ArchiveHeader,
+    },
+}
+
+/// Archive's result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The header of an archive
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ArchiveHeader {
+    /// The version of the archive format
+    pub version: u32,
+    /// The chain of the archived state
+    pub chain_id: ChainId,
+    /// The height of the last committed block
+    pub height: BlockHeight,
+    /// The merkle root of the last committed block
+    pub merkle_root: Hash,
+}
+
+/// Export the state of the last block committed to the DB into an archive.
+/// If a height is given, it must be the height of the last committed block.
+pub fn export_state(
+    db: &PersistentDB,
+    chain_id: ChainId,
+    height: Option<BlockHeight>,
+    out: impl Write,
+) -> Result<ArchiveHeader> {
+    let (last_height, merkle_root) =
+        read_last_state(db)?.ok_or(Error::NoState)?;
+    match height {
+        Some(height) if height != last_height => {
+            return Err(Error::UnavailableHeight {
+                requested: height,
+                last: last_height,
+            });
+        }
+        _ => {}
+    }
+    let height = last_height;
+    let header = ArchiveHeader {
+        version: ARCHIVE_VERSION,
+        chain_id,
+        height,
+        merkle_root,
+    };
+
+    let encoder = GzEncoder::new(out, Compression::default());
+    let mut writer = ChecksumWriter {
+        inner: encoder,
+        hasher: Sha256::new(),
+    };
+    header.serialize(&mut writer)?;
+    db.for_each_snapshot_entry(|cf, key, value| {
+        let entry = Entry {
+            cf: cf.to_owned(),
+            key: key.to_vec(),
+            value: value.to_vec(),
+        };
+        Some(entry).serialize(&mut writer).map_err(Error::from)
+    })?;
+    None::<Entry>.serialize(&mut writer)?;
+
+    let checksum = writer.hasher.finalize();
+    let mut encoder = writer.inner;
+    encoder.write_all(&checksum)?;
+    encoder.finish()?.flush()?;
+    Ok(header)
+}
+
+/// Import the state from an archive into the empty DB of the given storage.
+/// The checksum of the archive is verified, and the merkle tree is rebuilt
+/// from the imported subspace to verify it against the merkle root of the
+/// archive's header and of the imported tree stores. On failure, the DB is
+/// cleared.
+pub fn import_state<H: StorageHasher>(
+    storage: &mut State<PersistentDB, H>,
+    chain_id: &ChainId,
+    archive: impl Read,
+) -> Result<ArchiveHeader> {
+    if storage.db.read_last_block()?.is_some() {
+        return Err(Error::NonEmptyDb);
+    }
+    let result = import_entries(&mut storage.db, chain_id, archive)
+        .and_then(|header| verify_imported_state(storage, header));
+    if result.is_err() {
+        storage.last_block = None;
+        storage.db.clear()?;
+    }
+    result
+}
+
+/// Load the imported state and check that its height and the root of the
+/// merkle tree rebuilt from its subspace match the archive's header
+fn verify_imported_state<H: StorageHasher>(
+    storage: &mut State<PersistentDB, H>,
+    header: ArchiveHeader,
+) -> Result<ArchiveHeader> {
+    storage.load_last_state()?;
+    let (_, height) = storage.get_state().ok_or(Error::NoState)?;
+    let root = storage.rebuild_merkle_tree_from_subspace()?.root();
+    let height = BlockHeight(height);
+    let root = Hash(root.0);
+    if height != header.height
+        || root != header.merkle_root
+        || root != Hash(storage.merkle_root().0)
+    {
+        return Err(Error::StateMismatch {
+            height,
+            root,
+            header,
+        });
+    }
+    Ok(header)
+}
+
+fn import_entries(
+    db: &mut PersistentDB,
+    chain_id: &ChainId,
+    archive: impl Read,
+) -> Result<ArchiveHeader> {
+    let mut reader = ChecksumReader {
+        inner: GzDecoder::new(archive),
+        hasher: Sha256::new(),
+    };
+    let header = ArchiveHeader::deserialize_reader(&mut reader)?;
+    if header.version != ARCHIVE_VERSION {
+        return Err(Error::UnsupportedVersion(header.version));
+    }
+    if &header.chain_id != chain_id {
+        return Err(Error::ChainIdMismatch {
+            expected: chain_id.clone(),
+            found: header.chain_id,
+        });
+    }
+
+    let mut entries = vec![];
+    let mut size = 0;
+    while let Some(entry) = Option::<Entry>::deserialize_reader(&mut reader)? {
+        size += entry.cf.len() + entry.key.len() + entry.value.len();
+        entries.push(entry);
+        if size >= IMPORT_BATCH_SIZE {
+            write_entries(db, &entries)?;
+            entries.clear();
+            size = 0;
+        }
+    }
+    write_entries(db, &entries)?;
+
+    let checksum = reader.hasher.finalize();
+    let mut expected_checksum = [0_u8; 32];
+    reader.inner.read_exact(&mut expected_checksum)?;
+    if checksum.as_slice() != expected_checksum {
+        return Err(Error::InvalidChecksum);
+    }
+    Ok(header)
+}
+
+fn write_entries(db: &mut PersistentDB, entries: &[Entry]) -> Result<()> {
+    db.write_snapshot_entries(entries.iter().map(|entry| {
+        (entry.cf.as_str(), entry.key.as_slice(), entry.value.as_slice())
+    }))?;
+    Ok(())
+}
+
+/// Read the height and the merkle root of the last committed block, if any
+fn read_last_state(db: &PersistentDB) -> Result<Option<(BlockHeight, Hash)>> {
+    let block = match db.read_last_block()? {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    let tree = MerkleTree::<Sha256Hasher>::new(block.merkle_tree_stores)
+        .map_err(DbError::from)?;
+    Ok(Some((block.height, Hash(tree.root().0))))
+}
+
+/// Computes the checksum of the written bytes
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Computes the checksum of the read bytes
+struct ChecksumReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
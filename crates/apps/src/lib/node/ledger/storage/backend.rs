@@ -0,0 +1,515 @@
+//! The persistent DB, backed by one of the storage backends that the node is
+//! built with. A new DB is created with the backend selected in the config,
+//! while an existing DB is always opened with the backend that created it.
+
+use std::path::{Path, PathBuf};
+
+use namada::state::{
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
+    DbResult as Result, MerkleTreeStoresRead, StoreType, DB,
+};
+use namada::types::ethereum_events;
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Epoch, Header, Key};
+
+#[cfg(feature = "redb")]
+use super::redb::{self, RedbDB, RedbWriteBatch};
+#[cfg(feature = "rocksdb")]
+use super::rocksdb::{self, RocksDB, RocksDBWriteBatch};
+use crate::config::DbBackend;
+
+#[cfg(not(any(feature = "rocksdb", feature = "redb")))]
+compile_error!(
+    "At least one of the `rocksdb` and `redb` storage backend features must \
+     be enabled"
+);
+
+/// The file of a DB created by redb
+pub(super) const REDB_FILE: &str = "namada.redb";
+/// The file that RocksDB creates in the directory of a DB
+const ROCKSDB_CURRENT_FILE: &str = "CURRENT";
+
+/// The persistent DB
+#[derive(Debug)]
+pub enum PersistentDB {
+    #[cfg(feature = "rocksdb")]
+    RocksDB(RocksDB),
+    #[cfg(feature = "redb")]
+    Redb(RedbDB),
+}
+
+/// The cache of the persistent DB, which also selects the backend of a new DB
+pub enum DbCache {
+    #[cfg(feature = "rocksdb")]
+    RocksDB(::rocksdb::Cache),
+    /// The size of the cache in bytes
+    #[cfg(feature = "redb")]
+    Redb(usize),
+}
+
+/// DB Handle for batch writes. A batch is created before the backend of the
+/// DB it's written to is known, so it holds a batch for every backend.
+#[derive(Default)]
+pub struct PersistentDBWriteBatch {
+    #[cfg(feature = "rocksdb")]
+    rocksdb: RocksDBWriteBatch,
+    #[cfg(feature = "redb")]
+    redb: RedbWriteBatch,
+}
+
+/// The prefix iterator of the persistent DB
+#[derive(Debug)]
+pub enum PersistentPrefixIterator<'a> {
+    #[cfg(feature = "rocksdb")]
+    RocksDB(rocksdb::PersistentPrefixIterator<'a>),
+    #[cfg(feature = "redb")]
+    Redb(redb::PersistentPrefixIterator<'a>),
+}
+
+/// Evaluate the same expression with the DB of any backend
+macro_rules! dispatch {
+    ($db:expr, $inner:ident => $body:expr) => {
+        match $db {
+            #[cfg(feature = "rocksdb")]
+            PersistentDB::RocksDB($inner) => $body,
+            #[cfg(feature = "redb")]
+            PersistentDB::Redb($inner) => $body,
+        }
+    };
+}
+
+/// Evaluate the same expression with the DB of any backend and its batch
+macro_rules! dispatch_batch {
+    ($db:expr, $batch:expr, $inner:ident, $inner_batch:ident => $body:expr) => {
+        match $db {
+            #[cfg(feature = "rocksdb")]
+            PersistentDB::RocksDB($inner) => {
+                let $inner_batch = &mut $batch.rocksdb;
+                $body
+            }
+            #[cfg(feature = "redb")]
+            PersistentDB::Redb($inner) => {
+                let $inner_batch = &mut $batch.redb;
+                $body
+            }
+        }
+    };
+}
+
+/// Create the prefix iterator of the DB of any backend
+macro_rules! dispatch_iter {
+    ($db:expr, $inner:ident => $body:expr) => {
+        match $db {
+            #[cfg(feature = "rocksdb")]
+            PersistentDB::RocksDB($inner) => {
+                PersistentPrefixIterator::RocksDB($body)
+            }
+            #[cfg(feature = "redb")]
+            PersistentDB::Redb($inner) => PersistentPrefixIterator::Redb($body),
+        }
+    };
+}
+
+/// Open the DB in the given directory. An existing DB is opened with the
+/// backend that created it, which must match the backend of the given cache,
+/// if any. A new DB is created with the backend of the given cache, if any,
+/// or else with the default backend.
+pub fn open(
+    path: impl AsRef<Path>,
+    cache: Option<&DbCache>,
+) -> Result<PersistentDB> {
+    let path = path.as_ref();
+    let backend = match (existing_backend(path), cache) {
+        (Some(existing), Some(cache)) if existing != cache.backend() => {
+            return Err(Error::DBError(format!(
+                "The DB in {} was created with the {existing:?} backend, but \
+                 the {:?} backend is configured",
+                path.to_string_lossy(),
+                cache.backend()
+            )));
+        }
+        (Some(existing), _) => existing,
+        (None, Some(cache)) => cache.backend(),
+        (None, None) => DbBackend::default(),
+    };
+    tracing::info!("Using the {backend:?} storage backend.");
+    match backend {
+        #[cfg(feature = "rocksdb")]
+        DbBackend::RocksDB => {
+            let cache = match cache {
+                Some(DbCache::RocksDB(cache)) => Some(cache),
+                _ => None,
+            };
+            rocksdb::open(path, cache).map(PersistentDB::RocksDB)
+        }
+        #[cfg(feature = "redb")]
+        DbBackend::Redb => {
+            let cache_size = match cache {
+                Some(DbCache::Redb(cache_size)) => Some(*cache_size),
+                _ => None,
+            };
+            redb::open(path, cache_size).map(PersistentDB::Redb)
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported_backend(backend)),
+    }
+}
+
+/// The backend of the DB in the given directory, if any
+fn existing_backend(path: &Path) -> Option<DbBackend> {
+    if path.join(REDB_FILE).is_file() {
+        Some(DbBackend::Redb)
+    } else if path.join(ROCKSDB_CURRENT_FILE).is_file() {
+        Some(DbBackend::RocksDB)
+    } else {
+        None
+    }
+}
+
+fn unsupported_backend(backend: DbBackend) -> Error {
+    Error::DBError(format!(
+        "The node was built without the {backend:?} storage backend"
+    ))
+}
+
+impl DbCache {
+    /// Create a cache of the given size in bytes for the given backend, which
+    /// must be one that the node is built with.
+    pub fn new(backend: DbBackend, size_bytes: usize) -> Result<Self> {
+        match backend {
+            #[cfg(feature = "rocksdb")]
+            DbBackend::RocksDB => {
+                Ok(Self::RocksDB(::rocksdb::Cache::new_lru_cache(size_bytes)))
+            }
+            #[cfg(feature = "redb")]
+            DbBackend::Redb => Ok(Self::Redb(size_bytes)),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_backend(backend)),
+        }
+    }
+
+    /// The backend of the cache
+    pub fn backend(&self) -> DbBackend {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDB(_) => DbBackend::RocksDB,
+            #[cfg(feature = "redb")]
+            Self::Redb(_) => DbBackend::Redb,
+        }
+    }
+}
+
+impl PersistentDB {
+    /// The backend of the DB
+    pub fn backend(&self) -> DbBackend {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDB(_) => DbBackend::RocksDB,
+            #[cfg(feature = "redb")]
+            Self::Redb(_) => DbBackend::Redb,
+        }
+    }
+
+    /// Dump last known block
+    pub fn dump_block(
+        &self,
+        out_file_path: PathBuf,
+        historic: bool,
+        height: Option<BlockHeight>,
+    ) {
+        dispatch!(self, db => db.dump_block(out_file_path, historic, height))
+    }
+
+    /// Rollback to previous block
+    pub fn rollback(
+        &mut self,
+        tendermint_block_height: BlockHeight,
+    ) -> Result<()> {
+        dispatch!(self, db => db.rollback(tendermint_block_height))
+    }
+
+    /// Create a checkpoint of the DB in the given directory, which must not
+    /// exist yet.
+    pub fn checkpoint(&self, dir: impl AsRef<Path>) -> Result<()> {
+        dispatch!(self, db => db.checkpoint(dir))
+    }
+
+    /// Call the given function with every entry needed to resume the chain
+    /// from the last committed block, along with the name of its column
+    /// family.
+    pub fn for_each_snapshot_entry<E>(
+        &self,
+        f: impl FnMut(&str, &[u8], &[u8]) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<Error>,
+    {
+        dispatch!(self, db => db.for_each_snapshot_entry(f))
+    }
+
+    /// Write the entries of a state sync snapshot chunk, given with the name
+    /// of their column family.
+    pub fn write_snapshot_entries<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'a str, &'a [u8], &'a [u8])>,
+    ) -> Result<()> {
+        dispatch!(self, db => db.write_snapshot_entries(entries))
+    }
+
+    /// Delete all the entries of the DB
+    pub fn clear(&mut self) -> Result<()> {
+        dispatch!(self, db => db.clear())
+    }
+}
+
+impl DB for PersistentDB {
+    type Cache = DbCache;
+    type WriteBatch = PersistentDBWriteBatch;
+
+    fn open(
+        db_path: impl AsRef<std::path::Path>,
+        cache: Option<&Self::Cache>,
+    ) -> Self {
+        open(db_path, cache).expect("cannot open the DB")
+    }
+
+    fn flush(&self, wait: bool) -> Result<()> {
+        dispatch!(self, db => db.flush(wait))
+    }
+
+    fn read_last_block(&self) -> Result<Option<BlockStateRead>> {
+        dispatch!(self, db => db.read_last_block())
+    }
+
+    fn add_block_to_batch(
+        &self,
+        state: BlockStateWrite,
+        batch: &mut Self::WriteBatch,
+        is_full_commit: bool,
+    ) -> Result<()> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.add_block_to_batch(state, batch, is_full_commit)
+        })
+    }
+
+    fn read_block_header(&self, height: BlockHeight) -> Result<Option<Header>> {
+        dispatch!(self, db => db.read_block_header(height))
+    }
+
+    fn read_merkle_tree_stores(
+        &self,
+        epoch: Epoch,
+        base_height: BlockHeight,
+        store_type: Option<StoreType>,
+    ) -> Result<Option<MerkleTreeStoresRead>> {
+        dispatch!(self, db => {
+            db.read_merkle_tree_stores(epoch, base_height, store_type)
+        })
+    }
+
+    fn has_replay_protection_entry(&self, hash: &Hash) -> Result<bool> {
+        dispatch!(self, db => db.has_replay_protection_entry(hash))
+    }
+
+    fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        dispatch!(self, db => db.read_subspace_val(key))
+    }
+
+    fn read_subspace_val_with_height(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>> {
+        dispatch!(self, db => {
+            db.read_subspace_val_with_height(key, height, last_height)
+        })
+    }
+
+    fn read_diffs_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        is_old: bool,
+    ) -> Result<Option<Vec<u8>>> {
+        dispatch!(self, db => db.read_diffs_val(key, height, is_old))
+    }
+
+    fn write_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        dispatch!(self, db => {
+            db.write_subspace_val(height, key, value, persist_diffs)
+        })
+    }
+
+    fn delete_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        dispatch!(self, db => {
+            db.delete_subspace_val(height, key, persist_diffs)
+        })
+    }
+
+    fn batch() -> Self::WriteBatch {
+        PersistentDBWriteBatch::default()
+    }
+
+    fn exec_batch(&mut self, batch: Self::WriteBatch) -> Result<()> {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            PersistentDB::RocksDB(db) => DB::exec_batch(db, batch.rocksdb),
+            #[cfg(feature = "redb")]
+            PersistentDB::Redb(db) => DB::exec_batch(db, batch.redb),
+        }
+    }
+
+    fn batch_write_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.batch_write_subspace_val(
+                batch,
+                height,
+                key,
+                value,
+                persist_diffs,
+            )
+        })
+    }
+
+    fn batch_delete_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.batch_delete_subspace_val(batch, height, key, persist_diffs)
+        })
+    }
+
+    fn prune_merkle_tree_store(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        store_type: &StoreType,
+        pruned_epoch: Epoch,
+    ) -> Result<()> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.prune_merkle_tree_store(batch, store_type, pruned_epoch)
+        })
+    }
+
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        max_heights: u64,
+    ) -> Result<Option<BlockHeight>> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.prune_history(batch, height, max_heights)
+        })
+    }
+
+    fn read_bridge_pool_signed_nonce(
+        &self,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<ethereum_events::Uint>> {
+        dispatch!(self, db => {
+            db.read_bridge_pool_signed_nonce(height, last_height)
+        })
+    }
+
+    fn write_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> Result<()> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.write_replay_protection_entry(batch, key)
+        })
+    }
+
+    fn delete_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> Result<()> {
+        dispatch_batch!(self, batch, db, batch => {
+            db.delete_replay_protection_entry(batch, key)
+        })
+    }
+}
+
+impl<'iter> DBIter<'iter> for PersistentDB {
+    type PrefixIter = PersistentPrefixIterator<'iter>;
+
+    fn iter_prefix(
+        &'iter self,
+        prefix: Option<&Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        dispatch_iter!(self, db => db.iter_prefix(prefix))
+    }
+
+    fn iter_prefix_from(
+        &'iter self,
+        prefix: Option<&Key>,
+        start: &Key,
+    ) -> PersistentPrefixIterator<'iter> {
+        dispatch_iter!(self, db => db.iter_prefix_from(prefix, start))
+    }
+
+    fn iter_results(&'iter self) -> PersistentPrefixIterator<'iter> {
+        dispatch_iter!(self, db => db.iter_results())
+    }
+
+    fn iter_old_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        dispatch_iter!(self, db => db.iter_old_diffs(height, prefix))
+    }
+
+    fn iter_new_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        dispatch_iter!(self, db => db.iter_new_diffs(height, prefix))
+    }
+
+    fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
+        dispatch_iter!(self, db => db.iter_replay_protection())
+    }
+}
+
+impl<'a> Iterator for PersistentPrefixIterator<'a> {
+    type Item = (String, Vec<u8>, u64);
+
+    /// Returns the next pair and the gas cost
+    fn next(&mut self) -> Option<(String, Vec<u8>, u64)> {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDB(iter) => iter.next(),
+            #[cfg(feature = "redb")]
+            Self::Redb(iter) => iter.next(),
+        }
+    }
+}
+
+impl DBWriteBatch for PersistentDBWriteBatch {}
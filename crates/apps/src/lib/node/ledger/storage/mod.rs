@@ -1,7 +1,13 @@
 //! The storage module handles both the current state in-memory and the stored
 //! state in DB.
 
+pub mod archive;
+mod backend;
+#[cfg(feature = "redb")]
+mod redb;
+#[cfg(feature = "rocksdb")]
 mod rocksdb;
+pub mod snapshot;
 
 use std::fmt;
 
@@ -11,11 +17,11 @@ use arse_merkle_tree::H256;
 use blake2b_rs::{Blake2b, Blake2bBuilder};
 use namada::state::{State, StorageHasher};
 
+pub use self::backend::{DbCache, PersistentDB};
+
 #[derive(Default)]
 pub struct PersistentStorageHasher(Blake2bHasher);
 
-pub type PersistentDB = rocksdb::RocksDB;
-
 pub type PersistentStorage = State<PersistentDB, PersistentStorageHasher>;
 
 impl Hasher for PersistentStorageHasher {
@@ -53,7 +59,11 @@ mod tests {
     use std::collections::HashMap;
 
     use borsh::BorshDeserialize;
+    use borsh_ext::BorshSerializeExt;
     use itertools::Itertools;
+    use masp_primitives::bls12_381;
+    use masp_primitives::merkle_tree::FrozenCommitmentTree;
+    use masp_primitives::sapling::Node;
     use namada::eth_bridge::storage::proof::BridgePoolRootProof;
     use namada::ledger::eth_bridge::storage::bridge_pool;
     use namada::ledger::gas::STORAGE_ACCESS_GAS_PER_BYTE;
@@ -64,12 +74,14 @@ mod tests {
         self, StorageRead, StorageWrite, StoreType, WlStorage, DB,
     };
     use namada::token::conversion::update_allowed_conversions;
+    use namada::token::storage_key::masp_convert_anchor_key;
     use namada::types::chain::ChainId;
     use namada::types::ethereum_events::Uint;
     use namada::types::hash::Hash;
     use namada::types::keccak::KeccakHash;
     use namada::types::storage::{BlockHash, BlockHeight, Key};
     use namada::types::time::DurationSecs;
+    use namada::types::token::ConversionState;
     use namada::types::{address, storage};
     use namada::{parameters, types};
     use proptest::collection::vec;
@@ -267,6 +279,324 @@ mod tests {
         }
     }
 
+    /// Test that a state sync snapshot restores the last committed state in
+    /// a fresh DB, and that tampered chunks or block state are rejected.
+    #[test]
+    fn test_snapshot_restore() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = PersistentStorage::open(
+            db_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(100))
+            .expect("begin_block failed");
+        let key = Key::parse("key").expect("cannot parse the key string");
+        let value_bytes = types::encode(&1_u64);
+        storage
+            .write(&key, value_bytes.clone())
+            .expect("write failed");
+        // the restored conversion tree is verified against its anchor
+        let anchor = Hash(
+            bls12_381::Scalar::from(storage.conversion_state.tree.root())
+                .to_bytes(),
+        );
+        storage
+            .write(&masp_convert_anchor_key(), anchor.serialize_to_vec())
+            .expect("write failed");
+        let batch = PersistentStorage::batch();
+        storage.commit_block(batch).expect("commit failed");
+        let (root, height) = storage.get_state().expect("no block exists");
+
+        // write a snapshot of the committed block
+        let snapshots_dir =
+            TempDir::new().expect("Unable to create a snapshots directory");
+        let store = snapshot::SnapshotStore::new(snapshots_dir.path());
+        let metadata = store
+            .write_snapshot(&storage.db, BlockHeight(100))
+            .expect("writing the snapshot failed");
+        let snapshots = store.list().expect("listing the snapshots failed");
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].height, BlockHeight(100));
+        assert_eq!(snapshots[0].metadata, metadata);
+
+        // restore it in a fresh DB
+        let restored_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut restored = PersistentStorage::open(
+            restored_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        let mut chunk = store
+            .load_chunk(BlockHeight(100), 0)
+            .expect("loading the chunk failed");
+        let last_byte = chunk.last_mut().expect("chunk is empty");
+        *last_byte = last_byte.wrapping_add(1);
+        assert!(matches!(
+            snapshot::apply_chunk(&mut restored.db, &metadata, 0, &chunk),
+            Err(snapshot::Error::InvalidChunkHash(0))
+        ));
+        for index in 0..metadata.chunks() {
+            let chunk = store
+                .load_chunk(BlockHeight(100), index)
+                .expect("loading the chunk failed");
+            snapshot::apply_chunk(&mut restored.db, &metadata, index, &chunk)
+                .expect("applying the chunk failed");
+        }
+        snapshot::verify_restored_state(
+            &mut restored,
+            BlockHeight(100),
+            root.0.as_slice(),
+        )
+        .expect("verifying the restored state failed");
+        let (restored_root, restored_height) =
+            restored.get_state().expect("no block was restored");
+        assert_eq!(restored_root.0, root.0);
+        assert_eq!(restored_height, height);
+        let (val, _) = restored.read(&key).expect("read failed");
+        assert_eq!(val.expect("no value"), value_bytes);
+
+        // tamper a subspace value, re-encoding the chunks and the metadata
+        // so that they are consistent, but not with the app hash
+        let tampered_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut tampered = PersistentStorage::open(
+            tampered_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        let mut chunks = vec![];
+        for index in 0..metadata.chunks() {
+            let chunk = store
+                .load_chunk(BlockHeight(100), index)
+                .expect("loading the chunk failed");
+            let mut entries = Vec::<snapshot::Entry>::try_from_slice(&chunk)
+                .expect("decoding the chunk failed");
+            for entry in entries.iter_mut() {
+                if entry.cf == "subspace"
+                    && entry.key == key.to_string().as_bytes()
+                {
+                    entry.value = types::encode(&2_u64);
+                }
+            }
+            chunks.push(entries.serialize_to_vec());
+        }
+        let tampered_metadata = snapshot::SnapshotMetadata {
+            chunk_hashes: chunks.iter().map(Hash::sha256).collect(),
+        };
+        for (index, chunk) in chunks.iter().enumerate() {
+            snapshot::apply_chunk(
+                &mut tampered.db,
+                &tampered_metadata,
+                index as u32,
+                chunk,
+            )
+            .expect("applying the chunk failed");
+        }
+        // the tree stores of the snapshot still match the app hash
+        tampered
+            .load_last_state()
+            .expect("loading the tampered state failed");
+        assert_eq!(tampered.merkle_root().0, root.0);
+        assert!(matches!(
+            snapshot::verify_restored_state(
+                &mut tampered,
+                BlockHeight(100),
+                root.0.as_slice(),
+            ),
+            Err(snapshot::Error::InvalidRestoredState { .. })
+        ));
+
+        // tamper the conversion state, which isn't committed to by the app
+        // hash
+        let tampered_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut tampered = PersistentStorage::open(
+            tampered_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        let conversion_state = ConversionState {
+            tree: FrozenCommitmentTree::new(&[Node::new([1; 32])]),
+            ..Default::default()
+        };
+        let mut chunks = vec![];
+        for index in 0..metadata.chunks() {
+            let chunk = store
+                .load_chunk(BlockHeight(100), index)
+                .expect("loading the chunk failed");
+            let mut entries = Vec::<snapshot::Entry>::try_from_slice(&chunk)
+                .expect("decoding the chunk failed");
+            for entry in entries.iter_mut() {
+                if entry.cf == "state" && entry.key == b"conversion_state" {
+                    entry.value = types::encode(&conversion_state);
+                }
+            }
+            chunks.push(entries.serialize_to_vec());
+        }
+        let tampered_metadata = snapshot::SnapshotMetadata {
+            chunk_hashes: chunks.iter().map(Hash::sha256).collect(),
+        };
+        for (index, chunk) in chunks.iter().enumerate() {
+            snapshot::apply_chunk(
+                &mut tampered.db,
+                &tampered_metadata,
+                index as u32,
+                chunk,
+            )
+            .expect("applying the chunk failed");
+        }
+        assert!(matches!(
+            snapshot::verify_restored_state(
+                &mut tampered,
+                BlockHeight(100),
+                root.0.as_slice(),
+            ),
+            Err(snapshot::Error::InvalidRestoredBlockState(_))
+        ));
+    }
+
+    /// Test that the state exported into an archive is imported in a fresh
+    /// DB, and that a corrupted archive or an archive whose subspace doesn't
+    /// match its merkle root is rejected.
+    #[test]
+    fn test_export_import_state() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = PersistentStorage::open(
+            db_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(100))
+            .expect("begin_block failed");
+        let key = Key::parse("key").expect("cannot parse the key string");
+        let value_bytes = types::encode(&1_u64);
+        storage
+            .write(&key, value_bytes.clone())
+            .expect("write failed");
+        let batch = PersistentStorage::batch();
+        storage.commit_block(batch).expect("commit failed");
+        let (root, height) = storage.get_state().expect("no block exists");
+
+        assert!(matches!(
+            archive::export_state(
+                &storage.db,
+                ChainId::default(),
+                Some(BlockHeight(99)),
+                &mut vec![],
+            ),
+            Err(archive::Error::UnavailableHeight { .. })
+        ));
+        let mut exported = vec![];
+        let header = archive::export_state(
+            &storage.db,
+            ChainId::default(),
+            Some(BlockHeight(100)),
+            &mut exported,
+        )
+        .expect("exporting the state failed");
+        assert_eq!(header.height, BlockHeight(100));
+
+        let restored_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut restored = PersistentStorage::open(
+            restored_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        let mut corrupted = exported.clone();
+        let middle = corrupted.len() / 2;
+        corrupted[middle] = corrupted[middle].wrapping_add(1);
+        assert!(
+            archive::import_state(
+                &mut restored,
+                &ChainId::default(),
+                &corrupted[..]
+            )
+            .is_err()
+        );
+        let imported_header = archive::import_state(
+            &mut restored,
+            &ChainId::default(),
+            &exported[..],
+        )
+        .expect("importing the state failed");
+        assert_eq!(imported_header, header);
+
+        restored
+            .load_last_state()
+            .expect("loading the imported state failed");
+        let (restored_root, restored_height) =
+            restored.get_state().expect("no block was imported");
+        assert_eq!(restored_root.0, root.0);
+        assert_eq!(restored_height, height);
+        let (val, _) = restored.read(&key).expect("read failed");
+        assert_eq!(val.expect("no value"), value_bytes);
+
+        // tamper a subspace value without updating the tree, so that the
+        // header and the tree stores of the archive are still consistent
+        storage
+            .db
+            .write_subspace_val(
+                BlockHeight(100),
+                &key,
+                types::encode(&2_u64),
+                false,
+            )
+            .expect("write failed");
+        let mut tampered = vec![];
+        let tampered_header = archive::export_state(
+            &storage.db,
+            ChainId::default(),
+            None,
+            &mut tampered,
+        )
+        .expect("exporting the state failed");
+        assert_eq!(tampered_header, header);
+        let tampered_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut tampered_storage = PersistentStorage::open(
+            tampered_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+            is_merklized_storage_key,
+        );
+        assert!(matches!(
+            archive::import_state(
+                &mut tampered_storage,
+                &ChainId::default(),
+                &tampered[..]
+            ),
+            Err(archive::Error::StateMismatch { .. })
+        ));
+        assert!(tampered_storage.db.read_last_block().unwrap().is_none());
+    }
+
     #[test]
     fn test_validity_predicate() {
         let db_path =
@@ -653,7 +983,7 @@ mod tests {
         assert!(result.is_err(), "The bridge pool tree should be pruned");
     }
 
-    /// Test the prefix iterator with RocksDB.
+    /// Test the prefix iterator with the persistent DB.
     #[test]
     fn test_persistent_storage_prefix_iter() {
         let db_path =
@@ -0,0 +1,1986 @@
+//! The persistent storage in redb, a pure-Rust embedded key-value store.
+//!
+//! The DB is a single file in the DB directory, with a table for each of the
+//! RocksDB column families. The tables and their keys are the same as in
+//! RocksDB (see the `rocksdb` module), so that the state sync snapshots and
+//! the archives are independent of the backend.
+//!
+//! Every batch is written in a single write transaction, while the reads and
+//! the iterators use read transactions. The iterators read their entries in
+//! pages of [`ITER_PAGE_SIZE`] entries, each from its own read transaction.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Bound;
+use std::path::Path;
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use itertools::Either;
+use namada::eth_bridge::storage::proof::BridgePoolRootProof;
+use namada::ledger::eth_bridge::storage::bridge_pool;
+use namada::ledger::replay_protection;
+use namada::ledger::storage::tx_queue::TxQueue;
+use namada::state::merkle_tree::{
+    base_tree_key_prefix, subtree_key_prefix, MerkleTreeLayout,
+};
+use namada::state::{
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
+    DbResult as Result, MerkleTreeStoresRead, StoreType, DB,
+};
+use namada::types;
+use namada::types::storage::{
+    BlockHeight, BlockResults, Epoch, EthEventsQueue, Header, Key, KeySeg,
+    KEY_SEGMENT_SEPARATOR,
+};
+use namada::types::time::DateTimeUtc;
+use namada::types::token::ConversionState;
+use namada::types::{ethereum_events, ethereum_structs};
+use redb::{ReadTransaction, ReadableTable, TableDefinition};
+
+use super::backend::REDB_FILE;
+
+/// Table names, the same as the RocksDB column families
+const SUBSPACE_CF: &str = "subspace";
+const DIFFS_CF: &str = "diffs";
+const STATE_CF: &str = "state";
+const BLOCK_CF: &str = "block";
+const REPLAY_PROTECTION_CF: &str = "replay_protection";
+
+const TABLES: [&str; 5] = [
+    SUBSPACE_CF,
+    DIFFS_CF,
+    STATE_CF,
+    BLOCK_CF,
+    REPLAY_PROTECTION_CF,
+];
+
+const OLDEST_HEIGHT_KEY: &str = "oldest_height";
+
+const OLD_DIFF_PREFIX: &str = "old";
+const NEW_DIFF_PREFIX: &str = "new";
+
+/// The number of entries read at once by the prefix iterators
+const ITER_PAGE_SIZE: usize = 1024;
+
+/// redb handle
+pub struct RedbDB(redb::Database);
+
+/// DB Handle for batch writes. The writes are applied in a single write
+/// transaction.
+#[derive(Debug, Default)]
+pub struct RedbWriteBatch(Vec<BatchOp>);
+
+/// A write of a batch
+#[derive(Debug)]
+enum BatchOp {
+    Put {
+        table: &'static str,
+        key: String,
+        value: Vec<u8>,
+    },
+    Delete {
+        table: &'static str,
+        key: String,
+    },
+}
+
+impl RedbWriteBatch {
+    fn put(
+        &mut self,
+        table: &'static str,
+        key: impl Into<String>,
+        value: impl Into<Vec<u8>>,
+    ) {
+        self.0.push(BatchOp::Put {
+            table,
+            key: key.into(),
+            value: value.into(),
+        })
+    }
+
+    fn delete(&mut self, table: &'static str, key: impl Into<String>) {
+        self.0.push(BatchOp::Delete {
+            table,
+            key: key.into(),
+        })
+    }
+}
+
+/// Open redb for the DB, with a cache of the given size in bytes, if any
+pub fn open(
+    path: impl AsRef<Path>,
+    cache_size: Option<usize>,
+) -> Result<RedbDB> {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).map_err(db_error)?;
+    let mut builder = redb::Database::builder();
+    if let Some(cache_size) = cache_size {
+        builder.set_cache_size(cache_size);
+    }
+    let db = builder.create(path.join(REDB_FILE)).map_err(db_error)?;
+
+    // Create the tables, so that they can be opened by read transactions
+    let txn = db.begin_write().map_err(db_error)?;
+    for name in TABLES {
+        txn.open_table(table(name)).map_err(db_error)?;
+    }
+    txn.commit().map_err(db_error)?;
+    Ok(RedbDB(db))
+}
+
+impl std::fmt::Debug for RedbDB {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RedbDB")
+    }
+}
+
+impl RedbDB {
+    /// Read the value of a key from a table
+    fn get(&self, table_name: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let txn = self.0.begin_read().map_err(db_error)?;
+        let table = txn.open_table(table(table_name)).map_err(db_error)?;
+        let value = table
+            .get(key)
+            .map_err(db_error)?
+            .map(|value| value.value().to_vec());
+        Ok(value)
+    }
+
+    /// Read the entries of a table in the given range, whose key starts with
+    /// the given prefix, up to the given limit, if any.
+    fn read_range(
+        &self,
+        table_name: &str,
+        range: (Bound<&str>, Bound<&str>),
+        prefix: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let txn = self.0.begin_read().map_err(db_error)?;
+        let table = txn.open_table(table(table_name)).map_err(db_error)?;
+        let mut entries = vec![];
+        for entry in table.range::<&str>(range).map_err(db_error)? {
+            let (key, value) = entry.map_err(db_error)?;
+            let key = key.value();
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key.to_owned(), value.value().to_vec()));
+            if Some(entries.len()) == limit {
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Read the entries of a table whose key starts with the given prefix
+    fn read_prefix(
+        &self,
+        table_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        self.read_range(
+            table_name,
+            (Bound::Included(prefix), Bound::Unbounded),
+            prefix,
+            None,
+        )
+    }
+
+    /// Persist the diff of an account subspace key-val under the height where
+    /// it was changed in a batch write.
+    fn batch_write_subspace_diff(
+        &self,
+        batch: &mut RedbWriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        old_value: Option<&[u8]>,
+        new_value: Option<&[u8]>,
+        persist_diffs: bool,
+    ) -> Result<()> {
+        let (old_val_key, new_val_key) = old_and_new_diff_key(key, height)?;
+
+        if let Some(old_value) = old_value {
+            batch.put(DIFFS_CF, old_val_key, old_value);
+        }
+
+        if let Some(new_value) = new_value {
+            batch.put(DIFFS_CF, new_val_key, new_value);
+        }
+
+        // If not persisting the diffs, remove the last diffs.
+        if !persist_diffs && height > BlockHeight::first() {
+            let mut height = height.prev_height();
+            while height >= BlockHeight::first() {
+                let (old_diff_key, new_diff_key) =
+                    old_and_new_diff_key(key, height)?;
+                let has_old_diff = self.get(DIFFS_CF, &old_diff_key)?.is_some();
+                let has_new_diff = self.get(DIFFS_CF, &new_diff_key)?.is_some();
+                if has_old_diff {
+                    batch.delete(DIFFS_CF, old_diff_key);
+                }
+                if has_new_diff {
+                    batch.delete(DIFFS_CF, new_diff_key);
+                }
+                if has_old_diff || has_new_diff {
+                    break;
+                }
+                height = height.prev_height();
+            }
+        }
+        Ok(())
+    }
+
+    /// Dump last known block
+    pub fn dump_block(
+        &self,
+        out_file_path: std::path::PathBuf,
+        historic: bool,
+        height: Option<BlockHeight>,
+    ) {
+        // Find the last block height
+        let last_height: BlockHeight = types::decode(
+            self.get(STATE_CF, "height")
+                .expect("Unable to read DB")
+                .expect("No block height found"),
+        )
+        .expect("Unable to decode block height");
+
+        let height = height.unwrap_or(last_height);
+
+        let full_path = out_file_path
+            .with_file_name(format!(
+                "{}_{height}",
+                out_file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "dump_db".to_string())
+            ))
+            .with_extension("toml");
+
+        let file = File::options()
+            .append(true)
+            .create_new(true)
+            .open(&full_path)
+            .expect("Cannot open the output file");
+        let mut buf = BufWriter::new(file);
+
+        println!("Will write to {} ...", full_path.to_string_lossy());
+
+        if historic {
+            // Dump the keys prepended with the selected block height (includes
+            // subspace diff keys)
+            let prefix = height.raw();
+            self.dump_it(DIFFS_CF, &prefix, &mut buf);
+            self.dump_it(BLOCK_CF, &prefix, &mut buf);
+        }
+
+        // subspace
+        if height != last_height {
+            // Restoring subspace at specified height
+            for (key, _value, _gas) in self.iter_prefix(None) {
+                let value = self
+                    .read_subspace_val_with_height(
+                        &Key::from(key.to_db_key()),
+                        height,
+                        last_height,
+                    )
+                    .expect("Unable to find subspace key");
+                if let Some(value) = value {
+                    let val = HEXLOWER.encode(&value);
+                    writeln!(buf, "\"{key}\" = \"{val}\"")
+                        .expect("Unable to write to buffer");
+                }
+            }
+        } else {
+            // Just dump the current subspace
+            self.dump_it(SUBSPACE_CF, "", &mut buf);
+        }
+
+        // replay protection
+        // Dump of replay protection keys is possible only at the last height or
+        // the previous one
+        if height == last_height {
+            self.dump_it(REPLAY_PROTECTION_CF, "", &mut buf);
+        } else if height == last_height - 1 {
+            self.dump_it(REPLAY_PROTECTION_CF, "all", &mut buf);
+        }
+
+        buf.flush().expect("Unable to write to output file");
+        println!("Done writing to {}", full_path.to_string_lossy());
+    }
+
+    /// Dump data
+    fn dump_it(
+        &self,
+        table_name: &'static str,
+        prefix: &str,
+        buf: &mut impl Write,
+    ) {
+        let iter = PersistentPrefixIterator::new(
+            self,
+            table_name,
+            prefix.to_owned(),
+            // Empty string to prevent prefix stripping
+            String::default(),
+        );
+        for (key, raw_val, _gas) in iter {
+            let val = HEXLOWER.encode(&raw_val);
+            writeln!(buf, "\"{key}\" = \"{val}\"")
+                .expect("Unable to write to buffer");
+        }
+    }
+
+    /// Rollback to previous block. Given the inner working of tendermint
+    /// rollback and of the key structure of Namada, calling rollback more than
+    /// once without restarting the chain results in a single rollback.
+    pub fn rollback(
+        &mut self,
+        tendermint_block_height: BlockHeight,
+    ) -> Result<()> {
+        let last_block = self.read_last_block()?.ok_or(Error::DBError(
+            "Missing last block in storage".to_string(),
+        ))?;
+        tracing::info!(
+            "Namada last block height: {}, Tendermint last block height: {}",
+            last_block.height,
+            tendermint_block_height
+        );
+
+        // If the block height to which tendermint rolled back matches the
+        // Namada height, there's no need to rollback
+        if tendermint_block_height == last_block.height {
+            tracing::info!(
+                "Namada height already matches the rollback Tendermint \
+                 height, no need to rollback."
+            );
+            return Ok(());
+        }
+
+        let mut batch = RedbWriteBatch::default();
+        let previous_height =
+            BlockHeight::from(u64::from(last_block.height) - 1);
+
+        // Revert the non-height-prepended metadata storage keys which get
+        // updated with every block. Because of the way we save these
+        // three keys in storage we can only perform one rollback before
+        // restarting the chain
+        tracing::info!("Reverting non-height-prepended metadata keys");
+        batch.put(STATE_CF, "height", types::encode(&previous_height));
+        for metadata_key in [
+            "next_epoch_min_start_height",
+            "next_epoch_min_start_time",
+            "tx_queue",
+        ] {
+            let previous_key = format!("pred/{}", metadata_key);
+            let previous_value = self
+                .get(STATE_CF, &previous_key)?
+                .ok_or(Error::UnknownKey { key: previous_key })?;
+            batch.put(STATE_CF, metadata_key, previous_value);
+        }
+
+        // Revert conversion state if the epoch had been changed
+        if last_block.pred_epochs.get_epoch(previous_height)
+            != Some(last_block.epoch)
+        {
+            let previous_key = "pred/conversion_state".to_string();
+            let previous_value = self
+                .get(STATE_CF, &previous_key)?
+                .ok_or(Error::UnknownKey { key: previous_key })?;
+            batch.put(STATE_CF, "conversion_state", previous_value);
+        }
+
+        // Delete block results for the last block
+        tracing::info!("Removing last block results");
+        batch.delete(BLOCK_CF, format!("results/{}", last_block.height.raw()));
+
+        // Delete the tx hashes included in the last block
+        tracing::info!("Removing replay protection hashes");
+        let last_prefix = format!("{}/", replay_protection::last_prefix());
+        for (key, _value) in
+            self.read_prefix(REPLAY_PROTECTION_CF, &last_prefix)?
+        {
+            batch.delete(REPLAY_PROTECTION_CF, key);
+        }
+
+        tracing::info!("Restoring previous height subspace diffs");
+        for (key, _value, _gas) in self.iter_prefix(None) {
+            // Restore previous height diff if present, otherwise delete the
+            // subspace key
+            match self.read_subspace_val_with_height(
+                &Key::from(key.to_db_key()),
+                previous_height,
+                last_block.height,
+            )? {
+                Some(previous_value) => {
+                    batch.put(SUBSPACE_CF, key, previous_value)
+                }
+                None => batch.delete(SUBSPACE_CF, key),
+            }
+        }
+
+        // Look for diffs in this block to find what has been deleted
+        for (key, val, _) in
+            iter_diffs_prefix(self, last_block.height, None, true)
+        {
+            let key = Key::parse(key).unwrap();
+            let (_, diff_new_key) =
+                old_and_new_diff_key(&key, last_block.height)?;
+            if self.get(DIFFS_CF, &diff_new_key)?.is_none() {
+                // If there is no new value, it has been deleted in this
+                // block and we have to restore it
+                batch.put(SUBSPACE_CF, key.to_string(), val)
+            }
+        }
+
+        tracing::info!("Deleting keys prepended with the last height");
+        let prefix = format!("{}/", last_block.height.raw());
+        // Delete any height-prepended key in subspace diffs and in the block
+        for table_name in [DIFFS_CF, BLOCK_CF] {
+            for (key, _value) in self.read_prefix(table_name, &prefix)? {
+                batch.delete(table_name, key);
+            }
+        }
+
+        // Write the batch and persist changes to disk
+        tracing::info!("Flushing restored state to disk");
+        self.exec_batch(batch)
+    }
+
+    /// Create a checkpoint of the DB in the given directory, which must not
+    /// exist yet. The checkpoint is a consistent view of the DB that can be
+    /// opened independently of it. Unlike with RocksDB, all the entries are
+    /// copied into the checkpoint.
+    pub fn checkpoint(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        if dir.exists() {
+            return Err(Error::DBError(format!(
+                "The checkpoint directory {} already exists",
+                dir.to_string_lossy()
+            )));
+        }
+        let checkpoint = open(dir, None)?;
+        let read_txn = self.0.begin_read().map_err(db_error)?;
+        let write_txn = checkpoint.0.begin_write().map_err(db_error)?;
+        for name in TABLES {
+            let from = read_txn.open_table(table(name)).map_err(db_error)?;
+            let mut to = write_txn.open_table(table(name)).map_err(db_error)?;
+            for entry in from.iter().map_err(db_error)? {
+                let (key, value) = entry.map_err(db_error)?;
+                to.insert(key.value(), value.value()).map_err(db_error)?;
+            }
+        }
+        write_txn.commit().map_err(db_error)
+    }
+
+    /// Call the given function with every entry needed to resume the chain
+    /// from the last committed block, along with the name of its table. These
+    /// are the same entries as with RocksDB, read from a single read
+    /// transaction.
+    pub fn for_each_snapshot_entry<E>(
+        &self,
+        mut f: impl FnMut(&str, &[u8], &[u8]) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<Error>,
+    {
+        let txn = self.0.begin_read().map_err(db_error)?;
+        let read = |table_name: &str, key: &str| -> Result<Option<Vec<u8>>> {
+            let table = txn.open_table(table(table_name)).map_err(db_error)?;
+            let value = table
+                .get(key)
+                .map_err(db_error)?
+                .map(|value| value.value().to_vec());
+            Ok(value)
+        };
+        let height: BlockHeight = match read(STATE_CF, "height")? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(()),
+        };
+
+        for table_name in [STATE_CF, SUBSPACE_CF, REPLAY_PROTECTION_CF] {
+            for_each_table_entry(&txn, table_name, "", &mut f)?;
+        }
+        // The history before the last height is not part of the snapshot
+        f(
+            STATE_CF,
+            OLDEST_HEIGHT_KEY.as_bytes(),
+            &types::encode(&height),
+        )?;
+        let prefix = format!("{}/", height.raw());
+        for_each_table_entry(&txn, DIFFS_CF, &prefix, &mut f)?;
+        for_each_table_entry(&txn, BLOCK_CF, &prefix, &mut f)?;
+
+        let mut keys = vec![format!("results/{}", height.raw())];
+        let epoch_key = format!("{}/epoch", height.raw());
+        if let Some(bytes) = read(BLOCK_CF, &epoch_key)? {
+            let epoch: Epoch =
+                types::decode(bytes).map_err(Error::CodingError)?;
+            for st in StoreType::iter_subtrees() {
+                let key_prefix = subtree_key_prefix(st, epoch);
+                keys.push(
+                    key_prefix
+                        .clone()
+                        .with_segment("root".to_owned())
+                        .to_string(),
+                );
+                keys.push(
+                    key_prefix.with_segment("store".to_owned()).to_string(),
+                );
+            }
+        }
+        for key in keys {
+            if let Some(value) = read(BLOCK_CF, &key)? {
+                f(BLOCK_CF, key.as_bytes(), &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the entries of a state sync snapshot chunk, given with the name
+    /// of their table.
+    pub fn write_snapshot_entries<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'a str, &'a [u8], &'a [u8])>,
+    ) -> Result<()> {
+        let mut batch = RedbWriteBatch::default();
+        for (table_name, key, value) in entries {
+            let table_name = TABLES
+                .into_iter()
+                .find(|name| *name == table_name)
+                .ok_or_else(|| {
+                    Error::DBError(format!("No {table_name} table"))
+                })?;
+            let key = String::from_utf8(key.to_vec()).map_err(|e| {
+                Error::DBError(format!("Invalid key in {table_name}: {e}"))
+            })?;
+            batch.put(table_name, key, value);
+        }
+        self.exec_batch(batch)
+    }
+
+    /// Read the oldest height whose diffs and block data haven't been pruned,
+    /// if any height has been pruned.
+    fn read_oldest_height(&self) -> Result<Option<BlockHeight>> {
+        self.get(STATE_CF, OLDEST_HEIGHT_KEY)?
+            .map(|bytes| types::decode(bytes).map_err(Error::CodingError))
+            .transpose()
+    }
+
+    /// Delete all the entries of the DB, e.g. to discard the state restored
+    /// from an invalid state sync snapshot.
+    pub fn clear(&mut self) -> Result<()> {
+        let txn = self.0.begin_write().map_err(db_error)?;
+        for name in TABLES {
+            txn.delete_table(table(name)).map_err(db_error)?;
+            txn.open_table(table(name)).map_err(db_error)?;
+        }
+        txn.commit().map_err(db_error)
+    }
+}
+
+impl DB for RedbDB {
+    /// The size of the cache in bytes
+    type Cache = usize;
+    type WriteBatch = RedbWriteBatch;
+
+    fn open(
+        db_path: impl AsRef<std::path::Path>,
+        cache: Option<&Self::Cache>,
+    ) -> Self {
+        open(db_path, cache.copied()).expect("cannot open the DB")
+    }
+
+    fn flush(&self, _wait: bool) -> Result<()> {
+        // The write transactions are durable once committed
+        Ok(())
+    }
+
+    fn read_last_block(&self) -> Result<Option<BlockStateRead>> {
+        // Block height
+        let height: BlockHeight = match self.get(STATE_CF, "height")? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+
+        // Block results
+        let results_path = format!("results/{}", height.raw());
+        let results: BlockResults = match self.get(BLOCK_CF, &results_path)? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+
+        // Epoch start height and time
+        let next_epoch_min_start_height: BlockHeight = match self
+            .get(STATE_CF, "next_epoch_min_start_height")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!(
+                    "Couldn't load next epoch start height from the DB"
+                );
+                return Ok(None);
+            }
+        };
+        let next_epoch_min_start_time: DateTimeUtc = match self
+            .get(STATE_CF, "next_epoch_min_start_time")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!(
+                    "Couldn't load next epoch start time from the DB"
+                );
+                return Ok(None);
+            }
+        };
+        let update_epoch_blocks_delay: Option<u32> = match self
+            .get(STATE_CF, "update_epoch_blocks_delay")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!(
+                    "Couldn't load epoch update block delay from the DB"
+                );
+                return Ok(None);
+            }
+        };
+        // The layout isn't stored by the versions before the subtrees were
+        // split from the account subtree
+        let merkle_tree_layout: MerkleTreeLayout =
+            match self.get(STATE_CF, "merkle_tree_layout")? {
+                Some(bytes) => {
+                    types::decode(bytes).map_err(Error::CodingError)?
+                }
+                None => MerkleTreeLayout::LEGACY,
+            };
+        let conversion_state: ConversionState = match self
+            .get(STATE_CF, "conversion_state")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!("Couldn't load conversion state from the DB");
+                return Ok(None);
+            }
+        };
+        let tx_queue: TxQueue = match self.get(STATE_CF, "tx_queue")? {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!("Couldn't load tx queue from the DB");
+                return Ok(None);
+            }
+        };
+        let ethereum_height: Option<ethereum_structs::BlockHeight> = match self
+            .get(STATE_CF, "ethereum_height")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!("Couldn't load ethereum height from the DB");
+                return Ok(None);
+            }
+        };
+        let eth_events_queue: EthEventsQueue = match self
+            .get(STATE_CF, "eth_events_queue")?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => {
+                tracing::error!(
+                    "Couldn't load the eth events queue from the DB"
+                );
+                return Ok(None);
+            }
+        };
+
+        // Load data at the height
+        let prefix = format!("{}/", height.raw());
+        let mut merkle_tree_stores = MerkleTreeStoresRead::default();
+        let mut hash = None;
+        let mut time = None;
+        let mut epoch: Option<Epoch> = None;
+        let mut pred_epochs = None;
+        let mut address_gen = None;
+        for (path, bytes) in self.read_prefix(BLOCK_CF, &prefix)? {
+            let segments: Vec<&str> =
+                path.split(KEY_SEGMENT_SEPARATOR).collect();
+            match segments.get(1) {
+                Some(prefix) => match *prefix {
+                    // Restore the base tree of Merkle tree
+                    "tree" => match segments.get(2) {
+                        Some(s) => {
+                            let st = StoreType::from_str(s)?;
+                            match segments.get(3) {
+                                Some(&"root") => merkle_tree_stores.set_root(
+                                    &st,
+                                    types::decode(bytes)
+                                        .map_err(Error::CodingError)?,
+                                ),
+                                Some(&"store") => merkle_tree_stores
+                                    .set_store(st.decode_store(bytes)?),
+                                _ => unknown_key_error(&path)?,
+                            }
+                        }
+                        None => unknown_key_error(&path)?,
+                    },
+                    "header" => {
+                        // the block header doesn't have to be restored
+                    }
+                    "hash" => {
+                        hash = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "time" => {
+                        time = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "epoch" => {
+                        epoch = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "pred_epochs" => {
+                        pred_epochs = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        )
+                    }
+                    "address_gen" => {
+                        address_gen = Some(
+                            types::decode(bytes).map_err(Error::CodingError)?,
+                        );
+                    }
+                    _ => unknown_key_error(&path)?,
+                },
+                None => unknown_key_error(&path)?,
+            }
+        }
+        // Restore subtrees of Merkle tree
+        if let Some(epoch) = epoch {
+            for st in StoreType::iter_subtrees() {
+                let key_prefix = subtree_key_prefix(st, epoch);
+                let root_key =
+                    key_prefix.clone().with_segment("root".to_owned());
+                if let Some(bytes) =
+                    self.get(BLOCK_CF, &root_key.to_string())?
+                {
+                    merkle_tree_stores.set_root(
+                        st,
+                        types::decode(bytes).map_err(Error::CodingError)?,
+                    );
+                }
+                let store_key = key_prefix.with_segment("store".to_owned());
+                if let Some(bytes) =
+                    self.get(BLOCK_CF, &store_key.to_string())?
+                {
+                    merkle_tree_stores.set_store(st.decode_store(bytes)?);
+                }
+            }
+        }
+        match (hash, time, epoch, pred_epochs, address_gen) {
+            (
+                Some(hash),
+                Some(time),
+                Some(epoch),
+                Some(pred_epochs),
+                Some(address_gen),
+            ) => Ok(Some(BlockStateRead {
+                merkle_tree_stores,
+                merkle_tree_layout,
+                hash,
+                height,
+                time,
+                epoch,
+                pred_epochs,
+                results,
+                conversion_state,
+                next_epoch_min_start_height,
+                next_epoch_min_start_time,
+                update_epoch_blocks_delay,
+                address_gen,
+                tx_queue,
+                ethereum_height,
+                eth_events_queue,
+            })),
+            _ => Err(Error::Temporary {
+                error: "Essential data couldn't be read from the DB"
+                    .to_string(),
+            }),
+        }
+    }
+
+    fn add_block_to_batch(
+        &self,
+        state: BlockStateWrite,
+        batch: &mut Self::WriteBatch,
+        is_full_commit: bool,
+    ) -> Result<()> {
+        let BlockStateWrite {
+            merkle_tree_stores,
+            merkle_tree_layout,
+            header,
+            hash,
+            height,
+            time,
+            epoch,
+            pred_epochs,
+            next_epoch_min_start_height,
+            next_epoch_min_start_time,
+            update_epoch_blocks_delay,
+            address_gen,
+            results,
+            conversion_state,
+            tx_queue,
+            ethereum_height,
+            eth_events_queue,
+        }: BlockStateWrite = state;
+
+        // Write the predecessor value of a state key for rollback, if any
+        let mut write_pred = |key: &str| -> Result<()> {
+            if let Some(current_value) = self.get(STATE_CF, key)? {
+                batch.put(STATE_CF, format!("pred/{key}"), current_value);
+            }
+            Ok(())
+        };
+
+        // Epoch start height and time
+        write_pred("next_epoch_min_start_height")?;
+        write_pred("next_epoch_min_start_time")?;
+        write_pred("update_epoch_blocks_delay")?;
+        // Save the conversion state when the epoch is updated
+        if is_full_commit {
+            write_pred("conversion_state")?;
+        }
+        write_pred("tx_queue")?;
+
+        batch.put(
+            STATE_CF,
+            "next_epoch_min_start_height",
+            types::encode(&next_epoch_min_start_height),
+        );
+        batch.put(
+            STATE_CF,
+            "next_epoch_min_start_time",
+            types::encode(&next_epoch_min_start_time),
+        );
+        batch.put(
+            STATE_CF,
+            "update_epoch_blocks_delay",
+            types::encode(&update_epoch_blocks_delay),
+        );
+        batch.put(
+            STATE_CF,
+            "merkle_tree_layout",
+            types::encode(&merkle_tree_layout),
+        );
+        if is_full_commit {
+            batch.put(
+                STATE_CF,
+                "conversion_state",
+                types::encode(conversion_state),
+            );
+        }
+        batch.put(STATE_CF, "tx_queue", types::encode(&tx_queue));
+        batch.put(STATE_CF, "ethereum_height", types::encode(&ethereum_height));
+        batch.put(
+            STATE_CF,
+            "eth_events_queue",
+            types::encode(&eth_events_queue),
+        );
+
+        let prefix_key = Key::from(height.to_db_key());
+        // Merkle tree
+        for st in StoreType::iter() {
+            if *st == StoreType::Base || is_full_commit {
+                let key_prefix = if *st == StoreType::Base {
+                    base_tree_key_prefix(height)
+                } else {
+                    subtree_key_prefix(st, epoch)
+                };
+                let root_key =
+                    key_prefix.clone().with_segment("root".to_owned());
+                batch.put(
+                    BLOCK_CF,
+                    root_key.to_string(),
+                    types::encode(merkle_tree_stores.root(st)),
+                );
+                let store_key = key_prefix.with_segment("store".to_owned());
+                batch.put(
+                    BLOCK_CF,
+                    store_key.to_string(),
+                    merkle_tree_stores.store(st).encode(),
+                );
+            }
+        }
+        let block_key = |segment: &str| -> Result<String> {
+            prefix_key
+                .push(&segment.to_owned())
+                .map(|key| key.to_string())
+                .map_err(Error::KeyError)
+        };
+        // Block header
+        if let Some(h) = header {
+            batch.put(BLOCK_CF, block_key("header")?, h.serialize_to_vec());
+        }
+        // Block hash
+        batch.put(BLOCK_CF, block_key("hash")?, types::encode(&hash));
+        // Block time
+        batch.put(BLOCK_CF, block_key("time")?, types::encode(&time));
+        // Block epoch
+        batch.put(BLOCK_CF, block_key("epoch")?, types::encode(&epoch));
+        // Block results
+        let results_path = format!("results/{}", height.raw());
+        batch.put(BLOCK_CF, results_path, types::encode(&results));
+        // Predecessor block epochs
+        batch.put(
+            BLOCK_CF,
+            block_key("pred_epochs")?,
+            types::encode(&pred_epochs),
+        );
+        // Address gen
+        batch.put(
+            BLOCK_CF,
+            block_key("address_gen")?,
+            types::encode(&address_gen),
+        );
+
+        // Block height
+        batch.put(STATE_CF, "height", types::encode(&height));
+
+        Ok(())
+    }
+
+    fn read_block_header(&self, height: BlockHeight) -> Result<Option<Header>> {
+        let prefix_key = Key::from(height.to_db_key());
+        let key = prefix_key
+            .push(&"header".to_owned())
+            .map_err(Error::KeyError)?;
+        match self.get(BLOCK_CF, &key.to_string())? {
+            Some(v) => Ok(Some(
+                Header::try_from_slice(&v[..])
+                    .map_err(Error::BorshCodingError)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn read_merkle_tree_stores(
+        &self,
+        epoch: Epoch,
+        base_height: BlockHeight,
+        store_type: Option<StoreType>,
+    ) -> Result<Option<MerkleTreeStoresRead>> {
+        let mut merkle_tree_stores = MerkleTreeStoresRead::default();
+        let store_types = store_type
+            .as_ref()
+            .map(|st| Either::Left(std::iter::once(st)))
+            .unwrap_or_else(|| Either::Right(StoreType::iter()));
+        for st in store_types {
+            let key_prefix = if *st == StoreType::Base {
+                base_tree_key_prefix(base_height)
+            } else {
+                subtree_key_prefix(st, epoch)
+            };
+            let root_key = key_prefix.clone().with_segment("root".to_owned());
+            match self.get(BLOCK_CF, &root_key.to_string())? {
+                Some(b) => {
+                    let root = types::decode(b).map_err(Error::CodingError)?;
+                    merkle_tree_stores.set_root(st, root);
+                }
+                // The subtrees split from the account subtree aren't stored
+                // in the epochs before the split
+                None if st.is_split_from_account() => continue,
+                None => return Ok(None),
+            }
+
+            let store_key = key_prefix.with_segment("store".to_owned());
+            match self.get(BLOCK_CF, &store_key.to_string())? {
+                Some(b) => {
+                    merkle_tree_stores.set_store(st.decode_store(b)?);
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(merkle_tree_stores))
+    }
+
+    fn has_replay_protection_entry(
+        &self,
+        hash: &namada::types::hash::Hash,
+    ) -> Result<bool> {
+        for key in [
+            replay_protection::last_key(hash),
+            replay_protection::all_key(hash),
+        ] {
+            if self.get(REPLAY_PROTECTION_CF, &key.to_string())?.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn read_diffs_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        is_old: bool,
+    ) -> Result<Option<Vec<u8>>> {
+        let (old_val_key, new_val_key) = old_and_new_diff_key(key, height)?;
+        let key = if is_old { old_val_key } else { new_val_key };
+        self.get(DIFFS_CF, &key)
+    }
+
+    fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        self.get(SUBSPACE_CF, &key.to_string())
+    }
+
+    fn read_subspace_val_with_height(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>> {
+        if let Some(oldest_height) = self.read_oldest_height()? {
+            if height < oldest_height {
+                return Err(Error::Pruned {
+                    height,
+                    oldest_height,
+                });
+            }
+        }
+
+        // Check if the value changed at this height
+        let (old_val_key, new_val_key) = old_and_new_diff_key(key, height)?;
+
+        // If it has a "new" val, it was written at this height
+        if let Some(new_val) = self.get(DIFFS_CF, &new_val_key)? {
+            return Ok(Some(new_val));
+        }
+        // If it has an "old" val, it was deleted at this height
+        if self.get(DIFFS_CF, &old_val_key)?.is_some() {
+            return Ok(None);
+        }
+
+        // If the value didn't change at the given height, we try to look for it
+        // at successor heights, up to the `last_height`
+        let mut raw_height = height.0 + 1;
+        loop {
+            // Try to find the next diff on this key
+            let (old_val_key, new_val_key) =
+                old_and_new_diff_key(key, BlockHeight(raw_height))?;
+            // If it has an "old" val, it's the one we're looking for
+            if let Some(bytes) = self.get(DIFFS_CF, &old_val_key)? {
+                return Ok(Some(bytes));
+            }
+            // Check if the value was created at this height instead, which
+            // would mean that it wasn't present before
+            if self.get(DIFFS_CF, &new_val_key)?.is_some() {
+                return Ok(None);
+            }
+            if raw_height >= last_height.0 {
+                // Read from latest height
+                return self.read_subspace_val(key);
+            } else {
+                raw_height += 1
+            }
+        }
+    }
+
+    fn write_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        let mut batch = RedbWriteBatch::default();
+        let size_diff = self.batch_write_subspace_val(
+            &mut batch,
+            height,
+            key,
+            value,
+            persist_diffs,
+        )?;
+        self.exec_batch(batch)?;
+        Ok(size_diff)
+    }
+
+    fn delete_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        let mut batch = RedbWriteBatch::default();
+        let prev_len = self.batch_delete_subspace_val(
+            &mut batch,
+            height,
+            key,
+            persist_diffs,
+        )?;
+        self.exec_batch(batch)?;
+        Ok(prev_len)
+    }
+
+    fn batch() -> Self::WriteBatch {
+        RedbWriteBatch::default()
+    }
+
+    fn exec_batch(&mut self, batch: Self::WriteBatch) -> Result<()> {
+        let txn = self.0.begin_write().map_err(db_error)?;
+        {
+            let mut tables = TABLES
+                .into_iter()
+                .map(|name| txn.open_table(table(name)))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(db_error)?;
+            for op in batch.0 {
+                match op {
+                    BatchOp::Put { table, key, value } => {
+                        tables[table_index(table)]
+                            .insert(key.as_str(), value.as_slice())
+                            .map_err(db_error)?;
+                    }
+                    BatchOp::Delete { table, key } => {
+                        tables[table_index(table)]
+                            .remove(key.as_str())
+                            .map_err(db_error)?;
+                    }
+                }
+            }
+        }
+        txn.commit().map_err(db_error)
+    }
+
+    fn batch_write_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        let value = value.as_ref();
+        let size_diff = match self.read_subspace_val(key)? {
+            Some(old_value) => {
+                let size_diff = value.len() as i64 - old_value.len() as i64;
+                // Persist the previous value
+                self.batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    Some(&old_value),
+                    Some(value),
+                    persist_diffs,
+                )?;
+                size_diff
+            }
+            None => {
+                self.batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    None,
+                    Some(value),
+                    persist_diffs,
+                )?;
+                value.len() as i64
+            }
+        };
+
+        // Write the new key-val
+        batch.put(SUBSPACE_CF, key.to_string(), value);
+
+        Ok(size_diff)
+    }
+
+    fn batch_delete_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> Result<i64> {
+        // Check the length of previous value, if any
+        let prev_len = match self.read_subspace_val(key)? {
+            Some(prev_value) => {
+                let prev_len = prev_value.len() as i64;
+                // Persist the previous value
+                self.batch_write_subspace_diff(
+                    batch,
+                    height,
+                    key,
+                    Some(&prev_value),
+                    None,
+                    persist_diffs,
+                )?;
+                prev_len
+            }
+            None => 0,
+        };
+
+        // Delete the key-val
+        batch.delete(SUBSPACE_CF, key.to_string());
+
+        Ok(prev_len)
+    }
+
+    fn prune_merkle_tree_store(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        store_type: &StoreType,
+        epoch: Epoch,
+    ) -> Result<()> {
+        let key_prefix = subtree_key_prefix(store_type, epoch);
+        let root_key = key_prefix.clone().with_segment("root".to_owned());
+        batch.delete(BLOCK_CF, root_key.to_string());
+        let store_key = key_prefix.with_segment("store".to_owned());
+        batch.delete(BLOCK_CF, store_key.to_string());
+        Ok(())
+    }
+
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        max_heights: u64,
+    ) -> Result<Option<BlockHeight>> {
+        let from = self.read_oldest_height()?.unwrap_or_default();
+        let to = BlockHeight(height.0.min(from.0.saturating_add(max_heights)));
+        if to <= from {
+            return Ok(None);
+        }
+        let (from_key, to_key) = (from.raw(), to.raw());
+        let range = (
+            Bound::Included(from_key.as_str()),
+            Bound::Excluded(to_key.as_str()),
+        );
+
+        // The diffs keys are prefixed with their height
+        for (key, _value) in self.read_range(DIFFS_CF, range, "", None)? {
+            batch.delete(DIFFS_CF, key);
+        }
+
+        let (results_from, results_to) =
+            (format!("results/{from_key}"), format!("results/{to_key}"));
+        for (key, _value) in self.read_range(
+            BLOCK_CF,
+            (
+                Bound::Included(results_from.as_str()),
+                Bound::Excluded(results_to.as_str()),
+            ),
+            "results/",
+            None,
+        )? {
+            batch.delete(BLOCK_CF, key);
+        }
+        let base_tree = StoreType::Base.to_string();
+        for (key, _value) in self.read_range(BLOCK_CF, range, "", None)? {
+            let mut segments = key.split(KEY_SEGMENT_SEPARATOR).skip(1);
+            // The merkle subtree stores are prefixed with an epoch instead of
+            // a height
+            let is_subtree_store = segments.next() == Some("tree")
+                && segments.next() != Some(base_tree.as_str());
+            if !is_subtree_store {
+                batch.delete(BLOCK_CF, key);
+            }
+        }
+
+        batch.put(STATE_CF, OLDEST_HEIGHT_KEY, types::encode(&to));
+        Ok(Some(to))
+    }
+
+    fn read_bridge_pool_signed_nonce(
+        &self,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> Result<Option<ethereum_events::Uint>> {
+        let nonce_key = bridge_pool::get_signed_root_key();
+        let bytes = if height == BlockHeight(0) || height >= last_height {
+            self.read_subspace_val(&nonce_key)?
+        } else {
+            self.read_subspace_val_with_height(&nonce_key, height, last_height)?
+        };
+        match bytes {
+            Some(bytes) => {
+                let bp_root_proof = BridgePoolRootProof::try_from_slice(&bytes)
+                    .map_err(Error::BorshCodingError)?;
+                Ok(Some(bp_root_proof.data.1))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> Result<()> {
+        batch.put(REPLAY_PROTECTION_CF, key.to_string(), vec![]);
+        Ok(())
+    }
+
+    fn delete_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> Result<()> {
+        batch.delete(REPLAY_PROTECTION_CF, key.to_string());
+        Ok(())
+    }
+}
+
+impl<'iter> DBIter<'iter> for RedbDB {
+    type PrefixIter = PersistentPrefixIterator<'iter>;
+
+    fn iter_prefix(
+        &'iter self,
+        prefix: Option<&Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        iter_prefix(self, SUBSPACE_CF, None, prefix)
+    }
+
+    fn iter_prefix_from(
+        &'iter self,
+        prefix: Option<&Key>,
+        start: &Key,
+    ) -> PersistentPrefixIterator<'iter> {
+        let mut iter = iter_prefix(self, SUBSPACE_CF, None, prefix);
+        iter.start = start.to_string();
+        iter
+    }
+
+    fn iter_results(&'iter self) -> PersistentPrefixIterator<'iter> {
+        let prefix = "results/".to_owned();
+        PersistentPrefixIterator::new(self, BLOCK_CF, prefix.clone(), prefix)
+    }
+
+    fn iter_old_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        iter_diffs_prefix(self, height, prefix, true)
+    }
+
+    fn iter_new_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        iter_diffs_prefix(self, height, prefix, false)
+    }
+
+    fn iter_replay_protection(&'iter self) -> Self::PrefixIter {
+        let stripped_prefix = Some(replay_protection::last_prefix());
+        iter_prefix(self, REPLAY_PROTECTION_CF, stripped_prefix.as_ref(), None)
+    }
+}
+
+fn iter_diffs_prefix<'a>(
+    db: &'a RedbDB,
+    height: BlockHeight,
+    prefix: Option<&Key>,
+    is_old: bool,
+) -> PersistentPrefixIterator<'a> {
+    let kind = if is_old {
+        OLD_DIFF_PREFIX
+    } else {
+        NEW_DIFF_PREFIX
+    };
+    let stripped_prefix = Some(
+        Key::from(height.to_db_key())
+            .push(&kind.to_string())
+            .unwrap(),
+    );
+    // get keys without the `stripped_prefix`
+    iter_prefix(db, DIFFS_CF, stripped_prefix.as_ref(), prefix)
+}
+
+/// Create an iterator over key-vals in the given table matching the given
+/// prefix(es). If any, the `stripped_prefix` is matched first and will be
+/// removed from the matched keys. If any, the second `prefix` is matched
+/// against the stripped keys and remains in the matched keys.
+fn iter_prefix<'a>(
+    db: &'a RedbDB,
+    table_name: &'static str,
+    stripped_prefix: Option<&Key>,
+    prefix: Option<&Key>,
+) -> PersistentPrefixIterator<'a> {
+    let stripped_prefix = match stripped_prefix {
+        Some(p) if !p.is_empty() => format!("{p}/"),
+        _ => "".to_owned(),
+    };
+    let prefix = match prefix {
+        Some(p) if !p.is_empty() => {
+            format!("{stripped_prefix}{p}/")
+        }
+        _ => stripped_prefix.clone(),
+    };
+    PersistentPrefixIterator::new(db, table_name, prefix, stripped_prefix)
+}
+
+/// An iterator over the entries of a table whose key starts with a prefix.
+/// The entries are read in pages, so that no read transaction is kept open
+/// while iterating.
+#[derive(Debug)]
+pub struct PersistentPrefixIterator<'a> {
+    db: &'a RedbDB,
+    table_name: &'static str,
+    prefix: String,
+    stripped_prefix: String,
+    page: std::vec::IntoIter<(String, Vec<u8>)>,
+    /// The key from which the first page starts
+    start: String,
+    /// The last key read, from which the next page starts
+    last_key: Option<String>,
+    is_last_page: bool,
+}
+
+impl<'a> PersistentPrefixIterator<'a> {
+    fn new(
+        db: &'a RedbDB,
+        table_name: &'static str,
+        prefix: String,
+        stripped_prefix: String,
+    ) -> Self {
+        Self {
+            db,
+            table_name,
+            start: prefix.clone(),
+            prefix,
+            stripped_prefix,
+            page: vec![].into_iter(),
+            last_key: None,
+            is_last_page: false,
+        }
+    }
+
+    /// Read the next page of entries
+    fn read_page(&mut self) {
+        let start = match self.last_key.as_ref() {
+            Some(last_key) => Bound::Excluded(last_key.as_str()),
+            None => Bound::Included(self.start.as_str()),
+        };
+        let page = self
+            .db
+            .read_range(
+                self.table_name,
+                (start, Bound::Unbounded),
+                &self.prefix,
+                Some(ITER_PAGE_SIZE),
+            )
+            .expect("Prefix iterator shouldn't fail");
+        self.is_last_page = page.len() < ITER_PAGE_SIZE;
+        if let Some((key, _)) = page.last() {
+            self.last_key = Some(key.clone());
+        }
+        self.page = page.into_iter();
+    }
+}
+
+impl<'a> Iterator for PersistentPrefixIterator<'a> {
+    type Item = (String, Vec<u8>, u64);
+
+    /// Returns the next pair and the gas cost
+    fn next(&mut self) -> Option<(String, Vec<u8>, u64)> {
+        loop {
+            match self.page.next() {
+                Some((key, val)) => {
+                    if let Some(k) = key.strip_prefix(&self.stripped_prefix) {
+                        let gas = k.len() + val.len();
+                        return Some((k.to_owned(), val, gas as _));
+                    } else {
+                        tracing::warn!(
+                            "Unmatched prefix \"{}\" in iterator's key \
+                             \"{key}\"",
+                            self.stripped_prefix
+                        );
+                    }
+                }
+                None if self.is_last_page => return None,
+                None => self.read_page(),
+            }
+        }
+    }
+}
+
+impl DBWriteBatch for RedbWriteBatch {}
+
+/// Call the given function with every entry of a table whose key starts
+/// with the given prefix.
+fn for_each_table_entry<E>(
+    txn: &ReadTransaction,
+    table_name: &str,
+    prefix: &str,
+    f: &mut impl FnMut(&str, &[u8], &[u8]) -> std::result::Result<(), E>,
+) -> std::result::Result<(), E>
+where
+    E: From<Error>,
+{
+    let table = txn.open_table(table(table_name)).map_err(db_error)?;
+    for entry in table.range::<&str>(prefix..).map_err(db_error)? {
+        let (key, value) = entry.map_err(db_error)?;
+        let key = key.value();
+        if !key.starts_with(prefix) {
+            break;
+        }
+        f(table_name, key.as_bytes(), value.value())?;
+    }
+    Ok(())
+}
+
+/// The definition of a table with the given name
+fn table(name: &str) -> TableDefinition<'_, &'static str, &'static [u8]> {
+    TableDefinition::new(name)
+}
+
+/// The index of a table in [`TABLES`]
+fn table_index(name: &str) -> usize {
+    TABLES
+        .iter()
+        .position(|table| *table == name)
+        .expect("The batch writes only to the known tables")
+}
+
+fn db_error(e: impl std::fmt::Display) -> Error {
+    Error::DBError(e.to_string())
+}
+
+fn old_and_new_diff_key(
+    key: &Key,
+    height: BlockHeight,
+) -> Result<(String, String)> {
+    let key_prefix = Key::from(height.to_db_key());
+    let old = key_prefix
+        .push(&OLD_DIFF_PREFIX.to_owned())
+        .map_err(Error::KeyError)?
+        .join(key);
+    let new = key_prefix
+        .push(&NEW_DIFF_PREFIX.to_owned())
+        .map_err(Error::KeyError)?
+        .join(key);
+    Ok((old.to_string(), new.to_string()))
+}
+
+fn unknown_key_error(key: &str) -> Result<()> {
+    Err(Error::UnknownKey {
+        key: key.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use namada::state::{MerkleTree, Sha256Hasher};
+    use namada::types::address::{
+        gen_established_address, EstablishedAddressGen,
+    };
+    use namada::types::storage::{BlockHash, Epoch, Epochs};
+    use tempfile::tempdir;
+    use test_log::test;
+
+    use super::*;
+
+    /// Test that a block written can be loaded back from DB.
+    #[test]
+    fn test_load_state() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let mut batch = RedbDB::batch();
+        let last_height = BlockHeight::default();
+        db.batch_write_subspace_val(
+            &mut batch,
+            last_height,
+            &Key::parse("test").unwrap(),
+            vec![1_u8, 1, 1, 1],
+            true,
+        )
+        .unwrap();
+
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            BlockHeight::default(),
+            Epoch::default(),
+            Epochs::default(),
+            &ConversionState::default(),
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        let _state = db
+            .read_last_block()
+            .expect("Should be able to read last block")
+            .expect("Block should have been written");
+    }
+
+    #[test]
+    fn test_read() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let key = Key::parse("test").unwrap();
+        let last_height = BlockHeight(100);
+        db.write_subspace_val(last_height, &key, vec![1_u8, 1, 1, 0], true)
+            .unwrap();
+        let last_height = BlockHeight(111);
+        db.write_subspace_val(last_height, &key, vec![2_u8, 2, 2, 0], true)
+            .unwrap();
+
+        let prev_value = db
+            .read_subspace_val_with_height(&key, BlockHeight(100), last_height)
+            .expect("read should succeed");
+        assert_eq!(prev_value, Some(vec![1_u8, 1, 1, 0]));
+        let updated_value = db
+            .read_subspace_val_with_height(&key, BlockHeight(111), last_height)
+            .expect("read should succeed");
+        assert_eq!(updated_value, Some(vec![2_u8, 2, 2, 0]));
+        let latest_value =
+            db.read_subspace_val(&key).expect("read should succeed");
+        assert_eq!(latest_value, Some(vec![2_u8, 2, 2, 0]));
+
+        let last_height = BlockHeight(222);
+        db.delete_subspace_val(last_height, &key, true).unwrap();
+
+        let deleted_value = db
+            .read_subspace_val_with_height(&key, BlockHeight(222), last_height)
+            .expect("read should succeed");
+        assert_eq!(deleted_value, None);
+        let latest_value =
+            db.read_subspace_val(&key).expect("read should succeed");
+        assert_eq!(latest_value, None);
+    }
+
+    /// Test that the prefix iterators match whole key segments and read
+    /// across several pages.
+    #[test]
+    fn test_prefix_iter() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let prefix_0 = Key::parse("0").unwrap();
+        let prefix_01 = Key::parse("01").unwrap();
+        let keys_0: Vec<Key> = (0..ITER_PAGE_SIZE * 2 + 1)
+            .map(|i| prefix_0.push(&format!("{i:05}")).unwrap())
+            .collect();
+        let key_01_a = prefix_01.push(&"a".to_string()).unwrap();
+
+        let mut batch = RedbDB::batch();
+        let height = BlockHeight(1);
+        for key in keys_0.iter().chain([&key_01_a]) {
+            db.batch_write_subspace_val(&mut batch, height, key, [0_u8], true)
+                .unwrap();
+        }
+        db.exec_batch(batch).unwrap();
+
+        // Prefix "0" shouldn't match prefix "01"
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&prefix_0))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(keys_0.clone(), itered_keys);
+
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(Some(&prefix_01))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(vec![key_01_a.clone()], itered_keys);
+
+        let itered_keys: Vec<Key> = db
+            .iter_prefix(None)
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(
+            keys_0.into_iter().chain([key_01_a]),
+            itered_keys,
+        );
+    }
+
+    #[test]
+    fn test_rollback() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        // A key that's gonna be added on a second block
+        let add_key = Key::parse("add").unwrap();
+        // A key that's gonna be deleted on a second block
+        let delete_key = Key::parse("delete").unwrap();
+        // A key that's gonna be overwritten on a second block
+        let overwrite_key = Key::parse("overwrite").unwrap();
+
+        // Write first block
+        let mut batch = RedbDB::batch();
+        let height_0 = BlockHeight(100);
+        let mut pred_epochs = Epochs::default();
+        pred_epochs.new_epoch(height_0);
+        let mut conversion_state_0 = ConversionState::default();
+        conversion_state_0
+            .tokens
+            .insert("dummy1".to_string(), gen_established_address("test"));
+        let to_delete_val = vec![1_u8, 1, 0, 0];
+        let to_overwrite_val = vec![1_u8, 1, 1, 0];
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_0,
+            &delete_key,
+            &to_delete_val,
+            true,
+        )
+        .unwrap();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_0,
+            &overwrite_key,
+            &to_overwrite_val,
+            true,
+        )
+        .unwrap();
+
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_0,
+            Epoch(1),
+            pred_epochs.clone(),
+            &conversion_state_0,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        // Write second block
+        let mut batch = RedbDB::batch();
+        let height_1 = BlockHeight(101);
+        pred_epochs.new_epoch(height_1);
+        let mut conversion_state_1 = ConversionState::default();
+        conversion_state_1
+            .tokens
+            .insert("dummy2".to_string(), gen_established_address("test"));
+        let add_val = vec![1_u8, 0, 0, 0];
+        let overwrite_val = vec![1_u8, 1, 1, 1];
+        db.batch_write_subspace_val(
+            &mut batch, height_1, &add_key, &add_val, true,
+        )
+        .unwrap();
+        db.batch_write_subspace_val(
+            &mut batch,
+            height_1,
+            &overwrite_key,
+            &overwrite_val,
+            true,
+        )
+        .unwrap();
+        db.batch_delete_subspace_val(&mut batch, height_1, &delete_key, true)
+            .unwrap();
+
+        add_block_to_batch(
+            &db,
+            &mut batch,
+            height_1,
+            Epoch(2),
+            pred_epochs,
+            &conversion_state_1,
+        )
+        .unwrap();
+        db.exec_batch(batch).unwrap();
+
+        // Rollback to the first block height
+        db.rollback(height_0).unwrap();
+
+        // Check that the values are back to the state at the first block
+        let added = db.read_subspace_val(&add_key).unwrap();
+        assert_eq!(added, None);
+        let overwritten = db.read_subspace_val(&overwrite_key).unwrap();
+        assert_eq!(overwritten, Some(to_overwrite_val));
+        let deleted = db.read_subspace_val(&delete_key).unwrap();
+        assert_eq!(deleted, Some(to_delete_val));
+        // Check the conversion state
+        let conversion_state =
+            db.get(STATE_CF, "conversion_state").unwrap().unwrap();
+        assert_eq!(conversion_state, types::encode(&conversion_state_0));
+    }
+
+    #[test]
+    fn test_diffs() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let key_with_diffs = Key::parse("with_diffs").unwrap();
+        let key_without_diffs = Key::parse("without_diffs").unwrap();
+
+        let initial_val = vec![1_u8, 1, 0, 0];
+        let overwrite_val = vec![1_u8, 1, 1, 0];
+        let height_0 = BlockHeight::first();
+        let height_1 = height_0 + 10;
+
+        for (height, val) in
+            [(height_0, &initial_val), (height_1, &overwrite_val)]
+        {
+            let mut batch = RedbDB::batch();
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                &key_with_diffs,
+                val,
+                true,
+            )
+            .unwrap();
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                &key_without_diffs,
+                val,
+                false,
+            )
+            .unwrap();
+            db.exec_batch(batch).unwrap();
+        }
+
+        let has_diff = |key: &Key, height: BlockHeight| {
+            let (old, new) = old_and_new_diff_key(key, height).unwrap();
+            (
+                db.get(DIFFS_CF, &old).unwrap().is_some(),
+                db.get(DIFFS_CF, &new).unwrap().is_some(),
+            )
+        };
+        // The diffs of `key_with_diffs` are kept at every height
+        assert_eq!(has_diff(&key_with_diffs, height_0), (false, true));
+        assert_eq!(has_diff(&key_with_diffs, height_1), (true, true));
+        // Only the last diffs of `key_without_diffs` are kept
+        assert_eq!(has_diff(&key_without_diffs, height_0), (false, false));
+        assert_eq!(has_diff(&key_without_diffs, height_1), (true, true));
+    }
+
+    /// Test that the pruned history can't be read anymore, while the merkle
+    /// subtree stores and the history after the oldest height are kept.
+    #[test]
+    fn test_prune_history() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        let key = Key::parse("test").unwrap();
+
+        for height in 1_u64..=3 {
+            let mut batch = RedbDB::batch();
+            let height = BlockHeight(height);
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                &key,
+                vec![height.0 as u8],
+                true,
+            )
+            .unwrap();
+            add_block_to_batch(
+                &db,
+                &mut batch,
+                height,
+                Epoch::default(),
+                Epochs::default(),
+                &ConversionState::default(),
+            )
+            .unwrap();
+            db.exec_batch(batch).unwrap();
+        }
+
+        let mut prune_history = |max_heights| {
+            let mut batch = RedbDB::batch();
+            let oldest_height = db
+                .prune_history(&mut batch, BlockHeight(3), max_heights)
+                .unwrap();
+            db.exec_batch(batch).unwrap();
+            oldest_height
+        };
+        // The pruning is limited to the given number of heights
+        assert_eq!(prune_history(1), Some(BlockHeight(1)));
+        assert_eq!(prune_history(10), Some(BlockHeight(3)));
+        assert_eq!(prune_history(10), None);
+
+        let last_height = BlockHeight(3);
+        let result =
+            db.read_subspace_val_with_height(&key, BlockHeight(2), last_height);
+        assert!(matches!(
+            result,
+            Err(Error::Pruned { height, oldest_height })
+                if height == BlockHeight(2) && oldest_height == BlockHeight(3)
+        ));
+        let val = db
+            .read_subspace_val_with_height(&key, BlockHeight(3), last_height)
+            .unwrap();
+        assert_eq!(val, Some(vec![3_u8]));
+
+        let hash_key = |height: BlockHeight| format!("{}/hash", height.raw());
+        assert!(db
+            .get(BLOCK_CF, &hash_key(BlockHeight(2)))
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get(BLOCK_CF, &hash_key(BlockHeight(3)))
+            .unwrap()
+            .is_some());
+        let root_key = subtree_key_prefix(&StoreType::Account, Epoch(0))
+            .with_segment("root".to_owned());
+        assert!(db.get(BLOCK_CF, &root_key.to_string()).unwrap().is_some());
+    }
+
+    /// Test that a checkpoint can be opened as a copy of the DB.
+    #[test]
+    fn test_checkpoint() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        let key = Key::parse("test").unwrap();
+        db.write_subspace_val(BlockHeight(1), &key, vec![1_u8], true)
+            .unwrap();
+
+        let checkpoint_dir = tempdir().unwrap();
+        let checkpoint_path = checkpoint_dir.path().join("checkpoint");
+        db.checkpoint(&checkpoint_path).unwrap();
+        db.delete_subspace_val(BlockHeight(2), &key, true).unwrap();
+
+        let checkpoint = open(&checkpoint_path, None).unwrap();
+        assert_eq!(checkpoint.read_subspace_val(&key).unwrap(), Some(vec![1]));
+        assert_eq!(db.read_subspace_val(&key).unwrap(), None);
+    }
+
+    /// A test helper to write a block
+    fn add_block_to_batch(
+        db: &RedbDB,
+        batch: &mut RedbWriteBatch,
+        height: BlockHeight,
+        epoch: Epoch,
+        pred_epochs: Epochs,
+        conversion_state: &ConversionState,
+    ) -> Result<()> {
+        let merkle_tree = MerkleTree::<Sha256Hasher>::default();
+        let merkle_tree_stores = merkle_tree.stores();
+        let hash = BlockHash::default();
+        let time = DateTimeUtc::now();
+        let next_epoch_min_start_height = BlockHeight::default();
+        let next_epoch_min_start_time = DateTimeUtc::now();
+        let update_epoch_blocks_delay = None;
+        let address_gen = EstablishedAddressGen::new("whatever");
+        let tx_queue = TxQueue::default();
+        let results = BlockResults::default();
+        let eth_events_queue = EthEventsQueue::default();
+        let block = BlockStateWrite {
+            merkle_tree_stores,
+            merkle_tree_layout: MerkleTreeLayout::default(),
+            header: None,
+            hash: &hash,
+            height,
+            time,
+            epoch,
+            results: &results,
+            conversion_state,
+            pred_epochs: &pred_epochs,
+            next_epoch_min_start_height,
+            next_epoch_min_start_time,
+            update_epoch_blocks_delay,
+            address_gen: &address_gen,
+            tx_queue: &tx_queue,
+            ethereum_height: None,
+            eth_events_queue: &eth_events_queue,
+        };
+
+        db.add_block_to_batch(block, batch, true)
+    }
+}
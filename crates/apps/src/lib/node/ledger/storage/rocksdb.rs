@@ -12,12 +12,16 @@
 //!     epoch can start
 //!   - `next_epoch_min_start_time`: minimum block time from which the next
 //!     epoch can start
+//!   - `merkle_tree_layout`: the layout of the merkle subtrees and the height
+//!     from which the token and governance subtrees are split
 //!   - `replay_protection`: hashes of the processed transactions
 //!   - `pred`: predecessor values of the top-level keys of the same name
 //!     - `tx_queue`
 //!     - `next_epoch_min_start_height`
 //!     - `next_epoch_min_start_time`
 //!   - `conversion_state`: MASP conversion state
+//!   - `oldest_height`: the oldest height whose diffs and block data haven't
+//!     been pruned, if any height has been pruned
 //! - `subspace`: accounts sub-spaces
 //!   - `{address}/{dyn}`: any byte data associated with accounts
 //! - `diffs`: diffs in account subspaces' key-vals
@@ -52,7 +56,9 @@ use namada::eth_bridge::storage::proof::BridgePoolRootProof;
 use namada::ledger::eth_bridge::storage::bridge_pool;
 use namada::ledger::replay_protection;
 use namada::ledger::storage::tx_queue::TxQueue;
-use namada::state::merkle_tree::{base_tree_key_prefix, subtree_key_prefix};
+use namada::state::merkle_tree::{
+    base_tree_key_prefix, subtree_key_prefix, MerkleTreeLayout,
+};
 use namada::state::types::PrefixIterator;
 use namada::state::{
     BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
@@ -67,6 +73,7 @@ use namada::types::time::DateTimeUtc;
 use namada::types::token::ConversionState;
 use namada::types::{ethereum_events, ethereum_structs};
 use rayon::prelude::*;
+use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{
     BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, DBCompactionStyle,
     DBCompressionType, Direction, FlushOptions, IteratorMode, Options,
@@ -88,6 +95,8 @@ const STATE_CF: &str = "state";
 const BLOCK_CF: &str = "block";
 const REPLAY_PROTECTION_CF: &str = "replay_protection";
 
+const OLDEST_HEIGHT_KEY: &str = "oldest_height";
+
 const OLD_DIFF_PREFIX: &str = "old";
 const NEW_DIFF_PREFIX: &str = "new";
 
@@ -633,6 +642,155 @@ impl RocksDB {
         tracing::info!("Flushing restored state to disk");
         self.exec_batch(batch)
     }
+
+    /// Create a checkpoint of the DB in the given directory, which must not
+    /// exist yet. The checkpoint is a consistent view of the DB that can be
+    /// opened independently of it.
+    pub fn checkpoint(&self, dir: impl AsRef<Path>) -> Result<()> {
+        Checkpoint::new(&self.0)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(dir))
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
+    /// Call the given function with every entry needed to resume the chain
+    /// from the last committed block, along with the name of its column
+    /// family. These are the whole `state`, `subspace` and
+    /// `replay_protection` column families, the diffs and the block state of
+    /// the last height, its results and the merkle subtrees of its epoch.
+    pub fn for_each_snapshot_entry<E>(
+        &self,
+        mut f: impl FnMut(&str, &[u8], &[u8]) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<Error>,
+    {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        let height: BlockHeight = match self
+            .0
+            .get_cf(state_cf, "height")
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(()),
+        };
+
+        for cf_name in [STATE_CF, SUBSPACE_CF, REPLAY_PROTECTION_CF] {
+            self.for_each_cf_entry(cf_name, None, &mut f)?;
+        }
+        // The history before the last height is not part of the snapshot
+        f(STATE_CF, OLDEST_HEIGHT_KEY.as_bytes(), &types::encode(&height))?;
+        let prefix = format!("{}/", height.raw());
+        self.for_each_cf_entry(DIFFS_CF, Some(prefix.clone()), &mut f)?;
+        self.for_each_cf_entry(BLOCK_CF, Some(prefix), &mut f)?;
+
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let mut keys = vec![format!("results/{}", height.raw())];
+        let epoch_key = format!("{}/epoch", height.raw());
+        if let Some(bytes) = self
+            .0
+            .get_cf(block_cf, epoch_key)
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            let epoch: Epoch =
+                types::decode(bytes).map_err(Error::CodingError)?;
+            for st in StoreType::iter_subtrees() {
+                let key_prefix = subtree_key_prefix(st, epoch);
+                keys.push(
+                    key_prefix
+                        .clone()
+                        .with_segment("root".to_owned())
+                        .to_string(),
+                );
+                keys.push(
+                    key_prefix.with_segment("store".to_owned()).to_string(),
+                );
+            }
+        }
+        for key in keys {
+            if let Some(value) = self
+                .0
+                .get_cf(block_cf, &key)
+                .map_err(|e| Error::DBError(e.into_string()))?
+            {
+                f(BLOCK_CF, key.as_bytes(), &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Call the given function with every entry of a column family, whose
+    /// key starts with the given prefix, if any.
+    fn for_each_cf_entry<E>(
+        &self,
+        cf_name: &str,
+        prefix: Option<String>,
+        f: &mut impl FnMut(&str, &[u8], &[u8]) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<Error>,
+    {
+        let cf = self.get_column_family(cf_name)?;
+        let read_opts = make_iter_read_opts(prefix.clone());
+        let mode = match prefix.as_ref() {
+            Some(prefix) => {
+                IteratorMode::From(prefix.as_bytes(), Direction::Forward)
+            }
+            None => IteratorMode::Start,
+        };
+        for entry in self.0.iterator_cf_opt(cf, read_opts, mode) {
+            let (key, value) =
+                entry.map_err(|e| Error::DBError(e.into_string()))?;
+            if let Some(prefix) = prefix.as_ref() {
+                if !key.starts_with(prefix.as_bytes()) {
+                    break;
+                }
+            }
+            f(cf_name, &key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Write the entries of a state sync snapshot chunk, given with the name
+    /// of their column family.
+    pub fn write_snapshot_entries<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'a str, &'a [u8], &'a [u8])>,
+    ) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for (cf_name, key, value) in entries {
+            let cf = self.get_column_family(cf_name)?;
+            batch.put_cf(cf, key, value);
+        }
+        self.exec_batch(batch)
+    }
+
+    /// Read the oldest height whose diffs and block data haven't been pruned,
+    /// if any height has been pruned.
+    fn read_oldest_height(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.0
+            .get_cf(state_cf, OLDEST_HEIGHT_KEY)
+            .map_err(|e| Error::DBError(e.into_string()))?
+            .map(|bytes| types::decode(bytes).map_err(Error::CodingError))
+            .transpose()
+    }
+
+    /// Delete all the entries of the DB, e.g. to discard the state restored
+    /// from an invalid state sync snapshot.
+    pub fn clear(&mut self) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for cf_name in
+            [SUBSPACE_CF, DIFFS_CF, STATE_CF, BLOCK_CF, REPLAY_PROTECTION_CF]
+        {
+            let cf = self.get_column_family(cf_name)?;
+            for entry in self.0.iterator_cf(cf, IteratorMode::Start) {
+                let (key, _value) =
+                    entry.map_err(|e| Error::DBError(e.into_string()))?;
+                batch.delete_cf(cf, key);
+            }
+        }
+        self.exec_batch(batch)
+    }
 }
 
 impl DB for RocksDB {
@@ -722,6 +880,16 @@ impl DB for RocksDB {
                 return Ok(None);
             }
         };
+        // The layout isn't stored by the versions before the subtrees were
+        // split from the account subtree
+        let merkle_tree_layout: MerkleTreeLayout = match self
+            .0
+            .get_cf(state_cf, "merkle_tree_layout")
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => types::decode(bytes).map_err(Error::CodingError)?,
+            None => MerkleTreeLayout::LEGACY,
+        };
         let conversion_state: ConversionState = match self
             .0
             .get_cf(state_cf, "conversion_state")
@@ -889,6 +1057,7 @@ impl DB for RocksDB {
                 Some(address_gen),
             ) => Ok(Some(BlockStateRead {
                 merkle_tree_stores,
+                merkle_tree_layout,
                 hash,
                 height,
                 time,
@@ -919,6 +1088,7 @@ impl DB for RocksDB {
     ) -> Result<()> {
         let BlockStateWrite {
             merkle_tree_stores,
+            merkle_tree_layout,
             header,
             hash,
             height,
@@ -990,6 +1160,11 @@ impl DB for RocksDB {
             "update_epoch_blocks_delay",
             types::encode(&update_epoch_blocks_delay),
         );
+        batch.0.put_cf(
+            state_cf,
+            "merkle_tree_layout",
+            types::encode(&merkle_tree_layout),
+        );
 
         // Save the conversion state when the epoch is updated
         if is_full_commit {
@@ -1184,6 +1359,9 @@ impl DB for RocksDB {
                     let root = types::decode(b).map_err(Error::CodingError)?;
                     merkle_tree_stores.set_root(st, root);
                 }
+                // The subtrees split from the account subtree aren't stored
+                // in the epochs before the split
+                None if st.is_split_from_account() => continue,
                 None => return Ok(None),
             }
 
@@ -1256,6 +1434,15 @@ impl DB for RocksDB {
         height: BlockHeight,
         last_height: BlockHeight,
     ) -> Result<Option<Vec<u8>>> {
+        if let Some(oldest_height) = self.read_oldest_height()? {
+            if height < oldest_height {
+                return Err(Error::Pruned {
+                    height,
+                    oldest_height,
+                });
+            }
+        }
+
         // Check if the value changed at this height
         let diffs_cf = self.get_column_family(DIFFS_CF)?;
         let (old_val_key, new_val_key) = old_and_new_diff_key(key, height)?;
@@ -1513,6 +1700,56 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        max_heights: u64,
+    ) -> Result<Option<BlockHeight>> {
+        let from = self.read_oldest_height()?.unwrap_or_default();
+        let to = BlockHeight(height.0.min(from.0.saturating_add(max_heights)));
+        if to <= from {
+            return Ok(None);
+        }
+        let (from_key, to_key) = (from.raw(), to.raw());
+
+        // The diffs keys are prefixed with their height
+        let diffs_cf = self.get_column_family(DIFFS_CF)?;
+        batch.0.delete_range_cf(diffs_cf, &from_key, &to_key);
+
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        batch.0.delete_range_cf(
+            block_cf,
+            format!("results/{from_key}"),
+            format!("results/{to_key}"),
+        );
+        let base_tree = StoreType::Base.to_string();
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_total_order_seek(true);
+        read_opts.set_iterate_upper_bound(to_key);
+        for entry in self.0.iterator_cf_opt(
+            block_cf,
+            read_opts,
+            IteratorMode::From(from_key.as_bytes(), Direction::Forward),
+        ) {
+            let (key, _value) =
+                entry.map_err(|e| Error::DBError(e.into_string()))?;
+            let path = String::from_utf8_lossy(&key);
+            let mut segments = path.split(KEY_SEGMENT_SEPARATOR).skip(1);
+            // The merkle subtree stores are prefixed with an epoch instead of
+            // a height
+            let is_subtree_store = segments.next() == Some("tree")
+                && segments.next() != Some(base_tree.as_str());
+            if !is_subtree_store {
+                batch.0.delete_cf(block_cf, &key);
+            }
+        }
+
+        let state_cf = self.get_column_family(STATE_CF)?;
+        batch.0.put_cf(state_cf, OLDEST_HEIGHT_KEY, types::encode(&to));
+        Ok(Some(to))
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         height: BlockHeight,
@@ -1573,6 +1810,28 @@ impl<'iter> DBIter<'iter> for RocksDB {
         iter_subspace_prefix(self, prefix)
     }
 
+    fn iter_prefix_from(
+        &'iter self,
+        prefix: Option<&Key>,
+        start: &Key,
+    ) -> PersistentPrefixIterator<'iter> {
+        let subspace_cf = self
+            .get_column_family(SUBSPACE_CF)
+            .expect("{SUBSPACE_CF} column family should exist");
+        let prefix = match prefix {
+            Some(p) if !p.is_empty() => format!("{p}/"),
+            _ => "".to_owned(),
+        };
+        let read_opts = make_iter_read_opts(Some(prefix));
+        let start = start.to_string();
+        let iter = self.0.iterator_cf_opt(
+            subspace_cf,
+            read_opts,
+            IteratorMode::From(start.as_bytes(), Direction::Forward),
+        );
+        PersistentPrefixIterator(PrefixIterator::new(iter, "".to_owned()))
+    }
+
     fn iter_results(&'iter self) -> PersistentPrefixIterator<'iter> {
         let db_prefix = "results/".to_owned();
         let prefix = "results".to_owned();
@@ -2271,6 +2530,84 @@ mod test {
         }
     }
 
+    /// Test that the pruned history can't be read anymore, while the merkle
+    /// subtree stores and the history after the oldest height are kept.
+    #[test]
+    fn test_prune_history() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        let key = Key::parse("test").unwrap();
+
+        for height in 1_u64..=3 {
+            let mut batch = RocksDB::batch();
+            let height = BlockHeight(height);
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                &key,
+                vec![height.0 as u8],
+                true,
+            )
+            .unwrap();
+            add_block_to_batch(
+                &db,
+                &mut batch,
+                height,
+                Epoch::default(),
+                Epochs::default(),
+                &ConversionState::default(),
+            )
+            .unwrap();
+            db.exec_batch(batch.0).unwrap();
+        }
+
+        let mut prune_history = |max_heights| {
+            let mut batch = RocksDB::batch();
+            let oldest_height = db
+                .prune_history(&mut batch, BlockHeight(3), max_heights)
+                .unwrap();
+            db.exec_batch(batch.0).unwrap();
+            oldest_height
+        };
+        // The pruning is limited to the given number of heights
+        assert_eq!(prune_history(1), Some(BlockHeight(1)));
+        assert_eq!(prune_history(10), Some(BlockHeight(3)));
+        assert_eq!(prune_history(10), None);
+
+        let last_height = BlockHeight(3);
+        let result =
+            db.read_subspace_val_with_height(&key, BlockHeight(2), last_height);
+        assert!(matches!(
+            result,
+            Err(Error::Pruned { height, oldest_height })
+                if height == BlockHeight(2) && oldest_height == BlockHeight(3)
+        ));
+        let val = db
+            .read_subspace_val_with_height(&key, BlockHeight(3), last_height)
+            .unwrap();
+        assert_eq!(val, Some(vec![3_u8]));
+
+        let block_cf = db.get_column_family(BLOCK_CF).unwrap();
+        let hash_key = |height: BlockHeight| format!("{}/hash", height.raw());
+        assert!(
+            db.0.get_cf(block_cf, hash_key(BlockHeight(2)))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            db.0.get_cf(block_cf, hash_key(BlockHeight(3)))
+                .unwrap()
+                .is_some()
+        );
+        let root_key = subtree_key_prefix(&StoreType::Account, Epoch(0))
+            .with_segment("root".to_owned());
+        assert!(
+            db.0.get_cf(block_cf, root_key.to_string())
+                .unwrap()
+                .is_some()
+        );
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
@@ -2293,6 +2630,7 @@ mod test {
         let eth_events_queue = EthEventsQueue::default();
         let block = BlockStateWrite {
             merkle_tree_stores,
+            merkle_tree_layout: MerkleTreeLayout::default(),
             header: None,
             hash: &hash,
             height,
@@ -0,0 +1,404 @@
+//! State sync snapshots of the persistent storage.
+//!
+//! A snapshot holds the DB entries needed to resume the chain from the block
+//! at which it was taken. The entries are streamed into borsh encoded chunks,
+//! written under `{snapshots_dir}/{height}` along with the snapshot's
+//! metadata, which lists the hashes of the chunks. The hash of the snapshot
+//! commits to its metadata, so that every chunk received from a peer can be
+//! verified before it's applied.
+//!
+//! The restored subspace is verified against the app hash. The block state
+//! that is not committed to by the app hash is verified against the restored
+//! subspace where it can be derived from it, i.e. the epoch and the MASP
+//! conversions. The rest of it, such as the queue of transactions to decrypt
+//! or the queue of Ethereum events, can only be checked by executing the
+//! following blocks: an invalid state diverges the app hash of the next block
+//! from the one of the network, which CometBFT rejects.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use masp_primitives::bls12_381;
+use masp_primitives::ff::PrimeField;
+use masp_primitives::merkle_tree::FrozenCommitmentTree;
+use masp_primitives::sapling::Node;
+use namada::state::{self, DbError, State, StorageHasher};
+use namada::token::storage_key::masp_convert_anchor_key;
+use namada::types::hash::Hash;
+use namada::types::storage::BlockHeight;
+use thiserror::Error;
+
+use super::backend::{self, PersistentDB};
+
+/// The version of the snapshot format
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// The size of the entries after which a chunk is complete. CometBFT rejects
+/// chunks larger than 16 MiB.
+const CHUNK_SIZE: usize = 10 * 1024 * 1024;
+
+/// The name of the file holding the metadata of a snapshot
+const METADATA_FILE: &str = "metadata";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("DB error: {0}")]
+    Db(#[from] DbError),
+    #[error("Error decoding chunk {0}: {1}")]
+    ChunkDecoding(u32, std::io::Error),
+    #[error("Chunk {0} is not part of the snapshot")]
+    UnknownChunk(u32),
+    #[error("Chunk {0} doesn't match its hash in the snapshot metadata")]
+    InvalidChunkHash(u32),
+    #[error("State error: {0}")]
+    State(#[from] state::Error),
+    #[error("No state was restored from the snapshot")]
+    NoRestoredState,
+    #[error(
+        "The restored state at height {height} with app hash {root} doesn't \
+         match the snapshot"
+    )]
+    InvalidRestoredState { height: u64, root: Hash },
+    #[error("The restored {0} doesn't match the restored subspace")]
+    InvalidRestoredBlockState(&'static str),
+}
+
+/// Snapshot's result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An entry of a DB column family
+#[derive(BorshSerialize, BorshDeserialize)]
+pub(super) struct Entry {
+    pub cf: String,
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// The metadata of a snapshot, sent to the peers along with it
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotMetadata {
+    /// The hashes of the chunks, in order
+    pub chunk_hashes: Vec<Hash>,
+}
+
+impl SnapshotMetadata {
+    /// The number of chunks of the snapshot
+    pub fn chunks(&self) -> u32 {
+        self.chunk_hashes.len() as u32
+    }
+
+    /// The hash of the snapshot
+    pub fn hash(&self) -> Hash {
+        Hash::sha256(self.serialize_to_vec())
+    }
+}
+
+/// A snapshot stored on disk
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    /// The height of the block at which the snapshot was taken
+    pub height: BlockHeight,
+    /// The metadata of the snapshot
+    pub metadata: SnapshotMetadata,
+}
+
+/// The directory in which the snapshots are stored
+#[derive(Clone, Debug)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Use the given directory to store snapshots. It's created on the first
+    /// snapshot taken.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    /// Take a snapshot of the last block committed to the DB at the given
+    /// height. Only a checkpoint of the DB is created synchronously, which is
+    /// cheap, while the snapshot is written from it in a background thread.
+    /// Once written, only the `keep` most recent snapshots are kept.
+    pub fn take_snapshot(
+        &self,
+        db: &PersistentDB,
+        height: BlockHeight,
+        keep: usize,
+    ) -> Result<JoinHandle<()>> {
+        fs::create_dir_all(&self.dir)?;
+        let checkpoint_dir = self.dir.join(format!(".checkpoint-{height}"));
+        if checkpoint_dir.exists() {
+            fs::remove_dir_all(&checkpoint_dir)?;
+        }
+        db.checkpoint(&checkpoint_dir)?;
+
+        let store = self.clone();
+        Ok(thread::spawn(move || {
+            let result = backend::open(&checkpoint_dir, None)
+                .map_err(Error::from)
+                .and_then(|checkpoint| {
+                    store.write_snapshot(&checkpoint, height)
+                })
+                .and_then(|metadata| {
+                    store.prune(keep)?;
+                    Ok(metadata)
+                });
+            match result {
+                Ok(metadata) => tracing::info!(
+                    "Took a state sync snapshot at height {height} with {} \
+                     chunks",
+                    metadata.chunks()
+                ),
+                Err(e) => tracing::error!(
+                    "Failed to take a state sync snapshot at height \
+                     {height}: {e}"
+                ),
+            }
+            if let Err(e) = fs::remove_dir_all(&checkpoint_dir) {
+                tracing::error!(
+                    "Failed to remove the DB checkpoint {}: {e}",
+                    checkpoint_dir.to_string_lossy()
+                );
+            }
+        }))
+    }
+
+    /// Write a snapshot of the last block committed to the DB at the given
+    /// height. The snapshot only becomes visible once it's complete.
+    pub fn write_snapshot(
+        &self,
+        db: &PersistentDB,
+        height: BlockHeight,
+    ) -> Result<SnapshotMetadata> {
+        let tmp_dir = self.dir.join(format!(".tmp-{height}"));
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+        fs::create_dir_all(&tmp_dir)?;
+
+        let mut writer = ChunkWriter::new(tmp_dir.clone());
+        db.for_each_snapshot_entry(|cf, key, value| {
+            writer.push(Entry {
+                cf: cf.to_owned(),
+                key: key.to_vec(),
+                value: value.to_vec(),
+            })
+        })?;
+        let metadata = writer.finish()?;
+        fs::write(tmp_dir.join(METADATA_FILE), metadata.serialize_to_vec())?;
+
+        let dir = self.snapshot_dir(height);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::rename(tmp_dir, dir)?;
+        Ok(metadata)
+    }
+
+    /// List the complete snapshots, from the most recent one.
+    pub fn list(&self) -> Result<Vec<Snapshot>> {
+        if !self.dir.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut snapshots = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let height = match path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<u64>().ok())
+            {
+                Some(height) => BlockHeight(height),
+                None => continue,
+            };
+            let metadata_path = path.join(METADATA_FILE);
+            if !metadata_path.is_file() {
+                continue;
+            }
+            let metadata =
+                SnapshotMetadata::try_from_slice(&fs::read(metadata_path)?)?;
+            snapshots.push(Snapshot { height, metadata });
+        }
+        snapshots.sort_by(|a, b| b.height.cmp(&a.height));
+        Ok(snapshots)
+    }
+
+    /// Load a chunk of the snapshot taken at the given height.
+    pub fn load_chunk(
+        &self,
+        height: BlockHeight,
+        index: u32,
+    ) -> Result<Vec<u8>> {
+        let path = self.snapshot_dir(height).join(chunk_file_name(index));
+        if !path.is_file() {
+            return Err(Error::UnknownChunk(index));
+        }
+        Ok(fs::read(path)?)
+    }
+
+    /// Remove all but the `keep` most recent snapshots.
+    fn prune(&self, keep: usize) -> Result<()> {
+        for snapshot in self.list()?.into_iter().skip(keep) {
+            fs::remove_dir_all(self.snapshot_dir(snapshot.height))?;
+        }
+        Ok(())
+    }
+
+    fn snapshot_dir(&self, height: BlockHeight) -> PathBuf {
+        self.dir.join(height.0.to_string())
+    }
+}
+
+/// Verify a chunk of a snapshot against its metadata and write its entries
+/// to the DB.
+pub fn apply_chunk(
+    db: &mut PersistentDB,
+    metadata: &SnapshotMetadata,
+    index: u32,
+    chunk: &[u8],
+) -> Result<()> {
+    let expected_hash = metadata
+        .chunk_hashes
+        .get(index as usize)
+        .ok_or(Error::UnknownChunk(index))?;
+    if Hash::sha256(chunk) != *expected_hash {
+        return Err(Error::InvalidChunkHash(index));
+    }
+    let entries = Vec::<Entry>::try_from_slice(chunk)
+        .map_err(|e| Error::ChunkDecoding(index, e))?;
+    db.write_snapshot_entries(entries.iter().map(|entry| {
+        (entry.cf.as_str(), entry.key.as_slice(), entry.value.as_slice())
+    }))?;
+    Ok(())
+}
+
+/// Load the state restored from all the chunks of a snapshot and verify it
+/// against the height and app hash of the snapshot. Neither the Merkle tree
+/// stores nor the subspace of the snapshot are trusted, the tree is rebuilt
+/// from the restored subspace and its root must match the app hash. The
+/// block state is then verified against the restored subspace.
+pub fn verify_restored_state<H: StorageHasher>(
+    storage: &mut State<PersistentDB, H>,
+    height: BlockHeight,
+    app_hash: &[u8],
+) -> Result<()> {
+    storage.load_last_state()?;
+    let (_, restored_height) =
+        storage.get_state().ok_or(Error::NoRestoredState)?;
+    let root = storage.rebuild_merkle_tree_from_subspace()?.root();
+    if restored_height != height.0
+        || root.0.as_slice() != app_hash
+        || root != storage.merkle_root()
+    {
+        return Err(Error::InvalidRestoredState {
+            height: restored_height,
+            root: root.into(),
+        });
+    }
+    verify_restored_block_state(storage)
+}
+
+/// Verify the block state restored from a snapshot against the restored
+/// subspace. The epoch must match the restored epochs and the conversion tree
+/// must be rebuilt from the conversions of the assets, and match the convert
+/// anchor of the subspace.
+fn verify_restored_block_state<H: StorageHasher>(
+    storage: &State<PersistentDB, H>,
+) -> Result<()> {
+    let block = &storage.block;
+    if block.pred_epochs.get_epoch(block.height) != Some(block.epoch) {
+        return Err(Error::InvalidRestoredBlockState("epoch"));
+    }
+
+    // The assets that are only used for decoding are not in the tree
+    let conversion_state = &storage.conversion_state;
+    let mut leaves = vec![None; conversion_state.tree.size()];
+    for (_, _, conversion, pos) in conversion_state.assets.values() {
+        if let Some(leaf) = leaves.get_mut(*pos) {
+            if leaf
+                .replace(Node::new(conversion.cmu().to_repr()))
+                .is_some()
+            {
+                return Err(Error::InvalidRestoredBlockState(
+                    "conversion state",
+                ));
+            }
+        }
+    }
+    let leaves = leaves
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::InvalidRestoredBlockState("conversion state"))?;
+    let root = conversion_state.tree.root();
+    if !leaves.is_empty() && FrozenCommitmentTree::new(&leaves).root() != root {
+        return Err(Error::InvalidRestoredBlockState("conversion state"));
+    }
+    let anchor = storage
+        .read(&masp_convert_anchor_key())?
+        .0
+        .map(|bytes| Hash::try_from_slice(&bytes))
+        .transpose()?;
+    if anchor != Some(Hash(bls12_381::Scalar::from(root).to_bytes())) {
+        return Err(Error::InvalidRestoredBlockState("conversion tree"));
+    }
+    Ok(())
+}
+
+fn chunk_file_name(index: u32) -> String {
+    format!("chunk_{index}")
+}
+
+/// Splits the entries of a snapshot into chunk files
+struct ChunkWriter {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    size: usize,
+    chunk_hashes: Vec<Hash>,
+}
+
+impl ChunkWriter {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: vec![],
+            size: 0,
+            chunk_hashes: vec![],
+        }
+    }
+
+    fn push(&mut self, entry: Entry) -> Result<()> {
+        self.size += entry.cf.len() + entry.key.len() + entry.value.len();
+        self.entries.push(entry);
+        if self.size >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let bytes = std::mem::take(&mut self.entries).serialize_to_vec();
+        let index = self.chunk_hashes.len() as u32;
+        fs::write(self.dir.join(chunk_file_name(index)), &bytes)?;
+        self.chunk_hashes.push(Hash::sha256(&bytes));
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Write the last chunk and return the snapshot's metadata. A snapshot
+    /// always has at least one chunk, as required by CometBFT.
+    fn finish(mut self) -> Result<SnapshotMetadata> {
+        if !self.entries.is_empty() || self.chunk_hashes.is_empty() {
+            self.flush()?;
+        }
+        Ok(SnapshotMetadata {
+            chunk_hashes: self.chunk_hashes,
+        })
+    }
+}
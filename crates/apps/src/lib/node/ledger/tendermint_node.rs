@@ -281,6 +281,9 @@ fn validator_key_to_json(
                 sk_sec.serialize_to_vec(),
             )
         }
+        common::SecretKey::Bls(_) => {
+            return Err(ParseSecretKeyError::MismatchedScheme);
+        }
     };
 
     Ok(json!({
@@ -301,7 +304,8 @@ pub fn write_validator_key(
     home_dir: impl AsRef<Path>,
     consensus_key: &common::SecretKey,
 ) -> Result<()> {
-    let key = validator_key_to_json(consensus_key).unwrap();
+    let key = validator_key_to_json(consensus_key)
+        .map_err(|_| Error::CantEncode("a BLS key as a consensus key"))?;
     write_validator(validator_key(home_dir), KEY_DIR, KEY_FILE, key)
 }
 
@@ -365,6 +369,11 @@ pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
             let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
             bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
         }
+        common::PublicKey::Bls(_) => {
+            let _pk: bls::PublicKey = pk.try_to_pk().unwrap();
+            let digest = Sha256::digest(_pk.serialize_to_vec().as_slice());
+            bytes.copy_from_slice(&digest[..TENDERMINT_NODE_ID_LENGTH]);
+        }
     }
     TendermintNodeId::new(bytes)
 }
@@ -3,9 +3,11 @@ use std::collections::{HashMap, HashSet};
 use criterion::{criterion_group, criterion_main, Criterion};
 use namada::core::types::account::AccountPublicKeysMap;
 use namada::core::types::address;
+use namada::core::types::hash::Hash;
+use namada::core::types::key::{bls, common, SigScheme};
 use namada::ledger::storage::DB;
 use namada::token::{Amount, Transfer};
-use namada::tx::Signature;
+use namada::tx::{AggregateSignature, Signature};
 use namada::vm::wasm::TxCache;
 use namada_apps::bench_utils::{
     BenchShell, TX_INIT_PROPOSAL_WASM, TX_REVEAL_PK_WASM, TX_TRANSFER_WASM,
@@ -52,13 +54,91 @@ fn tx_section_signature_validation(c: &mut Criterion) {
                     &mut HashSet::new(),
                     &pkim,
                     &None,
-                    &mut || Ok(()),
+                    &mut |_| Ok(()),
                 )
                 .unwrap()
         })
     });
 }
 
+// Benchmarks the validation of a single BLS signature on a single `Section` of
+// a transaction
+fn tx_section_bls_signature_validation(c: &mut Criterion) {
+    let mut csprng = rand::rngs::OsRng {};
+    let keypair = common::SecretKey::Bls(bls::SigScheme::generate(&mut csprng));
+    let section_hash = Hash::sha256(b"section");
+
+    let pkim = AccountPublicKeysMap::from_iter([keypair.to_public()]);
+
+    let multisig = Signature::new(
+        vec![section_hash],
+        pkim.index_secret_keys(vec![keypair]),
+        None,
+    );
+
+    c.bench_function("tx_section_bls_signature_validation", |b| {
+        b.iter(|| {
+            multisig
+                .verify_signature(
+                    &mut HashSet::new(),
+                    &pkim,
+                    &None,
+                    &mut |_| Ok(()),
+                )
+                .unwrap()
+        })
+    });
+}
+
+// Benchmarks the validation of an aggregate of BLS signatures on a single
+// `Section` of a transaction, by number of signers. The cost per signer is the
+// slope of the results.
+fn tx_section_aggregate_signature_validation(c: &mut Criterion) {
+    let mut group =
+        c.benchmark_group("tx_section_aggregate_signature_validation");
+    let mut csprng = rand::rngs::OsRng {};
+    let signer = Some(defaults::albert_address());
+    let section_hash = Hash::sha256(b"section");
+
+    for signers in [2, 4, 8, 16, 32] {
+        let keypairs: Vec<_> = (0..signers)
+            .map(|_| {
+                common::SecretKey::Bls(bls::SigScheme::generate(&mut csprng))
+            })
+            .collect();
+        let pkim = AccountPublicKeysMap::from_iter(
+            keypairs.iter().map(|keypair| keypair.to_public()),
+        );
+        let signatures = Signature::new(
+            vec![section_hash],
+            pkim.index_secret_keys(keypairs),
+            signer.clone(),
+        );
+        let aggregate = AggregateSignature::new(
+            vec![section_hash],
+            defaults::albert_address(),
+            &signatures.signatures,
+            &pkim,
+        )
+        .unwrap();
+
+        group.bench_function(format!("signers: {signers}"), |b| {
+            b.iter(|| {
+                aggregate
+                    .verify_signature(
+                        &mut HashSet::new(),
+                        &pkim,
+                        &signer,
+                        &mut |_| Ok(()),
+                    )
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
 fn compile_wasm(c: &mut Criterion) {
     let mut group = c.benchmark_group("compile_wasm");
     let mut txs: HashMap<&str, Vec<u8>> = HashMap::default();
@@ -333,6 +413,8 @@ fn storage_write(c: &mut Criterion) {
 criterion_group!(
     host_env,
     tx_section_signature_validation,
+    tx_section_bls_signature_validation,
+    tx_section_aggregate_signature_validation,
     compile_wasm,
     untrusted_wasm_validation,
     write_log_read,
@@ -23,7 +23,7 @@ use namada::ibc::core::host::types::identifiers::{
 };
 use namada::ibc::{IbcActions, TransferModule};
 use namada::ledger::eth_bridge::read_native_erc20_address;
-use namada::ledger::gas::{TxGasMeter, VpGasMeter};
+use namada::ledger::gas::{TxGasMeter, VpGasMeter, VpsGas};
 use namada::ledger::governance::GovernanceVp;
 use namada::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
 use namada::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
@@ -54,6 +54,7 @@ use namada_apps::bench_utils::{
     TX_TRANSFER_WASM, TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL_WASM,
 };
 use namada_apps::wallet::defaults;
+use rayon::prelude::*;
 
 fn governance(c: &mut Criterion) {
     let mut group = c.benchmark_group("vp_governance");
@@ -1089,6 +1090,118 @@ fn pos(c: &mut Criterion) {
     group.finish();
 }
 
+/// Run the native VPs triggered by an IBC transfer, which read some of the
+/// same values from storage, with a cache of these values either shared by
+/// the VPs or cleared before every VP
+fn vps_pre_state_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vps_pre_state_cache");
+    let shell = BenchShell::default();
+    let outgoing_transfer = shell.generate_ibc_transfer_tx();
+
+    let mut shell = BenchShell::default();
+    shell.init_ibc_channel();
+    shell.execute_tx(&outgoing_transfer);
+    let (verifiers, keys_changed) = shell
+        .wl_storage
+        .write_log
+        .verifiers_and_changed_keys(&BTreeSet::default());
+
+    let ibc_address = Address::Internal(InternalAddress::Ibc);
+    let multitoken_address = Address::Internal(InternalAddress::Multitoken);
+    let new_ctx = |address| {
+        Ctx::new(
+            address,
+            &shell.wl_storage.storage,
+            &shell.wl_storage.write_log,
+            &outgoing_transfer,
+            &TxIndex(0),
+            VpGasMeter::new_from_tx_meter(&TxGasMeter::new_from_sub_limit(
+                u64::MAX.into(),
+            )),
+            &keys_changed,
+            &verifiers,
+            shell.vp_wasm_cache.clone(),
+        )
+    };
+    let ibc = Ibc {
+        ctx: new_ctx(&ibc_address),
+    };
+    let multitoken = MultitokenVp {
+        ctx: new_ctx(&multitoken_address),
+    };
+    let cache = &shell.wl_storage.storage.pre_state_cache;
+
+    for (bench_name, shared) in
+        [("separate_caches", false), ("shared_cache", true)]
+    {
+        group.bench_function(bench_name, |b| {
+            b.iter(|| {
+                cache.clear();
+                assert!(
+                    ibc.validate_tx(
+                        &outgoing_transfer,
+                        ibc.ctx.keys_changed,
+                        ibc.ctx.verifiers,
+                    )
+                    .unwrap()
+                );
+                if !shared {
+                    cache.clear();
+                }
+                assert!(
+                    multitoken
+                        .validate_tx(
+                            &outgoing_transfer,
+                            multitoken.ctx.keys_changed,
+                            multitoken.ctx.verifiers,
+                        )
+                        .unwrap()
+                );
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Run the given number of empty VPs the way the VPs of a transaction are run,
+/// either in parallel or sequentially, to measure the overhead of scheduling a
+/// VP on the thread pool and merging its gas with the other VPs
+fn vps_parallel_overhead(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vps_parallel_overhead");
+    let tx_gas_meter = TxGasMeter::new_from_sub_limit(u64::MAX.into());
+    let run_vp = |mut vps_gas: VpsGas, _vp: &u64| {
+        vps_gas
+            .set(VpGasMeter::new_from_tx_meter(&tx_gas_meter))
+            .map(|()| vps_gas)
+    };
+
+    for num_vps in [1_u64, 2, 4, 8, 16, 32, 64] {
+        let verifiers: BTreeSet<u64> = (0..num_vps).collect();
+        group.bench_function(format!("parallel_{num_vps}"), |b| {
+            b.iter(|| {
+                verifiers
+                    .par_iter()
+                    .try_fold(VpsGas::default, run_vp)
+                    .try_reduce(VpsGas::default, |mut a, b| {
+                        a.merge(b, &tx_gas_meter).map(|()| a)
+                    })
+                    .unwrap()
+            })
+        });
+        group.bench_function(format!("sequential_{num_vps}"), |b| {
+            b.iter(|| {
+                verifiers
+                    .iter()
+                    .try_fold(VpsGas::default(), run_vp)
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
 fn ibc_vp_validate_action(c: &mut Criterion) {
     let mut group = c.benchmark_group("vp_ibc_validate_action");
     let shell = BenchShell::default();
@@ -1299,6 +1412,8 @@ criterion_group!(
     eth_bridge_pool,
     parameters,
     pos,
+    vps_pre_state_cache,
+    vps_parallel_overhead,
     ibc_vp_validate_action,
     ibc_vp_execute_action
 );
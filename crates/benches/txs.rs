@@ -1,8 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::str::FromStr;
 
 use criterion::{criterion_group, criterion_main, Criterion};
-use namada::account::{InitAccount, UpdateAccount};
+use namada::account::{
+    GuardianSet, InitAccount, InitiateRecovery, QueueWithdrawal,
+    QueuedWithdrawalAction, SpendingPolicy, UpdateAccount, UpdateGuardians,
+    UpdateSpendingPolicy,
+};
 use namada::core::types::key::{
     common, SecretKey as SecretKeyInterface, SigScheme,
 };
@@ -25,7 +29,11 @@ use namada::proof_of_stake::storage::read_pos_params;
 use namada::proof_of_stake::types::SlashType;
 use namada::proof_of_stake::{self, KeySeg};
 use namada::state::{StorageRead, StorageWrite};
-use namada::token::Amount;
+use namada::token::{
+    Allowance, Amount, Approve, BurnTokens, DenominatedAmount, MintTokens,
+    RegisterToken, TokenMetadata, TransferFrom, UpdateTokenMetadata,
+    NATIVE_MAX_DECIMAL_PLACES,
+};
 use namada::tx::data::pos::{
     BecomeValidator, Bond, CommissionChange, ConsensusKeyChange,
     MetaDataChange, Redelegation, Withdraw,
@@ -36,17 +44,23 @@ use namada::types::eth_bridge_pool::{GasFee, PendingTransfer};
 use namada::types::hash::Hash;
 use namada::types::key::{ed25519, secp256k1, PublicKey, RefTo};
 use namada::types::masp::{TransferSource, TransferTarget};
-use namada::types::storage::Key;
+use namada::types::storage::{Epoch, Key};
 use namada_apps::bench_utils::{
     BenchShell, BenchShieldedCtx, ALBERT_PAYMENT_ADDRESS, ALBERT_SPENDING_KEY,
-    BERTHA_PAYMENT_ADDRESS, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_VALIDATOR_COMMISSION_WASM, TX_CHANGE_VALIDATOR_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL_WASM, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL_WASM, TX_WITHDRAW_WASM,
+    BERTHA_PAYMENT_ADDRESS, TX_APPROVE_WASM, TX_BECOME_VALIDATOR_WASM,
+    TX_BOND_WASM, TX_BRIDGE_POOL_WASM, TX_BURN_TOKENS_WASM,
+    TX_CANCEL_RECOVERY_WASM, TX_CANCEL_WITHDRAWAL_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_VALIDATOR_COMMISSION_WASM,
+    TX_CHANGE_VALIDATOR_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+    TX_DEACTIVATE_VALIDATOR_WASM, TX_EXECUTE_WITHDRAWAL_WASM,
+    TX_FINALIZE_RECOVERY_WASM, TX_IBC_WASM, TX_INITIATE_RECOVERY_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL_WASM, TX_MINT_TOKENS_WASM,
+    TX_QUEUE_WITHDRAWAL_WASM, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
+    TX_REGISTER_TOKEN_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK_WASM,
+    TX_TRANSFER_FROM_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_GUARDIANS_WASM,
+    TX_UPDATE_SPENDING_POLICY_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_UPDATE_TOKEN_METADATA_WASM, TX_VOTE_PROPOSAL_WASM, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
 use namada_apps::wallet::defaults;
@@ -383,6 +397,7 @@ fn update_account(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::albert_keypair().ref_to()],
         threshold: None,
+        weights: None,
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
@@ -426,6 +441,7 @@ fn init_account(c: &mut Criterion) {
         public_keys: vec![new_account.to_public()],
         vp_code_hash: extra_hash,
         threshold: 1,
+        weights: None,
     };
     let tx = shell.generate_tx(
         TX_INIT_ACCOUNT_WASM,
@@ -1076,6 +1092,508 @@ fn claim_rewards(c: &mut Criterion) {
     group.finish();
 }
 
+/// Register Albert's account as a user-issued token minted by Albert
+fn register_user_token(shell: &mut BenchShell) {
+    namada::token::register_token(
+        &mut shell.wl_storage,
+        &defaults::albert_address(),
+        NATIVE_MAX_DECIMAL_PLACES.into(),
+        &defaults::albert_address(),
+        None,
+    )
+    .unwrap();
+    shell.wl_storage.commit_tx();
+}
+
+fn register_token(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_REGISTER_TOKEN_WASM,
+        RegisterToken {
+            token: defaults::albert_address(),
+            denom: NATIVE_MAX_DECIMAL_PLACES.into(),
+            minter: defaults::albert_address(),
+            max_supply: Some(Amount::native_whole(1_000_000)),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("register_token", |b| {
+        b.iter_batched_ref(
+            BenchShell::default,
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn mint_tokens(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_MINT_TOKENS_WASM,
+        MintTokens {
+            minter: defaults::albert_address(),
+            target: defaults::bertha_address(),
+            token: defaults::albert_address(),
+            amount: DenominatedAmount::native(Amount::native_whole(1000)),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("mint_tokens", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                register_user_token(&mut shell);
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn burn_tokens(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let amount = Amount::native_whole(1000);
+    let signed_tx = shell.generate_tx(
+        TX_BURN_TOKENS_WASM,
+        BurnTokens {
+            owner: defaults::bertha_address(),
+            token: defaults::albert_address(),
+            amount: DenominatedAmount::native(amount),
+        },
+        None,
+        None,
+        vec![&defaults::bertha_keypair(), &defaults::albert_keypair()],
+    );
+
+    c.bench_function("burn_tokens", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                register_user_token(&mut shell);
+                namada::token::credit_tokens(
+                    &mut shell.wl_storage,
+                    &defaults::albert_address(),
+                    &defaults::bertha_address(),
+                    amount,
+                )
+                .unwrap();
+                shell.wl_storage.commit_tx();
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn update_token_metadata(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_UPDATE_TOKEN_METADATA_WASM,
+        UpdateTokenMetadata {
+            token: defaults::albert_address(),
+            metadata: TokenMetadata {
+                symbol: "ALB".to_string(),
+                name: "Albert".to_string(),
+                denom: NATIVE_MAX_DECIMAL_PLACES.into(),
+                icon_url: Some("https://albert.example/icon.png".to_string()),
+                description: Some(
+                    "A user-issued token for benchmarking".to_string(),
+                ),
+                ibc_trace: None,
+            },
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("update_token_metadata", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                register_user_token(&mut shell);
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn approve(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_APPROVE_WASM,
+        Approve {
+            owner: defaults::albert_address(),
+            spender: defaults::bertha_address(),
+            token: shell.wl_storage.storage.native_token.clone(),
+            amount: DenominatedAmount::native(Amount::native_whole(1000)),
+            expiry: Some(Epoch(100)),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("approve", |b| {
+        b.iter_batched_ref(
+            BenchShell::default,
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn transfer_from(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let amount = Amount::native_whole(500);
+    let signed_tx = shell.generate_tx(
+        TX_TRANSFER_FROM_WASM,
+        TransferFrom {
+            spender: defaults::bertha_address(),
+            source: defaults::albert_address(),
+            target: defaults::christel_address(),
+            token: shell.wl_storage.storage.native_token.clone(),
+            amount: DenominatedAmount::native(amount),
+        },
+        None,
+        None,
+        vec![&defaults::bertha_keypair()],
+    );
+
+    c.bench_function("transfer_from", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                let native_token =
+                    shell.wl_storage.storage.native_token.clone();
+                namada::token::write_allowance(
+                    &mut shell.wl_storage,
+                    &native_token,
+                    &defaults::albert_address(),
+                    &defaults::bertha_address(),
+                    Allowance {
+                        amount: Amount::native_whole(1000),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+                // Limit the debits of the source so that they get recorded
+                namada::account::update_spending_policy(
+                    &mut shell.wl_storage,
+                    &defaults::albert_address(),
+                    Some(spending_policy(&native_token)),
+                )
+                .unwrap();
+                shell.wl_storage.commit_tx();
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+/// A spending policy limiting the debits of the given token from an account
+fn spending_policy(token: &Address) -> SpendingPolicy {
+    SpendingPolicy {
+        epoch_limits: BTreeMap::from([(
+            token.clone(),
+            Amount::native_whole(10_000),
+        )]),
+        delay_thresholds: BTreeMap::from([(
+            token.clone(),
+            Amount::native_whole(100),
+        )]),
+        delay: 2,
+        recovery_key: Some(defaults::bertha_keypair().ref_to()),
+    }
+}
+
+/// Set a spending policy on Albert's account and queue a withdrawal of the
+/// native token from it with id 0. Returns the delay of the policy.
+fn queue_albert_withdrawal(shell: &mut BenchShell) -> u64 {
+    let native_token = shell.wl_storage.storage.native_token.clone();
+    let policy = spending_policy(&native_token);
+    let delay = policy.delay;
+    namada::account::update_spending_policy(
+        &mut shell.wl_storage,
+        &defaults::albert_address(),
+        Some(policy),
+    )
+    .unwrap();
+    namada::account::queue_withdrawal(
+        &mut shell.wl_storage,
+        &defaults::albert_address(),
+        &native_token,
+        &defaults::bertha_address(),
+        Amount::native_whole(500),
+    )
+    .unwrap();
+    shell.wl_storage.commit_tx();
+    delay
+}
+
+fn update_spending_policy(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_UPDATE_SPENDING_POLICY_WASM,
+        UpdateSpendingPolicy {
+            addr: defaults::albert_address(),
+            policy: Some(spending_policy(
+                &shell.wl_storage.storage.native_token,
+            )),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("update_spending_policy", |b| {
+        b.iter_batched_ref(
+            BenchShell::default,
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn queue_withdrawal(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_QUEUE_WITHDRAWAL_WASM,
+        QueueWithdrawal {
+            source: defaults::albert_address(),
+            target: defaults::bertha_address(),
+            token: shell.wl_storage.storage.native_token.clone(),
+            amount: DenominatedAmount::native(Amount::native_whole(500)),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("queue_withdrawal", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                let native_token =
+                    shell.wl_storage.storage.native_token.clone();
+                namada::account::update_spending_policy(
+                    &mut shell.wl_storage,
+                    &defaults::albert_address(),
+                    Some(spending_policy(&native_token)),
+                )
+                .unwrap();
+                shell.wl_storage.commit_tx();
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn execute_withdrawal(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_EXECUTE_WITHDRAWAL_WASM,
+        QueuedWithdrawalAction {
+            source: defaults::albert_address(),
+            id: 0,
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("execute_withdrawal", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                let delay = queue_albert_withdrawal(&mut shell);
+                // Advance past the delay of the spending policy
+                for _ in 0..=delay {
+                    shell.advance_epoch();
+                }
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+fn cancel_withdrawal(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_CANCEL_WITHDRAWAL_WASM,
+        QueuedWithdrawalAction {
+            source: defaults::albert_address(),
+            id: 0,
+        },
+        None,
+        None,
+        vec![&defaults::bertha_keypair()],
+    );
+
+    c.bench_function("cancel_withdrawal", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                queue_albert_withdrawal(&mut shell);
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+/// A guardian set of Bertha and Christel for an account
+fn guardian_set() -> GuardianSet {
+    GuardianSet {
+        guardians: BTreeSet::from([
+            defaults::bertha_address(),
+            defaults::christel_address(),
+        ]),
+        threshold: 2,
+        delay: 2,
+    }
+}
+
+/// Set the guardians of Albert's account and initiate its recovery
+fn initiate_albert_recovery(shell: &mut BenchShell) {
+    namada::account::update_guardians(
+        &mut shell.wl_storage,
+        &defaults::albert_address(),
+        Some(guardian_set()),
+    )
+    .unwrap();
+    namada::account::initiate_recovery(
+        &mut shell.wl_storage,
+        &defaults::albert_address(),
+        vec![defaults::daewon_keypair().ref_to()],
+        1,
+    )
+    .unwrap();
+    shell.wl_storage.commit_tx();
+}
+
+fn update_guardians(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_UPDATE_GUARDIANS_WASM,
+        UpdateGuardians {
+            addr: defaults::albert_address(),
+            guardians: Some(guardian_set()),
+        },
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("update_guardians", |b| {
+        b.iter_batched_ref(
+            BenchShell::default,
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn initiate_recovery(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_INITIATE_RECOVERY_WASM,
+        InitiateRecovery {
+            addr: defaults::albert_address(),
+            public_keys: vec![defaults::daewon_keypair().ref_to()],
+            threshold: 1,
+        },
+        None,
+        None,
+        vec![&defaults::bertha_keypair(), &defaults::christel_keypair()],
+    );
+
+    c.bench_function("initiate_recovery", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                namada::account::update_guardians(
+                    &mut shell.wl_storage,
+                    &defaults::albert_address(),
+                    Some(guardian_set()),
+                )
+                .unwrap();
+                shell.wl_storage.commit_tx();
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn cancel_recovery(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_CANCEL_RECOVERY_WASM,
+        defaults::albert_address(),
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("cancel_recovery", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                initiate_albert_recovery(&mut shell);
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn finalize_recovery(c: &mut Criterion) {
+    let shell = BenchShell::default();
+    let signed_tx = shell.generate_tx(
+        TX_FINALIZE_RECOVERY_WASM,
+        defaults::albert_address(),
+        None,
+        None,
+        vec![&defaults::albert_keypair()],
+    );
+
+    c.bench_function("finalize_recovery", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut shell = BenchShell::default();
+                initiate_albert_recovery(&mut shell);
+                // Advance past the delay of the guardians
+                for _ in 0..=guardian_set().delay {
+                    shell.advance_epoch();
+                }
+                shell
+            },
+            |shell| shell.execute_tx(&signed_tx),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
 criterion_group!(
     allowed_txs,
     transfer,
@@ -1099,6 +1617,20 @@ criterion_group!(
     reactivate_validator,
     change_validator_metadata,
     claim_rewards,
-    change_consensus_key
+    change_consensus_key,
+    register_token,
+    mint_tokens,
+    burn_tokens,
+    update_token_metadata,
+    approve,
+    transfer_from,
+    update_spending_policy,
+    queue_withdrawal,
+    execute_withdrawal,
+    cancel_withdrawal,
+    update_guardians,
+    initiate_recovery,
+    cancel_recovery,
+    finalize_recovery
 );
 criterion_main!(allowed_txs);
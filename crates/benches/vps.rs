@@ -84,6 +84,7 @@ fn vp_user(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::albert_keypair().to_public()],
         threshold: None,
+        weights: None,
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
@@ -370,6 +371,7 @@ fn vp_validator(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::validator_account_keypair().to_public()],
         threshold: None,
+        weights: None,
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
@@ -2,7 +2,7 @@
 
 use std::collections::{BTreeMap, HashMap};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::key::{common, RefTo};
@@ -78,3 +78,92 @@ impl AccountPublicKeysMap {
             .collect()
     }
 }
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    Default,
+)]
+/// The weights of an account's public keys and the total weight of the
+/// signatures needed to authorize an action, replacing the signature count
+/// threshold
+pub struct AccountWeights {
+    /// Map from public key index to its weight. Keys without an entry have a
+    /// weight of 1.
+    pub weights: BTreeMap<u8, u8>,
+    /// The minimum total weight of valid signatures
+    pub threshold: u16,
+}
+
+impl AccountWeights {
+    /// Retrieve the weight of the public key at the index
+    pub fn weight_of(&self, index: u8) -> u16 {
+        self.weights.get(&index).copied().unwrap_or(1).into()
+    }
+
+    /// Sum the weights of the public keys at the given indices
+    pub fn total_weight<'a>(
+        &self,
+        indices: impl IntoIterator<Item = &'a u8>,
+    ) -> u16 {
+        indices
+            .into_iter()
+            .map(|index| self.weight_of(*index))
+            .fold(0, u16::saturating_add)
+    }
+
+    /// Check if these are the default weights, which remove the weighting of
+    /// an account instead of being set on it
+    pub fn is_removal(&self) -> bool {
+        self.weights.is_empty() && self.threshold == 0
+    }
+
+    /// Check that the weights can be set on an account with the given number
+    /// of public keys. There must be some weighted keys, which must exist and
+    /// have a positive weight, and the threshold must be positive and
+    /// reachable with the total weight of the keys.
+    pub fn validate(&self, num_public_keys: usize) -> Result<(), String> {
+        if self.threshold == 0 {
+            return Err("the weight threshold must be positive".to_string());
+        }
+        if self.weights.is_empty() {
+            return Err(format!(
+                "the weight threshold {} is set without any weights",
+                self.threshold
+            ));
+        }
+        if let Some(index) = self
+            .weights
+            .keys()
+            .find(|index| usize::from(**index) >= num_public_keys)
+        {
+            return Err(format!(
+                "the weighted key {} is not one of the {} public keys",
+                index, num_public_keys
+            ));
+        }
+        if let Some(index) = self
+            .weights
+            .iter()
+            .find_map(|(index, weight)| (*weight == 0).then_some(index))
+        {
+            return Err(format!("the weight of the key {} is zero", index));
+        }
+        let indices = (0..=u8::MAX).take(num_public_keys).collect::<Vec<_>>();
+        let total_weight = self.total_weight(&indices);
+        if total_weight < self.threshold {
+            return Err(format!(
+                "the weight threshold {} must be at most the total weight {}",
+                self.threshold, total_weight
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,636 @@
+//! BLS12-381 keys and related functionality
+//!
+//! Public keys live in G1 and signatures in G2. Signatures made by several
+//! keys over the same message can be aggregated into a single signature that
+//! is checked with a single pairing equation. To protect against rogue key
+//! attacks without requiring proofs of possession, every signature is
+//! weighted by a coefficient binding its public key to the whole set of
+//! signers before being aggregated.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+#[cfg(any(test, feature = "rand"))]
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{
+    ParsePublicKeyError, ParseSecretKeyError, ParseSignatureError, RefTo,
+    SchemeType, SigScheme as SigSchemeTrait, SignableBytes, VerifySigError,
+};
+use crate::types::key::StorageHasher;
+
+const PUBLIC_KEY_LENGTH: usize = 48;
+const SECRET_KEY_LENGTH: usize = 32;
+const SIGNATURE_LENGTH: usize = 96;
+
+/// Domain separation tag of the hash to G2
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+/// Domain separation tag of the aggregation coefficients
+const AGGREGATION_DST: &[u8] = b"NAMADA_BLS_AGGREGATION_COEFFICIENT";
+
+fn invalid_input(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+/// BLS12-381 public key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PublicKey(pub G1Affine);
+
+impl super::PublicKey for PublicKey {
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_pk<PK: super::PublicKey>(
+        pk: &PK,
+    ) -> Result<Self, ParsePublicKeyError> {
+        if PK::TYPE == super::common::PublicKey::TYPE {
+            super::common::PublicKey::try_from_pk(pk).and_then(|x| match x {
+                super::common::PublicKey::Bls(epk) => Ok(epk),
+                _ => Err(ParsePublicKeyError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParsePublicKeyError::InvalidEncoding)
+        } else {
+            Err(ParsePublicKeyError::MismatchedScheme)
+        }
+    }
+}
+
+impl BorshDeserialize for PublicKey {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let bytes: [u8; PUBLIC_KEY_LENGTH] =
+            BorshDeserialize::deserialize_reader(reader)?;
+        let point = Option::<G1Affine>::from(G1Affine::from_compressed(&bytes))
+            .ok_or_else(|| invalid_input("invalid BLS public key"))?;
+        // The identity would verify any signature of the identity
+        if bool::from(point.is_identity()) {
+            return Err(invalid_input("BLS public key cannot be the identity"));
+        }
+        Ok(PublicKey(point))
+    }
+}
+
+impl BorshSerialize for PublicKey {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0.to_compressed(), writer)
+    }
+}
+
+impl BorshSchema for PublicKey {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; PUBLIC_KEY_LENGTH]`
+        let elements = "u8".into();
+        let length = PUBLIC_KEY_LENGTH as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "bls::PublicKey".into()
+    }
+}
+
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_compressed().hash(state);
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_compressed().cmp(&other.0.to_compressed())
+    }
+}
+
+impl Display for PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0.to_compressed()))
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = ParsePublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = HEXLOWER
+            .decode(s.as_ref())
+            .map_err(ParsePublicKeyError::InvalidHex)?;
+        BorshDeserialize::try_from_slice(&vec)
+            .map_err(ParsePublicKeyError::InvalidEncoding)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// BLS12-381 secret key, stored as the canonical little-endian encoding of
+/// its scalar
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct SecretKey([u8; SECRET_KEY_LENGTH]);
+
+impl SecretKey {
+    fn scalar(&self) -> Scalar {
+        Option::<Scalar>::from(Scalar::from_bytes(&self.0))
+            .expect("BLS secret keys are always canonical scalars")
+    }
+
+    /// Map arbitrary bytes to a secret key, rejecting the zero scalar
+    fn from_seed(seed: &[u8]) -> Self {
+        let mut counter = 0u8;
+        loop {
+            let scalar =
+                hash_to_scalar(&[b"NAMADA_BLS_KEYGEN", seed, &[counter]]);
+            if scalar != Scalar::zero() {
+                return Self(scalar.to_bytes());
+            }
+            counter = counter.wrapping_add(1);
+        }
+    }
+}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SecretKey").field(&"<redacted>").finish()
+    }
+}
+
+impl super::SecretKey for SecretKey {
+    type PublicKey = PublicKey;
+
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_sk<PK: super::SecretKey>(
+        pk: &PK,
+    ) -> Result<Self, ParseSecretKeyError> {
+        if PK::TYPE == super::common::SecretKey::TYPE {
+            super::common::SecretKey::try_from_sk(pk).and_then(|x| match x {
+                super::common::SecretKey::Bls(epk) => Ok(epk),
+                _ => Err(ParseSecretKeyError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParseSecretKeyError::InvalidEncoding)
+        } else {
+            Err(ParseSecretKeyError::MismatchedScheme)
+        }
+    }
+}
+
+impl RefTo<PublicKey> for SecretKey {
+    fn ref_to(&self) -> PublicKey {
+        PublicKey((G1Affine::generator() * self.scalar()).into())
+    }
+}
+
+impl BorshDeserialize for SecretKey {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let bytes: [u8; SECRET_KEY_LENGTH] =
+            BorshDeserialize::deserialize_reader(reader)?;
+        let scalar = Option::<Scalar>::from(Scalar::from_bytes(&bytes))
+            .ok_or_else(|| invalid_input("invalid BLS secret key"))?;
+        if scalar == Scalar::zero() {
+            return Err(invalid_input("BLS secret key cannot be zero"));
+        }
+        Ok(SecretKey(bytes))
+    }
+}
+
+impl BorshSerialize for SecretKey {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+impl BorshSchema for SecretKey {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; SECRET_KEY_LENGTH]`
+        let elements = "u8".into();
+        let length = SECRET_KEY_LENGTH as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "bls::SecretKey".into()
+    }
+}
+
+impl Display for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0))
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = ParseSecretKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = HEXLOWER
+            .decode(s.as_ref())
+            .map_err(ParseSecretKeyError::InvalidHex)?;
+        BorshDeserialize::try_from_slice(&vec)
+            .map_err(ParseSecretKeyError::InvalidEncoding)
+    }
+}
+
+impl Serialize for SecretKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// BLS12-381 signature, possibly aggregated
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Signature(pub G2Affine);
+
+impl super::Signature for Signature {
+    const TYPE: SchemeType = SigScheme::TYPE;
+
+    fn try_from_sig<PK: super::Signature>(
+        pk: &PK,
+    ) -> Result<Self, ParseSignatureError> {
+        if PK::TYPE == super::common::Signature::TYPE {
+            super::common::Signature::try_from_sig(pk).and_then(|x| match x {
+                super::common::Signature::Bls(epk) => Ok(epk),
+                _ => Err(ParseSignatureError::MismatchedScheme),
+            })
+        } else if PK::TYPE == Self::TYPE {
+            Self::try_from_slice(pk.serialize_to_vec().as_slice())
+                .map_err(ParseSignatureError::InvalidEncoding)
+        } else {
+            Err(ParseSignatureError::MismatchedScheme)
+        }
+    }
+}
+
+impl BorshDeserialize for Signature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let bytes: [u8; SIGNATURE_LENGTH] =
+            BorshDeserialize::deserialize_reader(reader)?;
+        Option::<G2Affine>::from(G2Affine::from_compressed(&bytes))
+            .map(Signature)
+            .ok_or_else(|| invalid_input("invalid BLS signature"))
+    }
+}
+
+impl BorshSerialize for Signature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0.to_compressed(), writer)
+    }
+}
+
+impl BorshSchema for Signature {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        // Encoded as `[u8; SIGNATURE_LENGTH]`
+        let elements = "u8".into();
+        let length = SIGNATURE_LENGTH as u64;
+        let definition = borsh::schema::Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=length,
+            elements,
+        };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        "bls::Signature".into()
+    }
+}
+
+impl Hash for Signature {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_compressed().hash(state);
+    }
+}
+
+impl PartialOrd for Signature {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Signature {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_compressed().cmp(&other.0.to_compressed())
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.0.to_compressed()))
+    }
+}
+
+impl FromStr for Signature {
+    type Err = ParseSignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = HEXLOWER
+            .decode(s.as_ref())
+            .map_err(ParseSignatureError::InvalidHex)?;
+        BorshDeserialize::try_from_slice(&vec)
+            .map_err(ParseSignatureError::InvalidEncoding)
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Hash the signed data to a point of G2
+fn hash_to_g2<H>(data: &impl SignableBytes) -> G2Affine
+where
+    H: 'static + StorageHasher,
+{
+    <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+        data.signable_hash::<H>(),
+        SIGNATURE_DST,
+    )
+    .into()
+}
+
+/// The coefficient of a signer's contribution to an aggregate, binding its
+/// public key to the ordered set of all the signers' public keys
+fn aggregation_coefficient(pk: &PublicKey, signers: &[PublicKey]) -> Scalar {
+    let pk = pk.0.to_compressed();
+    let signers: Vec<_> =
+        signers.iter().map(|pk| pk.0.to_compressed()).collect();
+    let mut parts: Vec<&[u8]> = vec![AGGREGATION_DST, &pk];
+    parts.extend(signers.iter().map(|pk| pk.as_slice()));
+    hash_to_scalar(&parts)
+}
+
+/// Reduce the SHA-512 hash of the concatenated parts to a scalar
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_wide(&wide)
+}
+
+/// Aggregate the signatures of the given public keys over the same data into
+/// a single signature. The order of the signers must be the same as the one
+/// given to [`verify_aggregate_signature`].
+pub fn aggregate_signatures(
+    signatures: &[(PublicKey, Signature)],
+) -> Signature {
+    let signers: Vec<_> = signatures.iter().map(|(pk, _)| *pk).collect();
+    let aggregate = signatures.iter().fold(
+        G2Projective::identity(),
+        |acc, (pk, sig)| acc + sig.0 * aggregation_coefficient(pk, &signers),
+    );
+    Signature(aggregate.into())
+}
+
+/// Check that the aggregate signature was made by all the given public keys
+/// over the given data, using a single pairing equation.
+pub fn verify_aggregate_signature<H>(
+    signers: &[PublicKey],
+    data: &impl SignableBytes,
+    sig: &Signature,
+) -> Result<(), VerifySigError>
+where
+    H: 'static + StorageHasher,
+{
+    if signers.is_empty() {
+        return Err(VerifySigError::SigVerifyError(
+            "no signers to verify the aggregate signature against".to_string(),
+        ));
+    }
+    let aggregate_pk = signers.iter().fold(G1Projective::identity(), |acc, pk| {
+        acc + pk.0 * aggregation_coefficient(pk, signers)
+    });
+    verify_pairing::<H>(&aggregate_pk.into(), data, sig)
+}
+
+/// Check the pairing equation e(pk, H(m)) = e(g1, sig)
+fn verify_pairing<H>(
+    pk: &G1Affine,
+    data: &impl SignableBytes,
+    sig: &Signature,
+) -> Result<(), VerifySigError>
+where
+    H: 'static + StorageHasher,
+{
+    let lhs = bls12_381::pairing(pk, &hash_to_g2::<H>(data));
+    let rhs = bls12_381::pairing(&G1Affine::generator(), &sig.0);
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(VerifySigError::SigVerifyError(
+            "BLS signature verification failed".to_string(),
+        ))
+    }
+}
+
+/// An implementation of the BLS12-381 signature scheme
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Default,
+)]
+pub struct SigScheme;
+
+impl super::SigScheme for SigScheme {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Signature = Signature;
+
+    const TYPE: SchemeType = SchemeType::Bls;
+
+    #[cfg(any(test, feature = "rand"))]
+    fn generate<R>(csprng: &mut R) -> SecretKey
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut seed = [0u8; 32];
+        csprng.fill_bytes(&mut seed);
+        let sk = SecretKey::from_seed(&seed);
+        seed.zeroize();
+        sk
+    }
+
+    fn from_bytes(bytes: [u8; 32]) -> SecretKey {
+        SecretKey::from_seed(&bytes)
+    }
+
+    fn sign_with_hasher<H>(
+        keypair: &SecretKey,
+        data: impl SignableBytes,
+    ) -> Self::Signature
+    where
+        H: 'static + StorageHasher,
+    {
+        Signature((hash_to_g2::<H>(&data) * keypair.scalar()).into())
+    }
+
+    fn verify_signature_with_hasher<H>(
+        pk: &Self::PublicKey,
+        data: &impl SignableBytes,
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError>
+    where
+        H: 'static + StorageHasher,
+    {
+        verify_pairing::<H>(&pk.0, data, sig)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::types::hash::Sha256Hasher;
+
+    /// Check that aggregated signatures verify against the exact set of
+    /// signers, in order, and nothing else.
+    #[test]
+    fn test_aggregate_signatures() {
+        let keys: Vec<_> =
+            (0..3).map(|_| SigScheme::generate(&mut thread_rng())).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(RefTo::ref_to).collect();
+        let signatures: Vec<_> = keys
+            .iter()
+            .zip(&pks)
+            .map(|(sk, pk)| (*pk, SigScheme::sign(sk, b"hello")))
+            .collect();
+        let aggregate = aggregate_signatures(&signatures);
+        assert!(
+            verify_aggregate_signature::<Sha256Hasher>(
+                &pks, b"hello", &aggregate
+            )
+            .is_ok()
+        );
+        // A different message
+        assert!(
+            verify_aggregate_signature::<Sha256Hasher>(
+                &pks, b"world", &aggregate
+            )
+            .is_err()
+        );
+        // A subset of the signers
+        assert!(
+            verify_aggregate_signature::<Sha256Hasher>(
+                &pks[..2],
+                b"hello",
+                &aggregate
+            )
+            .is_err()
+        );
+        // The signers in a different order
+        let reordered = [pks[1], pks[0], pks[2]];
+        assert!(
+            verify_aggregate_signature::<Sha256Hasher>(
+                &reordered, b"hello", &aggregate
+            )
+            .is_err()
+        );
+        // A plain sum of signatures is not a valid aggregate
+        let sum = signatures
+            .iter()
+            .fold(G2Projective::identity(), |acc, (_, sig)| acc + sig.0);
+        assert!(
+            verify_aggregate_signature::<Sha256Hasher>(
+                &pks,
+                b"hello",
+                &Signature(sum.into())
+            )
+            .is_err()
+        );
+    }
+}
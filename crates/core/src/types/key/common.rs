@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use super::{
-    ed25519, secp256k1, ParsePublicKeyError, ParseSecretKeyError,
+    bls, ed25519, secp256k1, ParsePublicKeyError, ParseSecretKeyError,
     ParseSignatureError, RefTo, SchemeType, SigScheme as SigSchemeTrait,
     VerifySigError,
 };
@@ -40,10 +40,13 @@ pub enum PublicKey {
     Ed25519(ed25519::PublicKey),
     /// Encapsulate Secp256k1 public keys
     Secp256k1(secp256k1::PublicKey),
+    /// Encapsulate BLS12-381 public keys
+    Bls(bls::PublicKey),
 }
 
 const ED25519_PK_PREFIX: &str = "ED25519_PK_PREFIX";
 const SECP256K1_PK_PREFIX: &str = "SECP256K1_PK_PREFIX";
+const BLS_PK_PREFIX: &str = "BLS_PK_PREFIX";
 
 impl Serialize for PublicKey {
     fn serialize<S>(
@@ -57,6 +60,7 @@ impl Serialize for PublicKey {
         let prefix = match self {
             PublicKey::Ed25519(_) => ED25519_PK_PREFIX,
             PublicKey::Secp256k1(_) => SECP256K1_PK_PREFIX,
+            PublicKey::Bls(_) => BLS_PK_PREFIX,
         };
         let keypair_string = format!("{}{}", prefix, self);
         Serialize::serialize(&keypair_string, serializer)
@@ -79,6 +83,8 @@ impl<'de> Deserialize<'de> for PublicKey {
             keypair_string.strip_prefix(SECP256K1_PK_PREFIX)
         {
             PublicKey::from_str(raw).map_err(D::Error::custom)
+        } else if let Some(raw) = keypair_string.strip_prefix(BLS_PK_PREFIX) {
+            PublicKey::from_str(raw).map_err(D::Error::custom)
         } else {
             Err(D::Error::custom(
                 "Could not deserialize SecretKey do to invalid prefix",
@@ -110,6 +116,11 @@ impl super::PublicKey for PublicKey {
                 )
                 .map_err(ParsePublicKeyError::InvalidEncoding)?,
             ))
+        } else if PK::TYPE == bls::PublicKey::TYPE {
+            Ok(Self::Bls(
+                bls::PublicKey::try_from_slice(pk.serialize_to_vec().as_slice())
+                    .map_err(ParsePublicKeyError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParsePublicKeyError::MismatchedScheme)
         }
@@ -138,16 +149,20 @@ impl string_encoding::Format for PublicKey {
 
 impl_display_and_from_str_via_format!(PublicKey);
 
-impl From<PublicKey> for crate::tendermint::PublicKey {
-    fn from(value: PublicKey) -> Self {
+impl TryFrom<PublicKey> for crate::tendermint::PublicKey {
+    type Error = ParsePublicKeyError;
+
+    fn try_from(value: PublicKey) -> Result<Self, Self::Error> {
         use crate::tendermint::PublicKey as TmPK;
         match value {
             PublicKey::Ed25519(ed25519::PublicKey(pk)) => {
-                TmPK::from_raw_ed25519(pk.as_bytes()).unwrap()
+                Ok(TmPK::from_raw_ed25519(pk.as_bytes()).unwrap())
             }
             PublicKey::Secp256k1(secp256k1::PublicKey(pk)) => {
-                TmPK::from_raw_secp256k1(&pk.to_sec1_bytes()).unwrap()
+                Ok(TmPK::from_raw_secp256k1(&pk.to_sec1_bytes()).unwrap())
             }
+            // BLS keys cannot be used as consensus keys
+            PublicKey::Bls(_) => Err(ParsePublicKeyError::MismatchedScheme),
         }
     }
 }
@@ -157,6 +172,8 @@ impl From<PublicKey> for crate::tendermint::PublicKey {
 pub enum EthAddressConvError {
     #[error("Eth key cannot be ed25519, only secp256k1")]
     CannotBeEd25519,
+    #[error("Eth key cannot be BLS, only secp256k1")]
+    CannotBeBls,
 }
 
 impl TryFrom<&PublicKey> for EthAddress {
@@ -166,6 +183,7 @@ impl TryFrom<&PublicKey> for EthAddress {
         match value {
             PublicKey::Ed25519(_) => Err(EthAddressConvError::CannotBeEd25519),
             PublicKey::Secp256k1(pk) => Ok(EthAddress::from(pk)),
+            PublicKey::Bls(_) => Err(EthAddressConvError::CannotBeBls),
         }
     }
 }
@@ -178,6 +196,8 @@ pub enum SecretKey {
     Ed25519(ed25519::SecretKey),
     /// Encapsulate Secp256k1 secret keys
     Secp256k1(secp256k1::SecretKey),
+    /// Encapsulate BLS12-381 secret keys
+    Bls(bls::SecretKey),
 }
 
 impl Serialize for SecretKey {
@@ -192,6 +212,7 @@ impl Serialize for SecretKey {
         let prefix = match self {
             SecretKey::Ed25519(_) => "ED25519_SK_PREFIX",
             SecretKey::Secp256k1(_) => "SECP256K1_SK_PREFIX",
+            SecretKey::Bls(_) => "BLS_SK_PREFIX",
         };
         let keypair_string = format!("{}{}", prefix, self);
         Serialize::serialize(&keypair_string, serializer)
@@ -212,6 +233,9 @@ impl<'de> Deserialize<'de> for SecretKey {
             SecretKey::from_str(raw).map_err(D::Error::custom)
         } else if let Some(raw) =
             keypair_string.strip_prefix("SECP256K1_SK_PREFIX")
+        {
+            SecretKey::from_str(raw).map_err(D::Error::custom)
+        } else if let Some(raw) = keypair_string.strip_prefix("BLS_SK_PREFIX")
         {
             SecretKey::from_str(raw).map_err(D::Error::custom)
         } else {
@@ -254,6 +278,11 @@ impl super::SecretKey for SecretKey {
                 )
                 .map_err(ParseSecretKeyError::InvalidEncoding)?,
             ))
+        } else if SK::TYPE == bls::SecretKey::TYPE {
+            Ok(Self::Bls(
+                bls::SecretKey::try_from_slice(sk.serialize_to_vec().as_ref())
+                    .map_err(ParseSecretKeyError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParseSecretKeyError::MismatchedScheme)
         }
@@ -265,6 +294,7 @@ impl RefTo<PublicKey> for SecretKey {
         match self {
             SecretKey::Ed25519(sk) => PublicKey::Ed25519(sk.ref_to()),
             SecretKey::Secp256k1(sk) => PublicKey::Secp256k1(sk.ref_to()),
+            SecretKey::Bls(sk) => PublicKey::Bls(sk.ref_to()),
         }
     }
 }
@@ -307,6 +337,8 @@ pub enum Signature {
     Ed25519(ed25519::Signature),
     /// Encapsulate Secp256k1 signatures
     Secp256k1(secp256k1::Signature),
+    /// Encapsulate BLS12-381 signatures, possibly aggregated
+    Bls(bls::Signature),
 }
 
 impl string_encoding::Format for Signature {
@@ -340,6 +372,12 @@ impl From<secp256k1::Signature> for Signature {
     }
 }
 
+impl From<bls::Signature> for Signature {
+    fn from(sig: bls::Signature) -> Self {
+        Signature::Bls(sig)
+    }
+}
+
 impl super::Signature for Signature {
     const TYPE: SchemeType = SigScheme::TYPE;
 
@@ -363,6 +401,13 @@ impl super::Signature for Signature {
                 )
                 .map_err(ParseSignatureError::InvalidEncoding)?,
             ))
+        } else if SIG::TYPE == bls::Signature::TYPE {
+            Ok(Self::Bls(
+                bls::Signature::try_from_slice(
+                    sig.serialize_to_vec().as_slice(),
+                )
+                .map_err(ParseSignatureError::InvalidEncoding)?,
+            ))
         } else {
             Err(ParseSignatureError::MismatchedScheme)
         }
@@ -426,6 +471,9 @@ impl super::SigScheme for SigScheme {
             SecretKey::Secp256k1(kp) => Signature::Secp256k1(
                 secp256k1::SigScheme::sign_with_hasher::<H>(kp, data),
             ),
+            SecretKey::Bls(kp) => Signature::Bls(
+                bls::SigScheme::sign_with_hasher::<H>(kp, data),
+            ),
         }
     }
 
@@ -448,6 +496,9 @@ impl super::SigScheme for SigScheme {
                     pk, data, sig,
                 )
             }
+            (PublicKey::Bls(pk), Signature::Bls(sig)) => {
+                bls::SigScheme::verify_signature_with_hasher::<H>(pk, data, sig)
+            }
             _ => Err(VerifySigError::MismatchedScheme),
         }
     }
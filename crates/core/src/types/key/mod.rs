@@ -1,5 +1,6 @@
 //! Cryptographic keys
 
+pub mod bls;
 pub mod common;
 pub mod ed25519;
 pub mod secp256k1;
@@ -96,6 +97,9 @@ pub enum SchemeType {
     Ed25519,
     /// Type identifier for Secp256k1 scheme
     Secp256k1,
+    /// Type identifier for BLS12-381 scheme, whose signatures can be
+    /// aggregated
+    Bls,
     /// Type identifier for Common
     Common,
 }
@@ -107,6 +111,7 @@ impl FromStr for SchemeType {
         match input.to_lowercase().as_str() {
             "ed25519" => Ok(Self::Ed25519),
             "secp256k1" => Ok(Self::Secp256k1),
+            "bls" => Ok(Self::Bls),
             "common" => Ok(Self::Common),
             _ => Err(()),
         }
@@ -381,6 +386,7 @@ pub fn tm_consensus_key_raw_hash(pk: &common::PublicKey) -> String {
     let pkh = match pk {
         common::PublicKey::Ed25519(pk) => PublicKeyHash::from(pk),
         common::PublicKey::Secp256k1(pk) => PublicKeyHash::from(pk),
+        common::PublicKey::Bls(pk) => PublicKeyHash::from(pk),
     };
     pkh.to_string()
 }
@@ -646,6 +652,8 @@ macro_rules! sigscheme_test {
 sigscheme_test! {ed25519_test, ed25519::SigScheme}
 #[cfg(test)]
 sigscheme_test! {secp256k1_test, secp256k1::SigScheme}
+#[cfg(test)]
+sigscheme_test! {bls_test, bls::SigScheme}
 
 #[cfg(test)]
 mod more_tests {
@@ -369,6 +369,9 @@ pub enum TransferSource {
     Address(Address),
     /// A transfer coming from a shielded address
     ExtendedSpendingKey(ExtendedSpendingKey),
+    /// A transfer coming from a shielded address whose spends are signed by
+    /// an external MASP prover holding its spending key
+    ExtendedViewingKey(ExtendedViewingKey),
 }
 
 impl TransferSource {
@@ -376,9 +379,9 @@ impl TransferSource {
     pub fn effective_address(&self) -> Address {
         match self {
             Self::Address(x) => x.clone(),
-            // An ExtendedSpendingKey for a source effectively means that
-            // assets will be drawn from the MASP
-            Self::ExtendedSpendingKey(_) => MASP,
+            // An ExtendedSpendingKey or ExtendedViewingKey for a source
+            // effectively means that assets will be drawn from the MASP
+            Self::ExtendedSpendingKey(_) | Self::ExtendedViewingKey(_) => MASP,
         }
     }
 
@@ -390,6 +393,20 @@ impl TransferSource {
         }
     }
 
+    /// Get the viewing key of the shielded source, if any
+    pub fn viewing_key(&self) -> Option<ExtendedViewingKey> {
+        match self {
+            Self::ExtendedSpendingKey(x) => {
+                let sk = masp_primitives::zip32::ExtendedSpendingKey::from(*x);
+                Some(ExtendedViewingKey(
+                    masp_primitives::zip32::ExtendedFullViewingKey::from(&sk),
+                ))
+            }
+            Self::ExtendedViewingKey(x) => Some(*x),
+            _ => None,
+        }
+    }
+
     /// Get the contained Address, if any
     pub fn address(&self) -> Option<Address> {
         match self {
@@ -404,6 +421,7 @@ impl Display for TransferSource {
         match self {
             Self::Address(x) => x.fmt(f),
             Self::ExtendedSpendingKey(x) => x.fmt(f),
+            Self::ExtendedViewingKey(x) => x.fmt(f),
         }
     }
 }
@@ -1292,6 +1292,46 @@ pub struct PrefixValue {
     pub value: Vec<u8>,
 }
 
+/// The change of the value of a storage key committed in a block
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct StateChange {
+    /// Storage key
+    pub key: Key,
+    /// Raw value bytes before the block, `None` if the key had no value
+    pub old: Option<Vec<u8>>,
+    /// Raw value bytes after the block, `None` if the key was deleted
+    pub new: Option<Vec<u8>>,
+}
+
+/// The changes of the state committed in a block, ordered by storage key
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct StateDiff {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The changed keys
+    pub changes: Vec<StateChange>,
+}
+
 /// Container of all Ethereum event queues.
 #[derive(Default, Debug, BorshSerialize, BorshDeserialize)]
 pub struct EthEventsQueue {
@@ -1029,6 +1029,23 @@ pub struct MaspParams {
     pub locked_amount_target: u64,
 }
 
+impl MaspParams {
+    /// Check that the shielded rewards controller parameters are in their
+    /// valid ranges: the maximum reward rate and both the nominal gains must
+    /// lie in the interval `[0, 1]`.
+    pub fn is_valid(&self) -> bool {
+        [&self.max_reward_rate, &self.kp_gain_nom, &self.kd_gain_nom]
+            .into_iter()
+            .all(Self::is_valid_rate)
+    }
+
+    /// Check that the given shielded rewards rate or nominal gain lies in the
+    /// interval `[0, 1]`.
+    pub fn is_valid_rate(value: &Dec) -> bool {
+        !value.is_negative() && *value <= Dec::one()
+    }
+}
+
 impl Default for MaspParams {
     fn default() -> Self {
         Self {
@@ -1069,6 +1086,260 @@ pub struct Transfer {
     pub shielded: Option<Hash>,
 }
 
+/// The on-chain display information of a token
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct TokenMetadata {
+    /// The ticker symbol of the token
+    pub symbol: String,
+    /// The full name of the token
+    pub name: String,
+    /// The denomination of the token
+    pub denom: Denomination,
+    /// The URL of the token's icon
+    pub icon_url: Option<String>,
+    /// A description of the token
+    pub description: Option<String>,
+    /// The IBC denomination trace of an IBC token
+    pub ibc_trace: Option<String>,
+}
+
+impl TokenMetadata {
+    /// The maximum length of the token symbol
+    pub const MAX_SYMBOL_LEN: usize = 16;
+    /// The maximum length of the token name
+    pub const MAX_NAME_LEN: usize = 64;
+    /// The maximum length of the token description
+    pub const MAX_DESCRIPTION_LEN: usize = 1024;
+    /// The maximum length of the URL of the token's icon
+    pub const MAX_ICON_URL_LEN: usize = 256;
+    /// The maximum length of the IBC denomination trace
+    pub const MAX_IBC_TRACE_LEN: usize = 512;
+
+    /// Check that the symbol is a non-empty alphanumeric string of limited
+    /// length
+    pub fn has_valid_symbol(&self) -> bool {
+        !self.symbol.is_empty()
+            && self.symbol.len() <= Self::MAX_SYMBOL_LEN
+            && self.symbol.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    /// Check that the name, description, icon URL and IBC denomination trace
+    /// are within their maximum lengths
+    pub fn has_valid_lengths(&self) -> bool {
+        let is_within = |field: &Option<String>, max_len: usize| {
+            field.as_ref().map_or(true, |field| field.len() <= max_len)
+        };
+        self.name.len() <= Self::MAX_NAME_LEN
+            && is_within(&self.description, Self::MAX_DESCRIPTION_LEN)
+            && is_within(&self.icon_url, Self::MAX_ICON_URL_LEN)
+            && is_within(&self.ibc_trace, Self::MAX_IBC_TRACE_LEN)
+    }
+}
+
+/// Tx data to update the metadata of a token
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateTokenMetadata {
+    /// Token's address
+    pub token: Address,
+    /// The new metadata of the token
+    pub metadata: TokenMetadata,
+}
+
+/// Registration of a user-issued token
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct RegisterToken {
+    /// The established account issuing the token, whose address is used as
+    /// the token's address
+    pub token: Address,
+    /// The denomination of the token
+    pub denom: Denomination,
+    /// The address whose VP authorizes mints and burns of the token
+    pub minter: Address,
+    /// The maximum supply of the token, if it is capped
+    pub max_supply: Option<Amount>,
+}
+
+/// A mint of a user-issued token, authorized by its minter
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct MintTokens {
+    /// The minter of the token
+    pub minter: Address,
+    /// Target address will receive the minted tokens
+    pub target: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens to mint
+    pub amount: DenominatedAmount,
+}
+
+/// A burn of a user-issued token, authorized by its owner and its minter
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct BurnTokens {
+    /// Owner address will spend the burned tokens
+    pub owner: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens to burn
+    pub amount: DenominatedAmount,
+}
+
+/// An amount of tokens that a spender may transfer from an owner's account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct Allowance {
+    /// The remaining amount that may be spent
+    pub amount: Amount,
+    /// The epoch from which the allowance can no longer be spent, if any
+    pub expiry: Option<Epoch>,
+}
+
+impl Allowance {
+    /// Check if the allowance can no longer be spent in the given epoch
+    pub fn is_expired(&self, current_epoch: Epoch) -> bool {
+        self.expiry
+            .map(|expiry| current_epoch >= expiry)
+            .unwrap_or_default()
+    }
+
+    /// Return the amount spent if this allowance is replaced by the `post`
+    /// allowance. Only a decrease of the amount that keeps the expiry is a
+    /// spend, any other change is an update by the owner.
+    pub fn spent(&self, post: &Allowance) -> Option<Amount> {
+        if self.expiry == post.expiry && post.amount < self.amount {
+            self.amount.checked_sub(post.amount)
+        } else {
+            None
+        }
+    }
+}
+
+/// Tx data to set the allowance of a spender over an owner's tokens
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct Approve {
+    /// The owner of the tokens granting the allowance
+    pub owner: Address,
+    /// The address allowed to spend the tokens
+    pub spender: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount the spender may transfer. A zero amount revokes the
+    /// allowance.
+    pub amount: DenominatedAmount,
+    /// The epoch from which the allowance can no longer be spent, if any
+    pub expiry: Option<Epoch>,
+}
+
+/// A transfer from an owner's account submitted by a spender within its
+/// allowance
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+pub struct TransferFrom {
+    /// The address spending the allowance
+    pub spender: Address,
+    /// Source address whose tokens are spent
+    pub source: Address,
+    /// Target address will receive the tokens
+    pub target: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens
+    pub amount: DenominatedAmount,
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum AmountError {
@@ -1405,4 +1676,28 @@ mod tests {
             Ordering::Less
         );
     }
+
+    #[test]
+    fn test_masp_params_validity() {
+        assert!(MaspParams::default().is_valid());
+
+        let params = MaspParams {
+            max_reward_rate: Dec::one(),
+            kp_gain_nom: Dec::zero(),
+            ..Default::default()
+        };
+        assert!(params.is_valid());
+
+        let params = MaspParams {
+            max_reward_rate: Dec::from_str("1.01").unwrap(),
+            ..Default::default()
+        };
+        assert!(!params.is_valid());
+
+        let params = MaspParams {
+            kd_gain_nom: Dec::from_str("-0.25").unwrap(),
+            ..Default::default()
+        };
+        assert!(!params.is_valid());
+    }
 }
@@ -20,7 +20,16 @@ pub enum Error {
 }
 
 const COMPILE_GAS_PER_BYTE: u64 = 24;
-const PARALLEL_GAS_DIVIDER: u64 = 10;
+// NOTE: this accounts for scheduling a VP run on the thread pool and merging
+// its result with the others. Measured with the `vps_parallel_overhead` bench
+// as the largest difference per VP between the parallel and sequential runs,
+// reached with 2 VPs, relative to the `tx_section_signature_validation` bench
+const PARALLEL_VP_OVERHEAD_GAS: u64 = 574;
+// NOTE: the VPs of a transaction can only run in parallel on as many threads
+// as the node has, which is not the same for every node. The gas of the VPs
+// can hence only be discounted for the parallelism of this many threads, which
+// any validator node is expected to have
+const PARALLEL_VP_THREADS: u64 = 4;
 const WASM_CODE_VALIDATION_GAS_PER_BYTE: u64 = 1;
 const WRAPPER_TX_VALIDATION_GAS: u64 = 58_371;
 const STORAGE_OCCUPATION_GAS_PER_BYTE: u64 =
@@ -43,8 +52,22 @@ pub const STORAGE_ACCESS_GAS_PER_BYTE: u64 =
 /// The cost of writing data to storage, per byte
 pub const STORAGE_WRITE_GAS_PER_BYTE: u64 =
     MEMORY_ACCESS_GAS_PER_BYTE + 848 + STORAGE_OCCUPATION_GAS_PER_BYTE;
+/// The gas refunded for freeing a byte of storage previously occupied by the
+/// same account. Only half of the occupation cost is refunded so that writing
+/// and deleting data is never profitable
+pub const STORAGE_REFUND_GAS_PER_BYTE: u64 =
+    STORAGE_OCCUPATION_GAS_PER_BYTE / 2;
 /// The cost of verifying a single signature of a transaction
 pub const VERIFY_TX_SIG_GAS: u64 = 9_793;
+/// The cost of verifying a single BLS signature of a transaction, dominated by
+/// the two pairings of its verification equation. Measured with the
+/// `tx_section_bls_signature_validation` bench, relative to the
+/// `tx_section_signature_validation` one.
+pub const VERIFY_BLS_SIG_GAS: u64 = 713_420;
+/// The additional cost of verifying a BLS aggregate signature, per aggregated
+/// signer, to compute the aggregate public key. Measured with the
+/// `tx_section_aggregate_signature_validation` bench.
+pub const VERIFY_BLS_AGGREGATE_SIG_GAS_PER_SIGNER: u64 = 80_009;
 /// The cost for requesting one more page in wasm (64KiB)
 pub const WASM_MEMORY_PAGE_GAS: u32 =
     MEMORY_ACCESS_GAS_PER_BYTE as u32 * 64 * 1_024;
@@ -103,6 +126,12 @@ impl Gas {
         }
     }
 
+    /// Converts the sub gas units to whole ones, discarding the sub units
+    /// that don't make a whole unit
+    pub fn get_whole_gas_units_floor(&self) -> u64 {
+        self.sub / SCALE
+    }
+
     /// Generates a `Gas` instance from a whole amount
     pub fn from_whole_units(whole: u64) -> Self {
         Self { sub: whole * SCALE }
@@ -198,7 +227,10 @@ pub struct VpGasMeter {
     current_gas: Gas,
 }
 
-/// Gas meter for VPs parallel runs
+/// Gas meter for VPs parallel runs. The VPs run in parallel, so their cost is
+/// the one of the critical path, i.e. the most expensive VP, plus an overhead
+/// for every VP run. As there are only so many threads to run the VPs on,
+/// their cost is at least their total gas divided by the number of threads.
 #[derive(
     Clone,
     Debug,
@@ -211,7 +243,8 @@ pub struct VpGasMeter {
 )]
 pub struct VpsGas {
     max: Gas,
-    rest: Vec<Gas>,
+    sum: Gas,
+    num_vps: u64,
 }
 
 impl GasMetering for TxGasMeter {
@@ -332,11 +365,13 @@ impl VpsGas {
     /// instance which shouldn't be accessed passed this point.
     pub fn set(&mut self, vp_gas_meter: VpGasMeter) -> Result<()> {
         if vp_gas_meter.current_gas > self.max {
-            self.rest.push(self.max);
             self.max = vp_gas_meter.current_gas;
-        } else {
-            self.rest.push(vp_gas_meter.current_gas);
         }
+        self.sum = self
+            .sum
+            .checked_add(vp_gas_meter.current_gas)
+            .ok_or(Error::GasOverflow)?;
+        self.num_vps = self.num_vps.checked_add(1).ok_or(Error::GasOverflow)?;
 
         self.check_limit(&vp_gas_meter)
     }
@@ -345,16 +380,17 @@ impl VpsGas {
     /// the other `VpsGas` instance which shouldn't be used passed this point.
     pub fn merge(
         &mut self,
-        mut other: VpsGas,
+        other: VpsGas,
         tx_gas_meter: &TxGasMeter,
     ) -> Result<()> {
         if self.max < other.max {
-            self.rest.push(self.max);
             self.max = other.max;
-        } else {
-            self.rest.push(other.max);
         }
-        self.rest.append(&mut other.rest);
+        self.sum = self.sum.checked_add(other.sum).ok_or(Error::GasOverflow)?;
+        self.num_vps = self
+            .num_vps
+            .checked_add(other.num_vps)
+            .ok_or(Error::GasOverflow)?;
 
         self.check_limit(tx_gas_meter)
     }
@@ -374,11 +410,15 @@ impl VpsGas {
 
     /// Get the gas consumed by the parallelized VPs
     fn get_current_gas(&self) -> Result<Gas> {
-        let parallel_gas =
-            self.rest.iter().try_fold(Gas::default(), |acc, gas| {
-                acc.checked_add(*gas).ok_or(Error::GasOverflow)
-            })? / PARALLEL_GAS_DIVIDER;
-        self.max.checked_add(parallel_gas).ok_or(Error::GasOverflow)
+        let overhead = PARALLEL_VP_OVERHEAD_GAS
+            .checked_mul(self.num_vps)
+            .ok_or(Error::GasOverflow)?;
+        let threads = self.num_vps.clamp(1, PARALLEL_VP_THREADS);
+        let share = self.sum / threads;
+        let critical_path = if share > self.max { share } else { self.max };
+        critical_path
+            .checked_add(overhead.into())
+            .ok_or(Error::GasOverflow)
     }
 }
 
@@ -432,6 +472,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vps_gas_critical_path() {
+        let tx_gas_meter = TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into());
+        let vp_gas_meter = |gas: u64| {
+            let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
+            meter.consume(gas).expect("cannot add the gas");
+            meter
+        };
+
+        // The VPs run in two parallel batches merged together
+        let mut vps_gas = VpsGas::default();
+        vps_gas.set(vp_gas_meter(100)).unwrap();
+        vps_gas.set(vp_gas_meter(300)).unwrap();
+        let mut other = VpsGas::default();
+        other.set(vp_gas_meter(200)).unwrap();
+        vps_gas.merge(other, &tx_gas_meter).unwrap();
+        vps_gas.merge(VpsGas::default(), &tx_gas_meter).unwrap();
+
+        assert_eq!(
+            vps_gas.get_current_gas().unwrap(),
+            Gas::from(300 + 3 * PARALLEL_VP_OVERHEAD_GAS)
+        );
+    }
+
+    #[test]
+    fn test_vps_gas_capped_discount() {
+        let tx_gas_meter = TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into());
+        let vp_gas_meter = |gas: u64| {
+            let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
+            meter.consume(gas).expect("cannot add the gas");
+            meter
+        };
+
+        // More VPs than threads cannot all run in parallel, so their total
+        // gas is only divided by the number of threads
+        let num_vps = 2 * PARALLEL_VP_THREADS;
+        let mut vps_gas = VpsGas::default();
+        for _ in 0..num_vps {
+            vps_gas.set(vp_gas_meter(100)).unwrap();
+        }
+
+        assert_eq!(
+            vps_gas.get_current_gas().unwrap(),
+            Gas::from(
+                num_vps * 100 / PARALLEL_VP_THREADS
+                    + num_vps * PARALLEL_VP_OVERHEAD_GAS
+            )
+        );
+    }
+
     #[test]
     fn test_tx_gas_overflow() {
         let mut meter = TxGasMeter::new_from_sub_limit(BLOCK_GAS_LIMIT.into());
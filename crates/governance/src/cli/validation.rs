@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::types::address::Address;
 use namada_core::types::storage::Epoch;
@@ -6,6 +6,7 @@ use namada_core::types::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::proposal::ShieldedRewardsUpdate;
 
 /// This enum raprresent a proposal data
 #[derive(Clone, Debug, PartialEq, Error)]
@@ -69,6 +70,12 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The shielded rewards data is not valid
+    #[error(
+        "Invalid proposal extra data: cannot be empty, tokens must be unique \
+         and the maximum reward rate and gains must be between 0 and 1."
+    )]
+    InvalidShieldedRewardsExtraData,
 }
 
 pub fn is_valid_author_balance(
@@ -250,3 +257,25 @@ pub fn is_valid_pgf_funding_data(
         Err(ProposalValidation::InvalidPgfFundingExtraData)
     }
 }
+
+pub fn is_valid_shielded_rewards_data(
+    data: &[ShieldedRewardsUpdate],
+) -> Result<(), ProposalValidation> {
+    let unique_tokens = data
+        .iter()
+        .map(|update| &update.token)
+        .collect::<BTreeSet<&Address>>();
+    let are_updates_valid = data
+        .iter()
+        .all(|update| !update.alias.is_empty() && update.params.is_valid());
+
+    let is_valid = !data.is_empty()
+        && unique_tokens.len() == data.len()
+        && are_updates_valid;
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidShieldedRewardsExtraData)
+    }
+}
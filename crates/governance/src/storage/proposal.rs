@@ -6,6 +6,7 @@ use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::Epoch;
+use namada_core::types::token::MaspParams;
 use namada_trans_token::Amount;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -13,7 +14,7 @@ use thiserror::Error;
 use super::vote::ProposalVote;
 use crate::cli::onchain::{
     DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    PgfSteward, PgfStewardProposal, ShieldedRewardsProposal, StewardsUpdate,
 };
 use crate::utils::{ProposalStatus, TallyType};
 
@@ -157,6 +158,24 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
     }
 }
 
+impl TryFrom<ShieldedRewardsProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ShieldedRewardsProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ShieldedRewards(
+                value.data.into_iter().collect(),
+            ),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+        })
+    }
+}
+
 /// Storage struture for pgf fundings
 #[derive(
     Debug,
@@ -200,6 +219,33 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// Shielded rewards proposal, adding tokens to the shielded rewards or
+    /// updating their controller parameters
+    ShieldedRewards(BTreeSet<ShieldedRewardsUpdate>),
+}
+
+/// An update of the shielded rewards of a token
+#[derive(
+    Debug,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct ShieldedRewardsUpdate {
+    /// The alias of the token in the shielded rewards set, used only if the
+    /// token is not already earning shielded rewards
+    pub alias: String,
+    /// The token address
+    pub token: Address,
+    /// The new shielded rewards controller parameters
+    pub params: MaspParams,
 }
 
 /// An add or remove action for PGF
@@ -393,6 +439,7 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "Pgf steward"),
             ProposalType::PGFPayment(_) => write!(f, "Pgf funding"),
+            ProposalType::ShieldedRewards(_) => write!(f, "Shielded rewards"),
         }
     }
 }
@@ -566,6 +613,7 @@ impl Display for StorageProposal {
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
     use namada_core::types::address::testing::arb_non_internal_address;
+    use namada_core::types::dec::testing::arb_dec;
     use namada_core::types::hash::testing::arb_hash;
     use namada_core::types::storage::testing::arb_epoch;
     use namada_core::types::token::testing::arb_amount;
@@ -612,6 +660,29 @@ pub mod testing {
         ]
     }
 
+    prop_compose! {
+        /// Generate an arbitrary shielded rewards update
+        pub fn arb_shielded_rewards_update()(
+            alias in "[a-zA-Z0-9_]{1,16}",
+            token in arb_non_internal_address(),
+            max_reward_rate in arb_dec(),
+            kd_gain_nom in arb_dec(),
+            kp_gain_nom in arb_dec(),
+            locked_amount_target: u64,
+        ) -> ShieldedRewardsUpdate {
+            ShieldedRewardsUpdate {
+                alias,
+                token,
+                params: MaspParams {
+                    max_reward_rate,
+                    kd_gain_nom,
+                    kp_gain_nom,
+                    locked_amount_target,
+                },
+            }
+        }
+    }
+
     /// Generate an arbitrary proposal type
     pub fn arb_proposal_type() -> impl Strategy<Value = ProposalType> {
         prop_oneof![
@@ -623,6 +694,8 @@ pub mod testing {
             .prop_map(ProposalType::PGFSteward),
             collection::btree_set(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment),
+            collection::btree_set(arb_shielded_rewards_update(), 0..10)
+                .prop_map(ProposalType::ShieldedRewards),
         ]
     }
 
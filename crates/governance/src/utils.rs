@@ -85,6 +85,7 @@ impl TallyType {
             (ProposalType::PGFPayment(_), false) => {
                 TallyType::OneHalfOverOneThird
             }
+            (ProposalType::ShieldedRewards(_), _) => TallyType::TwoThirds,
         }
     }
 }
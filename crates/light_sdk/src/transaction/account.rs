@@ -1,3 +1,4 @@
+use namada_sdk::account::AccountWeights;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::{Signature, Tx, TxError};
 use namada_sdk::types::address::Address;
@@ -22,12 +23,14 @@ impl InitAccount {
         public_keys: Vec<common::PublicKey>,
         vp_code_hash: Hash,
         threshold: u8,
+        weights: Option<AccountWeights>,
         args: GlobalArgs,
     ) -> Self {
         let init_account = namada_sdk::account::InitAccount {
             public_keys,
             vp_code_hash,
             threshold,
+            weights,
         };
 
         Self(transaction::build_tx(
@@ -177,6 +180,7 @@ impl UpdateAccount {
         vp_code_hash: Option<Hash>,
         public_keys: Vec<common::PublicKey>,
         threshold: Option<u8>,
+        weights: Option<AccountWeights>,
         args: GlobalArgs,
     ) -> Self {
         let update_account = namada_sdk::account::UpdateAccount {
@@ -184,6 +188,7 @@ impl UpdateAccount {
             vp_code_hash,
             public_keys,
             threshold,
+            weights,
         };
 
         Self(transaction::build_tx(
@@ -12,8 +12,12 @@ use arse_merkle_tree::{
     Hash as SmtHash, Key as TreeKey, SparseMerkleTree as ArseMerkleTree, H256,
 };
 use eth_bridge_pool::{BridgePoolProof, BridgePoolTree};
+use ics23::batch_entry::Proof as Ics23BatchEntryProof;
 use ics23::commitment_proof::Proof as Ics23Proof;
-use ics23::{CommitmentProof, ExistenceProof, NonExistenceProof};
+use ics23::{
+    BatchEntry, BatchProof as Ics23BatchProof, CommitmentProof, ExistenceProof,
+    NonExistenceProof,
+};
 use ics23_specs::ibc_leaf_spec;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
 use namada_core::bytes::ByteBuf;
@@ -97,12 +101,16 @@ pub enum Error {
     StoreType(String),
     #[error("Non-existence proofs not supported for store type: {0}")]
     NonExistenceProof(String),
+    #[error("Batch proofs not supported for store type: {0}")]
+    BatchProof(String),
     #[error("Invalid value given to sub-tree storage")]
     InvalidValue,
     #[error("ICS23 commitment proofs do not support multiple leaves")]
     Ics23MultiLeaf,
     #[error("A Tendermint proof can only be constructed from an ICS23 proof.")]
     TendermintProof,
+    #[error("Invalid storage proof: {0}")]
+    InvalidProof(String),
 }
 
 /// Result for functions that may fail
@@ -147,6 +155,10 @@ pub enum StoreType {
     PoS,
     /// For the Ethereum bridge Pool transfers
     BridgePool,
+    /// For token balances and other token data
+    Token,
+    /// For governance and PGF data
+    Governance,
 }
 
 /// Backing storage for merkle trees
@@ -161,6 +173,10 @@ pub enum Store {
     PoS(SmtStore),
     /// For the Ethereum bridge Pool transfers
     BridgePool(BridgePoolStore),
+    /// For token balances and other token data
+    Token(SmtStore),
+    /// For governance and PGF data
+    Governance(SmtStore),
 }
 
 impl Store {
@@ -172,6 +188,8 @@ impl Store {
             Self::Ibc(store) => StoreRef::Ibc(store),
             Self::PoS(store) => StoreRef::PoS(store),
             Self::BridgePool(store) => StoreRef::BridgePool(store),
+            Self::Token(store) => StoreRef::Token(store),
+            Self::Governance(store) => StoreRef::Governance(store),
         }
     }
 }
@@ -188,6 +206,10 @@ pub enum StoreRef<'a> {
     PoS(&'a SmtStore),
     /// For the Ethereum bridge Pool transfers
     BridgePool(&'a BridgePoolStore),
+    /// For token balances and other token data
+    Token(&'a SmtStore),
+    /// For governance and PGF data
+    Governance(&'a SmtStore),
 }
 
 impl<'a> StoreRef<'a> {
@@ -199,6 +221,8 @@ impl<'a> StoreRef<'a> {
             Self::Ibc(store) => Store::Ibc(store.to_owned()),
             Self::PoS(store) => Store::PoS(store.to_owned()),
             Self::BridgePool(store) => Store::BridgePool(store.to_owned()),
+            Self::Token(store) => Store::Token(store.to_owned()),
+            Self::Governance(store) => Store::Governance(store.to_owned()),
         }
     }
 
@@ -210,6 +234,8 @@ impl<'a> StoreRef<'a> {
             Self::Ibc(store) => store.serialize_to_vec(),
             Self::PoS(store) => store.serialize_to_vec(),
             Self::BridgePool(store) => store.serialize_to_vec(),
+            Self::Token(store) => store.serialize_to_vec(),
+            Self::Governance(store) => store.serialize_to_vec(),
         }
     }
 }
@@ -217,23 +243,27 @@ impl<'a> StoreRef<'a> {
 impl StoreType {
     /// Get an iterator for the base tree and subtrees
     pub fn iter() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 5] = [
+        static SUB_TREE_TYPES: [StoreType; 7] = [
             StoreType::Base,
             StoreType::Account,
             StoreType::PoS,
             StoreType::Ibc,
             StoreType::BridgePool,
+            StoreType::Token,
+            StoreType::Governance,
         ];
         SUB_TREE_TYPES.iter()
     }
 
     /// Get an iterator for subtrees
     pub fn iter_subtrees() -> std::slice::Iter<'static, Self> {
-        static SUB_TREE_TYPES: [StoreType; 4] = [
+        static SUB_TREE_TYPES: [StoreType; 6] = [
             StoreType::Account,
             StoreType::PoS,
             StoreType::Ibc,
             StoreType::BridgePool,
+            StoreType::Token,
+            StoreType::Governance,
         ];
         SUB_TREE_TYPES.iter()
     }
@@ -247,11 +277,45 @@ impl StoreType {
 
     /// Get an iterator for the non-provable subtrees
     pub fn iter_non_provable() -> std::slice::Iter<'static, Self> {
+        static SUB_TREE_TYPES: [StoreType; 4] = [
+            StoreType::Account,
+            StoreType::PoS,
+            StoreType::Token,
+            StoreType::Governance,
+        ];
+        SUB_TREE_TYPES.iter()
+    }
+
+    /// Get an iterator for the subtrees split from the account subtree. The
+    /// trees committed before the split have their data in the account
+    /// subtree.
+    pub fn iter_split_from_account() -> std::slice::Iter<'static, Self> {
         static SUB_TREE_TYPES: [StoreType; 2] =
-            [StoreType::Account, StoreType::PoS];
+            [StoreType::Token, StoreType::Governance];
         SUB_TREE_TYPES.iter()
     }
 
+    /// Check if this is a subtree split from the account subtree
+    pub fn is_split_from_account(&self) -> bool {
+        matches!(self, StoreType::Token | StoreType::Governance)
+    }
+
+    /// Get the key prefixes of the data in a subtree split from the account
+    /// subtree. Returns an empty list for the other store types.
+    pub fn split_prefixes(&self) -> Vec<Key> {
+        let addrs: &[InternalAddress] = match self {
+            Self::Token => &[InternalAddress::Multitoken],
+            Self::Governance => {
+                &[InternalAddress::Governance, InternalAddress::Pgf]
+            }
+            _ => &[],
+        };
+        addrs
+            .iter()
+            .map(|addr| Address::Internal(addr.clone()).to_db_key().into())
+            .collect()
+    }
+
     /// Get the store type and the sub key
     pub fn sub_key(key: &Key) -> Result<(Self, Key)> {
         if key.is_empty() {
@@ -275,6 +339,14 @@ impl StoreType {
                             Ok((StoreType::Account, key.clone()))
                         }
                     }
+                    // use the same key as in the account subtree from
+                    // which these subtrees were split
+                    InternalAddress::Multitoken => {
+                        Ok((StoreType::Token, key.clone()))
+                    }
+                    InternalAddress::Governance | InternalAddress::Pgf => {
+                        Ok((StoreType::Governance, key.clone()))
+                    }
                     // use the same key for Parameters
                     _ => Ok((StoreType::Account, key.clone())),
                 }
@@ -284,6 +356,17 @@ impl StoreType {
         }
     }
 
+    /// Get the store type and the sub key in a tree committed before the
+    /// token and governance subtrees were split from the account subtree
+    pub fn legacy_sub_key(key: &Key) -> Result<(Self, Key)> {
+        let (store_type, sub_key) = Self::sub_key(key)?;
+        if store_type.is_split_from_account() {
+            Ok((StoreType::Account, sub_key))
+        } else {
+            Ok((store_type, sub_key))
+        }
+    }
+
     /// Get the key prefix if the store type is for a provable subtree.
     /// Otherwise, returns None.
     pub fn provable_prefix(&self) -> Option<Key> {
@@ -308,6 +391,8 @@ impl StoreType {
             Self::Ibc => Ok(Store::Ibc(types::decode(bytes)?)),
             Self::PoS => Ok(Store::PoS(types::decode(bytes)?)),
             Self::BridgePool => Ok(Store::BridgePool(types::decode(bytes)?)),
+            Self::Token => Ok(Store::Token(types::decode(bytes)?)),
+            Self::Governance => Ok(Store::Governance(types::decode(bytes)?)),
         }
     }
 }
@@ -322,6 +407,8 @@ impl FromStr for StoreType {
             "ibc" => Ok(StoreType::Ibc),
             "pos" => Ok(StoreType::PoS),
             "eth_bridge_pool" => Ok(StoreType::BridgePool),
+            "token" => Ok(StoreType::Token),
+            "governance" => Ok(StoreType::Governance),
             _ => Err(Error::StoreType(s.to_string())),
         }
     }
@@ -335,6 +422,8 @@ impl fmt::Display for StoreType {
             StoreType::Ibc => write!(f, "ibc"),
             StoreType::PoS => write!(f, "pos"),
             StoreType::BridgePool => write!(f, "eth_bridge_pool"),
+            StoreType::Token => write!(f, "token"),
+            StoreType::Governance => write!(f, "governance"),
         }
     }
 }
@@ -355,6 +444,54 @@ pub fn subtree_key_prefix(st: &StoreType, epoch: Epoch) -> Key {
         .with_segment(st.to_string())
 }
 
+/// The layout of the merkle tree. It's stored with the state, so that the
+/// trees of all the heights are rebuilt with their own layout.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct MerkleTreeLayout {
+    /// The height from which the token and governance data are in their own
+    /// subtrees. It's `None` while they are in the account subtree, as on the
+    /// chains started before these subtrees were split.
+    pub subtrees_split_height: Option<BlockHeight>,
+    /// The height from which the MASP note commitment tree and nullifiers
+    /// are merklized, if they are
+    pub masp_merklization_height: Option<BlockHeight>,
+}
+
+impl Default for MerkleTreeLayout {
+    /// The new chains are started with the latest layout
+    fn default() -> Self {
+        Self {
+            subtrees_split_height: Some(BlockHeight::default()),
+            masp_merklization_height: Some(BlockHeight::default()),
+        }
+    }
+}
+
+impl MerkleTreeLayout {
+    /// The layout of the chains started before the layout was stored
+    pub const LEGACY: Self = Self {
+        subtrees_split_height: None,
+        masp_merklization_height: None,
+    };
+
+    /// Check if the tree committed at the given height has the token and
+    /// governance data in the account subtree
+    pub fn is_legacy_at(&self, height: BlockHeight) -> bool {
+        !matches!(self.subtrees_split_height, Some(split) if split <= height)
+    }
+
+    /// Check if the MASP note commitment tree and nullifiers are merklized
+    /// in the tree committed at the given height
+    pub fn is_masp_merklized_at(&self, height: BlockHeight) -> bool {
+        matches!(
+            self.masp_merklization_height,
+            Some(merklization) if merklization <= height
+        )
+    }
+}
+
 /// Merkle tree storage
 #[derive(Default)]
 pub struct MerkleTree<H: StorageHasher + Default> {
@@ -363,6 +500,11 @@ pub struct MerkleTree<H: StorageHasher + Default> {
     ibc: Amt<H>,
     pos: Smt<H>,
     bridge_pool: BridgePoolTree,
+    token: Smt<H>,
+    governance: Smt<H>,
+    /// Whether the token and governance data are still in the account
+    /// subtree, as in the trees committed before these subtrees were split
+    legacy_layout: bool,
 }
 
 impl<H: StorageHasher + Default> core::fmt::Debug for MerkleTree<H> {
@@ -383,12 +525,18 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         let pos = Smt::new(stores.pos.0.into(), stores.pos.1);
         let bridge_pool =
             BridgePoolTree::new(stores.bridge_pool.0, stores.bridge_pool.1);
+        let token = Smt::new(stores.token.0.into(), stores.token.1);
+        let governance =
+            Smt::new(stores.governance.0.into(), stores.governance.1);
         let tree = Self {
             base,
             account,
             ibc,
             pos,
             bridge_pool,
+            token,
+            governance,
+            legacy_layout: false,
         };
 
         // validate
@@ -400,15 +548,23 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         let pos_root = tree.base.get(&pos_key.into())?;
         let bp_key = H::hash(StoreType::BridgePool.to_string());
         let bp_root = tree.base.get(&bp_key.into())?;
+        let token_key = H::hash(StoreType::Token.to_string());
+        let token_root = tree.base.get(&token_key.into())?;
+        let gov_key = H::hash(StoreType::Governance.to_string());
+        let gov_root = tree.base.get(&gov_key.into())?;
         if tree.base.root().is_zero()
             && tree.account.root().is_zero()
             && tree.ibc.root().is_zero()
             && tree.pos.root().is_zero()
             && tree.bridge_pool.root().is_zero()
+            && tree.token.root().is_zero()
+            && tree.governance.root().is_zero()
             || (account_root == tree.account.root().into()
                 && ibc_root == tree.ibc.root().into()
                 && pos_root == tree.pos.root().into()
-                && bp_root == tree.bridge_pool.root().into())
+                && bp_root == tree.bridge_pool.root().into()
+                && token_root == tree.token.root().into()
+                && gov_root == tree.governance.root().into())
         {
             Ok(tree)
         } else {
@@ -426,15 +582,88 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         let pos = Smt::new(stores.pos.0.into(), stores.pos.1);
         let bridge_pool =
             BridgePoolTree::new(stores.bridge_pool.0, stores.bridge_pool.1);
+        let token = Smt::new(stores.token.0.into(), stores.token.1);
+        let governance =
+            Smt::new(stores.governance.0.into(), stores.governance.1);
         Self {
             base,
             account,
             ibc,
             pos,
             bridge_pool,
+            token,
+            governance,
+            legacy_layout: false,
         }
     }
 
+    /// Set whether the token and governance data are still in the account
+    /// subtree. The layout isn't part of the stores, so it has to be set on
+    /// the trees restored from the stores committed before the split.
+    pub fn with_legacy_layout(mut self, legacy_layout: bool) -> Self {
+        self.legacy_layout = legacy_layout;
+        self
+    }
+
+    /// Check if the token and governance data are still in the account
+    /// subtree, as in the trees committed before these subtrees were split
+    pub fn is_legacy_layout(&self) -> bool {
+        self.legacy_layout
+    }
+
+    /// Get the store type of the given key in this tree
+    pub fn store_type(&self, key: &Key) -> Result<StoreType> {
+        self.sub_key(key).map(|(store_type, _)| store_type)
+    }
+
+    /// Get the store type and the sub key in this tree
+    fn sub_key(&self, key: &Key) -> Result<(StoreType, Key)> {
+        if self.legacy_layout {
+            StoreType::legacy_sub_key(key)
+        } else {
+            StoreType::sub_key(key)
+        }
+    }
+
+    /// Move the token and governance data of the given keys from the account
+    /// subtree into their own subtrees. The other keys and the keys that
+    /// aren't in the account subtree are ignored. Afterwards, the keys are
+    /// routed to the new subtrees.
+    pub fn split_account_subtree(
+        &mut self,
+        keys: impl IntoIterator<Item = Key>,
+    ) -> Result<()> {
+        for key in keys {
+            let (store_type, sub_key) = StoreType::sub_key(&key)?;
+            if !store_type.is_split_from_account() {
+                continue;
+            }
+            // The subtrees hash their keys the same way, so the leaves are
+            // moved without their values
+            let tree_key = H::hash(sub_key.to_string());
+            let value = self.account.get(&tree_key.into())?;
+            if value.is_zero() {
+                continue;
+            }
+            self.account.update(tree_key.into(), Hash::zero())?;
+            let sub_tree = match store_type {
+                StoreType::Token => &mut self.token,
+                _ => &mut self.governance,
+            };
+            sub_tree.update(tree_key.into(), value)?;
+        }
+        for store_type in [StoreType::Account]
+            .iter()
+            .chain(StoreType::iter_split_from_account())
+        {
+            let sub_root: Hash = self.tree(store_type).root().into();
+            let base_key = H::hash(store_type.to_string());
+            self.base.update(base_key.into(), sub_root)?;
+        }
+        self.legacy_layout = false;
+        Ok(())
+    }
+
     fn tree(&self, store_type: &StoreType) -> Box<dyn SubTreeRead + '_> {
         match store_type {
             StoreType::Base => Box::new(&self.base),
@@ -442,6 +671,8 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
             StoreType::Ibc => Box::new(&self.ibc),
             StoreType::PoS => Box::new(&self.pos),
             StoreType::BridgePool => Box::new(&self.bridge_pool),
+            StoreType::Token => Box::new(&self.token),
+            StoreType::Governance => Box::new(&self.governance),
         }
     }
 
@@ -455,6 +686,8 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
             StoreType::Ibc => Box::new(&mut self.ibc),
             StoreType::PoS => Box::new(&mut self.pos),
             StoreType::BridgePool => Box::new(&mut self.bridge_pool),
+            StoreType::Token => Box::new(&mut self.token),
+            StoreType::Governance => Box::new(&mut self.governance),
         }
     }
 
@@ -477,25 +710,25 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
 
     /// Check if the key exists in the tree
     pub fn has_key(&self, key: &Key) -> Result<bool> {
-        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        let (store_type, sub_key) = self.sub_key(key)?;
         self.tree(&store_type).subtree_has_key(&sub_key)
     }
 
     /// Get the value in the tree
     pub fn get(&self, key: &Key) -> Result<Vec<u8>> {
-        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        let (store_type, sub_key) = self.sub_key(key)?;
         self.tree(&store_type).subtree_get(&sub_key)
     }
 
     /// Update the tree with the given key and value
     pub fn update(&mut self, key: &Key, value: impl AsRef<[u8]>) -> Result<()> {
-        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        let (store_type, sub_key) = self.sub_key(key)?;
         self.update_tree(&store_type, &sub_key, value)
     }
 
     /// Delete the value corresponding to the given key
     pub fn delete(&mut self, key: &Key) -> Result<()> {
-        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        let (store_type, sub_key) = self.sub_key(key)?;
         let sub_root = self.tree_mut(&store_type).subtree_delete(&sub_key)?;
         if store_type != StoreType::Base {
             let base_key = H::hash(store_type.to_string());
@@ -525,6 +758,11 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
                 self.bridge_pool.root().into(),
                 self.bridge_pool.store(),
             ),
+            token: (self.token.root().into(), self.token.store()),
+            governance: (
+                self.governance.root().into(),
+                self.governance.store(),
+            ),
         }
     }
 
@@ -539,9 +777,9 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
                 "No keys provided for existence proof.".into(),
             )
         })?;
-        let (store_type, sub_key) = StoreType::sub_key(first_key)?;
+        let (store_type, sub_key) = self.sub_key(first_key)?;
         if !keys.iter().all(|k| {
-            if let Ok((s, _)) = StoreType::sub_key(k) {
+            if let Ok((s, _)) = self.sub_key(k) {
                 s == store_type
             } else {
                 false
@@ -559,14 +797,42 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
 
     /// Get the non-existence proof
     pub fn get_non_existence_proof(&self, key: &Key) -> Result<Proof> {
-        let (store_type, sub_key) = StoreType::sub_key(key)?;
-        if store_type != StoreType::Ibc {
-            return Err(Error::NonExistenceProof(store_type.to_string()));
-        }
+        let (store_type, sub_key) = self.sub_key(key)?;
+        let nep =
+            self.get_sub_tree_non_existence_proof(&store_type, &sub_key)?;
 
-        let string_key =
-            StringKey::try_from_bytes(sub_key.to_string().as_bytes())?;
-        let mut nep = self.ibc.non_membership_proof(&string_key)?;
+        // Get a proof of the sub tree
+        self.get_sub_tree_proof(key, nep)
+    }
+
+    /// Get the non-existence proof of a key in its sub-tree. Except in the
+    /// IBC sub-tree, the keys are hashed, so the neighbours of the key in the
+    /// proof are given with their hashed keys and values.
+    fn get_sub_tree_non_existence_proof(
+        &self,
+        store_type: &StoreType,
+        sub_key: &Key,
+    ) -> Result<CommitmentProof> {
+        let mut nep = match store_type {
+            StoreType::Ibc => {
+                let string_key =
+                    StringKey::try_from_bytes(sub_key.to_string().as_bytes())?;
+                self.ibc.non_membership_proof(&string_key)?
+            }
+            StoreType::Account => self
+                .account
+                .non_membership_proof(&H::hash(sub_key.to_string()).into())?,
+            StoreType::PoS => self
+                .pos
+                .non_membership_proof(&H::hash(sub_key.to_string()).into())?,
+            StoreType::Token => self
+                .token
+                .non_membership_proof(&H::hash(sub_key.to_string()).into())?,
+            StoreType::Governance => self
+                .governance
+                .non_membership_proof(&H::hash(sub_key.to_string()).into())?,
+            _ => return Err(Error::NonExistenceProof(store_type.to_string())),
+        };
         // Replace the values and the leaf op for the verification
         if let Some(ref mut nep) = nep.proof {
             match nep {
@@ -586,9 +852,94 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
                 _ => unreachable!(),
             }
         }
+        Ok(nep)
+    }
 
-        // Get a proof of the sub tree
-        self.get_sub_tree_proof(key, nep)
+    /// Get a batch proof of the values of the given keys, or of their absence
+    /// for the keys without a value. The keys can be in different sub-trees,
+    /// except for the bridge pool one whose proofs are not ICS23 compliant.
+    pub fn get_batch_proof(
+        &self,
+        entries: &[(Key, Option<StorageBytes>)],
+    ) -> Result<BatchProof> {
+        let mut sub_tree_proofs = vec![];
+        for store_type in StoreType::iter_subtrees() {
+            let mut sub_tree_entries = vec![];
+            for (key, value) in entries {
+                if self.sub_key(key)?.0 == *store_type {
+                    sub_tree_entries.push((key.clone(), *value));
+                }
+            }
+            if !sub_tree_entries.is_empty() {
+                sub_tree_proofs.push(
+                    self.get_sub_tree_batch_proof(
+                        store_type,
+                        &sub_tree_entries,
+                    )?,
+                );
+            }
+        }
+        Ok(BatchProof { sub_tree_proofs })
+    }
+
+    /// Get a batch proof of the values of the given keys in a sub-tree, or
+    /// of their absence for the keys without a value
+    pub fn get_sub_tree_batch_proof(
+        &self,
+        store_type: &StoreType,
+        entries: &[(Key, Option<StorageBytes>)],
+    ) -> Result<SubTreeBatchProof> {
+        if matches!(store_type, StoreType::Base | StoreType::BridgePool) {
+            return Err(Error::BatchProof(store_type.to_string()));
+        }
+        let mut batch_entries = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            let (key_store_type, sub_key) = self.sub_key(key)?;
+            if key_store_type != *store_type {
+                return Err(Error::InvalidMerkleKey(format!(
+                    "The key {key} is not in the {store_type} sub-tree"
+                )));
+            }
+            let proof = match value {
+                Some(value) => match self
+                    .tree(store_type)
+                    .subtree_membership_proof(
+                        std::array::from_ref(&sub_key),
+                        vec![*value],
+                    )? {
+                    MembershipProof::ICS23(proof) => proof,
+                    MembershipProof::BridgePool(_) => {
+                        return Err(Error::BatchProof(store_type.to_string()));
+                    }
+                },
+                None => {
+                    self.get_sub_tree_non_existence_proof(store_type, &sub_key)?
+                }
+            };
+            let proof = match proof.proof {
+                Some(Ics23Proof::Exist(ep)) => Ics23BatchEntryProof::Exist(ep),
+                Some(Ics23Proof::Nonexist(nep)) => {
+                    Ics23BatchEntryProof::Nonexist(nep)
+                }
+                // the sub-tree proofs are single existence or non-existence
+                // proofs
+                _ => unreachable!(),
+            };
+            batch_entries.push(BatchEntry { proof: Some(proof) });
+        }
+        let sub_proof = CommitmentProof {
+            proof: Some(Ics23Proof::Batch(Ics23BatchProof {
+                entries: batch_entries,
+            })),
+        };
+        // The paths shared by the keys are only kept once
+        let sub_proof = ics23::compress(&sub_proof)
+            .map_err(|e| Error::MerkleTree(e.to_string()))?;
+        Ok(SubTreeBatchProof {
+            store_type: *store_type,
+            sub_proof,
+            base_proof: self.get_base_proof(store_type)?,
+        })
     }
 
     /// Get the Tendermint proof with the base proof
@@ -597,29 +948,37 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         key: &Key,
         sub_proof: CommitmentProof,
     ) -> Result<Proof> {
+        let (store_type, _) = self.sub_key(key)?;
+        let base_proof = self.get_base_proof(&store_type)?;
+
+        Ok(Proof {
+            key: key.clone(),
+            sub_proof,
+            base_proof,
+        })
+    }
+
+    /// Get the membership proof of the root of a sub-tree in the base tree
+    fn get_base_proof(
+        &self,
+        store_type: &StoreType,
+    ) -> Result<CommitmentProof> {
         // Get a membership proof of the base tree because the sub root should
         // exist
-        let (store_type, _) = StoreType::sub_key(key)?;
         let base_key = store_type.to_string();
         let cp = self.base.membership_proof(&H::hash(&base_key).into())?;
         // Replace the values and the leaf op for the verification
-        let base_proof = match cp.proof.expect("The proof should exist") {
-            Ics23Proof::Exist(ep) => CommitmentProof {
+        match cp.proof.expect("The proof should exist") {
+            Ics23Proof::Exist(ep) => Ok(CommitmentProof {
                 proof: Some(Ics23Proof::Exist(ExistenceProof {
                     key: base_key.as_bytes().to_vec(),
                     leaf: Some(ics23_specs::base_leaf_spec::<H>()),
                     ..ep
                 })),
-            },
+            }),
             // the proof should have an ExistenceProof
             _ => unreachable!(),
-        };
-
-        Ok(Proof {
-            key: key.clone(),
-            sub_proof,
-            base_proof,
-        })
+        }
     }
 }
 
@@ -672,6 +1031,8 @@ pub struct MerkleTreeStoresRead {
     ibc: (Hash, AmtStore),
     pos: (Hash, SmtStore),
     bridge_pool: (KeccakHash, BridgePoolStore),
+    token: (Hash, SmtStore),
+    governance: (Hash, SmtStore),
 }
 
 impl MerkleTreeStoresRead {
@@ -683,6 +1044,8 @@ impl MerkleTreeStoresRead {
             StoreType::Ibc => self.ibc.0 = root,
             StoreType::PoS => self.pos.0 = root,
             StoreType::BridgePool => self.bridge_pool.0 = root.into(),
+            StoreType::Token => self.token.0 = root,
+            StoreType::Governance => self.governance.0 = root,
         }
     }
 
@@ -694,6 +1057,8 @@ impl MerkleTreeStoresRead {
             Store::Ibc(store) => self.ibc.1 = store,
             Store::PoS(store) => self.pos.1 = store,
             Store::BridgePool(store) => self.bridge_pool.1 = store,
+            Store::Token(store) => self.token.1 = store,
+            Store::Governance(store) => self.governance.1 = store,
         }
     }
 
@@ -705,6 +1070,8 @@ impl MerkleTreeStoresRead {
             StoreType::Ibc => StoreRef::Ibc(&self.ibc.1),
             StoreType::PoS => StoreRef::PoS(&self.pos.1),
             StoreType::BridgePool => StoreRef::BridgePool(&self.bridge_pool.1),
+            StoreType::Token => StoreRef::Token(&self.token.1),
+            StoreType::Governance => StoreRef::Governance(&self.governance.1),
         }
     }
 
@@ -716,6 +1083,8 @@ impl MerkleTreeStoresRead {
             StoreType::Ibc => self.ibc.0,
             StoreType::PoS => self.pos.0,
             StoreType::BridgePool => Hash(self.bridge_pool.0.0),
+            StoreType::Token => self.token.0,
+            StoreType::Governance => self.governance.0,
         }
     }
 }
@@ -727,6 +1096,8 @@ pub struct MerkleTreeStoresWrite<'a> {
     ibc: (Hash, &'a AmtStore),
     pos: (Hash, &'a SmtStore),
     bridge_pool: (Hash, &'a BridgePoolStore),
+    token: (Hash, &'a SmtStore),
+    governance: (Hash, &'a SmtStore),
 }
 
 impl<'a> MerkleTreeStoresWrite<'a> {
@@ -738,6 +1109,8 @@ impl<'a> MerkleTreeStoresWrite<'a> {
             StoreType::Ibc => &self.ibc.0,
             StoreType::PoS => &self.pos.0,
             StoreType::BridgePool => &self.bridge_pool.0,
+            StoreType::Token => &self.token.0,
+            StoreType::Governance => &self.governance.0,
         }
     }
 
@@ -749,6 +1122,8 @@ impl<'a> MerkleTreeStoresWrite<'a> {
             StoreType::Ibc => StoreRef::Ibc(self.ibc.1),
             StoreType::PoS => StoreRef::PoS(self.pos.1),
             StoreType::BridgePool => StoreRef::BridgePool(self.bridge_pool.1),
+            StoreType::Token => StoreRef::Token(self.token.1),
+            StoreType::Governance => StoreRef::Governance(self.governance.1),
         }
     }
 }
@@ -814,6 +1189,258 @@ impl From<Proof> for namada_core::tendermint::merkle::proof::ProofOps {
     }
 }
 
+/// A batch proof of the values, or of the absence, of several storage keys
+#[derive(Debug)]
+pub struct BatchProof {
+    /// The proofs of the keys in each of their sub-trees
+    pub sub_tree_proofs: Vec<SubTreeBatchProof>,
+}
+
+/// A batch proof of the values, or of the absence, of several storage keys
+/// in a sub-tree
+#[derive(Debug)]
+pub struct SubTreeBatchProof {
+    /// The sub-tree of the keys
+    pub store_type: StoreType,
+    /// Compressed ICS23 batch proof of the keys in the sub-tree
+    pub sub_proof: CommitmentProof,
+    /// Proof of the sub-tree root in the base tree
+    pub base_proof: CommitmentProof,
+}
+
+impl From<BatchProof> for namada_core::tendermint::merkle::proof::ProofOps {
+    fn from(BatchProof { sub_tree_proofs }: BatchProof) -> Self {
+        use namada_core::tendermint::merkle::proof::ProofOp;
+        use prost::Message;
+
+        // Set a pair of ProofOps from leaf to root for each sub-tree
+        let ops = sub_tree_proofs
+            .into_iter()
+            .flat_map(
+                |SubTreeBatchProof {
+                     store_type,
+                     sub_proof,
+                     base_proof,
+                 }| {
+                    [sub_proof, base_proof].map(|proof| ProofOp {
+                        field_type: "ics23_CommitmentProof".to_string(),
+                        key: store_type.to_string().as_bytes().to_vec(),
+                        data: proof.encode_to_vec(),
+                    })
+                },
+            )
+            .collect();
+        Self { ops }
+    }
+}
+
+/// Verify a storage proof in the format returned by the storage queries
+/// against the merkle root of a block. If `value` is given, the proof must
+/// prove that the key holds this value, otherwise that the key is absent.
+pub fn verify_storage_proof<H: StorageHasher + Default>(
+    proof: &namada_core::tendermint::merkle::proof::ProofOps,
+    root: &MerkleRoot,
+    key: &Key,
+    value: Option<&[u8]>,
+) -> Result<bool> {
+    let decode = |index: usize| {
+        let op = proof.ops.get(index).ok_or_else(|| {
+            Error::InvalidProof(format!("Missing proof op {index}"))
+        })?;
+        decode_commitment_proof(op)
+    };
+    let sub_proof = decode(0)?;
+    let base_proof = decode(1)?;
+
+    // The proofs from the trees committed before the token and governance
+    // subtrees were split from the account subtree have their keys in the
+    // account subtree
+    let (store_type, sub_key) =
+        if proven_store_type(&base_proof) == Some(StoreType::Account) {
+            StoreType::legacy_sub_key(key)?
+        } else {
+            StoreType::sub_key(key)?
+        };
+    let sub_root = match sub_tree_root(&sub_proof)? {
+        Some(sub_root) => sub_root,
+        None => return Ok(false),
+    };
+    Ok(verify_sub_tree_proof::<H>(
+        &sub_proof,
+        &sub_root,
+        &store_type,
+        &sub_key,
+        value,
+    ) && verify_base_proof::<H>(&base_proof, root, &store_type, &sub_root))
+}
+
+/// Verify a batch proof in the format returned by the storage queries
+/// against the merkle root of a block. The proof must prove the values of
+/// all the given keys, or their absence for the keys without a value.
+pub fn verify_batch_storage_proof<H: StorageHasher + Default>(
+    proof: &namada_core::tendermint::merkle::proof::ProofOps,
+    root: &MerkleRoot,
+    entries: &[(Key, Option<&[u8]>)],
+) -> Result<bool> {
+    if proof.ops.len() % 2 != 0 {
+        return Err(Error::InvalidProof(
+            "A batch proof must have a pair of proof ops for each sub-tree"
+                .to_string(),
+        ));
+    }
+    // Check the root of every sub-tree in the base tree
+    let mut sub_trees = Vec::with_capacity(proof.ops.len() / 2);
+    for ops in proof.ops.chunks(2) {
+        let store_type = std::str::from_utf8(&ops[0].key)
+            .map_err(|e| Error::InvalidProof(e.to_string()))
+            .and_then(StoreType::from_str)?;
+        let sub_proof = ics23::decompress(&decode_commitment_proof(&ops[0])?)
+            .map_err(|e| Error::InvalidProof(e.to_string()))?;
+        let base_proof = decode_commitment_proof(&ops[1])?;
+        let sub_root = match sub_tree_root(&sub_proof)? {
+            Some(sub_root) => sub_root,
+            None => return Ok(false),
+        };
+        if !verify_base_proof::<H>(&base_proof, root, &store_type, &sub_root) {
+            return Ok(false);
+        }
+        sub_trees.push((store_type, sub_proof, sub_root));
+    }
+
+    // Check every key against the root of its sub-tree
+    for (key, value) in entries {
+        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        // The proofs from the trees committed before the token and
+        // governance subtrees were split have their keys in the account
+        // subtree
+        let (legacy_store_type, _) = StoreType::legacy_sub_key(key)?;
+        let Some((store_type, sub_proof, sub_root)) = sub_trees
+            .iter()
+            .find(|(st, _, _)| *st == store_type)
+            .or_else(|| {
+                sub_trees.iter().find(|(st, _, _)| *st == legacy_store_type)
+            })
+        else {
+            return Ok(false);
+        };
+        if !verify_sub_tree_proof::<H>(
+            sub_proof, sub_root, store_type, &sub_key, *value,
+        ) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Decode an ICS23 commitment proof from a proof op
+fn decode_commitment_proof(
+    op: &namada_core::tendermint::merkle::proof::ProofOp,
+) -> Result<CommitmentProof> {
+    use prost::Message;
+
+    CommitmentProof::decode(&op.data[..])
+        .map_err(|e| Error::InvalidProof(e.to_string()))
+}
+
+/// Get the store type whose sub-tree root is proven by a base tree proof
+fn proven_store_type(base_proof: &CommitmentProof) -> Option<StoreType> {
+    match &base_proof.proof {
+        Some(Ics23Proof::Exist(ep)) => std::str::from_utf8(&ep.key)
+            .ok()
+            .and_then(|st| StoreType::from_str(st).ok()),
+        _ => None,
+    }
+}
+
+/// Calculate the root of a sub-tree from the first existence proof of a key
+/// or of its neighbours in the given proof, if any
+fn sub_tree_root(proof: &CommitmentProof) -> Result<Option<Vec<u8>>> {
+    use ics23::HostFunctionsManager;
+
+    let ep = match &proof.proof {
+        Some(Ics23Proof::Exist(ep)) => Some(ep),
+        Some(Ics23Proof::Nonexist(nep)) => {
+            nep.left.as_ref().or(nep.right.as_ref())
+        }
+        Some(Ics23Proof::Batch(batch)) => {
+            batch.entries.first().and_then(|entry| match &entry.proof {
+                Some(Ics23BatchEntryProof::Exist(ep)) => Some(ep),
+                Some(Ics23BatchEntryProof::Nonexist(nep)) => {
+                    nep.left.as_ref().or(nep.right.as_ref())
+                }
+                None => None,
+            })
+        }
+        _ => None,
+    };
+    ep.map(|ep| {
+        ics23::calculate_existence_root::<HostFunctionsManager>(ep)
+            .map_err(|e| Error::InvalidProof(e.to_string()))
+    })
+    .transpose()
+}
+
+/// Verify the value, or the absence, of a key in a sub-tree proof against
+/// the sub-tree root. Except in the IBC sub-tree, the absence of a key is
+/// proven with the hashed keys and values of its neighbours.
+fn verify_sub_tree_proof<H: StorageHasher + Default>(
+    sub_proof: &CommitmentProof,
+    sub_root: &[u8],
+    store_type: &StoreType,
+    sub_key: &Key,
+    value: Option<&[u8]>,
+) -> bool {
+    use ics23::HostFunctionsManager;
+
+    match value {
+        Some(value) => {
+            let specs = if *store_type == StoreType::Ibc {
+                ics23_specs::ibc_proof_specs::<H>()
+            } else {
+                ics23_specs::proof_specs::<H>()
+            };
+            ics23::verify_membership::<HostFunctionsManager>(
+                sub_proof,
+                &specs[0],
+                &sub_root.to_vec(),
+                sub_key.to_string().as_bytes(),
+                value,
+            )
+        }
+        None => {
+            let key = if *store_type == StoreType::Ibc {
+                sub_key.to_string().into_bytes()
+            } else {
+                H::hash(sub_key.to_string()).as_slice().to_vec()
+            };
+            ics23::verify_non_membership::<HostFunctionsManager>(
+                sub_proof,
+                &ics23_specs::ibc_proof_specs::<H>()[0],
+                &sub_root.to_vec(),
+                &key,
+            )
+        }
+    }
+}
+
+/// Verify the membership proof of the root of a sub-tree in the base tree
+fn verify_base_proof<H: StorageHasher + Default>(
+    base_proof: &CommitmentProof,
+    root: &MerkleRoot,
+    store_type: &StoreType,
+    sub_root: &[u8],
+) -> bool {
+    use ics23::HostFunctionsManager;
+
+    ics23::verify_membership::<HostFunctionsManager>(
+        base_proof,
+        &ics23_specs::proof_specs::<H>()[1],
+        &root.0.to_vec(),
+        store_type.to_string().as_bytes(),
+        sub_root,
+    )
+}
+
 impl<'a, H: StorageHasher + Default> SubTreeRead for &'a Smt<H> {
     fn root(&self) -> MerkleRoot {
         Smt::<H>::root(self).into()
@@ -1004,6 +1631,7 @@ impl<'a> SubTreeWrite for &'a mut BridgePoolTree {
 #[cfg(test)]
 mod test {
     use ics23::HostFunctionsManager;
+    use namada_core::tendermint::merkle::proof::ProofOps;
     use namada_core::types::hash::Sha256Hasher;
     use namada_core::types::storage::KeySeg;
 
@@ -1121,6 +1749,86 @@ mod test {
         assert!(restored_tree.has_key(&pos_key).unwrap());
     }
 
+    #[test]
+    fn test_split_account_subtree() {
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Multitoken).to_db_key().into();
+        let token_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Governance).to_db_key().into();
+        let gov_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Pgf).to_db_key().into();
+        let pgf_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Parameters).to_db_key().into();
+        let param_key = key_prefix.push(&"test".to_string()).unwrap();
+        let keys = [token_key, gov_key, pgf_key, param_key];
+
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        let mut legacy_tree = MerkleTree::<Sha256Hasher> {
+            legacy_layout: true,
+            ..Default::default()
+        };
+        for (i, key) in keys.iter().enumerate() {
+            tree.update(key, [i as u8; 8]).unwrap();
+            legacy_tree.update(key, [i as u8; 8]).unwrap();
+        }
+        assert_eq!(tree.store_type(&keys[0]).unwrap(), StoreType::Token);
+        assert_eq!(tree.store_type(&keys[1]).unwrap(), StoreType::Governance);
+        assert_eq!(tree.store_type(&keys[2]).unwrap(), StoreType::Governance);
+        for key in &keys {
+            assert_eq!(
+                legacy_tree.store_type(key).unwrap(),
+                StoreType::Account
+            );
+        }
+        assert_ne!(tree.root(), legacy_tree.root());
+
+        // The layout of a tree is set when it's restored
+        let restore = |tree: &MerkleTree<Sha256Hasher>, legacy_layout| {
+            let stores_write = tree.stores();
+            let mut stores_read = MerkleTreeStoresRead::default();
+            for st in StoreType::iter() {
+                stores_read.set_root(st, *stores_write.root(st));
+                stores_read.set_store(stores_write.store(st).to_owned());
+            }
+            MerkleTree::<Sha256Hasher>::new(stores_read)
+                .unwrap()
+                .with_legacy_layout(legacy_layout)
+        };
+        let mut legacy_tree = restore(&legacy_tree, true);
+        for key in &keys {
+            assert!(legacy_tree.has_key(key).unwrap());
+        }
+
+        legacy_tree.split_account_subtree(keys.clone()).unwrap();
+        assert!(!legacy_tree.is_legacy_layout());
+        assert_eq!(tree.root(), legacy_tree.root());
+        for st in StoreType::iter_subtrees() {
+            assert_eq!(tree.sub_root(st), legacy_tree.sub_root(st));
+        }
+        for key in &keys {
+            assert!(legacy_tree.has_key(key).unwrap());
+        }
+        assert_eq!(restore(&legacy_tree, false).root(), tree.root());
+
+        let layout = MerkleTreeLayout {
+            subtrees_split_height: Some(BlockHeight(2)),
+            masp_merklization_height: Some(BlockHeight(3)),
+        };
+        assert!(layout.is_legacy_at(BlockHeight(1)));
+        assert!(!layout.is_legacy_at(BlockHeight(2)));
+        assert!(!layout.is_masp_merklized_at(BlockHeight(2)));
+        assert!(layout.is_masp_merklized_at(BlockHeight(3)));
+        let layout = MerkleTreeLayout::default();
+        assert!(!layout.is_legacy_at(BlockHeight(1)));
+        assert!(layout.is_masp_merklized_at(BlockHeight(1)));
+        let layout = MerkleTreeLayout::LEGACY;
+        assert!(layout.is_legacy_at(BlockHeight(2)));
+        assert!(!layout.is_masp_merklized_at(BlockHeight(2)));
+    }
+
     #[test]
     fn test_ibc_existence_proof() {
         let mut tree = MerkleTree::<Sha256Hasher>::default();
@@ -1313,4 +2021,167 @@ mod test {
             );
         assert!(basetree_verification_res);
     }
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Parameters).to_db_key().into();
+        let account_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Ibc).to_db_key().into();
+        let ibc_key = key_prefix.push(&"test".to_string()).unwrap();
+        let ibc_non_key = key_prefix.push(&"test2".to_string()).unwrap();
+
+        let account_val = [1u8; 8].to_vec();
+        tree.update(&account_key, account_val.clone()).unwrap();
+        tree.update(&ibc_key, [2u8; 8]).unwrap();
+        let root = tree.root();
+
+        let proof = match tree
+            .get_sub_tree_existence_proof(
+                std::array::from_ref(&account_key),
+                vec![&account_val],
+            )
+            .unwrap()
+        {
+            MembershipProof::ICS23(proof) => proof,
+            _ => panic!("Test failed"),
+        };
+        let proof: ProofOps =
+            tree.get_sub_tree_proof(&account_key, proof).unwrap().into();
+        assert!(
+            verify_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &account_key,
+                Some(&account_val),
+            )
+            .unwrap()
+        );
+        assert!(
+            !verify_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &account_key,
+                Some(&[3u8; 8]),
+            )
+            .unwrap()
+        );
+
+        let proof: ProofOps =
+            tree.get_non_existence_proof(&ibc_non_key).unwrap().into();
+        assert!(
+            verify_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &ibc_non_key,
+                None,
+            )
+            .unwrap()
+        );
+        assert!(
+            !verify_storage_proof::<Sha256Hasher>(
+                &proof,
+                &MerkleRoot([0u8; 32]),
+                &ibc_non_key,
+                None,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_batch_storage_proof() {
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Parameters).to_db_key().into();
+        let account_key = key_prefix.push(&"test".to_string()).unwrap();
+        let account_non_key = key_prefix.push(&"test2".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::PoS).to_db_key().into();
+        let pos_key = key_prefix.push(&"test".to_string()).unwrap();
+        let key_prefix: Key =
+            Address::Internal(InternalAddress::Ibc).to_db_key().into();
+        let ibc_key = key_prefix.push(&"test".to_string()).unwrap();
+        let ibc_non_key = key_prefix.push(&"test2".to_string()).unwrap();
+
+        let account_val = [1u8; 8].to_vec();
+        let pos_val = [2u8; 8].to_vec();
+        tree.update(&account_key, &account_val).unwrap();
+        tree.update(&account_non_key, [3u8; 8]).unwrap();
+        tree.delete(&account_non_key).unwrap();
+        tree.update(&pos_key, &pos_val).unwrap();
+        tree.update(&ibc_key, [4u8; 8]).unwrap();
+        let root = tree.root();
+
+        let proof: ProofOps = tree
+            .get_batch_proof(&[
+                (account_key.clone(), Some(&account_val[..])),
+                (account_non_key.clone(), None),
+                (pos_key.clone(), Some(&pos_val[..])),
+                (ibc_non_key.clone(), None),
+            ])
+            .unwrap()
+            .into();
+        // A pair of proof ops for each of the 3 sub-trees
+        assert_eq!(proof.ops.len(), 6);
+
+        let entries = [
+            (account_key.clone(), Some(&account_val[..])),
+            (account_non_key.clone(), None),
+            (pos_key.clone(), Some(&pos_val[..])),
+            (ibc_non_key, None),
+        ];
+        assert!(
+            verify_batch_storage_proof::<Sha256Hasher>(&proof, &root, &entries)
+                .unwrap()
+        );
+        // A single key of the batch can also be verified
+        assert!(
+            verify_batch_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &entries[1..2],
+            )
+            .unwrap()
+        );
+        // The values must match
+        assert!(
+            !verify_batch_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &[(pos_key, Some(&account_val[..]))],
+            )
+            .unwrap()
+        );
+        // An existing key can't be proven absent
+        assert!(
+            !verify_batch_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &[(account_key, None)],
+            )
+            .unwrap()
+        );
+        // The keys must be in the proof
+        assert!(
+            !verify_batch_storage_proof::<Sha256Hasher>(
+                &proof,
+                &root,
+                &[(ibc_key, Some(&[4u8; 8][..]))],
+            )
+            .unwrap()
+        );
+        assert!(
+            !verify_batch_storage_proof::<Sha256Hasher>(
+                &proof,
+                &MerkleRoot([0u8; 32]),
+                &entries,
+            )
+            .unwrap()
+        );
+    }
 }
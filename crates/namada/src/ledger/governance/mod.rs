@@ -6,7 +6,7 @@ use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
 use namada_governance::storage::proposal::{
-    AddRemove, PGFAction, ProposalType,
+    AddRemove, PGFAction, ProposalType, ShieldedRewardsUpdate,
 };
 use namada_governance::storage::{is_proposal_accepted, keys as gov_storage};
 use namada_governance::utils::is_valid_validator_voting_period;
@@ -35,6 +35,9 @@ pub const ADDRESS: Address = Address::Internal(InternalAddress::Governance);
 /// The maximum number of item in a pgf proposal
 pub const MAX_PGF_ACTIONS: usize = 20;
 
+/// The maximum number of token updates in a shielded rewards proposal
+pub const MAX_SHIELDED_REWARDS_UPDATES: usize = 20;
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum Error {
@@ -427,6 +430,9 @@ where
                     && are_continous_fundings_unique
                     && are_targets_unique)
             }
+            ProposalType::ShieldedRewards(updates) => {
+                Ok(are_valid_shielded_rewards_updates(&updates))
+            }
             _ => Ok(true), // default proposal
         }
     }
@@ -812,6 +818,28 @@ enum KeyType {
     UNKNOWN,
 }
 
+/// Check that a shielded rewards proposal updates between one and
+/// [`MAX_SHIELDED_REWARDS_UPDATES`] distinct tokens, each with an alias and
+/// valid parameters
+fn are_valid_shielded_rewards_updates(
+    updates: &BTreeSet<ShieldedRewardsUpdate>,
+) -> bool {
+    let unique_tokens = updates
+        .iter()
+        .map(|update| &update.token)
+        .collect::<BTreeSet<&Address>>()
+        .len();
+
+    let is_total_updates_valid =
+        !updates.is_empty() && updates.len() <= MAX_SHIELDED_REWARDS_UPDATES;
+    let are_tokens_unique = unique_tokens == updates.len();
+    let are_updates_valid = updates
+        .iter()
+        .all(|update| !update.alias.is_empty() && update.params.is_valid());
+
+    is_total_updates_valid && are_tokens_unique && are_updates_valid
+}
+
 impl KeyType {
     fn from_key(key: &Key, native_token: &Address) -> Self {
         if gov_storage::is_vote_key(key) {
@@ -849,3 +877,41 @@ impl KeyType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::address_from_simple_seed;
+    use namada_core::types::token::MaspParams;
+
+    use super::*;
+
+    fn shielded_rewards_updates(
+        num_updates: usize,
+    ) -> BTreeSet<ShieldedRewardsUpdate> {
+        (0..num_updates as u64)
+            .map(|seed| ShieldedRewardsUpdate {
+                alias: format!("token{seed}"),
+                token: address_from_simple_seed(seed),
+                params: MaspParams::default(),
+            })
+            .collect()
+    }
+
+    /// Test the bounds on the number of token updates of a shielded rewards
+    /// proposal
+    #[test]
+    fn test_shielded_rewards_updates_limit() {
+        assert!(!are_valid_shielded_rewards_updates(
+            &shielded_rewards_updates(0)
+        ));
+        assert!(are_valid_shielded_rewards_updates(
+            &shielded_rewards_updates(1)
+        ));
+        assert!(are_valid_shielded_rewards_updates(
+            &shielded_rewards_updates(MAX_SHIELDED_REWARDS_UPDATES)
+        ));
+        assert!(!are_valid_shielded_rewards_updates(
+            &shielded_rewards_updates(MAX_SHIELDED_REWARDS_UPDATES + 1)
+        ));
+    }
+}
@@ -10,6 +10,7 @@ pub mod pos;
 pub mod protocol;
 pub use namada_sdk::queries;
 pub mod storage;
+pub mod storage_deposit;
 pub mod vp_host_fns;
 
 #[cfg(feature = "wasm-runtime")]
@@ -139,7 +140,8 @@ mod test {
     use namada_core::types::hash::Hash;
     use namada_core::types::storage::{BlockHeight, Key};
     use namada_sdk::queries::{
-        EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
+        EncodedResponseQuery, RequestCtx, RequestQuery, Router,
+        MAX_STORAGE_VALUES_KEYS, RPC,
     };
     use namada_sdk::tendermint_rpc::{self, Error as RpcError, Response};
     use namada_state::testing::TestWlStorage;
@@ -369,4 +371,37 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_shell_queries_storage_values_keys_limit() {
+        let client = TestClient::new(RPC);
+        let keys: Vec<Key> = (0..=MAX_STORAGE_VALUES_KEYS)
+            .map(|i| Key::parse(format!("key{i}")).unwrap())
+            .collect();
+
+        // Up to the maximum number of keys can be read at once ...
+        let max_keys = &keys[..MAX_STORAGE_VALUES_KEYS];
+        let response = RPC
+            .shell()
+            .storage_values(
+                &client,
+                Some(max_keys.serialize_to_vec()),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        let values =
+            Vec::<(Key, Option<Vec<u8>>)>::try_from_slice(&response.data)
+                .unwrap();
+        assert_eq!(values.len(), MAX_STORAGE_VALUES_KEYS);
+        assert!(values.iter().all(|(_, value)| value.is_none()));
+
+        // ... but one more key is rejected
+        let result = RPC
+            .shell()
+            .storage_values(&client, Some(keys.serialize_to_vec()), None, false)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -8,7 +8,7 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use masp_primitives::transaction::components::I128Sum;
-use masp_primitives::transaction::Transaction;
+use masp_primitives::transaction::{Transaction, TransparentAddress};
 use namada_core::types::address::Address;
 use namada_core::types::address::InternalAddress::Masp;
 use namada_core::types::masp::encode_asset_type;
@@ -27,8 +27,10 @@ use token::storage_key::{
     balance_key, is_any_shielded_action_balance_key, is_masp_allowed_key,
     is_masp_key, is_masp_nullifier_key, is_masp_tx_pin_key,
     masp_commitment_anchor_key, masp_commitment_tree_key,
-    masp_convert_anchor_key, masp_nullifier_key,
+    masp_convert_anchor_key, masp_note_commitment_count_key,
+    masp_nullifier_count_key, masp_nullifier_key,
 };
+use token::utils::{note_commitment_count, nullifier_count};
 use token::Amount;
 
 use crate::ledger::native_vp;
@@ -174,6 +176,42 @@ where
         Ok(true)
     }
 
+    // Check that the shielded pool counters have been increased by exactly the
+    // number of nullifiers revealed and note commitments appended by the
+    // transaction
+    fn valid_counters_update(&self, transaction: &Transaction) -> Result<bool> {
+        let (spends, outputs) =
+            transaction.sapling_bundle().map_or((0, 0), |bundle| {
+                (bundle.shielded_spends.len(), bundle.shielded_outputs.len())
+            });
+
+        for (key, pre, post, expected_increment) in [
+            (
+                masp_nullifier_count_key(),
+                nullifier_count(&self.ctx.pre())?,
+                nullifier_count(&self.ctx.post())?,
+                spends,
+            ),
+            (
+                masp_note_commitment_count_key(),
+                note_commitment_count(&self.ctx.pre())?,
+                note_commitment_count(&self.ctx.post())?,
+                outputs,
+            ),
+        ] {
+            if pre.checked_add(expected_increment as u64) != Some(post) {
+                tracing::debug!(
+                    "The MASP counter {key} was incorrectly updated: \
+                     expected an increment of {expected_increment}, found \
+                     {pre} -> {post}"
+                );
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
     // Check that the spend descriptions anchors of a transaction are valid
     fn valid_spend_descriptions_anchor(
         &self,
@@ -382,6 +420,189 @@ where
             amount,
         })
     }
+
+    // Check that the transparent outputs of the transaction are directed to
+    // the given transparent address and carry asset types properly derived
+    // from the given token. Returns the total value of the outputs, or `None`
+    // if any of them is invalid.
+    fn transparent_outputs_value(
+        &self,
+        shielded_tx: &Transaction,
+        target: &TransparentAddress,
+        token: &Address,
+        denom: token::Denomination,
+        transparent_tx_pool: &mut I128Sum,
+    ) -> Result<Option<Amount>> {
+        let epoch = self.ctx.get_block_epoch()?;
+        let conversion_state = self.ctx.storage.get_conversion_state();
+        let transp_bundle = shielded_tx.transparent_bundle().ok_or_err_msg(
+            "Expected transparent outputs in unshielding transaction",
+        )?;
+
+        let mut total_out_values = token::Amount::zero();
+        // To help recognize asset types not in the conversion tree
+        let unepoched_tokens = unepoched_tokens(token, denom)?;
+
+        for out in &transp_bundle.vout {
+            // Non-masp destinations subtract from transparent tx
+            // pool
+            *transparent_tx_pool = transparent_tx_pool
+                .checked_sub(
+                    &I128Sum::from_nonnegative(
+                        out.asset_type,
+                        out.value as i128,
+                    )
+                    .ok()
+                    .ok_or_err_msg("invalid value or asset type for amount")?,
+                )
+                .ok_or_err_msg("Underflow in output subtraction")?;
+
+            // Satisfies 3.
+            if target.0 != out.address.0 {
+                tracing::debug!(
+                    "the public key of the output account does not match \
+                     the transfer target"
+                );
+                return Ok(None);
+            }
+            match conversion_state.assets.get(&out.asset_type) {
+                // Satisfies 2.
+                Some((
+                    (address, asset_denom, digit),
+                    asset_epoch,
+                    _,
+                    _,
+                )) if address == token
+                    && *asset_denom == denom
+                    && *asset_epoch <= epoch =>
+                {
+                    total_out_values = total_out_values
+                        .checked_add(token::Amount::from_masp_denominated(
+                            out.value, *digit,
+                        ))
+                        .ok_or_else(|| {
+                            Error::NativeVpError(
+                                native_vp::Error::SimpleMessage(
+                                    "Overflow in total out values sum",
+                                ),
+                            )
+                        })?;
+                }
+                // Maybe the asset type has no attached epoch
+                None if unepoched_tokens.contains_key(&out.asset_type) => {
+                    let (_token, _denom, digit) =
+                        &unepoched_tokens[&out.asset_type];
+                    // Otherwise note the contribution to this
+                    // trransparent input
+                    total_out_values = total_out_values
+                        .checked_add(token::Amount::from_masp_denominated(
+                            out.value, *digit,
+                        ))
+                        .ok_or_else(|| {
+                            Error::NativeVpError(
+                                native_vp::Error::SimpleMessage(
+                                    "Overflow in total out values sum",
+                                ),
+                            )
+                        })?;
+                }
+                // unrecognized asset
+                _ => return Ok(None),
+            };
+        }
+        Ok(Some(total_out_values))
+    }
+
+    /// Validate the masp section paying the fee of a wrapper directly from
+    /// the shielded pool. The section must spend shielded notes only and
+    /// unshield exactly the given fee amount to the given fee payment target,
+    /// committing to the wrapper, after which the protocol moves the fee from
+    /// the MASP to the block proposer.
+    pub fn validate_fee_payment(
+        &self,
+        tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        fee_payment_target: &TransparentAddress,
+        fee_token: &Address,
+        fee: Amount,
+    ) -> Result<bool> {
+        let shielded_tx = self.ctx.get_shielded_action(tx_data)?;
+
+        if u64::from(self.ctx.get_block_height()?)
+            > u64::from(shielded_tx.expiry_height())
+        {
+            tracing::debug!("MASP fee payment is expired");
+            return Ok(false);
+        }
+
+        // No transparent input and valid shielded spends
+        if shielded_tx
+            .transparent_bundle()
+            .is_some_and(|bundle| !bundle.vin.is_empty())
+        {
+            tracing::debug!("Transparent input found in MASP fee payment");
+            return Ok(false);
+        }
+        if !(self.valid_spend_descriptions_anchor(&shielded_tx)?
+            && self.valid_convert_descriptions_anchor(&shielded_tx)?
+            && self.valid_nullifiers_reveal(keys_changed, &shielded_tx)?
+            && self.valid_note_commitment_update(&shielded_tx)?
+            && self.valid_counters_update(&shielded_tx)?)
+        {
+            return Ok(false);
+        }
+
+        // The fee must be unshielded to the target committing to the wrapper
+        let denom = read_denom(&self.ctx.pre(), fee_token)?.ok_or_err_msg(
+            "No denomination found in storage for the given token",
+        )?;
+        let mut transparent_tx_pool = I128Sum::zero();
+        transparent_tx_pool += shielded_tx.sapling_value_balance();
+        let Some(total_out_values) = self.transparent_outputs_value(
+            &shielded_tx,
+            fee_payment_target,
+            fee_token,
+            denom,
+            &mut transparent_tx_pool,
+        )?
+        else {
+            return Ok(false);
+        };
+        if total_out_values != fee
+            || !is_balanced_value_pool(&transparent_tx_pool)
+        {
+            return Ok(false);
+        }
+
+        // Verify the proofs and charge the gas for the expensive execution
+        self.ctx
+            .charge_gas(MASP_VERIFY_SHIELDED_TX_GAS)
+            .map_err(Error::NativeVpError)?;
+        Ok(verify_shielded_tx(&shielded_tx))
+    }
+}
+
+// Check that the transparent transaction value pool is empty
+fn is_balanced_value_pool(transparent_tx_pool: &I128Sum) -> bool {
+    match transparent_tx_pool.partial_cmp(&I128Sum::zero()) {
+        None | Some(Ordering::Less) => {
+            tracing::debug!(
+                "Transparent transaction value pool must be nonnegative. \
+                 Violation may be caused by transaction being constructed in \
+                 previous epoch. Maybe try again."
+            );
+            // Section 3.4: The remaining value in the transparent
+            // transaction value pool MUST be nonnegative.
+            false
+        }
+        Some(Ordering::Greater) => {
+            tracing::debug!(
+                "Transaction fees cannot be paid inside MASP transaction."
+            );
+            false
+        }
+        _ => true,
+    }
 }
 
 // Make a map to help recognize asset types lacking an epoch
@@ -593,6 +814,12 @@ where
             return Ok(false);
         }
 
+        // The shielded pool statistics must be kept in sync with the
+        // nullifiers and note commitments
+        if !self.valid_counters_update(&shielded_tx)? {
+            return Ok(false);
+        }
+
         if transfer.target != Address::Internal(Masp) {
             // Handle transparent output
             // The following boundary conditions must be satisfied
@@ -600,87 +827,20 @@ where
             // amount 2. Asset type must be properly derived
             // 3. Public key must be the hash of the target
 
-            let transp_bundle =
-                shielded_tx.transparent_bundle().ok_or_err_msg(
-                    "Expected transparent outputs in unshielding transaction",
-                )?;
-
-            let mut total_out_values = token::Amount::zero();
+            // Satisfies 2. and 3.
             let target_enc = transfer.target.serialize_to_vec();
             let hash =
                 ripemd::Ripemd160::digest(sha2::Sha256::digest(&target_enc));
-            // To help recognize asset types not in the conversion tree
-            let unepoched_tokens = unepoched_tokens(&transfer.token, denom)?;
-
-            for out in &transp_bundle.vout {
-                // Non-masp destinations subtract from transparent tx
-                // pool
-                transparent_tx_pool = transparent_tx_pool
-                    .checked_sub(
-                        &I128Sum::from_nonnegative(
-                            out.asset_type,
-                            out.value as i128,
-                        )
-                        .ok()
-                        .ok_or_err_msg(
-                            "invalid value or asset type for amount",
-                        )?,
-                    )
-                    .ok_or_err_msg("Underflow in output subtraction")?;
-
-                // Satisfies 3.
-                if <[u8; 20]>::from(hash) != out.address.0 {
-                    tracing::debug!(
-                        "the public key of the output account does not match \
-                         the transfer target"
-                    );
-                    return Ok(false);
-                }
-                match conversion_state.assets.get(&out.asset_type) {
-                    // Satisfies 2.
-                    Some((
-                        (address, asset_denom, digit),
-                        asset_epoch,
-                        _,
-                        _,
-                    )) if *address == transfer.token
-                        && *asset_denom == denom
-                        && *asset_epoch <= epoch =>
-                    {
-                        total_out_values = total_out_values
-                            .checked_add(token::Amount::from_masp_denominated(
-                                out.value, *digit,
-                            ))
-                            .ok_or_else(|| {
-                                Error::NativeVpError(
-                                    native_vp::Error::SimpleMessage(
-                                        "Overflow in total out values sum",
-                                    ),
-                                )
-                            })?;
-                    }
-                    // Maybe the asset type has no attached epoch
-                    None if unepoched_tokens.contains_key(&out.asset_type) => {
-                        let (_token, _denom, digit) =
-                            &unepoched_tokens[&out.asset_type];
-                        // Otherwise note the contribution to this
-                        // trransparent input
-                        total_out_values = total_out_values
-                            .checked_add(token::Amount::from_masp_denominated(
-                                out.value, *digit,
-                            ))
-                            .ok_or_else(|| {
-                                Error::NativeVpError(
-                                    native_vp::Error::SimpleMessage(
-                                        "Overflow in total out values sum",
-                                    ),
-                                )
-                            })?;
-                    }
-                    // unrecognized asset
-                    _ => return Ok(false),
-                };
-            }
+            let Some(total_out_values) = self.transparent_outputs_value(
+                &shielded_tx,
+                &TransparentAddress(hash.into()),
+                &transfer.token,
+                denom,
+                &mut transparent_tx_pool,
+            )?
+            else {
+                return Ok(false);
+            };
             // Satisfies 1.
             if total_out_values != transfer.amount {
                 return Ok(false);
@@ -712,24 +872,8 @@ where
             }
         }
 
-        match transparent_tx_pool.partial_cmp(&I128Sum::zero()) {
-            None | Some(Ordering::Less) => {
-                tracing::debug!(
-                    "Transparent transaction value pool must be nonnegative. \
-                     Violation may be caused by transaction being constructed \
-                     in previous epoch. Maybe try again."
-                );
-                // Section 3.4: The remaining value in the transparent
-                // transaction value pool MUST be nonnegative.
-                return Ok(false);
-            }
-            Some(Ordering::Greater) => {
-                tracing::debug!(
-                    "Transaction fees cannot be paid inside MASP transaction."
-                );
-                return Ok(false);
-            }
-            _ => {}
+        if !is_balanced_value_pool(&transparent_tx_pool) {
+            return Ok(false);
         }
 
         // Verify the proofs and charge the gas for the expensive execution
@@ -10,12 +10,18 @@ use thiserror::Error;
 
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
 use crate::token::storage_key::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
-    minter_key,
+    balance_key, denom_key, is_any_allowance_key, is_any_denom_key,
+    is_any_max_supply_key, is_any_metadata_key, is_any_minted_balance_key,
+    is_any_minter_key, is_any_token_balance_key, max_supply_key, metadata_key,
+    minted_balance_key, minter_key, MASP_KD_GAIN_KEY, MASP_KP_GAIN_KEY,
+    MASP_LOCKED_AMOUNT_TARGET_KEY, MASP_MAX_REWARD_RATE_KEY,
+};
+use crate::token::{
+    read_denom, Allowance, Amount, Denomination, MaspParams, TokenMetadata,
 };
-use crate::token::Amount;
 use crate::types::address::{Address, InternalAddress};
-use crate::types::storage::{Key, KeySeg};
+use crate::types::dec::Dec;
+use crate::types::storage::{DbKeySeg, Key, KeySeg};
 use crate::vm::WasmCacheAccess;
 
 #[allow(missing_docs)]
@@ -57,6 +63,8 @@ where
         let mut dec_changes: HashMap<Address, Amount> = HashMap::new();
         let mut inc_mints: HashMap<Address, Amount> = HashMap::new();
         let mut dec_mints: HashMap<Address, Amount> = HashMap::new();
+        let mut allowance_spends: HashMap<(Address, Address), Amount> =
+            HashMap::new();
         for key in keys_changed {
             if let Some([token, _]) = is_any_token_balance_key(key) {
                 let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
@@ -90,6 +98,27 @@ where
                             })?;
                     }
                 }
+            } else if let Some([token, owner, _]) = is_any_allowance_key(key) {
+                let pre = self.ctx.read_pre::<Allowance>(key)?;
+                let post = self.ctx.read_post::<Allowance>(key)?;
+                if let (Some(pre), Some(post)) = (pre, post) {
+                    if let Some(spent) = pre.spent(&post) {
+                        // An allowance cannot be spent once it has expired
+                        if pre.is_expired(self.ctx.get_block_epoch()?) {
+                            return Ok(false);
+                        }
+                        let total = allowance_spends
+                            .entry((token.clone(), owner.clone()))
+                            .or_default();
+                        *total = total.checked_add(spent).ok_or_else(|| {
+                            Error::NativeVpError(
+                                native_vp::Error::SimpleMessage(
+                                    "Overflowed in allowance check",
+                                ),
+                            )
+                        })?;
+                    }
+                }
             } else if let Some(token) = is_any_minted_balance_key(key) {
                 let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
                 let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
@@ -119,15 +148,33 @@ where
                     }
                 }
                 // Check if the minter is set
-                if !self.is_valid_minter(token, verifiers)? {
+                if !self.is_valid_minter(token, verifiers)?
+                    || !self.is_within_max_supply(token, &post)?
+                {
                     return Ok(false);
                 }
             } else if let Some(token) = is_any_minter_key(key) {
-                if !self.is_valid_minter(token, verifiers)? {
+                if !self.is_valid_minter_update(token, verifiers)? {
+                    return Ok(false);
+                }
+            } else if let Some(token) = is_any_max_supply_key(key) {
+                if !self.is_valid_max_supply_update(token, verifiers)? {
+                    return Ok(false);
+                }
+            } else if let Some(token) = is_any_metadata_key(key) {
+                if !self.is_valid_metadata_update(tx_data, token, verifiers)? {
+                    return Ok(false);
+                }
+            } else if let Some(token) = is_any_denom_key(key) {
+                if !self.is_valid_denom_update(token)? {
                     return Ok(false);
                 }
             } else if is_any_token_parameter_key(key).is_some() {
-                return self.is_valid_parameter(tx_data);
+                if !self.is_valid_parameter(tx_data)?
+                    || !self.is_valid_masp_parameter(key)?
+                {
+                    return Ok(false);
+                }
             } else if key.segments.get(0)
                 == Some(
                     &Address::Internal(InternalAddress::Multitoken).to_db_key(),
@@ -139,6 +186,16 @@ where
             }
         }
 
+        // Spent allowances have to be transferred out of the owners' balances
+        for ((token, owner), spent) in allowance_spends {
+            let key = balance_key(&token, &owner);
+            let pre: Amount = self.ctx.read_pre(&key)?.unwrap_or_default();
+            let post: Amount = self.ctx.read_post(&key)?.unwrap_or_default();
+            if pre.checked_sub(post).unwrap_or_default() < spent {
+                return Ok(false);
+            }
+        }
+
         let mut all_tokens = BTreeSet::new();
         all_tokens.extend(inc_changes.keys().cloned());
         all_tokens.extend(dec_changes.keys().cloned());
@@ -194,6 +251,14 @@ where
                     _ => Ok(false),
                 }
             }
+            Address::Established(_) => {
+                // User-issued tokens are minted and burned with the
+                // authorization of their registered minter
+                match self.ctx.read_post::<Address>(&minter_key(token))? {
+                    Some(minter) => Ok(verifiers.contains(&minter)),
+                    None => Ok(false),
+                }
+            }
             _ => {
                 // ERC20 and other tokens should not be minted by a wasm
                 // transaction
@@ -202,6 +267,109 @@ where
         }
     }
 
+    /// Return if the minter of the given token can be set or changed. The
+    /// minter of a user-issued token is designated by the token account.
+    pub fn is_valid_minter_update(
+        &self,
+        token: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        match token {
+            Address::Internal(InternalAddress::IbcToken(_)) => {
+                self.is_valid_minter(token, verifiers)
+            }
+            Address::Established(_) => Ok(verifiers.contains(token)
+                && self.ctx.has_key_post(&minter_key(token))?),
+            _ => Ok(false),
+        }
+    }
+
+    /// Return if the maximum supply of a user-issued token can be updated.
+    /// The token account has to authorize it and the new cap cannot be
+    /// lower than the supply that has already been minted.
+    pub fn is_valid_max_supply_update(
+        &self,
+        token: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        if !matches!(token, Address::Established(_))
+            || !verifiers.contains(token)
+        {
+            return Ok(false);
+        }
+        match self.ctx.read_post::<Amount>(&max_supply_key(token))? {
+            Some(max_supply) => {
+                let minted: Amount = self
+                    .ctx
+                    .read_post(&minted_balance_key(token))?
+                    .unwrap_or_default();
+                Ok(minted <= max_supply)
+            }
+            // The supply cap cannot be lifted once it has been set
+            None => Ok(false),
+        }
+    }
+
+    /// Return if the given minted supply of the token is within its maximum
+    /// supply, if any
+    pub fn is_within_max_supply(
+        &self,
+        token: &Address,
+        minted: &Amount,
+    ) -> Result<bool> {
+        Ok(self
+            .ctx
+            .read_post::<Amount>(&max_supply_key(token))?
+            .map_or(true, |max_supply| *minted <= max_supply))
+    }
+
+    /// Return if the denomination of the given token can be changed. It is
+    /// fixed once some of the token has been minted, since changing it would
+    /// change the value of the existing balances.
+    pub fn is_valid_denom_update(&self, token: &Address) -> Result<bool> {
+        let key = denom_key(token);
+        let pre = self.ctx.read_pre::<Denomination>(&key)?;
+        let post = self.ctx.read_post::<Denomination>(&key)?;
+        if pre == post {
+            return Ok(true);
+        }
+        let minted: Amount = self
+            .ctx
+            .read_pre(&minted_balance_key(token))?
+            .unwrap_or_default();
+        Ok(minted.is_zero())
+    }
+
+    /// Return if the metadata of the given token was updated by its minter or
+    /// via a governance proposal, and if it is consistent with the
+    /// denomination of the token and within the length limits
+    pub fn is_valid_metadata_update(
+        &self,
+        tx: &Tx,
+        token: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let metadata = match self
+            .ctx
+            .read_post::<TokenMetadata>(&metadata_key(token))?
+        {
+            Some(metadata) => metadata,
+            None => return Ok(false),
+        };
+        let denom = read_denom(&self.ctx.post(), token)?;
+        if !metadata.has_valid_symbol()
+            || !metadata.has_valid_lengths()
+            || denom != Some(metadata.denom)
+        {
+            return Ok(false);
+        }
+        let is_minter = self
+            .ctx
+            .read_post::<Address>(&minter_key(token))?
+            .map_or(false, |minter| verifiers.contains(&minter));
+        Ok(is_minter || self.is_valid_parameter(tx)?)
+    }
+
     /// Return if the parameter change was done via a governance proposal
     pub fn is_valid_parameter(&self, tx: &Tx) -> Result<bool> {
         match tx.data() {
@@ -210,6 +378,27 @@ where
             None => Ok(false),
         }
     }
+
+    /// Check that an update of a shielded rewards parameter keeps it in its
+    /// valid range. Other token parameters are not constrained.
+    pub fn is_valid_masp_parameter(&self, key: &Key) -> Result<bool> {
+        let parameter = match key.last() {
+            Some(DbKeySeg::StringSeg(parameter)) => parameter.as_str(),
+            _ => return Ok(true),
+        };
+        match parameter {
+            MASP_MAX_REWARD_RATE_KEY | MASP_KP_GAIN_KEY | MASP_KD_GAIN_KEY => {
+                Ok(self
+                    .ctx
+                    .read_post::<Dec>(key)?
+                    .map_or(false, |rate| MaspParams::is_valid_rate(&rate)))
+            }
+            MASP_LOCKED_AMOUNT_TARGET_KEY => {
+                Ok(self.ctx.read_post::<Amount>(key)?.is_some())
+            }
+            _ => Ok(true),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -225,17 +414,19 @@ mod tests {
     use super::*;
     use crate::core::types::address::nam;
     use crate::core::types::address::testing::{
-        established_address_1, established_address_2,
+        established_address_1, established_address_2, established_address_3,
     };
     use crate::ledger::gas::VpGasMeter;
     use crate::ledger::ibc::storage::ibc_token;
     use crate::token::storage_key::{
-        balance_key, minted_balance_key, minter_key,
+        allowance_key, balance_key, denom_key, masp_kd_gain_key,
+        masp_kp_gain_key, masp_max_reward_rate_key, max_supply_key,
+        metadata_key, minted_balance_key, minter_key,
     };
     use crate::token::Amount;
     use crate::types::address::{Address, InternalAddress};
     use crate::types::key::testing::keypair_1;
-    use crate::types::storage::TxIndex;
+    use crate::types::storage::{Epoch, TxIndex};
     use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
 
     const ADDRESS: Address = Address::Internal(InternalAddress::Multitoken);
@@ -255,6 +446,35 @@ mod tests {
         tx
     }
 
+    /// Validate the changed keys of the given storage with the multitoken VP
+    fn validate_changes(
+        wl_storage: &TestWlStorage,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> bool {
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            keys_changed,
+            verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        vp.validate_tx(&tx, keys_changed, verifiers)
+            .expect("validation failed")
+    }
+
     #[test]
     fn test_valid_transfer() {
         let mut wl_storage = TestWlStorage::default();
@@ -646,6 +866,330 @@ mod tests {
         );
     }
 
+    /// Mint the given amount of a user-issued token with the given maximum
+    /// supply and validate the tx
+    fn validate_user_token_mint(
+        max_supply: Amount,
+        amount: Amount,
+        is_minter_verifier: bool,
+    ) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        // registered user token
+        let token = established_address_2();
+        let minter = established_address_1();
+        wl_storage
+            .storage
+            .write(&minter_key(&token), minter.serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .storage
+            .write(&max_supply_key(&token), max_supply.serialize_to_vec())
+            .expect("write failed");
+
+        // mint
+        let target_key = balance_key(&token, &established_address_3());
+        wl_storage
+            .write_log
+            .write(&target_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(target_key);
+        let minted_key = minted_balance_key(&token);
+        wl_storage
+            .write_log
+            .write(&minted_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(minted_key);
+
+        let mut verifiers = BTreeSet::new();
+        if is_minter_verifier {
+            verifiers.insert(minter);
+        }
+        validate_changes(&wl_storage, &keys_changed, &verifiers)
+    }
+
+    #[test]
+    fn test_user_token_mint() {
+        let max_supply = Amount::native_whole(1000);
+        // the minter authorizes a mint within the maximum supply
+        assert!(validate_user_token_mint(
+            max_supply,
+            Amount::native_whole(100),
+            true
+        ));
+        // the minter didn't authorize the mint
+        assert!(!validate_user_token_mint(
+            max_supply,
+            Amount::native_whole(100),
+            false
+        ));
+        // the mint exceeds the maximum supply
+        assert!(!validate_user_token_mint(
+            max_supply,
+            Amount::native_whole(1001),
+            true
+        ));
+    }
+
+    #[test]
+    fn test_max_supply_update() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_2();
+        wl_storage
+            .storage
+            .write(
+                &minted_balance_key(&token),
+                Amount::native_whole(100).serialize_to_vec(),
+            )
+            .expect("write failed");
+        // lower the cap below the minted supply
+        let max_supply_key = max_supply_key(&token);
+        wl_storage
+            .write_log
+            .write(
+                &max_supply_key,
+                Amount::native_whole(10).serialize_to_vec(),
+            )
+            .expect("write failed");
+        keys_changed.insert(max_supply_key.clone());
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let mut verifiers = BTreeSet::new();
+        // for the token account
+        verifiers.insert(token.clone());
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        assert!(
+            !vp.is_valid_max_supply_update(&token, &verifiers)
+                .expect("validation failed")
+        );
+        assert!(
+            !vp.is_valid_max_supply_update(&token, &BTreeSet::new())
+                .expect("validation failed")
+        );
+    }
+
+    /// Update the metadata of a user-issued token and validate the tx
+    fn validate_metadata_update(
+        metadata: TokenMetadata,
+        is_minter_verifier: bool,
+    ) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_2();
+        let minter = established_address_1();
+        wl_storage
+            .storage
+            .write(&minter_key(&token), minter.serialize_to_vec())
+            .expect("write failed");
+        crate::token::write_denom(&mut wl_storage, &token, 6u8.into())
+            .expect("write failed");
+        wl_storage.commit_block().expect("commit failed");
+
+        let metadata_key = metadata_key(&token);
+        wl_storage
+            .write_log
+            .write(&metadata_key, metadata.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(metadata_key);
+
+        let mut verifiers = BTreeSet::new();
+        if is_minter_verifier {
+            verifiers.insert(minter);
+        }
+        validate_changes(&wl_storage, &keys_changed, &verifiers)
+    }
+
+    #[test]
+    fn test_metadata_update() {
+        let metadata = TokenMetadata {
+            symbol: "COMM".to_string(),
+            name: "Community token".to_string(),
+            denom: 6u8.into(),
+            icon_url: None,
+            description: None,
+            ibc_trace: None,
+        };
+        // the minter updates the metadata
+        assert!(validate_metadata_update(metadata.clone(), true));
+        // the minter didn't authorize the update
+        assert!(!validate_metadata_update(metadata.clone(), false));
+        // the denomination doesn't match the token's
+        let invalid_denom = TokenMetadata {
+            denom: 8u8.into(),
+            ..metadata.clone()
+        };
+        assert!(!validate_metadata_update(invalid_denom, true));
+        // the symbol is invalid
+        let invalid_symbol = TokenMetadata {
+            symbol: "COMM TOKEN".to_string(),
+            ..metadata.clone()
+        };
+        assert!(!validate_metadata_update(invalid_symbol, true));
+        // the fields are within their maximum lengths
+        let max_lengths = TokenMetadata {
+            name: "n".repeat(TokenMetadata::MAX_NAME_LEN),
+            description: Some("d".repeat(TokenMetadata::MAX_DESCRIPTION_LEN)),
+            icon_url: Some("i".repeat(TokenMetadata::MAX_ICON_URL_LEN)),
+            ibc_trace: Some("t".repeat(TokenMetadata::MAX_IBC_TRACE_LEN)),
+            ..metadata.clone()
+        };
+        assert!(validate_metadata_update(max_lengths, true));
+        // the name is too long
+        let long_name = TokenMetadata {
+            name: "n".repeat(TokenMetadata::MAX_NAME_LEN + 1),
+            ..metadata.clone()
+        };
+        assert!(!validate_metadata_update(long_name, true));
+        // the description is too long
+        let long_description = TokenMetadata {
+            description: Some(
+                "d".repeat(TokenMetadata::MAX_DESCRIPTION_LEN + 1),
+            ),
+            ..metadata
+        };
+        assert!(!validate_metadata_update(long_description, true));
+    }
+
+    /// Change the denomination of a user-issued token with the given minted
+    /// supply and validate the tx
+    fn validate_denom_update(minted: Amount, denom: Denomination) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_2();
+        let denom_key = denom_key(&token);
+        wl_storage
+            .storage
+            .write(&denom_key, Denomination(6).serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .storage
+            .write(&minted_balance_key(&token), minted.serialize_to_vec())
+            .expect("write failed");
+
+        wl_storage
+            .write_log
+            .write(&denom_key, denom.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(denom_key);
+
+        let verifiers = BTreeSet::from([token]);
+        validate_changes(&wl_storage, &keys_changed, &verifiers)
+    }
+
+    #[test]
+    fn test_denom_update() {
+        // the denomination can be changed before any mint
+        assert!(validate_denom_update(Amount::zero(), Denomination(8)));
+        // the denomination is fixed once the token has been minted
+        assert!(!validate_denom_update(
+            Amount::native_whole(100),
+            Denomination(8)
+        ));
+        // rewriting the same denomination is not a change
+        assert!(validate_denom_update(
+            Amount::native_whole(100),
+            Denomination(6)
+        ));
+    }
+
+    fn validate_allowance_change(
+        pre: Allowance,
+        post: Allowance,
+        debit: Amount,
+    ) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let owner = established_address_1();
+        let spender = established_address_2();
+        let receiver = established_address_3();
+        let owner_key = balance_key(&nam(), &owner);
+        let allowance_key = allowance_key(&nam(), &owner, &spender);
+        let balance = Amount::native_whole(100);
+        wl_storage
+            .storage
+            .write(&owner_key, balance.serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .storage
+            .write(&allowance_key, pre.serialize_to_vec())
+            .expect("write failed");
+
+        wl_storage
+            .write_log
+            .write(&allowance_key, post.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(allowance_key);
+        wl_storage
+            .write_log
+            .write(&owner_key, (balance - debit).serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(owner_key);
+        let receiver_key = balance_key(&nam(), &receiver);
+        wl_storage
+            .write_log
+            .write(&receiver_key, debit.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(receiver_key);
+
+        let verifiers = BTreeSet::from([owner, spender]);
+        validate_changes(&wl_storage, &keys_changed, &verifiers)
+    }
+
+    #[test]
+    fn test_allowance_spend() {
+        let allowance = |amount: u64, expiry: Option<u64>| Allowance {
+            amount: Amount::native_whole(amount),
+            expiry: expiry.map(Epoch),
+        };
+        // the spent allowance is transferred out of the owner's balance
+        assert!(validate_allowance_change(
+            allowance(50, Some(10)),
+            allowance(40, Some(10)),
+            Amount::native_whole(10),
+        ));
+        // the owner's balance is debited by less than the spent allowance
+        assert!(!validate_allowance_change(
+            allowance(50, None),
+            allowance(40, None),
+            Amount::native_whole(5),
+        ));
+        // the allowance has expired
+        assert!(!validate_allowance_change(
+            allowance(50, Some(0)),
+            allowance(40, Some(0)),
+            Amount::native_whole(10),
+        ));
+        // an update of the allowance is not a spend
+        assert!(validate_allowance_change(
+            allowance(50, None),
+            allowance(20, Some(10)),
+            Amount::zero(),
+        ));
+    }
+
     #[test]
     fn test_invalid_key_update() {
         let mut wl_storage = TestWlStorage::default();
@@ -688,4 +1232,66 @@ mod tests {
                 .expect("validation failed")
         );
     }
+
+    #[test]
+    fn test_masp_parameter_range() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let valid_key = masp_max_reward_rate_key(&nam());
+        wl_storage
+            .write_log
+            .write(&valid_key, Dec::new(1, 1).unwrap().serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(valid_key.clone());
+        let invalid_key = masp_kp_gain_key(&nam());
+        wl_storage
+            .write_log
+            .write(&invalid_key, Dec::new(-1, 0).unwrap().serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(invalid_key.clone());
+        let deleted_key = masp_kd_gain_key(&nam());
+        wl_storage
+            .storage
+            .write(&deleted_key, Dec::new(1, 1).unwrap().serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .write_log
+            .delete(&deleted_key)
+            .expect("delete failed");
+        keys_changed.insert(deleted_key.clone());
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let verifiers = BTreeSet::new();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        assert!(
+            vp.is_valid_masp_parameter(&valid_key)
+                .expect("validation failed")
+        );
+        assert!(
+            !vp.is_valid_masp_parameter(&invalid_key)
+                .expect("validation failed")
+        );
+        assert!(
+            !vp.is_valid_masp_parameter(&deleted_key)
+                .expect("validation failed")
+        );
+    }
 }
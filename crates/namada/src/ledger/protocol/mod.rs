@@ -1,4 +1,7 @@
 //! The ledger's protocol
+
+pub mod parallel;
+
 use std::collections::BTreeSet;
 
 use borsh_ext::BorshSerializeExt;
@@ -6,7 +9,12 @@ use eyre::{eyre, WrapErr};
 use masp_primitives::transaction::Transaction;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::Key;
-use namada_gas::TxGasMeter;
+use namada_gas::{Gas, TxGasMeter};
+use namada_parameters::storage::{
+    get_fee_unshielding_descriptions_limit_key,
+    get_fee_unshielding_gas_limit_key,
+};
+use namada_sdk::masp::masp_fee_payment_target;
 use namada_sdk::tx::TX_TRANSFER_WASM;
 use namada_state::wl_storage::WriteLogAndStorage;
 use namada_state::StorageRead;
@@ -31,10 +39,11 @@ use crate::ledger::native_vp::parameters::{self, ParametersVp};
 use crate::ledger::native_vp::{self, NativeVp};
 use crate::ledger::pgf::PgfVp;
 use crate::ledger::pos::{self, PosVP};
+use crate::ledger::storage_deposit;
 use crate::state::write_log::WriteLog;
 use crate::state::{DBIter, State, StorageHasher, WlStorage, DB};
 use crate::token::Amount;
-use crate::types::address::{Address, InternalAddress};
+use crate::types::address::{self, Address, InternalAddress};
 use crate::types::storage;
 use crate::types::storage::TxIndex;
 use crate::vm::wasm::{TxCache, VpCache};
@@ -259,6 +268,7 @@ where
 
     // Charge fee before performing any fallible operations
     charge_fee(
+        &tx,
         wrapper,
         fee_unshield_transaction,
         &mut shell_params,
@@ -275,8 +285,9 @@ where
     Ok(changed_keys)
 }
 
-/// Retrieve the Masp `Transaction` for fee unshielding from the provided
-/// transaction, if present
+/// Retrieve the Masp `Transaction` for fee payment from the provided
+/// transaction, if present: either the fee unshielding or the payment directly
+/// from the shielded pool (see [`pays_fee_from_shielded_pool`])
 pub fn get_fee_unshielding_transaction(
     tx: &Tx,
     wrapper: &WrapperTx,
@@ -293,6 +304,39 @@ pub fn get_fee_unshielding_transaction(
         })
 }
 
+/// Check if the provided Masp `Transaction` for fee payment pays the fee of
+/// the wrapper directly from the shielded pool, i.e. it unshields the fee to
+/// the [`masp_fee_payment_target`] of the wrapper rather than to its signer. A
+/// section taken from another wrapper doesn't qualify.
+pub fn pays_fee_from_shielded_pool(
+    tx: &Tx,
+    wrapper: &WrapperTx,
+    transaction: &Transaction,
+) -> bool {
+    let target = masp_fee_payment_target(tx, wrapper);
+    transaction.transparent_bundle().is_some_and(|bundle| {
+        !bundle.vout.is_empty()
+            && bundle.vout.iter().all(|out| out.address.0 == target.0)
+    })
+}
+
+/// Get the address of the account paying the fee of the provided wrapper
+/// transaction: the MASP if the fee is paid directly from the shielded pool,
+/// otherwise the implicit account associated with the public key
+pub fn fee_payer(
+    tx: &Tx,
+    wrapper: &WrapperTx,
+    masp_transaction: Option<&Transaction>,
+) -> Address {
+    if masp_transaction.is_some_and(|transaction| {
+        pays_fee_from_shielded_pool(tx, wrapper, transaction)
+    }) {
+        address::MASP
+    } else {
+        wrapper.fee_payer()
+    }
+}
+
 /// Charge fee for the provided wrapper transaction. In ABCI returns an error if
 /// the balance of the block proposer overflows. In ABCI plus returns error if:
 /// - The unshielding fails
@@ -300,8 +344,9 @@ pub fn get_fee_unshielding_transaction(
 /// - Not enough funds are available to pay the entire amount of the fee
 /// - The accumulated fee amount to be credited to the block proposer overflows
 fn charge_fee<'a, D, H, CA, WLS>(
+    tx: &Tx,
     wrapper: &WrapperTx,
-    masp_transaction: Option<Transaction>,
+    mut masp_transaction: Option<Transaction>,
     shell_params: &mut ShellParams<'a, CA, WLS>,
     changed_keys: &mut BTreeSet<Key>,
     wrapper_args: Option<&mut WrapperArgs>,
@@ -319,6 +364,21 @@ where
         tx_wasm_cache,
     } = shell_params;
 
+    let fee_payer = fee_payer(tx, wrapper, masp_transaction.as_ref());
+    // Pay fees directly from the shielded pool if requested. Contrary to the
+    // fee unshielding, this must fail if the masp section is invalid since the
+    // fee payer has no transparent funds to fall back to
+    let is_masp_fee_payment = fee_payer == address::MASP;
+    if is_masp_fee_payment {
+        apply_masp_fee_payment(
+            tx,
+            wrapper,
+            masp_transaction.take(),
+            *wl_storage,
+            vp_wasm_cache,
+        )?;
+    }
+
     // Unshield funds if requested
     let requires_fee_unshield = if let Some(transaction) = masp_transaction {
         // The unshielding tx does not charge gas, instantiate a
@@ -392,8 +452,8 @@ where
         Some(WrapperArgs {
             block_proposer,
             is_committed_fee_unshield: _,
-        }) => transfer_fee(*wl_storage, block_proposer, wrapper)?,
-        None => check_fees(*wl_storage, wrapper)?,
+        }) => transfer_fee(*wl_storage, block_proposer, wrapper, &fee_payer)?,
+        None => check_fees(*wl_storage, wrapper, &fee_payer)?,
     }
 
     changed_keys.extend(wl_storage.write_log_mut().get_keys_with_precommit());
@@ -402,30 +462,128 @@ where
     wl_storage.write_log_mut().commit_tx();
     // Update the flag only after the fee payment has been committed
     if let Some(args) = wrapper_args {
-        args.is_committed_fee_unshield = requires_fee_unshield;
+        args.is_committed_fee_unshield =
+            requires_fee_unshield || is_masp_fee_payment;
     }
 
     Ok(())
 }
 
+/// Apply the masp section paying the fee of the wrapper directly from the
+/// shielded pool. The changes to the shielded state are written to the tx
+/// write log and validated by the MASP VP in the same pass, after which the
+/// fee can be moved from the MASP to the block proposer like any transparent
+/// fee. Returns an error if the section is missing or invalid, or if it doesn't
+/// unshield the fee to the [`masp_fee_payment_target`] of the wrapper of the
+/// given tx, in which case the tx write log is dropped.
+pub fn apply_masp_fee_payment<D, H, CA, WLS>(
+    tx: &Tx,
+    wrapper: &WrapperTx,
+    masp_transaction: Option<Transaction>,
+    wl_storage: &mut WLS,
+    vp_wasm_cache: &mut VpCache<CA>,
+) -> Result<()>
+where
+    CA: 'static + WasmCacheAccess + Sync,
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    WLS: WriteLogAndStorage<D = D, H = H> + StorageRead,
+{
+    let transaction = masp_transaction.ok_or_else(|| {
+        Error::FeeError("Missing the masp section for fee payment".to_string())
+    })?;
+    let descriptions_limit = wl_storage
+        .read(&get_fee_unshielding_descriptions_limit_key())
+        .expect("Error reading the storage")
+        .expect("Missing fee unshielding descriptions limit param in storage");
+    let fee_tx = wrapper
+        .check_and_generate_masp_fee_payment(
+            descriptions_limit,
+            transaction.clone(),
+        )
+        .map_err(Error::FeeUnshieldingError)?;
+    let fee = wrapper.get_tx_fee().map_err(Error::FeeUnshieldingError)?;
+    let fee = crate::token::denom_to_amount(fee, &wrapper.fee.token, wl_storage)
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    // The validation does not charge gas, instantiate a custom gas meter for
+    // this step
+    let tx_gas_meter = TxGasMeter::new(GasLimit::from(
+        wl_storage
+            .read::<u64>(&get_fee_unshielding_gas_limit_key())
+            .expect("Error reading the storage")
+            .expect("Missing fee unshielding gas limit in storage"),
+    ));
+
+    // NOTE: A clean tx write log must be provided to the MASP VP for a correct
+    // validation. Block write log, instead, should contain any prior changes
+    // (if any)
+    wl_storage.write_log_mut().precommit_tx();
+    let writes = crate::token::utils::masp_tx_writes(wl_storage, &transaction)
+        .map_err(Error::StorageError)?;
+    for (key, value) in writes {
+        wl_storage
+            .write_log_mut()
+            .write(&key, value)
+            .map_err(|e| Error::FeeError(e.to_string()))?;
+    }
+
+    let accepted = {
+        let (write_log, storage) = wl_storage.split_borrow();
+        let (verifiers, keys_changed) =
+            write_log.verifiers_and_changed_keys(&BTreeSet::new());
+        let masp = MaspVp {
+            ctx: native_vp::Ctx::new(
+                &Address::Internal(InternalAddress::Masp),
+                storage,
+                write_log,
+                &fee_tx,
+                &TxIndex::default(),
+                VpGasMeter::new_from_tx_meter(&tx_gas_meter),
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache.clone(),
+            ),
+        };
+        masp.validate_fee_payment(
+            &fee_tx,
+            &keys_changed,
+            &masp_fee_payment_target(tx, wrapper),
+            &wrapper.fee.token,
+            fee,
+        )
+    };
+    match accepted {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            wl_storage.write_log_mut().drop_tx_keep_precommit();
+            Err(Error::FeeError(
+                "The MASP VP rejected the fee payment".to_string(),
+            ))
+        }
+        Err(e) => {
+            wl_storage.write_log_mut().drop_tx_keep_precommit();
+            Err(Error::MaspNativeVpError(e))
+        }
+    }
+}
+
 /// Perform the actual transfer of fess from the fee payer to the block
 /// proposer.
 pub fn transfer_fee<WLS>(
     wl_storage: &mut WLS,
     block_proposer: &Address,
     wrapper: &WrapperTx,
+    fee_payer: &Address,
 ) -> Result<()>
 where
     WLS: WriteLogAndStorage + StorageRead,
 {
-    let balance = crate::token::read_balance(
-        wl_storage,
-        &wrapper.fee.token,
-        &wrapper.fee_payer(),
-    )
-    .unwrap();
+    let balance =
+        crate::token::read_balance(wl_storage, &wrapper.fee.token, fee_payer)
+            .unwrap();
+    let refunded_gas = refunded_gas(wl_storage, wrapper, fee_payer)?;
 
-    match wrapper.get_tx_fee() {
+    match wrapper.get_tx_fee_after_refund(refunded_gas) {
         Ok(fees) => {
             let fees = crate::token::denom_to_amount(
                 fees,
@@ -437,11 +595,12 @@ where
                 token_transfer(
                     wl_storage,
                     &wrapper.fee.token,
-                    &wrapper.fee_payer(),
+                    fee_payer,
                     block_proposer,
                     fees,
                 )
-                .map_err(|e| Error::FeeError(e.to_string()))
+                .map_err(|e| Error::FeeError(e.to_string()))?;
+                spend_refunded_gas(wl_storage, fee_payer, refunded_gas)
             } else {
                 // Balance was insufficient for fee payment, move all the
                 // available funds in the transparent balance of
@@ -456,7 +615,7 @@ where
                 token_transfer(
                     wl_storage,
                     &wrapper.fee.token,
-                    &wrapper.fee_payer(),
+                    fee_payer,
                     block_proposer,
                     balance,
                 )
@@ -483,6 +642,51 @@ where
     }
 }
 
+/// Get the whole gas units of the gas limit of the given wrapper that are
+/// covered by the storage refunds of its fee payer
+fn refunded_gas<WLS>(
+    wl_storage: &WLS,
+    wrapper: &WrapperTx,
+    fee_payer: &Address,
+) -> Result<u64>
+where
+    WLS: StorageRead,
+{
+    let refund = storage_deposit::read_refund(wl_storage, fee_payer)
+        .map_err(Error::StorageError)?;
+    Ok(refund
+        .get_whole_gas_units_floor()
+        .min(u64::from(wrapper.gas_limit)))
+}
+
+/// Deduct the given whole gas units, used to pay the fee of a wrapper, from
+/// the storage refunds of the fee payer. Like [`token_transfer`], this
+/// updates the tx write log.
+fn spend_refunded_gas<WLS>(
+    wl_storage: &mut WLS,
+    fee_payer: &Address,
+    refunded_gas: u64,
+) -> Result<()>
+where
+    WLS: WriteLogAndStorage + StorageRead,
+{
+    if refunded_gas == 0 {
+        return Ok(());
+    }
+    let refund = storage_deposit::read_refund(wl_storage, fee_payer)
+        .map_err(Error::StorageError)?
+        .checked_sub(Gas::from_whole_units(refunded_gas))
+        .unwrap_or_default();
+    wl_storage
+        .write_log_mut()
+        .write(
+            &storage_deposit::refund_key(fee_payer),
+            refund.serialize_to_vec(),
+        )
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    Ok(())
+}
+
 /// Transfer `token` from `src` to `dest`. Returns an `Err` if `src` has
 /// insufficient balance or if the transfer the `dest` would overflow (This can
 /// only happen if the total supply doesn't fit in `token::Amount`). Contrary to
@@ -535,19 +739,20 @@ where
 }
 
 /// Check if the fee payer has enough transparent balance to pay fees
-pub fn check_fees<WLS>(wl_storage: &WLS, wrapper: &WrapperTx) -> Result<()>
+pub fn check_fees<WLS>(
+    wl_storage: &WLS,
+    wrapper: &WrapperTx,
+    fee_payer: &Address,
+) -> Result<()>
 where
     WLS: WriteLogAndStorage + StorageRead,
 {
-    let balance = crate::token::read_balance(
-        wl_storage,
-        &wrapper.fee.token,
-        &wrapper.fee_payer(),
-    )
-    .unwrap();
+    let balance =
+        crate::token::read_balance(wl_storage, &wrapper.fee.token, fee_payer)
+            .unwrap();
 
     let fees = wrapper
-        .get_tx_fee()
+        .get_tx_fee_after_refund(refunded_gas(wl_storage, wrapper, fee_payer)?)
         .map_err(|e| Error::FeeError(e.to_string()))?;
 
     let fees =
@@ -843,6 +1048,11 @@ where
     H: 'static + StorageHasher + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
+    // The VPs share the values they read from storage. The cache is cleared
+    // between transactions to bound its size, but since it only holds
+    // committed values it's also safe to share with the transactions executed
+    // speculatively in parallel
+    storage.pre_state_cache.clear();
     let vps_result = verifiers
         .par_iter()
         .try_fold(VpsResult::default, |mut result, addr| {
@@ -1112,9 +1322,10 @@ where
         })
         .try_reduce(VpsResult::default, |a, b| {
             merge_vp_results(a, b, tx_gas_meter)
-        })?;
+        });
+    storage.pre_state_cache.clear();
 
-    Ok(vps_result)
+    vps_result
 }
 
 /// Merge VP results from parallel runs
@@ -1157,6 +1368,7 @@ mod tests {
         EthereumEvent, TransferToNamada,
     };
     use namada_core::types::keccak::keccak_hash;
+    use namada_core::types::key::RefTo;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_core::types::{address, key};
@@ -1167,11 +1379,14 @@ mod tests {
     use namada_ethereum_bridge::storage::proof::EthereumProof;
     use namada_ethereum_bridge::storage::{vote_tallies, vp};
     use namada_ethereum_bridge::test_utils;
-    use namada_state::StorageRead;
+    use namada_sdk::masp::testing::arb_deshielding_transfer;
+    use namada_state::testing::TestWlStorage;
+    use namada_state::{StorageRead, StorageWrite};
     use namada_token::Amount;
     use namada_tx::{SignableEthMessage, Signed};
     use namada_vote_ext::bridge_pool_roots::BridgePoolRootVext;
     use namada_vote_ext::ethereum_events::EthereumEventsVext;
+    use proptest::prelude::*;
 
     use super::*;
 
@@ -1349,4 +1564,205 @@ mod tests {
             }
         }
     }
+
+    // Make a wrapper tx signed with the given key, paying its fee from the
+    // shielded pool
+    fn masp_fee_wrapper(
+        pk: key::common::PublicKey,
+        gas_limit: u64,
+        chain_id: ChainId,
+    ) -> (Tx, WrapperTx) {
+        let wrapper = WrapperTx::new(
+            namada_tx::data::Fee {
+                amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                token: address::nam(),
+            },
+            pk,
+            0.into(),
+            gas_limit.into(),
+            Some(Hash([1; 32])),
+        );
+        let mut tx = Tx::new(chain_id, None);
+        tx.update_header(TxType::Wrapper(Box::new(wrapper.clone())));
+        (tx, wrapper)
+    }
+
+    // Credit the given balance and storage refund to the fee payer of the
+    // given wrapper
+    fn fund_fee_payer(
+        wl_storage: &mut TestWlStorage,
+        wrapper: &WrapperTx,
+        balance: u64,
+        refund: Gas,
+    ) {
+        let fee_payer = wrapper.fee_payer();
+        crate::token::credit_tokens(
+            wl_storage,
+            &wrapper.fee.token,
+            &fee_payer,
+            Amount::from(balance),
+        )
+        .unwrap();
+        wl_storage
+            .write(&storage_deposit::refund_key(&fee_payer), refund)
+            .unwrap();
+        wl_storage.commit_tx();
+    }
+
+    #[test]
+    fn test_check_fees_after_refund() {
+        let (_, wrapper) = masp_fee_wrapper(
+            key::testing::keypair_1().ref_to(),
+            20_000,
+            ChainId::default(),
+        );
+        let fee_payer = wrapper.fee_payer();
+
+        // The balance doesn't cover the fee of the whole gas limit
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        fund_fee_payer(&mut wl_storage, &wrapper, 15_000, Gas::default());
+        assert_matches!(
+            check_fees(&wl_storage, &wrapper, &fee_payer),
+            Err(Error::FeeError(_))
+        );
+
+        // The refund covers the rest of the fee
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        fund_fee_payer(
+            &mut wl_storage,
+            &wrapper,
+            15_000,
+            Gas::from_whole_units(5_000),
+        );
+        assert!(check_fees(&wl_storage, &wrapper, &fee_payer).is_ok());
+    }
+
+    #[test]
+    fn test_transfer_fee_after_refund() {
+        let (_, wrapper) = masp_fee_wrapper(
+            key::testing::keypair_1().ref_to(),
+            20_000,
+            ChainId::default(),
+        );
+        let fee_payer = wrapper.fee_payer();
+        let block_proposer = address::testing::established_address_4();
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        fund_fee_payer(
+            &mut wl_storage,
+            &wrapper,
+            20_000,
+            Gas::from_whole_units(5_000)
+                .checked_add(Gas::from(1))
+                .unwrap(),
+        );
+
+        // Only the gas not covered by the refund is charged
+        transfer_fee(&mut wl_storage, &block_proposer, &wrapper, &fee_payer)
+            .unwrap();
+        wl_storage.commit_tx();
+        let balance = |owner| {
+            crate::token::read_balance(&wl_storage, &wrapper.fee.token, owner)
+                .unwrap()
+        };
+        assert_eq!(balance(&fee_payer), Amount::from(5_000));
+        assert_eq!(balance(&block_proposer), Amount::from(15_000));
+
+        // The whole gas units used are deducted from the refund
+        assert_eq!(
+            storage_deposit::read_refund(&wl_storage, &fee_payer).unwrap(),
+            Gas::from(1)
+        );
+    }
+
+    #[test]
+    fn test_spend_refunded_gas() {
+        let fee_payer = address::testing::established_address_4();
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        wl_storage
+            .write(
+                &storage_deposit::refund_key(&fee_payer),
+                Gas::from_whole_units(10),
+            )
+            .unwrap();
+        wl_storage.commit_tx();
+
+        // Nothing is written without refunded gas
+        spend_refunded_gas(&mut wl_storage, &fee_payer, 0).unwrap();
+        assert!(wl_storage.write_log.get_keys().is_empty());
+
+        spend_refunded_gas(&mut wl_storage, &fee_payer, 4).unwrap();
+        wl_storage.commit_tx();
+        assert_eq!(
+            storage_deposit::read_refund(&wl_storage, &fee_payer).unwrap(),
+            Gas::from_whole_units(6)
+        );
+
+        // The refund can't go below zero
+        spend_refunded_gas(&mut wl_storage, &fee_payer, 10).unwrap();
+        wl_storage.commit_tx();
+        assert_eq!(
+            storage_deposit::read_refund(&wl_storage, &fee_payer).unwrap(),
+            Gas::default()
+        );
+    }
+
+    // The wrapper of the victim of a front-running attempt
+    fn victim_wrapper() -> (Tx, WrapperTx) {
+        masp_fee_wrapper(
+            key::testing::keypair_1().ref_to(),
+            20_000,
+            ChainId::default(),
+        )
+    }
+
+    proptest! {
+        /// Test that a masp section paying the fee of a wrapper directly from
+        /// the shielded pool is only recognized as such for the wrapper it
+        /// commits to. Re-attached to another wrapper, the section would have
+        /// to unshield the fee to the signer of that wrapper instead, which
+        /// the MASP VP rejects.
+        #[test]
+        fn test_reattached_masp_fee_payment(
+            (transfer, _) in arb_deshielding_transfer(
+                masp_fee_payment_target(
+                    &victim_wrapper().0,
+                    &victim_wrapper().1,
+                ),
+                1..3,
+            ),
+        ) {
+            let masp_tx = transfer.masp_tx;
+            let (tx, wrapper) = victim_wrapper();
+            assert!(pays_fee_from_shielded_pool(&tx, &wrapper, &masp_tx));
+            assert_eq!(fee_payer(&tx, &wrapper, Some(&masp_tx)), address::MASP);
+
+            // Re-attached to the wrapper of another signer
+            let attacker_pk = key::testing::keypair_2().ref_to();
+            let (tx, wrapper) = masp_fee_wrapper(
+                attacker_pk.clone(),
+                20_000,
+                ChainId::default(),
+            );
+            assert!(!pays_fee_from_shielded_pool(&tx, &wrapper, &masp_tx));
+            assert_eq!(
+                fee_payer(&tx, &wrapper, Some(&masp_tx)),
+                Address::from(&attacker_pk),
+            );
+
+            // Re-attached to a wrapper of the same signer with another gas
+            // limit or chain id
+            let (tx, wrapper) = masp_fee_wrapper(
+                key::testing::keypair_1().ref_to(),
+                40_000,
+                ChainId::default(),
+            );
+            assert!(!pays_fee_from_shielded_pool(&tx, &wrapper, &masp_tx));
+            let (tx, wrapper) = masp_fee_wrapper(
+                key::testing::keypair_1().ref_to(),
+                20_000,
+                ChainId("other-chain".to_string()),
+            );
+            assert!(!pays_fee_from_shielded_pool(&tx, &wrapper, &masp_tx));
+        }
+    }
 }
@@ -0,0 +1,157 @@
+//! Optimistic parallel execution of the decrypted transactions of a block.
+//!
+//! The transactions are first executed speculatively in parallel, each one on
+//! its own fork of the block write log. They are then applied in block order:
+//! the result of a speculative execution is kept only if none of the storage
+//! read by the transaction and its VPs has been modified since the fork,
+//! otherwise the transaction is executed again on the block write log. Either
+//! way, the result is the same as the one of a sequential execution.
+
+use std::collections::HashMap;
+
+use namada_gas::{Gas, TxGasMeter};
+use namada_state::TempWlStorage;
+use namada_tx::data::TxResult;
+use namada_tx::Tx;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::{apply_wasm_tx, Result, ShellParams};
+use crate::state::write_log::WriteLog;
+use crate::state::{DBIter, StorageHasher, WlStorage, DB};
+use crate::types::storage::TxIndex;
+use crate::vm::wasm::{TxCache, VpCache};
+use crate::vm::WasmCacheAccess;
+
+/// A decrypted transaction to execute speculatively
+#[derive(Debug)]
+pub struct SpeculativeTx {
+    /// The decrypted transaction
+    pub tx: Tx,
+    /// The index of the transaction in the block
+    pub tx_index: TxIndex,
+    /// The gas available to the transaction, in sub units
+    pub gas_limit: Gas,
+}
+
+/// The outcome of the speculative execution of a transaction
+struct Speculation {
+    /// The fork of the block write log the transaction was executed on
+    write_log: WriteLog,
+    /// The gas meter of the transaction
+    tx_gas_meter: TxGasMeter,
+    /// The result of the transaction
+    result: Result<TxResult>,
+}
+
+/// The decrypted transactions of a block executed speculatively
+#[derive(Default)]
+pub struct SpeculativeBlock {
+    /// The block write log the transactions were executed on a fork of
+    base: Option<WriteLog>,
+    /// The speculative executions by transaction index
+    speculations: HashMap<TxIndex, Speculation>,
+}
+
+impl SpeculativeBlock {
+    /// Speculatively execute the given transactions in parallel, each one on
+    /// a fork of the current block write log.
+    pub fn execute<D, H, CA>(
+        txs: Vec<SpeculativeTx>,
+        wl_storage: &WlStorage<D, H>,
+        vp_wasm_cache: &VpCache<CA>,
+        tx_wasm_cache: &TxCache<CA>,
+    ) -> Self
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        // Nothing can run in parallel with a single transaction
+        if txs.len() < 2 {
+            return Self::default();
+        }
+        let base = wl_storage.write_log.clone();
+        let speculations = txs
+            .into_par_iter()
+            .map(
+                |SpeculativeTx {
+                     tx,
+                     tx_index,
+                     gas_limit,
+                 }| {
+                    let mut temp_wl_storage = TempWlStorage {
+                        write_log: base.fork(),
+                        storage: &wl_storage.storage,
+                    };
+                    let mut tx_gas_meter =
+                        TxGasMeter::new_from_sub_limit(gas_limit);
+                    let result = apply_wasm_tx(
+                        tx,
+                        &tx_index,
+                        ShellParams::new(
+                            &mut tx_gas_meter,
+                            &mut temp_wl_storage,
+                            &mut vp_wasm_cache.clone(),
+                            &mut tx_wasm_cache.clone(),
+                        ),
+                    );
+                    let speculation = Speculation {
+                        write_log: temp_wl_storage.write_log,
+                        tx_gas_meter,
+                        result,
+                    };
+                    (tx_index, speculation)
+                },
+            )
+            .collect();
+        Self {
+            base: Some(base),
+            speculations,
+        }
+    }
+
+    /// Apply the decrypted transaction at the given index to the block write
+    /// log. The result of its speculative execution is used if it's still
+    /// valid, otherwise the transaction is executed again.
+    pub fn apply<D, H, CA>(
+        &mut self,
+        tx: Tx,
+        tx_index: TxIndex,
+        tx_gas_meter: &mut TxGasMeter,
+        wl_storage: &mut WlStorage<D, H>,
+        vp_wasm_cache: &mut VpCache<CA>,
+        tx_wasm_cache: &mut TxCache<CA>,
+    ) -> Result<TxResult>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        if let (Some(base), Some(speculation)) =
+            (&self.base, self.speculations.remove(&tx_index))
+        {
+            if speculation.tx_gas_meter.tx_gas_limit
+                == tx_gas_meter.tx_gas_limit
+                && wl_storage.write_log.merge_fork(base, speculation.write_log)
+            {
+                *tx_gas_meter = speculation.tx_gas_meter;
+                return speculation.result;
+            }
+            tracing::debug!(
+                "Re-executing the transaction at index {} that conflicts \
+                 with a previous transaction of the block",
+                tx_index.0
+            );
+        }
+        apply_wasm_tx(
+            tx,
+            &tx_index,
+            ShellParams::new(
+                tx_gas_meter,
+                wl_storage,
+                vp_wasm_cache,
+                tx_wasm_cache,
+            ),
+        )
+    }
+}
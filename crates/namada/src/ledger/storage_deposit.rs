@@ -0,0 +1,144 @@
+//! Storage deposits and refunds.
+//!
+//! The gas of a storage write includes the cost of occupying the written
+//! bytes. The bytes of the values occupied by the transactions of an account
+//! are tracked as its storage deposit. When the account frees some of them by
+//! deleting or shrinking values, a part of their occupation cost is credited
+//! back to it as gas, which reduces the fees of its next transactions.
+//!
+//! The bytes occupied and freed by a transaction are the net size difference
+//! accumulated by the write log while the transaction writes and deletes keys,
+//! which meters the lookup of the prior size of each modified key.
+//!
+//! The deposits and refunds are stored under the parameters address, whose VP
+//! rejects any modification of these keys by a transaction.
+//!
+//! Only the keys written by the transactions are deposited. The MASP pin keys
+//! are written by the transfers and are deposited like any other key. The
+//! replay protection entries of the transactions are written by the protocol
+//! and are neither deposited nor refunded, so their storage growth is not
+//! addressed by the deposits.
+
+use namada_core::types::address::Address;
+use namada_core::types::storage::{Key, KeySeg};
+use namada_gas::{Gas, STORAGE_REFUND_GAS_PER_BYTE};
+use namada_parameters::ADDRESS;
+use namada_state::wl_storage::WriteLogAndStorage;
+use namada_state::{StorageRead, StorageResult, StorageWrite};
+
+const DEPOSIT_KEY_SEGMENT: &str = "storage_deposit";
+const REFUND_KEY_SEGMENT: &str = "storage_refund";
+
+/// Storage key of the bytes of storage deposited by the given account
+pub fn deposit_key(owner: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DEPOSIT_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&owner.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of the gas refunded to the given account for the storage it
+/// freed and not yet spent on fees
+pub fn refund_key(owner: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&REFUND_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&owner.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Read the bytes of storage deposited by the given account
+pub fn read_deposit<S>(storage: &S, owner: &Address) -> StorageResult<u64>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&deposit_key(owner))?.unwrap_or_default())
+}
+
+/// Read the gas refunded to the given account and not yet spent on fees
+pub fn read_refund<S>(storage: &S, owner: &Address) -> StorageResult<Gas>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&refund_key(owner))?.unwrap_or_default())
+}
+
+/// Update the storage deposit of the given account with the storage occupied
+/// or freed by the current transaction. The freed bytes that were deposited
+/// by the account are refunded to it.
+pub fn settle_tx_storage<S>(
+    wl_storage: &mut S,
+    owner: &Address,
+) -> StorageResult<()>
+where
+    S: WriteLogAndStorage + StorageRead + StorageWrite,
+{
+    let size_diff = wl_storage.write_log().tx_size_diff();
+    let occupied = size_diff.max(0).unsigned_abs();
+    let freed = size_diff.min(0).unsigned_abs();
+    let deposit = read_deposit(wl_storage, owner)?.saturating_add(occupied);
+    let refunded = freed.min(deposit);
+    if occupied == 0 && refunded == 0 {
+        return Ok(());
+    }
+
+    wl_storage.write(&deposit_key(owner), deposit - refunded)?;
+    if refunded > 0 {
+        let refund = read_refund(wl_storage, owner)?
+            .checked_add(Gas::from(
+                refunded.saturating_mul(STORAGE_REFUND_GAS_PER_BYTE),
+            ))
+            .unwrap_or_else(|| Gas::from(u64::MAX));
+        wl_storage.write(&refund_key(owner), refund)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_state::testing::TestWlStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_settle_tx_storage() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = established_address_1();
+        let key = Key::parse("key").unwrap();
+
+        // Writing a new key deposits the size of its value
+        wl_storage.write_log.record_prior_size(0);
+        wl_storage.write_log.write(&key, vec![0_u8; 10]).unwrap();
+        settle_tx_storage(&mut wl_storage, &owner).unwrap();
+        wl_storage.commit_tx();
+        assert_eq!(read_deposit(&wl_storage, &owner).unwrap(), 10);
+        assert_eq!(read_refund(&wl_storage, &owner).unwrap(), Gas::default());
+
+        // Shrinking the value refunds the freed bytes, also when the value is
+        // modified more than once
+        wl_storage.write_log.record_prior_size(10);
+        wl_storage.write_log.write(&key, vec![0_u8; 8]).unwrap();
+        wl_storage.write_log.write(&key, vec![0_u8; 4]).unwrap();
+        settle_tx_storage(&mut wl_storage, &owner).unwrap();
+        wl_storage.commit_tx();
+        assert_eq!(read_deposit(&wl_storage, &owner).unwrap(), 4);
+        assert_eq!(
+            read_refund(&wl_storage, &owner).unwrap(),
+            Gas::from(6 * STORAGE_REFUND_GAS_PER_BYTE)
+        );
+
+        // Another account deleting the key only gets a refund for the bytes
+        // it deposited
+        let other = established_address_2();
+        wl_storage.write_log.record_prior_size(4);
+        wl_storage.write_log.delete(&key).unwrap();
+        settle_tx_storage(&mut wl_storage, &other).unwrap();
+        wl_storage.commit_tx();
+        assert_eq!(read_deposit(&wl_storage, &other).unwrap(), 0);
+        assert_eq!(read_refund(&wl_storage, &other).unwrap(), Gas::default());
+    }
+}
@@ -95,8 +95,10 @@ where
         }
         None => {
             // When not found in write log, try to read from the storage
-            let (value, gas) =
-                storage.read(key).map_err(RuntimeError::StorageError)?;
+            // through the cache shared by the VPs
+            let (value, gas) = storage
+                .read_cached(key)
+                .map_err(RuntimeError::StorageError)?;
             add_gas(gas_meter, gas, sentinel)?;
             Ok(value)
         }
@@ -138,8 +140,10 @@ where
         }
         None => {
             // When not found in write log, try to read from the storage
-            let (value, gas) =
-                storage.read(key).map_err(RuntimeError::StorageError)?;
+            // through the cache shared by the VPs
+            let (value, gas) = storage
+                .read_cached(key)
+                .map_err(RuntimeError::StorageError)?;
             add_gas(gas_meter, gas, sentinel)?;
             Ok(value)
         }
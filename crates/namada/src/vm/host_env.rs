@@ -7,6 +7,7 @@ use std::num::TryFromIntError;
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
+use namada_core::types::account::AccountWeights;
 use namada_core::types::address::ESTABLISHED_ADDRESS_BYTES_LEN;
 use namada_core::types::internal::KeyVal;
 use namada_core::types::storage::{Epochs, TX_INDEX_LENGTH};
@@ -513,6 +514,41 @@ where
     })
 }
 
+/// Record in the write log the size of the value of the given key prior to its
+/// first modification by the current transaction, reading it from storage if
+/// the write log doesn't have it. Returns the gas cost of the reads.
+fn tx_record_prior_size<'a, DB, H, CA>(
+    ctx: &TxCtx<'a, DB, H, CA>,
+    key: &Key,
+) -> std::result::Result<u64, namada_state::Error>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let write_log = unsafe { ctx.write_log.get() };
+    if write_log.has_tx_modification(key) {
+        return Ok(0);
+    }
+    let (prior, mut gas) = write_log.read(key);
+    let size = match prior {
+        Some(write_log::StorageModification::Write { value }) => value.len(),
+        Some(write_log::StorageModification::InitAccount { vp_code_hash }) => {
+            vp_code_hash.len()
+        }
+        Some(write_log::StorageModification::Delete)
+        | Some(write_log::StorageModification::Temp { .. }) => 0,
+        None => {
+            let storage = unsafe { ctx.storage.get() };
+            let (value, read_gas) = storage.read(key)?;
+            gas += read_gas;
+            value.map_or(0, |value| value.len())
+        }
+    };
+    write_log.record_prior_size(size as u64);
+    Ok(gas)
+}
+
 /// Called from VP wasm to request to use the given gas amount
 pub fn vp_charge_gas<MEM, DB, H, EVAL, CA>(
     env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
@@ -848,6 +884,9 @@ where
 
     check_address_existence(env, &key)?;
 
+    let gas = tx_record_prior_size(&env.ctx, &key)
+        .map_err(TxRuntimeError::StateError)?;
+    tx_charge_gas(env, gas)?;
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .write(&key, value)
@@ -976,6 +1015,9 @@ where
         return Err(TxRuntimeError::CannotDeleteVp);
     }
 
+    let gas = tx_record_prior_size(&env.ctx, &key)
+        .map_err(TxRuntimeError::StateError)?;
+    tx_charge_gas(env, gas)?;
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .delete(&key)
@@ -1481,6 +1523,9 @@ where
 
     tx_validate_vp_code_hash(env, &code_hash, &code_tag)?;
 
+    let gas = tx_record_prior_size(&env.ctx, &key)
+        .map_err(TxRuntimeError::StateError)?;
+    tx_charge_gas(env, gas)?;
     let write_log = unsafe { env.ctx.write_log.get() };
     let (gas, _size_diff) = write_log
         .write(&key, code_hash)
@@ -2011,7 +2056,103 @@ where
         &Some(signer),
         threshold,
         max_signatures,
-        || gas_meter.consume(gas::VERIFY_TX_SIG_GAS),
+        |gas| gas_meter.consume(gas),
+    ) {
+        Ok(_) => Ok(HostEnvResult::Success.to_i64()),
+        Err(err) => match err {
+            namada_tx::VerifySigError::Gas(inner) => {
+                sentinel.set_out_of_gas();
+                Err(vp_host_fns::RuntimeError::OutOfGas(inner))
+            }
+            namada_tx::VerifySigError::InvalidSectionSignature(_) => {
+                sentinel.set_invalid_signature();
+                Ok(HostEnvResult::Fail.to_i64())
+            }
+            _ => Ok(HostEnvResult::Fail.to_i64()),
+        },
+    }
+}
+
+/// Verify a transaction signature against the weights of the signer's keys.
+/// Like [`vp_verify_tx_section_signature`], which is kept unchanged for the
+/// existing VPs, this is a workaround to track gas for multiple signature
+/// verifications.
+#[allow(clippy::too_many_arguments)]
+pub fn vp_verify_tx_section_weighted_signature<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    hash_list_ptr: u64,
+    hash_list_len: u64,
+    public_keys_map_ptr: u64,
+    public_keys_map_len: u64,
+    signer_ptr: u64,
+    signer_len: u64,
+    weights_ptr: u64,
+    weights_len: u64,
+    max_signatures_ptr: u64,
+    max_signatures_len: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (hash_list, gas) = env
+        .memory
+        .read_bytes(hash_list_ptr, hash_list_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    let hashes = <[Hash; 1]>::try_from_slice(&hash_list)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (public_keys_map, gas) = env
+        .memory
+        .read_bytes(public_keys_map_ptr, public_keys_map_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    let public_keys_map =
+        namada_core::types::account::AccountPublicKeysMap::try_from_slice(
+            &public_keys_map,
+        )
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (signer, gas) = env
+        .memory
+        .read_bytes(signer_ptr, signer_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    let signer = Address::try_from_slice(&signer)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (weights, gas) = env
+        .memory
+        .read_bytes(weights_ptr, weights_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    let weights = AccountWeights::try_from_slice(&weights)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (max_signatures, gas) = env
+        .memory
+        .read_bytes(max_signatures_ptr, max_signatures_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    let max_signatures = Option::<u8>::try_from_slice(&max_signatures)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let tx = unsafe { env.ctx.tx.get() };
+
+    match tx.verify_weighted_signatures(
+        &hashes,
+        public_keys_map,
+        &Some(signer),
+        &weights,
+        max_signatures,
+        |gas| gas_meter.consume(gas),
     ) {
         Ok(_) => Ok(HostEnvResult::Success.to_i64()),
         Err(err) => match err {
@@ -2219,7 +2360,7 @@ where
         &None,
         threshold,
         max_signatures,
-        || gas_meter.consume(gas::VERIFY_TX_SIG_GAS),
+        |gas| gas_meter.consume(gas),
     ) {
         Ok(_) => Ok(HostEnvResult::Success.to_i64()),
         Err(err) => match err {
@@ -2558,6 +2699,8 @@ where
         key: &Key,
         data: impl AsRef<[u8]>,
     ) -> Result<(), StorageError> {
+        let gas = tx_record_prior_size(self, key).into_storage_result()?;
+        ibc_tx_charge_gas(self, gas)?;
         let write_log = unsafe { self.write_log.get() };
         let (gas, _size_diff) = write_log
             .write(key, data.as_ref().to_vec())
@@ -2570,6 +2713,8 @@ where
             return Err(TxRuntimeError::CannotDeleteVp).into_storage_result();
         }
 
+        let gas = tx_record_prior_size(self, key).into_storage_result()?;
+        ibc_tx_charge_gas(self, gas)?;
         let write_log = unsafe { self.write_log.get() };
         let (gas, _size_diff) = write_log.delete(key).into_storage_result()?;
         ibc_tx_charge_gas(self, gas)
@@ -1,7 +1,7 @@
 //! Parameters storage
 
 use namada_core::types::address::Address;
-use namada_core::types::storage::{DbKeySeg, Key};
+use namada_core::types::storage::{BlockHeight, DbKeySeg, Key};
 use namada_macros::StorageKeys;
 use namada_storage::StorageRead;
 
@@ -45,6 +45,15 @@ struct Keys {
     fee_unshielding_gas_limit: &'static str,
     fee_unshielding_descriptions_limit: &'static str,
     max_signatures_per_transaction: &'static str,
+    // ========================================
+    // Activation heights of the upgrades set by governance
+    // ========================================
+    /// Sub-key for storing the height from which the MASP note commitment
+    /// tree and nullifiers are merklized
+    masp_merklization_height: &'static str,
+    /// Sub-key for storing the height from which the token and governance
+    /// data are moved into their own merkle subtrees
+    merkle_subtrees_split_height: &'static str,
 }
 
 /// Returns if the key is a parameter key.
@@ -190,6 +199,24 @@ pub fn get_max_signatures_per_transaction_key() -> Key {
     get_max_signatures_per_transaction_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the MASP merklization activation height
+pub fn get_masp_merklization_height_key() -> Key {
+    get_masp_merklization_height_key_at_addr(ADDRESS)
+}
+
+/// Helper function to retrieve the activation height of the MASP
+/// merklization, if it has been set by governance
+pub fn read_masp_merklization_height(
+    storage: &impl StorageRead,
+) -> std::result::Result<Option<BlockHeight>, namada_storage::Error> {
+    storage.read(&get_masp_merklization_height_key())
+}
+
+/// Storage key used for the merkle subtrees split activation height
+pub fn get_merkle_subtrees_split_height_key() -> Key {
+    get_merkle_subtrees_split_height_key_at_addr(ADDRESS)
+}
+
 /// Helper function to retrieve the `max_block_gas` protocol parameter from
 /// storage
 pub fn get_max_block_gas(
@@ -1326,7 +1326,7 @@ where
     // Require that the new consensus key is an Ed25519 key
     match consensus_key {
         common::PublicKey::Ed25519(_) => {}
-        common::PublicKey::Secp256k1(_) => {
+        common::PublicKey::Secp256k1(_) | common::PublicKey::Bls(_) => {
             return Err(ConsensusKeyChangeError::MustBeEd25519.into());
         }
     }
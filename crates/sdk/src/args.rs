@@ -16,6 +16,7 @@ use namada_core::types::time::DateTimeUtc;
 use namada_core::types::{storage, token};
 use namada_governance::cli::onchain::{
     DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    ShieldedRewardsProposal,
 };
 use namada_tx::data::GasLimit;
 use namada_tx::Memo;
@@ -25,6 +26,7 @@ use zeroize::Zeroizing;
 use crate::eth_bridge::bridge_pool;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use crate::signing::SigningTxData;
+use crate::wallet::ExtendedPublicKey;
 use crate::{rpc, tx, Namada};
 
 /// [`Duration`](StdDuration) wrapper that provides a
@@ -293,6 +295,920 @@ impl TxTransfer {
     }
 }
 
+/// User-issued token registration transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxRegisterToken<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The established account issuing the token
+    pub token: C::Address,
+    /// The denomination of the token
+    pub denom: token::Denomination,
+    /// The address authorizing the mints and burns of the token
+    pub minter: C::Address,
+    /// The maximum supply of the token, if it is capped
+    pub max_supply: Option<token::DenominatedAmount>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxRegisterToken<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxRegisterToken {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxRegisterToken<C> {
+    /// The established account issuing the token
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// The denomination of the token
+    pub fn denom(self, denom: token::Denomination) -> Self {
+        Self { denom, ..self }
+    }
+
+    /// The address authorizing the mints and burns of the token
+    pub fn minter(self, minter: C::Address) -> Self {
+        Self { minter, ..self }
+    }
+
+    /// The maximum supply of the token
+    pub fn max_supply(self, max_supply: token::DenominatedAmount) -> Self {
+        Self {
+            max_supply: Some(max_supply),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxRegisterToken {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_register_token(context, self).await
+    }
+}
+
+/// User-issued token mint transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxMintTokens<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The minter of the token
+    pub minter: C::Address,
+    /// The address receiving the minted tokens
+    pub target: C::Address,
+    /// Minted token address
+    pub token: C::Address,
+    /// Minted token amount
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxMintTokens<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxMintTokens {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxMintTokens<C> {
+    /// The minter of the token
+    pub fn minter(self, minter: C::Address) -> Self {
+        Self { minter, ..self }
+    }
+
+    /// The address receiving the minted tokens
+    pub fn target(self, target: C::Address) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Minted token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Minted token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxMintTokens {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_mint_tokens(context, self).await
+    }
+}
+
+/// User-issued token burn transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxBurnTokens<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The owner of the burned tokens
+    pub owner: C::Address,
+    /// Burned token address
+    pub token: C::Address,
+    /// Burned token amount
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxBurnTokens<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxBurnTokens {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxBurnTokens<C> {
+    /// The owner of the burned tokens
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// Burned token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Burned token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxBurnTokens {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_burn_tokens(context, self).await
+    }
+}
+
+/// Token metadata update transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxUpdateTokenMetadata<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The token whose metadata is updated
+    pub token: C::Address,
+    /// The ticker symbol of the token
+    pub symbol: String,
+    /// The full name of the token
+    pub name: String,
+    /// The URL of the token's icon
+    pub icon_url: Option<String>,
+    /// A description of the token
+    pub description: Option<String>,
+    /// The IBC denomination trace of an IBC token
+    pub ibc_trace: Option<String>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateTokenMetadata<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateTokenMetadata {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateTokenMetadata<C> {
+    /// The token whose metadata is updated
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// The ticker symbol of the token
+    pub fn symbol(self, symbol: String) -> Self {
+        Self { symbol, ..self }
+    }
+
+    /// The full name of the token
+    pub fn name(self, name: String) -> Self {
+        Self { name, ..self }
+    }
+
+    /// The URL of the token's icon
+    pub fn icon_url(self, icon_url: String) -> Self {
+        Self {
+            icon_url: Some(icon_url),
+            ..self
+        }
+    }
+
+    /// A description of the token
+    pub fn description(self, description: String) -> Self {
+        Self {
+            description: Some(description),
+            ..self
+        }
+    }
+
+    /// The IBC denomination trace of an IBC token
+    pub fn ibc_trace(self, ibc_trace: String) -> Self {
+        Self {
+            ibc_trace: Some(ibc_trace),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxUpdateTokenMetadata {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_token_metadata(context, self).await
+    }
+}
+
+/// Token allowance approval transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxApprove<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The owner of the tokens granting the allowance
+    pub owner: C::Address,
+    /// The address allowed to spend the tokens
+    pub spender: C::Address,
+    /// Token address
+    pub token: C::Address,
+    /// The amount the spender may transfer
+    pub amount: InputAmount,
+    /// The epoch from which the allowance can no longer be spent
+    pub expiry: Option<Epoch>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxApprove<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxApprove {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxApprove<C> {
+    /// The owner of the tokens granting the allowance
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The address allowed to spend the tokens
+    pub fn spender(self, spender: C::Address) -> Self {
+        Self { spender, ..self }
+    }
+
+    /// Token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// The amount the spender may transfer
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// The epoch from which the allowance can no longer be spent
+    pub fn expiry(self, expiry: Epoch) -> Self {
+        Self {
+            expiry: Some(expiry),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxApprove {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_approve(context, self).await
+    }
+}
+
+/// Transfer within an allowance transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxTransferFrom<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The address spending its allowance
+    pub spender: C::Address,
+    /// Transfer source address
+    pub source: C::Address,
+    /// Transfer target address
+    pub target: C::Address,
+    /// Transferred token address
+    pub token: C::Address,
+    /// Transferred token amount
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxTransferFrom<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxTransferFrom {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxTransferFrom<C> {
+    /// The address spending its allowance
+    pub fn spender(self, spender: C::Address) -> Self {
+        Self { spender, ..self }
+    }
+
+    /// Transfer source address
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Transfer target address
+    pub fn target(self, target: C::Address) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Transferred token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Transferred token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxTransferFrom {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_transfer_from(context, self).await
+    }
+}
+
+/// Update spending policy transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxUpdateSpendingPolicy<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account whose policy is updated
+    pub addr: C::Address,
+    /// The JSON encoded policy. `None` removes the policy.
+    pub policy: Option<C::Data>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateSpendingPolicy<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateSpendingPolicy {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateSpendingPolicy<C> {
+    /// Address of the account whose policy is updated
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// The JSON encoded policy. `None` removes the policy.
+    pub fn policy(self, policy: Option<C::Data>) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxUpdateSpendingPolicy {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_spending_policy(context, self).await
+    }
+}
+
+/// Queue withdrawal transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxQueueWithdrawal<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Withdrawal source address
+    pub source: C::Address,
+    /// Withdrawal target address
+    pub target: C::Address,
+    /// Withdrawn token address
+    pub token: C::Address,
+    /// Withdrawn token amount
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxQueueWithdrawal<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxQueueWithdrawal {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxQueueWithdrawal<C> {
+    /// Withdrawal source address
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Withdrawal target address
+    pub fn target(self, target: C::Address) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Withdrawn token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Withdrawn token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxQueueWithdrawal {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_queue_withdrawal(context, self).await
+    }
+}
+
+/// Execute queued withdrawal transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxExecuteWithdrawal<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Source address of the queued withdrawal
+    pub source: C::Address,
+    /// The id of the queued withdrawal
+    pub id: u64,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxExecuteWithdrawal<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxExecuteWithdrawal {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxExecuteWithdrawal<C> {
+    /// Source address of the queued withdrawal
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// The id of the queued withdrawal
+    pub fn id(self, id: u64) -> Self {
+        Self { id, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxExecuteWithdrawal {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_execute_withdrawal(context, self).await
+    }
+}
+
+/// Cancel queued withdrawal transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxCancelWithdrawal<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Source address of the queued withdrawal
+    pub source: C::Address,
+    /// The id of the queued withdrawal
+    pub id: u64,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxCancelWithdrawal<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxCancelWithdrawal {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxCancelWithdrawal<C> {
+    /// Source address of the queued withdrawal
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// The id of the queued withdrawal
+    pub fn id(self, id: u64) -> Self {
+        Self { id, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxCancelWithdrawal {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_cancel_withdrawal(context, self).await
+    }
+}
+
+/// Update guardians transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxUpdateGuardians<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account
+    pub addr: C::Address,
+    /// The guardians of the account, empty to remove them
+    pub guardians: Vec<C::Address>,
+    /// The number of guardians needed to initiate a recovery
+    pub threshold: Option<u8>,
+    /// The number of epochs before a recovery can be finalized
+    pub delay: u64,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateGuardians<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateGuardians {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateGuardians<C> {
+    /// Address of the account
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// The guardians of the account, empty to remove them
+    pub fn guardians(self, guardians: Vec<C::Address>) -> Self {
+        Self { guardians, ..self }
+    }
+
+    /// The number of guardians needed to initiate a recovery
+    pub fn threshold(self, threshold: Option<u8>) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// The number of epochs before a recovery can be finalized
+    pub fn delay(self, delay: u64) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxUpdateGuardians {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_guardians(context, self).await
+    }
+}
+
+/// Initiate account recovery transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxInitiateRecovery<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account
+    pub addr: C::Address,
+    /// The public keys replacing the account's keys
+    pub public_keys: Vec<C::PublicKey>,
+    /// The account signature threshold after the recovery
+    pub threshold: Option<u8>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxInitiateRecovery<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxInitiateRecovery {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxInitiateRecovery<C> {
+    /// Address of the account
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// The public keys replacing the account's keys
+    pub fn public_keys(self, public_keys: Vec<C::PublicKey>) -> Self {
+        Self {
+            public_keys,
+            ..self
+        }
+    }
+
+    /// The account signature threshold after the recovery
+    pub fn threshold(self, threshold: Option<u8>) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxInitiateRecovery {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_initiate_recovery(context, self).await
+    }
+}
+
+/// Cancel account recovery transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxCancelRecovery<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account
+    pub addr: C::Address,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxCancelRecovery<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxCancelRecovery {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxCancelRecovery<C> {
+    /// Address of the account
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxCancelRecovery {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_cancel_recovery(context, self).await
+    }
+}
+
+/// Finalize account recovery transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxFinalizeRecovery<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account
+    pub addr: C::Address,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxFinalizeRecovery<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxFinalizeRecovery {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxFinalizeRecovery<C> {
+    /// Address of the account
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxFinalizeRecovery {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_finalize_recovery(context, self).await
+    }
+}
+
 /// IBC transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
@@ -420,6 +1336,8 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type shielded rewards
+    pub is_shielded_rewards: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -466,6 +1384,14 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type shielded rewards
+    pub fn is_shielded_rewards(self, is_shielded_rewards: bool) -> Self {
+        Self {
+            is_shielded_rewards,
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -528,6 +1454,34 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_shielded_rewards {
+            let proposal = ShieldedRewardsProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_shielded_rewards_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -655,6 +1609,10 @@ pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account multisignature threshold
     pub threshold: Option<u8>,
+    /// The weights of the public keys, in the order of the account's keys
+    pub weights: Vec<u8>,
+    /// The account weight threshold
+    pub weight_threshold: Option<u16>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxInitAccount<C> {
@@ -686,6 +1644,19 @@ impl<C: NamadaTypes> TxInitAccount<C> {
         }
     }
 
+    /// The weights of the public keys, in the order of the account's keys
+    pub fn weights(self, weights: Vec<u8>) -> Self {
+        Self { weights, ..self }
+    }
+
+    /// The account weight threshold
+    pub fn weight_threshold(self, weight_threshold: u16) -> Self {
+        Self {
+            weight_threshold: Some(weight_threshold),
+            ..self
+        }
+    }
+
     /// Path to the VP WASM code file
     pub fn vp_code_path(self, vp_code_path: PathBuf) -> Self {
         Self {
@@ -808,6 +1779,11 @@ pub struct TxUpdateAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account threshold
     pub threshold: Option<u8>,
+    /// The weights of the public keys, in the order of the account's keys
+    pub weights: Vec<u8>,
+    /// The account weight threshold. Without weights, it removes the
+    /// weighting of the account.
+    pub weight_threshold: Option<u16>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxUpdateAccount<C> {
@@ -859,6 +1835,19 @@ impl<C: NamadaTypes> TxUpdateAccount<C> {
             ..self
         }
     }
+
+    /// The weights of the public keys, in the order of the account's keys
+    pub fn weights(self, weights: Vec<u8>) -> Self {
+        Self { weights, ..self }
+    }
+
+    /// The account weight threshold
+    pub fn weight_threshold(self, weight_threshold: u16) -> Self {
+        Self {
+            weight_threshold: Some(weight_threshold),
+            ..self
+        }
+    }
 }
 
 impl TxUpdateAccount {
@@ -1267,6 +2256,31 @@ pub struct QueryAccount<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Discover the used accounts of an HD wallet
+#[derive(Clone, Debug)]
+pub struct DiscoverAccounts<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Scheme type
+    pub scheme: SchemeType,
+    /// Alias prefix of the imported keys
+    pub alias: String,
+    /// Whether to force overwrite the aliases
+    pub alias_force: bool,
+    /// Don't encrypt the keypairs
+    pub unsafe_dont_encrypt: bool,
+    /// BIP44 derivation path of the first address
+    pub derivation_path: String,
+    /// Allow non-compliant derivation path
+    pub allow_non_compliant: bool,
+    /// Prompt for BIP39 passphrase
+    pub prompt_bip39_passphrase: bool,
+    /// Extended public key to derive the addresses from in watch-only mode
+    pub xpub: Option<ExtendedPublicKey>,
+    /// Number of consecutive unused addresses after which to stop
+    pub gap_limit: u32,
+}
+
 /// Query token balance(s)
 #[derive(Clone, Debug)]
 pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
@@ -1906,6 +2920,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub fee_token: C::AddrOrNativeToken,
     /// The optional spending key for fee unshielding
     pub fee_unshield: Option<C::TransferSource>,
+    /// The optional spending key to pay the fee directly from the shielded
+    /// pool
+    pub masp_fee: Option<C::TransferSource>,
     /// The max amount of gas used to process tx
     pub gas_limit: GasLimit,
     /// The optional expiration of the transaction
@@ -1927,6 +2944,8 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub memo: Option<Memo>,
     /// Use device to sign the transaction
     pub use_device: bool,
+    /// The socket of a remote signing daemon to sign the transaction with
+    pub signer_socket: Option<PathBuf>,
 }
 
 /// Builder functions for Tx
@@ -2022,6 +3041,14 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The optional spending key to pay the fee directly from the shielded
+    /// pool
+    fn masp_fee(self, masp_fee: C::TransferSource) -> Self {
+        self.tx(|x| Tx {
+            masp_fee: Some(masp_fee),
+            ..x
+        })
+    }
     /// The max amount of gas used to process tx
     fn gas_limit(self, gas_limit: GasLimit) -> Self {
         self.tx(|x| Tx { gas_limit, ..x })
@@ -2070,6 +3097,13 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The socket of a remote signing daemon to sign the transaction with
+    fn signer_socket(self, signer_socket: PathBuf) -> Self {
+        self.tx(|x| Tx {
+            signer_socket: Some(signer_socket),
+            ..x
+        })
+    }
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for Tx<C> {
@@ -2127,6 +3161,15 @@ pub struct KeyDerive {
     pub use_device: bool,
 }
 
+/// Wallet extended public key derivation arguments
+#[derive(Clone, Debug)]
+pub struct KeyXpub {
+    /// BIP44 derivation path of the extended public key
+    pub derivation_path: String,
+    /// Prompt for BIP39 passphrase
+    pub prompt_bip39_passphrase: bool,
+}
+
 /// Wallet list arguments
 #[derive(Clone, Copy, Debug)]
 pub struct KeyAddressList {
@@ -2166,6 +3209,22 @@ pub struct KeyAddressFind {
     /// Show secret keys to user
     pub unsafe_show_secret: bool,
 }
+/// Wallet backup arguments
+#[derive(Clone, Debug)]
+pub struct WalletBackup {
+    /// Path of the backup file to create
+    pub file_path: String,
+}
+
+/// Wallet restore arguments
+#[derive(Clone, Debug)]
+pub struct WalletRestore {
+    /// Path of the backup file
+    pub file_path: String,
+    /// Replace the existing aliases that the backup would override
+    pub force: bool,
+}
+
 /// Wallet key export arguments
 #[derive(Clone, Debug)]
 pub struct KeyExport {
@@ -293,9 +293,18 @@ pub enum TxSubmitError {
     /// Account threshold is not set
     #[error("Account threshold must be set.")]
     MissingAccountThreshold,
+    /// Invalid account weights
+    #[error("Invalid account weights: {0}")]
+    InvalidAccountWeights(String),
     /// Not enough signature
     #[error("Account threshold is {0} but the valid signatures are {1}.")]
     MissingSigningKeys(u8, u8),
+    /// Not enough signature weight
+    #[error(
+        "Account weight threshold is {0} but the weight of the signing keys \
+         is {1}."
+    )]
+    MissingSigningWeight(u16, u16),
     /// Invalid owner account
     #[error("The source account {0} is not valid or doesn't exist.")]
     InvalidAccount(String),
@@ -330,6 +339,42 @@ pub enum TxSubmitError {
     /// The consensus key is not unique
     #[error("The consensus key has already been registered and is not unique")]
     ConsensusKeyNotUnique,
+    /// Invalid token registration
+    #[error("Invalid token registration: {0}")]
+    InvalidTokenRegistration(String),
+    /// The address is not the minter of the token
+    #[error("The address {0} is not the minter of the token {1}.")]
+    InvalidMinter(Address, Address),
+    /// The spender has no sufficient allowance
+    #[error(
+        "The address {0} has no sufficient allowance to transfer the token \
+         {1} from {2}."
+    )]
+    InsufficientAllowance(Address, Address, Address),
+    /// No queued withdrawal with the given id
+    #[error("The account {0} has no queued withdrawal with id {1}")]
+    UnknownQueuedWithdrawal(Address, u64),
+    /// The queued withdrawal cannot be executed yet
+    #[error(
+        "The queued withdrawal {1} of the account {0} is locked until epoch \
+         {2}"
+    )]
+    LockedQueuedWithdrawal(Address, u64, Epoch),
+    /// Invalid guardian set
+    #[error("Invalid guardian set: {0}")]
+    InvalidGuardianSet(String),
+    /// The account has no guardians
+    #[error("The account {0} has no guardians")]
+    NoGuardians(Address),
+    /// A recovery of the account is already pending
+    #[error("A recovery of the account {0} is already pending")]
+    RecoveryPending(Address),
+    /// The account has no pending recovery
+    #[error("The account {0} has no pending recovery")]
+    NoPendingRecovery(Address),
+    /// The pending recovery cannot be finalized yet
+    #[error("The recovery of the account {0} is locked until epoch {1}")]
+    LockedRecovery(Address, Epoch),
     /// Other Errors that may show up when using the interface
     #[error("{0}")]
     Other(String),
@@ -15,6 +15,9 @@ pub mod rpc;
 
 pub mod args;
 pub mod masp;
+pub mod masp_disclosure;
+pub mod masp_prover;
+pub mod signer;
 pub mod signing;
 #[allow(clippy::result_large_err)]
 pub mod tx;
@@ -52,17 +55,23 @@ use crate::masp::{ShieldedContext, ShieldedUtils};
 use crate::rpc::{
     denominate_amount, format_denominated_amount, query_native_token,
 };
+use crate::signer::{SignWith, Signer};
 use crate::signing::SigningTxData;
 use crate::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use crate::tx::{
-    ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    ProcessTxResponse, TX_APPROVE_WASM, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+    TX_BRIDGE_POOL_WASM, TX_BURN_TOKENS_WASM, TX_CANCEL_RECOVERY_WASM,
+    TX_CANCEL_WITHDRAWAL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+    TX_EXECUTE_WITHDRAWAL_WASM, TX_FINALIZE_RECOVERY_WASM, TX_IBC_WASM,
+    TX_INITIATE_RECOVERY_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_MINT_TOKENS_WASM, TX_QUEUE_WITHDRAWAL_WASM, TX_REACTIVATE_VALIDATOR_WASM,
+    TX_REDELEGATE_WASM, TX_REGISTER_TOKEN_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK,
+    TX_TRANSFER_FROM_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_GUARDIANS_WASM,
+    TX_UPDATE_SPENDING_POLICY_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_UPDATE_TOKEN_METADATA_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
 use crate::wallet::{Wallet, WalletIo, WalletStorage};
@@ -137,6 +146,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             wrapper_fee_payer: None,
             fee_token: self.native_token(),
             fee_unshield: None,
+            masp_fee: None,
             gas_limit: GasLimit::from(20_000),
             expiration: None,
             disposable_signing_key: false,
@@ -147,6 +157,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             password: None,
             memo: None,
             use_device: false,
+            signer_socket: None,
         }
     }
 
@@ -180,6 +191,8 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             tx_code_path: PathBuf::from(TX_INIT_ACCOUNT_WASM),
             public_keys,
             threshold,
+            weights: vec![],
+            weight_threshold: None,
         }
     }
 
@@ -191,6 +204,227 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a RegisterToken builder from the given minimum set of arguments
+    fn new_register_token(
+        &self,
+        token: Address,
+        denom: token::Denomination,
+        minter: Address,
+    ) -> args::TxRegisterToken {
+        args::TxRegisterToken {
+            token,
+            denom,
+            minter,
+            max_supply: None,
+            tx_code_path: PathBuf::from(TX_REGISTER_TOKEN_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a MintTokens builder from the given minimum set of arguments
+    fn new_mint_tokens(
+        &self,
+        minter: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxMintTokens {
+        args::TxMintTokens {
+            minter,
+            target,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_MINT_TOKENS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a BurnTokens builder from the given minimum set of arguments
+    fn new_burn_tokens(
+        &self,
+        owner: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxBurnTokens {
+        args::TxBurnTokens {
+            owner,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_BURN_TOKENS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a UpdateTokenMetadata builder from the given minimum set of
+    /// arguments
+    fn new_update_token_metadata(
+        &self,
+        token: Address,
+        symbol: String,
+        name: String,
+    ) -> args::TxUpdateTokenMetadata {
+        args::TxUpdateTokenMetadata {
+            token,
+            symbol,
+            name,
+            icon_url: None,
+            description: None,
+            ibc_trace: None,
+            tx_code_path: PathBuf::from(TX_UPDATE_TOKEN_METADATA_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make an Approve builder from the given minimum set of arguments
+    fn new_approve(
+        &self,
+        owner: Address,
+        spender: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxApprove {
+        args::TxApprove {
+            owner,
+            spender,
+            token,
+            amount,
+            expiry: None,
+            tx_code_path: PathBuf::from(TX_APPROVE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TransferFrom builder from the given minimum set of arguments
+    fn new_transfer_from(
+        &self,
+        spender: Address,
+        source: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxTransferFrom {
+        args::TxTransferFrom {
+            spender,
+            source,
+            target,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_TRANSFER_FROM_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a UpdateSpendingPolicy builder from the given minimum set of
+    /// arguments
+    fn new_update_spending_policy(
+        &self,
+        addr: Address,
+        policy: Option<Vec<u8>>,
+    ) -> args::TxUpdateSpendingPolicy {
+        args::TxUpdateSpendingPolicy {
+            addr,
+            policy,
+            tx_code_path: PathBuf::from(TX_UPDATE_SPENDING_POLICY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a QueueWithdrawal builder from the given minimum set of arguments
+    fn new_queue_withdrawal(
+        &self,
+        source: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxQueueWithdrawal {
+        args::TxQueueWithdrawal {
+            source,
+            target,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_QUEUE_WITHDRAWAL_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a ExecuteWithdrawal builder from the given minimum set of
+    /// arguments
+    fn new_execute_withdrawal(
+        &self,
+        source: Address,
+        id: u64,
+    ) -> args::TxExecuteWithdrawal {
+        args::TxExecuteWithdrawal {
+            source,
+            id,
+            tx_code_path: PathBuf::from(TX_EXECUTE_WITHDRAWAL_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a CancelWithdrawal builder from the given minimum set of arguments
+    fn new_cancel_withdrawal(
+        &self,
+        source: Address,
+        id: u64,
+    ) -> args::TxCancelWithdrawal {
+        args::TxCancelWithdrawal {
+            source,
+            id,
+            tx_code_path: PathBuf::from(TX_CANCEL_WITHDRAWAL_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a UpdateGuardians builder from the given minimum set of arguments
+    fn new_update_guardians(
+        &self,
+        addr: Address,
+        guardians: Vec<Address>,
+    ) -> args::TxUpdateGuardians {
+        args::TxUpdateGuardians {
+            addr,
+            guardians,
+            threshold: None,
+            delay: 0,
+            tx_code_path: PathBuf::from(TX_UPDATE_GUARDIANS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a InitiateRecovery builder from the given minimum set of arguments
+    fn new_initiate_recovery(
+        &self,
+        addr: Address,
+        public_keys: Vec<common::PublicKey>,
+    ) -> args::TxInitiateRecovery {
+        args::TxInitiateRecovery {
+            addr,
+            public_keys,
+            threshold: None,
+            tx_code_path: PathBuf::from(TX_INITIATE_RECOVERY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a CancelRecovery builder from the given minimum set of arguments
+    fn new_cancel_recovery(&self, addr: Address) -> args::TxCancelRecovery {
+        args::TxCancelRecovery {
+            addr,
+            tx_code_path: PathBuf::from(TX_CANCEL_RECOVERY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a FinalizeRecovery builder from the given minimum set of arguments
+    fn new_finalize_recovery(&self, addr: Address) -> args::TxFinalizeRecovery {
+        args::TxFinalizeRecovery {
+            addr,
+            tx_code_path: PathBuf::from(TX_FINALIZE_RECOVERY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a Bond builder from the given minimum set of arguments
     fn new_bond(
         &self,
@@ -275,6 +509,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             is_offline: false,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_shielded_rewards: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -287,6 +522,8 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             vp_code_path: None,
             public_keys: vec![],
             threshold: None,
+            weights: vec![],
+            weight_threshold: None,
             tx_code_path: PathBuf::from(TX_UPDATE_ACCOUNT_WASM),
             tx: self.tx_builder(),
         }
@@ -556,12 +793,24 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             args,
             tx,
             signing_data,
-            with,
-            user_data,
+            &SignWith::new(with, user_data),
         )
         .await
     }
 
+    /// Sign the given transaction using the given signing data and the given
+    /// signer for the keys missing from the wallet
+    async fn sign_with_signer(
+        &self,
+        tx: &mut Tx,
+        args: &args::Tx,
+        signing_data: SigningTxData,
+        signer: &impl Signer,
+    ) -> crate::error::Result<()> {
+        signing::sign_tx(self.wallet_lock(), args, tx, signing_data, signer)
+            .await
+    }
+
     /// Process the given transaction using the given flags
     async fn submit(
         &self,
@@ -652,6 +901,7 @@ where
                 wrapper_fee_payer: None,
                 fee_token: native_token,
                 fee_unshield: None,
+                masp_fee: None,
                 gas_limit: GasLimit::from(20_000),
                 expiration: None,
                 disposable_signing_key: false,
@@ -662,6 +912,7 @@ where
                 password: None,
                 memo: None,
                 use_device: false,
+                signer_socket: None,
             },
         }
     }
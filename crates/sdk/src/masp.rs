@@ -75,6 +75,7 @@ use token::Amount;
 use crate::error::EncodingError;
 use crate::error::{Error, PinnedBalanceError, QueryError};
 use crate::io::Io;
+use crate::masp_prover::{LocalMaspProver, MaspProver, ProverError};
 use crate::queries::Client;
 use crate::rpc::{
     query_block, query_conversion, query_denom, query_epoch_at_height,
@@ -137,6 +138,28 @@ pub struct MaspTokenRewardData {
     pub locked_amount_target: Uint,
 }
 
+/// Shielded pool balance of a token
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct MaspTokenStats {
+    /// The address of the token
+    pub address: Address,
+    /// The transparent balance of the token held by the MASP
+    pub balance: Amount,
+}
+
+/// Statistics of the shielded pool
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct MaspStats {
+    /// The transparent balance held by the MASP for each known token
+    pub tokens: Vec<MaspTokenStats>,
+    /// The number of note commitments in the note commitment tree
+    pub note_commitments: u64,
+    /// The number of revealed nullifiers
+    pub nullifiers: u64,
+    /// The epoch of the latest conversions update
+    pub conversion_epoch: Epoch,
+}
+
 #[cfg(feature = "testing")]
 #[derive(Clone, Copy, Debug)]
 enum LoadOrSaveProofs {
@@ -156,6 +179,15 @@ pub enum TransferErr {
     General(#[from] Error),
 }
 
+impl From<ProverError> for TransferErr {
+    fn from(err: ProverError) -> Self {
+        match err {
+            ProverError::Build(err) => Self::Build(err),
+            err => Self::General(Error::Other(err.to_string())),
+        }
+    }
+}
+
 /// MASP verifying keys
 pub struct PVKs {
     /// spend verifying key
@@ -384,6 +416,30 @@ pub fn verify_shielded_tx(transaction: &Transaction) -> bool {
     result
 }
 
+/// Get the transparent address to which the masp section paying the fee of the
+/// given wrapper directly from the shielded pool must unshield the fee.
+/// Besides the MASP address, it commits to the public key, fee and gas limit
+/// of the wrapper and to the chain id and expiration of the tx. Since the
+/// transparent outputs are covered by the signatures of the masp transaction,
+/// the section can't be attached to another wrapper.
+pub fn masp_fee_payment_target(
+    tx: &Tx,
+    wrapper: &WrapperTx,
+) -> TransparentAddress {
+    let binding = (
+        MASP,
+        wrapper.pk.clone(),
+        wrapper.fee.clone(),
+        wrapper.gas_limit,
+        tx.header.chain_id.clone(),
+        tx.header.expiration,
+    )
+        .serialize_to_vec();
+    TransparentAddress(
+        ripemd::Ripemd160::digest(sha2::Sha256::digest(&binding)).into(),
+    )
+}
+
 /// Get the path to MASP parameters from [`ENV_VAR_MASP_PARAMS_DIR`] env var or
 /// use the default.
 pub fn get_params_dir() -> PathBuf {
@@ -431,6 +487,43 @@ impl<P1, R1, N1>
     fn map_notifier(&self, _s: N1) {}
 }
 
+/// Freeze a Builder into the format necessary for inclusion in a Tx, with the
+/// given full viewing key as the key of all its spends. This allows building
+/// the spends of a source whose spending key is held by the prover only.
+struct ViewingKeyMap(ExtendedFullViewingKey);
+
+impl<P1>
+    masp_primitives::transaction::components::sapling::builder::MapBuilder<
+        P1,
+        ExtendedSpendingKey,
+        (),
+        ExtendedFullViewingKey,
+    > for ViewingKeyMap
+{
+    fn map_params(&self, _s: P1) {}
+
+    fn map_key(&self, _s: ExtendedSpendingKey) -> ExtendedFullViewingKey {
+        self.0
+    }
+}
+
+impl<P1, R1, N1>
+    MapBuilder<
+        P1,
+        R1,
+        ExtendedSpendingKey,
+        N1,
+        (),
+        (),
+        ExtendedFullViewingKey,
+        (),
+    > for ViewingKeyMap
+{
+    fn map_rng(&self, _s: R1) {}
+
+    fn map_notifier(&self, _s: N1) {}
+}
+
 /// Abstracts platform specific details away from the logic of shielded pool
 /// operations.
 #[cfg_attr(feature = "async-send", async_trait::async_trait)]
@@ -441,6 +534,12 @@ pub trait ShieldedUtils:
     /// Get a MASP transaction prover
     fn local_tx_prover(&self) -> LocalTxProver;
 
+    /// Get the prover used to build shielded transfers. Defaults to proving
+    /// in-process with the local prover.
+    fn tx_prover(&self) -> std::io::Result<Box<dyn MaspProver>> {
+        Ok(Box::new(LocalMaspProver::new(self.local_tx_prover())))
+    }
+
     /// Load up the currently saved ShieldedContext
     async fn load<U: ShieldedUtils + MaybeSend>(
         &self,
@@ -1866,6 +1965,44 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         target: &TransferTarget,
         token: &Address,
         amount: token::DenominatedAmount,
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        Self::gen_shielded_transfer_to(
+            context, source, target, None, token, amount,
+        )
+        .await
+    }
+
+    /// Make the masp transaction paying the fee of a wrapper directly from
+    /// the shielded pool: the given amount is unshielded from the source to
+    /// the [`masp_fee_payment_target`] of the wrapper.
+    pub async fn gen_masp_fee_payment(
+        context: &impl Namada,
+        source: &TransferSource,
+        fee_payment_target: TransparentAddress,
+        token: &Address,
+        amount: token::DenominatedAmount,
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        Self::gen_shielded_transfer_to(
+            context,
+            source,
+            &TransferTarget::Address(MASP),
+            Some(fee_payment_target),
+            token,
+            amount,
+        )
+        .await
+    }
+
+    // Make shielded components as in [`Self::gen_shielded_transfer`],
+    // optionally overriding the transparent address of the outputs to a
+    // transparent target
+    async fn gen_shielded_transfer_to(
+        context: &impl Namada,
+        source: &TransferSource,
+        target: &TransferTarget,
+        transparent_target: Option<TransparentAddress>,
+        token: &Address,
+        amount: token::DenominatedAmount,
     ) -> Result<Option<ShieldedTransfer>, TransferErr> {
         // No shielded components are needed when neither source nor destination
         // are shielded
@@ -1874,23 +2011,28 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         use rand_core::SeedableRng;
 
         let spending_key = source.spending_key();
+        // The spending key of a shielded source given by its viewing key is
+        // held by the prover only
+        let viewing_key: Option<ExtendedFullViewingKey> =
+            source.viewing_key().map(Into::into);
         let payment_address = target.payment_address();
         // No shielded components are needed when neither source nor
         // destination are shielded
-        if spending_key.is_none() && payment_address.is_none() {
+        if viewing_key.is_none() && payment_address.is_none() {
             return Ok(None);
         }
         // We want to fund our transaction solely from supplied spending key
-        let spending_key = spending_key.map(|x| x.into());
+        let spending_key: Option<ExtendedSpendingKey> =
+            spending_key.map(|x| x.into());
         let spending_keys: Vec<_> = spending_key.into_iter().collect();
         {
-            // Load the current shielded context given the spending key we
-            // possess
+            // Load the current shielded context given the viewing key of the
+            // source
             let mut shielded = context.shielded_mut().await;
             let _ = shielded.load().await;
-            shielded
-                .fetch(context.client(), &spending_keys, &[])
-                .await?;
+            let viewing_keys: Vec<_> =
+                viewing_key.iter().map(|vk| vk.fvk.vk.clone()).collect();
+            shielded.fetch(context.client(), &[], &viewing_keys).await?;
             // Save the update state so that future fetches can be
             // short-circuited
             let _ = shielded.save().await;
@@ -1902,7 +2044,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let memo = MemoBytes::empty();
 
         // Try to get a seed from env var, if any.
-        let rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
+        let mut rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
             .map_err(|e| Error::Other(e.to_string()))
             .and_then(|seed| {
                 let exp_str =
@@ -1919,6 +2061,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         } else {
             StdRng::from_rng(OsRng).unwrap()
         };
+        // The seed of the randomness of the proofs and signatures
+        let mut prover_seed = [0u8; 32];
+        rng.fill_bytes(&mut prover_seed);
 
         // Now we build up the transaction within this object
         let expiration_height: u32 = match context.tx_builder().expiration {
@@ -1991,14 +2136,19 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         };
 
         // If there are shielded inputs
-        if let Some(sk) = spending_key {
+        if let Some(vk) = viewing_key {
+            // The spends are added with the spending key if we possess it,
+            // otherwise with a placeholder that is replaced by the viewing
+            // key when freezing the builder for the prover
+            let sk = spending_key
+                .unwrap_or_else(|| ExtendedSpendingKey::master(&[]));
             // Locate unspent notes that can help us meet the transaction amount
             let (_, unspent_notes, used_convs) = context
                 .shielded_mut()
                 .await
                 .collect_unspent_notes(
                     context,
-                    &to_viewing_key(&sk).vk,
+                    &vk.fvk.vk,
                     I128Sum::from_sum(masp_amount),
                     epoch,
                 )
@@ -2069,7 +2219,11 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         // If we are sending to a transparent output, then we will need to embed
         // the transparent target address into the shielded transaction so that
         // it can be signed
-        let transparent_target_hash = if payment_address.is_none() {
+        let transparent_target_hash = if payment_address.is_some() {
+            None
+        } else if let Some(transparent_target) = transparent_target {
+            Some(transparent_target.0)
+        } else {
             let target_enc = target
                 .address()
                 .ok_or_else(|| {
@@ -2078,18 +2232,19 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                     )
                 })?
                 .serialize_to_vec();
-            Some(ripemd::Ripemd160::digest(sha2::Sha256::digest(
-                target_enc.as_ref(),
-            )))
-        } else {
-            None
+            Some(
+                ripemd::Ripemd160::digest(sha2::Sha256::digest(
+                    target_enc.as_ref(),
+                ))
+                .into(),
+            )
         };
         // This indicates how many more assets need to be sent to the receiver
         // in order to satisfy the requested transfer amount.
         let mut rem_amount = amount.amount().raw_amount().0;
         // If we are sending to a shielded address, we may need the outgoing
         // viewing key in the following computations.
-        let ovk_opt = spending_key.map(|x| x.expsk.ovk);
+        let ovk_opt = viewing_key.map(|x| x.fvk.ovk);
 
         // Now handle the outputs of this transaction
         // Loop through the value balance components and see which
@@ -2125,12 +2280,10 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                         .map_err(builder::Error::SaplingBuild)?;
                 } else {
                     // If there is a transparent output
-                    let hash = transparent_target_hash
-                        .expect(
-                            "transparent target hash should have been \
-                             computed already",
-                        )
-                        .into();
+                    let hash = transparent_target_hash.expect(
+                        "transparent target hash should have been computed \
+                         already",
+                    );
                     builder
                         .add_transparent_output(
                             &TransparentAddress(hash),
@@ -2159,7 +2312,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
 
         // Now add outputs representing the change from this payment
-        if let Some(sk) = spending_key {
+        if let Some(vk) = viewing_key {
             // Represents the amount of inputs we are short by
             let mut additional = I128Sum::zero();
             for (asset_type, amt) in builder
@@ -2177,8 +2330,8 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                         // Send the change in this asset type back to the sender
                         builder
                             .add_sapling_output(
-                                Some(sk.expsk.ovk),
-                                sk.default_address().1,
+                                Some(vk.fvk.ovk),
+                                vk.default_address().1,
                                 *asset_type,
                                 *amt as u64,
                                 memo.clone(),
@@ -2234,18 +2387,23 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             LoadOrSaveProofs::Neither
         };
 
-        let builder_clone = builder.clone().map_builder(WalletMap);
+        // Freeze the builder with the viewing key of the source, which is
+        // also the key of the placeholder spends
+        let builder_clone = match viewing_key {
+            Some(vk) => builder.map_builder(ViewingKeyMap(vk)),
+            None => builder.map_builder(WalletMap),
+        };
         #[cfg(feature = "testing")]
         let builder_bytes = borsh::to_vec(&builder_clone).map_err(|e| {
             Error::from(EncodingError::Conversion(e.to_string()))
         })?;
 
-        let build_transfer = |prover: LocalTxProver| -> Result<
+        let build_transfer = |prover: Box<dyn MaspProver>| -> Result<
             ShieldedTransfer,
-            builder::Error<std::convert::Infallible>,
+            ProverError,
         > {
-            let (masp_tx, metadata) = builder
-                .build(&prover, &FeeRule::non_standard(U64Sum::zero()))?;
+            let (masp_tx, metadata) =
+                prover.build(builder_clone.clone(), spending_keys, prover_seed)?;
             Ok(ShieldedTransfer {
                 builder: builder_clone,
                 masp_tx,
@@ -2294,9 +2452,13 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                 Ok(Some(loaded))
             } else {
                 // Build and return the constructed transaction
-                let built = build_transfer(
-                    context.shielded().await.utils.local_tx_prover(),
-                )?;
+                let prover = context
+                    .shielded()
+                    .await
+                    .utils
+                    .tx_prover()
+                    .map_err(|e| Error::Other(e.to_string()))?;
+                let built = build_transfer(prover)?;
                 if let LoadOrSaveProofs::Save = load_or_save {
                     let built_bytes = borsh::to_vec(&built).map_err(|e| {
                         Error::from(EncodingError::Conversion(e.to_string()))
@@ -2312,9 +2474,13 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         #[cfg(not(feature = "testing"))]
         {
             // Build and return the constructed transaction
-            let built = build_transfer(
-                context.shielded().await.utils.local_tx_prover(),
-            )?;
+            let prover = context
+                .shielded()
+                .await
+                .utils
+                .tx_prover()
+                .map_err(|e| Error::Other(e.to_string()))?;
+            let built = build_transfer(prover)?;
             Ok(Some(built))
         }
     }
@@ -3518,6 +3684,7 @@ pub mod fs {
     use std::io::{Read, Write};
 
     use super::*;
+    use crate::masp_prover::RemoteTxProver;
 
     /// Shielded context file name
     const FILE_NAME: &str = "shielded.dat";
@@ -3584,6 +3751,16 @@ pub mod fs {
             }
         }
 
+        /// Use the external prover given by the
+        /// [`crate::masp_prover::ENV_VAR_MASP_PROVER`] env var
+        /// if any, otherwise prove locally.
+        fn tx_prover(&self) -> std::io::Result<Box<dyn MaspProver>> {
+            Ok(match RemoteTxProver::from_env()? {
+                Some(prover) => Box::new(prover),
+                None => Box::new(LocalMaspProver::new(self.local_tx_prover())),
+            })
+        }
+
         /// Try to load the last saved shielded context from the given context
         /// directory. If this fails, then leave the current context unchanged.
         async fn load<U: ShieldedUtils + MaybeSend>(
@@ -0,0 +1,519 @@
+//! Disclosure of the shielded balance of a viewing key. A disclosure contains
+//! the unspent notes of the viewing key at some block height together with
+//! the proofs needed by a third party to check offline, against the storage
+//! root of that block, that the notes are in the note commitment tree and
+//! that they have not been spent. All the nullifiers are only merklized from
+//! the height at which the MASP merklization was completed, so the
+//! disclosures of the older heights are rejected.
+
+use std::collections::{BTreeMap, HashSet};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use masp_primitives::asset_type::AssetType;
+use masp_primitives::ff::PrimeField;
+use masp_primitives::merkle_tree::{CommitmentTree, IncrementalWitness};
+use masp_primitives::sapling::{Diversifier, Node, Note};
+use masp_primitives::zip32::ExtendedFullViewingKey;
+use namada_core::tendermint::merkle::proof::{ProofOp, ProofOps};
+use namada_core::types::masp::ExtendedViewingKey;
+use namada_core::types::storage::BlockHeight;
+use namada_state::merkle_tree::{verify_storage_proof, MerkleRoot};
+use namada_state::Sha256Hasher;
+use namada_token::storage_key::{
+    masp_commitment_tree_key, masp_merklization_height_key, masp_nullifier_key,
+};
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::masp::{ShieldedContext, ShieldedUtils};
+use crate::queries::Client;
+use crate::rpc::query_storage_value_bytes;
+
+/// Errors in the verification of a balance disclosure
+#[allow(missing_docs)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DisclosureError {
+    #[error("Invalid proof of the MASP merklization height")]
+    InvalidMerklizationHeightProof,
+    #[error(
+        "The MASP keys are only merklized from the height {0}, after the \
+         disclosure height"
+    )]
+    NotMerklized(BlockHeight),
+    #[error("Invalid proof of the note commitment tree")]
+    InvalidCommitmentTreeProof,
+    #[error("Note at position {0} is disclosed more than once")]
+    DuplicateNote(u64),
+    #[error("Note at position {0} does not belong to the viewing key")]
+    ForeignNote(u64),
+    #[error("Note at position {0} is not in the note commitment tree")]
+    InvalidMerklePath(u64),
+    #[error("Invalid proof of non-nullification of the note at position {0}")]
+    InvalidNullifierProof(u64),
+    #[error("Failed to verify a storage proof: {0}")]
+    Proof(String),
+}
+
+/// A storage proof, in a serializable form of the [`ProofOps`] returned by
+/// the storage queries
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct StorageProof {
+    /// The proof operations, as (type, key, data) triples
+    pub ops: Vec<(String, Vec<u8>, Vec<u8>)>,
+}
+
+impl From<ProofOps> for StorageProof {
+    fn from(proof: ProofOps) -> Self {
+        Self {
+            ops: proof
+                .ops
+                .into_iter()
+                .map(|op| (op.field_type, op.key, op.data))
+                .collect(),
+        }
+    }
+}
+
+impl From<StorageProof> for ProofOps {
+    fn from(proof: StorageProof) -> Self {
+        Self {
+            ops: proof
+                .ops
+                .into_iter()
+                .map(|(field_type, key, data)| ProofOp {
+                    field_type,
+                    key,
+                    data,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An unspent note disclosed with its merkle path
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct DisclosedNote {
+    /// The note
+    pub note: Note,
+    /// The diversifier of the payment address of the note
+    pub diversifier: Diversifier,
+    /// The position of the note in the note commitment tree
+    pub position: u64,
+    /// The witness of the note in the note commitment tree
+    pub witness: IncrementalWitness<Node>,
+    /// The proof that the nullifier of the note has not been revealed. It is
+    /// `None` if the node could not produce such a proof, in which case the
+    /// note is reported as unproven by the verification.
+    pub nullifier_proof: Option<StorageProof>,
+}
+
+/// A disclosure of the shielded balance of a viewing key at a block height
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct BalanceDisclosure {
+    /// The height of the block whose storage the disclosure is proven against
+    pub height: BlockHeight,
+    /// The disclosed viewing key
+    pub viewing_key: ExtendedViewingKey,
+    /// The height from which the MASP keys are merklized
+    pub merklization_height: BlockHeight,
+    /// The proof of the merklization height against the storage root
+    pub merklization_height_proof: StorageProof,
+    /// The note commitment tree at the given height
+    pub commitment_tree: CommitmentTree<Node>,
+    /// The proof of the note commitment tree against the storage root
+    pub commitment_tree_proof: StorageProof,
+    /// The unspent notes of the viewing key
+    pub notes: Vec<DisclosedNote>,
+}
+
+/// The outcome of a successful disclosure verification
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifiedBalance {
+    /// The proven balance, per asset type
+    pub balance: BTreeMap<AssetType, u64>,
+    /// The positions of the notes whose non-nullification couldn't be proven
+    /// and which are not counted in the proven balance
+    pub unproven_notes: Vec<u64>,
+}
+
+impl BalanceDisclosure {
+    /// Verify the disclosure against the storage merkle root of the block at
+    /// the disclosure height, i.e. the app hash in the header of the next
+    /// block, which the verifier must obtain from a trusted source.
+    pub fn verify(
+        &self,
+        root: &MerkleRoot,
+    ) -> Result<VerifiedBalance, DisclosureError> {
+        let proof_err = |e: namada_state::merkle_tree::Error| {
+            DisclosureError::Proof(e.to_string())
+        };
+        // Without the nullifiers in the tree, the proofs of non-nullification
+        // would be valid for the spent notes too
+        let height_proof =
+            ProofOps::from(self.merklization_height_proof.clone());
+        let height_bytes = self.merklization_height.serialize_to_vec();
+        if !verify_storage_proof::<Sha256Hasher>(
+            &height_proof,
+            root,
+            &masp_merklization_height_key(),
+            Some(&height_bytes),
+        )
+        .map_err(proof_err)?
+        {
+            return Err(DisclosureError::InvalidMerklizationHeightProof);
+        }
+        if self.merklization_height > self.height {
+            return Err(DisclosureError::NotMerklized(
+                self.merklization_height,
+            ));
+        }
+
+        let tree_proof = ProofOps::from(self.commitment_tree_proof.clone());
+        let tree_bytes = self.commitment_tree.serialize_to_vec();
+        if !verify_storage_proof::<Sha256Hasher>(
+            &tree_proof,
+            root,
+            &masp_commitment_tree_key(),
+            Some(&tree_bytes),
+        )
+        .map_err(proof_err)?
+        {
+            return Err(DisclosureError::InvalidCommitmentTreeProof);
+        }
+        let tree_root = self.commitment_tree.root();
+
+        let vk = ExtendedFullViewingKey::from(self.viewing_key).fvk.vk;
+        let mut verified = VerifiedBalance::default();
+        let mut positions = HashSet::new();
+        let mut nullifiers = HashSet::new();
+        for disclosed in &self.notes {
+            let DisclosedNote {
+                note,
+                diversifier,
+                position,
+                witness,
+                nullifier_proof,
+            } = disclosed;
+            // A note must only be counted once
+            let nf = note.nf(&vk.nk, *position);
+            if !positions.insert(*position) || !nullifiers.insert(nf) {
+                return Err(DisclosureError::DuplicateNote(*position));
+            }
+            // The note must be addressed to the viewing key
+            let own_note = vk.to_payment_address(*diversifier).and_then(|pa| {
+                pa.create_note(note.asset_type, note.value, note.rseed)
+            });
+            if own_note.map(|own| own.cmu()) != Some(note.cmu()) {
+                return Err(DisclosureError::ForeignNote(*position));
+            }
+            // The note must be at the given position of the tree
+            let leaf = Node::new(note.cmu().to_repr());
+            let is_in_tree = witness.path().map_or(false, |path| {
+                path.position == *position && path.root(leaf) == tree_root
+            });
+            if !is_in_tree {
+                return Err(DisclosureError::InvalidMerklePath(*position));
+            }
+            // The nullifier of the note must not have been revealed
+            let Some(nullifier_proof) = nullifier_proof else {
+                verified.unproven_notes.push(*position);
+                continue;
+            };
+            if !verify_storage_proof::<Sha256Hasher>(
+                &nullifier_proof.clone().into(),
+                root,
+                &masp_nullifier_key(&nf),
+                None,
+            )
+            .map_err(proof_err)?
+            {
+                return Err(DisclosureError::InvalidNullifierProof(*position));
+            }
+            let balance =
+                verified.balance.entry(note.asset_type).or_default();
+            *balance = balance.checked_add(note.value).ok_or_else(|| {
+                DisclosureError::Proof("Balance overflow".to_string())
+            })?;
+        }
+        Ok(verified)
+    }
+}
+
+impl<U: ShieldedUtils> ShieldedContext<U> {
+    /// Produce a disclosure of the balance of the given viewing key at the
+    /// given height. The shielded context must have been fetched up to this
+    /// height, so that the witnesses of the notes match the note commitment
+    /// tree in storage.
+    pub async fn gen_balance_disclosure<C: Client + Sync>(
+        &self,
+        client: &C,
+        viewing_key: &ExtendedFullViewingKey,
+        height: BlockHeight,
+    ) -> Result<BalanceDisclosure, Error> {
+        let (height_bytes, height_proof) = query_storage_value_bytes(
+            client,
+            &masp_merklization_height_key(),
+            Some(height),
+            true,
+        )
+        .await?;
+        let merklization_height = match height_bytes {
+            Some(bytes) => BlockHeight::try_from_slice(&bytes)
+                .map_err(|e| Error::Other(e.to_string()))?,
+            None => {
+                return Err(Error::Other(format!(
+                    "The MASP keys are not merklized at height {height}"
+                )));
+            }
+        };
+        let merklization_height_proof = height_proof.ok_or_else(|| {
+            Error::Other(
+                "The node did not return a proof of the MASP merklization \
+                 height"
+                    .to_string(),
+            )
+        })?;
+
+        let (tree_bytes, tree_proof) = query_storage_value_bytes(
+            client,
+            &masp_commitment_tree_key(),
+            Some(height),
+            true,
+        )
+        .await?;
+        let commitment_tree = match tree_bytes {
+            Some(bytes) => CommitmentTree::<Node>::try_from_slice(&bytes)
+                .map_err(|e| Error::Other(e.to_string()))?,
+            None => CommitmentTree::empty(),
+        };
+        let commitment_tree_proof = tree_proof.ok_or_else(|| {
+            Error::Other(
+                "The node did not return a proof of the note commitment tree"
+                    .to_string(),
+            )
+        })?;
+        let tree_root = commitment_tree.root();
+
+        let vk = viewing_key.fvk.vk;
+        let mut notes = vec![];
+        for position in self.pos_map.get(&vk).into_iter().flatten() {
+            if self.spents.contains(position) {
+                continue;
+            }
+            let note_err = || {
+                Error::Other(format!(
+                    "Missing data of the note at position {position}"
+                ))
+            };
+            let note = *self.note_map.get(position).ok_or_else(note_err)?;
+            let diversifier =
+                *self.div_map.get(position).ok_or_else(note_err)?;
+            let witness =
+                self.witness_map.get(position).ok_or_else(note_err)?.clone();
+            if witness.root() != tree_root {
+                return Err(Error::Other(format!(
+                    "The shielded context is not synced to height {height}"
+                )));
+            }
+            let position = *position as u64;
+
+            let nf = note.nf(&vk.nk, position);
+            let nullifier_proof = match query_storage_value_bytes(
+                client,
+                &masp_nullifier_key(&nf),
+                Some(height),
+                true,
+            )
+            .await
+            {
+                // The note was spent at the given height
+                Ok((Some(_), _)) => continue,
+                Ok((None, proof)) => proof.map(StorageProof::from),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed to get a proof of non-nullification of the \
+                         note at position {position}: {err}"
+                    );
+                    None
+                }
+            };
+            notes.push(DisclosedNote {
+                note,
+                diversifier,
+                position,
+                witness,
+                nullifier_proof,
+            });
+        }
+
+        Ok(BalanceDisclosure {
+            height,
+            viewing_key: (*viewing_key).into(),
+            merklization_height,
+            merklization_height_proof: merklization_height_proof.into(),
+            commitment_tree,
+            commitment_tree_proof: commitment_tree_proof.into(),
+            notes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::asset_type::AssetType;
+    use masp_primitives::sapling::Rseed;
+    use masp_primitives::zip32::ExtendedSpendingKey;
+    use namada_state::merkle_tree::{MembershipProof, MerkleTree};
+    use namada_state::Key;
+
+    use super::*;
+
+    /// Get the proof of the given key and value in the tree
+    fn existence_proof(
+        tree: &MerkleTree<Sha256Hasher>,
+        key: &Key,
+        value: Vec<u8>,
+    ) -> StorageProof {
+        let MembershipProof::ICS23(proof) = tree
+            .get_sub_tree_existence_proof(std::array::from_ref(key), vec![
+                &value,
+            ])
+            .unwrap()
+        else {
+            panic!("Test failed")
+        };
+        ProofOps::from(tree.get_sub_tree_proof(key, proof).unwrap()).into()
+    }
+
+    /// Get the proof of the absence of the given key in the tree
+    fn non_existence_proof(
+        tree: &MerkleTree<Sha256Hasher>,
+        key: &Key,
+    ) -> StorageProof {
+        ProofOps::from(tree.get_non_existence_proof(key).unwrap()).into()
+    }
+
+    /// Disclose a spent and an unspent note of a viewing key and check that
+    /// only the unspent one is accepted
+    #[test]
+    fn test_disclose_and_verify_notes() {
+        let spending_key = ExtendedSpendingKey::master(&[0; 32]);
+        let viewing_key = ExtendedFullViewingKey::from(&spending_key);
+        let vk = viewing_key.fvk.vk;
+        let (_, payment_address) = viewing_key.default_address();
+        let asset_type = AssetType::new(b"test").unwrap();
+        let unspent = payment_address
+            .create_note(asset_type, 10, Rseed::AfterZip212([1; 32]))
+            .unwrap();
+        let spent = payment_address
+            .create_note(asset_type, 20, Rseed::AfterZip212([2; 32]))
+            .unwrap();
+
+        // Append both notes to the note commitment tree
+        let mut commitment_tree = CommitmentTree::<Node>::empty();
+        commitment_tree
+            .append(Node::new(unspent.cmu().to_repr()))
+            .unwrap();
+        let mut unspent_witness =
+            IncrementalWitness::from_tree(&commitment_tree);
+        let spent_node = Node::new(spent.cmu().to_repr());
+        commitment_tree.append(spent_node).unwrap();
+        unspent_witness.append(spent_node).unwrap();
+        let spent_witness = IncrementalWitness::from_tree(&commitment_tree);
+
+        // The second note is spent, so its nullifier is in the storage
+        let height = BlockHeight(2);
+        let merklization_height = BlockHeight(1);
+        let unspent_nf_key = masp_nullifier_key(&unspent.nf(&vk.nk, 0));
+        let spent_nf_key = masp_nullifier_key(&spent.nf(&vk.nk, 1));
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        tree.update(
+            &masp_merklization_height_key(),
+            merklization_height.serialize_to_vec(),
+        )
+        .unwrap();
+        tree.update(
+            &masp_commitment_tree_key(),
+            commitment_tree.serialize_to_vec(),
+        )
+        .unwrap();
+        // A proof from before the note was spent
+        let stale_nf_proof = non_existence_proof(&tree, &spent_nf_key);
+        tree.update(&spent_nf_key, ().serialize_to_vec()).unwrap();
+        let root = tree.root();
+
+        let disclose = |notes: Vec<DisclosedNote>| BalanceDisclosure {
+            height,
+            viewing_key: viewing_key.into(),
+            merklization_height,
+            merklization_height_proof: existence_proof(
+                &tree,
+                &masp_merklization_height_key(),
+                merklization_height.serialize_to_vec(),
+            ),
+            commitment_tree: commitment_tree.clone(),
+            commitment_tree_proof: existence_proof(
+                &tree,
+                &masp_commitment_tree_key(),
+                commitment_tree.serialize_to_vec(),
+            ),
+            notes,
+        };
+        let unspent_note = DisclosedNote {
+            note: unspent,
+            diversifier: *payment_address.diversifier(),
+            position: 0,
+            witness: unspent_witness,
+            nullifier_proof: Some(non_existence_proof(&tree, &unspent_nf_key)),
+        };
+
+        // The unspent note is accepted after a serialization round trip
+        let disclosure = disclose(vec![unspent_note.clone()]);
+        let bytes = disclosure.serialize_to_vec();
+        let disclosure = BalanceDisclosure::try_from_slice(&bytes).unwrap();
+        let verified = disclosure.verify(&root).unwrap();
+        assert_eq!(verified.balance, BTreeMap::from([(asset_type, 10)]));
+        assert!(verified.unproven_notes.is_empty());
+
+        // The spent note is rejected, as its nullifier is in the tree
+        let spent_note = DisclosedNote {
+            note: spent,
+            diversifier: *payment_address.diversifier(),
+            position: 1,
+            witness: spent_witness,
+            nullifier_proof: Some(stale_nf_proof),
+        };
+        assert_eq!(
+            disclose(vec![unspent_note.clone(), spent_note])
+                .verify(&root)
+                .unwrap_err(),
+            DisclosureError::InvalidNullifierProof(1)
+        );
+
+        // A note disclosed twice is rejected
+        assert_eq!(
+            disclose(vec![unspent_note.clone(), unspent_note.clone()])
+                .verify(&root)
+                .unwrap_err(),
+            DisclosureError::DuplicateNote(0)
+        );
+
+        // A disclosure before the merklization height is rejected, since
+        // the nullifiers weren't in the tree yet
+        let mut disclosure = disclose(vec![unspent_note]);
+        disclosure.height = BlockHeight(0);
+        assert_eq!(
+            disclosure.verify(&root).unwrap_err(),
+            DisclosureError::NotMerklized(merklization_height)
+        );
+
+        // A disclosure without a proof of the merklization height is rejected
+        let mut disclosure = disclose(vec![]);
+        disclosure.merklization_height_proof = StorageProof { ops: vec![] };
+        assert!(matches!(
+            disclosure.verify(&root),
+            Err(DisclosureError::Proof(_))
+        ));
+    }
+}
@@ -0,0 +1,744 @@
+//! MASP transaction provers. A [`MaspProver`] proves and signs the MASP
+//! transactions built by the client, either in-process with the local MASP
+//! parameters, or by delegating to an external prover that speaks the
+//! request/response protocol defined here over any byte stream (e.g. the
+//! stdio of a child process, a local socket or a TLS connection).
+//!
+//! The client sends the builder of the transaction with its spends identified
+//! by the full viewing keys of their spending keys, as in the MASP builder
+//! sections of the transactions. The prover signs the spends with the
+//! spending keys sent along with the builder, if any, or else with the
+//! spending keys it holds itself. A client can hence make shielded transfers
+//! from a viewing key without ever holding its spending key.
+//!
+//! The requests carry the private notes of the transaction and possibly
+//! spending keys, so a stream to a remote host must be authenticated and
+//! encrypted. Plain TCP connections are thus only accepted to loopback
+//! addresses, and remote provers are reached over mutually authenticated TLS.
+
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::io::{self, Read, Write};
+use std::net::ToSocketAddrs;
+use std::sync::Mutex;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use masp_primitives::consensus::TestNetwork;
+use masp_primitives::transaction::builder::{self, Builder, MapBuilder};
+use masp_primitives::transaction::components::sapling::builder::SaplingMetadata;
+use masp_primitives::transaction::components::U64Sum;
+use masp_primitives::transaction::fees::fixed::FeeRule;
+use masp_primitives::transaction::Transaction;
+use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
+use masp_proofs::prover::LocalTxProver;
+use rand::rngs::StdRng;
+use rand_core::SeedableRng;
+use thiserror::Error;
+
+/// Env var to point to an external MASP prover. Its value is either the
+/// address of a prover listening on a loopback TCP socket, a
+/// `tls://<host>:<port>` address of a remote prover, or the path to a prover
+/// executable that communicates over its stdio.
+pub const ENV_VAR_MASP_PROVER: &str = "NAMADA_MASP_PROVER";
+
+/// Env var to the PEM file of the certificate authorities of the remote MASP
+/// prover
+pub const ENV_VAR_MASP_PROVER_CA: &str = "NAMADA_MASP_PROVER_CA";
+
+/// Env var to the PEM file of the client certificate chain presented to the
+/// remote MASP prover
+pub const ENV_VAR_MASP_PROVER_CERT: &str = "NAMADA_MASP_PROVER_CERT";
+
+/// Env var to the PEM file of the private key of the client certificate
+pub const ENV_VAR_MASP_PROVER_KEY: &str = "NAMADA_MASP_PROVER_KEY";
+
+/// The scheme of the addresses of remote MASP provers reached over TLS
+pub const TLS_SCHEME: &str = "tls://";
+
+/// The maximum length of a prover message, in bytes. The largest messages
+/// carry the builders and proofs of transactions with many notes, well below
+/// this limit.
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// The builder of a MASP transaction whose spends are identified by the full
+/// viewing keys of their spending keys
+pub type ViewingKeyBuilder = Builder<(), (), ExtendedFullViewingKey, ()>;
+
+/// A proven and signed MASP transaction with its metadata
+pub type BuiltTransaction = (Transaction, SaplingMetadata);
+
+/// Errors of the MASP provers
+#[derive(Error, Debug)]
+pub enum ProverError {
+    /// The transaction could not be built
+    #[error("{0}")]
+    Build(#[from] builder::Error<Infallible>),
+    /// A spend of the transaction has no known spending key
+    #[error(
+        "The spending key of a spend of the MASP transaction is unknown to \
+         the prover"
+    )]
+    MissingSpendingKey,
+    /// The external prover could not be reached
+    #[error("Failed to communicate with the external MASP prover: {0}")]
+    Io(#[from] io::Error),
+    /// The external prover failed to build the transaction
+    #[error("The external MASP prover failed: {0}")]
+    Remote(String),
+}
+
+/// A prover of MASP transactions
+pub trait MaspProver: Send + Sync {
+    /// Prove the transaction of the given builder, and sign its spends with
+    /// the given spending keys or with the spending keys held by the prover.
+    /// The randomness of the proofs and signatures is drawn from a RNG seeded
+    /// with the given seed.
+    fn build(
+        &self,
+        builder: ViewingKeyBuilder,
+        spending_keys: Vec<ExtendedSpendingKey>,
+        seed: [u8; 32],
+    ) -> Result<BuiltTransaction, ProverError>;
+}
+
+/// A prover generating the proofs in-process
+pub struct LocalMaspProver {
+    prover: LocalTxProver,
+    spending_keys: Vec<ExtendedSpendingKey>,
+}
+
+impl LocalMaspProver {
+    /// Prove with the given local prover
+    pub fn new(prover: LocalTxProver) -> Self {
+        Self {
+            prover,
+            spending_keys: vec![],
+        }
+    }
+
+    /// Sign the spends of the given spending keys when the client doesn't
+    /// send them
+    pub fn with_spending_keys(
+        mut self,
+        spending_keys: Vec<ExtendedSpendingKey>,
+    ) -> Self {
+        self.spending_keys = spending_keys;
+        self
+    }
+}
+
+impl MaspProver for LocalMaspProver {
+    fn build(
+        &self,
+        builder: ViewingKeyBuilder,
+        spending_keys: Vec<ExtendedSpendingKey>,
+        seed: [u8; 32],
+    ) -> Result<BuiltTransaction, ProverError> {
+        let map = SpendingKeyMap {
+            spending_keys: spending_keys
+                .into_iter()
+                .chain(self.spending_keys.iter().copied())
+                .collect(),
+            seed,
+            missing: Cell::new(false),
+        };
+        let builder: Builder<TestNetwork, StdRng, ExtendedSpendingKey, ()> =
+            builder.map_builder(&map);
+        if map.missing.get() {
+            return Err(ProverError::MissingSpendingKey);
+        }
+        Ok(builder
+            .build(&self.prover, &FeeRule::non_standard(U64Sum::zero()))?)
+    }
+}
+
+/// Thaw a builder whose spends are identified by full viewing keys into a
+/// builder that can be proven, by looking up the spending keys of the full
+/// viewing keys. A spend without a known spending key is recorded in
+/// `missing`, as the mapping cannot fail.
+struct SpendingKeyMap {
+    spending_keys: Vec<ExtendedSpendingKey>,
+    seed: [u8; 32],
+    missing: Cell<bool>,
+}
+
+impl
+    masp_primitives::transaction::components::sapling::builder::MapBuilder<
+        (),
+        ExtendedFullViewingKey,
+        TestNetwork,
+        ExtendedSpendingKey,
+    > for &SpendingKeyMap
+{
+    fn map_params(&self, _s: ()) -> TestNetwork {
+        TestNetwork
+    }
+
+    fn map_key(&self, s: ExtendedFullViewingKey) -> ExtendedSpendingKey {
+        self.spending_keys
+            .iter()
+            .find(|sk| ExtendedFullViewingKey::from(*sk) == s)
+            .copied()
+            .unwrap_or_else(|| {
+                self.missing.set(true);
+                ExtendedSpendingKey::master(&[])
+            })
+    }
+}
+
+impl
+    MapBuilder<
+        (),
+        (),
+        ExtendedFullViewingKey,
+        (),
+        TestNetwork,
+        StdRng,
+        ExtendedSpendingKey,
+        (),
+    > for &SpendingKeyMap
+{
+    fn map_rng(&self, _s: ()) -> StdRng {
+        StdRng::from_seed(self.seed)
+    }
+
+    fn map_notifier(&self, _s: ()) {}
+}
+
+/// A request sent to an external MASP prover
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct ProverRequest {
+    /// The builder of the transaction to prove
+    pub builder: ViewingKeyBuilder,
+    /// The spending keys of the spends of the transaction. Empty if the
+    /// prover holds the spending keys itself.
+    pub spending_keys: Vec<ExtendedSpendingKey>,
+    /// The seed of the randomness of the proofs and signatures
+    pub seed: [u8; 32],
+}
+
+/// A response of an external MASP prover to a [`ProverRequest`]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub enum ProverResponse {
+    /// The proven and signed transaction and its metadata
+    Built(Transaction, SaplingMetadata),
+    /// The request could not be processed
+    Error(String),
+}
+
+/// Write a length-prefixed message to the given stream
+pub fn write_message<T: BorshSerialize>(
+    stream: &mut impl Write,
+    message: &T,
+) -> io::Result<()> {
+    let bytes = borsh::to_vec(message)?;
+    if bytes.len() > MAX_MESSAGE_LEN {
+        return Err(invalid_data("Message too long"));
+    }
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| invalid_data("Message too long"))?;
+    stream.write_all(&len.to_le_bytes())?;
+    stream.write_all(&bytes)?;
+    stream.flush()
+}
+
+/// Read a length-prefixed message from the given stream. Returns `None` if
+/// the stream was closed before the start of a message.
+pub fn read_message<T: BorshDeserialize>(
+    stream: &mut impl Read,
+) -> io::Result<Option<T>> {
+    let mut len = [0u8; 4];
+    match stream.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+            return Ok(None);
+        }
+        Err(err) => return Err(err),
+    }
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(invalid_data("Message too long"));
+    }
+    let mut bytes = vec![0u8; len];
+    stream.read_exact(&mut bytes)?;
+    T::try_from_slice(&bytes).map(Some)
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// A connection to an external MASP prover. Every build is a blocking round
+/// trip over the underlying stream.
+pub struct RemoteTxProver {
+    stream: Mutex<Box<dyn ProverStream>>,
+}
+
+/// A byte stream to an external MASP prover
+pub trait ProverStream: Read + Write + Send {}
+
+impl<S: Read + Write + Send> ProverStream for S {}
+
+impl RemoteTxProver {
+    /// Use an already established stream to an external prover
+    pub fn new(stream: impl ProverStream + 'static) -> Self {
+        Self {
+            stream: Mutex::new(Box::new(stream)),
+        }
+    }
+
+    /// Connect to an external prover listening on the given TCP address,
+    /// which must be a loopback address as the requests are not encrypted.
+    /// Use [`RemoteTxProver::connect_tls`] to reach a remote host.
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let addrs = addr.to_socket_addrs()?.collect::<Vec<_>>();
+        if addrs.iter().any(|addr| !addr.ip().is_loopback()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "A plain TCP connection to an external MASP prover must be to \
+                 a loopback address, use TLS to reach a remote prover",
+            ));
+        }
+        std::net::TcpStream::connect(&addrs[..]).map(Self::new)
+    }
+
+    /// Connect to an external prover on the given host over TLS. The prover
+    /// must present a certificate for the host issued by one of the given
+    /// authorities, and this client authenticates with the given certificate.
+    #[cfg(feature = "std")]
+    pub fn connect_tls(
+        host: &str,
+        port: u16,
+        credentials: &TlsCredentials,
+    ) -> io::Result<Self> {
+        let server_name = rustls::ServerName::try_from(host).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid MASP prover host name {host}"),
+            )
+        })?;
+        let conn = rustls::ClientConnection::new(
+            credentials.client_config()?,
+            server_name,
+        )
+        .map_err(tls_error)?;
+        let stream = std::net::TcpStream::connect((host, port))?;
+        Ok(Self::new(rustls::StreamOwned::new(conn, stream)))
+    }
+
+    /// Spawn the given prover executable and communicate with it over its
+    /// stdin and stdout
+    #[cfg(feature = "std")]
+    pub fn spawn(program: impl AsRef<std::ffi::OsStr>) -> io::Result<Self> {
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let not_piped = || {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "Failed to pipe the stdio of the external MASP prover",
+            )
+        };
+        let stdin = child.stdin.take().ok_or_else(not_piped)?;
+        let stdout = child.stdout.take().ok_or_else(not_piped)?;
+        Ok(Self::new(ChildStream {
+            child,
+            stdin,
+            stdout,
+        }))
+    }
+
+    /// Connect to the external prover given by [`ENV_VAR_MASP_PROVER`], if
+    /// any
+    #[cfg(feature = "std")]
+    pub fn from_env() -> io::Result<Option<Self>> {
+        let Ok(prover) = std::env::var(ENV_VAR_MASP_PROVER) else {
+            return Ok(None);
+        };
+        if let Some(addr) = prover.strip_prefix(TLS_SCHEME) {
+            let (host, port) = addr
+                .rsplit_once(':')
+                .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Invalid MASP prover address {prover}"),
+                    )
+                })?;
+            let credentials = TlsCredentials::from_env()?;
+            return Self::connect_tls(host, port, &credentials).map(Some);
+        }
+        match prover.parse::<std::net::SocketAddr>() {
+            Ok(addr) => Self::connect(addr),
+            Err(_) => Self::spawn(prover),
+        }
+        .map(Some)
+    }
+}
+
+impl MaspProver for RemoteTxProver {
+    fn build(
+        &self,
+        builder: ViewingKeyBuilder,
+        spending_keys: Vec<ExtendedSpendingKey>,
+        seed: [u8; 32],
+    ) -> Result<BuiltTransaction, ProverError> {
+        let request = ProverRequest {
+            builder,
+            spending_keys,
+            seed,
+        };
+        let mut stream = self
+            .stream
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "Poisoned"))?;
+        write_message(&mut *stream, &request)?;
+        match read_message(&mut *stream)? {
+            Some(ProverResponse::Built(tx, metadata)) => Ok((tx, metadata)),
+            Some(ProverResponse::Error(err)) => Err(ProverError::Remote(err)),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The external MASP prover closed the connection",
+            )
+            .into()),
+        }
+    }
+}
+
+/// The stdio of a spawned prover process. The process is killed when the
+/// stream is dropped.
+#[cfg(feature = "std")]
+struct ChildStream {
+    child: std::process::Child,
+    stdin: std::process::ChildStdin,
+    stdout: std::process::ChildStdout,
+}
+
+#[cfg(feature = "std")]
+impl Read for ChildStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stdout.read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for ChildStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stdin.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdin.flush()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for ChildStream {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The PEM files of one end of a mutually authenticated TLS connection
+/// between a client and an external MASP prover
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct TlsCredentials {
+    /// The certificates of the authorities that issue the certificates of the
+    /// other end
+    pub ca_certs: std::path::PathBuf,
+    /// The certificate chain presented by this end
+    pub cert_chain: std::path::PathBuf,
+    /// The private key of the certificate of this end
+    pub private_key: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl TlsCredentials {
+    /// Get the client credentials from the [`ENV_VAR_MASP_PROVER_CA`],
+    /// [`ENV_VAR_MASP_PROVER_CERT`] and [`ENV_VAR_MASP_PROVER_KEY`] env vars
+    pub fn from_env() -> io::Result<Self> {
+        let var = |name: &str| {
+            std::env::var_os(name).map(Into::into).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("The {name} env var is required to use TLS"),
+                )
+            })
+        };
+        Ok(Self {
+            ca_certs: var(ENV_VAR_MASP_PROVER_CA)?,
+            cert_chain: var(ENV_VAR_MASP_PROVER_CERT)?,
+            private_key: var(ENV_VAR_MASP_PROVER_KEY)?,
+        })
+    }
+
+    /// The configuration of a client verifying the prover's certificate and
+    /// authenticating with its own
+    pub fn client_config(
+        &self,
+    ) -> io::Result<std::sync::Arc<rustls::ClientConfig>> {
+        let (roots, cert_chain, private_key) = self.load()?;
+        rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_client_auth_cert(cert_chain, private_key)
+            .map(std::sync::Arc::new)
+            .map_err(tls_error)
+    }
+
+    /// The configuration of a prover only accepting the clients with a
+    /// certificate issued by one of the authorities
+    pub fn server_config(
+        &self,
+    ) -> io::Result<std::sync::Arc<rustls::ServerConfig>> {
+        let (roots, cert_chain, private_key) = self.load()?;
+        let verifier = rustls::server::AllowAnyAuthenticatedClient::new(roots);
+        rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_client_cert_verifier(std::sync::Arc::new(verifier))
+            .with_single_cert(cert_chain, private_key)
+            .map(std::sync::Arc::new)
+            .map_err(tls_error)
+    }
+
+    fn load(
+        &self,
+    ) -> io::Result<(
+        rustls::RootCertStore,
+        Vec<rustls::Certificate>,
+        rustls::PrivateKey,
+    )> {
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in read_pem_certs(&self.ca_certs)? {
+            roots.add(&cert).map_err(tls_error)?;
+        }
+        let cert_chain = read_pem_certs(&self.cert_chain)?;
+        let mut reader =
+            io::BufReader::new(std::fs::File::open(&self.private_key)?);
+        let private_key = loop {
+            match rustls_pemfile::read_one(&mut reader)? {
+                Some(
+                    rustls_pemfile::Item::PKCS8Key(key)
+                    | rustls_pemfile::Item::RSAKey(key)
+                    | rustls_pemfile::Item::ECKey(key),
+                ) => break rustls::PrivateKey(key),
+                Some(_) => continue,
+                None => {
+                    return Err(invalid_data(format!(
+                        "No private key in {}",
+                        self.private_key.to_string_lossy()
+                    )));
+                }
+            }
+        };
+        Ok((roots, cert_chain, private_key))
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_pem_certs(
+    path: &std::path::Path,
+) -> io::Result<Vec<rustls::Certificate>> {
+    let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader)?;
+    if certs.is_empty() {
+        return Err(invalid_data(format!(
+            "No certificate in {}",
+            path.to_string_lossy()
+        )));
+    }
+    Ok(certs.into_iter().map(rustls::Certificate).collect())
+}
+
+#[cfg(feature = "std")]
+fn tls_error(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+}
+
+/// Wrap an accepted TCP connection of a prover into a TLS stream with the
+/// given server configuration
+#[cfg(feature = "std")]
+pub fn accept_tls(
+    config: std::sync::Arc<rustls::ServerConfig>,
+    stream: std::net::TcpStream,
+) -> io::Result<impl ProverStream> {
+    let conn = rustls::ServerConnection::new(config).map_err(tls_error)?;
+    Ok(rustls::StreamOwned::new(conn, stream))
+}
+
+/// Answer the requests read from the given stream with the given prover until
+/// the stream is closed. This is the server side of [`RemoteTxProver`].
+pub fn serve<P, S>(prover: &P, mut stream: S) -> io::Result<()>
+where
+    P: MaspProver + ?Sized,
+    S: Read + Write,
+{
+    while let Some(request) = read_message::<ProverRequest>(&mut stream)? {
+        let response = match prover.build(
+            request.builder,
+            request.spending_keys,
+            request.seed,
+        ) {
+            Ok((tx, metadata)) => ProverResponse::Built(tx, metadata),
+            Err(err) => ProverResponse::Error(err.to_string()),
+        };
+        write_message(&mut stream, &response)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A prover that rejects every transaction
+    struct RejectingProver;
+
+    impl MaspProver for RejectingProver {
+        fn build(
+            &self,
+            _builder: ViewingKeyBuilder,
+            spending_keys: Vec<ExtendedSpendingKey>,
+            _seed: [u8; 32],
+        ) -> Result<BuiltTransaction, ProverError> {
+            assert!(spending_keys.is_empty());
+            Err(ProverError::MissingSpendingKey)
+        }
+    }
+
+    #[test]
+    fn test_prover_response_roundtrip() {
+        let response = ProverResponse::Error("rejected".to_string());
+        let mut bytes = vec![];
+        write_message(&mut bytes, &response).unwrap();
+        let mut stream = &bytes[..];
+        let decoded: Option<ProverResponse> =
+            read_message(&mut stream).unwrap();
+        match decoded {
+            Some(ProverResponse::Error(err)) => assert_eq!(err, "rejected"),
+            _ => panic!("Unexpected decoded response"),
+        }
+        // The stream is closed after the message
+        let decoded: Option<ProverResponse> =
+            read_message(&mut stream).unwrap();
+        assert!(decoded.is_none());
+    }
+
+    #[test]
+    fn test_serve_build_request() {
+        // A request without spending keys is answered with the error of the
+        // prover
+        let request = ProverRequest {
+            builder: Builder::<TestNetwork, StdRng>::new_with_rng(
+                TestNetwork,
+                1u32.into(),
+                StdRng::seed_from_u64(0),
+            )
+            .map_builder(&SpendingKeyMapBack),
+            spending_keys: vec![],
+            seed: [0; 32],
+        };
+        let mut input = vec![];
+        write_message(&mut input, &request).unwrap();
+        let mut output = vec![];
+        serve(&RejectingProver, ReadWrite(&input[..], &mut output)).unwrap();
+        let mut stream = &output[..];
+        match read_message(&mut stream).unwrap() {
+            Some(ProverResponse::Error(err)) => {
+                assert_eq!(err, ProverError::MissingSpendingKey.to_string())
+            }
+            _ => panic!("Unexpected response"),
+        }
+    }
+
+    #[test]
+    fn test_prover_message_too_long() {
+        // A peer-sent length above the limit is rejected before allocating
+        let len = u32::try_from(MAX_MESSAGE_LEN + 1).unwrap();
+        let bytes = len.to_le_bytes();
+        let mut stream = &bytes[..];
+        let err = read_message::<ProverRequest>(&mut stream).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_remote_prover_rejects_non_loopback() {
+        let err = RemoteTxProver::connect("192.0.2.1:7777")
+            .err()
+            .expect("Non-loopback addresses should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_tls_credentials_missing_files() {
+        let credentials = TlsCredentials {
+            ca_certs: "/nonexistent/ca.pem".into(),
+            cert_chain: "/nonexistent/cert.pem".into(),
+            private_key: "/nonexistent/key.pem".into(),
+        };
+        let err = RemoteTxProver::connect_tls("localhost", 7777, &credentials)
+            .err()
+            .expect("Missing credentials should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    /// Freeze a builder without spends into a [`ViewingKeyBuilder`]
+    struct SpendingKeyMapBack;
+
+    impl<P1>
+        masp_primitives::transaction::components::sapling::builder::MapBuilder<
+            P1,
+            ExtendedSpendingKey,
+            (),
+            ExtendedFullViewingKey,
+        > for &SpendingKeyMapBack
+    {
+        fn map_params(&self, _s: P1) {}
+
+        fn map_key(&self, s: ExtendedSpendingKey) -> ExtendedFullViewingKey {
+            (&s).into()
+        }
+    }
+
+    impl<P1, R1, N1>
+        MapBuilder<
+            P1,
+            R1,
+            ExtendedSpendingKey,
+            N1,
+            (),
+            (),
+            ExtendedFullViewingKey,
+            (),
+        > for &SpendingKeyMapBack
+    {
+        fn map_rng(&self, _s: R1) {}
+
+        fn map_notifier(&self, _s: N1) {}
+    }
+
+    /// A stream reading from and writing to separate buffers
+    struct ReadWrite<'a>(&'a [u8], &'a mut Vec<u8>);
+
+    impl Read for ReadWrite<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Write for ReadWrite<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.1.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
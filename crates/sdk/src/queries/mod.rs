@@ -4,7 +4,7 @@
 // Re-export to show in rustdoc!
 use namada_core::types::storage::BlockHeight;
 use namada_state::{DBIter, StorageHasher, DB};
-pub use shell::Shell;
+pub use shell::{Shell, MAX_STATE_DIFF_CHANGES, MAX_STORAGE_VALUES_KEYS};
 use shell::SHELL;
 pub use types::{
     EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
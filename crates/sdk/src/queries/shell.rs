@@ -9,13 +9,13 @@ use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_account::{Account, AccountPublicKeysMap};
 use namada_core::hints;
-use namada_core::types::address::Address;
+use namada_core::types::address::{Address, InternalAddress, MASP};
 use namada_core::types::dec::Dec;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::{
-    self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
+    self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue, StateDiff,
 };
-use namada_core::types::token::{Denomination, MaspDigitPos};
+use namada_core::types::token::{self, Denomination, MaspDigitPos};
 use namada_core::types::uint::Uint;
 use namada_state::{DBIter, LastBlock, StorageHasher, DB};
 use namada_storage::{self, ResultExt, StorageRead};
@@ -28,7 +28,7 @@ use crate::events::{Event, EventType};
 use crate::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, PortId, Sequence,
 };
-use crate::masp::MaspTokenRewardData;
+use crate::masp::{MaspStats, MaspTokenRewardData, MaspTokenStats};
 use crate::queries::types::{RequestCtx, RequestQuery};
 use crate::queries::{require_latest_height, EncodedResponseQuery};
 use crate::tendermint::merkle::proof::ProofOps;
@@ -50,6 +50,14 @@ type Conversion = (
     MerklePath<Node>,
 );
 
+/// The maximum number of storage keys that can be read in a single
+/// `/shell/values` request
+pub const MAX_STORAGE_VALUES_KEYS: usize = 256;
+
+/// The maximum number of state changes returned in a single page of the
+/// `/shell/state_diff` and `/shell/state_diff_prefix` requests
+pub const MAX_STATE_DIFF_CHANGES: usize = 1024;
+
 router! {SHELL,
     // Shell provides storage read access, block metadata and can dry-run a tx
 
@@ -72,6 +80,11 @@ router! {SHELL,
     ( "value" / [storage_key: storage::Key] )
         -> Vec<u8> = (with_options storage_value),
 
+    // Raw storage access - read the values of the borsh-encoded keys in the
+    // request data
+    ( "values" )
+        -> Vec<(storage::Key, Option<Vec<u8>>)> = (with_options storage_values),
+
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
@@ -92,9 +105,22 @@ router! {SHELL,
     // Conversion state access - read conversion
     ( "masp_reward_tokens" ) -> Vec<MaspTokenRewardData> = masp_reward_tokens,
 
+    // Shielded pool statistics
+    ( "masp_stats" ) -> MaspStats = masp_stats,
+
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
+    // A page of the changes of the state committed in the block at the given
+    // height
+    ( "state_diff" / [height: BlockHeight] / [page: u64] )
+        -> StateDiff = state_diff,
+
+    // A page of the changes of the state committed in the block at the given
+    // height of the keys with the given prefix
+    ( "state_diff_prefix" / [height: BlockHeight] / [prefix: storage::Key]
+        / [page: u64] ) -> StateDiff = state_diff_prefix,
+
     // was the transaction accepted?
     ( "accepted" / [tx_hash: Hash] ) -> Option<Event> = accepted,
 
@@ -164,6 +190,97 @@ where
     Ok(results)
 }
 
+/// Query a page of the changes of the state committed in the block at the
+/// given height. The height `0` is the last committed block. The pages, from
+/// `0`, hold [`MAX_STATE_DIFF_CHANGES`] changes ordered by their keys, so a
+/// page with fewer changes is the last one.
+fn state_diff<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+    page: u64,
+) -> namada_storage::Result<StateDiff>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let height = state_diff_height(&ctx, height)?;
+    Ok(ctx.wl_storage.storage.get_state_diff_page(
+        height,
+        None,
+        state_diff_page_offset(page),
+        MAX_STATE_DIFF_CHANGES,
+    ))
+}
+
+/// Query a page of the changes of the state committed in the block at the
+/// given height of the keys with the given prefix. The height `0` is the last
+/// committed block. The pages are the same as for [`state_diff`].
+fn state_diff_prefix<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+    prefix: storage::Key,
+    page: u64,
+) -> namada_storage::Result<StateDiff>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let height = state_diff_height(&ctx, height)?;
+    Ok(ctx.wl_storage.storage.get_state_diff_page(
+        height,
+        Some(&prefix),
+        state_diff_page_offset(page),
+        MAX_STATE_DIFF_CHANGES,
+    ))
+}
+
+/// Get the number of changes preceding the given page of a state diff query
+fn state_diff_page_offset(page: u64) -> usize {
+    usize::try_from(page)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(MAX_STATE_DIFF_CHANGES)
+}
+
+/// Get the height of a state diff query, which must be a committed block
+/// within the configured limit of the past heights that can be read
+fn state_diff_height<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> namada_storage::Result<BlockHeight>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_committed_height = ctx.wl_storage.storage.get_last_block_height();
+    let height = if height.0 == 0 {
+        last_committed_height
+    } else {
+        height
+    };
+    if height > last_committed_height {
+        return Err(namada_storage::Error::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The block at height {height} has not been committed yet, the \
+                 last committed height is {last_committed_height}."
+            ),
+        )));
+    }
+    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
+        if height + past_height_limit < last_committed_height {
+            return Err(namada_storage::Error::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot query more than {past_height_limit} blocks in the \
+                     past (configured via \
+                     `shell.storage_read_past_height_limit`)."
+                ),
+            )));
+        }
+    }
+    Ok(height)
+}
+
 /// Query to read the conversion state
 fn read_conversions<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -300,6 +417,59 @@ where
     Ok(data)
 }
 
+/// Query the shielded pool statistics: the MASP balance of every token held by
+/// the MASP, the note commitment tree size, the number of revealed nullifiers
+/// and the epoch of the latest conversions.
+fn masp_stats<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<MaspStats>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    // Tokens that aren't in the conversion state can still be held by the
+    // MASP, so all the balance keys are scanned
+    let balances_prefix = storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    );
+    let mut tokens = vec![];
+    for entry in
+        namada_storage::iter_prefix_bytes(ctx.wl_storage, &balances_prefix)?
+    {
+        let (key, value) = entry?;
+        match namada_token::storage_key::is_any_token_balance_key(&key) {
+            Some([token, owner]) if *owner == MASP => {
+                let balance = token::Amount::try_from_slice(&value)
+                    .into_storage_result()?;
+                tokens.push(MaspTokenStats {
+                    address: token.clone(),
+                    balance,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let conversion_state = &ctx.wl_storage.storage.conversion_state;
+
+    let note_commitments =
+        namada_token::utils::note_commitment_count(ctx.wl_storage)?;
+    let nullifiers = namada_token::utils::nullifier_count(ctx.wl_storage)?;
+    let conversion_epoch = conversion_state
+        .assets
+        .values()
+        .map(|(_, epoch, ..)| *epoch)
+        .max()
+        .unwrap_or(ctx.wl_storage.storage.last_epoch);
+
+    Ok(MaspStats {
+        tokens,
+        note_commitments,
+        nullifiers,
+        conversion_epoch,
+    })
+}
+
 fn epoch<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
 ) -> namada_storage::Result<Epoch>
@@ -356,30 +526,7 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    let last_committed_height = ctx.wl_storage.storage.get_last_block_height();
-    let queried_height = {
-        let height: BlockHeight = request.height.into();
-        let is_last_height_query = height.0 == 0;
-
-        if hints::likely(is_last_height_query) {
-            last_committed_height
-        } else {
-            height
-        }
-    };
-
-    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
-        if queried_height + past_height_limit < last_committed_height {
-            return Err(namada_storage::Error::new(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!(
-                    "Cannot query more than {past_height_limit} blocks in the \
-                     past (configured via \
-                     `shell.storage_read_past_height_limit`)."
-                ),
-            )));
-        }
-    }
+    let queried_height = queried_storage_height(&ctx, request)?;
 
     match ctx
         .wl_storage
@@ -424,6 +571,100 @@ where
     }
 }
 
+/// Returns the values of several storage keys, given as borsh-encoded keys in
+/// the request data, with `None` for the keys that are not found. With
+/// `prove`, a single batch proof of the values, or of the absence of the keys
+/// that are not found, is returned. Requests with more than
+/// [`MAX_STORAGE_VALUES_KEYS`] keys are rejected.
+fn storage_values<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let queried_height = queried_storage_height(&ctx, request)?;
+    let keys = Vec::<storage::Key>::try_from_slice(&request.data)
+        .into_storage_result()?;
+    if keys.len() > MAX_STORAGE_VALUES_KEYS {
+        return Err(namada_storage::Error::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "Cannot query more than {MAX_STORAGE_VALUES_KEYS} storage \
+                 keys in a single request, got {}",
+                keys.len()
+            ),
+        )));
+    }
+
+    let mut values = Vec::with_capacity(keys.len());
+    for key in keys {
+        let (value, _gas) = ctx
+            .wl_storage
+            .storage
+            .read_with_height(&key, queried_height)
+            .into_storage_result()?;
+        values.push((key, value));
+    }
+    let proof = if request.prove {
+        let entries: Vec<_> = values
+            .iter()
+            .map(|(key, value)| (key.clone(), value.as_deref()))
+            .collect();
+        let proof = ctx
+            .wl_storage
+            .storage
+            .get_batch_proof(&entries, queried_height)
+            .into_storage_result()?;
+        Some(proof)
+    } else {
+        None
+    };
+    Ok(EncodedResponseQuery {
+        data: values.serialize_to_vec(),
+        proof,
+        ..Default::default()
+    })
+}
+
+/// Get the height of a storage read query, within the configured limit of
+/// the past heights that can be read
+fn queried_storage_height<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<BlockHeight>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_committed_height = ctx.wl_storage.storage.get_last_block_height();
+    let queried_height = {
+        let height: BlockHeight = request.height.into();
+        let is_last_height_query = height.0 == 0;
+
+        if hints::likely(is_last_height_query) {
+            last_committed_height
+        } else {
+            height
+        }
+    };
+
+    if let Some(past_height_limit) = ctx.storage_read_past_height_limit {
+        if queried_height + past_height_limit < last_committed_height {
+            return Err(namada_storage::Error::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot query more than {past_height_limit} blocks in the \
+                     past (configured via \
+                     `shell.storage_read_past_height_limit`)."
+                ),
+            )));
+        }
+    }
+    Ok(queried_height)
+}
+
 fn storage_prefix<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     request: &RequestQuery,
@@ -589,11 +830,13 @@ where
     if account_exists {
         let public_keys = namada_account::public_keys(ctx.wl_storage, &owner)?;
         let threshold = namada_account::threshold(ctx.wl_storage, &owner)?;
+        let weights = namada_account::weights(ctx.wl_storage, &owner)?;
 
         Ok(Some(Account {
             public_keys_map: AccountPublicKeysMap::from_iter(public_keys),
             address: owner,
             threshold: threshold.unwrap_or(1),
+            weights,
         }))
     } else {
         Ok(None)
@@ -616,6 +859,7 @@ where
 #[cfg(test)]
 mod test {
     use namada_core::types::address;
+    use namada_core::types::storage::BlockHeight;
     use namada_token::storage_key::balance_key;
 
     use crate::queries::RPC;
@@ -631,6 +875,9 @@ mod test {
         let path = RPC.shell().storage_value_path(&key);
         assert_eq!(format!("/shell/value/{}", key), path);
 
+        let path = RPC.shell().storage_values_path();
+        assert_eq!("/shell/values", path);
+
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
@@ -639,5 +886,15 @@ mod test {
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
+
+        let path = RPC.shell().masp_stats_path();
+        assert_eq!("/shell/masp_stats", path);
+
+        let height = BlockHeight(10);
+        let path = RPC.shell().state_diff_path(&height, &2);
+        assert_eq!("/shell/state_diff/10/2", path);
+
+        let path = RPC.shell().state_diff_prefix_path(&height, &key, &2);
+        assert_eq!(format!("/shell/state_diff_prefix/10/{}/2", key), path);
     }
 }
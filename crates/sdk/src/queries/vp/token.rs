@@ -3,13 +3,17 @@
 use namada_core::types::address::Address;
 use namada_core::types::token;
 use namada_state::{DBIter, StorageHasher, DB};
-use namada_token::{read_denom, read_total_supply};
+use namada_token::{
+    read_allowance, read_denom, read_metadata, read_total_supply,
+};
 
 use crate::queries::RequestCtx;
 
 router! {TOKEN,
     ( "denomination" / [addr: Address] ) -> Option<token::Denomination> = denomination,
     ( "total_supply" / [addr: Address] ) -> token::Amount = total_supply,
+    ( "metadata" / [addr: Address] ) -> Option<token::TokenMetadata> = metadata,
+    ( "allowance" / [token: Address] / [owner: Address] / [spender: Address] ) -> Option<token::Allowance> = allowance,
 }
 
 /// Get the number of decimal places (in base 10) for a
@@ -37,6 +41,32 @@ where
     read_total_supply(ctx.wl_storage, &addr)
 }
 
+/// Get the on-chain metadata of a token address, if any
+fn metadata<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    addr: Address,
+) -> namada_storage::Result<Option<token::TokenMetadata>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_metadata(ctx.wl_storage, &addr)
+}
+
+/// Get the allowance of a spender over an owner's balance of a token, if any
+fn allowance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> namada_storage::Result<Option<token::Allowance>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_allowance(ctx.wl_storage, &token, &owner, &spender)
+}
+
 #[cfg(any(test, feature = "async-client"))]
 pub mod client_only_methods {
     use borsh::BorshDeserialize;
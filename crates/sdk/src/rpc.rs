@@ -6,15 +6,18 @@ use std::ops::ControlFlow;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
-use namada_account::Account;
+use namada_account::{
+    Account, GuardianSet, PendingRecovery, QueuedWithdrawal, SpendingPolicy,
+};
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::{
-    BlockHeight, BlockResults, Epoch, Key, PrefixValue,
+    BlockHeight, BlockResults, Epoch, Key, KeySeg, PrefixValue, StateDiff,
 };
 use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDigitPos,
@@ -34,7 +37,8 @@ use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
     BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
 };
-use namada_state::LastBlock;
+use namada_state::merkle_tree::{verify_batch_storage_proof, MerkleRoot};
+use namada_state::{LastBlock, Sha256Hasher};
 use namada_tx::data::{ResultCode, TxResult};
 use serde::Serialize;
 
@@ -44,7 +48,7 @@ use crate::error::{EncodingError, Error, QueryError, TxSubmitError};
 use crate::events::Event;
 use crate::internal_macros::echo_error;
 use crate::io::Io;
-use crate::masp::MaspTokenRewardData;
+use crate::masp::{MaspStats, MaspTokenRewardData};
 use crate::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
 use crate::queries::{Client, RPC};
 use crate::tendermint::block::Height;
@@ -239,6 +243,32 @@ pub async fn has_bonds<C: crate::queries::Client + Sync>(
     convert_response::<C, bool>(RPC.vp().pos().has_bonds(client, source).await)
 }
 
+/// Query the owners of a non-zero balance of any token.
+pub async fn query_token_holders<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<HashSet<Address>, error::Error> {
+    let prefix =
+        Key::from(Address::Internal(InternalAddress::Multitoken).to_db_key());
+    let balances = convert_response::<C, _>(
+        RPC.shell()
+            .storage_prefix(client, None, None, false, &prefix)
+            .await,
+    )?;
+    let mut holders = HashSet::new();
+    for PrefixValue { key, value } in balances.data {
+        if let Some([_token, owner]) =
+            namada_token::storage_key::is_any_token_balance_key(&key)
+        {
+            let balance = Amount::try_from_slice(&value)
+                .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+            if !balance.is_zero() {
+                holders.insert(owner.clone());
+            }
+        }
+    }
+    Ok(holders)
+}
+
 /// Get the set of pgf stewards
 pub async fn query_pgf_stewards<C: crate::queries::Client + Sync>(
     client: &C,
@@ -329,6 +359,49 @@ pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the shielded pool statistics.
+pub async fn query_masp_stats<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<MaspStats, Error> {
+    convert_response::<C, _>(RPC.shell().masp_stats(client).await)
+}
+
+/// Query the changes of the state committed in the block at the given
+/// height, optionally only the ones of the keys with the given prefix. The
+/// changes are fetched by pages of at most
+/// [`MAX_STATE_DIFF_CHANGES`](crate::queries::MAX_STATE_DIFF_CHANGES).
+pub async fn query_state_diff<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+    prefix: Option<&Key>,
+) -> Result<StateDiff, Error> {
+    let mut diff = StateDiff {
+        height,
+        changes: vec![],
+    };
+    for page in 0_u64.. {
+        let response = match prefix {
+            Some(prefix) => {
+                RPC.shell()
+                    .state_diff_prefix(client, &diff.height, prefix, &page)
+                    .await
+            }
+            None => RPC.shell().state_diff(client, &diff.height, &page).await,
+        };
+        let StateDiff { height, changes } = convert_response::<C, _>(response)?;
+        // The height `0` of the request is resolved by the node to the last
+        // committed block, which must be kept for the next pages
+        diff.height = height;
+        let is_last_page =
+            changes.len() < crate::queries::MAX_STATE_DIFF_CHANGES;
+        diff.changes.extend(changes);
+        if is_last_page {
+            break;
+        }
+    }
+    Ok(diff)
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -411,6 +484,68 @@ pub async fn query_storage_value_bytes<C: crate::queries::Client + Sync>(
     })
 }
 
+/// The values of several storage keys at a block height, with a batch proof
+/// of the values, or of the absence of the keys without a value
+#[derive(Debug, Clone)]
+pub struct ProvenStorageValues {
+    /// The height of the block whose storage the values were read from
+    pub height: BlockHeight,
+    /// The queried keys with their values, if any
+    pub values: Vec<(storage::Key, Option<Vec<u8>>)>,
+    /// The batch proof of the values
+    pub proof: ProofOps,
+}
+
+impl ProvenStorageValues {
+    /// Verify the values against the storage merkle root of the block at
+    /// their height, i.e. the app hash in the header of the next block, which
+    /// the verifier must obtain from a trusted source.
+    pub fn verify(&self, root: &MerkleRoot) -> Result<bool, error::Error> {
+        let entries: Vec<_> = self
+            .values
+            .iter()
+            .map(|(key, value)| (key.clone(), value.as_deref()))
+            .collect();
+        verify_batch_storage_proof::<Sha256Hasher>(&self.proof, root, &entries)
+            .map_err(|err| Error::Other(err.to_string()))
+    }
+}
+
+/// Query the values of several storage keys at a block height in a single
+/// request, with a batch proof of the values that can be checked with
+/// [`ProvenStorageValues::verify`]. The keys can be in different sub-trees of
+/// the merkle tree, except for the Ethereum bridge pool one. At most
+/// [`MAX_STORAGE_VALUES_KEYS`](crate::queries::MAX_STORAGE_VALUES_KEYS) keys
+/// can be queried at once.
+pub async fn query_proven_storage_values<C: crate::queries::Client + Sync>(
+    client: &C,
+    keys: &[storage::Key],
+    height: BlockHeight,
+) -> Result<ProvenStorageValues, error::Error> {
+    let data = Some(keys.serialize_to_vec());
+    let response = convert_response::<C, _>(
+        RPC.shell()
+            .storage_values(client, data, Some(height), true)
+            .await,
+    )?;
+    let values =
+        Vec::<(storage::Key, Option<Vec<u8>>)>::try_from_slice(&response.data)
+            .map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })?;
+    let proof = response.proof.ok_or_else(|| {
+        Error::from(QueryError::General(
+            "The node did not return a proof of the storage values"
+                .to_string(),
+        ))
+    })?;
+    Ok(ProvenStorageValues {
+        height,
+        values,
+        proof,
+    })
+}
+
 /// Query a range of storage values with a matching prefix and decode them with
 /// [`BorshDeserialize`]. Returns an iterator of the storage keys paired with
 /// their associated values.
@@ -1185,6 +1320,99 @@ pub async fn query_denom<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the on-chain metadata of a token, if any.
+pub async fn query_token_metadata<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<Option<token::TokenMetadata>, error::Error> {
+    convert_response::<C, _>(RPC.vp().token().metadata(client, token).await)
+}
+
+/// Query the allowance of a spender over an owner's balance of a token, if
+/// any.
+pub async fn query_allowance<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> Result<Option<token::Allowance>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .token()
+            .allowance(client, token, owner, spender)
+            .await,
+    )
+}
+
+/// Query the spending policy of an account, if any.
+pub async fn query_spending_policy<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Option<SpendingPolicy>, error::Error> {
+    let key = namada_account::spending_policy_key(owner);
+    let (value, _proof) =
+        query_storage_value_bytes(client, &key, None, false).await?;
+    value
+        .map(|bytes| {
+            SpendingPolicy::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query the queued withdrawals of an account, keyed by their ids.
+pub async fn query_queued_withdrawals<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<BTreeMap<u64, QueuedWithdrawal>, error::Error> {
+    let key = namada_account::queued_withdrawals_key(owner);
+    let (value, _proof) =
+        query_storage_value_bytes(client, &key, None, false).await?;
+    value
+        .map(|bytes| {
+            BTreeMap::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Query the guardian set of an account, if any.
+pub async fn query_guardians<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Option<GuardianSet>, error::Error> {
+    let key = namada_account::guardians_key(owner);
+    let (value, _proof) =
+        query_storage_value_bytes(client, &key, None, false).await?;
+    value
+        .map(|bytes| {
+            GuardianSet::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query the recovery of an account initiated by its guardians, if any.
+pub async fn query_pending_recovery<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Option<PendingRecovery>, error::Error> {
+    let key = namada_account::pending_recovery_key(owner);
+    let (value, _proof) =
+        query_storage_value_bytes(client, &key, None, false).await?;
+    value
+        .map(|bytes| {
+            PendingRecovery::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
 /// Get the correct representation of the amount given the token type.
 pub async fn validate_amount<N: Namada>(
     context: &N,
@@ -0,0 +1,121 @@
+//! Pluggable backends holding the secret keys that sign transactions
+
+#[cfg(unix)]
+pub mod remote;
+
+use std::collections::HashSet;
+use std::future::Future;
+
+use namada_account::AccountPublicKeysMap;
+use namada_core::types::key::*;
+use namada_tx::Tx;
+use tokio::sync::RwLock;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::signing::Signable;
+use crate::wallet::{Wallet, WalletIo};
+use crate::{MaybeSend, MaybeSync};
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+/// A backend signing the parts of transactions with the secret keys it holds
+pub trait Signer: MaybeSync {
+    /// Sign the given parts of a transaction with the secret key of the given
+    /// public key and return the signed transaction
+    async fn sign(
+        &self,
+        tx: Tx,
+        public_key: &common::PublicKey,
+        parts: HashSet<Signable>,
+    ) -> Result<Tx, Error>;
+}
+
+/// A signer using the secret keys of a local wallet
+pub struct WalletSigner<'a, U> {
+    wallet: &'a RwLock<Wallet<U>>,
+    password: Option<Zeroizing<String>>,
+}
+
+impl<'a, U> WalletSigner<'a, U> {
+    /// Sign with the keys of the given wallet, decrypting them with the given
+    /// password if any
+    pub fn new(
+        wallet: &'a RwLock<Wallet<U>>,
+        password: Option<Zeroizing<String>>,
+    ) -> Self {
+        Self { wallet, password }
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<'a, U> Signer for WalletSigner<'a, U>
+where
+    U: WalletIo + MaybeSend + MaybeSync,
+{
+    async fn sign(
+        &self,
+        mut tx: Tx,
+        public_key: &common::PublicKey,
+        parts: HashSet<Signable>,
+    ) -> Result<Tx, Error> {
+        let secret_key = self
+            .wallet
+            .write()
+            .await
+            .find_key_by_pk(public_key, self.password.clone())
+            .map_err(|err| {
+                Error::Other(format!(
+                    "Unable to load the keypair from the wallet for public \
+                     key {}. Failed with: {}",
+                    public_key, err
+                ))
+            })?;
+        if parts.contains(&Signable::RawHeader) {
+            tx.sign_raw(
+                vec![secret_key.clone()],
+                AccountPublicKeysMap::from_iter([public_key.clone()]),
+                None,
+            );
+        }
+        if parts.contains(&Signable::FeeHeader) {
+            tx.sign_wrapper(secret_key);
+        }
+        Ok(tx)
+    }
+}
+
+/// A signer delegating to a signing function called with the given user data,
+/// such as a hardware wallet
+pub struct SignWith<S, D> {
+    sign: S,
+    user_data: D,
+}
+
+impl<S, D> SignWith<S, D> {
+    /// Sign with the given function and user data
+    pub fn new(sign: S, user_data: D) -> Self {
+        Self { sign, user_data }
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<S, D, F> Signer for SignWith<S, D>
+where
+    S: Fn(Tx, common::PublicKey, HashSet<Signable>, D) -> F
+        + MaybeSend
+        + MaybeSync,
+    D: Clone + MaybeSend + MaybeSync,
+    F: Future<Output = Result<Tx, Error>> + MaybeSend,
+{
+    async fn sign(
+        &self,
+        tx: Tx,
+        public_key: &common::PublicKey,
+        parts: HashSet<Signable>,
+    ) -> Result<Tx, Error> {
+        (self.sign)(tx, public_key.clone(), parts, self.user_data.clone()).await
+    }
+}
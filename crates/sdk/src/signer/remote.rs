@@ -0,0 +1,367 @@
+//! A signer forwarding the signing requests to a separate signing daemon,
+//! such as a service fronting a hardware security module.
+//!
+//! The daemon listens on a Unix domain socket and serves one request per
+//! connection. Each message is a little-endian `u32` length followed by that
+//! many bytes of a Borsh encoded [`SignRequest`] from the client and of a
+//! [`SignResponse`] from the daemon. The client sends the transaction without
+//! its MASP builders and the daemon returns only the signature sections it
+//! added to the transaction, which the client appends to its own copy of the
+//! transaction.
+//!
+//! [`serve`] is a reference daemon backed by any other [`Signer`] that refuses
+//! the requests not allowed by a [`SignerPolicy`].
+
+use std::collections::HashSet;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use namada_core::types::hash::Hash;
+use namada_core::types::key::*;
+use namada_tx::{Section, Signature, Tx};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+use super::Signer;
+use crate::error::{EncodingError, Error};
+use crate::signing::Signable;
+
+/// The maximum length of a message, in bytes
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// The time given to a client to send its request or to receive the response
+/// of the daemon, after which the connection is dropped
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A request to sign a transaction
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct SignRequest {
+    /// The transaction to sign, without its MASP builders
+    pub tx: Tx,
+    /// The public key of the secret key to sign with
+    pub public_key: common::PublicKey,
+    /// Whether to sign the raw header of the transaction
+    pub raw_header: bool,
+    /// Whether to sign the wrapper header of the transaction
+    pub fee_header: bool,
+}
+
+/// The response of a signing daemon to a [`SignRequest`]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub enum SignResponse {
+    /// The signature sections added to the transaction
+    Signed(Vec<Signature>),
+    /// The reason why the request was refused
+    Refused(String),
+}
+
+/// A signer forwarding the signing requests to a signing daemon listening on
+/// a local socket
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    socket_path: PathBuf,
+}
+
+impl RemoteSigner {
+    /// Sign with the daemon listening on the given socket
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl Signer for RemoteSigner {
+    async fn sign(
+        &self,
+        mut tx: Tx,
+        public_key: &common::PublicKey,
+        parts: HashSet<Signable>,
+    ) -> Result<Tx, Error> {
+        // The MASP builders hold private data that the daemon does not need,
+        // so only send it a copy of the transaction without them
+        let mut request_tx = tx.clone();
+        request_tx.protocol_filter();
+        let request = SignRequest {
+            tx: request_tx,
+            public_key: public_key.clone(),
+            raw_header: parts.contains(&Signable::RawHeader),
+            fee_header: parts.contains(&Signable::FeeHeader),
+        };
+        let mut stream =
+            UnixStream::connect(&self.socket_path).await.map_err(|err| {
+                Error::Other(format!(
+                    "Unable to connect to the remote signer at {}: {}",
+                    self.socket_path.to_string_lossy(),
+                    err
+                ))
+            })?;
+        write_message(&mut stream, &request).await?;
+        let signatures = match read_message(&mut stream).await? {
+            SignResponse::Signed(signatures) => signatures,
+            SignResponse::Refused(reason) => {
+                return Err(Error::Other(format!(
+                    "The remote signer refused to sign with {}: {}",
+                    public_key, reason
+                )));
+            }
+        };
+        // The wrapper signature commits to all the sections, so the MASP
+        // builders must be dropped from the transaction as the wallet does
+        if request.fee_header {
+            tx.protocol_filter();
+        }
+        for signature in signatures {
+            // Only accept the signatures made with the requested key
+            let is_signed_with_key = match &signature.signer {
+                namada_tx::Signer::PubKeys(pks) => {
+                    pks.len() == 1 && pks[0] == *public_key
+                }
+                namada_tx::Signer::Address(_) => false,
+            };
+            if !is_signed_with_key {
+                return Err(Error::Other(format!(
+                    "The remote signer returned a signature that is not made \
+                     with {}",
+                    public_key
+                )));
+            }
+            tx.add_section(Section::Signature(signature));
+        }
+        Ok(tx)
+    }
+}
+
+/// The allow-list of a signing daemon
+#[derive(Debug, Clone, Default)]
+pub struct SignerPolicy {
+    /// The public keys that may be signed with
+    pub public_keys: HashSet<common::PublicKey>,
+    /// The hashes of the transaction codes that may be signed. All the codes
+    /// are allowed if empty.
+    pub tx_code_hashes: HashSet<Hash>,
+}
+
+impl SignerPolicy {
+    /// Check that the given request is allowed by this policy
+    pub fn check(&self, request: &SignRequest) -> Result<(), String> {
+        if !self.public_keys.contains(&request.public_key) {
+            return Err(format!(
+                "the public key {} is not allowed",
+                request.public_key
+            ));
+        }
+        if !self.tx_code_hashes.is_empty() {
+            let code_hash = request
+                .tx
+                .get_section(request.tx.code_sechash())
+                .and_then(|section| section.code_sec())
+                .map(|code| code.code.hash())
+                .ok_or_else(|| "the transaction has no code".to_string())?;
+            if !self.tx_code_hashes.contains(&code_hash) {
+                return Err(format!(
+                    "the transaction code {} is not allowed",
+                    code_hash
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serve the signing requests received on the given socket with the given
+/// signer, refusing the requests not allowed by the given policy. The socket
+/// is only accessible by the user running the daemon. The connections are
+/// served one at a time, and dropped if the client is too slow to send its
+/// request or to receive the response.
+pub async fn serve(
+    socket_path: &Path,
+    signer: &impl Signer,
+    policy: &SignerPolicy,
+) -> std::io::Result<()> {
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(
+        socket_path,
+        std::fs::Permissions::from_mode(0o600),
+    )?;
+    loop {
+        let (mut stream, _addr) = listener.accept().await?;
+        if let Err(err) = serve_request(&mut stream, signer, policy).await {
+            tracing::warn!("Failed to serve a signing request: {}", err);
+        }
+    }
+}
+
+/// Serve the signing request of a connection
+async fn serve_request(
+    stream: &mut UnixStream,
+    signer: &impl Signer,
+    policy: &SignerPolicy,
+) -> Result<(), Error> {
+    let mut request: SignRequest = with_timeout(read_message(stream)).await?;
+    request.tx.protocol_filter();
+    let response = match policy.check(&request) {
+        Ok(()) => match sign_request(signer, request).await {
+            Ok(signatures) => SignResponse::Signed(signatures),
+            Err(err) => SignResponse::Refused(err.to_string()),
+        },
+        Err(reason) => {
+            tracing::info!("Refused a signing request: {}", reason);
+            SignResponse::Refused(reason)
+        }
+    };
+    with_timeout(write_message(stream, &response)).await
+}
+
+/// Fail the given message exchange if it takes longer than the timeout
+async fn with_timeout<T>(
+    exchange: impl std::future::Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    tokio::time::timeout(MESSAGE_TIMEOUT, exchange)
+        .await
+        .map_err(|_| {
+            Error::Other("The signing message timed out".to_string())
+        })?
+}
+
+/// Sign the transaction of the given request and return the added signatures
+async fn sign_request(
+    signer: &impl Signer,
+    request: SignRequest,
+) -> Result<Vec<Signature>, Error> {
+    let mut parts = HashSet::new();
+    if request.raw_header {
+        parts.insert(Signable::RawHeader);
+    }
+    if request.fee_header {
+        parts.insert(Signable::FeeHeader);
+    }
+    let num_sections = request.tx.sections.len();
+    let signed_tx = signer.sign(request.tx, &request.public_key, parts).await?;
+    Ok(signed_tx
+        .sections
+        .into_iter()
+        .skip(num_sections)
+        .filter_map(|section| match section {
+            Section::Signature(signature) => Some(signature),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Write a length-prefixed message to the given stream
+async fn write_message<T: BorshSerialize>(
+    stream: &mut UnixStream,
+    message: &T,
+) -> Result<(), Error> {
+    let bytes = message.serialize_to_vec();
+    if bytes.len() > MAX_MESSAGE_LEN {
+        return Err(Error::Other(format!(
+            "The signing message of {} bytes is too long",
+            bytes.len()
+        )));
+    }
+    let len = bytes.len() as u32;
+    stream
+        .write_all(&len.to_le_bytes())
+        .await
+        .map_err(|err| Error::Other(err.to_string()))?;
+    stream
+        .write_all(&bytes)
+        .await
+        .map_err(|err| Error::Other(err.to_string()))
+}
+
+/// Read a length-prefixed message from the given stream
+async fn read_message<T: BorshDeserialize>(
+    stream: &mut UnixStream,
+) -> Result<T, Error> {
+    let mut len = [0u8; 4];
+    stream
+        .read_exact(&mut len)
+        .await
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(Error::Other(format!(
+            "The signing message of {} bytes is too long",
+            len
+        )));
+    }
+    let mut bytes = vec![0u8; len];
+    stream
+        .read_exact(&mut bytes)
+        .await
+        .map_err(|err| Error::Other(err.to_string()))?;
+    T::try_from_slice(&bytes)
+        .map_err(|err| Error::from(EncodingError::Decoding(err.to_string())))
+}
+
+#[cfg(test)]
+mod test_remote_signer {
+    use namada_core::types::chain::ChainId;
+    use namada_core::types::key::testing::{keypair_1, keypair_2};
+
+    use super::*;
+    use crate::signer::SignWith;
+
+    /// Sign the raw header with the first testing keypair
+    async fn sign_with_keypair_1(
+        mut tx: Tx,
+        _public_key: common::PublicKey,
+        _parts: HashSet<Signable>,
+        _user_data: (),
+    ) -> Result<Tx, Error> {
+        let keypair = keypair_1();
+        let public_keys = [keypair.ref_to()].into_iter().collect();
+        tx.sign_raw(vec![keypair], public_keys, None);
+        Ok(tx)
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("signer.sock");
+        let policy = SignerPolicy {
+            public_keys: [keypair_1().ref_to()].into_iter().collect(),
+            tx_code_hashes: HashSet::new(),
+        };
+        let signer = SignWith::new(sign_with_keypair_1, ());
+        let daemon = serve(&socket_path, &signer, &policy);
+
+        let client = async {
+            // Wait for the daemon to listen
+            while !socket_path.exists() {
+                tokio::task::yield_now().await;
+            }
+            let remote_signer = RemoteSigner::new(&socket_path);
+            let tx = Tx::new(ChainId::default(), None);
+            let parts = HashSet::from([Signable::RawHeader]);
+            let signed_tx = remote_signer
+                .sign(tx.clone(), &keypair_1().ref_to(), parts)
+                .await
+                .unwrap();
+            assert_eq!(signed_tx.sections.len(), 1);
+
+            // The keys missing from the allow-list are refused
+            let parts = HashSet::from([Signable::RawHeader]);
+            assert!(
+                remote_signer
+                    .sign(tx, &keypair_2().ref_to(), parts)
+                    .await
+                    .is_err()
+            );
+        };
+
+        tokio::select! {
+            biased;
+            result = daemon => panic!("The daemon stopped: {:?}", result),
+            () = client => {}
+        }
+    }
+}
@@ -1,5 +1,5 @@
 //! Functions to sign transactions
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Display;
 
 use borsh::BorshDeserialize;
@@ -10,7 +10,10 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::components::sapling::fees::{
     InputView, OutputView,
 };
-use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
+use masp_primitives::transaction::Transaction;
+use namada_account::{
+    AccountPublicKeysMap, AccountWeights, InitAccount, UpdateAccount,
+};
 use namada_core::types::address::{
     Address, ImplicitAddress, InternalAddress, MASP,
 };
@@ -30,7 +33,7 @@ use namada_parameters::storage as parameter_storage;
 use namada_token::storage_key::balance_key;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::BecomeValidator;
-use namada_tx::data::{pos, Fee};
+use namada_tx::data::{pos, Fee, WrapperTx};
 use namada_tx::{MaspBuilder, Section, Tx};
 use prost::Message;
 use rand::rngs::OsRng;
@@ -38,13 +41,14 @@ use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use tokio::sync::RwLock;
 
-use super::masp::{ShieldedContext, ShieldedTransfer};
+use super::masp::{masp_fee_payment_target, ShieldedContext, ShieldedTransfer};
 use crate::args::SdkTypes;
 use crate::error::{EncodingError, Error, TxSubmitError};
 use crate::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use crate::ibc::primitives::proto::Any;
 use crate::io::*;
 use crate::rpc::validate_amount;
+use crate::signer::{Signer, WalletSigner};
 use crate::tx::{
     TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
     TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
@@ -59,7 +63,7 @@ use crate::tx::{
 use crate::types::eth_bridge_pool::PendingTransfer;
 pub use crate::wallet::store::AddressVpType;
 use crate::wallet::{Wallet, WalletIo};
-use crate::{args, display_line, rpc, MaybeSend, Namada};
+use crate::{args, display_line, rpc, MaybeSend, MaybeSync, Namada};
 
 /// A structure holding the signing data to craft a transaction
 #[derive(Clone)]
@@ -196,19 +200,20 @@ pub async fn default_sign(
 /// hashes needed for monitoring the tx on chain.
 ///
 /// If it is a dry run, it is not put in a wrapper, but returned as is.
-pub async fn sign_tx<'a, D, F, U>(
+///
+/// The keys that are not found in the software wallet are signed with by the
+/// given signer.
+pub async fn sign_tx<U>(
     wallet: &RwLock<Wallet<U>>,
     args: &args::Tx,
     tx: &mut Tx,
     signing_data: SigningTxData,
-    sign: impl Fn(Tx, common::PublicKey, HashSet<Signable>, D) -> F,
-    user_data: D,
+    signer: &impl Signer,
 ) -> Result<(), Error>
 where
-    D: Clone + MaybeSend,
-    U: WalletIo,
-    F: std::future::Future<Output = Result<Tx, Error>>,
+    U: WalletIo + MaybeSend + MaybeSync,
 {
+    let wallet_signer = WalletSigner::new(wallet, args.password.clone());
     let mut used_pubkeys = HashSet::new();
 
     // First try to sign the raw header with the supplied signatures
@@ -225,73 +230,57 @@ where
         tx.add_signatures(signatures);
     }
 
-    // Then try to sign the raw header with private keys in the software wallet
-    if let Some(account_public_keys_map) = signing_data.account_public_keys_map
-    {
-        let mut wallet = wallet.write().await;
-        let signing_tx_keypairs = signing_data
-            .public_keys
-            .iter()
-            .filter_map(|public_key| {
-                if used_pubkeys.contains(public_key) {
-                    None
-                } else {
-                    match find_key_by_pk(&mut wallet, args, public_key) {
-                        Ok(secret_key) => {
-                            used_pubkeys.insert(public_key.clone());
-                            Some(secret_key)
-                        }
-                        Err(_) => None,
-                    }
-                }
-            })
-            .collect::<Vec<common::SecretKey>>();
-        if !signing_tx_keypairs.is_empty() {
-            tx.sign_raw(
-                signing_tx_keypairs,
-                account_public_keys_map,
-                signing_data.owner,
-            );
-        }
-    }
-
-    // Then try to sign the raw header using the hardware wallet
+    // Then try to sign the raw header with the private keys in the software
+    // wallet, otherwise using the given signer
     for pubkey in signing_data.public_keys {
-        if !used_pubkeys.contains(&pubkey) && pubkey != signing_data.fee_payer {
-            if let Ok(ntx) = sign(
-                tx.clone(),
-                pubkey.clone(),
-                HashSet::from([Signable::RawHeader]),
-                user_data.clone(),
-            )
-            .await
+        if used_pubkeys.contains(&pubkey) {
+            continue;
+        }
+        if signing_data.account_public_keys_map.is_some() {
+            let parts = HashSet::from([Signable::RawHeader]);
+            if let Ok(ntx) =
+                wallet_signer.sign(tx.clone(), &pubkey, parts).await
             {
                 *tx = ntx;
-                used_pubkeys.insert(pubkey.clone());
+                used_pubkeys.insert(pubkey);
+                continue;
+            }
+        }
+        if pubkey != signing_data.fee_payer {
+            let parts = HashSet::from([Signable::RawHeader]);
+            if let Ok(ntx) = signer.sign(tx.clone(), &pubkey, parts).await {
+                *tx = ntx;
+                used_pubkeys.insert(pubkey);
             }
         }
     }
 
+    // Then aggregate the BLS signatures made on behalf of a multisignature
+    // account, before the fee header commits to the sections
+    if let (Some(owner), Some(account_public_keys_map)) =
+        (&signing_data.owner, &signing_data.account_public_keys_map)
+    {
+        tx.aggregate_signatures(owner, account_public_keys_map);
+    }
+
     // Then try signing the fee header with the software wallet otherwise use
-    // the fallback
-    let key = {
-        // Lock the wallet just long enough to extract a key from it without
-        // interfering with the sign closure call
-        let mut wallet = wallet.write().await;
-        find_key_by_pk(&mut *wallet, args, &signing_data.fee_payer)
-    };
-    match key {
-        Ok(fee_payer_keypair) => {
-            tx.sign_wrapper(fee_payer_keypair);
+    // the given signer
+    let parts = HashSet::from([Signable::FeeHeader]);
+    match wallet_signer
+        .sign(tx.clone(), &signing_data.fee_payer, parts)
+        .await
+    {
+        Ok(ntx) => {
+            *tx = ntx;
         }
         Err(_) => {
-            *tx = sign(
-                tx.clone(),
-                signing_data.fee_payer.clone(),
-                HashSet::from([Signable::FeeHeader, Signable::RawHeader]),
-                user_data,
-            )
-            .await?;
+            *tx = signer
+                .sign(
+                    tx.clone(),
+                    &signing_data.fee_payer,
+                    HashSet::from([Signable::FeeHeader, Signable::RawHeader]),
+                )
+                .await?;
         }
     }
     Ok(())
@@ -316,6 +305,15 @@ pub async fn aux_signing_data(
             let account =
                 rpc::get_account_info(context.client(), owner).await?;
             if let Some(account) = account {
+                if let Some(weights) = &account.weights {
+                    check_signing_weight(
+                        context,
+                        args,
+                        &account.public_keys_map,
+                        weights,
+                        &public_keys,
+                    )?;
+                }
                 (Some(account.public_keys_map), account.threshold)
             } else {
                 return Err(Error::from(TxSubmitError::InvalidAccount(
@@ -344,6 +342,14 @@ pub async fn aux_signing_data(
             .await
             .gen_disposable_signing_key(&mut OsRng)
             .to_public()
+    } else if args.masp_fee.is_some() {
+        // The fee is paid from the shielded pool, the wrapper only needs to
+        // be signed by a fresh key that is never persisted
+        context
+            .wallet_mut()
+            .await
+            .gen_ephemeral_signing_key(&mut OsRng)
+            .to_public()
     } else {
         match &args.wrapper_fee_payer {
             Some(keypair) => keypair.clone(),
@@ -363,6 +369,48 @@ pub async fn aux_signing_data(
     })
 }
 
+/// Check that the signing keys and the keys of the given signatures reach the
+/// weight threshold of a weighted account. The number of keys signing on
+/// behalf of a weighted account doesn't tell if they authorize the tx.
+fn check_signing_weight(
+    context: &impl Namada,
+    args: &args::Tx<SdkTypes>,
+    public_keys_map: &AccountPublicKeysMap,
+    weights: &AccountWeights,
+    public_keys: &[common::PublicKey],
+) -> Result<(), Error> {
+    let signature_keys = args.signatures.iter().filter_map(|bytes| {
+        SignatureIndex::deserialize(bytes)
+            .ok()
+            .map(|signature| signature.pubkey)
+    });
+    let indices = public_keys
+        .iter()
+        .cloned()
+        .chain(signature_keys)
+        .filter_map(|public_key| {
+            public_keys_map.get_index_from_public_key(&public_key)
+        })
+        .collect::<BTreeSet<u8>>();
+    let weight = weights.total_weight(&indices);
+    if weight < weights.threshold {
+        edisplay_line!(
+            context.io(),
+            "The weight threshold of the account is {} but the weight of the \
+             signing keys is {}.",
+            weights.threshold,
+            weight
+        );
+        if !args.force {
+            return Err(Error::from(TxSubmitError::MissingSigningWeight(
+                weights.threshold,
+                weight,
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub async fn init_validator_signing_data(
     context: &impl Namada,
     args: &args::Tx<SdkTypes>,
@@ -384,6 +432,14 @@ pub async fn init_validator_signing_data(
             .await
             .gen_disposable_signing_key(&mut OsRng)
             .to_public()
+    } else if args.masp_fee.is_some() {
+        // The fee is paid from the shielded pool, the wrapper only needs to
+        // be signed by a fresh key that is never persisted
+        context
+            .wallet_mut()
+            .await
+            .gen_ephemeral_signing_key(&mut OsRng)
+            .to_public()
     } else {
         match &args.wrapper_fee_payer {
             Some(keypair) => keypair.clone(),
@@ -482,6 +538,56 @@ pub async fn wrap_tx<N: Namada>(
         None => validated_minimum_fee,
     };
 
+    let total_fee = fee_amount.amount() * u64::from(args.gas_limit);
+
+    // Pay the fee directly from the shielded pool if requested: the signer of
+    // the wrapper holds no funds, so its transparent balance is irrelevant
+    if let Some(spending_key) = &args.masp_fee {
+        let fee = Fee {
+            amount_per_gas_unit: fee_amount,
+            token: args.fee_token.clone(),
+        };
+        // The fee is unshielded to a target committing to the wrapper, which
+        // doesn't depend on the hash of the masp section
+        let wrapper = WrapperTx::new(
+            fee.clone(),
+            fee_payer.clone(),
+            epoch,
+            args.gas_limit,
+            None,
+        );
+        let masp_fee =
+            ShieldedContext::<N::ShieldedUtils>::gen_masp_fee_payment(
+                context,
+                spending_key,
+                masp_fee_payment_target(tx, &wrapper),
+                &args.fee_token,
+                DenominatedAmount::new(total_fee, 0.into()),
+            )
+            .await
+            .map_err(|e| TxSubmitError::FeeUnshieldingError(e.to_string()))?
+            .ok_or_else(|| {
+                TxSubmitError::FeeUnshieldingError(
+                    "Missing masp fee payment transaction".to_string(),
+                )
+            })?
+            .masp_tx;
+        check_fee_descriptions_limit(context, &masp_fee, args.force).await?;
+
+        let section = Section::MaspTx(masp_fee);
+        let mut hasher = sha2::Sha256::new();
+        section.hash(&mut hasher);
+        tx.add_section(section);
+        tx.add_wrapper(
+            fee,
+            fee_payer,
+            epoch,
+            args.gas_limit,
+            Some(namada_core::types::hash::Hash(hasher.finalize().into())),
+        );
+        return Ok(());
+    }
+
     let mut updated_balance = match tx_source_balance {
         Some(TxSourcePostBalance {
             post_balance: balance,
@@ -500,8 +606,6 @@ pub async fn wrap_tx<N: Namada>(
         }
     };
 
-    let total_fee = fee_amount.amount() * u64::from(args.gas_limit);
-
     let unshield = match total_fee.checked_sub(updated_balance) {
         Some(diff) if !diff.is_zero() => {
             if let Some(spending_key) = args.fee_unshield.clone() {
@@ -534,45 +638,12 @@ pub async fn wrap_tx<N: Namada>(
                         metadata: _data,
                         epoch: _unshielding_epoch,
                     })) => {
-                        let spends = transaction
-                            .sapling_bundle()
-                            .unwrap()
-                            .shielded_spends
-                            .len();
-                        let converts = transaction
-                            .sapling_bundle()
-                            .unwrap()
-                            .shielded_converts
-                            .len();
-                        let outs = transaction
-                            .sapling_bundle()
-                            .unwrap()
-                            .shielded_outputs
-                            .len();
-
-                        let descriptions = spends + converts + outs;
-
-                        let descriptions_limit_key=  parameter_storage::get_fee_unshielding_descriptions_limit_key();
-                        let descriptions_limit =
-                            rpc::query_storage_value::<_, u64>(
-                                context.client(),
-                                &descriptions_limit_key,
-                            )
-                            .await
-                            .unwrap();
-
-                        if u64::try_from(descriptions).unwrap()
-                            > descriptions_limit
-                            && !args.force
-                        {
-                            return Err(Error::from(
-                                TxSubmitError::FeeUnshieldingError(format!(
-                                    "Descriptions exceed the limit: found \
-                                     {descriptions}, limit \
-                                     {descriptions_limit}"
-                                )),
-                            ));
-                        }
+                        check_fee_descriptions_limit(
+                            context,
+                            &transaction,
+                            args.force,
+                        )
+                        .await?;
 
                         updated_balance += total_fee;
                         Some(transaction)
@@ -656,6 +727,35 @@ pub async fn wrap_tx<N: Namada>(
     Ok(())
 }
 
+// Check that the number of descriptions of the masp transaction used for fee
+// payment does not exceed the limit set by the protocol
+async fn check_fee_descriptions_limit<N: Namada>(
+    context: &N,
+    transaction: &Transaction,
+    force: bool,
+) -> Result<(), Error> {
+    let descriptions = transaction.sapling_bundle().map_or(0, |bundle| {
+        bundle.shielded_spends.len()
+            + bundle.shielded_converts.len()
+            + bundle.shielded_outputs.len()
+    });
+    let descriptions_limit_key =
+        parameter_storage::get_fee_unshielding_descriptions_limit_key();
+    let descriptions_limit = rpc::query_storage_value::<_, u64>(
+        context.client(),
+        &descriptions_limit_key,
+    )
+    .await?;
+
+    if descriptions as u64 > descriptions_limit && !force {
+        return Err(Error::from(TxSubmitError::FeeUnshieldingError(format!(
+            "Descriptions exceed the limit: found {descriptions}, limit \
+             {descriptions_limit}"
+        ))));
+    }
+    Ok(())
+}
+
 #[allow(clippy::result_large_err)]
 fn other_err<T>(string: String) -> Result<T, Error> {
     Err(Error::Other(string))
@@ -924,6 +1024,7 @@ impl<'a> Display for LedgerProposalType<'a> {
             }
             ProposalType::PGFSteward(_) => write!(f, "PGF Steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF Payment"),
+            ProposalType::ShieldedRewards(_) => write!(f, "Shielded Rewards"),
         }
     }
 }
@@ -991,10 +1092,19 @@ pub async fn to_ledger_vector(
                 .iter()
                 .map(|k| format!("Public key : {}", k)),
         );
-        tv.output.extend(vec![
-            format!("Threshold : {}", init_account.threshold),
-            format!("VP type : {}", vp_code),
-        ]);
+        tv.output
+            .extend(vec![format!("Threshold : {}", init_account.threshold)]);
+        if let Some(weights) = &init_account.weights {
+            tv.output
+                .extend(weights.weights.iter().map(|(index, weight)| {
+                    format!("Weight {} : {}", index, weight)
+                }));
+            tv.output.extend(vec![format!(
+                "Weight threshold : {}",
+                weights.threshold
+            )]);
+        }
+        tv.output.extend(vec![format!("VP type : {}", vp_code)]);
 
         tv.output_expert.extend(
             init_account
@@ -1002,10 +1112,21 @@ pub async fn to_ledger_vector(
                 .iter()
                 .map(|k| format!("Public key : {}", k)),
         );
-        tv.output_expert.extend(vec![
-            format!("Threshold : {}", init_account.threshold),
-            format!("VP type : {}", HEXLOWER.encode(&extra.code.hash().0)),
-        ]);
+        tv.output_expert
+            .extend(vec![format!("Threshold : {}", init_account.threshold)]);
+        if let Some(weights) = &init_account.weights {
+            tv.output_expert.extend(weights.weights.iter().map(
+                |(index, weight)| format!("Weight {} : {}", index, weight),
+            ));
+            tv.output_expert.extend(vec![format!(
+                "Weight threshold : {}",
+                weights.threshold
+            )]);
+        }
+        tv.output_expert.extend(vec![format!(
+            "VP type : {}",
+            HEXLOWER.encode(&extra.code.hash().0)
+        )]);
     } else if code_sec.tag == Some(TX_BECOME_VALIDATOR_WASM.to_string()) {
         let init_validator = BecomeValidator::try_from_slice(
             &tx.data()
@@ -1197,6 +1318,15 @@ pub async fn to_ledger_vector(
                 update_account.threshold.unwrap()
             )])
         }
+        if let Some(weights) = &update_account.weights {
+            tv.output.extend(weights.weights.iter().map(|(index, weight)| {
+                format!("Weight {} : {}", index, weight)
+            }));
+            tv.output.extend(vec![format!(
+                "Weight threshold : {}",
+                weights.threshold
+            )]);
+        }
 
         let vp_code_data = match &update_account.vp_code_hash {
             Some(hash) => {
@@ -1230,6 +1360,15 @@ pub async fn to_ledger_vector(
             tv.output_expert
                 .extend(vec![format!("Threshold : {}", threshold,)])
         }
+        if let Some(weights) = &update_account.weights {
+            tv.output_expert.extend(weights.weights.iter().map(
+                |(index, weight)| format!("Weight {} : {}", index, weight),
+            ));
+            tv.output_expert.extend(vec![format!(
+                "Weight threshold : {}",
+                weights.threshold
+            )]);
+        }
         if let Some((_, extra_code_hash)) = vp_code_data {
             tv.output_expert.extend(vec![format!(
                 "VP type : {}",
@@ -1,7 +1,7 @@
 //! SDK functions to construct different types of transactions
 
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -18,7 +18,11 @@ use masp_primitives::transaction::components::transparent::fees::{
     InputView as TransparentInputView, OutputView as TransparentOutputView,
 };
 use masp_primitives::transaction::components::I128Sum;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{
+    AccountWeights, GuardianSet, InitAccount, InitiateRecovery,
+    QueueWithdrawal, QueuedWithdrawalAction, SpendingPolicy, UpdateAccount,
+    UpdateGuardians, UpdateSpendingPolicy,
+};
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::PrefixedCoin;
@@ -37,6 +41,7 @@ use namada_core::types::time::DateTimeUtc;
 use namada_core::types::{storage, token};
 use namada_governance::cli::onchain::{
     DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    ShieldedRewardsProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
@@ -46,7 +51,7 @@ use namada_governance::storage::vote::ProposalVote;
 use namada_ibc::storage::channel_key;
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
-use namada_token::storage_key::balance_key;
+use namada_token::storage_key::{balance_key, minter_key};
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::{pos, ResultCode, TxResult};
 pub use namada_tx::{Signature, *};
@@ -116,6 +121,35 @@ pub const TX_UPDATE_STEWARD_COMMISSION: &str =
     "tx_update_steward_commission.wasm";
 /// Redelegate transaction WASM path
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
+/// Register token WASM path
+pub const TX_REGISTER_TOKEN_WASM: &str = "tx_register_token.wasm";
+/// Mint tokens WASM path
+pub const TX_MINT_TOKENS_WASM: &str = "tx_mint_tokens.wasm";
+/// Burn tokens WASM path
+pub const TX_BURN_TOKENS_WASM: &str = "tx_burn_tokens.wasm";
+/// Update token metadata WASM path
+pub const TX_UPDATE_TOKEN_METADATA_WASM: &str = "tx_update_token_metadata.wasm";
+/// Approve allowance WASM path
+pub const TX_APPROVE_WASM: &str = "tx_approve.wasm";
+/// Transfer within an allowance WASM path
+pub const TX_TRANSFER_FROM_WASM: &str = "tx_transfer_from.wasm";
+/// Update spending policy WASM path
+pub const TX_UPDATE_SPENDING_POLICY_WASM: &str =
+    "tx_update_spending_policy.wasm";
+/// Queue withdrawal WASM path
+pub const TX_QUEUE_WITHDRAWAL_WASM: &str = "tx_queue_withdrawal.wasm";
+/// Execute queued withdrawal WASM path
+pub const TX_EXECUTE_WITHDRAWAL_WASM: &str = "tx_execute_withdrawal.wasm";
+/// Cancel queued withdrawal WASM path
+pub const TX_CANCEL_WITHDRAWAL_WASM: &str = "tx_cancel_withdrawal.wasm";
+/// Update guardians WASM path
+pub const TX_UPDATE_GUARDIANS_WASM: &str = "tx_update_guardians.wasm";
+/// Initiate account recovery WASM path
+pub const TX_INITIATE_RECOVERY_WASM: &str = "tx_initiate_recovery.wasm";
+/// Cancel account recovery WASM path
+pub const TX_CANCEL_RECOVERY_WASM: &str = "tx_cancel_recovery.wasm";
+/// Finalize account recovery WASM path
+pub const TX_FINALIZE_RECOVERY_WASM: &str = "tx_finalize_recovery.wasm";
 
 /// Default timeout in seconds for requests to the `/accepted`
 /// and `/applied` ABCI query endpoints.
@@ -804,52 +838,927 @@ pub async fn build_validator_metadata_change(
     .map(|tx| (tx, signing_data))
 }
 
-/// Craft transaction to update a steward commission
-pub async fn build_update_steward_commission(
+/// Craft transaction to update a steward commission
+pub async fn build_update_steward_commission(
+    context: &impl Namada,
+    args::UpdateStewardCommission {
+        tx: tx_args,
+        steward,
+        commission,
+        tx_code_path,
+    }: &args::UpdateStewardCommission,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(steward.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(steward.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if !rpc::is_steward(context.client(), steward).await && !tx_args.force {
+        edisplay_line!(
+            context.io(),
+            "The given address {} is not a steward.",
+            &steward
+        );
+        return Err(Error::from(TxSubmitError::InvalidSteward(
+            steward.clone(),
+        )));
+    };
+
+    let commission = Commission::try_from(commission.as_ref())
+        .map_err(|e| TxSubmitError::InvalidStewardCommission(e.to_string()))?;
+
+    if !commission.is_valid() && !tx_args.force {
+        edisplay_line!(
+            context.io(),
+            "The sum of all percentage must not be greater than 1."
+        );
+        return Err(Error::from(TxSubmitError::InvalidStewardCommission(
+            "Commission sum is greater than 1.".to_string(),
+        )));
+    }
+
+    let data = UpdateStewardCommission {
+        steward: steward.clone(),
+        commission: commission.reward_distribution,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to resign as a steward
+pub async fn build_resign_steward(
+    context: &impl Namada,
+    args::ResignSteward {
+        tx: tx_args,
+        steward,
+        tx_code_path,
+    }: &args::ResignSteward,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(steward.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(steward.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if !rpc::is_steward(context.client(), steward).await && !tx_args.force {
+        edisplay_line!(
+            context.io(),
+            "The given address {} is not a steward.",
+            &steward
+        );
+        return Err(Error::from(TxSubmitError::InvalidSteward(
+            steward.clone(),
+        )));
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        steward.clone(),
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to register a user-issued token
+pub async fn build_register_token(
+    context: &impl Namada,
+    args::TxRegisterToken {
+        tx: tx_args,
+        token,
+        denom,
+        minter,
+        max_supply,
+        tx_code_path,
+    }: &args::TxRegisterToken,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(token.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(token.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if !matches!(token, Address::Established(_)) {
+        edisplay_line!(
+            context.io(),
+            "Only established accounts can register a token, got {}.",
+            token
+        );
+        return Err(Error::from(TxSubmitError::InvalidTokenRegistration(
+            format!("{token} is not an established account"),
+        )));
+    }
+    if rpc::query_has_storage_key(context.client(), &minter_key(token)).await? {
+        edisplay_line!(
+            context.io(),
+            "The token {} has already been registered.",
+            token
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidTokenRegistration(
+                format!("{token} has already been registered"),
+            )));
+        }
+    }
+    let max_supply = max_supply
+        .map(|max_supply| max_supply.scale(*denom))
+        .transpose()
+        .map_err(|err| {
+            TxSubmitError::InvalidTokenRegistration(err.to_string())
+        })?;
+
+    let data = token::RegisterToken {
+        token: token.clone(),
+        denom: *denom,
+        minter: minter.clone(),
+        max_supply,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to mint a user-issued token
+pub async fn build_mint_tokens(
+    context: &impl Namada,
+    args::TxMintTokens {
+        tx: tx_args,
+        minter,
+        target,
+        token,
+        amount,
+        tx_code_path,
+    }: &args::TxMintTokens,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(minter.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(minter.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let registered_minter = rpc::query_storage_value::<_, Address>(
+        context.client(),
+        &minter_key(token),
+    )
+    .await
+    .ok();
+    if registered_minter.as_ref() != Some(minter) {
+        edisplay_line!(
+            context.io(),
+            "The address {} is not the minter of the token {}.",
+            minter,
+            token
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidMinter(
+                minter.clone(),
+                token.clone(),
+            )));
+        }
+    }
+    // Check that the target address exists on chain
+    target_exists_or_err(target.clone(), tx_args.force, context).await?;
+    let amount = validate_amount(context, *amount, token, tx_args.force).await?;
+
+    let data = token::MintTokens {
+        minter: minter.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to burn a user-issued token. Unless the owner of the
+/// tokens is also their minter, the signature of the minter has to be added
+/// to the transaction as well.
+pub async fn build_burn_tokens(
+    context: &impl Namada,
+    args::TxBurnTokens {
+        tx: tx_args,
+        owner,
+        token,
+        amount,
+        tx_code_path,
+    }: &args::TxBurnTokens,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let amount = validate_amount(context, *amount, token, tx_args.force).await?;
+    let balance_key = balance_key(token, owner);
+    check_balance_too_low_err(
+        token,
+        owner,
+        amount.amount(),
+        balance_key,
+        tx_args.force,
+        context,
+    )
+    .await?;
+
+    let data = token::BurnTokens {
+        owner: owner.clone(),
+        token: token.clone(),
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to update the metadata of a token. The transaction is
+/// signed by the minter of the token.
+pub async fn build_update_token_metadata(
+    context: &impl Namada,
+    args::TxUpdateTokenMetadata {
+        tx: tx_args,
+        token,
+        symbol,
+        name,
+        icon_url,
+        description,
+        ibc_trace,
+        tx_code_path,
+    }: &args::TxUpdateTokenMetadata,
+) -> Result<(Tx, SigningTxData)> {
+    let minter = rpc::query_storage_value::<_, Address>(
+        context.client(),
+        &minter_key(token),
+    )
+    .await
+    .map_err(|_| {
+        TxSubmitError::InvalidTokenRegistration(format!(
+            "{token} has no minter that could update its metadata"
+        ))
+    })?;
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(minter.clone()),
+        Some(minter),
+    )
+    .await?;
+
+    let denom = rpc::query_denom(context.client(), token)
+        .await
+        .ok_or_else(|| {
+            Error::from(QueryError::General(format!(
+                "denomination for token {token}"
+            )))
+        })?;
+    let metadata = token::TokenMetadata {
+        symbol: symbol.clone(),
+        name: name.clone(),
+        denom,
+        icon_url: icon_url.clone(),
+        description: description.clone(),
+        ibc_trace: ibc_trace.clone(),
+    };
+    if !metadata.has_valid_symbol() {
+        edisplay_line!(
+            context.io(),
+            "The token symbol must be a non-empty alphanumeric string of at \
+             most {} characters.",
+            token::TokenMetadata::MAX_SYMBOL_LEN
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidTokenRegistration(
+                format!("invalid token symbol {symbol}"),
+            )));
+        }
+    }
+    if !metadata.has_valid_lengths() {
+        edisplay_line!(
+            context.io(),
+            "The token name, description, icon URL and IBC trace must be at \
+             most {}, {}, {} and {} characters long respectively.",
+            token::TokenMetadata::MAX_NAME_LEN,
+            token::TokenMetadata::MAX_DESCRIPTION_LEN,
+            token::TokenMetadata::MAX_ICON_URL_LEN,
+            token::TokenMetadata::MAX_IBC_TRACE_LEN,
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidTokenRegistration(
+                "token metadata too long".to_string(),
+            )));
+        }
+    }
+
+    let data = token::UpdateTokenMetadata {
+        token: token.clone(),
+        metadata,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to set the allowance of a spender over the owner's
+/// tokens
+pub async fn build_approve(
+    context: &impl Namada,
+    args::TxApprove {
+        tx: tx_args,
+        owner,
+        spender,
+        token,
+        amount,
+        expiry,
+        tx_code_path,
+    }: &args::TxApprove,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let amount = validate_amount(context, *amount, token, tx_args.force).await?;
+
+    let data = token::Approve {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        token: token.clone(),
+        amount,
+        expiry: *expiry,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction for a spender to transfer tokens from the source's
+/// account within its allowance
+pub async fn build_transfer_from(
+    context: &impl Namada,
+    args::TxTransferFrom {
+        tx: tx_args,
+        spender,
+        source,
+        target,
+        token,
+        amount,
+        tx_code_path,
+    }: &args::TxTransferFrom,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(spender.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(spender.clone()),
+        default_signer,
+    )
+    .await?;
+
+    // Check that the target address exists on chain
+    target_exists_or_err(target.clone(), tx_args.force, context).await?;
+    let amount = validate_amount(context, *amount, token, tx_args.force).await?;
+
+    let allowance =
+        rpc::query_allowance(context.client(), token, source, spender).await?;
+    let epoch = rpc::query_epoch(context.client()).await?;
+    let is_sufficient = allowance.map_or(false, |allowance| {
+        !allowance.is_expired(epoch) && allowance.amount >= amount.amount()
+    });
+    if !is_sufficient {
+        edisplay_line!(
+            context.io(),
+            "The address {} has no sufficient allowance to transfer {} of \
+             the token {} from {}.",
+            spender,
+            amount,
+            token,
+            source
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InsufficientAllowance(
+                spender.clone(),
+                token.clone(),
+                source.clone(),
+            )));
+        }
+    }
+
+    let data = token::TransferFrom {
+        spender: spender.clone(),
+        source: source.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to set or remove the spending policy of an account. When
+/// the current policy has a recovery key, a signature of the recovery key has
+/// to be attached to the transaction as well.
+pub async fn build_update_spending_policy(
+    context: &impl Namada,
+    args::TxUpdateSpendingPolicy {
+        tx: tx_args,
+        addr,
+        policy,
+        tx_code_path,
+    }: &args::TxUpdateSpendingPolicy,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(addr.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(addr.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let policy = policy
+        .as_ref()
+        .map(|policy| {
+            serde_json::from_slice::<SpendingPolicy>(policy).map_err(|err| {
+                Error::from(EncodingError::Serde(err.to_string()))
+            })
+        })
+        .transpose()?;
+
+    let current_policy =
+        rpc::query_spending_policy(context.client(), addr).await?;
+    if let Some(recovery_key) =
+        current_policy.and_then(|policy| policy.recovery_key)
+    {
+        display_line!(
+            context.io(),
+            "The current spending policy of {} has a recovery key. The \
+             transaction also has to be signed with the key {}.",
+            addr,
+            recovery_key
+        );
+    }
+
+    let data = UpdateSpendingPolicy {
+        addr: addr.clone(),
+        policy,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to queue a withdrawal above the delay threshold of the
+/// source's spending policy
+pub async fn build_queue_withdrawal(
+    context: &impl Namada,
+    args::TxQueueWithdrawal {
+        tx: tx_args,
+        source,
+        target,
+        token,
+        amount,
+        tx_code_path,
+    }: &args::TxQueueWithdrawal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(source.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(source.clone()),
+        default_signer,
+    )
+    .await?;
+
+    // Check that the target address exists on chain
+    target_exists_or_err(target.clone(), tx_args.force, context).await?;
+    let amount = validate_amount(context, *amount, token, tx_args.force).await?;
+
+    let data = QueueWithdrawal {
+        source: source.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to execute a matured queued withdrawal
+pub async fn build_execute_withdrawal(
+    context: &impl Namada,
+    args::TxExecuteWithdrawal {
+        tx: tx_args,
+        source,
+        id,
+        tx_code_path,
+    }: &args::TxExecuteWithdrawal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(source.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(source.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let withdrawals =
+        rpc::query_queued_withdrawals(context.client(), source).await?;
+    let epoch = rpc::query_epoch(context.client()).await?;
+    match withdrawals.get(id) {
+        Some(withdrawal) if withdrawal.unlock_epoch > epoch => {
+            edisplay_line!(
+                context.io(),
+                "The queued withdrawal {} of {} is locked until epoch {}.",
+                id,
+                source,
+                withdrawal.unlock_epoch
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::LockedQueuedWithdrawal(
+                        source.clone(),
+                        *id,
+                        withdrawal.unlock_epoch,
+                    ),
+                ));
+            }
+        }
+        Some(_) => {}
+        None => {
+            edisplay_line!(
+                context.io(),
+                "The account {} has no queued withdrawal with id {}.",
+                source,
+                id
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::UnknownQueuedWithdrawal(source.clone(), *id),
+                ));
+            }
+        }
+    }
+
+    let data = QueuedWithdrawalAction {
+        source: source.clone(),
+        id: *id,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to cancel a queued withdrawal. When the source's spending
+/// policy has a recovery key, the transaction is signed on behalf of it.
+pub async fn build_cancel_withdrawal(
+    context: &impl Namada,
+    args::TxCancelWithdrawal {
+        tx: tx_args,
+        source,
+        id,
+        tx_code_path,
+    }: &args::TxCancelWithdrawal,
+) -> Result<(Tx, SigningTxData)> {
+    let withdrawals =
+        rpc::query_queued_withdrawals(context.client(), source).await?;
+    if !withdrawals.contains_key(id) {
+        edisplay_line!(
+            context.io(),
+            "The account {} has no queued withdrawal with id {}.",
+            source,
+            id
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::UnknownQueuedWithdrawal(
+                source.clone(),
+                *id,
+            )));
+        }
+    }
+
+    let policy = rpc::query_spending_policy(context.client(), source).await?;
+    let signer = policy
+        .and_then(|policy| policy.recovery_key)
+        .map_or_else(|| source.clone(), |key| Address::from(&key));
+    let default_signer = Some(signer.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(signer),
+        default_signer,
+    )
+    .await?;
+
+    let data = QueuedWithdrawalAction {
+        source: source.clone(),
+        id: *id,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to set or remove the guardians of an account
+pub async fn build_update_guardians(
     context: &impl Namada,
-    args::UpdateStewardCommission {
+    args::TxUpdateGuardians {
         tx: tx_args,
-        steward,
-        commission,
+        addr,
+        guardians,
+        threshold,
+        delay,
         tx_code_path,
-    }: &args::UpdateStewardCommission,
+    }: &args::TxUpdateGuardians,
 ) -> Result<(Tx, SigningTxData)> {
-    let default_signer = Some(steward.clone());
+    let default_signer = Some(addr.clone());
     let signing_data = signing::aux_signing_data(
         context,
         tx_args,
-        Some(steward.clone()),
+        Some(addr.clone()),
         default_signer,
     )
     .await?;
 
-    if !rpc::is_steward(context.client(), steward).await && !tx_args.force {
-        edisplay_line!(
+    let guardians = if guardians.is_empty() {
+        None
+    } else {
+        let guardians: BTreeSet<Address> = guardians.iter().cloned().collect();
+        let threshold = match threshold {
+            Some(threshold) => *threshold,
+            None if guardians.len() == 1 => 1,
+            None => {
+                return Err(Error::from(TxSubmitError::InvalidGuardianSet(
+                    "a threshold is required for more than one guardian"
+                        .to_string(),
+                )));
+            }
+        };
+        if threshold == 0 || usize::from(threshold) > guardians.len() {
+            return Err(Error::from(TxSubmitError::InvalidGuardianSet(
+                format!(
+                    "the threshold {} must be between 1 and the number of \
+                     guardians {}",
+                    threshold,
+                    guardians.len()
+                ),
+            )));
+        }
+        if guardians.contains(addr) {
+            return Err(Error::from(TxSubmitError::InvalidGuardianSet(
+                "an account cannot be its own guardian".to_string(),
+            )));
+        }
+        Some(GuardianSet {
+            guardians,
+            threshold,
+            delay: *delay,
+        })
+    };
+
+    if rpc::query_pending_recovery(context.client(), addr)
+        .await?
+        .is_some()
+    {
+        display_line!(
             context.io(),
-            "The given address {} is not a steward.",
-            &steward
+            "Note: the account {} has a pending recovery, which can still be \
+             finalized after its guardians are updated.",
+            addr
         );
-        return Err(Error::from(TxSubmitError::InvalidSteward(
-            steward.clone(),
-        )));
+    }
+
+    let data = UpdateGuardians {
+        addr: addr.clone(),
+        guardians,
     };
 
-    let commission = Commission::try_from(commission.as_ref())
-        .map_err(|e| TxSubmitError::InvalidStewardCommission(e.to_string()))?;
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
 
-    if !commission.is_valid() && !tx_args.force {
+/// Craft transaction for the guardians to initiate a recovery of an account.
+/// The transaction has to be signed with the keys of enough guardians.
+pub async fn build_initiate_recovery(
+    context: &impl Namada,
+    args::TxInitiateRecovery {
+        tx: tx_args,
+        addr,
+        public_keys,
+        threshold,
+        tx_code_path,
+    }: &args::TxInitiateRecovery,
+) -> Result<(Tx, SigningTxData)> {
+    // The guardians sign with their own keys, which are checked against
+    // their accounts by the recovered account's validity predicate
+    let signing_data = signing::init_validator_signing_data(
+        context,
+        tx_args,
+        tx_args.signing_keys.clone(),
+    )
+    .await?;
+
+    match rpc::query_guardians(context.client(), addr).await? {
+        Some(guardians)
+            if tx_args.signing_keys.len() < usize::from(guardians.threshold) =>
+        {
+            edisplay_line!(
+                context.io(),
+                "The recovery of {} must be signed by the keys of at least {} \
+                 guardians.",
+                addr,
+                guardians.threshold
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::MissingSigningKeys(
+                    guardians.threshold,
+                    tx_args.signing_keys.len() as u8,
+                )));
+            }
+        }
+        Some(_) => {}
+        None => {
+            edisplay_line!(
+                context.io(),
+                "The account {} has no guardians.",
+                addr
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::NoGuardians(
+                    addr.clone(),
+                )));
+            }
+        }
+    }
+    if rpc::query_pending_recovery(context.client(), addr)
+        .await?
+        .is_some()
+    {
         edisplay_line!(
             context.io(),
-            "The sum of all percentage must not be greater than 1."
+            "A recovery of the account {} is already pending.",
+            addr
         );
-        return Err(Error::from(TxSubmitError::InvalidStewardCommission(
-            "Commission sum is greater than 1.".to_string(),
-        )));
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::RecoveryPending(
+                addr.clone(),
+            )));
+        }
     }
 
-    let data = UpdateStewardCommission {
-        steward: steward.clone(),
-        commission: commission.reward_distribution,
+    let threshold = match threshold {
+        Some(threshold) => *threshold,
+        None => {
+            if public_keys.len() == 1 {
+                1u8
+            } else {
+                return Err(Error::from(
+                    TxSubmitError::MissingAccountThreshold,
+                ));
+            }
+        }
+    };
+
+    let data = InitiateRecovery {
+        addr: addr.clone(),
+        public_keys: public_keys.clone(),
+        threshold,
     };
 
     build(
@@ -865,40 +1774,103 @@ pub async fn build_update_steward_commission(
     .map(|tx| (tx, signing_data))
 }
 
-/// Craft transaction to resign as a steward
-pub async fn build_resign_steward(
+/// Craft transaction to cancel a pending recovery of an account
+pub async fn build_cancel_recovery(
     context: &impl Namada,
-    args::ResignSteward {
+    args::TxCancelRecovery {
         tx: tx_args,
-        steward,
+        addr,
         tx_code_path,
-    }: &args::ResignSteward,
+    }: &args::TxCancelRecovery,
 ) -> Result<(Tx, SigningTxData)> {
-    let default_signer = Some(steward.clone());
+    let default_signer = Some(addr.clone());
     let signing_data = signing::aux_signing_data(
         context,
         tx_args,
-        Some(steward.clone()),
+        Some(addr.clone()),
         default_signer,
     )
     .await?;
 
-    if !rpc::is_steward(context.client(), steward).await && !tx_args.force {
+    if rpc::query_pending_recovery(context.client(), addr)
+        .await?
+        .is_none()
+    {
         edisplay_line!(
             context.io(),
-            "The given address {} is not a steward.",
-            &steward
+            "The account {} has no pending recovery.",
+            addr
         );
-        return Err(Error::from(TxSubmitError::InvalidSteward(
-            steward.clone(),
-        )));
-    };
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::NoPendingRecovery(
+                addr.clone(),
+            )));
+        }
+    }
 
     build(
         context,
         tx_args,
         tx_code_path.clone(),
-        steward.clone(),
+        addr.clone(),
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Craft transaction to finalize a matured recovery of an account. The
+/// transaction needs no signature of the account, so anyone can submit it.
+pub async fn build_finalize_recovery(
+    context: &impl Namada,
+    args::TxFinalizeRecovery {
+        tx: tx_args,
+        addr,
+        tx_code_path,
+    }: &args::TxFinalizeRecovery,
+) -> Result<(Tx, SigningTxData)> {
+    let signing_data =
+        signing::aux_signing_data(context, tx_args, None, None).await?;
+
+    let recovery = rpc::query_pending_recovery(context.client(), addr).await?;
+    let epoch = rpc::query_epoch(context.client()).await?;
+    match recovery {
+        Some(recovery) if recovery.unlock_epoch > epoch => {
+            edisplay_line!(
+                context.io(),
+                "The recovery of the account {} is locked until epoch {}.",
+                addr,
+                recovery.unlock_epoch
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::LockedRecovery(
+                    addr.clone(),
+                    recovery.unlock_epoch,
+                )));
+            }
+        }
+        Some(_) => {}
+        None => {
+            edisplay_line!(
+                context.io(),
+                "The account {} has no pending recovery.",
+                addr
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::NoPendingRecovery(
+                    addr.clone(),
+                )));
+            }
+        }
+    }
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        addr.clone(),
         do_nothing,
         &signing_data.fee_payer,
         None,
@@ -1761,6 +2733,7 @@ pub async fn build_default_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_shielded_rewards: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -1900,6 +2873,7 @@ pub async fn build_pgf_funding_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_shielded_rewards: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -1944,6 +2918,7 @@ pub async fn build_pgf_stewards_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_shielded_rewards: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -1980,6 +2955,73 @@ pub async fn build_pgf_stewards_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a shielded rewards proposal governance
+pub async fn build_shielded_rewards_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_shielded_rewards: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: ShieldedRewardsProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+
+    // The minimum proposal fund is transferred from the author, so the fees
+    // are checked against the balance left after the transfer
+    let author = proposal.proposal.author.clone();
+    let native_token = context.native_token();
+    let governance_parameters =
+        rpc::query_governance_parameters(context.client()).await;
+    let post_balance = check_balance_too_low_err(
+        &native_token,
+        &author,
+        governance_parameters.min_proposal_fund,
+        balance_key(&native_token, &author),
+        tx.force,
+        context,
+    )
+    .await?;
+    let tx_source_balance = Some(TxSourcePostBalance {
+        post_balance,
+        source: author,
+        token: native_token,
+    });
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        &signing_data.fee_payer,
+        tx_source_balance,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit an IBC transfer
 pub async fn build_ibc_transfer(
     context: &impl Namada,
@@ -2468,6 +3510,8 @@ pub async fn build_init_account(
         tx_code_path,
         public_keys,
         threshold,
+        weights,
+        weight_threshold,
     }: &args::TxInitAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let signing_data =
@@ -2488,11 +3532,20 @@ pub async fn build_init_account(
         }
     };
 
+    let weights = account_weights(
+        context,
+        tx_args,
+        weights,
+        *weight_threshold,
+        public_keys.len(),
+    )?;
+
     let data = InitAccount {
         public_keys: public_keys.clone(),
         // We will add the hash inside the add_code_hash function
         vp_code_hash: Hash::zero(),
         threshold,
+        weights,
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut InitAccount| {
@@ -2516,6 +3569,54 @@ pub async fn build_init_account(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build the weights of an account's public keys, given in the order of the
+/// keys, and check that they are valid for the number of keys of the account.
+/// A weight threshold without weights removes the weighting of the account,
+/// otherwise every public key must be weighted.
+fn account_weights(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+    weights: &[u8],
+    weight_threshold: Option<u16>,
+    num_public_keys: usize,
+) -> Result<Option<AccountWeights>> {
+    let weights = match weight_threshold {
+        Some(_) if weights.is_empty() => AccountWeights::default(),
+        Some(threshold) => AccountWeights {
+            weights: (0..=u8::MAX).zip(weights.iter().copied()).collect(),
+            threshold,
+        },
+        None if weights.is_empty() => return Ok(None),
+        None => {
+            return Err(Error::from(TxSubmitError::InvalidAccountWeights(
+                "the weight threshold must be set together with the weights"
+                    .to_string(),
+            )));
+        }
+    };
+    let validation = if weights.is_removal() {
+        Ok(())
+    } else if weights.weights.len() == num_public_keys {
+        weights.validate(num_public_keys)
+    } else {
+        Err(format!(
+            "{} weights were given for {} public keys",
+            weights.weights.len(),
+            num_public_keys
+        ))
+    };
+    if let Err(error) = validation {
+        edisplay_line!(context.io(), "Invalid account weights: {}", error);
+        // Invalid weights are also rejected on-chain
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidAccountWeights(
+                error,
+            )));
+        }
+    }
+    Ok(Some(weights))
+}
+
 /// Submit a transaction to update a VP
 pub async fn build_update_account(
     context: &impl Namada,
@@ -2526,6 +3627,8 @@ pub async fn build_update_account(
         addr,
         public_keys,
         threshold,
+        weights,
+        weight_threshold,
     }: &args::TxUpdateAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(addr.clone());
@@ -2537,10 +3640,9 @@ pub async fn build_update_account(
     )
     .await?;
 
-    let addr = if let Some(account) =
-        rpc::get_account_info(context.client(), addr).await?
-    {
-        account.address
+    let account = rpc::get_account_info(context.client(), addr).await?;
+    let addr = if let Some(account) = &account {
+        account.address.clone()
     } else if tx_args.force {
         addr.clone()
     } else {
@@ -2549,6 +3651,21 @@ pub async fn build_update_account(
         )));
     };
 
+    let num_public_keys = if public_keys.is_empty() {
+        account
+            .as_ref()
+            .map_or(0, |account| account.public_keys_map.idx_to_pk.len())
+    } else {
+        public_keys.len()
+    };
+    let weights = account_weights(
+        context,
+        tx_args,
+        weights,
+        *weight_threshold,
+        num_public_keys,
+    )?;
+
     let vp_code_hash = match vp_code_path {
         Some(code_path) => {
             let vp_hash = query_wasm_code_hash_buf(context, code_path).await?;
@@ -2576,6 +3693,7 @@ pub async fn build_update_account(
         vp_code_hash: extra_section_hash,
         public_keys: public_keys.clone(),
         threshold: *threshold,
+        weights,
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut UpdateAccount| {
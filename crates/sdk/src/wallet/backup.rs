@@ -0,0 +1,252 @@
+//! Encrypted backups of a whole wallet.
+//!
+//! A backup is a TOML document with two fields:
+//!
+//! ```toml
+//! version = 1
+//! data = "<hex>"
+//! ```
+//!
+//! The `data` field holds the salt of the key derived from the backup password
+//! followed by the authenticated encryption of the wallet store, encoded as in
+//! the `wallet.toml` file. A backup thus covers all the aliases, derivation
+//! paths, transparent and shielded keys, payment addresses and validator data
+//! of the wallet. The secret keys that are encrypted in the wallet stay
+//! encrypted with their own password inside of a backup.
+//!
+//! The `version` field is bumped whenever the encoding of the store changes.
+//! Backups of older versions are upgraded with the [`MIGRATIONS`] on restore.
+
+use data_encoding::HEXLOWER;
+use orion::{aead, kdf};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use super::keys::{encryption_key, encryption_salt};
+use super::Store;
+
+/// The version of the backups made by this wallet
+pub const BACKUP_VERSION: u32 = 1;
+
+/// A migration of the encoded store of a backup to the next version
+type Migration = fn(toml::Value) -> Result<toml::Value, BackupError>;
+
+/// The migrations of the encoded store, where the one at index `i` upgrades a
+/// store of version `i + 1` to version `i + 2`
+const MIGRATIONS: &[Migration] = &[];
+
+/// Errors of wallet backups
+#[derive(Error, Debug)]
+pub enum BackupError {
+    /// Empty backup password
+    #[error("The backup password cannot be empty")]
+    EmptyPassword,
+    /// Unsupported backup version
+    #[error(
+        "Unsupported wallet backup version {0}, the latest supported version \
+         is {BACKUP_VERSION}"
+    )]
+    UnsupportedVersion(u32),
+    /// Backup decryption error
+    #[error("Unable to decrypt the wallet backup. Is the password correct?")]
+    Decryption,
+    /// Backup decoding error
+    #[error("Failed decoding the wallet backup: {0}")]
+    Decode(String),
+    /// Backup migration error
+    #[error("Failed migrating the wallet backup from version {0}: {1}")]
+    Migration(u32, String),
+    /// Aliases of the wallet that the backup would replace
+    #[error(
+        "The backup would replace the entries of the existing aliases {0}. \
+         Restore it with force to replace them."
+    )]
+    ConflictingAliases(String),
+}
+
+/// An encrypted backup of a wallet store
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WalletBackup {
+    /// The version of the backup format
+    pub version: u32,
+    /// The hex encoded encryption salt and encrypted store
+    pub data: String,
+}
+
+impl WalletBackup {
+    /// Encrypt a backup of the given store with the given password
+    pub fn new(
+        store: &Store,
+        password: Zeroizing<String>,
+    ) -> Result<Self, BackupError> {
+        if password.is_empty() {
+            return Err(BackupError::EmptyPassword);
+        }
+        let salt = encryption_salt();
+        let encryption_key = encryption_key(&salt, &password);
+
+        let data = Zeroizing::new(store.encode());
+        let encrypted_store = aead::seal(&encryption_key, &data)
+            .expect("Encryption of data shouldn't fail");
+        let encrypted_data = [salt.as_ref(), &encrypted_store].concat();
+
+        Ok(Self {
+            version: BACKUP_VERSION,
+            data: HEXLOWER.encode(&encrypted_data),
+        })
+    }
+
+    /// Decrypt the store of this backup, migrating it to the current version
+    pub fn restore(
+        &self,
+        password: Zeroizing<String>,
+    ) -> Result<Store, BackupError> {
+        if password.is_empty() {
+            return Err(BackupError::EmptyPassword);
+        }
+        if self.version == 0 || self.version > BACKUP_VERSION {
+            return Err(BackupError::UnsupportedVersion(self.version));
+        }
+        let encrypted_data = HEXLOWER
+            .decode(self.data.as_bytes())
+            .map_err(|err| BackupError::Decode(err.to_string()))?;
+
+        let salt_len = encryption_salt().len();
+        if encrypted_data.len() < salt_len {
+            return Err(BackupError::Decode(
+                "the encrypted data is too short".to_string(),
+            ));
+        }
+        let (raw_salt, cipher) = encrypted_data.split_at(salt_len);
+        let salt = kdf::Salt::from_slice(raw_salt)
+            .map_err(|_| BackupError::Decode("bad salt".to_string()))?;
+        let encryption_key = encryption_key(&salt, &password);
+
+        let data = Zeroizing::new(
+            aead::open(&encryption_key, cipher)
+                .map_err(|_| BackupError::Decryption)?,
+        );
+        let mut store: toml::Value = toml::from_slice(&data)
+            .map_err(|err| BackupError::Decode(err.to_string()))?;
+        for (index, migrate) in
+            MIGRATIONS.iter().enumerate().skip(self.version as usize - 1)
+        {
+            let version = index as u32 + 1;
+            store = migrate(store).map_err(|err| {
+                BackupError::Migration(version, err.to_string())
+            })?;
+        }
+        Store::deserialize(store)
+            .map_err(|err| BackupError::Decode(err.to_string()))
+    }
+
+    /// Decode a backup from the given bytes
+    pub fn decode(data: &[u8]) -> Result<Self, BackupError> {
+        toml::from_slice(data)
+            .map_err(|err| BackupError::Decode(err.to_string()))
+    }
+
+    /// Encode a backup into a string of bytes
+    pub fn encode(&self) -> Vec<u8> {
+        toml::to_vec(self).expect("Serializing of backup shouldn't fail")
+    }
+}
+
+#[cfg(test)]
+mod test_backup {
+    use std::path::PathBuf;
+
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::key::testing::{keypair_1, keypair_2};
+    use namada_core::types::key::{PublicKeyHash, RefTo};
+
+    use super::*;
+    use crate::wallet::fs::FsWalletUtils;
+    use crate::wallet::AddressVpType;
+
+    fn store() -> Store {
+        let mut store = Store::default();
+        store.add_vp_type_to_address(
+            AddressVpType::Token,
+            established_address_1(),
+        );
+        store
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let password = Zeroizing::new("password".to_string());
+        let backup = WalletBackup::new(&store(), password.clone()).unwrap();
+        let backup = WalletBackup::decode(&backup.encode()).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+
+        let restored = backup.restore(password).unwrap();
+        assert_eq!(restored.encode(), store().encode());
+    }
+
+    #[test]
+    fn test_backup_wrong_password() {
+        let password = Zeroizing::new("password".to_string());
+        let backup = WalletBackup::new(&store(), password).unwrap();
+
+        let wrong_password = Zeroizing::new("wrong".to_string());
+        assert!(matches!(
+            backup.restore(wrong_password),
+            Err(BackupError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_backup_unsupported_version() {
+        let password = Zeroizing::new("password".to_string());
+        let mut backup = WalletBackup::new(&store(), password.clone()).unwrap();
+        backup.version = BACKUP_VERSION + 1;
+        assert!(matches!(
+            backup.restore(password),
+            Err(BackupError::UnsupportedVersion(_))
+        ));
+    }
+
+    /// Test that restoring a backup with an alias of the wallet that refers to
+    /// another key fails, unless forced
+    #[test]
+    fn test_restore_conflicting_alias() {
+        let password = Zeroizing::new("password".to_string());
+        let pk = keypair_1().ref_to();
+        let backup_pk = keypair_2().ref_to();
+        let mut backup_store = Store::default();
+        backup_store.insert_public_key::<FsWalletUtils>(
+            "alias".into(),
+            backup_pk.clone(),
+            None,
+            None,
+            true,
+        );
+        let backup =
+            WalletBackup::new(&backup_store, password.clone()).unwrap();
+
+        let mut wallet = FsWalletUtils::new(PathBuf::new());
+        wallet.insert_public_key(
+            "alias".to_string(),
+            pk.clone(),
+            None,
+            None,
+            true,
+        );
+
+        // The clashing alias is kept without force
+        assert!(matches!(
+            wallet.restore(&backup, password.clone(), false),
+            Err(BackupError::ConflictingAliases(_))
+        ));
+        assert_eq!(wallet.find_public_key("alias").unwrap(), pk);
+
+        // The clashing alias is replaced with force, without leaving the old
+        // key reachable by its hash
+        wallet.restore(&backup, password, true).unwrap();
+        assert_eq!(wallet.find_public_key("alias").unwrap(), backup_pk);
+        let pkh = PublicKeyHash::from(&pk);
+        assert!(wallet.find_public_key(pkh.to_string()).is_err());
+    }
+}
@@ -42,7 +42,9 @@ impl DerivationPath {
         if let Some(coin_type) = self.0.as_ref().get(1) {
             let coin_type = coin_type.to_u32();
             match scheme {
-                SchemeType::Ed25519 => coin_type == NAMADA_COIN_TYPE,
+                SchemeType::Ed25519 | SchemeType::Bls => {
+                    coin_type == NAMADA_COIN_TYPE
+                }
                 SchemeType::Secp256k1 => coin_type == ETH_COIN_TYPE,
                 _ => true,
             }
@@ -93,7 +95,7 @@ impl DerivationPath {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#child-key-derivation-ckd-functions
     pub fn is_slip10_conform(&self, scheme: SchemeType) -> bool {
         match scheme {
-            SchemeType::Ed25519 => {
+            SchemeType::Ed25519 | SchemeType::Bls => {
                 // all indices must be hardened
                 self.0.as_ref().iter().all(|idx| idx.is_hardened())
             }
@@ -128,7 +130,7 @@ impl DerivationPath {
 
     pub fn is_namada_transparent_compliant(&self, scheme: SchemeType) -> bool {
         match scheme {
-            SchemeType::Ed25519 => {
+            SchemeType::Ed25519 | SchemeType::Bls => {
                 self.is_bip44_conform(false)
                     && self.is_slip10_conform(scheme)
                     && self.has_transparent_compatible_coin_type(scheme)
@@ -150,7 +152,9 @@ impl DerivationPath {
             ChildIndex::Hardened(BIP44_PURPOSE),
             match scheme {
                 SchemeType::Secp256k1 => ChildIndex::Hardened(ETH_COIN_TYPE),
-                SchemeType::Ed25519 => ChildIndex::Hardened(NAMADA_COIN_TYPE),
+                SchemeType::Ed25519 | SchemeType::Bls => {
+                    ChildIndex::Hardened(NAMADA_COIN_TYPE)
+                }
                 SchemeType::Common => unimplemented!("not implemented"),
             },
         ]
@@ -188,7 +192,9 @@ impl DerivationPath {
             self.0
                 .into_iter()
                 .map(|idx| match scheme {
-                    SchemeType::Ed25519 => ChildIndex::Hardened(idx.to_u32()),
+                    SchemeType::Ed25519 | SchemeType::Bls => {
+                        ChildIndex::Hardened(idx.to_u32())
+                    }
                     _ => *idx,
                 })
                 .collect::<Vec<_>>(),
@@ -221,6 +227,25 @@ impl DerivationPath {
     pub fn path(&self) -> &[ChildIndex] {
         self.0.path()
     }
+
+    /// Replace the address index, i.e. the last level of the path, keeping
+    /// its hardening. Returns `None` for the empty path.
+    pub fn with_address_index(&self, index: u32) -> Option<Self> {
+        let mut indexes = self.path().to_vec();
+        let last = indexes.last_mut()?;
+        *last = match last {
+            ChildIndex::Normal(_) => ChildIndex::Normal(index),
+            ChildIndex::Hardened(_) => ChildIndex::Hardened(index),
+        };
+        Some(Self::new(indexes))
+    }
+
+    /// The parent path, i.e. the path without its last level. Returns `None`
+    /// for the empty path.
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.path().split_last()?;
+        Some(Self::new(parent.to_vec()))
+    }
 }
 
 impl fmt::Display for DerivationPath {
@@ -386,4 +411,27 @@ mod tests {
         );
         assert!(path_z_2.is_namada_shielded_compliant());
     }
+
+    #[test]
+    fn path_address_index() {
+        let path_nam = DerivationPath::from_path_string("m/44'/877'/0'/0'/0'")
+            .expect("Path construction cannot fail.");
+        assert_eq!(
+            path_nam.with_address_index(7).unwrap().to_string(),
+            "m/44'/877'/0'/0'/7'"
+        );
+        assert_eq!(path_nam.parent().unwrap().to_string(), "m/44'/877'/0'/0'");
+
+        let path_eth = DerivationPath::from_path_string("m/44'/60'/0'/0/0")
+            .expect("Path construction cannot fail.");
+        assert_eq!(
+            path_eth.with_address_index(7).unwrap().to_string(),
+            "m/44'/60'/0'/0/7"
+        );
+
+        let path_empty = DerivationPath::from_path_string("m")
+            .expect("Path construction cannot fail.");
+        assert!(path_empty.with_address_index(7).is_none());
+        assert!(path_empty.parent().is_none());
+    }
 }
@@ -0,0 +1,130 @@
+//! Discovery of the used accounts of an HD wallet.
+//!
+//! Successive address indices are derived and the chain is queried for any
+//! trace of activity of the corresponding implicit addresses: a revealed
+//! public key, a balance of any token or bonds. An implicit account must
+//! reveal its public key before signing a transaction, so the revealed key
+//! also covers the addresses that have since spent all their tokens. The scan
+//! stops once a given number of consecutive unused addresses has been found,
+//! as per the BIP-0044 address gap limit.
+
+use std::collections::HashSet;
+
+use namada_core::types::address::Address;
+use namada_core::types::key::{common, RefTo, SchemeType};
+
+use super::store::derive_hd_secret_key;
+use super::{DerivationPath, ExtendedPublicKey, XpubError};
+use crate::error::Error;
+use crate::queries::Client;
+use crate::rpc;
+
+/// The default number of consecutive unused addresses after which the
+/// discovery stops
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// The keys from which the accounts are derived
+pub enum KeySource<'a> {
+    /// Derive secret keys from an HD seed. The address index, i.e. the last
+    /// level of the derivation path, is replaced for every derived key.
+    Seed {
+        /// Scheme of the derived keys
+        scheme: SchemeType,
+        /// The HD seed
+        seed: &'a [u8],
+        /// Derivation path of the first address
+        derivation_path: DerivationPath,
+    },
+    /// Derive the non-hardened children of a secp256k1 extended public key,
+    /// in watch-only mode.
+    Xpub(&'a ExtendedPublicKey),
+}
+
+/// A key derived at a used address index
+#[derive(Clone, Debug)]
+pub struct DiscoveredKey {
+    /// The address index
+    pub index: u32,
+    /// The implicit address of the key
+    pub address: Address,
+    /// The public key
+    pub public_key: common::PublicKey,
+    /// The secret key, unless derived in watch-only mode
+    pub secret_key: Option<common::SecretKey>,
+    /// The full derivation path, if known
+    pub derivation_path: Option<DerivationPath>,
+}
+
+impl<'a> KeySource<'a> {
+    /// Derive the key at the given address index. Returns `None` if the index
+    /// doesn't yield a valid key, in which case the next index must be used.
+    pub fn derive(&self, index: u32) -> Result<Option<DiscoveredKey>, Error> {
+        let (public_key, secret_key, derivation_path) = match self {
+            Self::Seed {
+                scheme,
+                seed,
+                derivation_path,
+            } => {
+                let path =
+                    derivation_path.with_address_index(index).ok_or_else(
+                        || Error::Other("Empty derivation path".to_string()),
+                    )?;
+                let sk = derive_hd_secret_key(*scheme, seed, path.clone());
+                (sk.ref_to(), Some(sk), Some(path))
+            }
+            Self::Xpub(xpub) => match xpub.child(index) {
+                Ok(child) => (child.public_key(), None, None),
+                Err(XpubError::InvalidChild(_)) => return Ok(None),
+                Err(err) => return Err(Error::Other(err.to_string())),
+            },
+        };
+        Ok(Some(DiscoveredKey {
+            index,
+            address: Address::from(&public_key),
+            public_key,
+            secret_key,
+            derivation_path,
+        }))
+    }
+}
+
+/// Check whether the given implicit address has been used on chain, given the
+/// owners of a token balance returned by [`rpc::query_token_holders`].
+pub async fn is_address_used<C: Client + Sync>(
+    client: &C,
+    token_holders: &HashSet<Address>,
+    owner: &Address,
+) -> Result<bool, Error> {
+    Ok(token_holders.contains(owner)
+        || rpc::is_public_key_revealed(client, owner).await?
+        || rpc::has_bonds(client, owner).await?)
+}
+
+/// Derive successive address indices from the key source, starting from 0,
+/// and return the keys of the used addresses. The indices that don't yield a
+/// valid key are skipped. The discovery stops after `gap_limit` consecutive
+/// unused addresses.
+pub async fn discover_keys<C: Client + Sync>(
+    client: &C,
+    source: &KeySource<'_>,
+    gap_limit: u32,
+) -> Result<Vec<DiscoveredKey>, Error> {
+    let token_holders = rpc::query_token_holders(client).await?;
+    let mut discovered = vec![];
+    let mut gap = 0;
+    let mut index = 0;
+    while gap < gap_limit {
+        if let Some(key) = source.derive(index)? {
+            if is_address_used(client, &token_holders, &key.address).await? {
+                discovered.push(key);
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+        }
+        index = index.checked_add(1).ok_or_else(|| {
+            Error::Other("Exhausted the address indices".to_string())
+        })?;
+    }
+    Ok(discovered)
+}
@@ -240,12 +240,15 @@ impl<T: BorshSerialize + BorshDeserialize> EncryptedKeypair<T> {
 }
 
 /// Keypair encryption salt
-fn encryption_salt() -> kdf::Salt {
+pub(super) fn encryption_salt() -> kdf::Salt {
     kdf::Salt::default()
 }
 
 /// Make encryption secret key from a password.
-fn encryption_key(salt: &kdf::Salt, password: &str) -> kdf::SecretKey {
+pub(super) fn encryption_key(
+    salt: &kdf::Salt,
+    password: &str,
+) -> kdf::SecretKey {
     kdf::Password::from_slice(password.as_bytes())
         .and_then(|password| kdf::derive_key(&password, salt, 3, 1 << 17, 32))
         .expect("Generation of encryption secret key shouldn't fail")
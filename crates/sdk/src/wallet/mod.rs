@@ -1,9 +1,12 @@
 //! Provides functionality for managing keys and addresses for a user
 pub mod alias;
+pub mod backup;
 mod derivation_path;
+pub mod discovery;
 mod keys;
 pub mod pre_genesis;
 pub mod store;
+mod xpub;
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
@@ -24,9 +27,12 @@ pub use store::{AddressVpType, Store};
 use thiserror::Error;
 use zeroize::Zeroizing;
 
+pub use self::backup::{BackupError, WalletBackup};
 pub use self::derivation_path::{DerivationPath, DerivationPathError};
+pub use self::discovery::{DiscoveredKey, KeySource};
 pub use self::keys::{DecryptionError, StoredKeypair};
 pub use self::store::{ConfirmationResponse, ValidatorData, ValidatorKeys};
+pub use self::xpub::{ExtendedPublicKey, XpubError};
 use crate::wallet::store::{derive_hd_secret_key, derive_hd_spending_key};
 
 /// Captures the interactive parts of the wallet's functioning
@@ -217,6 +223,7 @@ pub fn gen_secret_key(
         SchemeType::Secp256k1 => {
             secp256k1::SigScheme::generate(csprng).try_to_sk()
         }
+        SchemeType::Bls => bls::SigScheme::generate(csprng).try_to_sk(),
         SchemeType::Common => common::SigScheme::generate(csprng).try_to_sk(),
     }
     .unwrap()
@@ -662,6 +669,50 @@ impl<U: WalletIo> Wallet<U> {
         (mnemonic, seed)
     }
 
+    /// Read a BIP39 mnemonic code from stdin and derive the HD wallet seed from
+    /// it, optionally prompting for a passphrase.
+    pub fn read_hd_seed(prompt_bip39_passphrase: bool) -> Option<Seed> {
+        let mnemonic = U::read_mnemonic_code()?;
+        let passphrase = if prompt_bip39_passphrase {
+            U::read_mnemonic_passphrase(false)
+        } else {
+            Zeroizing::default()
+        };
+        Some(Seed::new(&mnemonic, &passphrase))
+    }
+
+    /// Insert a key found by the account discovery into the store with the
+    /// provided alias, converted to lower case. Keys derived in watch-only
+    /// mode are stored as public keys along with their implicit address.
+    /// If no encryption password is provided, the secret key will be stored
+    /// raw without encryption.
+    /// Returns the alias of the key.
+    pub fn insert_discovered_key(
+        &mut self,
+        alias: String,
+        alias_force: bool,
+        key: DiscoveredKey,
+        password: Option<Zeroizing<String>>,
+    ) -> Option<String> {
+        match key.secret_key {
+            Some(sk) => self.insert_keypair(
+                alias,
+                alias_force,
+                sk,
+                password,
+                None,
+                key.derivation_path,
+            ),
+            None => self.insert_public_key(
+                alias,
+                key.public_key,
+                Some(key.address),
+                key.derivation_path,
+                alias_force,
+            ),
+        }
+    }
+
     /// Derive a keypair from the given seed and path, derive an implicit
     /// address from this keypair, and insert them into the store with the
     /// provided alias, converted to lower case. If none provided, the alias
@@ -757,6 +808,22 @@ impl<U: WalletIo> Wallet<U> {
         disposable_keypair
     }
 
+    /// Generate an ephemeral signing key for a wrapper paying the fee from
+    /// the shielded pool. The key holds no funds, so it is only cached in
+    /// memory for the signing and never added to the store
+    pub fn gen_ephemeral_signing_key(
+        &mut self,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> common::SecretKey {
+        let keypair = gen_secret_key(SchemeType::Ed25519, rng);
+        let pkh: PublicKeyHash = (&keypair.ref_to()).into();
+        // Keys missing from the store are looked up in the cache by their
+        // public key hash
+        self.decrypted_key_cache
+            .insert(pkh.to_string().into(), keypair.clone());
+        keypair
+    }
+
     /// Find the stored key by an alias, a public key hash or a public key.
     /// If the key is encrypted and password not supplied, then password will be
     /// interactively prompted. Any keys that are decrypted are stored in and
@@ -1058,4 +1125,47 @@ impl<U: WalletIo> Wallet<U> {
     pub fn remove_all_by_alias(&mut self, alias: String) {
         self.store.remove_alias(&alias.into())
     }
+
+    /// Make a backup of this wallet encrypted with the given password
+    pub fn backup(
+        &self,
+        password: Zeroizing<String>,
+    ) -> Result<WalletBackup, BackupError> {
+        WalletBackup::new(&self.store, password)
+    }
+
+    /// Extend this wallet with the store of a backup. The validator data of
+    /// the backup is only restored if this wallet has none. If some aliases
+    /// of this wallet would be replaced by the backup, the restore fails
+    /// unless `force` is set, in which case these aliases are removed first.
+    pub fn restore(
+        &mut self,
+        backup: &WalletBackup,
+        password: Zeroizing<String>,
+        force: bool,
+    ) -> Result<(), BackupError> {
+        let mut store = backup.restore(password)?;
+        let conflicts = self.store.conflicting_aliases(&store);
+        if !conflicts.is_empty() && !force {
+            return Err(BackupError::ConflictingAliases(
+                conflicts
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        for alias in &conflicts {
+            self.store.remove_alias(alias);
+        }
+        let validator_data = store.take_validator_data();
+        self.store.extend(store);
+        if self.store.validator_data.is_none() {
+            self.store.validator_data = validator_data;
+        }
+        // Restored aliases may now refer to different keys
+        self.decrypted_key_cache.clear();
+        self.decrypted_spendkey_cache.clear();
+        Ok(())
+    }
 }
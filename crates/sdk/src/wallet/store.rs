@@ -1,10 +1,11 @@
 //! Wallet Store information
 
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Display;
 use std::str::FromStr;
 
 use bimap::BiBTreeMap;
+use borsh::{BorshDeserialize, BorshSerialize};
 use itertools::Itertools;
 use masp_primitives::zip32;
 use namada_core::types::address::{Address, ImplicitAddress};
@@ -593,6 +594,84 @@ impl Store {
         self.derivation_paths.remove(alias);
     }
 
+    /// Find the aliases of this store whose entries would be replaced by
+    /// extending it with the given store. These are the aliases of both
+    /// stores that refer to different keys, addresses or derivation paths, and
+    /// the aliases of this store whose address or payment address is under
+    /// another alias in the given store.
+    pub fn conflicting_aliases(&self, store: &Store) -> BTreeSet<Alias> {
+        fn differ<'a, V: PartialEq>(
+            ours: &'a BTreeMap<Alias, V>,
+            theirs: &'a BTreeMap<Alias, V>,
+        ) -> impl Iterator<Item = &'a Alias> {
+            ours.iter()
+                .filter(|(alias, value)| {
+                    theirs.get(*alias).map_or(false, |theirs| theirs != *value)
+                })
+                .map(|(alias, _value)| alias)
+        }
+        fn bi_differ<'a, V: Ord>(
+            ours: &'a BiBTreeMap<Alias, V>,
+            theirs: &'a BiBTreeMap<Alias, V>,
+        ) -> impl Iterator<Item = &'a Alias> {
+            ours.iter()
+                .filter(|(alias, value)| {
+                    theirs.get_by_left(*alias).map_or(false, |v| v != *value)
+                        || theirs
+                            .get_by_right(*value)
+                            .map_or(false, |a| a != *alias)
+                })
+                .map(|(alias, _value)| alias)
+        }
+        fn stored_keypair_string<T>(keypair: &StoredKeypair<T>) -> String
+        where
+            T: BorshSerialize + BorshDeserialize + Display + FromStr,
+            <T as FromStr>::Err: Display,
+        {
+            match keypair {
+                StoredKeypair::Encrypted(encrypted) => encrypted.to_string(),
+                StoredKeypair::Raw(raw) => raw.to_string(),
+            }
+        }
+        let secret_keys = |store: &Store| -> BTreeMap<Alias, String> {
+            store
+                .secret_keys
+                .iter()
+                .map(|(alias, sk)| (alias.clone(), stored_keypair_string(sk)))
+                .collect()
+        };
+        let spend_keys = |store: &Store| -> BTreeMap<Alias, String> {
+            store
+                .spend_keys
+                .iter()
+                .map(|(alias, sk)| (alias.clone(), stored_keypair_string(sk)))
+                .collect()
+        };
+        let derivation_paths = |store: &Store| -> BTreeMap<Alias, String> {
+            store
+                .derivation_paths
+                .iter()
+                .map(|(alias, path)| (alias.clone(), path.to_string()))
+                .collect()
+        };
+
+        let (our_secret_keys, their_secret_keys) =
+            (secret_keys(self), secret_keys(store));
+        let (our_spend_keys, their_spend_keys) =
+            (spend_keys(self), spend_keys(store));
+        let (our_paths, their_paths) =
+            (derivation_paths(self), derivation_paths(store));
+        differ(&self.view_keys, &store.view_keys)
+            .chain(differ(&our_spend_keys, &their_spend_keys))
+            .chain(bi_differ(&self.payment_addrs, &store.payment_addrs))
+            .chain(differ(&our_secret_keys, &their_secret_keys))
+            .chain(differ(&self.public_keys, &store.public_keys))
+            .chain(differ(&our_paths, &their_paths))
+            .chain(bi_differ(&self.addresses, &store.addresses))
+            .cloned()
+            .collect()
+    }
+
     /// Extend this store from another store (typically pre-genesis).
     /// Note that this method ignores `validator_data` if any.
     pub fn extend(&mut self, store: Store) {
@@ -719,6 +798,17 @@ pub fn derive_hd_secret_key(
             let sk = slip10_ed25519::derive_ed25519_private_key(seed, &indexes);
             ed25519::SigScheme::from_bytes(sk).try_to_sk().unwrap()
         }
+        SchemeType::Bls => {
+            let indexes = derivation_path
+                .path()
+                .iter()
+                .map(|idx| idx.to_bits())
+                .collect_vec();
+            // The key material is derived along hardened indexes as for
+            // Ed25519, then mapped to a BLS12-381 scalar.
+            let sk = slip10_ed25519::derive_ed25519_private_key(seed, &indexes);
+            bls::SigScheme::from_bytes(sk).try_to_sk().unwrap()
+        }
         SchemeType::Secp256k1 => {
             let xpriv = tiny_hderive::bip32::ExtendedPrivKey::derive(
                 seed,
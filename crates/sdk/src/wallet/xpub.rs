@@ -0,0 +1,264 @@
+//! BIP-0032 extended public keys for the secp256k1 scheme.
+//!
+//! An extended public key allows to derive the public keys (and hence the
+//! implicit addresses) of all the non-hardened children of a derivation path
+//! without having access to any secret. This is used to track the accounts of
+//! an HD wallet in watch-only mode.
+//! https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+
+use std::fmt;
+use std::str::FromStr;
+
+use base58::{FromBase58, ToBase58};
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::group::Curve;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+// The HMAC needs the digest traits of the SHA-2 version used by k256
+use k256::sha2::Sha512;
+use k256::{NonZeroScalar, ProjectivePoint};
+use namada_core::types::key::{common, secp256k1};
+use ripemd::Digest as RipemdDigest;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::DerivationPath;
+
+/// Version bytes of a mainnet extended public key ("xpub")
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+/// The length of a serialized extended key, without the checksum
+const XPUB_LEN: usize = 78;
+/// The length of the base58check checksum
+const CHECKSUM_LEN: usize = 4;
+/// The first hardened child index
+const HARDENED_OFFSET: u32 = 1 << 31;
+/// The HMAC key used to derive the master key from a seed
+const MASTER_KEY_SALT: &[u8] = b"Bitcoin seed";
+
+#[derive(Error, Debug)]
+pub enum XpubError {
+    #[error("Invalid base58 encoding of the extended public key")]
+    Base58,
+    #[error("Invalid extended public key length: {0}")]
+    Length(usize),
+    #[error("Invalid extended public key checksum")]
+    Checksum,
+    #[error("Unsupported extended public key version: {0:02x?}")]
+    Version([u8; 4]),
+    #[error("Invalid extended public key point")]
+    Point,
+    #[error("Cannot derive the hardened child {0} from a public key")]
+    HardenedChild(u32),
+    #[error("The child {0} is not a valid key, use the next index")]
+    InvalidChild(u32),
+}
+
+/// A secp256k1 public key together with the chain code needed to derive its
+/// non-hardened children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    public_key: k256::PublicKey,
+}
+
+impl ExtendedPublicKey {
+    /// Derive the extended public key at the given path from an HD seed.
+    pub fn from_seed(
+        seed: &[u8],
+        derivation_path: &DerivationPath,
+    ) -> Result<Self, XpubError> {
+        let i = hmac_sha512(MASTER_KEY_SALT, &[seed]);
+        let mut secret_key = k256::SecretKey::from_slice(&i[..32])
+            .map_err(|_| XpubError::InvalidChild(0))?;
+        let mut chain_code = chain_code(&i);
+        let mut parent_fingerprint = [0; 4];
+        let mut child_number = 0;
+        let path = derivation_path.path();
+        for index in path.iter().map(|idx| idx.to_bits()) {
+            let public_key = secret_key.public_key();
+            let i = if index >= HARDENED_OFFSET {
+                let secret = secret_key.to_bytes();
+                hmac_sha512(
+                    &chain_code,
+                    &[&[0], secret.as_slice(), &index.to_be_bytes()],
+                )
+            } else {
+                hmac_sha512(
+                    &chain_code,
+                    &[&serialize_point(&public_key), &index.to_be_bytes()],
+                )
+            };
+            let tweak = parse_scalar(&i, index)?;
+            let child = Option::<NonZeroScalar>::from(NonZeroScalar::new(
+                *tweak + *secret_key.to_nonzero_scalar(),
+            ))
+            .ok_or(XpubError::InvalidChild(index))?;
+            secret_key = k256::SecretKey::from(child);
+            chain_code = self::chain_code(&i);
+            parent_fingerprint = fingerprint(&public_key);
+            child_number = index;
+        }
+        Ok(Self {
+            depth: path.len() as u8,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            public_key: secret_key.public_key(),
+        })
+    }
+
+    /// Derive the non-hardened child at the given index.
+    pub fn child(&self, index: u32) -> Result<Self, XpubError> {
+        if index >= HARDENED_OFFSET {
+            return Err(XpubError::HardenedChild(index));
+        }
+        let i = hmac_sha512(
+            &self.chain_code,
+            &[&serialize_point(&self.public_key), &index.to_be_bytes()],
+        );
+        let tweak = parse_scalar(&i, index)?;
+        let point = ProjectivePoint::GENERATOR * *tweak
+            + self.public_key.to_projective();
+        let public_key = k256::PublicKey::from_affine(point.to_affine())
+            .map_err(|_| XpubError::InvalidChild(index))?;
+        Ok(Self {
+            depth: self.depth.saturating_add(1),
+            parent_fingerprint: fingerprint(&self.public_key),
+            child_number: index,
+            chain_code: chain_code(&i),
+            public_key,
+        })
+    }
+
+    /// The public key of this extended key.
+    pub fn public_key(&self) -> common::PublicKey {
+        common::PublicKey::Secp256k1(secp256k1::PublicKey(self.public_key))
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = Vec::with_capacity(XPUB_LEN + CHECKSUM_LEN);
+        bytes.extend_from_slice(&XPUB_VERSION);
+        bytes.push(self.depth);
+        bytes.extend_from_slice(&self.parent_fingerprint);
+        bytes.extend_from_slice(&self.child_number.to_be_bytes());
+        bytes.extend_from_slice(&self.chain_code);
+        bytes.extend_from_slice(&serialize_point(&self.public_key));
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        write!(f, "{}", bytes.to_base58())
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = XpubError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.from_base58().map_err(|_| XpubError::Base58)?;
+        if bytes.len() != XPUB_LEN + CHECKSUM_LEN {
+            return Err(XpubError::Length(bytes.len()));
+        }
+        let (payload, checksum) = bytes.split_at(XPUB_LEN);
+        if self::checksum(payload).as_slice() != checksum {
+            return Err(XpubError::Checksum);
+        }
+        let version: [u8; 4] = payload[0..4].try_into().unwrap();
+        if version != XPUB_VERSION {
+            return Err(XpubError::Version(version));
+        }
+        let public_key = k256::PublicKey::from_sec1_bytes(&payload[45..78])
+            .map_err(|_| XpubError::Point)?;
+        Ok(Self {
+            depth: payload[4],
+            parent_fingerprint: payload[5..9].try_into().unwrap(),
+            child_number: u32::from_be_bytes(
+                payload[9..13].try_into().unwrap(),
+            ),
+            chain_code: payload[13..45].try_into().unwrap(),
+            public_key,
+        })
+    }
+}
+
+/// Compressed SEC1 encoding of a point
+fn serialize_point(public_key: &k256::PublicKey) -> Vec<u8> {
+    public_key.to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// The first 4 bytes of the HASH160 of a public key
+fn fingerprint(public_key: &k256::PublicKey) -> [u8; 4] {
+    let hash = ripemd::Ripemd160::digest(Sha256::digest(serialize_point(
+        public_key,
+    )));
+    hash[..4].try_into().unwrap()
+}
+
+/// The first 4 bytes of the double SHA256 of the payload
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Sha256::digest(Sha256::digest(payload));
+    hash[..CHECKSUM_LEN].try_into().unwrap()
+}
+
+/// Parse the left half of an HMAC output as a non-zero scalar
+fn parse_scalar(i: &[u8; 64], index: u32) -> Result<NonZeroScalar, XpubError> {
+    k256::SecretKey::from_slice(&i[..32])
+        .map(|sk| sk.to_nonzero_scalar())
+        .map_err(|_| XpubError::InvalidChild(index))
+}
+
+/// The right half of an HMAC output
+fn chain_code(i: &[u8; 64]) -> [u8; 32] {
+    i[32..].try_into().unwrap()
+}
+
+/// HMAC-SHA512 of the concatenation of the given data
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut hmac = Hmac::<Sha512>::new_from_slice(key)
+        .expect("HMAC can take a key of any size");
+    for data in data {
+        hmac.update(data);
+    }
+    let mut mac = [0u8; 64];
+    mac.copy_from_slice(&hmac.finalize().into_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod test_xpub {
+    use data_encoding::HEXLOWER;
+
+    use super::*;
+
+    #[test]
+    fn test_xpub_derivation() {
+        // Test vector 1 from BIP-0032
+        let seed = HEXLOWER
+            .decode(b"000102030405060708090a0b0c0d0e0f")
+            .expect("Seed parsing cannot fail.");
+        let path = DerivationPath::from_path_string("m/0'/1/2'/2")
+            .expect("Path construction cannot fail.");
+        let xpub = ExtendedPublicKey::from_seed(&seed, &path)
+            .expect("Derivation cannot fail.");
+        assert_eq!(
+            xpub.to_string(),
+            "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiL\
+             jTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"
+        );
+        let child = xpub.child(1000000000).expect("Derivation cannot fail.");
+        assert_eq!(
+            child.to_string(),
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYF\
+             gJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+        );
+        let decoded: ExtendedPublicKey =
+            child.to_string().parse().expect("Decoding cannot fail.");
+        assert_eq!(decoded, child);
+        assert!(matches!(
+            xpub.child(HARDENED_OFFSET),
+            Err(XpubError::HardenedChild(_))
+        ));
+    }
+}
@@ -4,7 +4,8 @@ use namada_core::types::token::Amount;
 use namada_core::types::uint::Uint;
 use namada_storage as storage;
 use namada_storage::{StorageRead, StorageWrite};
-use storage::ResultExt;
+use namada_trans_token::read_denom;
+use storage::{OptionExt, ResultExt};
 
 use crate::storage_key::*;
 
@@ -36,3 +37,45 @@ where
     storage.write(&masp_locked_amount_target_key(address), raw_target)?;
     Ok(())
 }
+
+/// Update the shielded rewards parameters of the given token, e.g. from a
+/// governance proposal. If the token was not earning shielded rewards before,
+/// its last inflation and last locked amount are initialized as well. The new
+/// parameters take effect with the next update of the MASP conversions.
+pub fn update_params<S>(
+    params: &token::MaspParams,
+    storage: &mut S,
+    address: &Address,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !params.is_valid() {
+        return Err(storage::Error::new_const(
+            "Invalid shielded rewards parameters",
+        ));
+    }
+    let denom = read_denom(storage, address)?.ok_or_err_msg(
+        "No denomination found in storage for the given token",
+    )?;
+    let raw_target = Uint::from(10)
+        .checked_pow(Uint::from(denom.0))
+        .and_then(|scale| {
+            Uint::from(params.locked_amount_target).checked_mul(scale)
+        })
+        .ok_or_err_msg("Overflow in the locked amount target")?;
+    let raw_target = Amount::from_uint(raw_target, 0).into_storage_result()?;
+
+    if !storage.has_key(&masp_last_inflation_key(address))? {
+        storage.write(&masp_last_inflation_key(address), Amount::zero())?;
+    }
+    if !storage.has_key(&masp_last_locked_amount_key(address))? {
+        storage
+            .write(&masp_last_locked_amount_key(address), Amount::zero())?;
+    }
+    storage.write(&masp_max_reward_rate_key(address), params.max_reward_rate)?;
+    storage.write(&masp_kp_gain_key(address), params.kp_gain_nom)?;
+    storage.write(&masp_kd_gain_key(address), params.kd_gain_nom)?;
+    storage.write(&masp_locked_amount_target_key(address), raw_target)?;
+    Ok(())
+}
@@ -30,6 +30,16 @@ pub const MASP_KD_GAIN_KEY: &str = "derivative_gain";
 pub const MASP_LOCKED_AMOUNT_TARGET_KEY: &str = "locked_ratio_target";
 /// The key for the max reward rate for a given asset
 pub const MASP_MAX_REWARD_RATE_KEY: &str = "max_reward_rate";
+/// Key segment for the number of nullifiers revealed so far
+pub const MASP_NULLIFIER_COUNT_KEY: &str = "nullifier_count";
+/// Key segment for the number of note commitments appended to the tree so far
+pub const MASP_NOTE_COMMITMENT_COUNT_KEY: &str = "note_commitment_count";
+/// Key segment for the height from which the note commitment tree and the
+/// nullifiers are merklized
+pub const MASP_MERKLIZATION_HEIGHT_KEY: &str = "merklization_height";
+/// Key segment for the next existing key to merklize, while the merklization
+/// of the masp keys is in progress
+pub const MASP_MERKLIZATION_CURSOR_KEY: &str = "merklization_cursor";
 
 /// Obtain the nominal proportional key for the given token
 pub fn masp_kp_gain_key(token_addr: &Address) -> storage::Key {
@@ -77,7 +87,9 @@ pub fn is_masp_allowed_key(key: &storage::Key) -> bool {
         [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)]
             if *addr == address::MASP
                 && (key.starts_with(PIN_KEY_PREFIX)
-                    || key == MASP_NOTE_COMMITMENT_TREE_KEY) =>
+                    || key == MASP_NOTE_COMMITMENT_TREE_KEY
+                    || key == MASP_NULLIFIER_COUNT_KEY
+                    || key == MASP_NOTE_COMMITMENT_COUNT_KEY) =>
         {
             true
         }
@@ -108,6 +120,33 @@ pub fn is_masp_nullifier_key(key: &storage::Key) -> bool {
         ] if *addr == address::MASP && prefix == MASP_NULLIFIERS_KEY)
 }
 
+/// Check if the given storage key is a masp commitment tree key
+pub fn is_masp_commitment_tree_key(key: &storage::Key) -> bool {
+    matches!(&key.segments[..],
+        [DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(prefix),
+        ] if *addr == address::MASP && prefix == MASP_NOTE_COMMITMENT_TREE_KEY)
+}
+
+/// Check if the given storage key is the masp merklization height key
+pub fn is_masp_merklization_height_key(key: &storage::Key) -> bool {
+    matches!(&key.segments[..],
+        [DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(prefix),
+        ] if *addr == address::MASP && prefix == MASP_MERKLIZATION_HEIGHT_KEY)
+}
+
+/// Check if the given masp storage key must be merklized once the masp
+/// merklization is activated. The note commitment tree and the nullifiers are
+/// merklized so that the notes of a viewing key and their spent status can be
+/// proven against a block's storage root, and the height from which they are
+/// merklized, so that the proofs of the older heights are rejected.
+pub fn is_masp_merklized_key(key: &storage::Key) -> bool {
+    is_masp_commitment_tree_key(key)
+        || is_masp_nullifier_key(key)
+        || is_masp_merklization_height_key(key)
+}
+
 /// Get a key for a masp pin
 pub fn masp_pin_tx_key(key: &str) -> storage::Key {
     storage::Key::from(address::MASP.to_db_key())
@@ -117,9 +156,7 @@ pub fn masp_pin_tx_key(key: &str) -> storage::Key {
 
 /// Get a key for a masp nullifier
 pub fn masp_nullifier_key(nullifier: &Nullifier) -> storage::Key {
-    storage::Key::from(address::MASP.to_db_key())
-        .push(&MASP_NULLIFIERS_KEY.to_owned())
-        .expect("Cannot obtain a storage key")
+    masp_nullifiers_prefix()
         .push(&Hash(nullifier.0))
         .expect("Cannot obtain a storage key")
 }
@@ -146,3 +183,40 @@ pub fn masp_convert_anchor_key() -> storage::Key {
         .push(&MASP_CONVERT_ANCHOR_KEY.to_owned())
         .expect("Cannot obtain a storage key")
 }
+
+/// Get the prefix of the masp nullifiers keys
+pub fn masp_nullifiers_prefix() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_NULLIFIERS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the number of revealed masp nullifiers
+pub fn masp_nullifier_count_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_NULLIFIER_COUNT_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the number of note commitments in the masp commitment tree
+pub fn masp_note_commitment_count_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_NOTE_COMMITMENT_COUNT_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the height from which the masp note commitment tree and
+/// nullifiers are merklized
+pub fn masp_merklization_height_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_MERKLIZATION_HEIGHT_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key for the next existing masp key to merklize, while the
+/// merklization of the masp keys is in progress
+pub fn masp_merklization_cursor_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_MERKLIZATION_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
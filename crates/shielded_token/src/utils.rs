@@ -3,30 +3,88 @@
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use masp_primitives::transaction::Transaction;
-use namada_core::types::storage::IndexedTx;
-use namada_storage::{Error, Result, StorageRead, StorageWrite};
+use namada_core::borsh::BorshSerializeExt;
+use namada_core::types::storage::{IndexedTx, Key};
+use namada_storage::{
+    iter_prefix_bytes, Error, Result, StorageRead, StorageWrite,
+};
 
 use crate::storage_key::{
-    masp_commitment_tree_key, masp_nullifier_key, masp_pin_tx_key,
+    masp_commitment_tree_key, masp_note_commitment_count_key,
+    masp_nullifier_count_key, masp_nullifier_key, masp_nullifiers_prefix,
+    masp_pin_tx_key,
 };
 
+/// Read the number of revealed nullifiers
+pub fn nullifier_count(ctx: &impl StorageRead) -> Result<u64> {
+    Ok(ctx.read(&masp_nullifier_count_key())?.unwrap_or_default())
+}
+
+/// Read the number of note commitments appended to the commitment tree
+pub fn note_commitment_count(ctx: &impl StorageRead) -> Result<u64> {
+    Ok(ctx
+        .read(&masp_note_commitment_count_key())?
+        .unwrap_or_default())
+}
+
+/// Backfill the MASP counters on chains that revealed nullifiers or appended
+/// note commitments before the counters were introduced. The nullifiers are
+/// counted and the size of the commitment tree is read only once, when the
+/// counters are missing from storage. Returns `true` if any counter was
+/// written.
+pub fn backfill_counters(
+    ctx: &mut (impl StorageRead + StorageWrite),
+) -> Result<bool> {
+    let mut backfilled = false;
+    if !ctx.has_key(&masp_nullifier_count_key())? {
+        let mut count = 0_u64;
+        for nullifier in iter_prefix_bytes(ctx, &masp_nullifiers_prefix())? {
+            let _ = nullifier?;
+            count += 1;
+        }
+        ctx.write(&masp_nullifier_count_key(), count)?;
+        backfilled = true;
+    }
+    if !ctx.has_key(&masp_note_commitment_count_key())? {
+        let count = ctx
+            .read::<CommitmentTree<Node>>(&masp_commitment_tree_key())?
+            .map(|tree| tree.size() as u64)
+            .unwrap_or_default();
+        ctx.write(&masp_note_commitment_count_key(), count)?;
+        backfilled = true;
+    }
+    Ok(backfilled)
+}
+
+// Computes the nullifiers counter after revealing the given number of new
+// nullifiers. Must be called before writing the new nullifiers.
+fn next_nullifier_count(ctx: &impl StorageRead, items: usize) -> Result<u64> {
+    nullifier_count(ctx)?
+        .checked_add(items as u64)
+        .ok_or(Error::SimpleMessage("MASP counter overflow"))
+}
+
 // Writes the nullifiers of the provided masp transaction to storage
 fn reveal_nullifiers(
-    ctx: &mut impl StorageWrite,
+    ctx: &mut (impl StorageRead + StorageWrite),
     transaction: &Transaction,
 ) -> Result<()> {
-    for description in transaction
-        .sapling_bundle()
-        .map_or(&vec![], |description| &description.shielded_spends)
-    {
-        ctx.write(&masp_nullifier_key(&description.nullifier), ())?;
+    if let Some(bundle) = transaction.sapling_bundle() {
+        if !bundle.shielded_spends.is_empty() {
+            let count =
+                next_nullifier_count(ctx, bundle.shielded_spends.len())?;
+            for description in &bundle.shielded_spends {
+                ctx.write(&masp_nullifier_key(&description.nullifier), ())?;
+            }
+            ctx.write(&masp_nullifier_count_key(), count)?;
+        }
     }
 
     Ok(())
 }
 
 /// Appends the note commitments of the provided transaction to the merkle tree
-/// and updates the anchor
+/// and updates the anchor and the note commitments counter
 /// NOTE: this function is public as a temporary workaround because of an issue
 /// when running this function in WASM
 pub fn update_note_commitment_tree(
@@ -50,7 +108,9 @@ pub fn update_note_commitment_tree(
                     })?;
             }
 
+            let count = commitment_tree.size() as u64;
             ctx.write(&tree_key, commitment_tree)?;
+            ctx.write(&masp_note_commitment_count_key(), count)?;
         }
     }
 
@@ -82,3 +142,52 @@ pub fn handle_masp_tx(
 
     Ok(())
 }
+
+/// Collect the storage writes needed to apply the provided masp transaction:
+/// the revealed nullifiers, the note commitment tree with the new note
+/// commitments and the updated counters. Contrary to [`handle_masp_tx`], the
+/// changes are returned instead of written, so that the protocol can apply
+/// them to the write log of its choice.
+pub fn masp_tx_writes(
+    ctx: &impl StorageRead,
+    transaction: &Transaction,
+) -> Result<Vec<(Key, Vec<u8>)>> {
+    let mut writes = vec![];
+    let Some(bundle) = transaction.sapling_bundle() else {
+        return Ok(writes);
+    };
+    if !bundle.shielded_spends.is_empty() {
+        for description in &bundle.shielded_spends {
+            writes.push((
+                masp_nullifier_key(&description.nullifier),
+                ().serialize_to_vec(),
+            ));
+        }
+        writes.push((
+            masp_nullifier_count_key(),
+            next_nullifier_count(ctx, bundle.shielded_spends.len())?
+                .serialize_to_vec(),
+        ));
+    }
+
+    if !bundle.shielded_outputs.is_empty() {
+        let tree_key = masp_commitment_tree_key();
+        let mut commitment_tree: CommitmentTree<Node> =
+            ctx.read(&tree_key)?.ok_or(Error::SimpleMessage(
+                "Missing note commitment tree in storage",
+            ))?;
+        for description in &bundle.shielded_outputs {
+            commitment_tree
+                .append(Node::from_scalar(description.cmu))
+                .map_err(|_| {
+                    Error::SimpleMessage("Note commitment tree is full")
+                })?;
+        }
+        let count = commitment_tree.size() as u64;
+        writes.push((tree_key, commitment_tree.serialize_to_vec()));
+        writes
+            .push((masp_note_commitment_count_key(), count.serialize_to_vec()));
+    }
+
+    Ok(writes)
+}
@@ -1,5 +1,6 @@
 //! Ledger's state storage with key-value backed store and a merkle tree
 
+pub mod pre_state_cache;
 pub mod wl_storage;
 pub mod write_log;
 
@@ -18,23 +19,25 @@ use namada_core::types::hash::{Error as HashError, Hash};
 pub use namada_core::types::hash::{Sha256Hasher, StorageHasher};
 pub use namada_core::types::storage::{
     BlockHash, BlockHeight, BlockResults, Epoch, Epochs, EthEventsQueue,
-    Header, Key, KeySeg, TxIndex, BLOCK_HASH_LENGTH, BLOCK_HEIGHT_LENGTH,
-    EPOCH_TYPE_LENGTH,
+    Header, Key, KeySeg, StateChange, StateDiff, TxIndex, BLOCK_HASH_LENGTH,
+    BLOCK_HEIGHT_LENGTH, EPOCH_TYPE_LENGTH,
 };
 use namada_core::types::time::DateTimeUtc;
 pub use namada_core::types::token::ConversionState;
-use namada_core::types::{encode, ethereum_structs, storage};
+use namada_core::types::{decode, encode, ethereum_structs, storage};
 use namada_gas::{
     MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_ACCESS_GAS_PER_BYTE,
     STORAGE_WRITE_GAS_PER_BYTE,
 };
 pub use namada_merkle_tree::{
     self as merkle_tree, ics23_specs, MembershipProof, MerkleTree,
-    MerkleTreeStoresRead, MerkleTreeStoresWrite, StoreRef, StoreType,
+    MerkleTreeLayout, MerkleTreeStoresRead, MerkleTreeStoresWrite, StoreRef,
+    StoreType,
 };
 use namada_merkle_tree::{Error as MerkleTreeError, MerkleRoot};
 use namada_parameters::{self, EpochDuration, Parameters};
 pub use namada_storage::{Error as StorageError, Result as StorageResult, *};
+use pre_state_cache::PreStateCache;
 use thiserror::Error;
 use tx_queue::{ExpiredTxsQueue, TxQueue};
 pub use wl_storage::{
@@ -48,6 +51,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// it has 2 blocks delay on validator set update.
 pub const EPOCH_SWITCH_BLOCKS_DELAY: u32 = 2;
 
+/// The maximum number of heights pruned when a block is committed. The pruning
+/// of a long history, e.g. of an archive node that switches to a pruning mode,
+/// is spread over many blocks.
+pub const MAX_PRUNED_HEIGHTS_PER_BLOCK: u64 = 1000;
+
 /// The ledger's state
 #[derive(Debug)]
 pub struct State<D, H>
@@ -85,6 +93,9 @@ where
     /// Once the value is `Some(0)`, we're ready to switch to a new epoch and
     /// this is reset back to `None`.
     pub update_epoch_blocks_delay: Option<u32>,
+    /// The layout of the merkle tree, which tells the height from which the
+    /// token and governance data are in their dedicated subtrees
+    pub merkle_tree_layout: MerkleTreeLayout,
     /// The shielded transaction index
     pub tx_index: TxIndex,
     /// The currently saved conversion state
@@ -104,8 +115,17 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// Whether the history that can't be read past the
+    /// `storage_read_past_height_limit` is pruned when a block is committed
+    pub history_pruning: bool,
     /// Static merkle tree storage key filter
     pub merkle_tree_key_filter: fn(&storage::Key) -> bool,
+    /// Static filter of the MASP keys that are also merklized from the MASP
+    /// merklization height of the merkle tree layout
+    pub masp_merkle_tree_key_filter: fn(&storage::Key) -> bool,
+    /// The committed values read by the VPs of a transaction, shared by the
+    /// VPs running in parallel
+    pub pre_state_cache: PreStateCache,
 }
 
 /// Last committed block
@@ -208,6 +228,7 @@ where
                 "Privacy is a function of liberty.",
             ),
             update_epoch_blocks_delay: None,
+            merkle_tree_layout: MerkleTreeLayout::default(),
             tx_index: TxIndex::default(),
             conversion_state: ConversionState::default(),
             tx_queue: TxQueue::default(),
@@ -216,7 +237,10 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            history_pruning: false,
             merkle_tree_key_filter,
+            masp_merkle_tree_key_filter: |_| false,
+            pre_state_cache: PreStateCache::default(),
         }
     }
 
@@ -225,6 +249,7 @@ where
     pub fn load_last_state(&mut self) -> Result<()> {
         if let Some(BlockStateRead {
             merkle_tree_stores,
+            merkle_tree_layout,
             hash,
             height,
             time,
@@ -252,9 +277,14 @@ where
             self.next_epoch_min_start_time = next_epoch_min_start_time;
             self.update_epoch_blocks_delay = update_epoch_blocks_delay;
             self.address_gen = address_gen;
+            self.merkle_tree_layout = merkle_tree_layout;
             // Rebuild Merkle tree
-            self.block.tree = MerkleTree::new(merkle_tree_stores)
-                .or_else(|_| self.rebuild_full_merkle_tree(height))?;
+            self.block.tree = match MerkleTree::new(merkle_tree_stores) {
+                Ok(tree) => tree.with_legacy_layout(
+                    merkle_tree_layout.is_legacy_at(height),
+                ),
+                Err(_) => self.rebuild_full_merkle_tree(height)?,
+            };
             self.conversion_state = conversion_state;
             self.tx_queue = tx_queue;
             self.ethereum_height = ethereum_height;
@@ -297,6 +327,7 @@ where
 
         let state = BlockStateWrite {
             merkle_tree_stores: self.block.tree.stores(),
+            merkle_tree_layout: self.merkle_tree_layout,
             header: self.header.as_ref(),
             hash: &self.block.hash,
             height: self.block.height,
@@ -333,7 +364,14 @@ where
             // prune old merkle tree stores
             self.prune_merkle_tree_stores(&mut batch)?;
         }
+        if self.history_pruning {
+            // prune the history in the same batch, so that it's atomic with
+            // the commit
+            self.prune_history(&mut batch)?;
+        }
         self.db.exec_batch(batch)?;
+        // The cached values may have been updated
+        self.pre_state_cache.clear();
         Ok(())
     }
 
@@ -342,6 +380,13 @@ where
         self.block.tree.root()
     }
 
+    /// Check if the given key is merklized in the tree of the given height
+    pub fn is_merklized_key(&self, key: &Key, height: BlockHeight) -> bool {
+        (self.merkle_tree_key_filter)(key)
+            || (self.merkle_tree_layout.is_masp_merklized_at(height)
+                && (self.masp_merkle_tree_key_filter)(key))
+    }
+
     /// Check if the given key is present in storage. Returns the result and the
     /// gas cost.
     pub fn has_key(&self, key: &Key) -> Result<(bool, u64)> {
@@ -365,6 +410,22 @@ where
         }
     }
 
+    /// Returns a value from the specified subspace and the gas cost, like
+    /// [`State::read`], reading it through the pre-state cache. The gas cost
+    /// doesn't depend on whether the value was cached.
+    pub fn read_cached(&self, key: &Key) -> Result<(Option<Vec<u8>>, u64)> {
+        let value = match self.pre_state_cache.get(key) {
+            Some(value) => value,
+            None => {
+                let value = self.db.read_subspace_val(key)?;
+                self.pre_state_cache.insert(key.clone(), value.clone());
+                value
+            }
+        };
+        let len = key.len() + value.as_ref().map(Vec::len).unwrap_or_default();
+        Ok((value, len as u64 * STORAGE_ACCESS_GAS_PER_BYTE))
+    }
+
     /// Returns a value from the specified subspace at the given height (or the
     /// last committed height when 0) and the gas cost.
     pub fn read_with_height(
@@ -376,7 +437,7 @@ where
         if height == BlockHeight(0) || height >= self.get_last_block_height() {
             self.read(key)
         } else {
-            if !(self.merkle_tree_key_filter)(key) {
+            if !self.is_merklized_key(key, height) {
                 return Ok((None, 0));
             }
 
@@ -412,6 +473,23 @@ where
         )
     }
 
+    /// WARNING: This only works for values that have been committed to DB.
+    /// To be able to see values written or deleted, but not yet committed,
+    /// use the `StorageWithWriteLog`.
+    ///
+    /// Returns a prefix iterator from the given start key included, ordered by
+    /// storage keys, and the gas cost.
+    pub fn iter_prefix_from(
+        &self,
+        prefix: &Key,
+        start: &Key,
+    ) -> (<D as DBIter<'_>>::PrefixIter, u64) {
+        (
+            self.db.iter_prefix_from(Some(prefix), start),
+            prefix.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+        )
+    }
+
     /// Returns an iterator over the block results
     pub fn iter_results(&self) -> (<D as DBIter<'_>>::PrefixIter, u64) {
         (self.db.iter_results(), 0)
@@ -428,7 +506,7 @@ where
         // but with gas and storage bytes len diff accounting
         tracing::debug!("storage write key {}", key,);
         let value = value.as_ref();
-        let is_key_merklized = (self.merkle_tree_key_filter)(key);
+        let is_key_merklized = self.is_merklized_key(key, self.block.height);
 
         if is_pending_transfer_key(key) {
             // The tree of the bright pool stores the current height for the
@@ -450,6 +528,7 @@ where
             value,
             is_key_merklized,
         )?;
+        self.pre_state_cache.clear();
         Ok((gas, size_diff))
     }
 
@@ -460,7 +539,8 @@ where
         // but with gas and storage bytes len diff accounting
         let mut deleted_bytes_len = 0;
         if self.has_key(key)?.0 {
-            let is_key_merklized = (self.merkle_tree_key_filter)(key);
+            let is_key_merklized =
+                self.is_merklized_key(key, self.block.height);
             if is_key_merklized {
                 self.block.tree.delete(key)?;
             }
@@ -469,6 +549,7 @@ where
                 key,
                 is_key_merklized,
             )?;
+            self.pre_state_cache.clear();
         }
         let gas = (key.len() + deleted_bytes_len as usize) as u64
             * STORAGE_WRITE_GAS_PER_BYTE;
@@ -485,6 +566,14 @@ where
 
     /// Block data is in the Merkle tree as it's tracked by Tendermint in the
     /// block header. Hence, we don't update the tree when this is set.
+    ///
+    /// If the merkle tree still has the token and governance data in the
+    /// account subtree and this block begins the first epoch from the
+    /// activation height set by governance, the data is moved into the
+    /// dedicated subtrees first. All the subtrees are committed with a new
+    /// epoch, so the trees of the older epochs can still be rebuilt with
+    /// their own layout. All the validators must be running a version with
+    /// the dedicated subtrees before the activation height.
     pub fn begin_block(
         &mut self,
         hash: BlockHash,
@@ -492,6 +581,58 @@ where
     ) -> Result<()> {
         self.block.hash = hash;
         self.block.height = height;
+        // The epoch is switched in this block when the delay is down to 1
+        if self.block.tree.is_legacy_layout()
+            && self.update_epoch_blocks_delay == Some(1)
+            && self.is_merkle_subtrees_split_active(height)?
+        {
+            self.split_account_merkle_subtree()?;
+        }
+        Ok(())
+    }
+
+    /// Check if the activation height of the merkle subtrees split, if any,
+    /// has been reached at the given height
+    fn is_merkle_subtrees_split_active(
+        &self,
+        height: BlockHeight,
+    ) -> Result<bool> {
+        let key =
+            namada_parameters::storage::get_merkle_subtrees_split_height_key();
+        let activation_height: Option<BlockHeight> = match self.read(&key)?.0 {
+            Some(bytes) => Some(decode(bytes)?),
+            None => None,
+        };
+        Ok(
+            matches!(activation_height, Some(activation) if activation <= height),
+        )
+    }
+
+    /// Move the token and governance data in the merkle tree from the
+    /// account subtree into their dedicated subtrees. The keys are read from
+    /// the DB, so this must be called before any write in the block.
+    fn split_account_merkle_subtree(&mut self) -> Result<()> {
+        let mut keys = vec![];
+        for store_type in StoreType::iter_split_from_account() {
+            for prefix in store_type.split_prefixes() {
+                for (key, _, _) in self.db.iter_prefix(Some(&prefix)) {
+                    let key = Key::parse(key).map_err(Error::KeyError)?;
+                    if self.is_merklized_key(&key, self.block.height) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+        tracing::info!(
+            "Moving {} keys from the account merkle subtree into the token \
+             and governance subtrees",
+            keys.len()
+        );
+        self.block
+            .tree
+            .split_account_subtree(keys)
+            .map_err(Error::MerkleTreeError)?;
+        self.merkle_tree_layout.subtrees_split_height = Some(self.block.height);
         Ok(())
     }
 
@@ -570,26 +711,18 @@ where
             height
         };
 
-        let epoch = self
-            .block
-            .pred_epochs
-            .get_epoch(height)
-            .unwrap_or(Epoch::default());
-        let epoch_start_height =
-            match self.block.pred_epochs.get_start_height_of_epoch(epoch) {
-                Some(height) if height == BlockHeight(0) => BlockHeight(1),
-                Some(height) => height,
-                None => BlockHeight(1),
-            };
+        let (epoch, epoch_start_height) = self.merkle_tree_epoch(height);
         let stores = self
             .db
             .read_merkle_tree_stores(epoch, epoch_start_height, store_type)?
             .ok_or(Error::NoMerkleTree { height })?;
         let prefix = store_type.and_then(|st| st.provable_prefix());
+        let legacy_layout = self.merkle_tree_layout.is_legacy_at(height);
         let mut tree = match store_type {
             Some(_) => MerkleTree::<H>::new_partial(stores),
             None => MerkleTree::<H>::new(stores).expect("invalid stores"),
-        };
+        }
+        .with_legacy_layout(legacy_layout);
         // Restore the tree state with diffs
         let mut target_height = epoch_start_height;
         while target_height < height {
@@ -613,7 +746,9 @@ where
                         match old.0.cmp(&new.0) {
                             Ordering::Equal => {
                                 // the value was updated
-                                if (self.merkle_tree_key_filter)(&new_key) {
+                                if self
+                                    .is_merklized_key(&new_key, target_height)
+                                {
                                     tree.update(
                                         &new_key,
                                         if is_pending_transfer_key(&new_key) {
@@ -628,14 +763,18 @@ where
                             }
                             Ordering::Less => {
                                 // the value was deleted
-                                if (self.merkle_tree_key_filter)(&old_key) {
+                                if self
+                                    .is_merklized_key(&old_key, target_height)
+                                {
                                     tree.delete(&old_key)?;
                                 }
                                 old_diff = old_diff_iter.next();
                             }
                             Ordering::Greater => {
                                 // the value was inserted
-                                if (self.merkle_tree_key_filter)(&new_key) {
+                                if self
+                                    .is_merklized_key(&new_key, target_height)
+                                {
                                     tree.update(
                                         &new_key,
                                         if is_pending_transfer_key(&new_key) {
@@ -654,7 +793,7 @@ where
                         let key = Key::parse(old.0.clone())
                             .expect("the key should be parsable");
 
-                        if (self.merkle_tree_key_filter)(&key) {
+                        if self.is_merklized_key(&key, target_height) {
                             tree.delete(&key)?;
                         }
 
@@ -665,7 +804,7 @@ where
                         let key = Key::parse(new.0.clone())
                             .expect("the key should be parsable");
 
-                        if (self.merkle_tree_key_filter)(&key) {
+                        if self.is_merklized_key(&key, target_height) {
                             tree.update(
                                 &key,
                                 if is_pending_transfer_key(&key) {
@@ -692,11 +831,122 @@ where
             // Set the root and store of the rebuilt subtree
             stores.set_root(&st, *restored_stores.root(&st));
             stores.set_store(restored_stores.store(&st).to_owned());
-            tree = MerkleTree::<H>::new_partial(stores);
+            tree = MerkleTree::<H>::new_partial(stores)
+                .with_legacy_layout(legacy_layout);
         }
         Ok(tree)
     }
 
+    /// Get the changes of the state committed in the block at the given
+    /// height from the diffs in the DB, optionally only the ones of the keys
+    /// with the given prefix. The diffs of the keys that are not merklized
+    /// are only kept for the last committed block, so the changes of an older
+    /// block only include the merklized keys.
+    pub fn get_state_diff(
+        &self,
+        height: BlockHeight,
+        prefix: Option<&Key>,
+    ) -> StateDiff {
+        self.get_state_diff_page(height, prefix, 0, usize::MAX)
+    }
+
+    /// Get at most `limit` of the changes of the state committed in the
+    /// block at the given height, skipping the first `offset` ones, as in
+    /// [`Self::get_state_diff`]. The changes are ordered by their keys.
+    pub fn get_state_diff_page(
+        &self,
+        height: BlockHeight,
+        prefix: Option<&Key>,
+        offset: usize,
+        limit: usize,
+    ) -> StateDiff {
+        // An empty prefix matches all the diffs of the height
+        let all_keys = Key::default();
+        let prefix = Some(prefix.unwrap_or(&all_keys));
+        let mut old_diffs = self.db.iter_old_diffs(height, prefix).peekable();
+        let mut new_diffs = self.db.iter_new_diffs(height, prefix).peekable();
+
+        let mut changes = vec![];
+        let mut index = 0_usize;
+        while changes.len() < limit {
+            // compare keys as String
+            let ordering = match (old_diffs.peek(), new_diffs.peek()) {
+                (Some(old), Some(new)) => old.0.cmp(&new.0),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            let (key, old, new) = match ordering {
+                // the value was updated
+                Ordering::Equal => {
+                    let (key, old, _gas) = old_diffs.next().unwrap();
+                    let (_key, new, _gas) = new_diffs.next().unwrap();
+                    (key, Some(old), Some(new))
+                }
+                // the value was deleted
+                Ordering::Less => {
+                    let (key, old, _gas) = old_diffs.next().unwrap();
+                    (key, Some(old), None)
+                }
+                // the value was inserted
+                Ordering::Greater => {
+                    let (key, new, _gas) = new_diffs.next().unwrap();
+                    (key, None, Some(new))
+                }
+            };
+            if index >= offset {
+                let key = Key::parse(key).expect("the key should be parsable");
+                changes.push(StateChange { key, old, new });
+            }
+            index += 1;
+        }
+        StateDiff { height, changes }
+    }
+
+    /// Get the epoch of the given height and the height from which its
+    /// merkle tree can be rebuilt with the subtree stores of the epoch
+    fn merkle_tree_epoch(&self, height: BlockHeight) -> (Epoch, BlockHeight) {
+        let epoch = self
+            .block
+            .pred_epochs
+            .get_epoch(height)
+            .unwrap_or(Epoch::default());
+        let epoch_start_height =
+            match self.block.pred_epochs.get_start_height_of_epoch(epoch) {
+                Some(height) if height == BlockHeight(0) => BlockHeight(1),
+                Some(height) => height,
+                None => BlockHeight(1),
+            };
+        (epoch, epoch_start_height)
+    }
+
+    /// Check if the merkle tree at the given height still has the token and
+    /// governance data in the account subtree
+    fn is_legacy_merkle_tree(&self, height: BlockHeight) -> bool {
+        if height > self.get_last_block_height() {
+            self.block.tree.is_legacy_layout()
+        } else {
+            self.merkle_tree_layout.is_legacy_at(height)
+        }
+    }
+
+    /// Get the store type of the given key in the merkle tree at the given
+    /// height
+    fn merkle_tree_store_type(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+    ) -> Result<StoreType> {
+        let (store_type, _) = StoreType::sub_key(key)?;
+        if store_type.is_split_from_account()
+            && self.is_legacy_merkle_tree(height)
+        {
+            Ok(StoreType::Account)
+        } else {
+            Ok(store_type)
+        }
+    }
+
     /// Get a Tendermint-compatible existence proof.
     ///
     /// Proofs from the Ethereum bridge pool are not
@@ -734,7 +984,7 @@ where
                 Err(Error::MerkleTreeError(MerkleTreeError::TendermintProof))
             }
         } else {
-            let (store_type, _) = StoreType::sub_key(key)?;
+            let store_type = self.merkle_tree_store_type(key, height)?;
             let tree = self.get_merkle_tree(height, Some(store_type))?;
             if let MembershipProof::ICS23(proof) = tree
                 .get_sub_tree_existence_proof(array::from_ref(key), vec![value])
@@ -770,7 +1020,7 @@ where
                 ),
             })
         } else {
-            let (store_type, _) = StoreType::sub_key(key)?;
+            let store_type = self.merkle_tree_store_type(key, height)?;
             self.get_merkle_tree(height, Some(store_type))?
                 .get_non_existence_proof(key)
                 .map(Into::into)
@@ -778,6 +1028,55 @@ where
         }
     }
 
+    /// Get a batch proof of the values of the given keys, or of their
+    /// absence for the keys without a value. Only the sub-trees of the keys
+    /// are rebuilt.
+    pub fn get_batch_proof(
+        &self,
+        entries: &[(Key, Option<namada_merkle_tree::StorageBytes>)],
+        height: BlockHeight,
+    ) -> Result<ProofOps> {
+        // `0` means last committed height
+        let height = if height == BlockHeight(0) {
+            self.get_last_block_height()
+        } else {
+            height
+        };
+
+        if height > self.get_last_block_height() {
+            return Err(Error::Temporary {
+                error: format!(
+                    "The block at the height {} hasn't committed yet",
+                    height,
+                ),
+            });
+        }
+        let is_legacy = self.is_legacy_merkle_tree(height);
+        let mut sub_tree_proofs = vec![];
+        for store_type in StoreType::iter_subtrees() {
+            let mut sub_tree_entries = vec![];
+            for (key, value) in entries {
+                let (key_store_type, _) = if is_legacy {
+                    StoreType::legacy_sub_key(key)?
+                } else {
+                    StoreType::sub_key(key)?
+                };
+                if key_store_type == *store_type {
+                    sub_tree_entries.push((key.clone(), *value));
+                }
+            }
+            if sub_tree_entries.is_empty() {
+                continue;
+            }
+            let tree = self.get_merkle_tree(height, Some(*store_type))?;
+            sub_tree_proofs.push(
+                tree.get_sub_tree_batch_proof(store_type, &sub_tree_entries)
+                    .map_err(Error::MerkleTreeError)?,
+            );
+        }
+        Ok(merkle_tree::BatchProof { sub_tree_proofs }.into())
+    }
+
     /// Get the current (yet to be committed) block epoch
     pub fn get_current_epoch(&self) -> (Epoch, u64) {
         (
@@ -859,29 +1158,68 @@ where
 
     /// Update the merkle tree with epoch data
     fn update_epoch_in_merkle_tree(&mut self) -> Result<()> {
+        for (key, value) in self.epoch_merkle_tree_entries()? {
+            self.block.tree.update(&key, value)?;
+        }
+        Ok(())
+    }
+
+    /// The keys and values of the epoch data in the merkle tree, which aren't
+    /// in the subspace
+    fn epoch_merkle_tree_entries(&self) -> Result<[(Key, Vec<u8>); 3]> {
         let key_prefix: Key =
             Address::Internal(InternalAddress::PoS).to_db_key().into();
 
-        let key = key_prefix
+        let epoch_start_height_key = key_prefix
             .push(&"epoch_start_height".to_string())
             .map_err(Error::KeyError)?;
-        self.block
-            .tree
-            .update(&key, encode(&self.next_epoch_min_start_height))?;
-
-        let key = key_prefix
+        let epoch_start_time_key = key_prefix
             .push(&"epoch_start_time".to_string())
             .map_err(Error::KeyError)?;
-        self.block
-            .tree
-            .update(&key, encode(&self.next_epoch_min_start_time))?;
-
-        let key = key_prefix
+        let current_epoch_key = key_prefix
             .push(&"current_epoch".to_string())
             .map_err(Error::KeyError)?;
-        self.block.tree.update(&key, encode(&self.block.epoch))?;
 
-        Ok(())
+        Ok([
+            (
+                epoch_start_height_key,
+                encode(&self.next_epoch_min_start_height),
+            ),
+            (
+                epoch_start_time_key,
+                encode(&self.next_epoch_min_start_time),
+            ),
+            (current_epoch_key, encode(&self.block.epoch)),
+        ])
+    }
+
+    /// Rebuild the full Merkle tree of the last committed block from the
+    /// values in the subspace instead of the stores in the DB. The stores of a
+    /// state that wasn't committed by this node, e.g. restored from a snapshot
+    /// or imported from a file, can be inconsistent with its subspace, so the
+    /// root of the rebuilt tree has to be checked against a trusted app hash.
+    pub fn rebuild_merkle_tree_from_subspace(&self) -> Result<MerkleTree<H>> {
+        let height = self.get_last_block_height();
+        let mut tree = MerkleTree::<H>::default()
+            .with_legacy_layout(self.merkle_tree_layout.is_legacy_at(height));
+        for (key, value, _gas) in self.db.iter_prefix(None) {
+            let key = Key::parse(key).map_err(Error::KeyError)?;
+            if is_pending_transfer_key(&key) {
+                // The height of a pending transfer is only in the tree of the
+                // bridge pool, so it's taken from the loaded tree. A transfer
+                // missing from it fails the rebuild.
+                tree.update(&key, self.block.tree.get(&key)?)?;
+            } else if self.is_merklized_key(&key, height) {
+                tree.update(&key, value)?;
+            }
+        }
+        // The epoch data is only added to the tree on the first epoch change
+        for (key, value) in self.epoch_merkle_tree_entries()? {
+            if self.block.tree.has_key(&key)? {
+                tree.update(&key, value)?;
+            }
+        }
+        Ok(tree)
     }
 
     /// Start write batch.
@@ -891,7 +1229,9 @@ where
 
     /// Execute write batch.
     pub fn exec_batch(&mut self, batch: D::WriteBatch) -> Result<()> {
-        Ok(self.db.exec_batch(batch)?)
+        self.db.exec_batch(batch)?;
+        self.pre_state_cache.clear();
+        Ok(())
     }
 
     /// Batch write the value with the given height and account subspace key to
@@ -904,7 +1244,7 @@ where
         value: impl AsRef<[u8]>,
     ) -> Result<i64> {
         let value = value.as_ref();
-        let is_key_merklized = (self.merkle_tree_key_filter)(key);
+        let is_key_merklized = self.is_merklized_key(key, self.block.height);
 
         if is_pending_transfer_key(key) {
             // The tree of the bridge pool stores the current height for the
@@ -934,7 +1274,7 @@ where
         batch: &mut D::WriteBatch,
         key: &Key,
     ) -> Result<i64> {
-        let is_key_merklized = (self.merkle_tree_key_filter)(key);
+        let is_key_merklized = self.is_merklized_key(key, self.block.height);
         // Update the merkle tree
         if is_key_merklized {
             self.block.tree.delete(key)?;
@@ -996,6 +1336,27 @@ where
         Ok(())
     }
 
+    // Prune the diffs and the block data that can't be read anymore. Use after
+    // updating the last block in the commit.
+    fn prune_history(&mut self, batch: &mut D::WriteBatch) -> Result<()> {
+        // The merkle tree at a height is rebuilt from the start of its epoch,
+        // so the whole epoch of the oldest readable height is kept
+        let oldest_epoch = self.get_oldest_epoch();
+        let Some(oldest_height) =
+            self.block.pred_epochs.get_start_height_of_epoch(oldest_epoch)
+        else {
+            return Ok(());
+        };
+        if let Some(height) = self.db.prune_history(
+            batch,
+            oldest_height,
+            MAX_PRUNED_HEIGHTS_PER_BLOCK,
+        )? {
+            tracing::debug!("Pruned the history before height {height}");
+        }
+        Ok(())
+    }
+
     /// Get the height of the last committed block or 0 if no block has been
     /// committed yet. The first block is at height 1.
     pub fn get_last_block_height(&self) -> BlockHeight {
@@ -1143,6 +1504,7 @@ pub mod testing {
                     "Test address generator seed",
                 ),
                 update_epoch_blocks_delay: None,
+                merkle_tree_layout: MerkleTreeLayout::default(),
                 tx_index: TxIndex::default(),
                 conversion_state: ConversionState::default(),
                 tx_queue: TxQueue::default(),
@@ -1151,7 +1513,10 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                history_pruning: false,
                 merkle_tree_key_filter: merklize_all_keys,
+                masp_merkle_tree_key_filter: |_| false,
+                pre_state_cache: PreStateCache::default(),
             }
         }
     }
@@ -1172,6 +1537,7 @@ mod tests {
     use std::collections::BTreeMap;
 
     use chrono::{TimeZone, Utc};
+    use namada_core::types::address;
     use namada_core::types::dec::Dec;
     use namada_core::types::time::{self, Duration};
     use namada_core::types::token;
@@ -1578,4 +1944,145 @@ mod tests {
             .unwrap();
         assert!(res2.is_none());
     }
+    #[test]
+    fn test_get_state_diff() {
+        let mut wls = TestWlStorage::default();
+        (wls.storage.merkle_tree_key_filter) = merkle_tree_key_filter;
+
+        let key1 = test_key_1();
+        let key2 = test_key_2();
+        let key3 = key1.push(&"sub".to_owned()).unwrap();
+
+        // Block 0 inserts key-val-1 and key-val-2
+        wls.write(&key1, 1u64).unwrap();
+        wls.write(&key2, 2u64).unwrap();
+        wls.commit_block().unwrap();
+        let diff = wls.storage.get_state_diff(BlockHeight(0), None);
+        assert_eq!(diff.height, BlockHeight(0));
+        assert_eq!(
+            diff.changes,
+            vec![
+                StateChange {
+                    key: key1.clone(),
+                    old: None,
+                    new: Some(1u64.serialize_to_vec()),
+                },
+                StateChange {
+                    key: key2.clone(),
+                    old: None,
+                    new: Some(2u64.serialize_to_vec()),
+                },
+            ]
+        );
+        wls.storage.block.height = wls.storage.block.height.next_height();
+
+        // Block 1 updates key-val-1, deletes key-val-2 and inserts key-val-3
+        wls.write(&key1, 3u64).unwrap();
+        wls.delete(&key2).unwrap();
+        wls.write(&key3, 4u64).unwrap();
+        wls.commit_block().unwrap();
+        let diff = wls.storage.get_state_diff(BlockHeight(1), None);
+        assert_eq!(
+            diff.changes,
+            vec![
+                StateChange {
+                    key: key1.clone(),
+                    old: Some(1u64.serialize_to_vec()),
+                    new: Some(3u64.serialize_to_vec()),
+                },
+                StateChange {
+                    key: key3.clone(),
+                    old: None,
+                    new: Some(4u64.serialize_to_vec()),
+                },
+                StateChange {
+                    key: key2,
+                    old: Some(2u64.serialize_to_vec()),
+                    new: None,
+                },
+            ]
+        );
+
+        // Only the changes of the keys with the prefix are returned
+        let diff = wls.storage.get_state_diff(BlockHeight(1), Some(&key1));
+        assert_eq!(
+            diff.changes,
+            vec![StateChange {
+                key: key3.clone(),
+                old: None,
+                new: Some(4u64.serialize_to_vec()),
+            }]
+        );
+
+        // The changes can be read by pages
+        let diff = wls.storage.get_state_diff_page(BlockHeight(1), None, 1, 1);
+        assert_eq!(
+            diff.changes,
+            vec![StateChange {
+                key: key3,
+                old: None,
+                new: Some(4u64.serialize_to_vec()),
+            }]
+        );
+        let diff = wls.storage.get_state_diff_page(BlockHeight(1), None, 2, 2);
+        assert_eq!(diff.changes.len(), 1);
+        let diff = wls.storage.get_state_diff_page(BlockHeight(1), None, 3, 2);
+        assert!(diff.changes.is_empty());
+
+        // The diffs of block 0 of the keys that are not merklized are pruned
+        let diff = wls.storage.get_state_diff(BlockHeight(0), None);
+        assert_eq!(
+            diff.changes,
+            vec![StateChange {
+                key: key1,
+                old: None,
+                new: Some(1u64.serialize_to_vec()),
+            }]
+        );
+    }
+
+    /// Test that the token and governance data are only moved into their
+    /// own merkle subtrees from the activation height set by governance
+    #[test]
+    fn test_merkle_subtrees_split_activation() {
+        let mut wls = TestWlStorage::default();
+        // A chain started before the token and governance subtrees were split
+        wls.storage.block.tree = MerkleTree::default().with_legacy_layout(true);
+        wls.storage.merkle_tree_layout.subtrees_split_height = None;
+        let key = namada_trans_token::storage_key::balance_key(
+            &address::nam(),
+            &address::nam(),
+        );
+        wls.storage.write(&key, 1u64.serialize_to_vec()).unwrap();
+        let activation_height = BlockHeight(10);
+        wls.storage
+            .write(
+                &namada_parameters::storage::get_merkle_subtrees_split_height_key(
+                ),
+                activation_height.serialize_to_vec(),
+            )
+            .unwrap();
+        let legacy_root = wls.storage.merkle_root();
+
+        // The root is unchanged by a new epoch before the activation height
+        wls.storage.update_epoch_blocks_delay = Some(1);
+        wls.storage
+            .begin_block(BlockHash::default(), BlockHeight(9))
+            .unwrap();
+        assert!(wls.storage.block.tree.is_legacy_layout());
+        assert!(wls.storage.merkle_root() == legacy_root);
+
+        // The data is moved with the first new epoch from the activation
+        // height
+        wls.storage
+            .begin_block(BlockHash::default(), activation_height)
+            .unwrap();
+        assert!(!wls.storage.block.tree.is_legacy_layout());
+        assert!(wls.storage.merkle_root() != legacy_root);
+        assert_eq!(
+            wls.storage.merkle_tree_layout.subtrees_split_height,
+            Some(activation_height)
+        );
+        assert!(wls.storage.block.tree.has_key(&key).unwrap());
+    }
 }
@@ -0,0 +1,83 @@
+//! A read-through cache of the values committed in storage, shared by the
+//! validity predicates of a transaction running in parallel.
+//!
+//! The committed values don't change until the block is committed and the
+//! cache is cleared whenever the state writes to the DB, so a cached value is
+//! always the one in the DB. The gas of a read doesn't depend on whether its
+//! value was cached, which depends on the order in which the VPs ran.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use namada_core::types::storage::Key;
+
+/// The cache of the values committed in storage read by the VPs
+#[derive(Debug, Default)]
+pub struct PreStateCache {
+    values: RwLock<HashMap<Key, Option<Vec<u8>>>>,
+}
+
+impl PreStateCache {
+    /// Get the cached value of the given key. Returns `None` if the key isn't
+    /// cached and `Some(None)` if the key is cached as having no value.
+    pub fn get(&self, key: &Key) -> Option<Option<Vec<u8>>> {
+        self.values
+            .read()
+            .expect("The pre-state cache lock shouldn't be poisoned")
+            .get(key)
+            .cloned()
+    }
+
+    /// Cache the value of the given key
+    pub fn insert(&self, key: Key, value: Option<Vec<u8>>) {
+        self.values
+            .write()
+            .expect("The pre-state cache lock shouldn't be poisoned")
+            .insert(key, value);
+    }
+
+    /// Remove all the cached values
+    pub fn clear(&self) {
+        self.values
+            .write()
+            .expect("The pre-state cache lock shouldn't be poisoned")
+            .clear();
+    }
+
+    /// Get the number of cached keys
+    pub fn len(&self) -> usize {
+        self.values
+            .read()
+            .expect("The pre-state cache lock shouldn't be poisoned")
+            .len()
+    }
+
+    /// Check if no key is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_state_cache() {
+        let cache = PreStateCache::default();
+        let key = Key::parse("key").unwrap();
+        let absent_key = Key::parse("absent_key").unwrap();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&key), None);
+
+        cache.insert(key.clone(), Some(vec![1, 2, 3]));
+        cache.insert(absent_key.clone(), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key), Some(Some(vec![1, 2, 3])));
+        assert_eq!(cache.get(&absent_key), Some(None));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&key), None);
+    }
+}
@@ -2,6 +2,7 @@
 //! before they are committed to the ledger's storage.
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use itertools::Itertools;
 use namada_core::ledger::replay_protection;
@@ -13,8 +14,8 @@ use namada_core::types::ibc::IbcEvent;
 use namada_core::types::storage;
 use namada_gas::{MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE};
 use namada_trans_token::storage_key::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
-    is_any_token_parameter_key,
+    is_any_allowance_key, is_any_denom_key, is_any_minted_balance_key,
+    is_any_minter_key, is_any_token_balance_key, is_any_token_parameter_key,
 };
 use thiserror::Error;
 
@@ -44,7 +45,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// A storage modification
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum StorageModification {
     /// Write a new value
     Write {
@@ -98,11 +99,33 @@ pub struct WriteLog {
     /// only be populated through a dump of the `tx_write_log` and should be
     /// cleaned either when committing or dumping the `tx_write_log`
     tx_precommit_write_log: HashMap<storage::Key, StorageModification>,
+    /// The net change of the bytes of the values occupied in storage by the
+    /// modifications of the `tx_write_log`
+    tx_size_diff: i64,
+    /// The net change of the bytes of the values occupied in storage by the
+    /// modifications of the `tx_precommit_write_log`
+    tx_precommit_size_diff: i64,
     /// The IBC events for the current transaction
     ibc_events: BTreeSet<IbcEvent>,
     /// Storage modifications for the replay protection storage, always
     /// committed regardless of the result of the transaction
     replay_protection: HashMap<Hash, ReProtStorageModification>,
+    /// The storage read by the transaction, tracked only when the write log
+    /// is a fork used to execute a transaction speculatively
+    read_set: Option<Arc<Mutex<ReadSet>>>,
+}
+
+/// The storage read by a transaction executed on a fork of the write log
+#[derive(Debug, Default)]
+struct ReadSet {
+    /// The keys read from the write log
+    keys: HashSet<storage::Key>,
+    /// The prefixes iterated in the write log
+    prefixes: HashSet<storage::Key>,
+    /// The tx hashes looked up in the replay protection entries
+    tx_hashes: HashSet<Hash>,
+    /// Whether the generator of established addresses was used
+    address_gen: bool,
 }
 
 /// Write log prefix iterator
@@ -128,8 +151,11 @@ impl Default for WriteLog {
             block_write_log: HashMap::with_capacity(100_000),
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
+            tx_size_diff: 0,
+            tx_precommit_size_diff: 0,
             ibc_events: BTreeSet::new(),
             replay_protection: HashMap::with_capacity(1_000),
+            read_set: None,
         }
     }
 }
@@ -141,6 +167,9 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.track_read(|read_set| {
+            read_set.keys.insert(key.clone());
+        });
         // try to read from tx write log first
         match self
             .tx_write_log
@@ -179,6 +208,9 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.track_read(|read_set| {
+            read_set.keys.insert(key.clone());
+        });
         match self.block_write_log.get(key) {
             Some(v) => {
                 let gas = match v {
@@ -231,6 +263,7 @@ impl WriteLog {
             // the previous value exists on the storage
             None => len as i64,
         };
+        self.tx_size_diff += size_diff;
         Ok((gas as u64 * STORAGE_WRITE_GAS_PER_BYTE, size_diff))
     }
 
@@ -314,11 +347,15 @@ impl WriteLog {
             .insert(key.clone(), StorageModification::Delete)
         {
             Some(prev) => match prev {
-                StorageModification::Write { ref value } => value.len() as i64,
+                StorageModification::Write { ref value } => {
+                    self.tx_size_diff -= value.len() as i64;
+                    value.len() as i64
+                }
                 StorageModification::Delete => 0,
                 StorageModification::InitAccount { .. } => {
                     return Err(Error::DeleteVp);
                 }
+                // The temporary value was never accounted for
                 StorageModification::Temp { ref value } => value.len() as i64,
             },
             // set 0 because we don't know if the previous value exists on the
@@ -358,6 +395,7 @@ impl WriteLog {
         storage_address_gen: &EstablishedAddressGen,
         vp_code_hash: Hash,
     ) -> (Address, u64) {
+        self.track_read(|read_set| read_set.address_gen = true);
         // If we've previously generated a new account, we use the local copy of
         // the generator. Otherwise, we create a new copy from the storage
         let address_gen =
@@ -367,11 +405,35 @@ impl WriteLog {
         let key = storage::Key::validity_predicate(&addr);
         let gas = (key.len() + vp_code_hash.len()) as u64
             * STORAGE_WRITE_GAS_PER_BYTE;
+        self.tx_size_diff += vp_code_hash.len() as i64;
         self.tx_write_log
             .insert(key, StorageModification::InitAccount { vp_code_hash });
         (addr, gas)
     }
 
+    /// Check if the given key has been modified by the current transaction
+    /// since its last precommit
+    pub fn has_tx_modification(&self, key: &storage::Key) -> bool {
+        self.tx_write_log.contains_key(key)
+    }
+
+    /// Account for the size of the value that a key had prior to its first
+    /// modification by the current transaction. The size differences returned
+    /// by [`WriteLog::write`] and [`WriteLog::delete`] only know about the
+    /// prior modifications of the current transaction, so this has to be
+    /// recorded before them to track the exact size difference.
+    pub fn record_prior_size(&mut self, size: u64) {
+        self.tx_size_diff -= size as i64;
+    }
+
+    /// Get the net change of the bytes of the values occupied in storage by
+    /// the modifications of the current transaction, including the precommit
+    /// ones. Temporary values are never written to storage and are not
+    /// accounted for.
+    pub fn tx_size_diff(&self) -> i64 {
+        self.tx_size_diff + self.tx_precommit_size_diff
+    }
+
     /// Set an IBC event and return the gas cost.
     pub fn emit_ibc_event(&mut self, event: IbcEvent) -> u64 {
         let len = event
@@ -454,7 +516,8 @@ impl WriteLog {
             HashMap::with_capacity(100),
         );
 
-        self.tx_precommit_write_log.extend(tx_log)
+        self.tx_precommit_write_log.extend(tx_log);
+        self.tx_precommit_size_diff += std::mem::take(&mut self.tx_size_diff);
     }
 
     /// Commit the current transaction's write log and precommit log to the
@@ -474,6 +537,7 @@ impl WriteLog {
         );
 
         self.block_write_log.extend(tx_precommit_write_log);
+        self.tx_precommit_size_diff = 0;
         self.take_ibc_events();
     }
 
@@ -483,6 +547,8 @@ impl WriteLog {
     pub fn drop_tx(&mut self) {
         self.tx_precommit_write_log.clear();
         self.tx_write_log.clear();
+        self.tx_precommit_size_diff = 0;
+        self.tx_size_diff = 0;
     }
 
     /// Drop the current transaction's write log but keep the precommit one.
@@ -491,6 +557,7 @@ impl WriteLog {
     /// section.
     pub fn drop_tx_keep_precommit(&mut self) {
         self.tx_write_log.clear();
+        self.tx_size_diff = 0;
     }
 
     /// Commit the current block's write log to the storage. Starts a new block
@@ -599,6 +666,15 @@ impl WriteLog {
                 verifiers
                     .insert(Address::Internal(InternalAddress::Multitoken));
                 verifiers.insert(owner.clone());
+            } else if let Some([_token, owner, spender]) =
+                is_any_allowance_key(key)
+            {
+                // for allowances, trigger Multitoken VP and the VPs of both
+                // the owner and the spender
+                verifiers
+                    .insert(Address::Internal(InternalAddress::Multitoken));
+                verifiers.insert(owner.clone());
+                verifiers.insert(spender.clone());
             } else if is_any_minted_balance_key(key).is_some()
                 || is_any_minter_key(key).is_some()
                 || is_any_token_parameter_key(key).is_some()
@@ -606,6 +682,12 @@ impl WriteLog {
                 verifiers
                     .insert(Address::Internal(InternalAddress::Multitoken));
             } else {
+                // for denominations, trigger Multitoken VP on top of the VPs
+                // of the addresses in the key
+                if is_any_denom_key(key).is_some() {
+                    verifiers
+                        .insert(Address::Internal(InternalAddress::Multitoken));
+                }
                 for addr in key.iter_addresses() {
                     if verifiers_from_tx.contains(addr)
                         || initialized_accounts.contains(addr)
@@ -630,6 +712,9 @@ impl WriteLog {
     /// Iterate modifications prior to the current transaction, whose storage
     /// key matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_pre(&self, prefix: &storage::Key) -> PrefixIter {
+        self.track_read(|read_set| {
+            read_set.prefixes.insert(prefix.clone());
+        });
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -645,6 +730,9 @@ impl WriteLog {
     /// Iterate modifications posterior of the current tx, whose storage key
     /// matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_post(&self, prefix: &storage::Key) -> PrefixIter {
+        self.track_read(|read_set| {
+            read_set.prefixes.insert(prefix.clone());
+        });
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -665,6 +753,9 @@ impl WriteLog {
     /// Check if the given tx hash has already been processed. Returns `None` if
     /// the key is not known.
     pub fn has_replay_protection_entry(&self, hash: &Hash) -> Option<bool> {
+        self.track_read(|read_set| {
+            read_set.tx_hashes.insert(*hash);
+        });
         self.replay_protection
             .get(hash)
             .map(|action| !matches!(action, ReProtStorageModification::Delete))
@@ -726,6 +817,75 @@ impl WriteLog {
 
         Ok(())
     }
+
+    /// Fork the write log to speculatively execute a transaction on top of
+    /// the current block modifications. The fork tracks the storage read by
+    /// the transaction, which can then be applied with [`Self::merge_fork`].
+    pub fn fork(&self) -> Self {
+        Self {
+            address_gen: self.address_gen.clone(),
+            block_write_log: self.block_write_log.clone(),
+            tx_write_log: HashMap::with_capacity(100),
+            tx_precommit_write_log: HashMap::with_capacity(100),
+            tx_size_diff: 0,
+            tx_precommit_size_diff: 0,
+            ibc_events: BTreeSet::new(),
+            replay_protection: self.replay_protection.clone(),
+            read_set: Some(Arc::new(Mutex::new(ReadSet::default()))),
+        }
+    }
+
+    /// Apply the modifications of a transaction executed on a `fork` of the
+    /// `base` write log to the current transaction. The modifications are
+    /// applied only if none of the storage read by the transaction has been
+    /// modified since the fork, in which case executing the transaction on
+    /// this write log would have given the same result. Returns `false`,
+    /// leaving the write log untouched, otherwise.
+    pub fn merge_fork(&mut self, base: &WriteLog, fork: WriteLog) -> bool {
+        let Some(read_set) = fork.read_set.as_ref() else {
+            return false;
+        };
+        let read_set = read_set
+            .lock()
+            .expect("The read set lock shouldn't be poisoned");
+        let unchanged = read_set
+            .keys
+            .iter()
+            .all(|key| base.read(key).0 == self.read(key).0)
+            && read_set.prefixes.iter().all(|prefix| {
+                base.iter_prefix_post(prefix)
+                    .eq(self.iter_prefix_post(prefix))
+            })
+            && read_set.tx_hashes.iter().all(|hash| {
+                base.has_replay_protection_entry(hash)
+                    == self.has_replay_protection_entry(hash)
+            })
+            && (!read_set.address_gen || base.address_gen == self.address_gen);
+        if !unchanged {
+            return false;
+        }
+        if read_set.address_gen {
+            self.address_gen = fork.address_gen.clone();
+        }
+        drop(read_set);
+        self.tx_write_log = fork.tx_write_log;
+        self.tx_precommit_write_log = fork.tx_precommit_write_log;
+        self.tx_size_diff = fork.tx_size_diff;
+        self.tx_precommit_size_diff = fork.tx_precommit_size_diff;
+        self.ibc_events = fork.ibc_events;
+        true
+    }
+
+    /// Record a read in the read set, if the write log is a fork
+    fn track_read(&self, record: impl FnOnce(&mut ReadSet)) {
+        if let Some(read_set) = &self.read_set {
+            record(
+                &mut read_set
+                    .lock()
+                    .expect("The read set lock shouldn't be poisoned"),
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -738,6 +898,43 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_tx_size_diff() {
+        let mut write_log = WriteLog::default();
+        let key1 = storage::Key::parse("key1").unwrap();
+        let key2 = storage::Key::parse("key2").unwrap();
+
+        // A new value, then grown
+        write_log.record_prior_size(0);
+        write_log.write(&key1, vec![0; 4]).unwrap();
+        write_log.write(&key1, vec![0; 6]).unwrap();
+        assert_eq!(write_log.tx_size_diff(), 6);
+
+        // The precommit diff is kept when the tx write log is dropped
+        write_log.precommit_tx();
+        assert!(!write_log.has_tx_modification(&key1));
+        write_log.record_prior_size(10);
+        write_log.delete(&key2).unwrap();
+        assert_eq!(write_log.tx_size_diff(), -4);
+        write_log.drop_tx_keep_precommit();
+        assert_eq!(write_log.tx_size_diff(), 6);
+
+        // A temporary value isn't accounted for
+        write_log.write_temp(&key2, vec![0; 8]).unwrap();
+        assert_eq!(write_log.tx_size_diff(), 6);
+
+        write_log.commit_tx();
+        assert_eq!(write_log.tx_size_diff(), 0);
+
+        // A value deleted after being written
+        write_log.record_prior_size(6);
+        write_log.write(&key1, vec![0; 2]).unwrap();
+        write_log.delete(&key1).unwrap();
+        assert_eq!(write_log.tx_size_diff(), -6);
+        write_log.drop_tx();
+        assert_eq!(write_log.tx_size_diff(), 0);
+    }
+
     #[test]
     fn test_crud_value() {
         let mut write_log = WriteLog::default();
@@ -1046,6 +1243,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_fork() {
+        let mut write_log = WriteLog::default();
+        let read_key = storage::Key::parse("read").unwrap();
+        let written_key = storage::Key::parse("written").unwrap();
+        let other_key = storage::Key::parse("other").unwrap();
+        write_log.write(&read_key, vec![1]).unwrap();
+        write_log.commit_tx();
+        let base = write_log.clone();
+
+        // A tx reading `read_key` and writing `written_key`
+        let execute = |base: &WriteLog| {
+            let mut fork = base.fork();
+            let _ = fork.read(&read_key);
+            fork.write(&written_key, vec![2]).unwrap();
+            fork
+        };
+
+        // Only a fork can be merged
+        assert!(!write_log.merge_fork(&base, base.clone()));
+
+        // Nothing changed since the fork, the tx is applied
+        assert!(write_log.merge_fork(&base, execute(&base)));
+        assert_matches!(
+            write_log.read(&written_key).0,
+            Some(StorageModification::Write { value }) if value == &vec![2]
+        );
+        write_log.drop_tx();
+
+        // A modification of a key not read by the tx doesn't conflict
+        write_log.write(&other_key, vec![3]).unwrap();
+        write_log.commit_tx();
+        assert!(write_log.merge_fork(&base, execute(&base)));
+        write_log.drop_tx();
+
+        // A modification of a key read by the tx conflicts
+        write_log.write(&read_key, vec![4]).unwrap();
+        write_log.commit_tx();
+        assert!(!write_log.merge_fork(&base, execute(&base)));
+        assert!(write_log.read(&written_key).0.is_none());
+    }
+
     prop_compose! {
         fn arb_verifiers_changed_key_tx_all_key()
             (verifiers_from_tx in testing::arb_verifiers_from_tx())
@@ -10,8 +10,8 @@ use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::ConversionState;
 use namada_core::types::{ethereum_events, ethereum_structs};
 use namada_merkle_tree::{
-    Error as MerkleTreeError, MerkleTreeStoresRead, MerkleTreeStoresWrite,
-    StoreType,
+    Error as MerkleTreeError, MerkleTreeLayout, MerkleTreeStoresRead,
+    MerkleTreeStoresWrite, StoreType,
 };
 use thiserror::Error;
 
@@ -36,6 +36,14 @@ pub enum Error {
     BorshCodingError(std::io::Error),
     #[error("Merkle tree at the height {height} is not stored")]
     NoMerkleTree { height: BlockHeight },
+    #[error(
+        "The state at height {height} has been pruned, the oldest available \
+         height is {oldest_height}"
+    )]
+    Pruned {
+        height: BlockHeight,
+        oldest_height: BlockHeight,
+    },
     #[error("Code hash error: {0}")]
     InvalidCodeHash(HashError),
 }
@@ -47,6 +55,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct BlockStateRead {
     /// Merkle tree stores
     pub merkle_tree_stores: MerkleTreeStoresRead,
+    /// Layout of the merkle tree subtrees
+    pub merkle_tree_layout: MerkleTreeLayout,
     /// Hash of the block
     pub hash: BlockHash,
     /// Height of the block
@@ -82,6 +92,8 @@ pub struct BlockStateRead {
 pub struct BlockStateWrite<'a> {
     /// Merkle tree stores
     pub merkle_tree_stores: MerkleTreeStoresWrite<'a>,
+    /// Layout of the merkle tree subtrees
+    pub merkle_tree_layout: MerkleTreeLayout,
     /// Header of the block
     pub header: Option<&'a Header>,
     /// Hash of the block
@@ -240,6 +252,17 @@ pub trait DB: Debug {
         pruned_epoch: Epoch,
     ) -> Result<()>;
 
+    /// Prune the diffs and the block data of the heights below the given one,
+    /// except for the merkle subtree stores which are pruned by epoch. At most
+    /// `max_heights` heights are pruned at once. Returns the new oldest
+    /// available height, if any height was pruned.
+    fn prune_history(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        max_heights: u64,
+    ) -> Result<Option<BlockHeight>>;
+
     /// Read the signed nonce of Bridge Pool
     fn read_bridge_pool_signed_nonce(
         &self,
@@ -275,6 +298,15 @@ pub trait DBIter<'iter> {
     /// ordered by the storage keys.
     fn iter_prefix(&'iter self, prefix: Option<&Key>) -> Self::PrefixIter;
 
+    /// Read account subspace key value pairs with the given prefix from the DB,
+    /// ordered by the storage keys, from the given start key included. The
+    /// start key must not be before the prefix.
+    fn iter_prefix_from(
+        &'iter self,
+        prefix: Option<&Key>,
+        start: &Key,
+    ) -> Self::PrefixIter;
+
     /// Read results subspace key value pairs from the DB
     fn iter_results(&'iter self) -> Self::PrefixIter;
 
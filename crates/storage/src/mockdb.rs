@@ -19,7 +19,8 @@ use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::ConversionState;
 use namada_core::types::{ethereum_events, ethereum_structs};
 use namada_merkle_tree::{
-    base_tree_key_prefix, subtree_key_prefix, MerkleTreeStoresRead, StoreType,
+    base_tree_key_prefix, subtree_key_prefix, MerkleTreeLayout,
+    MerkleTreeStoresRead, StoreType,
 };
 
 use crate::db::{
@@ -102,6 +103,15 @@ impl DB for MockDB {
                 }
                 None => return Ok(None),
             };
+        // The layout isn't stored by the versions before the subtrees were
+        // split from the account subtree
+        let merkle_tree_layout: MerkleTreeLayout =
+            match self.0.borrow().get("merkle_tree_layout") {
+                Some(bytes) => {
+                    types::decode(bytes).map_err(Error::CodingError)?
+                }
+                None => MerkleTreeLayout::LEGACY,
+            };
         let conversion_state: ConversionState =
             match self.0.borrow().get("conversion_state") {
                 Some(bytes) => {
@@ -226,6 +236,7 @@ impl DB for MockDB {
                 Some(address_gen),
             ) => Ok(Some(BlockStateRead {
                 merkle_tree_stores,
+                merkle_tree_layout,
                 hash,
                 height,
                 time,
@@ -256,6 +267,7 @@ impl DB for MockDB {
     ) -> Result<()> {
         let BlockStateWrite {
             merkle_tree_stores,
+            merkle_tree_layout,
             header,
             hash,
             time,
@@ -286,6 +298,10 @@ impl DB for MockDB {
             "update_epoch_blocks_delay".into(),
             types::encode(&update_epoch_blocks_delay),
         );
+        self.0.borrow_mut().insert(
+            "merkle_tree_layout".into(),
+            types::encode(&merkle_tree_layout),
+        );
         self.0
             .borrow_mut()
             .insert("ethereum_height".into(), types::encode(&ethereum_height));
@@ -433,6 +449,9 @@ impl DB for MockDB {
                     let root = types::decode(b).map_err(Error::CodingError)?;
                     merkle_tree_stores.set_root(st, root);
                 }
+                // The subtrees split from the account subtree aren't stored
+                // in the epochs before the split
+                None if st.is_split_from_account() => continue,
                 None => return Ok(None),
             }
 
@@ -670,6 +689,16 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn prune_history(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        _height: BlockHeight,
+        _max_heights: u64,
+    ) -> Result<Option<BlockHeight>> {
+        // The history isn't pruned in memory
+        Ok(None)
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         _height: BlockHeight,
@@ -733,6 +762,31 @@ impl<'iter> DBIter<'iter> for MockDB {
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
     }
 
+    fn iter_prefix_from(
+        &'iter self,
+        prefix: Option<&Key>,
+        start: &Key,
+    ) -> MockPrefixIterator {
+        let stripped_prefix = "subspace/".to_owned();
+        let prefix = format!(
+            "{}{}",
+            stripped_prefix,
+            match prefix {
+                Some(prefix) => {
+                    if prefix == &Key::default() {
+                        prefix.to_string()
+                    } else {
+                        format!("{prefix}/")
+                    }
+                }
+                None => "".to_string(),
+            }
+        );
+        let start = format!("{stripped_prefix}{start}");
+        let iter = self.0.borrow().clone().split_off(&start).into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
+
     fn iter_results(&'iter self) -> MockPrefixIterator {
         let stripped_prefix = "results/".to_owned();
         let prefix = "results".to_owned();
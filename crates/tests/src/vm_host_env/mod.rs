@@ -589,7 +589,7 @@ mod tests {
                         &None,
                         1,
                         None,
-                        || Ok(())
+                        |_| Ok(())
                     )
                     .is_ok()
             );
@@ -605,7 +605,7 @@ mod tests {
                         &None,
                         1,
                         None,
-                        || Ok(())
+                        |_| Ok(())
                     )
                     .is_err()
             );
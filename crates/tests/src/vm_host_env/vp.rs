@@ -380,5 +380,17 @@ mod native_vp_host_env {
         max_signatures_ptr: u64,
         max_signatures_len: u64,
     ) -> i64);
+    native_host_fn!(vp_verify_tx_section_weighted_signature(
+        hash_list_ptr: u64,
+        hash_list_len: u64,
+        public_keys_map_ptr: u64,
+        public_keys_map_len: u64,
+        signer_ptr: u64,
+        signer_len: u64,
+        weights_ptr: u64,
+        weights_len: u64,
+        max_signatures_ptr: u64,
+        max_signatures_len: u64,
+    ) -> i64);
     native_host_fn!(vp_charge_gas(used_gas: u64));
 }
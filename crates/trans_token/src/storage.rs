@@ -97,6 +97,149 @@ where
     storage.write(&key, denom)
 }
 
+/// Read the minter of a given token, if any.
+pub fn read_minter<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<Option<Address>>
+where
+    S: StorageRead,
+{
+    storage.read(&minter_key(token))
+}
+
+/// Read the maximum supply of a given user-issued token, if it is capped.
+pub fn read_max_supply<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&max_supply_key(token))
+}
+
+/// Register a user-issued token. The `token` is the address of the
+/// established account issuing it, whose VP has to authorize the
+/// registration. The `minter` is the address whose VP authorizes all the
+/// subsequent mints and burns of the token, up to the optional `max_supply`.
+pub fn register_token<S>(
+    storage: &mut S,
+    token: &Address,
+    denom: token::Denomination,
+    minter: &Address,
+    max_supply: Option<token::Amount>,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !matches!(token, Address::Established(_)) {
+        return Err(storage::Error::new_const(
+            "Only established accounts can register a token",
+        ));
+    }
+    if read_minter(storage, token)?.is_some() {
+        return Err(storage::Error::new_const(
+            "The token has already been registered",
+        ));
+    }
+    write_denom(storage, token, denom)?;
+    storage.write(&minter_key(token), minter)?;
+    if let Some(max_supply) = max_supply {
+        storage.write(&max_supply_key(token), max_supply)?;
+    }
+    Ok(())
+}
+
+/// Read the metadata of a given token, if any.
+pub fn read_metadata<S>(
+    storage: &S,
+    token: &Address,
+) -> storage::Result<Option<token::TokenMetadata>>
+where
+    S: StorageRead,
+{
+    storage.read(&metadata_key(token))
+}
+
+/// Write the metadata of a given token.
+pub fn write_metadata<S>(
+    storage: &mut S,
+    token: &Address,
+    metadata: token::TokenMetadata,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&metadata_key(token), metadata)
+}
+
+/// Read the allowance of a spender over an owner's balance of a token, if
+/// any.
+pub fn read_allowance<S>(
+    storage: &S,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> storage::Result<Option<token::Allowance>>
+where
+    S: StorageRead,
+{
+    storage.read(&allowance_key(token, owner, spender))
+}
+
+/// Set the allowance of a spender over an owner's balance of a token. A zero
+/// amount removes the allowance. This has to be authorized by the owner.
+pub fn write_allowance<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+    allowance: token::Allowance,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = allowance_key(token, owner, spender);
+    if allowance.amount.is_zero() {
+        storage.delete(&key)
+    } else {
+        storage.write(&key, allowance)
+    }
+}
+
+/// Transfer `token` from `src` to `dest` on behalf of `spender`, deducting
+/// the `amount` from the spender's allowance. Returns an `Err` if the
+/// allowance is missing, expired or insufficient, or if the transfer fails.
+pub fn transfer_from<S>(
+    storage: &mut S,
+    token: &Address,
+    spender: &Address,
+    src: &Address,
+    dest: &Address,
+    amount: token::Amount,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let mut allowance = match read_allowance(storage, token, src, spender)? {
+        Some(allowance) if !allowance.is_expired(current_epoch) => allowance,
+        _ => {
+            return Err(storage::Error::new_const(
+                "The spender has no valid allowance",
+            ));
+        }
+    };
+    allowance.amount = allowance.amount.checked_sub(amount).ok_or_else(|| {
+        storage::Error::new_const("Insufficient allowance")
+    })?;
+    // NB: a spent allowance is kept in storage even when it drops to zero, as
+    // its removal is reserved to the owner
+    storage.write(&allowance_key(token, src, spender), allowance)?;
+    transfer(storage, token, src, dest, amount)
+}
+
 /// Transfer `token` from `src` to `dest`. Returns an `Err` if `src` has
 /// insufficient balance or if the transfer the `dest` would overflow (This can
 /// only happen if the total supply doesn't fit in `token::Amount`).
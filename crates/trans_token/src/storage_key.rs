@@ -13,6 +13,12 @@ pub const MINTER_STORAGE_KEY: &str = "minter";
 pub const MINTED_STORAGE_KEY: &str = "minted";
 /// Key segment for token parameters
 pub const PARAMETERS_STORAGE_KEY: &str = "parameters";
+/// Key segment for the maximum supply of a user-issued token
+pub const MAX_SUPPLY_STORAGE_KEY: &str = "max_supply";
+/// Key segment for the token metadata parameter
+pub const METADATA_STORAGE_KEY: &str = "metadata";
+/// Key segment for token allowances
+pub const ALLOWANCE_STORAGE_KEY: &str = "allowance";
 
 /// Gets the key for the given token address, error with the given
 /// message to expect if the key is not in the address
@@ -55,6 +61,32 @@ pub fn parameter_prefix(token_addr: &Address) -> storage::Key {
     .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key for the metadata of a token.
+pub fn metadata_key(token_addr: &Address) -> storage::Key {
+    parameter_prefix(token_addr)
+        .push(&METADATA_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a metadata key for an unspecified token.
+/// If it is, return the token address.
+pub fn is_any_metadata_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(parameter),
+            DbKeySeg::StringSeg(metadata),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && parameter == PARAMETERS_STORAGE_KEY
+            && metadata == METADATA_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Obtain a storage key for the multitoken minter.
 pub fn minter_key(token_addr: &Address) -> storage::Key {
     storage::Key::from(
@@ -73,6 +105,56 @@ pub fn minted_balance_key(token_addr: &Address) -> storage::Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key for the maximum supply of a user-issued token.
+pub fn max_supply_key(token_addr: &Address) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&token_addr.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&MAX_SUPPLY_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key for the allowance of a spender over an owner's
+/// balance of a token.
+pub fn allowance_key(
+    token_addr: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&token_addr.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&ALLOWANCE_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+    .push(&owner.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&spender.to_db_key())
+    .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is an allowance key for an unspecified
+/// token. If it is, return the token, owner and spender addresses.
+pub fn is_any_allowance_key(key: &storage::Key) -> Option<[&Address; 3]> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(allowance),
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::AddressSeg(spender),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && allowance == ALLOWANCE_STORAGE_KEY =>
+        {
+            Some([token, owner, spender])
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is a balance key for the given token. If it
 /// is, return the owner. For minted balances, use
 /// [`is_any_minted_balance_key()`].
@@ -149,6 +231,19 @@ pub fn is_denom_key(token_addr: &Address, key: &storage::Key) -> bool {
         ] if key == DENOM_STORAGE_KEY && addr == token_addr)
 }
 
+/// Check if the given storage key is a denomination key for an unspecified
+/// token. If it is, returns the token.
+pub fn is_any_denom_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(token), DbKeySeg::StringSeg(denom)]
+            if denom == DENOM_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is for a minter of a unspecified token.
 /// If it is, returns the token.
 pub fn is_any_minter_key(key: &storage::Key) -> Option<&Address> {
@@ -166,6 +261,23 @@ pub fn is_any_minter_key(key: &storage::Key) -> Option<&Address> {
     }
 }
 
+/// Check if the given storage key is for the maximum supply of an unspecified
+/// token. If it is, returns the token.
+pub fn is_any_max_supply_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(max_supply),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && max_supply == MAX_SUPPLY_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is for total supply of a unspecified token.
 /// If it is, returns the token.
 pub fn is_any_minted_balance_key(key: &storage::Key) -> Option<&Address> {
@@ -476,6 +476,45 @@ mod test_process_tx {
         let result = tx.validate_tx().expect_err("Test failed");
         assert_matches!(result, TxError::SigError(_));
     }
+    /// Test that a wrapper paying the fee from the shielded pool references
+    /// its masp section like a fee unshielding, without changing the encoding
+    /// of the wrapper
+    #[test]
+    fn test_masp_fee_wrapper_encoding() {
+        let keypair = gen_keypair();
+        let masp_fee_hash = Hash([1; 32]);
+        let fee = Fee {
+            amount_per_gas_unit: DenominatedAmount::native(
+                Amount::from_uint(10, 0).expect("Test failed"),
+            ),
+            token: nam(),
+        };
+        let wrapper = WrapperTx::new(
+            fee.clone(),
+            keypair.ref_to(),
+            Epoch(0),
+            Default::default(),
+            Some(masp_fee_hash),
+        );
+
+        let mut expected = fee.serialize_to_vec();
+        expected.extend(keypair.ref_to().serialize_to_vec());
+        expected.extend(Epoch(0).serialize_to_vec());
+        expected.extend(GasLimit::default().serialize_to_vec());
+        expected.extend(Some(masp_fee_hash).serialize_to_vec());
+        assert_eq!(wrapper.serialize_to_vec(), expected);
+
+        let mut tx = Tx::from_type(TxType::Wrapper(Box::new(wrapper)));
+        tx.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
+        tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        tx.add_section(Section::Signature(Signature::new(
+            tx.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        tx.validate_tx().expect("Test failed");
+    }
 }
 
 /// Test that process_tx correctly identifies a DecryptedTx
@@ -198,7 +198,10 @@ pub mod wrapper_tx {
         /// Max amount of gas that can be used when executing the inner tx
         pub gas_limit: GasLimit,
         /// The hash of the optional, unencrypted, unshielding transaction for
-        /// fee payment
+        /// fee payment. If the transaction unshields the fee to a target
+        /// committing to this wrapper rather than to its signer, the fee is
+        /// paid directly from the shielded pool and the signer of the wrapper
+        /// doesn't need any funds
         pub unshield_section_hash: Option<Hash>,
     }
 
@@ -248,40 +251,7 @@ pub mod wrapper_tx {
             descriptions_limit: u64,
             unshield: Transaction,
         ) -> Result<Tx, WrapperTxErr> {
-            // Check that the number of descriptions is within a certain limit
-            // to avoid a possible DoS vector
-            let sapling_bundle = unshield.sapling_bundle().ok_or(
-                WrapperTxErr::InvalidUnshield(
-                    "Missing required sapling bundle".to_string(),
-                ),
-            )?;
-            let spends = sapling_bundle.shielded_spends.len();
-            let converts = sapling_bundle.shielded_converts.len();
-            let outs = sapling_bundle.shielded_outputs.len();
-
-            let descriptions = spends
-                .checked_add(converts)
-                .ok_or_else(|| {
-                    WrapperTxErr::InvalidUnshield(
-                        "Descriptions overflow".to_string(),
-                    )
-                })?
-                .checked_add(outs)
-                .ok_or_else(|| {
-                    WrapperTxErr::InvalidUnshield(
-                        "Descriptions overflow".to_string(),
-                    )
-                })?;
-
-            if u64::try_from(descriptions)
-                .map_err(|e| WrapperTxErr::InvalidUnshield(e.to_string()))?
-                > descriptions_limit
-            {
-                return Err(WrapperTxErr::InvalidUnshield(
-                    "Descriptions exceed the maximum amount allowed"
-                        .to_string(),
-                ));
-            }
+            check_descriptions_limit(&unshield, descriptions_limit)?;
             self.generate_fee_unshielding(
                 transfer_code_hash,
                 transfer_code_tag,
@@ -295,10 +265,48 @@ pub mod wrapper_tx {
             transfer_code_hash: Hash,
             transfer_code_tag: Option<String>,
             unshield: Transaction,
+        ) -> Result<Tx, WrapperTxErr> {
+            let mut tx = self.masp_fee_tx(self.fee_payer(), unshield)?;
+            tx.set_code(Code::from_hash(transfer_code_hash, transfer_code_tag));
+
+            Ok(tx)
+        }
+
+        /// Performs validation on the masp section paying the fee from the
+        /// shielded pool and generates the tx carrying it, to be validated
+        /// by the MASP VP. The section is referenced by the unshielding
+        /// section hash of the wrapper and must unshield the fee amount to a
+        /// transparent target committing to this wrapper, which the MASP VP
+        /// checks. The protocol then moves the fee from the MASP to the block
+        /// proposer.
+        pub fn check_and_generate_masp_fee_payment(
+            &self,
+            descriptions_limit: u64,
+            masp_fee: Transaction,
+        ) -> Result<Tx, WrapperTxErr> {
+            check_descriptions_limit(&masp_fee, descriptions_limit)?;
+            if masp_fee
+                .transparent_bundle()
+                .is_some_and(|bundle| !bundle.vin.is_empty())
+            {
+                return Err(WrapperTxErr::InvalidUnshield(
+                    "Transparent inputs are not allowed in masp fee payment"
+                        .to_string(),
+                ));
+            }
+            self.masp_fee_tx(MASP, masp_fee)
+        }
+
+        // Generates a tx carrying the masp transaction of the fee payment and
+        // the transfer of the fee from the MASP to the given target
+        fn masp_fee_tx(
+            &self,
+            target: Address,
+            masp_tx: Transaction,
         ) -> Result<Tx, WrapperTxErr> {
             let mut tx =
                 Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
-            let masp_section = tx.add_section(Section::MaspTx(unshield));
+            let masp_section = tx.add_section(Section::MaspTx(masp_tx));
             let masp_hash = Hash(
                 masp_section
                     .hash(&mut Sha256::new())
@@ -308,7 +316,7 @@ pub mod wrapper_tx {
 
             let transfer = Transfer {
                 source: MASP,
-                target: self.fee_payer(),
+                target,
                 token: self.fee.token.clone(),
                 amount: self.get_tx_fee()?,
                 key: None,
@@ -316,7 +324,6 @@ pub mod wrapper_tx {
             };
             let data = transfer.serialize_to_vec();
             tx.set_data(Data::new(data));
-            tx.set_code(Code::from_hash(transfer_code_hash, transfer_code_tag));
 
             Ok(tx)
         }
@@ -329,6 +336,62 @@ pub mod wrapper_tx {
                 .checked_mul(Amount::from(self.gas_limit).into())
                 .ok_or(WrapperTxErr::OverflowingFee)
         }
+
+        /// Get the [`Amount`] of fees to be paid by the given wrapper when
+        /// `refunded_gas` whole units of its gas limit are covered by the
+        /// storage refunds of the fee payer. Returns an error if the amount
+        /// overflows
+        pub fn get_tx_fee_after_refund(
+            &self,
+            refunded_gas: u64,
+        ) -> Result<DenominatedAmount, WrapperTxErr> {
+            let gas = u64::from(self.gas_limit).saturating_sub(refunded_gas);
+            self.fee
+                .amount_per_gas_unit
+                .checked_mul(Amount::from(gas).into())
+                .ok_or(WrapperTxErr::OverflowingFee)
+        }
+    }
+
+    // Check that the number of descriptions of the masp transaction used for
+    // fee payment is within a certain limit to avoid a possible DoS vector
+    fn check_descriptions_limit(
+        transaction: &Transaction,
+        descriptions_limit: u64,
+    ) -> Result<(), WrapperTxErr> {
+        let sapling_bundle = transaction.sapling_bundle().ok_or(
+            WrapperTxErr::InvalidUnshield(
+                "Missing required sapling bundle".to_string(),
+            ),
+        )?;
+        let spends = sapling_bundle.shielded_spends.len();
+        let converts = sapling_bundle.shielded_converts.len();
+        let outs = sapling_bundle.shielded_outputs.len();
+
+        let descriptions = spends
+            .checked_add(converts)
+            .ok_or_else(|| {
+                WrapperTxErr::InvalidUnshield(
+                    "Descriptions overflow".to_string(),
+                )
+            })?
+            .checked_add(outs)
+            .ok_or_else(|| {
+                WrapperTxErr::InvalidUnshield(
+                    "Descriptions overflow".to_string(),
+                )
+            })?;
+
+        if u64::try_from(descriptions)
+            .map_err(|e| WrapperTxErr::InvalidUnshield(e.to_string()))?
+            > descriptions_limit
+        {
+            return Err(WrapperTxErr::InvalidUnshield(
+                "Descriptions exceed the maximum amount allowed".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
     #[cfg(test)]
@@ -7,9 +7,9 @@ mod types;
 pub use namada_core::types::key::SignableEthMessage;
 pub use namada_core::types::sign::SignatureIndex;
 pub use types::{
-    standalone_signature, verify_standalone_sig, Code, Commitment,
-    CompressedSignature, Data, DecodeError, Header, MaspBuilder, Memo, Section,
-    Signature, Signed, Signer, Tx, TxError, VerifySigError,
+    standalone_signature, verify_standalone_sig, AggregateSignature, Code,
+    Commitment, CompressedSignature, Data, DecodeError, Header, MaspBuilder,
+    Memo, Section, Signature, Signed, Signer, Tx, TxError, VerifySigError,
 };
 
 #[cfg(test)]
@@ -33,4 +33,111 @@ mod tests {
         let tx_from_bytes = Tx::decode(&tx_from_hex[..]).unwrap();
         assert_eq!(tx, tx_from_bytes);
     }
+
+    /// Check that the BLS signatures of a multisignature account are
+    /// aggregated into a single section that is verified with a single
+    /// pairing check, charged per aggregated signer.
+    #[test]
+    fn aggregate_signatures_round_trip() {
+        use namada_core::types::account::AccountPublicKeysMap;
+        use namada_core::types::address;
+        use namada_core::types::key::*;
+        use rand::thread_rng;
+
+        use crate::data::TxType;
+
+        let keys: Vec<common::SecretKey> = (0..3)
+            .map(|_| {
+                bls::SigScheme::generate(&mut thread_rng())
+                    .try_to_sk()
+                    .unwrap()
+            })
+            .collect();
+        let public_keys_map =
+            AccountPublicKeysMap::from_iter(keys.iter().map(RefTo::ref_to));
+        let owner = address::testing::established_address_1();
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
+        tx.sign_raw(keys, public_keys_map.clone(), Some(owner.clone()));
+        tx.aggregate_signatures(&owner, &public_keys_map);
+        assert!(matches!(
+            tx.sections.last(),
+            Some(Section::AggregateSignature(_))
+        ));
+        assert!(
+            !tx.sections
+                .iter()
+                .any(|section| matches!(section, Section::Signature(_)))
+        );
+
+        let mut verifications = 0;
+        let mut consumed_gas = 0;
+        tx.verify_signatures(
+            &[tx.raw_header_hash()],
+            public_keys_map,
+            &Some(owner),
+            3,
+            None,
+            |gas| {
+                verifications += 1;
+                consumed_gas += gas;
+                Ok(())
+            },
+        )
+        .expect("The aggregate signature should be valid");
+        assert_eq!(verifications, 1);
+        assert_eq!(
+            consumed_gas,
+            namada_gas::VERIFY_BLS_SIG_GAS
+                + 3 * namada_gas::VERIFY_BLS_AGGREGATE_SIG_GAS_PER_SIGNER
+        );
+    }
+
+    /// Check that the BLS signatures made separately by the public keys of a
+    /// multisignature account, such as by a signer holding a single key, are
+    /// aggregated too.
+    #[test]
+    fn aggregate_public_key_signatures() {
+        use namada_core::types::account::AccountPublicKeysMap;
+        use namada_core::types::address;
+        use namada_core::types::key::*;
+        use rand::thread_rng;
+
+        use crate::data::TxType;
+
+        let keys: Vec<common::SecretKey> = (0..2)
+            .map(|_| {
+                bls::SigScheme::generate(&mut thread_rng())
+                    .try_to_sk()
+                    .unwrap()
+            })
+            .collect();
+        let public_keys_map =
+            AccountPublicKeysMap::from_iter(keys.iter().map(RefTo::ref_to));
+        let owner = address::testing::established_address_1();
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new("wasm code".as_bytes().to_owned(), None));
+        // Sign the raw header with each key in reverse order
+        for key in keys.into_iter().rev() {
+            let public_keys = AccountPublicKeysMap::from_iter([key.ref_to()]);
+            tx.sign_raw(vec![key], public_keys, None);
+        }
+        tx.aggregate_signatures(&owner, &public_keys_map);
+        assert!(
+            !tx.sections
+                .iter()
+                .any(|section| matches!(section, Section::Signature(_)))
+        );
+        tx.verify_signatures(
+            &[tx.raw_header_hash()],
+            public_keys_map,
+            &Some(owner),
+            2,
+            None,
+            |_| Ok(()),
+        )
+        .expect("The aggregate signature should be valid");
+    }
 }
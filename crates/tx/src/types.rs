@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
@@ -14,9 +14,10 @@ use namada_core::borsh::schema::{add_definition, Declaration, Definition};
 use namada_core::borsh::{
     BorshDeserialize, BorshSchema, BorshSerialize, BorshSerializeExt,
 };
-use namada_core::types::account::AccountPublicKeysMap;
+use namada_core::types::account::{AccountPublicKeysMap, AccountWeights};
 use namada_core::types::address::Address;
 use namada_core::types::chain::ChainId;
+use namada_core::types::hash::Sha256Hasher;
 use namada_core::types::key::*;
 use namada_core::types::masp::AssetData;
 use namada_core::types::sign::SignatureIndex;
@@ -461,7 +462,7 @@ impl Signature {
         consume_verify_sig_gas: &mut F,
     ) -> std::result::Result<u8, VerifySigError>
     where
-        F: FnMut() -> std::result::Result<(), namada_gas::Error>,
+        F: FnMut(u64) -> std::result::Result<(), namada_gas::Error>,
     {
         // Records whether there are any successful verifications
         let mut verifications = 0;
@@ -473,7 +474,7 @@ impl Signature {
                     if let Some(pk) =
                         public_keys_index_map.get_public_key_from_index(*idx)
                     {
-                        consume_verify_sig_gas()?;
+                        consume_verify_sig_gas(verify_sig_gas(&pk))?;
                         common::SigScheme::verify_signature(
                             &pk,
                             &self.get_raw_hash(),
@@ -494,7 +495,7 @@ impl Signature {
                     if let Some(map_idx) =
                         public_keys_index_map.get_index_from_public_key(pk)
                     {
-                        consume_verify_sig_gas()?;
+                        consume_verify_sig_gas(verify_sig_gas(pk))?;
                         common::SigScheme::verify_signature(
                             pk,
                             &self.get_raw_hash(),
@@ -510,6 +511,157 @@ impl Signature {
     }
 }
 
+/// The gas cost of verifying a signature made by the given public key
+fn verify_sig_gas(pk: &common::PublicKey) -> u64 {
+    match pk {
+        common::PublicKey::Bls(_) => namada_gas::VERIFY_BLS_SIG_GAS,
+        _ => namada_gas::VERIFY_TX_SIG_GAS,
+    }
+}
+
+/// The gas cost of verifying a BLS aggregate signature of the given number of
+/// signers
+fn aggregate_verify_sig_gas(signers: usize) -> u64 {
+    namada_gas::VERIFY_BLS_AGGREGATE_SIG_GAS_PER_SIGNER
+        .saturating_mul(signers as u64)
+        .saturating_add(namada_gas::VERIFY_BLS_SIG_GAS)
+}
+
+/// A section representing the BLS signatures of several public keys of a
+/// multisignature account over another section, aggregated into a single
+/// signature
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct AggregateSignature {
+    /// The hash of the section being signed
+    pub targets: Vec<namada_core::types::hash::Hash>,
+    /// The address of the multisignature account
+    pub signer: Address,
+    /// The indices of the public keys whose signatures were aggregated
+    pub indices: BTreeSet<u8>,
+    /// The aggregate of the signatures over the above hash
+    pub signature: bls::Signature,
+}
+
+impl AggregateSignature {
+    /// Aggregate the given signatures made on behalf of a multisignature
+    /// account. Returns `None` unless there are at least two signatures and
+    /// they are all BLS signatures made by public keys of the account.
+    pub fn new(
+        targets: Vec<namada_core::types::hash::Hash>,
+        signer: Address,
+        signatures: &BTreeMap<u8, common::Signature>,
+        public_keys_index_map: &AccountPublicKeysMap,
+    ) -> Option<Self> {
+        if signatures.len() < 2 {
+            return None;
+        }
+        let indices = signatures.keys().copied().collect();
+        let signatures = signatures
+            .iter()
+            .map(|(idx, sig)| {
+                let pk = public_keys_index_map.get_public_key_from_index(*idx)?;
+                match (pk, sig) {
+                    (
+                        common::PublicKey::Bls(pk),
+                        common::Signature::Bls(sig),
+                    ) => Some((pk, *sig)),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            targets,
+            signer,
+            indices,
+            signature: bls::aggregate_signatures(&signatures),
+        })
+    }
+
+    /// Get the number of aggregated signatures. Returns `None` if it doesn't
+    /// fit in a `u8`, i.e. if all the 256 indices are aggregated.
+    pub fn total_signatures(&self) -> Option<u8> {
+        u8::try_from(self.indices.len()).ok()
+    }
+
+    /// Hash this signature section
+    pub fn hash<'a>(&self, hasher: &'a mut Sha256) -> &'a mut Sha256 {
+        hasher.update(self.serialize_to_vec());
+        hasher
+    }
+
+    /// Get the hash of this section
+    pub fn get_hash(&self) -> namada_core::types::hash::Hash {
+        namada_core::types::hash::Hash(
+            self.hash(&mut Sha256::new()).finalize_reset().into(),
+        )
+    }
+
+    /// Get the hash that the aggregated signatures were made over. It is the
+    /// same as that of a [`Signature`] section over the same targets.
+    pub fn get_raw_hash(&self) -> namada_core::types::hash::Hash {
+        Signature {
+            targets: self.targets.clone(),
+            signer: Signer::PubKeys(vec![]),
+            signatures: BTreeMap::new(),
+        }
+        .get_raw_hash()
+    }
+
+    /// Verify that the aggregate signature contained in this section is valid.
+    /// This costs a single pairing check plus the aggregation of the public
+    /// key of every signer.
+    pub fn verify_signature<F>(
+        &self,
+        verified_pks: &mut HashSet<u8>,
+        public_keys_index_map: &AccountPublicKeysMap,
+        signer: &Option<Address>,
+        consume_verify_sig_gas: &mut F,
+    ) -> std::result::Result<u8, VerifySigError>
+    where
+        F: FnMut(u64) -> std::result::Result<(), namada_gas::Error>,
+    {
+        // There is no efficient way to map the aggregated signatures to the
+        // given public keys if the account addresses do not match
+        if Some(&self.signer) != signer.as_ref() {
+            return Ok(0);
+        }
+        let mut pks = Vec::with_capacity(self.indices.len());
+        for idx in &self.indices {
+            match public_keys_index_map.get_public_key_from_index(*idx) {
+                Some(common::PublicKey::Bls(pk)) => pks.push(pk),
+                Some(_) => {
+                    return Err(VerifySigError::InvalidSectionSignature(
+                        "aggregated signature of a non-BLS key.".to_string(),
+                    ));
+                }
+                // The aggregate cannot be checked without all of its public
+                // keys
+                None => return Ok(0),
+            }
+        }
+        consume_verify_sig_gas(aggregate_verify_sig_gas(pks.len()))?;
+        bls::verify_aggregate_signature::<Sha256Hasher>(
+            &pks,
+            &self.get_raw_hash(),
+            &self.signature,
+        )?;
+        verified_pks.extend(self.indices.iter().copied());
+        self.total_signatures().ok_or_else(|| {
+            VerifySigError::InvalidSectionSignature(
+                "too many signatures.".to_string(),
+            )
+        })
+    }
+}
+
 /// A section representing a multisig over another section
 #[derive(
     Clone,
@@ -731,6 +883,8 @@ pub enum Section {
     MaspBuilder(MaspBuilder),
     /// Wrap a header with a section for the purposes of computing hashes
     Header(Header),
+    /// An aggregated multisig over other sections
+    AggregateSignature(AggregateSignature),
 }
 
 impl Section {
@@ -753,6 +907,7 @@ impl Section {
                 hasher
             }
             Self::Header(header) => header.hash(hasher),
+            Self::AggregateSignature(signature) => signature.hash(hasher),
         }
     }
 
@@ -1184,10 +1339,39 @@ impl Tx {
         signer: &Option<Address>,
         threshold: u8,
         max_signatures: Option<u8>,
+        consume_verify_sig_gas: F,
+    ) -> std::result::Result<Vec<&Signature>, VerifySigError>
+    where
+        F: FnMut(u64) -> std::result::Result<(), namada_gas::Error>,
+    {
+        // Every public key counts as a single signature
+        let weights = AccountWeights {
+            weights: BTreeMap::new(),
+            threshold: threshold.into(),
+        };
+        self.verify_weighted_signatures(
+            hashes,
+            public_keys_index_map,
+            signer,
+            &weights,
+            max_signatures,
+            consume_verify_sig_gas,
+        )
+    }
+
+    /// Verify that the section with the given hash has been signed by public
+    /// keys whose total weight reaches the weight threshold
+    pub fn verify_weighted_signatures<F>(
+        &self,
+        hashes: &[namada_core::types::hash::Hash],
+        public_keys_index_map: AccountPublicKeysMap,
+        signer: &Option<Address>,
+        weights: &AccountWeights,
+        max_signatures: Option<u8>,
         mut consume_verify_sig_gas: F,
     ) -> std::result::Result<Vec<&Signature>, VerifySigError>
     where
-        F: FnMut() -> std::result::Result<(), namada_gas::Error>,
+        F: FnMut(u64) -> std::result::Result<(), namada_gas::Error>,
     {
         let max_signatures = max_signatures.unwrap_or(u8::MAX);
         // Records the public key indices used in successful signatures
@@ -1231,7 +1415,47 @@ impl Tx {
                         witnesses.push(signatures);
                     }
                     // Short-circuit these checks if the threshold is exceeded
-                    if verified_pks.len() >= threshold.into() {
+                    if weights.total_weight(&verified_pks) >= weights.threshold
+                    {
+                        return Ok(witnesses);
+                    }
+                }
+            } else if let Section::AggregateSignature(signature) = section {
+                // The same checks apply to aggregated signatures, except that
+                // the whole aggregate is verified with a single pairing check
+                if hashes.iter().all(|x| {
+                    signature.targets.contains(x) || section.get_hash() == *x
+                }) && signature
+                    .targets
+                    .iter()
+                    .all(|x| self.get_section(x).is_some())
+                {
+                    match signature.total_signatures() {
+                        Some(total) if total <= max_signatures => {}
+                        _ => {
+                            return Err(
+                                VerifySigError::InvalidSectionSignature(
+                                    "too many signatures.".to_string(),
+                                ),
+                            );
+                        }
+                    }
+
+                    signature
+                        .verify_signature(
+                            &mut verified_pks,
+                            &public_keys_index_map,
+                            signer,
+                            &mut consume_verify_sig_gas,
+                        )
+                        .map_err(|_e| {
+                            VerifySigError::InvalidSectionSignature(
+                                "found invalid signature.".to_string(),
+                            )
+                        })?;
+                    // Short-circuit these checks if the threshold is exceeded
+                    if weights.total_weight(&verified_pks) >= weights.threshold
+                    {
                         return Ok(witnesses);
                     }
                 }
@@ -1239,8 +1463,8 @@ impl Tx {
         }
         Err(VerifySigError::InvalidSectionSignature(format!(
             "signature threshold not met: ({} < {})",
-            verified_pks.len(),
-            threshold
+            weights.total_weight(&verified_pks),
+            weights.threshold
         )))
     }
 
@@ -1260,7 +1484,7 @@ impl Tx {
             &None,
             1,
             None,
-            || Ok(()),
+            |_| Ok(()),
         )
         .map(|x| *x.first().unwrap())
         .map_err(|_| VerifySigError::InvalidWrapperSignature)
@@ -1526,6 +1750,69 @@ impl Tx {
         self
     }
 
+    /// Replace the signature sections made over the raw header on behalf of
+    /// the given multisignature account, or by public keys of that account, by
+    /// a single aggregate signature section, provided that they contain at
+    /// least two signatures and that these are all BLS signatures. Since this
+    /// changes the sections of the transaction, it must be done before the
+    /// wrapper is signed.
+    pub fn aggregate_signatures(
+        &mut self,
+        signer: &Address,
+        account_public_keys_map: &AccountPublicKeysMap,
+    ) -> &mut Self {
+        let targets = vec![self.raw_header_hash()];
+        let mut positions = Vec::new();
+        let mut signatures = BTreeMap::new();
+        for (pos, section) in self.sections.iter().enumerate() {
+            let Section::Signature(section) = section else {
+                continue;
+            };
+            if section.targets != targets {
+                continue;
+            }
+            let section_signatures = &section.signatures;
+            match &section.signer {
+                Signer::Address(addr) if addr == signer => {
+                    positions.push(pos);
+                    signatures.extend(section_signatures.clone());
+                }
+                Signer::Address(_) => {}
+                // The signatures made by public keys are indexed by their
+                // position in the section, so re-index them in the account
+                Signer::PubKeys(pks) => {
+                    let indexed = section_signatures
+                        .iter()
+                        .map(|(idx, sig)| {
+                            let pk = pks.get(*idx as usize)?;
+                            let map_idx = account_public_keys_map
+                                .get_index_from_public_key(pk)?;
+                            Some((map_idx, sig.clone()))
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(indexed) =
+                        indexed.filter(|indexed| !indexed.is_empty())
+                    {
+                        positions.push(pos);
+                        signatures.extend(indexed);
+                    }
+                }
+            }
+        }
+        if let Some(aggregate) = AggregateSignature::new(
+            targets,
+            signer.clone(),
+            &signatures,
+            account_public_keys_map,
+        ) {
+            for pos in positions.into_iter().rev() {
+                self.sections.remove(pos);
+            }
+            self.add_section(Section::AggregateSignature(aggregate));
+        }
+        self
+    }
+
     /// Add signatures
     pub fn add_signatures(
         &mut self,
@@ -13,6 +13,97 @@ pub fn init_account(
         &data.public_keys,
         data.threshold,
     )?;
+    if let Some(weights) = &data.weights {
+        namada_account::set_weights(ctx, owner, weights)?;
+    }
 
     Ok(owner.to_owned())
 }
+
+/// Set or remove the spending policy of an account. The account and the
+/// recovery key of its current policy, if any, have to authorize it. A change
+/// loosening the current policy has to be submitted again to be applied after
+/// the delay of the current policy.
+pub fn set_spending_policy(
+    ctx: &mut Ctx,
+    data: UpdateSpendingPolicy,
+) -> TxResult {
+    namada_account::update_spending_policy(ctx, &data.addr, data.policy)
+}
+
+/// Queue a withdrawal above the delay threshold of the source's spending
+/// policy.
+pub fn queue_token_withdrawal(
+    ctx: &mut Ctx,
+    data: QueueWithdrawal,
+) -> EnvResult<u64> {
+    let amount = token::denom_to_amount(data.amount, &data.token, ctx)?;
+    namada_account::queue_withdrawal(
+        ctx,
+        &data.source,
+        &data.token,
+        &data.target,
+        amount,
+    )
+}
+
+/// Execute a matured queued withdrawal.
+pub fn execute_queued_withdrawal(
+    ctx: &mut Ctx,
+    data: QueuedWithdrawalAction,
+) -> TxResult {
+    let withdrawal = namada_account::remove_queued_withdrawal(
+        ctx,
+        &data.source,
+        data.id,
+        true,
+    )?;
+    token::undenominated_transfer(
+        ctx,
+        &data.source,
+        &withdrawal.target,
+        &withdrawal.token,
+        withdrawal.amount,
+    )
+}
+
+/// Cancel a queued withdrawal. The account or its recovery key has to
+/// authorize it.
+pub fn cancel_queued_withdrawal(
+    ctx: &mut Ctx,
+    data: QueuedWithdrawalAction,
+) -> TxResult {
+    namada_account::remove_queued_withdrawal(ctx, &data.source, data.id, false)
+        .map(|_withdrawal| ())
+}
+
+/// Set or remove the guardian set of an account. The account has to authorize
+/// it.
+pub fn set_guardians(ctx: &mut Ctx, data: UpdateGuardians) -> TxResult {
+    namada_account::update_guardians(ctx, &data.addr, data.guardians)
+}
+
+/// Initiate a recovery of an account. A threshold of its guardians has to
+/// authorize it. Returns the epoch from which the recovery can be finalized.
+pub fn initiate_account_recovery(
+    ctx: &mut Ctx,
+    data: InitiateRecovery,
+) -> EnvResult<Epoch> {
+    namada_account::initiate_recovery(
+        ctx,
+        &data.addr,
+        data.public_keys,
+        data.threshold,
+    )
+}
+
+/// Cancel the pending recovery of an account. The account has to authorize
+/// it.
+pub fn cancel_account_recovery(ctx: &mut Ctx, owner: &Address) -> TxResult {
+    namada_account::cancel_recovery(ctx, owner)
+}
+
+/// Finalize the pending recovery of an account after its delay has passed.
+pub fn finalize_account_recovery(ctx: &mut Ctx, owner: &Address) -> TxResult {
+    namada_account::finalize_recovery(ctx, owner)
+}
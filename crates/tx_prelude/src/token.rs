@@ -1,3 +1,4 @@
+use namada_account::record_spending;
 use namada_core::types::address::Address;
 use namada_proof_of_stake::token::storage_key::{
     balance_key, minted_balance_key, minter_key,
@@ -5,7 +6,7 @@ use namada_proof_of_stake::token::storage_key::{
 use namada_storage::{Error as StorageError, ResultExt};
 pub use namada_token::*;
 
-use crate::{Ctx, StorageRead, StorageWrite, TxResult};
+use crate::{Ctx, StorageRead, StorageWrite, TxEnv, TxResult};
 
 #[allow(clippy::too_many_arguments)]
 /// A token transfer that can be used in a transaction.
@@ -18,6 +19,7 @@ pub fn transfer(
 ) -> TxResult {
     let amount = denom_to_amount(amount, token, ctx)?;
     if amount != Amount::default() && src != dest {
+        record_spending(ctx, src, token, amount)?;
         let src_key = balance_key(token, src);
         let dest_key = balance_key(token, dest);
         let src_bal: Option<Amount> = ctx.read(&src_key)?;
@@ -42,6 +44,7 @@ pub fn undenominated_transfer(
     amount: Amount,
 ) -> TxResult {
     if amount != Amount::default() && src != dest {
+        record_spending(ctx, src, token, amount)?;
         let src_key = balance_key(token, src);
         let dest_key = balance_key(token, dest);
         let src_bal: Option<Amount> = ctx.read(&src_key)?;
@@ -103,3 +106,83 @@ pub fn burn(
 
     Ok(())
 }
+
+/// Register a user-issued token that can be used in a transaction. The token
+/// account has to authorize the registration.
+pub fn register_user_token(
+    ctx: &mut Ctx,
+    registration: RegisterToken,
+) -> TxResult {
+    ctx.insert_verifier(&registration.token)?;
+    register_token(
+        ctx,
+        &registration.token,
+        registration.denom,
+        &registration.minter,
+        registration.max_supply,
+    )
+}
+
+/// Mint a user-issued token that can be used in a transaction. The registered
+/// minter of the token has to authorize the mint.
+pub fn mint_user_token(ctx: &mut Ctx, mint: MintTokens) -> TxResult {
+    if read_minter(ctx, &mint.token)?.as_ref() != Some(&mint.minter) {
+        return Err(StorageError::new_const(
+            "The given address is not the minter of the token",
+        ));
+    }
+    ctx.insert_verifier(&mint.minter)?;
+    let amount = denom_to_amount(mint.amount, &mint.token, ctx)?;
+    credit_tokens(ctx, &mint.token, &mint.target, amount)
+}
+
+/// Burn a user-issued token that can be used in a transaction. The registered
+/// minter of the token has to authorize the burn.
+pub fn burn_user_token(ctx: &mut Ctx, burn_data: BurnTokens) -> TxResult {
+    let minter = read_minter(ctx, &burn_data.token)?.ok_or_else(|| {
+        StorageError::new_const("The token has no registered minter")
+    })?;
+    ctx.insert_verifier(&minter)?;
+    let amount = denom_to_amount(burn_data.amount, &burn_data.token, ctx)?;
+    burn(ctx, &burn_data.owner, &burn_data.token, amount)
+}
+
+/// Update the metadata of a token that can be used in a transaction. The
+/// registered minter of the token, if any, has to authorize the update.
+pub fn update_metadata(ctx: &mut Ctx, update: UpdateTokenMetadata) -> TxResult {
+    if let Some(minter) = read_minter(ctx, &update.token)? {
+        ctx.insert_verifier(&minter)?;
+    }
+    write_metadata(ctx, &update.token, update.metadata)
+}
+
+/// Set the allowance of a spender over the owner's tokens that can be used in
+/// a transaction. The owner has to authorize it.
+pub fn approve(ctx: &mut Ctx, approval: Approve) -> TxResult {
+    let amount = denom_to_amount(approval.amount, &approval.token, ctx)?;
+    write_allowance(
+        ctx,
+        &approval.token,
+        &approval.owner,
+        &approval.spender,
+        Allowance {
+            amount,
+            expiry: approval.expiry,
+        },
+    )
+}
+
+/// A transfer from the source's account within the allowance of the spender
+/// that can be used in a transaction. The spender has to authorize it.
+pub fn spend_allowance(ctx: &mut Ctx, transfer: TransferFrom) -> TxResult {
+    let amount = denom_to_amount(transfer.amount, &transfer.token, ctx)?;
+    record_spending(ctx, &transfer.source, &transfer.token, amount)?;
+    transfer_from(
+        ctx,
+        &transfer.token,
+        &transfer.spender,
+        &transfer.source,
+        &transfer.target,
+        amount,
+    )
+}
@@ -249,6 +249,20 @@ pub mod vp {
             max_signatures_len: u64,
         ) -> i64;
 
+        // Verify the signatures of a tx against the weights of the keys
+        pub fn namada_vp_verify_tx_section_weighted_signature(
+            hash_list_ptr: u64,
+            hash_list_len: u64,
+            public_keys_map_ptr: u64,
+            public_keys_map_len: u64,
+            signer_ptr: u64,
+            signer_len: u64,
+            weights_ptr: u64,
+            weights_len: u64,
+            max_signatures_ptr: u64,
+            max_signatures_len: u64,
+        ) -> i64;
+
         pub fn namada_vp_eval(
             vp_code_hash_ptr: u64,
             vp_code_hash_len: u64,
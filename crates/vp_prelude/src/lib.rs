@@ -74,28 +74,91 @@ pub fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
     let public_keys_index_map =
         account::public_keys_index_map(&ctx.pre(), owner)?;
     let threshold = account::threshold(&ctx.pre(), owner)?.unwrap_or(1);
+    let weights = account::weights(&ctx.pre(), owner)?;
+
+    Ok(verify_section_signatures(
+        tx,
+        &public_keys_index_map,
+        owner,
+        threshold,
+        weights,
+        max_signatures_per_transaction,
+    ))
+}
+
+/// Verify that the tx is signed by the given public key, e.g. the recovery key
+/// of an account. The signature has to be made on behalf of the implicit
+/// address of the key.
+pub fn verify_signature_of_key(
+    ctx: &Ctx,
+    tx: &Tx,
+    public_key: &key::common::PublicKey,
+) -> VpResult {
+    let max_signatures_per_transaction =
+        parameters::max_signatures_per_transaction(&ctx.pre())?;
 
+    let public_keys_index_map =
+        account::AccountPublicKeysMap::from_iter([public_key.clone()]);
+    let signer = Address::from(public_key);
+
+    Ok(verify_section_signatures(
+        tx,
+        &public_keys_index_map,
+        &signer,
+        1,
+        None,
+        max_signatures_per_transaction,
+    ))
+}
+
+fn verify_section_signatures(
+    tx: &Tx,
+    public_keys_index_map: &account::AccountPublicKeysMap,
+    signer: &Address,
+    threshold: u8,
+    weights: Option<account::AccountWeights>,
+    max_signatures_per_transaction: Option<u8>,
+) -> bool {
     // Serialize parameters
     let max_signatures = max_signatures_per_transaction.serialize_to_vec();
     let public_keys_map = public_keys_index_map.serialize_to_vec();
     let targets = [tx.raw_header_hash()].serialize_to_vec();
-    let signer = owner.serialize_to_vec();
-
-    let valid = unsafe {
-        namada_vp_verify_tx_section_signature(
-            targets.as_ptr() as _,
-            targets.len() as _,
-            public_keys_map.as_ptr() as _,
-            public_keys_map.len() as _,
-            signer.as_ptr() as _,
-            signer.len() as _,
-            threshold,
-            max_signatures.as_ptr() as _,
-            max_signatures.len() as _,
-        )
+    let signer = signer.serialize_to_vec();
+
+    let valid = match weights {
+        Some(weights) => {
+            let weights = weights.serialize_to_vec();
+            unsafe {
+                namada_vp_verify_tx_section_weighted_signature(
+                    targets.as_ptr() as _,
+                    targets.len() as _,
+                    public_keys_map.as_ptr() as _,
+                    public_keys_map.len() as _,
+                    signer.as_ptr() as _,
+                    signer.len() as _,
+                    weights.as_ptr() as _,
+                    weights.len() as _,
+                    max_signatures.as_ptr() as _,
+                    max_signatures.len() as _,
+                )
+            }
+        }
+        None => unsafe {
+            namada_vp_verify_tx_section_signature(
+                targets.as_ptr() as _,
+                targets.len() as _,
+                public_keys_map.as_ptr() as _,
+                public_keys_map.len() as _,
+                signer.as_ptr() as _,
+                signer.len() as _,
+                threshold,
+                max_signatures.as_ptr() as _,
+                max_signatures.len() as _,
+            )
+        },
     };
 
-    Ok(HostEnvResult::is_success(valid))
+    HostEnvResult::is_success(valid)
 }
 
 /// Format and log a string in a debug build.
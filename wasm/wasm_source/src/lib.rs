@@ -1,9 +1,17 @@
+#[cfg(feature = "tx_approve")]
+pub mod tx_approve;
 #[cfg(feature = "tx_become_validator")]
 pub mod tx_become_validator;
 #[cfg(feature = "tx_bond")]
 pub mod tx_bond;
 #[cfg(feature = "tx_bridge_pool")]
 pub mod tx_bridge_pool;
+#[cfg(feature = "tx_burn_tokens")]
+pub mod tx_burn_tokens;
+#[cfg(feature = "tx_cancel_recovery")]
+pub mod tx_cancel_recovery;
+#[cfg(feature = "tx_cancel_withdrawal")]
+pub mod tx_cancel_withdrawal;
 #[cfg(feature = "tx_change_consensus_key")]
 pub mod tx_change_consensus_key;
 #[cfg(feature = "tx_change_validator_commission")]
@@ -14,30 +22,50 @@ pub mod tx_change_validator_metadata;
 pub mod tx_claim_rewards;
 #[cfg(feature = "tx_deactivate_validator")]
 pub mod tx_deactivate_validator;
+#[cfg(feature = "tx_execute_withdrawal")]
+pub mod tx_execute_withdrawal;
+#[cfg(feature = "tx_finalize_recovery")]
+pub mod tx_finalize_recovery;
 #[cfg(feature = "tx_ibc")]
 pub mod tx_ibc;
 #[cfg(feature = "tx_init_account")]
 pub mod tx_init_account;
 #[cfg(feature = "tx_init_proposal")]
 pub mod tx_init_proposal;
+#[cfg(feature = "tx_initiate_recovery")]
+pub mod tx_initiate_recovery;
+#[cfg(feature = "tx_mint_tokens")]
+pub mod tx_mint_tokens;
+#[cfg(feature = "tx_queue_withdrawal")]
+pub mod tx_queue_withdrawal;
 #[cfg(feature = "tx_reactivate_validator")]
 pub mod tx_reactivate_validator;
 #[cfg(feature = "tx_redelegate")]
 pub mod tx_redelegate;
+#[cfg(feature = "tx_register_token")]
+pub mod tx_register_token;
 #[cfg(feature = "tx_resign_steward")]
 pub mod tx_resign_steward;
 #[cfg(feature = "tx_reveal_pk")]
 pub mod tx_reveal_pk;
 #[cfg(feature = "tx_transfer")]
 pub mod tx_transfer;
+#[cfg(feature = "tx_transfer_from")]
+pub mod tx_transfer_from;
 #[cfg(feature = "tx_unbond")]
 pub mod tx_unbond;
 #[cfg(feature = "tx_unjail_validator")]
 pub mod tx_unjail_validator;
 #[cfg(feature = "tx_update_account")]
 pub mod tx_update_account;
+#[cfg(feature = "tx_update_guardians")]
+pub mod tx_update_guardians;
+#[cfg(feature = "tx_update_spending_policy")]
+pub mod tx_update_spending_policy;
 #[cfg(feature = "tx_update_steward_commission")]
 pub mod tx_update_steward_commission;
+#[cfg(feature = "tx_update_token_metadata")]
+pub mod tx_update_token_metadata;
 #[cfg(feature = "tx_vote_proposal")]
 pub mod tx_vote_proposal;
 #[cfg(feature = "tx_withdraw")]
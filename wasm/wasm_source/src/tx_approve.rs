@@ -0,0 +1,17 @@
+//! A tx to set the allowance of a spender over the owner's tokens.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1000000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let approval = token::Approve::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::Approve")?;
+    debug_log!("apply_tx called with approval: {:#?}", approval);
+
+    token::approve(ctx, approval)
+}
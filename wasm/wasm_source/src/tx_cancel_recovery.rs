@@ -0,0 +1,17 @@
+//! A tx to cancel the pending recovery of an account.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 920000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let owner = Address::try_from_slice(&data[..])
+        .wrap_err("failed to decode Address")?;
+    debug_log!("apply_tx called to cancel recovery of: {}", owner);
+
+    account::cancel_account_recovery(ctx, &owner)
+}
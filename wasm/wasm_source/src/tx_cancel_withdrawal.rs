@@ -0,0 +1,17 @@
+//! A tx to cancel a queued withdrawal.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1000000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let data = account::QueuedWithdrawalAction::try_from_slice(&data[..])
+        .wrap_err("failed to decode QueuedWithdrawalAction")?;
+    debug_log!("apply_tx called with data: {:#?}", data);
+
+    account::cancel_queued_withdrawal(ctx, data)
+}
@@ -0,0 +1,19 @@
+//! A tx for the guardians of an account to initiate its recovery.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1000000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let data = account::InitiateRecovery::try_from_slice(&data[..])
+        .wrap_err("failed to decode InitiateRecovery")?;
+    debug_log!("apply_tx called with data: {:#?}", data);
+
+    let unlock_epoch = account::initiate_account_recovery(ctx, data)?;
+    debug_log!("recovery can be finalized from epoch {}", unlock_epoch);
+    Ok(())
+}
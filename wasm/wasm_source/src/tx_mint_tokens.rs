@@ -0,0 +1,17 @@
+//! A tx to mint a user-issued token.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1750000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let mint = token::MintTokens::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::MintTokens")?;
+    debug_log!("apply_tx called with mint: {:#?}", mint);
+
+    token::mint_user_token(ctx, mint)
+}
@@ -0,0 +1,17 @@
+//! A tx to queue a withdrawal above the delay threshold of an account.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1150000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let data = account::QueueWithdrawal::try_from_slice(&data[..])
+        .wrap_err("failed to decode QueueWithdrawal")?;
+    debug_log!("apply_tx called with data: {:#?}", data);
+
+    account::queue_token_withdrawal(ctx, data).map(|_id| ())
+}
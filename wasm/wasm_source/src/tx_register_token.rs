@@ -0,0 +1,17 @@
+//! A tx to register a user-issued token.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1060000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let registration = token::RegisterToken::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::RegisterToken")?;
+    debug_log!("apply_tx called to register a token: {:#?}", registration);
+
+    token::register_user_token(ctx, registration)
+}
@@ -0,0 +1,18 @@
+//! A tx for a spender to transfer tokens from an owner's account within its
+//! allowance.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 2100000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let transfer = token::TransferFrom::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::TransferFrom")?;
+    debug_log!("apply_tx called with transfer: {:#?}", transfer);
+
+    token::spend_allowance(ctx, transfer)
+}
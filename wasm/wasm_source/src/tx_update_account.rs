@@ -32,11 +32,16 @@ fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
                 err
             })?;
 
-        ctx.update_validity_predicate(
-            owner,
-            vp_code_sec.code.hash(),
-            &vp_code_sec.tag,
-        )?;
+        // The VP of an account with a spending policy can only be replaced
+        // after the delay of the policy
+        let vp_code_hash = vp_code_sec.code.hash();
+        if account::queue_vp_update(ctx, owner, vp_code_hash)? {
+            ctx.update_validity_predicate(
+                owner,
+                vp_code_hash,
+                &vp_code_sec.tag,
+            )?;
+        }
     }
 
     if let Some(threshold) = tx_data.threshold {
@@ -56,5 +61,12 @@ fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
         }
     }
 
+    if let Some(weights) = &tx_data.weights {
+        account::set_weights(ctx, owner, weights)?;
+    } else if !tx_data.public_keys.is_empty() {
+        // The weights of the replaced public keys no longer apply
+        account::clear_weights(ctx, owner)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,17 @@
+//! A tx to set or remove the guardian set of an account.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 900000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let data = account::UpdateGuardians::try_from_slice(&data[..])
+        .wrap_err("failed to decode UpdateGuardians")?;
+    debug_log!("apply_tx called with data: {:#?}", data);
+
+    account::set_guardians(ctx, data)
+}
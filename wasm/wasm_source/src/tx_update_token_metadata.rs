@@ -0,0 +1,17 @@
+//! A tx to update the metadata of a token.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 980000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let update = token::UpdateTokenMetadata::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::UpdateTokenMetadata")?;
+    debug_log!("apply_tx called to update token metadata: {:#?}", update);
+
+    token::update_metadata(ctx, update)
+}
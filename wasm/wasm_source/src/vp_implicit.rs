@@ -12,6 +12,7 @@
 //! Any other storage key changes are allowed only with a valid signature.
 
 use core::ops::Deref;
+use std::collections::BTreeMap;
 
 use namada_vp_prelude::*;
 use once_cell::unsync::Lazy;
@@ -20,10 +21,19 @@ enum KeyType<'a> {
     /// Public key - written once revealed
     Pk(&'a Address),
     TokenBalance {
+        token: &'a Address,
         owner: &'a Address,
     },
-    TokenMinted,
+    TokenMinted(&'a Address),
     TokenMinter(&'a Address),
+    TokenMaxSupply(&'a Address),
+    TokenAllowance {
+        owner: &'a Address,
+        spender: &'a Address,
+    },
+    SpendingPolicy(&'a Address),
+    EpochSpending(&'a Address),
+    QueuedWithdrawals(&'a Address),
     PoS,
     Masp,
     PgfSteward(&'a Address),
@@ -36,15 +46,31 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
         if let Some(address) = account::is_pks_key(key) {
             Self::Pk(address)
-        } else if let Some([_, owner]) =
+        } else if let Some([token, owner]) =
             token::storage_key::is_any_token_balance_key(key)
         {
-            Self::TokenBalance { owner }
-        } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
-            Self::TokenMinted
+            Self::TokenBalance { token, owner }
+        } else if let Some(token) =
+            token::storage_key::is_any_minted_balance_key(key)
+        {
+            Self::TokenMinted(token)
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
         {
             Self::TokenMinter(minter)
+        } else if let Some(token) =
+            token::storage_key::is_any_max_supply_key(key)
+        {
+            Self::TokenMaxSupply(token)
+        } else if let Some([_, owner, spender]) =
+            token::storage_key::is_any_allowance_key(key)
+        {
+            Self::TokenAllowance { owner, spender }
+        } else if let Some(owner) = account::is_spending_policy_key(key) {
+            Self::SpendingPolicy(owner)
+        } else if let Some(owner) = account::is_epoch_spending_key(key) {
+            Self::EpochSpending(owner)
+        } else if let Some(owner) = account::is_queued_withdrawals_key(key) {
+            Self::QueuedWithdrawals(owner)
         } else if proof_of_stake::storage_key::is_pos_key(key) {
             Self::PoS
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
@@ -84,6 +110,18 @@ fn validate_tx(
     let valid_sig = Lazy::new(|| {
         matches!(verify_signatures(ctx, &tx_data, &addr), Ok(true))
     });
+    let policy = account::spending_policy(&ctx.pre(), &addr)?;
+    let recovery_key = policy
+        .as_ref()
+        .and_then(|policy| policy.recovery_key.as_ref());
+    let valid_recovery_sig = Lazy::new(|| {
+        recovery_key.map_or(false, |recovery_key| {
+            matches!(
+                verify_signature_of_key(ctx, &tx_data, recovery_key),
+                Ok(true)
+            )
+        })
+    });
 
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
@@ -113,15 +151,33 @@ fn validate_tx(
                 }
                 true
             }
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key)?.unwrap_or_default();
                     let post: token::Amount =
                         ctx.read_post(key)?.unwrap_or_default();
                     let change = post.change() - pre.change();
-                    // debit has to signed, credit doesn't
-                    let valid = change.non_negative() || *valid_sig;
+                    let debit = pre.checked_sub(post).unwrap_or_default();
+                    // debit has to signed, credit doesn't, unless the debit
+                    // is covered by allowances spent in this tx. Debits are
+                    // also restricted by the account's spending policy.
+                    let valid = change.non_negative()
+                        || ((*valid_sig
+                            || is_debit_within_allowances(
+                                ctx,
+                                &keys_changed,
+                                token,
+                                &addr,
+                                debit,
+                            )?)
+                            && is_debit_within_policy(
+                                ctx,
+                                policy.as_ref(),
+                                token,
+                                &addr,
+                                debit,
+                            )?);
                     let sign = if change.non_negative() { "" } else { "-" };
                     debug_log!(
                         "token key: {}, change: {}{:?}, valid_sig: {}, valid \
@@ -145,8 +201,49 @@ fn validate_tx(
                     true
                 }
             }
-            KeyType::TokenMinted => verifiers.contains(&address::MULTITOKEN),
+            KeyType::TokenMinted(token) => {
+                // Mints and burns of a token whose minter is this address
+                // have to be signed
+                let minter: Option<Address> =
+                    ctx.read_post(&token::storage_key::minter_key(token))?;
+                verifiers.contains(&address::MULTITOKEN)
+                    && (minter.as_ref() != Some(&addr) || *valid_sig)
+            }
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
+            KeyType::TokenMaxSupply(token) => token != &addr || *valid_sig,
+            KeyType::TokenAllowance { owner, spender } => {
+                let pre: Option<token::Allowance> = ctx.read_pre(key)?;
+                let post: Option<token::Allowance> = ctx.read_post(key)?;
+                let is_spend = matches!(
+                    (pre, post),
+                    (Some(pre), Some(post)) if pre.spent(&post).is_some()
+                );
+                // Allowances are spent with the spender's signature, any
+                // other change has to be signed by the owner
+                (owner != &addr || is_spend || *valid_sig)
+                    && (spender != &addr || !is_spend || *valid_sig)
+            }
+            KeyType::SpendingPolicy(owner) => {
+                // When the account has a recovery key, it has to authorize
+                // policy changes as well
+                owner != &addr
+                    || (*valid_sig
+                        && (recovery_key.is_none() || *valid_recovery_sig))
+            }
+            KeyType::EpochSpending(owner) => {
+                owner != &addr
+                    || validate_epoch_spending(ctx, key, &addr, &valid_sig)?
+            }
+            KeyType::QueuedWithdrawals(owner) => {
+                owner != &addr
+                    || validate_queued_withdrawals(
+                        ctx,
+                        key,
+                        policy.as_ref(),
+                        &valid_sig,
+                        &valid_recovery_sig,
+                    )?
+            }
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
@@ -165,6 +262,182 @@ fn validate_tx(
     accept()
 }
 
+/// Check if a debit of the token from the owner respects its spending policy,
+/// if any. A debit limited per epoch has to be recorded in the epoch spending
+/// of the owner and a debit above the delay threshold has to execute matured
+/// queued withdrawals.
+fn is_debit_within_policy(
+    ctx: &Ctx,
+    policy: Option<&account::SpendingPolicy>,
+    token: &Address,
+    owner: &Address,
+    debit: token::Amount,
+) -> VpResult {
+    let policy = match policy {
+        Some(policy) => policy,
+        None => return accept(),
+    };
+    let epoch = ctx.get_block_epoch()?;
+
+    if let Some(limit) = policy.epoch_limits.get(token) {
+        let key = account::epoch_spending_key(owner);
+        let spent = |spending: BTreeMap<Address, account::EpochSpending>| {
+            spending
+                .get(token)
+                .filter(|spending| spending.epoch == epoch)
+                .map(|spending| spending.amount)
+                .unwrap_or_default()
+        };
+        let pre = spent(ctx.read_pre(&key)?.unwrap_or_default());
+        let post = spent(ctx.read_post(&key)?.unwrap_or_default());
+        let is_recorded = post
+            .checked_sub(pre)
+            .map_or(false, |recorded| recorded >= debit);
+        if post > *limit || !is_recorded {
+            return reject();
+        }
+    }
+
+    match policy.delay_thresholds.get(token) {
+        Some(threshold) if debit > *threshold => {
+            let key = account::queued_withdrawals_key(owner);
+            let pre: BTreeMap<u64, account::QueuedWithdrawal> =
+                ctx.read_pre(&key)?.unwrap_or_default();
+            let post: BTreeMap<u64, account::QueuedWithdrawal> =
+                ctx.read_post(&key)?.unwrap_or_default();
+            let mut executed = token::Amount::zero();
+            for (id, withdrawal) in pre {
+                if withdrawal.token == *token
+                    && withdrawal.unlock_epoch <= epoch
+                    && !post.contains_key(&id)
+                {
+                    executed = match executed.checked_add(withdrawal.amount) {
+                        Some(executed) => executed,
+                        None => return reject(),
+                    };
+                }
+            }
+            Ok(debit <= executed)
+        }
+        _ => accept(),
+    }
+}
+
+/// Validate a change of the amounts debited from the owner in the current
+/// epoch. The amounts can only grow within the current epoch, either with the
+/// owner's signature or by the amount of a debit in this tx.
+fn validate_epoch_spending(
+    ctx: &Ctx,
+    key: &storage::Key,
+    owner: &Address,
+    valid_sig: &impl Deref<Target = bool>,
+) -> VpResult {
+    let epoch = ctx.get_block_epoch()?;
+    let pre: BTreeMap<Address, account::EpochSpending> =
+        ctx.read_pre(key)?.unwrap_or_default();
+    let post: BTreeMap<Address, account::EpochSpending> =
+        ctx.read_post(key)?.unwrap_or_default();
+
+    for (token, spending) in &post {
+        let pre_spending = pre.get(token);
+        if pre_spending == Some(spending) {
+            continue;
+        }
+        let spent = pre_spending
+            .filter(|spending| spending.epoch == epoch)
+            .map(|spending| spending.amount)
+            .unwrap_or_default();
+        let recorded = match spending.amount.checked_sub(spent) {
+            Some(recorded) if spending.epoch == epoch => recorded,
+            _ => return reject(),
+        };
+        if !**valid_sig {
+            let balance_key = token::storage_key::balance_key(token, owner);
+            let pre: token::Amount =
+                ctx.read_pre(&balance_key)?.unwrap_or_default();
+            let post: token::Amount =
+                ctx.read_post(&balance_key)?.unwrap_or_default();
+            if pre.checked_sub(post).unwrap_or_default() < recorded {
+                return reject();
+            }
+        }
+    }
+    // Only the amounts debited in past epochs can be removed
+    Ok(pre.iter().all(|(token, spending)| {
+        spending.epoch < epoch || post.contains_key(token)
+    }))
+}
+
+/// Validate a change of the queued withdrawals of the owner. New withdrawals
+/// have to be signed and delayed according to the owner's spending policy and
+/// withdrawals can be removed with the owner's signature, when executed, or
+/// with the recovery key's signature, when cancelled.
+fn validate_queued_withdrawals(
+    ctx: &Ctx,
+    key: &storage::Key,
+    policy: Option<&account::SpendingPolicy>,
+    valid_sig: &impl Deref<Target = bool>,
+    valid_recovery_sig: &impl Deref<Target = bool>,
+) -> VpResult {
+    let pre: BTreeMap<u64, account::QueuedWithdrawal> =
+        ctx.read_pre(key)?.unwrap_or_default();
+    let post: BTreeMap<u64, account::QueuedWithdrawal> =
+        ctx.read_post(key)?.unwrap_or_default();
+
+    for (id, withdrawal) in &post {
+        match pre.get(id) {
+            Some(pre_withdrawal) if pre_withdrawal == withdrawal => {}
+            // Queued withdrawals cannot be modified
+            Some(_) => return reject(),
+            None => {
+                let delay = match policy {
+                    Some(policy) => policy.delay,
+                    None => return reject(),
+                };
+                let min_unlock_epoch = ctx.get_block_epoch()? + delay;
+                if !**valid_sig || withdrawal.unlock_epoch < min_unlock_epoch {
+                    return reject();
+                }
+            }
+        }
+    }
+    let is_removed = pre.keys().any(|id| !post.contains_key(id));
+    Ok(!is_removed || **valid_sig || **valid_recovery_sig)
+}
+
+/// Check if a debit of the token from the owner is covered by the
+/// allowances spent in the same tx. The validity of the spends themselves is
+/// checked by the multitoken VP.
+fn is_debit_within_allowances(
+    ctx: &Ctx,
+    keys_changed: &BTreeSet<storage::Key>,
+    token: &Address,
+    owner: &Address,
+    debit: token::Amount,
+) -> VpResult {
+    let mut spent = token::Amount::zero();
+    for key in keys_changed {
+        let is_owner_allowance = token::storage_key::is_any_allowance_key(key)
+            .map_or(false, |[allowance_token, allowance_owner, _]| {
+                allowance_token == token && allowance_owner == owner
+            });
+        if !is_owner_allowance {
+            continue;
+        }
+        let pre: Option<token::Allowance> = ctx.read_pre(key)?;
+        let post: Option<token::Allowance> = ctx.read_post(key)?;
+        if let Some(amount) =
+            pre.zip(post).and_then(|(pre, post)| pre.spent(&post))
+        {
+            spent = match spent.checked_add(amount) {
+                Some(spent) => spent,
+                None => return reject(),
+            };
+        }
+    }
+    Ok(debit <= spent)
+}
+
 fn validate_pos_changes(
     ctx: &Ctx,
     owner: &Address,
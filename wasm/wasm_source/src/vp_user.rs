@@ -12,6 +12,7 @@
 //! Any other storage key changes are allowed only with a valid signature.
 
 use core::ops::Deref;
+use std::collections::BTreeMap;
 
 use namada_vp_prelude::*;
 use once_cell::unsync::Lazy;
@@ -31,9 +32,25 @@ use proof_of_stake::storage_key::{
 };
 
 enum KeyType<'a> {
-    TokenBalance { owner: &'a Address },
-    TokenMinted,
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
+    TokenMinted(&'a Address),
     TokenMinter(&'a Address),
+    TokenMaxSupply(&'a Address),
+    TokenAllowance {
+        owner: &'a Address,
+        spender: &'a Address,
+    },
+    SpendingPolicy(&'a Address),
+    PendingSpendingPolicy(&'a Address),
+    PendingVpUpdate(&'a Address),
+    EpochSpending(&'a Address),
+    QueuedWithdrawals(&'a Address),
+    AccountKeys(&'a Address),
+    Guardians(&'a Address),
+    PendingRecovery(&'a Address),
     PoS,
     Vp(&'a Address),
     Masp,
@@ -45,15 +62,46 @@ enum KeyType<'a> {
 
 impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
-        if let Some([_, owner]) =
+        if let Some([token, owner]) =
             token::storage_key::is_any_token_balance_key(key)
         {
-            Self::TokenBalance { owner }
-        } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
-            Self::TokenMinted
+            Self::TokenBalance { token, owner }
+        } else if let Some(token) =
+            token::storage_key::is_any_minted_balance_key(key)
+        {
+            Self::TokenMinted(token)
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
         {
             Self::TokenMinter(minter)
+        } else if let Some(token) =
+            token::storage_key::is_any_max_supply_key(key)
+        {
+            Self::TokenMaxSupply(token)
+        } else if let Some([_, owner, spender]) =
+            token::storage_key::is_any_allowance_key(key)
+        {
+            Self::TokenAllowance { owner, spender }
+        } else if let Some(owner) = account::is_spending_policy_key(key) {
+            Self::SpendingPolicy(owner)
+        } else if let Some(owner) = account::is_pending_spending_policy_key(key)
+        {
+            Self::PendingSpendingPolicy(owner)
+        } else if let Some(owner) = account::is_pending_vp_update_key(key) {
+            Self::PendingVpUpdate(owner)
+        } else if let Some(owner) = account::is_epoch_spending_key(key) {
+            Self::EpochSpending(owner)
+        } else if let Some(owner) = account::is_queued_withdrawals_key(key) {
+            Self::QueuedWithdrawals(owner)
+        } else if let Some(owner) = account::is_pks_key(key)
+            .or_else(|| account::is_threshold_key(key))
+            .or_else(|| account::is_weights_key(key))
+            .or_else(|| account::is_weight_threshold_key(key))
+        {
+            Self::AccountKeys(owner)
+        } else if let Some(owner) = account::is_guardians_key(key) {
+            Self::Guardians(owner)
+        } else if let Some(owner) = account::is_pending_recovery_key(key) {
+            Self::PendingRecovery(owner)
         } else if is_pos_key(key) {
             Self::PoS
         } else if gov_storage::keys::is_vote_key(key) {
@@ -95,19 +143,56 @@ fn validate_tx(
     let valid_sig = Lazy::new(|| {
         matches!(verify_signatures(ctx, &tx_data, &addr), Ok(true))
     });
+    let policy = account::spending_policy(&ctx.pre(), &addr)?;
+    let recovery_key = policy
+        .as_ref()
+        .and_then(|policy| policy.recovery_key.as_ref());
+    let valid_recovery_sig = Lazy::new(|| {
+        recovery_key.map_or(false, |recovery_key| {
+            matches!(
+                verify_signature_of_key(ctx, &tx_data, recovery_key),
+                Ok(true)
+            )
+        })
+    });
+    let recovery = account::pending_recovery(&ctx.pre(), &addr)?;
+    let is_finalizing_recovery = Lazy::new(|| {
+        matches!(
+            is_recovery_finalized(ctx, &addr, recovery.as_ref()),
+            Ok(true)
+        )
+    });
 
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
         let is_valid = match key_type {
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key)?.unwrap_or_default();
                     let post: token::Amount =
                         ctx.read_post(key)?.unwrap_or_default();
                     let change = post.change() - pre.change();
-                    // debit has to signed, credit doesn't
-                    let valid = change.non_negative() || *valid_sig;
+                    let debit = pre.checked_sub(post).unwrap_or_default();
+                    // debit has to signed, credit doesn't, unless the debit
+                    // is covered by allowances spent in this tx. Debits are
+                    // also restricted by the account's spending policy.
+                    let valid = change.non_negative()
+                        || ((*valid_sig
+                            || is_debit_within_allowances(
+                                ctx,
+                                &keys_changed,
+                                token,
+                                &addr,
+                                debit,
+                            )?)
+                            && is_debit_within_policy(
+                                ctx,
+                                policy.as_ref(),
+                                token,
+                                &addr,
+                                debit,
+                            )?);
                     debug_log!(
                         "token key: {}, change: {:?}, valid_sig: {}, valid \
                          modification: {}",
@@ -129,15 +214,109 @@ fn validate_tx(
                     true
                 }
             }
-            KeyType::TokenMinted => verifiers.contains(&address::MULTITOKEN),
+            KeyType::TokenMinted(token) => {
+                // Mints and burns of a token whose minter is this address
+                // have to be signed
+                let minter: Option<Address> =
+                    ctx.read_post(&token::storage_key::minter_key(token))?;
+                verifiers.contains(&address::MULTITOKEN)
+                    && (minter.as_ref() != Some(&addr) || *valid_sig)
+            }
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
+            KeyType::TokenMaxSupply(token) => token != &addr || *valid_sig,
+            KeyType::TokenAllowance { owner, spender } => {
+                let pre: Option<token::Allowance> = ctx.read_pre(key)?;
+                let post: Option<token::Allowance> = ctx.read_post(key)?;
+                let is_spend = matches!(
+                    (pre, post),
+                    (Some(pre), Some(post)) if pre.spent(&post).is_some()
+                );
+                // Allowances are spent with the spender's signature, any
+                // other change has to be signed by the owner
+                (owner != &addr || is_spend || *valid_sig)
+                    && (spender != &addr || !is_spend || *valid_sig)
+            }
+            KeyType::SpendingPolicy(owner) => {
+                // When the account has a recovery key, it has to authorize
+                // policy changes as well. Loosening changes have to be
+                // delayed.
+                owner != &addr
+                    || (*valid_sig
+                        && (recovery_key.is_none() || *valid_recovery_sig)
+                        && validate_spending_policy(
+                            ctx,
+                            &addr,
+                            policy.as_ref(),
+                        )?)
+            }
+            KeyType::PendingSpendingPolicy(owner) => {
+                owner != &addr
+                    || (*valid_sig
+                        && (recovery_key.is_none() || *valid_recovery_sig)
+                        && validate_pending_spending_policy(
+                            ctx,
+                            key,
+                            policy.as_ref(),
+                        )?)
+            }
+            KeyType::PendingVpUpdate(owner) => {
+                owner != &addr
+                    || (*valid_sig
+                        && (recovery_key.is_none() || *valid_recovery_sig)
+                        && validate_pending_vp_update(
+                            ctx,
+                            key,
+                            policy.as_ref(),
+                        )?)
+            }
+            KeyType::EpochSpending(owner) => {
+                owner != &addr
+                    || validate_epoch_spending(ctx, key, &addr, &valid_sig)?
+            }
+            KeyType::QueuedWithdrawals(owner) => {
+                owner != &addr
+                    || validate_queued_withdrawals(
+                        ctx,
+                        key,
+                        policy.as_ref(),
+                        &valid_sig,
+                        &valid_recovery_sig,
+                    )?
+            }
+            KeyType::AccountKeys(owner) => {
+                // The keys of the account can also be replaced by finalizing
+                // a recovery initiated by its guardians. The weights of the
+                // keys must remain valid for the new keys.
+                owner != &addr
+                    || ((*valid_sig || *is_finalizing_recovery)
+                        && are_account_weights_valid(ctx, &addr)?)
+            }
+            KeyType::Guardians(owner) => owner != &addr || *valid_sig,
+            KeyType::PendingRecovery(owner) => {
+                owner != &addr
+                    || validate_pending_recovery(
+                        ctx,
+                        &tx_data,
+                        key,
+                        &addr,
+                        &valid_sig,
+                        &is_finalizing_recovery,
+                    )?
+            }
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
             KeyType::Vp(owner) => {
                 let has_post: bool = ctx.has_key_post(key)?;
                 if owner == &addr {
-                    has_post && *valid_sig
+                    // When the account has a recovery key, it has to
+                    // authorize VP changes as well. While the account has a
+                    // spending policy, VP changes have to be delayed.
+                    has_post
+                        && *valid_sig
+                        && (recovery_key.is_none() || *valid_recovery_sig)
+                        && (policy.is_none()
+                            || validate_vp_update(ctx, key, &addr)?)
                 } else {
                     true
                 }
@@ -157,6 +336,370 @@ fn validate_tx(
     accept()
 }
 
+/// Check if a debit of the token from the owner respects its spending policy,
+/// if any. A debit limited per epoch has to be recorded in the epoch spending
+/// of the owner and a debit above the delay threshold has to execute matured
+/// queued withdrawals.
+fn is_debit_within_policy(
+    ctx: &Ctx,
+    policy: Option<&account::SpendingPolicy>,
+    token: &Address,
+    owner: &Address,
+    debit: token::Amount,
+) -> VpResult {
+    let policy = match policy {
+        Some(policy) => policy,
+        None => return accept(),
+    };
+    let epoch = ctx.get_block_epoch()?;
+
+    if let Some(limit) = policy.epoch_limits.get(token) {
+        let key = account::epoch_spending_key(owner);
+        let spent = |spending: BTreeMap<Address, account::EpochSpending>| {
+            spending
+                .get(token)
+                .filter(|spending| spending.epoch == epoch)
+                .map(|spending| spending.amount)
+                .unwrap_or_default()
+        };
+        let pre = spent(ctx.read_pre(&key)?.unwrap_or_default());
+        let post = spent(ctx.read_post(&key)?.unwrap_or_default());
+        let is_recorded = post
+            .checked_sub(pre)
+            .map_or(false, |recorded| recorded >= debit);
+        if post > *limit || !is_recorded {
+            return reject();
+        }
+    }
+
+    match policy.delay_thresholds.get(token) {
+        Some(threshold) if debit > *threshold => {
+            let key = account::queued_withdrawals_key(owner);
+            let pre: BTreeMap<u64, account::QueuedWithdrawal> =
+                ctx.read_pre(&key)?.unwrap_or_default();
+            let post: BTreeMap<u64, account::QueuedWithdrawal> =
+                ctx.read_post(&key)?.unwrap_or_default();
+            let mut executed = token::Amount::zero();
+            let mut credits: BTreeMap<Address, token::Amount> = BTreeMap::new();
+            for (id, withdrawal) in pre {
+                if withdrawal.token == *token
+                    && withdrawal.unlock_epoch <= epoch
+                    && !post.contains_key(&id)
+                {
+                    executed = match executed.checked_add(withdrawal.amount) {
+                        Some(executed) => executed,
+                        None => return reject(),
+                    };
+                    let credit = credits.entry(withdrawal.target).or_default();
+                    *credit = match credit.checked_add(withdrawal.amount) {
+                        Some(credit) => credit,
+                        None => return reject(),
+                    };
+                }
+            }
+            // The executed withdrawals have to be credited to their targets
+            for (target, credit) in credits {
+                let key = token::storage_key::balance_key(token, &target);
+                let pre: token::Amount =
+                    ctx.read_pre(&key)?.unwrap_or_default();
+                let post: token::Amount =
+                    ctx.read_post(&key)?.unwrap_or_default();
+                if post.checked_sub(pre).map_or(true, |change| change < credit)
+                {
+                    return reject();
+                }
+            }
+            Ok(debit <= executed)
+        }
+        _ => accept(),
+    }
+}
+
+/// Validate a change of the spending policy of the owner. A change loosening
+/// its current policy has to apply a matured pending change of the policy.
+fn validate_spending_policy(
+    ctx: &Ctx,
+    owner: &Address,
+    policy: Option<&account::SpendingPolicy>,
+) -> VpResult {
+    let post = account::spending_policy(&ctx.post(), owner)?;
+    match policy {
+        Some(policy) if policy.is_loosened_by(post.as_ref()) => {}
+        _ => return accept(),
+    }
+    let pending = account::pending_spending_policy(&ctx.pre(), owner)?;
+    let is_matured = match pending {
+        Some(pending) if pending.policy == post => {
+            pending.unlock_epoch <= ctx.get_block_epoch()?
+        }
+        _ => false,
+    };
+    let is_removed =
+        !ctx.has_key_post(&account::pending_spending_policy_key(owner))?;
+    Ok(is_matured && is_removed)
+}
+
+/// Validate a change of the pending spending policy change of the owner. A new
+/// pending change has to be delayed according to the owner's current spending
+/// policy.
+fn validate_pending_spending_policy(
+    ctx: &Ctx,
+    key: &storage::Key,
+    policy: Option<&account::SpendingPolicy>,
+) -> VpResult {
+    let pre: Option<account::PendingSpendingPolicy> = ctx.read_pre(key)?;
+    let post: Option<account::PendingSpendingPolicy> = ctx.read_post(key)?;
+
+    match post {
+        Some(pending) if pre.as_ref() != Some(&pending) => {
+            let delay = match policy {
+                Some(policy) => policy.delay,
+                None => return reject(),
+            };
+            let min_unlock_epoch = ctx.get_block_epoch()? + delay;
+            Ok(pending.unlock_epoch >= min_unlock_epoch)
+        }
+        // A pending change can be removed when applied or discarded
+        _ => accept(),
+    }
+}
+
+/// Validate a change of the VP of the owner with a spending policy. The change
+/// has to apply a matured pending update to the same VP.
+fn validate_vp_update(
+    ctx: &Ctx,
+    key: &storage::Key,
+    owner: &Address,
+) -> VpResult {
+    let post = ctx.read_bytes_post(key)?;
+    let pending = account::pending_vp_update(&ctx.pre(), owner)?;
+    let is_matured = match pending {
+        Some(pending)
+            if post.as_deref() == Some(&pending.vp_code_hash.0[..]) =>
+        {
+            pending.unlock_epoch <= ctx.get_block_epoch()?
+        }
+        _ => false,
+    };
+    let is_removed =
+        !ctx.has_key_post(&account::pending_vp_update_key(owner))?;
+    Ok(is_matured && is_removed)
+}
+
+/// Validate a change of the pending VP update of the owner. A new pending
+/// update has to be delayed according to the owner's current spending policy.
+fn validate_pending_vp_update(
+    ctx: &Ctx,
+    key: &storage::Key,
+    policy: Option<&account::SpendingPolicy>,
+) -> VpResult {
+    let pre: Option<account::PendingVpUpdate> = ctx.read_pre(key)?;
+    let post: Option<account::PendingVpUpdate> = ctx.read_post(key)?;
+
+    match post {
+        Some(pending) if pre.as_ref() != Some(&pending) => {
+            let delay = match policy {
+                Some(policy) => policy.delay,
+                None => return reject(),
+            };
+            let min_unlock_epoch = ctx.get_block_epoch()? + delay;
+            Ok(pending.unlock_epoch >= min_unlock_epoch)
+        }
+        // A pending update can be removed when applied or discarded
+        _ => accept(),
+    }
+}
+
+/// Validate a change of the amounts debited from the owner in the current
+/// epoch. The amounts can only grow within the current epoch, either with the
+/// owner's signature or by the amount of a debit in this tx.
+fn validate_epoch_spending(
+    ctx: &Ctx,
+    key: &storage::Key,
+    owner: &Address,
+    valid_sig: &impl Deref<Target = bool>,
+) -> VpResult {
+    let epoch = ctx.get_block_epoch()?;
+    let pre: BTreeMap<Address, account::EpochSpending> =
+        ctx.read_pre(key)?.unwrap_or_default();
+    let post: BTreeMap<Address, account::EpochSpending> =
+        ctx.read_post(key)?.unwrap_or_default();
+
+    for (token, spending) in &post {
+        let pre_spending = pre.get(token);
+        if pre_spending == Some(spending) {
+            continue;
+        }
+        let spent = pre_spending
+            .filter(|spending| spending.epoch == epoch)
+            .map(|spending| spending.amount)
+            .unwrap_or_default();
+        let recorded = match spending.amount.checked_sub(spent) {
+            Some(recorded) if spending.epoch == epoch => recorded,
+            _ => return reject(),
+        };
+        if !**valid_sig {
+            let balance_key = token::storage_key::balance_key(token, owner);
+            let pre: token::Amount =
+                ctx.read_pre(&balance_key)?.unwrap_or_default();
+            let post: token::Amount =
+                ctx.read_post(&balance_key)?.unwrap_or_default();
+            if pre.checked_sub(post).unwrap_or_default() < recorded {
+                return reject();
+            }
+        }
+    }
+    // Only the amounts debited in past epochs can be removed
+    Ok(pre.iter().all(|(token, spending)| {
+        spending.epoch < epoch || post.contains_key(token)
+    }))
+}
+
+/// Validate a change of the queued withdrawals of the owner. New withdrawals
+/// have to be signed and delayed according to the owner's spending policy and
+/// withdrawals can be removed with the owner's signature, when executed, or
+/// with the recovery key's signature, when cancelled.
+fn validate_queued_withdrawals(
+    ctx: &Ctx,
+    key: &storage::Key,
+    policy: Option<&account::SpendingPolicy>,
+    valid_sig: &impl Deref<Target = bool>,
+    valid_recovery_sig: &impl Deref<Target = bool>,
+) -> VpResult {
+    let pre: BTreeMap<u64, account::QueuedWithdrawal> =
+        ctx.read_pre(key)?.unwrap_or_default();
+    let post: BTreeMap<u64, account::QueuedWithdrawal> =
+        ctx.read_post(key)?.unwrap_or_default();
+
+    for (id, withdrawal) in &post {
+        match pre.get(id) {
+            Some(pre_withdrawal) if pre_withdrawal == withdrawal => {}
+            // Queued withdrawals cannot be modified
+            Some(_) => return reject(),
+            None => {
+                let delay = match policy {
+                    Some(policy) => policy.delay,
+                    None => return reject(),
+                };
+                let min_unlock_epoch = ctx.get_block_epoch()? + delay;
+                if !**valid_sig || withdrawal.unlock_epoch < min_unlock_epoch {
+                    return reject();
+                }
+            }
+        }
+    }
+    let is_removed = pre.keys().any(|id| !post.contains_key(id));
+    Ok(!is_removed || **valid_sig || **valid_recovery_sig)
+}
+
+/// Validate a change of the pending recovery of the owner. A recovery has to be
+/// initiated by a threshold of the owner's guardians and delayed according to
+/// its guardian set. It can be removed with the owner's signature, when
+/// cancelled, or when finalized.
+fn validate_pending_recovery(
+    ctx: &Ctx,
+    tx: &Tx,
+    key: &storage::Key,
+    owner: &Address,
+    valid_sig: &impl Deref<Target = bool>,
+    is_finalizing_recovery: &impl Deref<Target = bool>,
+) -> VpResult {
+    let pre: Option<account::PendingRecovery> = ctx.read_pre(key)?;
+    let post: Option<account::PendingRecovery> = ctx.read_post(key)?;
+
+    match (pre, post) {
+        (None, Some(recovery)) => {
+            let guardians = match account::guardians(&ctx.pre(), owner)? {
+                Some(guardians) if guardians.threshold > 0 => guardians,
+                _ => return reject(),
+            };
+            let min_unlock_epoch = ctx.get_block_epoch()? + guardians.delay;
+            if recovery.unlock_epoch < min_unlock_epoch {
+                return reject();
+            }
+            let mut approvals = 0_usize;
+            for guardian in &guardians.guardians {
+                if verify_signatures(ctx, tx, guardian)? {
+                    approvals += 1;
+                }
+            }
+            Ok(approvals >= usize::from(guardians.threshold))
+        }
+        (Some(_), None) => Ok(**valid_sig || **is_finalizing_recovery),
+        (None, None) => accept(),
+        // A pending recovery cannot be modified
+        (Some(_), Some(_)) => reject(),
+    }
+}
+
+/// Check if the pending recovery of the owner is finalized in this tx, i.e. it
+/// is matured and removed and its keys and threshold replace the owner's.
+fn is_recovery_finalized(
+    ctx: &Ctx,
+    owner: &Address,
+    recovery: Option<&account::PendingRecovery>,
+) -> VpResult {
+    let recovery = match recovery {
+        Some(recovery) => recovery,
+        None => return reject(),
+    };
+    let is_matured = recovery.unlock_epoch <= ctx.get_block_epoch()?;
+    let is_removed =
+        !ctx.has_key_post(&account::pending_recovery_key(owner))?;
+    let public_keys = account::public_keys(&ctx.post(), owner)?;
+    let threshold = account::threshold(&ctx.post(), owner)?;
+    let weights = account::weights(&ctx.post(), owner)?;
+    Ok(is_matured
+        && is_removed
+        && public_keys == recovery.public_keys
+        && threshold == Some(recovery.threshold)
+        && weights.is_none())
+}
+
+/// Check if the weights of the owner's keys after the tx, if any, are valid
+/// for its public keys after the tx.
+fn are_account_weights_valid(ctx: &Ctx, owner: &Address) -> VpResult {
+    let weights = match account::weights(&ctx.post(), owner)? {
+        Some(weights) => weights,
+        None => return accept(),
+    };
+    let num_public_keys = account::public_keys(&ctx.post(), owner)?.len();
+    Ok(weights.validate(num_public_keys).is_ok())
+}
+
+/// Check if a debit of the token from the owner is covered by the
+/// allowances spent in the same tx. The validity of the spends themselves is
+/// checked by the multitoken VP.
+fn is_debit_within_allowances(
+    ctx: &Ctx,
+    keys_changed: &BTreeSet<storage::Key>,
+    token: &Address,
+    owner: &Address,
+    debit: token::Amount,
+) -> VpResult {
+    let mut spent = token::Amount::zero();
+    for key in keys_changed {
+        let is_owner_allowance = token::storage_key::is_any_allowance_key(key)
+            .map_or(false, |[allowance_token, allowance_owner, _]| {
+                allowance_token == token && allowance_owner == owner
+            });
+        if !is_owner_allowance {
+            continue;
+        }
+        let pre: Option<token::Allowance> = ctx.read_pre(key)?;
+        let post: Option<token::Allowance> = ctx.read_post(key)?;
+        if let Some(amount) =
+            pre.zip(post).and_then(|(pre, post)| pre.spent(&post))
+        {
+            spent = match spent.checked_add(amount) {
+                Some(spent) => spent,
+                None => return reject(),
+            };
+        }
+    }
+    Ok(debit <= spent)
+}
+
 fn validate_pos_changes(
     ctx: &Ctx,
     owner: &Address,
@@ -548,6 +1091,598 @@ mod tests {
         );
     }
 
+    /// Test that a debit signed by keys reaching the weight threshold of a
+    /// weighted account is accepted, even below the signature threshold.
+    #[test]
+    fn test_weighted_signed_debit_transfer_accepted() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let other_public_key = key::testing::keypair_2().ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(
+            &vp_owner,
+            vec![public_key.clone(), other_public_key.clone()],
+            2,
+        );
+        // Give the first key enough weight to authorize on its own
+        account::set_weights(
+            &mut tx_env.wl_storage,
+            &vp_owner,
+            &account::AccountWeights {
+                weights: [(0, 2), (1, 1)].into_iter().collect(),
+                threshold: 2,
+            },
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.wl_storage,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        let amount = token::DenominatedAmount::new(
+            amount,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        );
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map =
+            AccountPublicKeysMap::from_iter(vec![public_key, other_public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that a recovery of an account initiated without the signatures of
+    /// its guardians is rejected.
+    #[test]
+    fn test_recovery_without_guardian_signatures_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let public_key = key::testing::keypair_1().ref_to();
+        let guardian = address::testing::established_address_2();
+        let guardian_keypair = key::testing::keypair_2();
+        let new_public_key = key::testing::keypair_3().ref_to();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &guardian]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key], 1);
+        tx_env.init_account_storage(
+            &guardian,
+            vec![guardian_keypair.ref_to()],
+            1,
+        );
+        account::update_guardians(
+            &mut tx_env.wl_storage,
+            &vp_owner,
+            Some(account::GuardianSet {
+                guardians: [guardian].into_iter().collect(),
+                threshold: 1,
+                delay: 2,
+            }),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Initiate the recovery in a transaction
+            account::initiate_recovery(
+                tx::ctx(),
+                address,
+                vec![new_public_key],
+                1,
+            )
+            .unwrap();
+        });
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        let unsigned_tx = tx.clone();
+        vp_env.tx = unsigned_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            !validate_tx(&CTX, unsigned_tx, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that signed weights with a threshold above the total weight of the
+    /// account's keys are rejected.
+    #[test]
+    fn test_signed_unreachable_weight_threshold_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Write the weights in a transaction, bypassing their validation
+            account::weights_handle(address)
+                .insert(tx::ctx(), 0, 1)
+                .unwrap();
+            tx::ctx()
+                .write(&account::weight_threshold_key(address), 2_u16)
+                .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            !validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that signed weights with a zero threshold, or with a threshold but
+    /// without any weighted key, are rejected.
+    #[test]
+    fn test_signed_degenerate_weights_rejected() {
+        let cases: [(&[(u8, u8)], u16); 3] =
+            [(&[], 0), (&[], 1), (&[(0, 1)], 0)];
+        for (weights, weight_threshold) in cases {
+            // Initialize a tx environment
+            let mut tx_env = TestTxEnv::default();
+
+            let vp_owner = address::testing::established_address_1();
+            let keypair = key::testing::keypair_1();
+            let public_key = keypair.ref_to();
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&vp_owner]);
+            tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+            // Initialize VP environment from a transaction
+            vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+                // Write the weights in a transaction, bypassing their
+                // validation
+                for (index, weight) in weights {
+                    account::weights_handle(address)
+                        .insert(tx::ctx(), *index, *weight)
+                        .unwrap();
+                }
+                tx::ctx()
+                    .write(
+                        &account::weight_threshold_key(address),
+                        weight_threshold,
+                    )
+                    .unwrap();
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![keypair]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert!(
+                !validate_tx(
+                    &CTX,
+                    signed_tx,
+                    vp_owner,
+                    keys_changed,
+                    verifiers
+                )
+                .unwrap(),
+                "Weights {weights:?} with the threshold {weight_threshold} \
+                 should be rejected"
+            );
+        }
+    }
+
+    /// Test that a mint of a user-issued token without a valid signature of its
+    /// minter is rejected.
+    #[test]
+    fn test_unsigned_user_token_mint_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let token = address::testing::established_address_3();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        // register the token with the VP owner as its minter
+        token::register_token(
+            &mut tx_env.wl_storage,
+            &token,
+            0u8.into(),
+            &vp_owner,
+            None,
+        )
+        .unwrap();
+
+        let amount = token::DenominatedAmount::new(amount, 0u8.into());
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply mint in a transaction
+            tx_host_env::token::mint_user_token(
+                tx::ctx(),
+                token::MintTokens {
+                    minter: address.clone(),
+                    target: target.clone(),
+                    token: token.clone(),
+                    amount,
+                },
+            )
+            .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let mut tx_data = Tx::from_type(TxType::Raw);
+        tx_data.set_data(Data::new(vec![]));
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> =
+            [address::MULTITOKEN].into_iter().collect();
+        vp_host_env::set(vp_env);
+        assert!(
+            !validate_tx(&CTX, tx_data, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that a debit without a valid signature is accepted when it is
+    /// covered by the allowance spent by a spender.
+    #[test]
+    fn test_unsigned_debit_within_allowance_accepted() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let spender = address::testing::established_address_3();
+        let token = address::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &spender, &token]);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.wl_storage,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner and allow the spender to transfer
+        // them before running the transaction
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        token::write_allowance(
+            &mut tx_env.wl_storage,
+            &token,
+            &vp_owner,
+            &spender,
+            token::Allowance {
+                amount,
+                expiry: None,
+            },
+        )
+        .unwrap();
+
+        let amount = token::DenominatedAmount::new(
+            amount,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        );
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply the transfer within the allowance in a transaction
+            tx_host_env::token::spend_allowance(
+                tx::ctx(),
+                token::TransferFrom {
+                    spender: spender.clone(),
+                    source: address.clone(),
+                    target: target.clone(),
+                    token: token.clone(),
+                    amount,
+                },
+            )
+            .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let mut tx_data = Tx::from_type(TxType::Raw);
+        tx_data.set_data(Data::new(vec![]));
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, tx_data, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that a signed debit above the epoch limit of the account's
+    /// spending policy is rejected.
+    #[test]
+    fn test_signed_debit_above_epoch_limit_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+        let limit = token::Amount::from_uint(1_000, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Credit the tokens to the VP owner and limit its debits before
+        // running the transaction
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        account::update_spending_policy(
+            &mut tx_env.wl_storage,
+            &vp_owner,
+            Some(account::SpendingPolicy {
+                epoch_limits: [(token.clone(), limit)].into_iter().collect(),
+                delay_thresholds: BTreeMap::new(),
+                delay: 0,
+                recovery_key: None,
+            }),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply a transfer that doesn't record the spending
+            token::transfer(tx::ctx(), &token, address, &target, amount)
+                .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            !validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that a signed removal of the account's spending policy is rejected
+    /// unless it was pending for the delay of the policy.
+    #[test]
+    fn test_signed_immediate_spending_policy_removal_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::nam();
+        let limit = token::Amount::from_uint(1_000, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Limit the debits of the VP owner before running the transaction
+        account::update_spending_policy(
+            &mut tx_env.wl_storage,
+            &vp_owner,
+            Some(account::SpendingPolicy {
+                epoch_limits: [(token.clone(), limit)].into_iter().collect(),
+                delay_thresholds: BTreeMap::new(),
+                delay: 2,
+                recovery_key: None,
+            }),
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Remove the policy without waiting for its delay
+            tx::ctx()
+                .delete(&account::spending_policy_key(address))
+                .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            !validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .unwrap()
+        );
+    }
+
+    /// Test that a matured queued withdrawal can only be executed to its
+    /// target.
+    #[test]
+    fn test_signed_queued_withdrawal_execution() {
+        let target = address::testing::established_address_2();
+        let other = address::testing::established_address_3();
+        for (receiver, expected) in [(&target, true), (&other, false)] {
+            // Initialize a tx environment
+            let mut tx_env = TestTxEnv::default();
+
+            let vp_owner = address::testing::established_address_1();
+            let keypair = key::testing::keypair_1();
+            let public_key = keypair.ref_to();
+            let token = address::nam();
+            let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+            let threshold = token::Amount::from_uint(1_000, 0).unwrap();
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&vp_owner, &target, &other, &token]);
+            tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+            // Credit the tokens to the VP owner and queue a withdrawal to
+            // the target before running the transaction
+            tx_env.credit_tokens(&vp_owner, &token, amount);
+            account::update_spending_policy(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                Some(account::SpendingPolicy {
+                    epoch_limits: BTreeMap::new(),
+                    delay_thresholds: [(token.clone(), threshold)]
+                        .into_iter()
+                        .collect(),
+                    delay: 0,
+                    recovery_key: None,
+                }),
+            )
+            .unwrap();
+            let id = account::queue_withdrawal(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                &token,
+                &target,
+                amount,
+            )
+            .unwrap();
+
+            // Initialize VP environment from a transaction
+            vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+                // Execute the withdrawal, transferring to the receiver
+                account::remove_queued_withdrawal(tx::ctx(), address, id, true)
+                    .unwrap();
+                token::transfer(tx::ctx(), &token, address, receiver, amount)
+                    .unwrap();
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![keypair]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert_eq!(
+                validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]
@@ -1401,4 +2536,97 @@ mod tests {
                 .unwrap()
         );
     }
+
+    /// Test that a signed validity predicate update of an account with a
+    /// spending policy and no recovery key is only accepted when it applies a
+    /// matured pending update.
+    #[test]
+    fn test_signed_vp_update_with_spending_policy() {
+        // Whether the update is pending, the number of epochs elapsed since
+        // and whether the update is accepted
+        for (is_pending, elapsed, expected) in
+            [(false, 2, false), (true, 1, false), (true, 2, true)]
+        {
+            // Initialize a tx environment
+            let mut tx_env = TestTxEnv::default();
+            tx_env.init_parameters(None, None, None, None);
+
+            let vp_owner = address::testing::established_address_1();
+            let keypair = key::testing::keypair_1();
+            let public_key = keypair.ref_to();
+            let token = address::nam();
+            let limit = token::Amount::from_uint(1_000, 0).unwrap();
+            let vp_code = TestWasms::VpAlwaysTrue.read_bytes();
+            let vp_hash = sha256(&vp_code);
+            // for the update
+            tx_env.store_wasm_code(vp_code);
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&vp_owner, &token]);
+            tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+            // Limit the debits of the VP owner and queue the update of its VP
+            // before running the transaction
+            account::update_spending_policy(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                Some(account::SpendingPolicy {
+                    epoch_limits: [(token.clone(), limit)]
+                        .into_iter()
+                        .collect(),
+                    delay_thresholds: BTreeMap::new(),
+                    delay: 2,
+                    recovery_key: None,
+                }),
+            )
+            .unwrap();
+            if is_pending {
+                let is_applied = account::queue_vp_update(
+                    &mut tx_env.wl_storage,
+                    &vp_owner,
+                    vp_hash,
+                )
+                .unwrap();
+                assert!(!is_applied);
+            }
+            for _ in 0..elapsed {
+                tx_env.wl_storage.storage.block.epoch =
+                    tx_env.wl_storage.storage.block.epoch.next();
+            }
+
+            // Initialize VP environment from a transaction
+            vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+                // Update VP in a transaction, applying the pending update
+                tx::ctx()
+                    .update_validity_predicate(address, vp_hash, &None)
+                    .unwrap();
+                tx::ctx()
+                    .delete(&account::pending_vp_update_key(address))
+                    .unwrap();
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![keypair]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert_eq!(
+                validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
 }